@@ -266,7 +266,7 @@ async fn test_fee_payer_signed_transaction() {
 
     // Create a new account with a multi-agent signer
     let txn = root_account.sign_fee_payer_with_transaction_builder(
-        vec![],
+        Vec::<&LocalAccount>::new(),
         &secondary,
         factory.create_user_account(account.public_key()),
     );
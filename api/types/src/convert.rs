@@ -490,7 +490,7 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
             let data = self
                 .inner
                 .view_value(event.event.type_tag(), event.event.event_data())?;
-            ret.push((event, MoveValue::try_from(data)?.json()?).into());
+            ret.push((event, MoveValue::try_from(data)?.json()?).try_into()?);
         }
         Ok(ret)
     }
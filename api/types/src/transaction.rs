@@ -11,6 +11,8 @@ use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH},
     multi_ed25519::{self, MultiEd25519PublicKey, BITMAP_NUM_OF_BYTES, MAX_NUM_OF_KEYS},
+    secp256k1_ecdsa::{self, Secp256k1EcdsaPublicKey},
+    secp256r1_ecdsa::{self, Secp256r1EcdsaPublicKey},
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -18,6 +20,7 @@ use aptos_types::{
     contract_event::{ContractEvent, EventWithVersion},
     transaction::{
         authenticator::{AccountAuthenticator, TransactionAuthenticator, MAX_NUM_OF_SIGS},
+        webauthn::{PartialAuthenticatorAssertionResponse, MIN_AUTHENTICATOR_DATA_LENGTH},
         Script, SignedTransaction, TransactionOutput, TransactionWithProof,
     },
 };
@@ -515,10 +518,13 @@ pub struct BlockMetadataTransaction {
 /// An event from a transaction
 #[derive(Clone, Debug, Deserialize, Eq, Object, PartialEq, Serialize)]
 pub struct Event {
-    // The globally unique identifier of this event stream.
-    pub guid: EventGuid,
-    // The sequence number of the event
-    pub sequence_number: U64,
+    // The globally unique identifier of this event stream, absent for module events, which are
+    // not emitted to an event stream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guid: Option<EventGuid>,
+    // The sequence number of the event, absent for module events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence_number: Option<U64>,
     #[serde(rename = "type")]
     #[oai(rename = "type")]
     pub typ: MoveType,
@@ -530,11 +536,17 @@ impl From<(&ContractEvent, serde_json::Value)> for Event {
     fn from((event, data): (&ContractEvent, serde_json::Value)) -> Self {
         match event {
             ContractEvent::V0(v0) => Self {
-                guid: (*v0.key()).into(),
-                sequence_number: v0.sequence_number().into(),
+                guid: Some((*v0.key()).into()),
+                sequence_number: Some(v0.sequence_number().into()),
                 typ: v0.type_tag().clone().into(),
                 data,
             },
+            ContractEvent::V1(v1) => Self {
+                guid: None,
+                sequence_number: None,
+                typ: v1.type_tag().clone().into(),
+                data,
+            },
         }
     }
 }
@@ -554,15 +566,22 @@ pub struct VersionedEvent {
     pub data: serde_json::Value,
 }
 
-impl From<(&EventWithVersion, serde_json::Value)> for VersionedEvent {
-    fn from((event, data): (&EventWithVersion, serde_json::Value)) -> Self {
+impl TryFrom<(&EventWithVersion, serde_json::Value)> for VersionedEvent {
+    type Error = anyhow::Error;
+
+    fn try_from((event, data): (&EventWithVersion, serde_json::Value)) -> anyhow::Result<Self> {
         match &event.event {
-            ContractEvent::V0(v0) => Self {
+            ContractEvent::V0(v0) => Ok(Self {
                 version: event.transaction_version.into(),
                 guid: (*v0.key()).into(),
                 sequence_number: v0.sequence_number().into(),
                 typ: v0.type_tag().clone().into(),
                 data,
+            }),
+            // Events are only looked up by their event stream, which module events are not
+            // emitted to
+            ContractEvent::V1(_) => {
+                bail!("Module events are not emitted to an event stream")
             },
         }
     }
@@ -849,6 +868,9 @@ pub enum TransactionSignature {
     MultiEd25519Signature(MultiEd25519Signature),
     MultiAgentSignature(MultiAgentSignature),
     FeePayerSignature(FeePayerSignature),
+    Secp256r1EcdsaSignature(Secp256r1EcdsaSignature),
+    Secp256k1EcdsaSignature(Secp256k1EcdsaSignature),
+    AbstractionSignature(AbstractionSignature),
 }
 
 impl VerifyInput for TransactionSignature {
@@ -858,6 +880,9 @@ impl VerifyInput for TransactionSignature {
             TransactionSignature::MultiEd25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiAgentSignature(inner) => inner.verify(),
             TransactionSignature::FeePayerSignature(inner) => inner.verify(),
+            TransactionSignature::Secp256r1EcdsaSignature(inner) => inner.verify(),
+            TransactionSignature::Secp256k1EcdsaSignature(inner) => inner.verify(),
+            TransactionSignature::AbstractionSignature(inner) => inner.verify(),
         }
     }
 }
@@ -871,6 +896,9 @@ impl TryFrom<TransactionSignature> for TransactionAuthenticator {
             TransactionSignature::MultiEd25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiAgentSignature(sig) => sig.try_into()?,
             TransactionSignature::FeePayerSignature(sig) => sig.try_into()?,
+            TransactionSignature::Secp256r1EcdsaSignature(sig) => sig.try_into()?,
+            TransactionSignature::Secp256k1EcdsaSignature(sig) => sig.try_into()?,
+            TransactionSignature::AbstractionSignature(sig) => sig.try_into()?,
         })
     }
 }
@@ -1067,18 +1095,219 @@ impl TryFrom<MultiEd25519Signature> for AccountAuthenticator {
     }
 }
 
+/// A single secp256r1 ECDSA signature, wrapped in a WebAuthn assertion
+///
+/// The signing message of the transaction is the assertion's challenge.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct Secp256r1EcdsaSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+    pub authenticator_data: HexEncodedBytes,
+    pub client_data_json: HexEncodedBytes,
+}
+
+impl VerifyInput for Secp256r1EcdsaSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        let public_key_len = self.public_key.inner().len();
+        let signature_len = self.signature.inner().len();
+        let authenticator_data_len = self.authenticator_data.inner().len();
+        if public_key_len != secp256r1_ecdsa::PUBLIC_KEY_LENGTH {
+            bail!(
+                "Secp256r1Ecdsa signature's public key is an invalid number of bytes, should be {} bytes but found {}",
+                secp256r1_ecdsa::PUBLIC_KEY_LENGTH, public_key_len
+            )
+        } else if signature_len != secp256r1_ecdsa::SIGNATURE_LENGTH {
+            bail!(
+                "Secp256r1Ecdsa signature length is an invalid number of bytes, should be {} bytes but found {}",
+                secp256r1_ecdsa::SIGNATURE_LENGTH, signature_len
+            )
+        } else if authenticator_data_len < MIN_AUTHENTICATOR_DATA_LENGTH {
+            bail!(
+                "Secp256r1Ecdsa signature's authenticator data is too short, should be at least {} bytes but found {}",
+                MIN_AUTHENTICATOR_DATA_LENGTH, authenticator_data_len
+            )
+        } else if self.client_data_json.inner().is_empty() {
+            bail!("Secp256r1Ecdsa signature has no client data JSON")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Secp256r1EcdsaSignature {
+    fn try_into_parts(
+        self,
+    ) -> anyhow::Result<(
+        Secp256r1EcdsaPublicKey,
+        PartialAuthenticatorAssertionResponse,
+    )> {
+        let Secp256r1EcdsaSignature {
+            public_key,
+            signature,
+            authenticator_data,
+            client_data_json,
+        } = self;
+        let public_key = public_key
+            .inner()
+            .try_into()
+            .context("Failed to parse given public_key bytes as a Secp256r1EcdsaPublicKey")?;
+        let signature = signature
+            .inner()
+            .try_into()
+            .context("Failed to parse given signature as a Secp256r1EcdsaSignature")?;
+        Ok((
+            public_key,
+            PartialAuthenticatorAssertionResponse::new(
+                signature,
+                authenticator_data.into(),
+                client_data_json.into(),
+            ),
+        ))
+    }
+}
+
+impl TryFrom<Secp256r1EcdsaSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256r1EcdsaSignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.try_into_parts()?;
+        Ok(TransactionAuthenticator::secp256r1_ecdsa(
+            public_key, signature,
+        ))
+    }
+}
+
+impl TryFrom<Secp256r1EcdsaSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256r1EcdsaSignature) -> Result<Self, Self::Error> {
+        let (public_key, signature) = value.try_into_parts()?;
+        Ok(AccountAuthenticator::secp256r1_ecdsa(public_key, signature))
+    }
+}
+
+/// A single Ethereum-style secp256k1 ECDSA signature
+///
+/// The signature is the 64-byte `r || s` encoding, without the recovery id, over the Keccak-256
+/// digest of the signing message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct Secp256k1EcdsaSignature {
+    pub public_key: HexEncodedBytes,
+    pub signature: HexEncodedBytes,
+}
+
+impl VerifyInput for Secp256k1EcdsaSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        let public_key_len = self.public_key.inner().len();
+        let signature_len = self.signature.inner().len();
+        if public_key_len != secp256k1_ecdsa::PUBLIC_KEY_LENGTH {
+            bail!(
+                "Secp256k1Ecdsa signature's public key is an invalid number of bytes, should be {} bytes but found {}",
+                secp256k1_ecdsa::PUBLIC_KEY_LENGTH, public_key_len
+            )
+        } else if signature_len != secp256k1_ecdsa::SIGNATURE_LENGTH {
+            bail!(
+                "Secp256k1Ecdsa signature length is an invalid number of bytes, should be {} bytes but found {}",
+                secp256k1_ecdsa::SIGNATURE_LENGTH, signature_len
+            )
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TryFrom<Secp256k1EcdsaSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256k1EcdsaSignature) -> Result<Self, Self::Error> {
+        let Secp256k1EcdsaSignature {
+            public_key,
+            signature,
+        } = value;
+        Ok(TransactionAuthenticator::secp256k1_ecdsa(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256k1EcdsaPublicKey")?,
+            signature
+                .inner()
+                .try_into()
+                .context("Failed to parse given signature as a Secp256k1EcdsaSignature")?,
+        ))
+    }
+}
+
+impl TryFrom<Secp256k1EcdsaSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Secp256k1EcdsaSignature) -> Result<Self, Self::Error> {
+        let Secp256k1EcdsaSignature {
+            public_key,
+            signature,
+        } = value;
+        Ok(AccountAuthenticator::secp256k1_ecdsa(
+            public_key
+                .inner()
+                .try_into()
+                .context("Failed to parse given public_key bytes as a Secp256k1EcdsaPublicKey")?,
+            signature
+                .inner()
+                .try_into()
+                .context("Failed to parse given signature as a Secp256k1EcdsaSignature")?,
+        ))
+    }
+}
+
+/// An opaque signature for an account using account abstraction
+///
+/// The signature is checked by the Move authentication function registered by the account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct AbstractionSignature {
+    pub signature: HexEncodedBytes,
+}
+
+impl VerifyInput for AbstractionSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl TryFrom<AbstractionSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: AbstractionSignature) -> Result<Self, Self::Error> {
+        Ok(TransactionAuthenticator::abstraction(
+            value.signature.into(),
+        ))
+    }
+}
+
+impl TryFrom<AbstractionSignature> for AccountAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: AbstractionSignature) -> Result<Self, Self::Error> {
+        Ok(AccountAuthenticator::abstraction(value.signature.into()))
+    }
+}
+
 /// Account signature scheme
 ///
-/// The account signature scheme allows you to have two types of accounts:
+/// The account signature scheme allows you to have five types of accounts:
 ///
 ///   1. A single Ed25519 key account, one private key
 ///   2. A k-of-n multi-Ed25519 key account, multiple private keys, such that k-of-n must sign a transaction.
+///   3. A single secp256r1 ECDSA key account, whose key is held by a WebAuthn authenticator (i.e., a passkey)
+///   4. A single Ethereum-style secp256k1 ECDSA key account, one private key
+///   5. An account using account abstraction, whose transactions are authenticated by a Move function
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
 pub enum AccountSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
+    Secp256r1EcdsaSignature(Secp256r1EcdsaSignature),
+    Secp256k1EcdsaSignature(Secp256k1EcdsaSignature),
+    AbstractionSignature(AbstractionSignature),
 }
 
 impl VerifyInput for AccountSignature {
@@ -1086,6 +1315,9 @@ impl VerifyInput for AccountSignature {
         match self {
             AccountSignature::Ed25519Signature(inner) => inner.verify(),
             AccountSignature::MultiEd25519Signature(inner) => inner.verify(),
+            AccountSignature::Secp256r1EcdsaSignature(inner) => inner.verify(),
+            AccountSignature::Secp256k1EcdsaSignature(inner) => inner.verify(),
+            AccountSignature::AbstractionSignature(inner) => inner.verify(),
         }
     }
 }
@@ -1097,6 +1329,9 @@ impl TryFrom<AccountSignature> for AccountAuthenticator {
         Ok(match sig {
             AccountSignature::Ed25519Signature(s) => s.try_into()?,
             AccountSignature::MultiEd25519Signature(s) => s.try_into()?,
+            AccountSignature::Secp256r1EcdsaSignature(s) => s.try_into()?,
+            AccountSignature::Secp256k1EcdsaSignature(s) => s.try_into()?,
+            AccountSignature::AbstractionSignature(s) => s.try_into()?,
         })
     }
 }
@@ -1193,6 +1428,46 @@ impl
     }
 }
 
+impl
+    From<(
+        &Secp256r1EcdsaPublicKey,
+        &PartialAuthenticatorAssertionResponse,
+    )> for Secp256r1EcdsaSignature
+{
+    fn from(
+        (pk, sig): (
+            &Secp256r1EcdsaPublicKey,
+            &PartialAuthenticatorAssertionResponse,
+        ),
+    ) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.signature().to_bytes().to_vec().into(),
+            authenticator_data: sig.authenticator_data().to_vec().into(),
+            client_data_json: sig.client_data_json().to_vec().into(),
+        }
+    }
+}
+
+impl
+    From<(
+        &Secp256k1EcdsaPublicKey,
+        &secp256k1_ecdsa::Secp256k1EcdsaSignature,
+    )> for Secp256k1EcdsaSignature
+{
+    fn from(
+        (pk, sig): (
+            &Secp256k1EcdsaPublicKey,
+            &secp256k1_ecdsa::Secp256k1EcdsaSignature,
+        ),
+    ) -> Self {
+        Self {
+            public_key: pk.to_bytes().to_vec().into(),
+            signature: sig.to_bytes().to_vec().into(),
+        }
+    }
+}
+
 impl From<&AccountAuthenticator> for AccountSignature {
     fn from(auth: &AccountAuthenticator) -> Self {
         use AccountAuthenticator::*;
@@ -1205,6 +1480,17 @@ impl From<&AccountAuthenticator> for AccountSignature {
                 public_key,
                 signature,
             } => Self::MultiEd25519Signature((public_key, signature).into()),
+            Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256r1EcdsaSignature((public_key, signature).into()),
+            Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256k1EcdsaSignature((public_key, signature).into()),
+            Abstraction { signature } => Self::AbstractionSignature(AbstractionSignature {
+                signature: signature.clone().into(),
+            }),
         }
     }
 }
@@ -1349,6 +1635,17 @@ impl From<TransactionAuthenticator> for TransactionSignature {
                 )
                     .into(),
             ),
+            Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256r1EcdsaSignature((public_key, signature).into()),
+            Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => Self::Secp256k1EcdsaSignature((public_key, signature).into()),
+            Abstraction { signature } => Self::AbstractionSignature(AbstractionSignature {
+                signature: signature.clone().into(),
+            }),
         }
     }
 }
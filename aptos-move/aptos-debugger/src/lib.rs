@@ -103,6 +103,36 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    /// Replays the user transactions in the given range one by one with the gas profiler enabled.
+    /// Transactions that cannot be profiled, e.g. block metadata or multisig transactions, are
+    /// skipped.
+    pub async fn execute_past_transactions_with_gas_profiler(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<Vec<(Version, TransactionGasLog)>> {
+        let (txns, _) = self
+            .debugger
+            .get_committed_transactions(begin, limit)
+            .await?;
+
+        let mut ret = vec![];
+        for (idx, txn) in txns.into_iter().enumerate() {
+            let version = begin + idx as Version;
+            if let Transaction::UserTransaction(txn) = txn {
+                if matches!(
+                    txn.payload(),
+                    TransactionPayload::Script(_) | TransactionPayload::EntryFunction(_)
+                ) {
+                    let (_, _, gas_log) =
+                        self.execute_transaction_at_version_with_gas_profiler(version, txn)?;
+                    ret.push((version, gas_log));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
     pub async fn execute_past_transactions(
         &self,
         mut begin: Version,
@@ -252,5 +282,5 @@ fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
     vm_output
         .events()
         .iter()
-        .any(|event| *event.key() == new_epoch_event_key)
+        .any(|event| event.event_key() == Some(&new_epoch_event_key))
 }
@@ -3,10 +3,12 @@
 
 use anyhow::Result;
 use aptos_debugger::AptosDebugger;
+use aptos_gas_profiling::TransactionGasLog;
 use aptos_rest_client::Client;
+use aptos_types::transaction::Version;
 use aptos_vm::AptosVM;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 #[derive(Subcommand)]
@@ -29,6 +31,52 @@ pub struct Argument {
 
     #[clap(long, default_value_t = 1)]
     concurrency_level: usize,
+
+    /// Log the conflicting keys, re-executions and dependency chains of every replayed block.
+    /// Requires a concurrency level above 1.
+    #[clap(long)]
+    profile_conflicts: bool,
+
+    /// Profile the gas usage of the user transactions instead, and save a report aggregated over
+    /// all of them to the given directory, as HTML, speedscope JSON and Chrome trace events.
+    #[clap(long)]
+    profile_gas: Option<PathBuf>,
+}
+
+fn save_gas_profile(
+    dir: &Path,
+    title: &str,
+    gas_logs: &[(Version, TransactionGasLog)],
+) -> Result<()> {
+    let mut logs = gas_logs.iter().map(|(_, log)| log);
+    let first = match logs.next() {
+        Some(log) => log,
+        None => {
+            println!("No user transactions to profile");
+            return Ok(());
+        },
+    };
+
+    let mut erased = first.to_erased();
+    let mut hotspots = first.exec_io.aggregate_gas_events();
+    for log in logs {
+        erased.merge(&log.to_erased());
+        hotspots.merge(&log.exec_io.aggregate_gas_events());
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let mut html = String::new();
+    erased.to_html_report(&mut html, title, &hotspots)?;
+    std::fs::write(dir.join("report.html"), html)?;
+    std::fs::write(dir.join("speedscope.json"), erased.to_speedscope(title))?;
+    std::fs::write(dir.join("trace.json"), erased.to_chrome_trace())?;
+
+    println!(
+        "Gas profile of {} transactions saved to {}",
+        gas_logs.len(),
+        dir.display()
+    );
+    Ok(())
 }
 
 #[tokio::main]
@@ -36,6 +84,9 @@ async fn main() -> Result<()> {
     aptos_logger::Logger::new().init();
     let args = Argument::parse();
     AptosVM::set_concurrency_level_once(args.concurrency_level);
+    if args.profile_conflicts {
+        AptosVM::set_conflict_profiling_once();
+    }
 
     let debugger = match args.target {
         Target::Rest { endpoint } => {
@@ -44,6 +95,17 @@ async fn main() -> Result<()> {
         Target::DB { path } => AptosDebugger::db(path)?,
     };
 
+    if let Some(dir) = args.profile_gas {
+        let gas_logs = debugger
+            .execute_past_transactions_with_gas_profiler(args.begin_version, args.limit)
+            .await?;
+        let title = format!(
+            "{} transactions from version {}",
+            args.limit, args.begin_version
+        );
+        return save_gas_profile(&dir, &title, &gas_logs);
+    }
+
     println!(
         "{:#?}",
         debugger
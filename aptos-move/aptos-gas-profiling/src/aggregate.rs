@@ -127,3 +127,40 @@ impl ExecutionAndIOCosts {
         }
     }
 }
+
+fn merge_sorted_vecs<U>(
+    v1: &[(String, usize, GasQuantity<U>)],
+    v2: &[(String, usize, GasQuantity<U>)],
+) -> Vec<(String, usize, GasQuantity<U>)> {
+    let mut map = BTreeMap::new();
+    for (key, count, amount) in v1.iter().chain(v2.iter()) {
+        match map.entry(key.clone()) {
+            btree_map::Entry::Occupied(entry) => {
+                let r: &mut (usize, GasQuantity<U>) = entry.into_mut();
+                r.0 += count;
+                r.1 += *amount;
+            },
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert((*count, *amount));
+            },
+        }
+    }
+    into_sorted_vec(map)
+}
+
+impl AggregatedExecutionGasEvents {
+    /// Merges the aggregated events of another transaction into this one.
+    ///
+    /// Both must have been produced with the same gas scaling factor.
+    pub fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            self.gas_scaling_factor, other.gas_scaling_factor,
+            "cannot merge gas events with different gas scaling factors"
+        );
+
+        self.total += other.total;
+        self.ops = merge_sorted_vecs(&self.ops, &other.ops);
+        self.storage_reads = merge_sorted_vecs(&self.storage_reads, &other.storage_reads);
+        self.storage_writes = merge_sorted_vecs(&self.storage_writes, &other.storage_writes);
+    }
+}
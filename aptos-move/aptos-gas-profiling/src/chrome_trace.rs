@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::erased::{Node, TypeErasedGasLog};
+use serde_json::{json, Value};
+
+const PID: u64 = 1;
+const EXEC_IO_TID: u64 = 1;
+const STORAGE_TID: u64 = 2;
+
+impl<U> Node<U> {
+    /// Emits a complete event for every node with a non-zero cost, laying the children of a node
+    /// out one after another from the start of their parent.
+    ///
+    /// Expects the costs of the children to be included in the cost of the parent.
+    fn to_trace_events(&self, start: f64, tid: u64, scale: f64, events: &mut Vec<Value>) {
+        if self.cost.is_zero() {
+            return;
+        }
+
+        events.push(json!({
+            "name": self.text,
+            "ph": "X",
+            "ts": start,
+            "dur": u64::from(self.cost) as f64 / scale,
+            "pid": PID,
+            "tid": tid,
+        }));
+
+        let mut child_start = start;
+        for child in &self.children {
+            child.to_trace_events(child_start, tid, scale, events);
+            child_start += u64::from(child.cost) as f64 / scale;
+        }
+    }
+}
+
+fn thread_name(tid: u64, name: &str) -> Value {
+    json!({
+        "name": "thread_name",
+        "ph": "M",
+        "pid": PID,
+        "tid": tid,
+        "args": { "name": name },
+    })
+}
+
+impl TypeErasedGasLog {
+    /// Exports the gas log in the Chrome trace event format, which can be loaded into
+    /// chrome://tracing or Perfetto. Costs are shown as durations, where one microsecond stands
+    /// for one gas unit on the execution & IO track, and for one Octa on the storage fee track.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut events = vec![
+            thread_name(EXEC_IO_TID, "execution & IO (gas units)"),
+            thread_name(STORAGE_TID, "storage fees (Octa)"),
+        ];
+
+        let mut exec_io = self.exec_io.tree.clone();
+        exec_io.include_child_costs();
+        exec_io.to_trace_events(
+            0.0,
+            EXEC_IO_TID,
+            u64::from(self.exec_io.gas_scaling_factor) as f64,
+            &mut events,
+        );
+
+        let mut storage = self.storage.tree.clone();
+        storage.include_child_costs();
+        storage.to_trace_events(0.0, STORAGE_TID, 1.0, &mut events);
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    }
+}
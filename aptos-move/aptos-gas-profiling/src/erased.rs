@@ -10,6 +10,7 @@ use crate::{
     FrameName, TransactionGasLog,
 };
 use aptos_gas_algebra::{Fee, GasQuantity, GasScalingFactor, InternalGas, InternalGasUnit, Octa};
+use std::collections::HashMap;
 
 /// Represents a node in a general tree structure with some text & cost attached to each node.
 pub struct Node<U> {
@@ -230,3 +231,43 @@ impl<U> Node<U> {
         }
     }
 }
+
+impl<U> Node<U> {
+    /// Merges another tree into this one, adding up the costs of the nodes with the same text
+    /// under the same parent. Children only present in the other tree are appended.
+    pub fn merge(&mut self, other: &Self) {
+        self.cost += other.cost;
+
+        let mut index = HashMap::new();
+        for (idx, child) in self.children.iter().enumerate() {
+            index.entry(child.text.clone()).or_insert(idx);
+        }
+        for other_child in &other.children {
+            match index.get(&other_child.text) {
+                Some(idx) => self.children[*idx].merge(other_child),
+                None => {
+                    index.insert(other_child.text.clone(), self.children.len());
+                    self.children.push(other_child.clone());
+                },
+            }
+        }
+    }
+}
+
+impl TypeErasedGasLog {
+    /// Merges the gas log of another transaction into this one, so that the result describes the
+    /// combined costs of all merged transactions.
+    ///
+    /// Both logs must have been produced with the same gas scaling factor.
+    pub fn merge(&mut self, other: &Self) {
+        assert_eq!(
+            self.exec_io.gas_scaling_factor, other.exec_io.gas_scaling_factor,
+            "cannot merge gas logs with different gas scaling factors"
+        );
+
+        self.exec_io.total += other.exec_io.total;
+        self.exec_io.tree.merge(&other.exec_io.tree);
+        self.storage.total += other.storage.total;
+        self.storage.tree.merge(&other.storage.tree);
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aggregate::AggregatedExecutionGasEvents,
+    erased::{Node, TypeErasedGasLog},
+    misc::strip_trailing_zeros_and_decimal_point,
+};
+use aptos_gas_algebra::{GasQuantity, InternalGas};
+use std::fmt::{self, Write};
+
+const STYLE: &str = r#"
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.5em; }
+h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 2px 12px; text-align: left; }
+td.num { text-align: right; font-family: monospace; }
+tr:nth-child(even) { background: #f4f4f4; }
+.tree, .tree details { margin-left: 1.5em; }
+.tree > details, .tree > div { margin-left: 0; }
+.node { font-family: monospace; white-space: pre; }
+.cost { color: #555; }
+.bar { display: inline-block; height: 0.7em; background: #e8743b; margin-right: 6px; }
+"#;
+
+struct Escape<'a>(&'a str);
+
+impl<'a> fmt::Display for Escape<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '<' => write!(f, "&lt;")?,
+                '>' => write!(f, "&gt;")?,
+                '&' => write!(f, "&amp;")?,
+                '"' => write!(f, "&quot;")?,
+                _ => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn fmt_scaled(cost: u64, scaling_factor: f64) -> String {
+    let cost_scaled = format!("{:.8}", cost as f64 / scaling_factor);
+    strip_trailing_zeros_and_decimal_point(&cost_scaled).to_string()
+}
+
+fn percentage(cost: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        cost as f64 / total as f64 * 100.0
+    }
+}
+
+impl<U> Node<U> {
+    /// Renders the tree as nested collapsible sections. Expects the costs of the children to be
+    /// included in the cost of the parent. Nodes without any cost are omitted.
+    fn render_html(
+        &self,
+        output: &mut impl Write,
+        total: GasQuantity<U>,
+        fmt_cost: &impl Fn(GasQuantity<U>) -> String,
+    ) -> fmt::Result {
+        if self.cost.is_zero() {
+            return Ok(());
+        }
+
+        let percentage = percentage(u64::from(self.cost), u64::from(total));
+        let label = format!(
+            r#"<span class="bar" style="width: {:.0}px"></span><span class="node">{}</span> <span class="cost">{} ({:.2}%)</span>"#,
+            percentage,
+            Escape(&self.text),
+            fmt_cost(self.cost),
+            percentage
+        );
+
+        if self.children.iter().all(|child| child.cost.is_zero()) {
+            return writeln!(output, "<div>{}</div>", label);
+        }
+
+        writeln!(output, "<details open><summary>{}</summary>", label)?;
+        for child in &self.children {
+            child.render_html(output, total, fmt_cost)?;
+        }
+        writeln!(output, "</details>")
+    }
+}
+
+fn render_hotspots(
+    output: &mut impl Write,
+    title: &str,
+    items: &[(String, usize, InternalGas)],
+    total: InternalGas,
+    scaling_factor: f64,
+) -> fmt::Result {
+    writeln!(output, "<h2>{}</h2>", title)?;
+    if items.is_empty() {
+        return writeln!(output, "<p>None</p>");
+    }
+
+    writeln!(
+        output,
+        "<table><tr><th>Name</th><th>Count</th><th>Gas units</th><th>Percentage</th></tr>"
+    )?;
+    for (name, count, cost) in items {
+        writeln!(
+            output,
+            r#"<tr><td class="node">{}</td><td class="num">{}</td><td class="num">{}</td><td class="num">{:.2}%</td></tr>"#,
+            Escape(name),
+            count,
+            fmt_scaled(u64::from(*cost), scaling_factor),
+            percentage(u64::from(*cost), u64::from(total))
+        )?;
+    }
+    writeln!(output, "</table>")
+}
+
+impl TypeErasedGasLog {
+    /// Renders a self-contained HTML report with the execution & IO and storage fee breakdowns,
+    /// followed by the most expensive instructions, native calls and storage accesses.
+    pub fn to_html_report(
+        &self,
+        output: &mut impl Write,
+        title: &str,
+        hotspots: &AggregatedExecutionGasEvents,
+    ) -> fmt::Result {
+        let scaling_factor = u64::from(self.exec_io.gas_scaling_factor) as f64;
+        let fmt_gas =
+            |cost: InternalGas| format!("{} gas units", fmt_scaled(cost.into(), scaling_factor));
+
+        writeln!(output, "<!DOCTYPE html>")?;
+        writeln!(output, r#"<html><head><meta charset="utf-8">"#)?;
+        writeln!(output, "<title>{}</title>", Escape(title))?;
+        writeln!(output, "<style>{}</style></head><body>", STYLE)?;
+        writeln!(output, "<h1>{}</h1>", Escape(title))?;
+
+        writeln!(output, "<table>")?;
+        writeln!(
+            output,
+            r#"<tr><th>Execution &amp; IO</th><td class="num">{}</td></tr>"#,
+            fmt_gas(self.exec_io.total)
+        )?;
+        writeln!(
+            output,
+            r#"<tr><th>Storage fees</th><td class="num">{} APT</td></tr>"#,
+            fmt_scaled(self.storage.total.into(), 1_0000_0000f64)
+        )?;
+        writeln!(output, "</table>")?;
+
+        writeln!(output, "<h2>Execution &amp; IO</h2>")?;
+        let mut exec_io = self.exec_io.tree.clone();
+        exec_io.include_child_costs();
+        writeln!(output, r#"<div class="tree">"#)?;
+        exec_io.render_html(output, self.exec_io.total, &fmt_gas)?;
+        writeln!(output, "</div>")?;
+
+        writeln!(output, "<h2>Storage fees</h2>")?;
+        let mut storage = self.storage.tree.clone();
+        storage.include_child_costs();
+        writeln!(output, r#"<div class="tree">"#)?;
+        storage.render_html(output, self.storage.total, &|cost| {
+            format!("{} APT", fmt_scaled(cost.into(), 1_0000_0000f64))
+        })?;
+        writeln!(output, "</div>")?;
+
+        render_hotspots(
+            output,
+            "Instructions &amp; native calls",
+            &hotspots.ops,
+            hotspots.total,
+            scaling_factor,
+        )?;
+        render_hotspots(
+            output,
+            "Storage reads",
+            &hotspots.storage_reads,
+            hotspots.total,
+            scaling_factor,
+        )?;
+        render_hotspots(
+            output,
+            "Storage writes",
+            &hotspots.storage_writes,
+            hotspots.total,
+            scaling_factor,
+        )?;
+
+        writeln!(output, "</body></html>")
+    }
+}
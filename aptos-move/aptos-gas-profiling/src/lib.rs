@@ -2,13 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aggregate;
+mod chrome_trace;
 mod erased;
 mod flamegraph;
+mod html;
 mod log;
 mod misc;
 mod profiler;
 mod render;
+mod speedscope;
 mod textualize;
 
+pub use aggregate::AggregatedExecutionGasEvents;
+pub use erased::TypeErasedGasLog;
 pub use log::{FrameName, TransactionGasLog};
 pub use profiler::GasProfiler;
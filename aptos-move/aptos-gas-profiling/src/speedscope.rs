@@ -0,0 +1,101 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::erased::{Node, TypeErasedGasLog};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// The frame table shared by all profiles in a speedscope file.
+#[derive(Default)]
+struct Frames {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+impl Frames {
+    fn get_or_insert(&mut self, name: &str) -> usize {
+        if let Some(idx) = self.index.get(name) {
+            return *idx;
+        }
+        let idx = self.names.len();
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), idx);
+        idx
+    }
+}
+
+impl<U> Node<U> {
+    /// Converts the tree into a sampled speedscope profile, with one sample per node that has
+    /// a cost of its own, weighted by that cost.
+    fn to_speedscope_profile(&self, name: &str, frames: &mut Frames, scale: f64) -> Value {
+        let mut samples = vec![];
+        let mut weights = vec![];
+        let mut stack = vec![];
+
+        fn visit<U>(
+            node: &Node<U>,
+            frames: &mut Frames,
+            scale: f64,
+            stack: &mut Vec<usize>,
+            samples: &mut Vec<Vec<usize>>,
+            weights: &mut Vec<f64>,
+        ) {
+            stack.push(frames.get_or_insert(&node.text));
+            if !node.cost.is_zero() {
+                samples.push(stack.clone());
+                weights.push(u64::from(node.cost) as f64 / scale);
+            }
+            for child in &node.children {
+                visit(child, frames, scale, stack, samples, weights);
+            }
+            stack.pop();
+        }
+        visit(self, frames, scale, &mut stack, &mut samples, &mut weights);
+
+        let total: f64 = weights.iter().sum();
+        json!({
+            "type": "sampled",
+            "name": name,
+            "unit": "none",
+            "startValue": 0,
+            "endValue": total,
+            "samples": samples,
+            "weights": weights,
+        })
+    }
+}
+
+impl TypeErasedGasLog {
+    /// Exports the gas log in the speedscope file format (https://www.speedscope.app), with one
+    /// profile for execution & IO (in gas units) and one for storage fees (in Octa).
+    pub fn to_speedscope(&self, name: &str) -> String {
+        let mut frames = Frames::default();
+
+        let profiles = vec![
+            self.exec_io.tree.to_speedscope_profile(
+                "execution & IO (gas units)",
+                &mut frames,
+                u64::from(self.exec_io.gas_scaling_factor) as f64,
+            ),
+            self.storage
+                .tree
+                .to_speedscope_profile("storage fees (Octa)", &mut frames, 1.0),
+        ];
+
+        json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "name": name,
+            "exporter": "aptos-gas-profiling",
+            "activeProfileIndex": 0,
+            "shared": {
+                "frames": frames
+                    .names
+                    .iter()
+                    .map(|name| json!({ "name": name }))
+                    .collect::<Vec<_>>(),
+            },
+            "profiles": profiles,
+        })
+        .to_string()
+    }
+}
@@ -8,6 +8,12 @@
 ///   - Changing how gas is calculated in any way
 ///
 /// Change log:
+/// - V12
+///   - Added module events (`event::emit`), which are not bound to an event handle
+///   - Added BN254 operations.
+///   - Added Poseidon hash natives over the BN254 and BLS12-381 scalar fields.
+///   - Added transaction context natives describing the user transaction and its payload.
+///   - Added the gas limit of the authentication functions of abstracted accounts.
 /// - V11
 //    - Ristretto255 natives (point cloning & double-scalar multiplication) and Bulletproofs natives
 /// - V10
@@ -41,4 +47,4 @@
 ///       global operations.
 /// - V1
 ///   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = 11;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = 12;
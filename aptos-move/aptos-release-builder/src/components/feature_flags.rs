@@ -45,6 +45,13 @@ pub enum FeatureFlag {
     GasPayerEnabled,
     AptosUniqueIdentifiers,
     BulletproofsNatives,
+    ModuleEvent,
+    Bn254Structures,
+    PoseidonNatives,
+    WebauthnSignature,
+    Secp256k1EcdsaAuthenticator,
+    TransactionContextExtension,
+    AccountAbstraction,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -168,6 +175,17 @@ impl From<FeatureFlag> for AptosFeatureFlag {
             FeatureFlag::GasPayerEnabled => AptosFeatureFlag::GAS_PAYER_ENABLED,
             FeatureFlag::AptosUniqueIdentifiers => AptosFeatureFlag::APTOS_UNIQUE_IDENTIFIERS,
             FeatureFlag::BulletproofsNatives => AptosFeatureFlag::BULLETPROOFS_NATIVES,
+            FeatureFlag::ModuleEvent => AptosFeatureFlag::MODULE_EVENT,
+            FeatureFlag::Bn254Structures => AptosFeatureFlag::BN254_STRUCTURES,
+            FeatureFlag::PoseidonNatives => AptosFeatureFlag::POSEIDON_NATIVES,
+            FeatureFlag::WebauthnSignature => AptosFeatureFlag::WEBAUTHN_SIGNATURE,
+            FeatureFlag::Secp256k1EcdsaAuthenticator => {
+                AptosFeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR
+            },
+            FeatureFlag::TransactionContextExtension => {
+                AptosFeatureFlag::TRANSACTION_CONTEXT_EXTENSION
+            },
+            FeatureFlag::AccountAbstraction => AptosFeatureFlag::ACCOUNT_ABSTRACTION,
         }
     }
 }
@@ -214,6 +232,17 @@ impl From<AptosFeatureFlag> for FeatureFlag {
             AptosFeatureFlag::GAS_PAYER_ENABLED => FeatureFlag::GasPayerEnabled,
             AptosFeatureFlag::APTOS_UNIQUE_IDENTIFIERS => FeatureFlag::AptosUniqueIdentifiers,
             AptosFeatureFlag::BULLETPROOFS_NATIVES => FeatureFlag::BulletproofsNatives,
+            AptosFeatureFlag::MODULE_EVENT => FeatureFlag::ModuleEvent,
+            AptosFeatureFlag::BN254_STRUCTURES => FeatureFlag::Bn254Structures,
+            AptosFeatureFlag::POSEIDON_NATIVES => FeatureFlag::PoseidonNatives,
+            AptosFeatureFlag::WEBAUTHN_SIGNATURE => FeatureFlag::WebauthnSignature,
+            AptosFeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR => {
+                FeatureFlag::Secp256k1EcdsaAuthenticator
+            },
+            AptosFeatureFlag::TRANSACTION_CONTEXT_EXTENSION => {
+                FeatureFlag::TransactionContextExtension
+            },
+            AptosFeatureFlag::ACCOUNT_ABSTRACTION => FeatureFlag::AccountAbstraction,
         }
     }
 }
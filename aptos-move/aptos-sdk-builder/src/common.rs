@@ -27,6 +27,13 @@ pub(crate) fn prepare_doc_string(doc: &str) -> String {
     doc.replace("\n ", "\n").trim().to_string()
 }
 
+/// Whether the struct tag is `0x1::string::String`, which is passed as a UTF-8 string.
+pub(crate) fn is_string(tag: &StructTag) -> bool {
+    static STRING_TAG: Lazy<StructTag> =
+        Lazy::new(|| StructTag::from_str("0x1::string::String").unwrap());
+    tag == Lazy::force(&STRING_TAG)
+}
+
 fn quote_type_as_format(type_tag: &TypeTag) -> Format {
     use TypeTag::*;
     let str_tag: Lazy<StructTag> =
@@ -16,6 +16,9 @@ use std::path::PathBuf;
 enum Language {
     Rust,
     Go,
+    #[value(name = "typescript")]
+    TypeScript,
+    Python,
 }
 
 #[derive(Debug, Parser)]
@@ -83,6 +86,10 @@ fn main() {
                     )
                     .unwrap();
                 },
+                Language::TypeScript => {
+                    aptos_sdk_builder::typescript::output(&mut out, &abis).unwrap()
+                },
+                Language::Python => aptos_sdk_builder::python::output(&mut out, &abis).unwrap(),
             }
             return;
         },
@@ -98,6 +105,13 @@ fn main() {
                     install_dir.clone(),
                     options.serde_package_name.clone(),
                 )),
+                // The TypeScript and Python builders use the types of the Aptos SDKs
+                Language::TypeScript | Language::Python => {
+                    panic!(
+                        "--with-aptos-types is not supported for {:?}",
+                        options.language
+                    )
+                },
             };
 
         let content =
@@ -118,6 +132,7 @@ fn main() {
                 vec!["aptos-types"],
             ),
             Language::Go => ("aptostypes".to_string(), vec!["aptostypes"]),
+            Language::TypeScript | Language::Python => unreachable!(),
         };
 
         let config = serdegen::CodeGeneratorConfig::new(package_name)
@@ -138,6 +153,10 @@ fn main() {
                 options.serde_package_name,
                 options.package_name,
             )),
+            Language::TypeScript => {
+                Box::new(aptos_sdk_builder::typescript::Installer::new(install_dir))
+            },
+            Language::Python => Box::new(aptos_sdk_builder::python::Installer::new(install_dir)),
         };
 
     if let Some(ref name) = options.module_name {
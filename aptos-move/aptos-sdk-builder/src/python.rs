@@ -0,0 +1,222 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common;
+use aptos_types::transaction::{EntryABI, EntryFunctionABI};
+use heck::SnakeCase;
+use move_core_types::language_storage::TypeTag;
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output entry function payload builders in Python for the given ABIs.
+/// The generated code depends on the `aptos_sdk` package. Transaction scripts are not supported.
+pub fn output(out: &mut dyn Write, abis: &[EntryABI]) -> Result<()> {
+    let mut emitter = PythonEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+    };
+
+    emitter.output_preamble()?;
+    for abi in common::entry_function_abis(abis) {
+        emitter.output_entry_function_builder(&abi)?;
+    }
+    Ok(())
+}
+
+/// Shared state for the Python code generator.
+struct PythonEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+}
+
+impl<T> PythonEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"# This file was generated by aptos-sdk-builder. Do not edit.
+
+from typing import List
+
+from aptos_sdk.account_address import AccountAddress
+from aptos_sdk.bcs import Serializer
+from aptos_sdk.transactions import (
+    EntryFunction,
+    TransactionArgument,
+    TransactionPayload,
+)
+from aptos_sdk.type_tag import TypeTag"#
+        )
+    }
+
+    fn output_entry_function_builder(&mut self, abi: &EntryFunctionABI) -> Result<()> {
+        let qualified_name = format!("{}::{}", abi.module_name().short_str_lossless(), abi.name());
+        if let Some(type_tag) = abi
+            .args()
+            .iter()
+            .map(|arg| arg.type_tag())
+            .find(|type_tag| quote_type(type_tag).is_none())
+        {
+            return writeln!(
+                self.out,
+                "\n\n# Skipped `{}`: arguments of type `{}` are not supported.",
+                qualified_name, type_tag
+            );
+        }
+
+        writeln!(self.out, "\n")?;
+        writeln!(
+            self.out,
+            "def {}(",
+            quote_identifier(&format!("{}_{}", abi.module_name().name(), abi.name()))
+        )?;
+        self.out.indent();
+        for ty_arg in abi.ty_args() {
+            writeln!(self.out, "{}: TypeTag,", quote_identifier(ty_arg.name()))?;
+        }
+        for arg in abi.args() {
+            writeln!(
+                self.out,
+                "{}: {},",
+                quote_identifier(arg.name()),
+                quote_type(arg.type_tag()).unwrap()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, ") -> TransactionPayload:")?;
+        self.out.indent();
+        self.output_comment(abi.doc())?;
+        writeln!(self.out, "return TransactionPayload(")?;
+        self.out.indent();
+        writeln!(self.out, "EntryFunction.natural(")?;
+        self.out.indent();
+        writeln!(self.out, "\"{}\",", abi.module_name().short_str_lossless())?;
+        writeln!(self.out, "\"{}\",", abi.name())?;
+        writeln!(
+            self.out,
+            "[{}],",
+            abi.ty_args()
+                .iter()
+                .map(|ty_arg| quote_identifier(ty_arg.name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(self.out, "[")?;
+        self.out.indent();
+        for arg in abi.args() {
+            writeln!(
+                self.out,
+                "TransactionArgument({}, {}),",
+                quote_identifier(arg.name()),
+                quote_encoder(arg.type_tag())
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "],")?;
+        self.out.unindent();
+        writeln!(self.out, ")")?;
+        self.out.unindent();
+        writeln!(self.out, ")")?;
+        self.out.unindent();
+        Ok(())
+    }
+
+    fn output_comment(&mut self, doc: &str) -> Result<()> {
+        let doc = common::prepare_doc_string(doc);
+        if doc.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.out, "\"\"\"")?;
+        for line in doc.lines() {
+            writeln!(
+                self.out,
+                "{}",
+                line.replace("\"\"\"", "\\\"\\\"\\\"").trim_end()
+            )?;
+        }
+        writeln!(self.out, "\"\"\"")
+    }
+}
+
+/// Convert a Move name into a Python identifier (e.g. `CoinType` to `coin_type`), avoiding
+/// keywords.
+fn quote_identifier(name: &str) -> String {
+    let identifier = name.to_snake_case();
+    match identifier.as_str() {
+        "and" | "as" | "assert" | "async" | "await" | "break" | "class" | "continue" | "def"
+        | "del" | "elif" | "else" | "except" | "finally" | "for" | "from" | "global" | "if"
+        | "import" | "in" | "is" | "lambda" | "nonlocal" | "not" | "or" | "pass" | "raise"
+        | "return" | "try" | "while" | "with" | "yield" => format!("{}_", identifier),
+        _ => identifier,
+    }
+}
+
+/// The Python type of an argument, or `None` if arguments of this type are not supported.
+fn quote_type(type_tag: &TypeTag) -> Option<String> {
+    use TypeTag::*;
+    Some(match type_tag {
+        Bool => "bool".into(),
+        U8 | U16 | U32 | U64 | U128 | U256 => "int".into(),
+        Address => "AccountAddress".into(),
+        Vector(type_tag) => match type_tag.as_ref() {
+            U8 => "bytes".into(),
+            type_tag => format!("List[{}]", quote_type(type_tag)?),
+        },
+        Struct(tag) if common::is_string(tag) => "str".into(),
+        Struct(_) | Signer => return None,
+    })
+}
+
+/// The `Serializer` method (or sequence serializer) encoding an argument of the given type.
+fn quote_encoder(type_tag: &TypeTag) -> String {
+    use TypeTag::*;
+    match type_tag {
+        Bool => "Serializer.bool".into(),
+        U8 => "Serializer.u8".into(),
+        U16 => "Serializer.u16".into(),
+        U32 => "Serializer.u32".into(),
+        U64 => "Serializer.u64".into(),
+        U128 => "Serializer.u128".into(),
+        U256 => "Serializer.u256".into(),
+        Address => "Serializer.struct".into(),
+        Vector(type_tag) => match type_tag.as_ref() {
+            U8 => "Serializer.to_bytes".into(),
+            type_tag => format!(
+                "Serializer.sequence_serializer({})",
+                quote_encoder(type_tag)
+            ),
+        },
+        Struct(tag) if common::is_string(tag) => "Serializer.str".into(),
+        Struct(_) | Signer => common::type_not_allowed(type_tag),
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[EntryABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("__init__.py"))?;
+        output(&mut file, abis)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,634 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed Rust bindings for the resources, events and view functions of a Move package.
+//!
+//! For each module, this generates a Rust struct (with serde derives, so that it can be
+//! decoded from BCS) for every struct of the module, a helper to fetch each resource of
+//! an account, and a typed wrapper for each view function (which calls the view function
+//! through the REST API and decodes the return values). Structs and functions that use
+//! types without a Rust representation (e.g., tables) are skipped.
+
+use heck::SnakeCase;
+use move_binary_format::{
+    file_format::Ability,
+    normalized::{Function, Module, Struct, Type},
+    CompiledModule,
+};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Result, Write},
+};
+
+/// A module to generate bindings for, along with the names of its view functions
+/// (as found in the module metadata).
+pub struct BindingsModule {
+    module: Module,
+    view_functions: BTreeSet<Identifier>,
+}
+
+impl BindingsModule {
+    pub fn new(
+        module: &CompiledModule,
+        view_functions: impl IntoIterator<Item = Identifier>,
+    ) -> Self {
+        Self {
+            module: Module::new(module),
+            view_functions: view_functions.into_iter().collect(),
+        }
+    }
+}
+
+/// Identifies a struct by (address, module name, struct name)
+type StructKey = (AccountAddress, Identifier, Identifier);
+
+/// Output typed bindings in Rust for the given modules.
+pub fn output(out: &mut dyn Write, modules: &[BindingsModule]) -> Result<()> {
+    if modules.is_empty() {
+        return Ok(());
+    }
+    let mut emitter = RustBindingsEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+        structs: modules
+            .iter()
+            .flat_map(|bindings_module| {
+                let module = &bindings_module.module;
+                module.structs.iter().map(|(name, struct_)| {
+                    (
+                        (module.address, module.name.clone(), name.clone()),
+                        struct_.clone(),
+                    )
+                })
+            })
+            .collect(),
+        supported_structs: BTreeSet::new(),
+        event_structs: BTreeSet::new(),
+    };
+    emitter.compute_supported_structs();
+    emitter.compute_event_structs();
+
+    emitter.output_preamble()?;
+    for bindings_module in modules {
+        emitter.output_module(bindings_module)?;
+    }
+    Ok(())
+}
+
+/// Shared state for the Rust bindings generator.
+struct RustBindingsEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// All the structs of the modules.
+    structs: BTreeMap<StructKey, Struct>,
+    /// The structs for which a Rust representation can be generated.
+    supported_structs: BTreeSet<StructKey>,
+    /// The structs that are emitted as events (i.e., used in an `EventHandle`).
+    event_structs: BTreeSet<StructKey>,
+}
+
+impl<T> RustBindingsEmitter<T>
+where
+    T: Write,
+{
+    /// A struct is supported iff all its fields are supported. As fields may refer
+    /// to other structs, this is computed as a fixpoint.
+    fn compute_supported_structs(&mut self) {
+        self.supported_structs = self.structs.keys().cloned().collect();
+        loop {
+            let unsupported_structs: Vec<_> = self
+                .structs
+                .iter()
+                .filter(|(key, struct_)| {
+                    self.supported_structs.contains(*key)
+                        && struct_.fields.iter().any(|field| {
+                            self.quote_type(&field.type_, &key.1, /* allow_type_params */ true)
+                                .is_none()
+                        })
+                })
+                .map(|(key, _)| key.clone())
+                .collect();
+            if unsupported_structs.is_empty() {
+                return;
+            }
+            for key in unsupported_structs {
+                self.supported_structs.remove(&key);
+            }
+        }
+    }
+
+    fn compute_event_structs(&mut self) {
+        for struct_ in self.structs.values() {
+            for field in &struct_.fields {
+                if let Type::Struct {
+                    address,
+                    module,
+                    name,
+                    type_arguments,
+                } = &field.type_
+                {
+                    if is_framework_struct(address, module, name, "event", "EventHandle") {
+                        if let Some(Type::Struct {
+                            address,
+                            module,
+                            name,
+                            ..
+                        }) = type_arguments.get(0)
+                        {
+                            self.event_structs
+                                .insert((*address, module.clone(), name.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// Typed bindings for the resources, events and view functions of Move modules.
+//
+// This code was generated from the compiled modules (and their metadata) with the tool `aptos-sdk-builder`.
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::too_many_arguments)]
+
+use aptos_sdk::{{
+    bcs,
+    move_types::{{
+        identifier::Identifier,
+        language_storage::{{StructTag, TypeTag}},
+        u256::U256,
+    }},
+    rest_client::{{
+        aptos_api_types::{{MoveType, ViewRequest}},
+        Client,
+    }},
+    types::{{account_address::AccountAddress, event::EventHandle}},
+}};
+use serde::{{de::DeserializeOwned, Deserialize, Serialize}};"#
+        )
+    }
+
+    fn output_module(&mut self, bindings_module: &BindingsModule) -> Result<()> {
+        let module = &bindings_module.module;
+        writeln!(
+            self.out,
+            "\n/// Bindings for the module `{}`.\npub mod {} {{",
+            module.module_id().short_str_lossless(),
+            quote_identifier(module.name.as_str()),
+        )?;
+        self.out.indent();
+        writeln!(self.out, "use super::*;")?;
+
+        for (name, struct_) in &module.structs {
+            let key = (module.address, module.name.clone(), name.clone());
+            if !self.supported_structs.contains(&key) {
+                writeln!(
+                    self.out,
+                    "\n// Skipped struct `{}` (unsupported field types).",
+                    name
+                )?;
+                continue;
+            }
+            self.output_struct(module, name, struct_)?;
+            if struct_.abilities.has_ability(Ability::Key) {
+                self.output_resource_getter(module, name, struct_)?;
+            }
+        }
+
+        for name in &bindings_module.view_functions {
+            match module.exposed_functions.get(name) {
+                Some(function) => {
+                    if !self.output_view_function(module, name, function)? {
+                        writeln!(
+                            self.out,
+                            "\n// Skipped view function `{}` (unsupported argument or return types).",
+                            name
+                        )?;
+                    }
+                },
+                None => writeln!(
+                    self.out,
+                    "\n// Skipped view function `{}` (not a public function).",
+                    name
+                )?,
+            }
+        }
+
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_struct(
+        &mut self,
+        module: &Module,
+        name: &Identifier,
+        struct_: &Struct,
+    ) -> Result<()> {
+        let key = (module.address, module.name.clone(), name.clone());
+        let kind = if struct_.abilities.has_ability(Ability::Key) {
+            "Resource"
+        } else if self.event_structs.contains(&key) {
+            "Event"
+        } else {
+            "Struct"
+        };
+        let generics = quote_generics(struct_);
+        writeln!(
+            self.out,
+            "\n/// {} `{}::{}`.\n#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]\npub struct {}{} {{",
+            kind,
+            module.module_id().short_str_lossless(),
+            name,
+            name,
+            generics,
+        )?;
+        self.out.indent();
+        for field in &struct_.fields {
+            let type_ = self
+                .quote_type(
+                    &field.type_,
+                    &module.name,
+                    /* allow_type_params */ true,
+                )
+                .expect("supported structs only have supported fields");
+            writeln!(
+                self.out,
+                "pub {}: {},",
+                quote_identifier(field.name.as_str()),
+                type_
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        writeln!(self.out, "\nimpl{0} {1}{0} {{", generics, name)?;
+        self.out.indent();
+        if struct_.type_parameters.is_empty() {
+            writeln!(
+                self.out,
+                "/// Returns the struct tag of `{}::{}`.\npub fn struct_tag() -> StructTag {{",
+                module.module_id().short_str_lossless(),
+                name,
+            )?;
+        } else {
+            writeln!(
+                self.out,
+                "/// Returns the struct tag of `{}::{}` with the given type arguments.\npub fn struct_tag(type_args: Vec<TypeTag>) -> StructTag {{",
+                module.module_id().short_str_lossless(),
+                name,
+            )?;
+        }
+        self.out.indent();
+        writeln!(
+            self.out,
+            r#"StructTag {{
+    address: {},
+    module: Identifier::new("{}").unwrap(),
+    name: Identifier::new("{}").unwrap(),
+    type_params: {},
+}}"#,
+            quote_address(&module.address),
+            module.name,
+            name,
+            if struct_.type_parameters.is_empty() {
+                "vec![]"
+            } else {
+                "type_args"
+            },
+        )?;
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        if self.event_structs.contains(&key) {
+            writeln!(
+                self.out,
+                r#"
+/// Decodes the event from its BCS encoded data.
+pub fn from_event_data(event_data: &[u8]) -> anyhow::Result<Self>
+where
+    Self: DeserializeOwned,
+{{
+    Ok(bcs::from_bytes(event_data)?)
+}}"#
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_resource_getter(
+        &mut self,
+        module: &Module,
+        name: &Identifier,
+        struct_: &Struct,
+    ) -> Result<()> {
+        let generics = quote_generics(struct_);
+        let bounded_generics = quote_bounded_generics(struct_);
+        let (type_args_param, type_args) = if struct_.type_parameters.is_empty() {
+            ("", "")
+        } else {
+            (", type_args: Vec<TypeTag>", "type_args")
+        };
+        writeln!(
+            self.out,
+            r#"
+/// Fetches the resource `{0}::{1}` of the given account.
+pub async fn get_{2}{3}(client: &Client, address: AccountAddress{4}) -> anyhow::Result<{1}{5}> {{
+    let resource_type = <{1}{5}>::struct_tag({6}).to_string();
+    Ok(client
+        .get_account_resource_bcs(address, &resource_type)
+        .await?
+        .into_inner())
+}}"#,
+            module.module_id().short_str_lossless(),
+            name,
+            name.as_str().to_snake_case(),
+            bounded_generics,
+            type_args_param,
+            generics,
+            type_args,
+        )
+    }
+
+    /// Outputs the wrapper of the view function, or returns false if the
+    /// view function has unsupported argument or return types.
+    fn output_view_function(
+        &mut self,
+        module: &Module,
+        name: &Identifier,
+        function: &Function,
+    ) -> Result<bool> {
+        let parameter_types = match function
+            .parameters
+            .iter()
+            .map(|type_| self.quote_type(type_, &module.name, /* allow_type_params */ false))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(parameter_types) => parameter_types,
+            None => return Ok(false),
+        };
+        let arguments = match function
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(index, type_)| quote_view_argument(type_, &format!("arg{}", index)))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(arguments) => arguments,
+            None => return Ok(false),
+        };
+        let return_types = match function
+            .return_
+            .iter()
+            .map(|type_| self.quote_type(type_, &module.name, /* allow_type_params */ false))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(return_types) => return_types,
+            None => return Ok(false),
+        };
+
+        let parameters: Vec<_> = std::iter::once("client: &Client".to_string())
+            .chain(
+                (0..function.type_parameters.len())
+                    .map(|index| format!("type_arg{}: TypeTag", index)),
+            )
+            .chain(
+                parameter_types
+                    .iter()
+                    .enumerate()
+                    .map(|(index, type_)| format!("arg{}: {}", index, type_)),
+            )
+            .collect();
+        let return_type = match return_types.len() {
+            1 => return_types[0].clone(),
+            _ => format!("({})", return_types.join(", ")),
+        };
+        let return_values = return_types
+            .iter()
+            .enumerate()
+            .map(|(index, type_)| format!("bcs::from_bytes::<{}>(&values[{}])?", type_, index))
+            .collect::<Vec<_>>();
+        let return_value = match return_values.len() {
+            1 => return_values[0].clone(),
+            _ => format!("({})", return_values.join(", ")),
+        };
+
+        writeln!(
+            self.out,
+            r#"
+/// Calls the view function `{0}::{1}`.
+pub async fn {2}({3}) -> anyhow::Result<{4}> {{
+    let request = ViewRequest {{
+        function: "{0}::{1}".parse()?,
+        type_arguments: vec![{5}],
+        arguments: vec![{6}],
+    }};
+    let values = client.view_bcs(&request, None).await?.into_inner();
+    if values.len() != {7} {{
+        anyhow::bail!("Expected {7} return values, but got {{}}", values.len());
+    }}
+    Ok({8})
+}}"#,
+            module.module_id().short_str_lossless(),
+            name,
+            quote_identifier(&name.as_str().to_snake_case()),
+            parameters.join(", "),
+            return_type,
+            (0..function.type_parameters.len())
+                .map(|index| format!("MoveType::from(&type_arg{})", index))
+                .collect::<Vec<_>>()
+                .join(", "),
+            arguments.join(", "),
+            return_types.len(),
+            return_value,
+        )?;
+        Ok(true)
+    }
+
+    /// Returns the Rust type of the given Move type (in the given module), or
+    /// None if the type has no Rust representation.
+    fn quote_type(
+        &self,
+        type_: &Type,
+        current_module: &Identifier,
+        allow_type_params: bool,
+    ) -> Option<String> {
+        Some(match type_ {
+            Type::Bool => "bool".into(),
+            Type::U8 => "u8".into(),
+            Type::U16 => "u16".into(),
+            Type::U32 => "u32".into(),
+            Type::U64 => "u64".into(),
+            Type::U128 => "u128".into(),
+            Type::U256 => "U256".into(),
+            Type::Address => "AccountAddress".into(),
+            Type::Vector(type_) => format!(
+                "Vec<{}>",
+                self.quote_type(type_, current_module, allow_type_params)?
+            ),
+            Type::TypeParameter(index) if allow_type_params => format!("T{}", index),
+            Type::Struct {
+                address,
+                module,
+                name,
+                type_arguments,
+            } => {
+                if is_framework_struct(address, module, name, "string", "String") {
+                    "String".into()
+                } else if is_framework_struct(address, module, name, "option", "Option") {
+                    format!(
+                        "Option<{}>",
+                        self.quote_type(&type_arguments[0], current_module, allow_type_params)?
+                    )
+                } else if is_framework_struct(address, module, name, "event", "EventHandle") {
+                    "EventHandle".into()
+                } else if is_framework_struct(address, module, name, "object", "Object") {
+                    // An object is represented by its address
+                    "AccountAddress".into()
+                } else {
+                    let key = (*address, module.clone(), name.clone());
+                    if !self.supported_structs.contains(&key) {
+                        return None;
+                    }
+
+                    // Only the non-phantom type parameters are generic parameters in Rust
+                    let type_parameters = &self.structs.get(&key)?.type_parameters;
+                    let type_arguments = type_arguments
+                        .iter()
+                        .zip(type_parameters.iter())
+                        .filter(|(_, type_parameter)| !type_parameter.is_phantom)
+                        .map(|(type_, _)| self.quote_type(type_, current_module, allow_type_params))
+                        .collect::<Option<Vec<_>>>()?;
+                    let path = if module == current_module {
+                        name.to_string()
+                    } else {
+                        format!("super::{}::{}", quote_identifier(module.as_str()), name)
+                    };
+                    if type_arguments.is_empty() {
+                        path
+                    } else {
+                        format!("{}<{}>", path, type_arguments.join(", "))
+                    }
+                }
+            },
+            Type::TypeParameter(_)
+            | Type::Signer
+            | Type::Reference(_)
+            | Type::MutableReference(_) => return None,
+        })
+    }
+}
+
+fn is_framework_struct(
+    address: &AccountAddress,
+    module: &Identifier,
+    name: &Identifier,
+    expected_module: &str,
+    expected_name: &str,
+) -> bool {
+    address == &AccountAddress::ONE
+        && module.as_str() == expected_module
+        && name.as_str() == expected_name
+}
+
+/// Returns the generic parameters of the Rust struct (i.e., the non-phantom type parameters).
+fn quote_generics(struct_: &Struct) -> String {
+    let generics: Vec<_> = non_phantom_type_parameters(struct_)
+        .map(|index| format!("T{}", index))
+        .collect();
+    if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.join(", "))
+    }
+}
+
+fn quote_bounded_generics(struct_: &Struct) -> String {
+    let generics: Vec<_> = non_phantom_type_parameters(struct_)
+        .map(|index| format!("T{}: DeserializeOwned", index))
+        .collect();
+    if generics.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generics.join(", "))
+    }
+}
+
+fn non_phantom_type_parameters(struct_: &Struct) -> impl Iterator<Item = usize> + '_ {
+    struct_
+        .type_parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, type_parameter)| !type_parameter.is_phantom)
+        .map(|(index, _)| index)
+}
+
+/// Returns the expression converting the given view function argument to
+/// its JSON representation in the REST API, or None if it's not supported.
+fn quote_view_argument(type_: &Type, expr: &str) -> Option<String> {
+    Some(match type_ {
+        Type::Bool | Type::U8 | Type::U16 | Type::U32 => format!("serde_json::json!({})", expr),
+        Type::U64 | Type::U128 | Type::U256 => format!("serde_json::json!({}.to_string())", expr),
+        Type::Address => format!("serde_json::json!({}.to_hex_literal())", expr),
+        Type::Vector(type_) => match type_.as_ref() {
+            // Bytes are hex encoded
+            Type::U8 => format!(
+                "serde_json::json!(format!(\"0x{{}}\", {}.iter().map(|byte| format!(\"{{:02x}}\", byte)).collect::<String>()))",
+                expr
+            ),
+            type_ => format!(
+                "serde_json::Value::Array({}.iter().map(|v| {}).collect())",
+                expr,
+                quote_view_argument(type_, "v")?
+            ),
+        },
+        Type::Struct {
+            address,
+            module,
+            name,
+            ..
+        } => {
+            if is_framework_struct(address, module, name, "string", "String") {
+                format!("serde_json::json!({})", expr)
+            } else if is_framework_struct(address, module, name, "object", "Object") {
+                format!("serde_json::json!({}.to_hex_literal())", expr)
+            } else {
+                return None;
+            }
+        },
+        Type::TypeParameter(_) | Type::Signer | Type::Reference(_) | Type::MutableReference(_) => {
+            return None
+        },
+    })
+}
+
+fn quote_address(address: &AccountAddress) -> String {
+    format!(
+        "AccountAddress::new([{}])",
+        address
+            .to_vec()
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Escapes the identifiers that are Rust keywords.
+fn quote_identifier(ident: &str) -> String {
+    const RUST_KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ];
+    if RUST_KEYWORDS.contains(&ident) {
+        format!("r#{}", ident)
+    } else {
+        ident.to_string()
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common;
+use aptos_types::transaction::{EntryABI, EntryFunctionABI};
+use heck::MixedCase;
+use move_core_types::language_storage::TypeTag;
+use serde_generate::indent::{IndentConfig, IndentedWriter};
+use std::{
+    io::{Result, Write},
+    path::PathBuf,
+};
+
+/// Output entry function payload builders in TypeScript for the given ABIs.
+/// The generated code depends on the `BCS` and `TxnBuilderTypes` namespaces of
+/// the `aptos` npm package. Transaction scripts are not supported.
+pub fn output(out: &mut dyn Write, abis: &[EntryABI]) -> Result<()> {
+    let mut emitter = TypeScriptEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(2)),
+    };
+
+    emitter.output_preamble()?;
+    for abi in common::entry_function_abis(abis) {
+        emitter.output_entry_function_builder(&abi)?;
+    }
+    Ok(())
+}
+
+/// Shared state for the TypeScript code generator.
+struct TypeScriptEmitter<T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+}
+
+impl<T> TypeScriptEmitter<T>
+where
+    T: Write,
+{
+    fn output_preamble(&mut self) -> Result<()> {
+        writeln!(
+            self.out,
+            r#"// This file was generated by aptos-sdk-builder. Do not edit.
+
+import {{ BCS, TxnBuilderTypes }} from "aptos";
+
+function serializeArg(serialize: (serializer: BCS.Serializer) => void): BCS.Bytes {{
+  const serializer = new BCS.Serializer();
+  serialize(serializer);
+  return serializer.getBytes();
+}}
+
+function serializeVector<T>(serializer: BCS.Serializer, values: T[], serialize: (value: T) => void): void {{
+  serializer.serializeU32AsUleb128(values.length);
+  values.forEach(serialize);
+}}"#
+        )
+    }
+
+    fn output_entry_function_builder(&mut self, abi: &EntryFunctionABI) -> Result<()> {
+        let qualified_name = format!("{}::{}", abi.module_name().short_str_lossless(), abi.name());
+        if let Some(type_tag) = abi
+            .args()
+            .iter()
+            .map(|arg| arg.type_tag())
+            .find(|type_tag| quote_type(type_tag).is_none())
+        {
+            return writeln!(
+                self.out,
+                "\n// Skipped `{}`: arguments of type `{}` are not supported.",
+                qualified_name, type_tag
+            );
+        }
+
+        writeln!(self.out)?;
+        self.output_comment(abi.doc())?;
+        writeln!(
+            self.out,
+            "export function {}(",
+            quote_identifier(&format!("{}_{}", abi.module_name().name(), abi.name()))
+        )?;
+        self.out.indent();
+        for ty_arg in abi.ty_args() {
+            writeln!(
+                self.out,
+                "{}: TxnBuilderTypes.TypeTag,",
+                quote_identifier(ty_arg.name())
+            )?;
+        }
+        for arg in abi.args() {
+            writeln!(
+                self.out,
+                "{}: {},",
+                quote_identifier(arg.name()),
+                quote_type(arg.type_tag()).unwrap()
+            )?;
+        }
+        self.out.unindent();
+        writeln!(
+            self.out,
+            "): TxnBuilderTypes.TransactionPayloadEntryFunction {{"
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "return new TxnBuilderTypes.TransactionPayloadEntryFunction("
+        )?;
+        self.out.indent();
+        writeln!(self.out, "TxnBuilderTypes.EntryFunction.natural(")?;
+        self.out.indent();
+        writeln!(self.out, "\"{}\",", abi.module_name().short_str_lossless())?;
+        writeln!(self.out, "\"{}\",", abi.name())?;
+        writeln!(
+            self.out,
+            "[{}],",
+            abi.ty_args()
+                .iter()
+                .map(|ty_arg| quote_identifier(ty_arg.name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(self.out, "[")?;
+        self.out.indent();
+        for arg in abi.args() {
+            writeln!(
+                self.out,
+                "serializeArg((serializer) => {}),",
+                quote_serialization(&quote_identifier(arg.name()), arg.type_tag(), 0)
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "],")?;
+        self.out.unindent();
+        writeln!(self.out, "),")?;
+        self.out.unindent();
+        writeln!(self.out, ");")?;
+        self.out.unindent();
+        writeln!(self.out, "}}")
+    }
+
+    fn output_comment(&mut self, doc: &str) -> Result<()> {
+        let doc = common::prepare_doc_string(doc);
+        if doc.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.out, "/**")?;
+        for line in doc.lines() {
+            writeln!(self.out, " * {}", line.replace("*/", "* /").trim_end())?;
+        }
+        writeln!(self.out, " */")
+    }
+}
+
+/// Convert a Move name into a TypeScript identifier (e.g. `CoinType` to `coinType`), avoiding
+/// reserved words.
+fn quote_identifier(name: &str) -> String {
+    let identifier = name.to_mixed_case();
+    match identifier.as_str() {
+        "break" | "case" | "catch" | "class" | "const" | "continue" | "debugger" | "default"
+        | "delete" | "do" | "else" | "enum" | "export" | "extends" | "false" | "finally"
+        | "for" | "function" | "if" | "import" | "in" | "instanceof" | "new" | "null"
+        | "return" | "super" | "switch" | "this" | "throw" | "true" | "try" | "typeof" | "var"
+        | "void" | "while" | "with" | "serializer" => format!("{}_", identifier),
+        _ => identifier,
+    }
+}
+
+/// The TypeScript type of an argument, or `None` if arguments of this type are not supported.
+fn quote_type(type_tag: &TypeTag) -> Option<String> {
+    use TypeTag::*;
+    Some(match type_tag {
+        Bool => "boolean".into(),
+        U8 | U16 | U32 => "number".into(),
+        U64 | U128 | U256 => "BCS.AnyNumber".into(),
+        Address => "TxnBuilderTypes.AccountAddress".into(),
+        Vector(type_tag) => match type_tag.as_ref() {
+            U8 => "BCS.Bytes".into(),
+            type_tag => format!("{}[]", quote_type(type_tag)?),
+        },
+        Struct(tag) if common::is_string(tag) => "string".into(),
+        Struct(_) | Signer => return None,
+    })
+}
+
+/// The TypeScript statement serializing `value` with the `serializer` in scope. Nested vectors
+/// use `depth` to name their elements.
+fn quote_serialization(value: &str, type_tag: &TypeTag, depth: usize) -> String {
+    use TypeTag::*;
+    match type_tag {
+        Bool => format!("serializer.serializeBool({})", value),
+        U8 => format!("serializer.serializeU8({})", value),
+        U16 => format!("serializer.serializeU16({})", value),
+        U32 => format!("serializer.serializeU32({})", value),
+        U64 => format!("serializer.serializeU64({})", value),
+        U128 => format!("serializer.serializeU128({})", value),
+        U256 => format!("serializer.serializeU256({})", value),
+        Address => format!("{}.serialize(serializer)", value),
+        Vector(type_tag) => match type_tag.as_ref() {
+            U8 => format!("serializer.serializeBytes({})", value),
+            type_tag => {
+                let item = format!("item{}", depth);
+                format!(
+                    "serializeVector(serializer, {}, ({}) => {})",
+                    value,
+                    item,
+                    quote_serialization(&item, type_tag, depth + 1)
+                )
+            },
+        },
+        Struct(tag) if common::is_string(tag) => format!("serializer.serializeStr({})", value),
+        Struct(_) | Signer => common::type_not_allowed(type_tag),
+    }
+}
+
+pub struct Installer {
+    install_dir: PathBuf,
+}
+
+impl Installer {
+    pub fn new(install_dir: PathBuf) -> Self {
+        Installer { install_dir }
+    }
+}
+
+impl crate::SourceInstaller for Installer {
+    type Error = Box<dyn std::error::Error>;
+
+    fn install_transaction_builders(
+        &self,
+        name: &str,
+        abis: &[EntryABI],
+    ) -> std::result::Result<(), Self::Error> {
+        let dir_path = self.install_dir.join(name);
+        std::fs::create_dir_all(&dir_path)?;
+        let mut file = std::fs::File::create(dir_path.join("index.ts"))?;
+        output(&mut file, abis)?;
+        Ok(())
+    }
+}
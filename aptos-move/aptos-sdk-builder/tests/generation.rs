@@ -3,11 +3,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_sdk_builder as buildgen;
-use aptos_types::transaction::EntryABI;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ArgumentABI, EntryABI, EntryFunctionABI, TypeArgumentABI},
+};
+use goldenfile::Mint;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
 use serde_generate as serdegen;
 use serde_generate::SourceInstaller as _;
 use serde_reflection::Registry;
-use std::{io::Write, process::Command};
+use std::{io::Write, process::Command, str::FromStr};
 use tempfile::tempdir;
 
 fn get_aptos_registry() -> Registry {
@@ -93,3 +101,111 @@ fn test_that_rust_entry_fun_code_compiles() {
         EXPECTED_SCRIPT_FUN_OUTPUT,
     );
 }
+
+#[test]
+fn test_rust_typed_bindings() {
+    let modules: Vec<_> = aptos_cached_packages::head_release_bundle()
+        .compiled_modules()
+        .iter()
+        .filter(|module| {
+            module.self_id().address() == &AccountAddress::ONE
+                && ["coin", "optional_aggregator", "aggregator"]
+                    .contains(&module.self_id().name().as_str())
+        })
+        .map(aptos_framework::bindings_module)
+        .collect();
+    assert_eq!(modules.len(), 3);
+
+    let mut output = vec![];
+    buildgen::rust_bindings::output(&mut output, &modules).unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // Resources (and their getters), with phantom type parameters omitted
+    assert!(output.contains("pub mod coin {"));
+    assert!(output.contains("pub struct CoinStore {"));
+    assert!(output.contains("pub deposit_events: EventHandle,"));
+    assert!(output.contains("pub async fn get_coin_store(client: &Client, address: AccountAddress, type_args: Vec<TypeTag>) -> anyhow::Result<CoinStore>"));
+
+    // Structs of other modules are referenced through their module
+    assert!(output.contains("pub supply: Option<super::optional_aggregator::OptionalAggregator>,"));
+
+    // Events
+    assert!(output.contains("/// Event `0x1::coin::DepositEvent`."));
+    assert!(output.contains("pub fn from_event_data(event_data: &[u8])"));
+
+    // View functions
+    assert!(output.contains("pub async fn balance(client: &Client, type_arg0: TypeTag, arg0: AccountAddress) -> anyhow::Result<u64>"));
+    assert!(output.contains("function: \"0x1::coin::balance\".parse()?,"));
+}
+
+fn entry_function_abi(
+    module_name: &str,
+    name: &str,
+    doc: &str,
+    ty_args: &[&str],
+    args: &[(&str, &str)],
+) -> EntryABI {
+    EntryABI::EntryFunction(EntryFunctionABI::new(
+        name.to_string(),
+        ModuleId::new(AccountAddress::ONE, Identifier::new(module_name).unwrap()),
+        doc.to_string(),
+        ty_args
+            .iter()
+            .map(|name| TypeArgumentABI::new(name.to_string()))
+            .collect(),
+        args.iter()
+            .map(|(name, type_tag)| {
+                ArgumentABI::new(name.to_string(), TypeTag::from_str(type_tag).unwrap())
+            })
+            .collect(),
+    ))
+}
+
+fn get_golden_abis() -> Vec<EntryABI> {
+    vec![
+        entry_function_abi(
+            "coin",
+            "transfer",
+            " Transfers `amount` of coins `CoinType` to `to`.",
+            &["CoinType"],
+            &[("to", "address"), ("amount", "u64")],
+        ),
+        // Not supported, as objects can't be passed as arguments
+        entry_function_abi("object", "transfer_call", "", &[], &[
+            ("object", "0x1::object::Object<0x1::object::ObjectCore>"),
+            ("to", "address"),
+        ]),
+        entry_function_abi(
+            "test_module",
+            "set_values",
+            " Sets all the values.\n With a second line.",
+            &[],
+            &[
+                ("from", "address"),
+                ("flag", "bool"),
+                ("small", "u8"),
+                ("medium", "u32"),
+                ("large", "u256"),
+                ("name", "0x1::string::String"),
+                ("data", "vector<u8>"),
+                ("recipients", "vector<address>"),
+                ("tags", "vector<vector<u8>>"),
+                ("amounts", "vector<u64>"),
+            ],
+        ),
+    ]
+}
+
+#[test]
+fn test_typescript_builders() {
+    let mut mint = Mint::new("tests/goldens");
+    let mut output = mint.new_goldenfile("typescript.ts").unwrap();
+    buildgen::typescript::output(&mut output, &get_golden_abis()).unwrap();
+}
+
+#[test]
+fn test_python_builders() {
+    let mut mint = Mint::new("tests/goldens");
+    let mut output = mint.new_goldenfile("python.py").unwrap();
+    buildgen::python::output(&mut output, &get_golden_abis()).unwrap();
+}
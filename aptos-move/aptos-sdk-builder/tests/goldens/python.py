@@ -0,0 +1,73 @@
+# This file was generated by aptos-sdk-builder. Do not edit.
+
+from typing import List
+
+from aptos_sdk.account_address import AccountAddress
+from aptos_sdk.bcs import Serializer
+from aptos_sdk.transactions import (
+    EntryFunction,
+    TransactionArgument,
+    TransactionPayload,
+)
+from aptos_sdk.type_tag import TypeTag
+
+
+def coin_transfer(
+    coin_type: TypeTag,
+    to: AccountAddress,
+    amount: int,
+) -> TransactionPayload:
+    """
+    Transfers `amount` of coins `CoinType` to `to`.
+    """
+    return TransactionPayload(
+        EntryFunction.natural(
+            "0x1::coin",
+            "transfer",
+            [coin_type],
+            [
+                TransactionArgument(to, Serializer.struct),
+                TransactionArgument(amount, Serializer.u64),
+            ],
+        )
+    )
+
+
+# Skipped `0x1::object::transfer_call`: arguments of type `0x1::object::Object<0x1::object::ObjectCore>` are not supported.
+
+
+def test_module_set_values(
+    from_: AccountAddress,
+    flag: bool,
+    small: int,
+    medium: int,
+    large: int,
+    name: str,
+    data: bytes,
+    recipients: List[AccountAddress],
+    tags: List[bytes],
+    amounts: List[int],
+) -> TransactionPayload:
+    """
+    Sets all the values.
+    With a second line.
+    """
+    return TransactionPayload(
+        EntryFunction.natural(
+            "0x1::test_module",
+            "set_values",
+            [],
+            [
+                TransactionArgument(from_, Serializer.struct),
+                TransactionArgument(flag, Serializer.bool),
+                TransactionArgument(small, Serializer.u8),
+                TransactionArgument(medium, Serializer.u32),
+                TransactionArgument(large, Serializer.u256),
+                TransactionArgument(name, Serializer.str),
+                TransactionArgument(data, Serializer.to_bytes),
+                TransactionArgument(recipients, Serializer.sequence_serializer(Serializer.struct)),
+                TransactionArgument(tags, Serializer.sequence_serializer(Serializer.to_bytes)),
+                TransactionArgument(amounts, Serializer.sequence_serializer(Serializer.u64)),
+            ],
+        )
+    )
@@ -0,0 +1,74 @@
+// This file was generated by aptos-sdk-builder. Do not edit.
+
+import { BCS, TxnBuilderTypes } from "aptos";
+
+function serializeArg(serialize: (serializer: BCS.Serializer) => void): BCS.Bytes {
+  const serializer = new BCS.Serializer();
+  serialize(serializer);
+  return serializer.getBytes();
+}
+
+function serializeVector<T>(serializer: BCS.Serializer, values: T[], serialize: (value: T) => void): void {
+  serializer.serializeU32AsUleb128(values.length);
+  values.forEach(serialize);
+}
+
+/**
+ * Transfers `amount` of coins `CoinType` to `to`.
+ */
+export function coinTransfer(
+  coinType: TxnBuilderTypes.TypeTag,
+  to: TxnBuilderTypes.AccountAddress,
+  amount: BCS.AnyNumber,
+): TxnBuilderTypes.TransactionPayloadEntryFunction {
+  return new TxnBuilderTypes.TransactionPayloadEntryFunction(
+    TxnBuilderTypes.EntryFunction.natural(
+      "0x1::coin",
+      "transfer",
+      [coinType],
+      [
+        serializeArg((serializer) => to.serialize(serializer)),
+        serializeArg((serializer) => serializer.serializeU64(amount)),
+      ],
+    ),
+  );
+}
+
+// Skipped `0x1::object::transfer_call`: arguments of type `0x1::object::Object<0x1::object::ObjectCore>` are not supported.
+
+/**
+ * Sets all the values.
+ * With a second line.
+ */
+export function testModuleSetValues(
+  from: TxnBuilderTypes.AccountAddress,
+  flag: boolean,
+  small: number,
+  medium: number,
+  large: BCS.AnyNumber,
+  name: string,
+  data: BCS.Bytes,
+  recipients: TxnBuilderTypes.AccountAddress[],
+  tags: BCS.Bytes[],
+  amounts: BCS.AnyNumber[],
+): TxnBuilderTypes.TransactionPayloadEntryFunction {
+  return new TxnBuilderTypes.TransactionPayloadEntryFunction(
+    TxnBuilderTypes.EntryFunction.natural(
+      "0x1::test_module",
+      "set_values",
+      [],
+      [
+        serializeArg((serializer) => from.serialize(serializer)),
+        serializeArg((serializer) => serializer.serializeBool(flag)),
+        serializeArg((serializer) => serializer.serializeU8(small)),
+        serializeArg((serializer) => serializer.serializeU32(medium)),
+        serializeArg((serializer) => serializer.serializeU256(large)),
+        serializeArg((serializer) => serializer.serializeStr(name)),
+        serializeArg((serializer) => serializer.serializeBytes(data)),
+        serializeArg((serializer) => serializeVector(serializer, recipients, (item0) => item0.serialize(serializer))),
+        serializeArg((serializer) => serializeVector(serializer, tags, (item0) => serializer.serializeBytes(item0))),
+        serializeArg((serializer) => serializeVector(serializer, amounts, (item0) => serializer.serializeU64(item0))),
+      ],
+    ),
+  );
+}
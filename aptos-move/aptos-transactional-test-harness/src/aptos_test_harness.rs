@@ -929,8 +929,10 @@ struct PrettyEvent<'a>(&'a ContractEvent);
 impl<'a> fmt::Display for PrettyEvent<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{{")?;
-        writeln!(f, "    key:     {}", self.0.key())?;
-        writeln!(f, "    seq_num: {}", self.0.sequence_number())?;
+        if let ContractEvent::V0(event) = self.0 {
+            writeln!(f, "    key:     {}", event.key())?;
+            writeln!(f, "    seq_num: {}", event.sequence_number())?;
+        }
         writeln!(f, "    type:    {}", self.0.type_tag())?;
         writeln!(f, "    data:    {:?}", hex::encode(self.0.event_data()))?;
         write!(f, "}}")
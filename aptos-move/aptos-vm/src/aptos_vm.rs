@@ -22,24 +22,26 @@ use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
 use aptos_crypto::HashValue;
 use aptos_framework::natives::code::PublishRequest;
 use aptos_gas_algebra::Gas;
-use aptos_gas_meter::{AptosGasMeter, StandardGasAlgebra, StandardGasMeter};
+use aptos_gas_meter::{AptosGasMeter, GasAlgebra, StandardGasAlgebra, StandardGasMeter};
 use aptos_gas_schedule::VMGasParameters;
 use aptos_logger::{enabled, prelude::*, Level};
 use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_state_view::StateView;
 use aptos_types::{
     account_config,
-    account_config::new_block_event_key,
+    account_config::{new_block_event_key, AuthenticationFunctionResource},
     block_executor::partitioner::SubBlocksForShard,
     block_metadata::BlockMetadata,
     fee_statement::FeeStatement,
     on_chain_config::{new_epoch_event_key, FeatureFlag, TimedFeatureOverride},
     state_store::state_key::StateKey,
     transaction::{
-        analyzed_transaction::AnalyzedTransaction, EntryFunction, ExecutionError, ExecutionStatus,
-        ModuleBundle, Multisig, MultisigTransactionPayload, SignatureCheckedTransaction,
-        SignedTransaction, Transaction, TransactionOutput, TransactionPayload, TransactionStatus,
-        VMValidatorResult, WriteSetPayload,
+        analyzed_transaction::AnalyzedTransaction,
+        authenticator::{AccountAuthenticator, Scheme},
+        EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
+        MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction, Transaction,
+        TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
+        WriteSetPayload,
     },
     vm_status::{AbortLocation, StatusCode, VMStatus},
     write_set::WriteOp,
@@ -56,6 +58,7 @@ use move_binary_format::{
     access::ModuleAccess,
     compatibility::Compatibility,
     errors::{verification_error, Location, PartialVMError, VMError, VMResult},
+    file_format::{SignatureToken, Visibility},
     CompiledModule, IndexKind,
 };
 use move_core_types::{
@@ -63,6 +66,7 @@ use move_core_types::{
     ident_str,
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
+    move_resource::MoveStructType,
     transaction_argument::convert_txn_args,
     value::{serialize_values, MoveValue},
     vm_status::StatusType,
@@ -82,6 +86,7 @@ use std::{
     },
 };
 
+static CONFLICT_PROFILING: OnceCell<bool> = OnceCell::new();
 static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
 static NUM_EXECUTION_SHARD: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
@@ -118,6 +123,21 @@ macro_rules! unwrap_or_discard {
     };
 }
 
+/// Turns the failure of the authentication function of `address` into an `INVALID_SIGNATURE`
+/// error, except for storage errors, which do not depend on the transaction.
+fn authentication_failure(address: AccountAddress, err: VMStatus) -> VMStatus {
+    if err.status_code() == StatusCode::STORAGE_ERROR {
+        return err;
+    }
+    VMStatus::error(
+        StatusCode::INVALID_SIGNATURE,
+        Some(format!(
+            "the authentication function of {} failed: {:?}",
+            address, err
+        )),
+    )
+}
+
 impl AptosVM {
     pub fn new(state: &impl StateView) -> Self {
         Self(AptosVMImpl::new(state))
@@ -216,6 +236,21 @@ impl AptosVM {
         }
     }
 
+    /// Enables logging a conflict report for every block executed in parallel when invoked the
+    /// first time.
+    pub fn set_conflict_profiling_once() {
+        // Only the first call succeeds, due to OnceCell semantics.
+        CONFLICT_PROFILING.set(true).ok();
+    }
+
+    /// Get whether parallel execution should record and log per-block conflicts.
+    pub fn get_conflict_profiling() -> bool {
+        match CONFLICT_PROFILING.get() {
+            Some(value) => *value,
+            None => false,
+        }
+    }
+
     pub fn internals(&self) -> AptosVMInternals {
         AptosVMInternals::new(&self.0)
     }
@@ -424,6 +459,11 @@ impl AptosVM {
 
             match payload {
                 TransactionPayload::Script(script) => {
+                    if self.0.get_features().is_enabled(FeatureFlag::MODULE_EVENT) {
+                        verifier::event_validation::verify_no_event_emission_in_script(
+                            script.code(),
+                        )?;
+                    }
                     let loaded_func =
                         session.load_script(script.code(), script.ty_args().to_vec())?;
                     let args =
@@ -995,6 +1035,7 @@ impl AptosVM {
                 .map_err(|err| Self::metadata_validation_error(&err.to_string()))?;
         }
         verifier::resource_groups::validate_resource_groups(session, modules)?;
+        verifier::event_validation::validate_module_events(modules)?;
 
         if !expected_modules.is_empty() {
             return Err(Self::metadata_validation_error(
@@ -1033,7 +1074,10 @@ impl AptosVM {
         gas_meter: &mut impl AptosGasMeter,
     ) -> (VMStatus, VMOutput) {
         // Revalidate the transaction.
-        let mut session = self.0.new_session(resolver, SessionId::prologue(txn));
+        let txn_data = TransactionMetadata::new(txn);
+        let mut session = self
+            .0
+            .new_session(resolver, SessionId::prologue_meta(&txn_data));
         if let Err(err) = self.validate_signature_checked_transaction(
             &mut session,
             resolver,
@@ -1043,6 +1087,13 @@ impl AptosVM {
         ) {
             return discard_error_vm_status(err);
         };
+        // The authentication functions of abstracted accounts replace the signature check, so
+        // the transaction is discarded if they fail, and charged for their gas otherwise.
+        if let Err(err) =
+            self.run_account_abstraction_authentication(resolver, txn, gas_meter, log_context)
+        {
+            return discard_error_vm_status(err);
+        }
 
         if self.0.get_gas_feature_version() >= 1 {
             // Create a new session so that the data cache is flushed.
@@ -1053,11 +1104,10 @@ impl AptosVM {
             // By releasing resource group cache, we start with a fresh slate for resource group
             // cost accounting.
             resolver.release_resource_group_cache();
-            session = self.0.new_session(resolver, SessionId::txn(txn));
+            session = self.0.new_user_session(resolver, &txn_data, txn.payload());
         }
 
         let storage_gas_params = unwrap_or_discard!(self.0.get_storage_gas_parameters(log_context));
-        let txn_data = TransactionMetadata::new(txn);
 
         // We keep track of whether any newly published modules are loaded into the Vm's loader
         // cache as part of executing transactions. This would allow us to decide whether the cache
@@ -1254,11 +1304,11 @@ impl AptosVM {
         let has_new_block_event = change_set
             .events()
             .iter()
-            .any(|e| *e.key() == new_block_event_key());
+            .any(|e| e.event_key() == Some(&new_block_event_key()));
         let has_new_epoch_event = change_set
             .events()
             .iter()
-            .any(|e| *e.key() == new_epoch_event_key());
+            .any(|e| e.event_key() == Some(&new_epoch_event_key()));
         if has_new_block_event && has_new_epoch_event {
             Ok(())
         } else {
@@ -1418,6 +1468,176 @@ impl AptosVM {
             .collect::<Vec<_>>())
     }
 
+    /// Runs the authentication function registered by every signer of the transaction that uses
+    /// an abstraction authenticator, which replaces the native signature check for that signer.
+    /// Each function runs in a separate session, whose changes are discarded, with at most
+    /// `max_aa_gas` units of gas, which are charged to `gas_meter` once it succeeds. A function
+    /// that cannot be resolved, aborts or runs out of gas fails with `INVALID_SIGNATURE`, so that
+    /// the transaction is discarded.
+    fn run_account_abstraction_authentication(
+        &self,
+        resolver: &impl MoveResolverExt,
+        transaction: &SignatureCheckedTransaction,
+        gas_meter: &mut impl AptosGasMeter,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(), VMStatus> {
+        let authenticator = transaction.authenticator_ref();
+        let signers: Vec<(AccountAddress, Vec<u8>)> =
+            std::iter::once((transaction.sender(), authenticator.sender()))
+                .chain(
+                    authenticator
+                        .secondary_signer_addreses()
+                        .into_iter()
+                        .zip(authenticator.secondary_signers()),
+                )
+                .chain(
+                    authenticator
+                        .fee_payer_address()
+                        .zip(authenticator.fee_payer_signer()),
+                )
+                .filter_map(|(address, signer)| match signer {
+                    AccountAuthenticator::Abstraction { signature } => Some((address, signature)),
+                    _ => None,
+                })
+                .collect();
+        if signers.is_empty() {
+            return Ok(());
+        }
+
+        let digest = authenticator
+            .signing_message_digest(transaction.raw_transaction_ref())
+            .map_err(|err| VMStatus::error(StatusCode::INVALID_SIGNATURE, Some(err.to_string())))?;
+        let max_aa_gas = self.0.get_gas_parameters(log_context)?.vm.txn.max_aa_gas;
+
+        for (address, signature) in signers {
+            let (module_id, function_name) =
+                self.resolve_authentication_function(resolver, address)?;
+            // The signer is passed by value and borrowed by the VM for the `&signer` parameter,
+            // as for entry functions.
+            let args = vec![
+                MoveValue::Signer(address),
+                MoveValue::vector_u8(signature),
+                MoveValue::vector_u8(digest.to_vec()),
+            ];
+
+            let mut session = self
+                .0
+                .new_session(resolver, SessionId::prologue(transaction));
+            let mut authentication_gas_meter =
+                self.make_standard_gas_meter(min(max_aa_gas, gas_meter.balance()), log_context)?;
+            session
+                .execute_function_bypass_visibility(
+                    &module_id,
+                    &function_name,
+                    vec![],
+                    serialize_values(&args),
+                    &mut authentication_gas_meter,
+                )
+                .map_err(|err| authentication_failure(address, err.into_vm_status()))?;
+
+            let algebra = authentication_gas_meter.algebra();
+            gas_meter
+                .algebra_mut()
+                .charge_execution(algebra.execution_gas_used())
+                .and_then(|()| gas_meter.algebra_mut().charge_io(algebra.io_gas_used()))
+                .map_err(|err| {
+                    authentication_failure(
+                        address,
+                        err.finish(Location::Undefined).into_vm_status(),
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Rejects, before they are accepted in the mempool, the transactions whose abstraction
+    /// authenticators are not accepted by the authentication functions of their signers.
+    fn validate_account_abstraction(
+        &self,
+        resolver: &impl MoveResolverExt,
+        transaction: &SignatureCheckedTransaction,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(), VMStatus> {
+        if !transaction
+            .authenticator_ref()
+            .all_signers()
+            .iter()
+            .any(|signer| matches!(signer.scheme(), Scheme::Abstraction))
+        {
+            return Ok(());
+        }
+        let balance = TransactionMetadata::new(transaction).max_gas_amount();
+        let mut gas_meter = self.make_standard_gas_meter(balance, log_context)?;
+        self.run_account_abstraction_authentication(
+            resolver,
+            transaction,
+            &mut gas_meter,
+            log_context,
+        )
+    }
+
+    /// Returns the authentication function registered by the account at `address`. Since it is
+    /// called bypassing visibility, the function is checked to be public and to have the signature
+    /// `fun(&signer, vector<u8>, vector<u8>)`.
+    fn resolve_authentication_function(
+        &self,
+        resolver: &impl MoveResolverExt,
+        address: AccountAddress,
+    ) -> Result<(ModuleId, Identifier), VMStatus> {
+        let resolution_failure = |msg: String| {
+            authentication_failure(
+                address,
+                VMStatus::error(StatusCode::FUNCTION_RESOLUTION_FAILURE, Some(msg)),
+            )
+        };
+        let function = resolver
+            .get_resource(&address, &AuthenticationFunctionResource::struct_tag())
+            .map_err(|_| VMStatus::error(StatusCode::STORAGE_ERROR, None))?
+            .and_then(|bytes| bcs::from_bytes::<AuthenticationFunctionResource>(&bytes).ok())
+            .ok_or_else(|| {
+                resolution_failure(format!("{} has no authentication function", address))
+            })?;
+        let (module_name, function_name) = match (
+            Identifier::new(function.module_name()),
+            Identifier::new(function.function_name()),
+        ) {
+            (Ok(module_name), Ok(function_name)) => (module_name, function_name),
+            _ => {
+                return Err(resolution_failure(format!(
+                    "{} has an invalid authentication function",
+                    address
+                )))
+            },
+        };
+
+        let module_id = ModuleId::new(function.module_address(), module_name);
+        let module = self
+            .0
+            .load_module(&module_id, resolver)
+            .map_err(|err| authentication_failure(address, err.into_vm_status()))?;
+        let expected_parameters = [
+            SignatureToken::Reference(Box::new(SignatureToken::Signer)),
+            SignatureToken::Vector(Box::new(SignatureToken::U8)),
+            SignatureToken::Vector(Box::new(SignatureToken::U8)),
+        ];
+        let is_valid = module.function_defs().iter().any(|function_def| {
+            let handle = module.function_handle_at(function_def.function);
+            module.identifier_at(handle.name) == function_name.as_ident_str()
+                && function_def.visibility == Visibility::Public
+                && handle.type_parameters.is_empty()
+                && module.signature_at(handle.return_).0.is_empty()
+                && module.signature_at(handle.parameters).0 == expected_parameters
+        });
+        if !is_valid {
+            return Err(resolution_failure(format!(
+                "the authentication function of {} must be a public function taking \
+                 (&signer, vector<u8>, vector<u8>)",
+                address
+            )));
+        }
+        Ok((module_id, function_name))
+    }
+
     fn run_prologue_with_payload(
         &self,
         session: &mut SessionExt,
@@ -1568,13 +1788,15 @@ impl VMValidator for AptosVM {
 
         let resolver = self.as_move_resolver(state_view);
         let mut session = self.0.new_session(&resolver, SessionId::prologue(&txn));
-        let validation_result = self.validate_signature_checked_transaction(
-            &mut session,
-            &resolver,
-            &txn,
-            true,
-            &log_context,
-        );
+        let validation_result = self
+            .validate_signature_checked_transaction(
+                &mut session,
+                &resolver,
+                &txn,
+                true,
+                &log_context,
+            )
+            .and_then(|()| self.validate_account_abstraction(&resolver, &txn, &log_context));
 
         // Increment the counter for transactions verified.
         let (counter_label, result) = match validation_result {
@@ -1627,6 +1849,24 @@ impl VMAdapter for AptosVM {
         transaction: &SignatureCheckedTransaction,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
+        // ECDSA and abstraction authenticators are only accepted once their feature is enabled.
+        let features = self.0.get_features();
+        if transaction
+            .authenticator_ref()
+            .all_signers()
+            .iter()
+            .any(|signer| match signer.scheme() {
+                Scheme::Secp256r1Ecdsa => !features.is_enabled(FeatureFlag::WEBAUTHN_SIGNATURE),
+                Scheme::Secp256k1Ecdsa => {
+                    !features.is_enabled(FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR)
+                },
+                Scheme::Abstraction => !features.is_enabled(FeatureFlag::ACCOUNT_ABSTRACTION),
+                _ => false,
+            })
+        {
+            return Err(VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None));
+        }
+
         let txn_data = TransactionMetadata::new(transaction);
         self.run_prologue_with_payload(
             session,
@@ -1644,7 +1884,7 @@ impl VMAdapter for AptosVM {
             .change_set()
             .events()
             .iter()
-            .any(|event| *event.key() == new_epoch_event_key)
+            .any(|event| event.event_key() == Some(&new_epoch_event_key))
     }
 
     fn execute_single_transaction(
@@ -1803,7 +2043,7 @@ impl AptosSimulationVM {
 
         // Revalidate the transaction.
         let txn_data = TransactionMetadata::new(txn);
-        let mut session = self.0.new_session(resolver, SessionId::txn_meta(&txn_data));
+        let mut session = self.0.new_user_session(resolver, &txn_data, txn.payload());
         if let Err(err) =
             self.validate_simulated_transaction(&mut session, resolver, txn, &txn_data, log_context)
         {
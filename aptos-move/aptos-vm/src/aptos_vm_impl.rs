@@ -26,7 +26,7 @@ use aptos_types::{
         ApprovedExecutionHashes, ConfigurationResource, FeatureFlag, Features, GasSchedule,
         GasScheduleV2, OnChainConfig, TimedFeatures, Version,
     },
-    transaction::{AbortInfo, ExecutionStatus, Multisig, TransactionStatus},
+    transaction::{AbortInfo, ExecutionStatus, Multisig, TransactionPayload, TransactionStatus},
     vm_status::{StatusCode, VMStatus},
 };
 use aptos_vm_logging::{log_schema::AdapterLogSchema, prelude::*};
@@ -608,6 +608,15 @@ impl AptosVMImpl {
         self.move_vm.new_session(resolver, session_id)
     }
 
+    pub fn new_user_session<'r>(
+        &self,
+        resolver: &'r impl MoveResolverExt,
+        txn_data: &TransactionMetadata,
+        payload: &TransactionPayload,
+    ) -> SessionExt<'r, '_> {
+        self.move_vm.new_user_session(resolver, txn_data, payload)
+    }
+
     pub fn load_module(
         &self,
         module_id: &ModuleId,
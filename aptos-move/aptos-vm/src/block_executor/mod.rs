@@ -206,7 +206,8 @@ impl BlockAptosVM {
             executor_thread_pool,
             maybe_block_gas_limit,
             transaction_commit_listener,
-        );
+        )
+        .with_conflict_profiling(AptosVM::get_conflict_profiling());
 
         let ret = executor.execute_block(state_view, signature_verified_block, state_view);
         match ret {
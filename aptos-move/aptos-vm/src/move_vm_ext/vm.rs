@@ -4,17 +4,22 @@
 use crate::{
     move_vm_ext::{MoveResolverExt, SessionExt, SessionId},
     natives::aptos_natives,
+    transaction_metadata::TransactionMetadata,
 };
 use aptos_framework::natives::{
     aggregator_natives::NativeAggregatorContext,
     code::NativeCodeContext,
     cryptography::{algebra::AlgebraContext, ristretto255_point::NativeRistrettoPointContext},
+    event::NativeEventContext,
     state_storage::NativeStateStorageContext,
-    transaction_context::NativeTransactionContext,
+    transaction_context::{NativeTransactionContext, UserTransactionContext},
 };
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
 use aptos_table_natives::NativeTableContext;
-use aptos_types::on_chain_config::{FeatureFlag, Features, TimedFeatureFlag, TimedFeatures};
+use aptos_types::{
+    on_chain_config::{FeatureFlag, Features, TimedFeatureFlag, TimedFeatures},
+    transaction::TransactionPayload,
+};
 use move_binary_format::errors::VMResult;
 use move_bytecode_verifier::VerifierConfig;
 use move_vm_runtime::{
@@ -97,6 +102,35 @@ impl MoveVmExt {
         &self,
         remote: &'r S,
         session_id: SessionId,
+    ) -> SessionExt<'r, '_> {
+        self.new_session_impl(remote, session_id, None)
+    }
+
+    /// Creates the session executing the payload of a user transaction. If the transaction
+    /// context extension is enabled, the transaction is exposed to Move code through the
+    /// transaction context natives.
+    pub fn new_user_session<'r, S: MoveResolverExt>(
+        &self,
+        remote: &'r S,
+        txn_data: &TransactionMetadata,
+        payload: &TransactionPayload,
+    ) -> SessionExt<'r, '_> {
+        let user_transaction_context_opt = self
+            .features
+            .is_enabled(FeatureFlag::TRANSACTION_CONTEXT_EXTENSION)
+            .then(|| txn_data.as_user_transaction_context(payload));
+        self.new_session_impl(
+            remote,
+            SessionId::txn_meta(txn_data),
+            user_transaction_context_opt,
+        )
+    }
+
+    fn new_session_impl<'r, S: MoveResolverExt>(
+        &self,
+        remote: &'r S,
+        session_id: SessionId,
+        user_transaction_context_opt: Option<UserTransactionContext>,
     ) -> SessionExt<'r, '_> {
         let mut extensions = NativeContextExtensions::default();
         let txn_hash: [u8; 32] = session_id
@@ -134,9 +168,11 @@ impl MoveVmExt {
             txn_hash.to_vec(),
             script_hash,
             self.chain_id,
+            user_transaction_context_opt,
         ));
         extensions.add(NativeCodeContext::default());
         extensions.add(NativeStateStorageContext::new(remote));
+        extensions.add(NativeEventContext::default());
 
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
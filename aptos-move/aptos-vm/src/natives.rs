@@ -17,7 +17,7 @@ use move_vm_runtime::native_functions::NativeFunctionTable;
 use {
     aptos_framework::natives::{
         aggregator_natives::NativeAggregatorContext, code::NativeCodeContext,
-        cryptography::ristretto255_point::NativeRistrettoPointContext,
+        cryptography::ristretto255_point::NativeRistrettoPointContext, event::NativeEventContext,
         transaction_context::NativeTransactionContext,
     },
     move_vm_runtime::native_extensions::NativeContextExtensions,
@@ -83,7 +83,8 @@ pub fn assert_no_test_natives(err_msg: &str) {
                 || module_name.as_str() == "bls12381"
                     && func_name.as_str() == "generate_proof_of_possession_internal"
                 || module_name.as_str() == "event"
-                    && func_name.as_str() == "emitted_events_internal")
+                    && func_name.as_str() == "emitted_events_internal"
+                || module_name.as_str() == "event" && func_name.as_str() == "emitted_events")
         }),
         "{}",
         err_msg
@@ -105,8 +106,10 @@ fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
         vec![1],
         vec![1],
         ChainId::test().id(),
+        None,
     )); // We use the testing environment chain ID here
     exts.add(NativeAggregatorContext::new([0; 32], &*DUMMY_RESOLVER));
     exts.add(NativeRistrettoPointContext::new());
     exts.add(AlgebraContext::new());
+    exts.add(NativeEventContext::default());
 }
@@ -3,13 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey};
+use aptos_framework::natives::transaction_context::UserTransactionContext;
 use aptos_gas_algebra::{FeePerGasUnit, Gas, NumBytes};
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
-    transaction::{authenticator::AuthenticationKey, SignedTransaction, TransactionPayload},
+    transaction::{
+        authenticator::{AccountAuthenticator, AuthenticationKey, Scheme},
+        SignedTransaction, TransactionPayload,
+    },
 };
-use std::convert::TryFrom;
+use std::{convert::TryFrom, sync::Arc};
 
 pub struct TransactionMetadata {
     pub sender: AccountAddress,
@@ -32,20 +36,20 @@ impl TransactionMetadata {
     pub fn new(txn: &SignedTransaction) -> Self {
         Self {
             sender: txn.sender(),
-            authentication_key: txn.authenticator().sender().authentication_key().to_vec(),
+            authentication_key: prologue_authentication_key(&txn.authenticator().sender()),
             secondary_signers: txn.authenticator().secondary_signer_addreses(),
             secondary_authentication_keys: txn
                 .authenticator()
                 .secondary_signers()
                 .iter()
-                .map(|account_auth| account_auth.authentication_key().to_vec())
+                .map(prologue_authentication_key)
                 .collect(),
             sequence_number: txn.sequence_number(),
             fee_payer: txn.authenticator_ref().fee_payer_address(),
             fee_payer_authentication_key: txn
                 .authenticator()
                 .fee_payer_signer()
-                .map(|signer| signer.authentication_key().to_vec()),
+                .map(|signer| prologue_authentication_key(&signer)),
             max_gas_amount: txn.max_gas_amount().into(),
             gas_unit_price: txn.gas_unit_price().into(),
             transaction_size: (txn.raw_txn_bytes_len() as u64).into(),
@@ -119,6 +123,37 @@ impl TransactionMetadata {
     pub fn is_multi_agent(&self) -> bool {
         !(self.secondary_signers.is_empty() && self.fee_payer.is_none())
     }
+
+    /// Builds the context exposed to the transaction context natives, with the entry function
+    /// of `payload` if any.
+    pub fn as_user_transaction_context(
+        &self,
+        payload: &TransactionPayload,
+    ) -> UserTransactionContext {
+        let entry_function_payload = match payload {
+            TransactionPayload::EntryFunction(entry_function) => {
+                Some(Arc::new(entry_function.clone()))
+            },
+            _ => None,
+        };
+        UserTransactionContext::new(
+            self.sender,
+            self.secondary_signers.clone(),
+            self.fee_payer.unwrap_or(self.sender),
+            self.max_gas_amount.into(),
+            self.gas_unit_price.into(),
+            entry_function_payload,
+        )
+    }
+}
+
+/// The authentication key of a signer checked by the prologue. It is empty for signers using
+/// account abstraction, whose authentication function is run by the VM instead.
+fn prologue_authentication_key(account_auth: &AccountAuthenticator) -> Vec<u8> {
+    match account_auth.scheme() {
+        Scheme::Abstraction => vec![],
+        _ => account_auth.authentication_key().to_vec(),
+    }
 }
 
 impl Default for TransactionMetadata {
@@ -0,0 +1,122 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_framework::{get_metadata_from_compiled_module, KnownAttribute};
+use move_binary_format::{
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    errors::{Location, PartialVMError, VMError, VMResult},
+    file_format::{Bytecode, CompiledScript, FunctionInstantiationIndex, SignatureToken},
+    CompiledModule,
+};
+use move_core_types::{
+    account_address::AccountAddress, ident_str, identifier::IdentStr, vm_status::StatusCode,
+};
+use std::collections::BTreeSet;
+
+const EVENT_MODULE_NAME: &IdentStr = ident_str!("event");
+const EMIT_FUNCTION_NAME: &IdentStr = ident_str!("emit");
+
+fn metadata_validation_error(msg: &str) -> VMError {
+    PartialVMError::new(StatusCode::CONSTRAINT_NOT_SATISFIED)
+        .with_message(format!("metadata and code bundle mismatch: {}", msg))
+        .finish(Location::Undefined)
+}
+
+/// Validate the module events of the published modules. Module events can only be emitted
+/// by the module defining them, i.e., each call to `0x1::event::emit<T>` must instantiate
+/// `T` with a struct defined in the calling module that has the `#[event]` attribute.
+pub(crate) fn validate_module_events(modules: &[CompiledModule]) -> VMResult<()> {
+    for module in modules {
+        let event_structs = extract_event_structs(module);
+        let view = BinaryIndexedView::Module(module);
+        for function_def in module.function_defs() {
+            if let Some(code) = &function_def.code {
+                validate_emit_calls(&view, &code.code, &event_structs)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Scripts cannot define structs, so they cannot emit module events.
+pub(crate) fn verify_no_event_emission_in_script(script_code: &[u8]) -> VMResult<()> {
+    let script =
+        CompiledScript::deserialize(script_code).map_err(|err| err.finish(Location::Script))?;
+    validate_emit_calls(
+        &BinaryIndexedView::Script(&script),
+        &script.code.code,
+        &BTreeSet::new(),
+    )
+}
+
+/// Returns the names of the structs of the module that have the `#[event]` attribute.
+fn extract_event_structs(module: &CompiledModule) -> BTreeSet<String> {
+    get_metadata_from_compiled_module(module)
+        .map(|metadata| {
+            metadata
+                .struct_attributes
+                .into_iter()
+                .filter(|(_, attrs)| attrs.iter().any(KnownAttribute::is_event))
+                .map(|(struct_name, _)| struct_name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn validate_emit_calls(
+    view: &BinaryIndexedView,
+    code: &[Bytecode],
+    event_structs: &BTreeSet<String>,
+) -> VMResult<()> {
+    for bytecode in code {
+        if let Bytecode::CallGeneric(idx) = bytecode {
+            if is_emit_call(view, *idx) {
+                validate_emit_call(view, *idx, event_structs)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_emit_call(view: &BinaryIndexedView, idx: FunctionInstantiationIndex) -> bool {
+    let function_handle = view.function_handle_at(view.function_instantiation_at(idx).handle);
+    let module_handle = view.module_handle_at(function_handle.module);
+    view.address_identifier_at(module_handle.address) == &AccountAddress::ONE
+        && view.identifier_at(module_handle.name) == EVENT_MODULE_NAME
+        && view.identifier_at(function_handle.name) == EMIT_FUNCTION_NAME
+}
+
+fn validate_emit_call(
+    view: &BinaryIndexedView,
+    idx: FunctionInstantiationIndex,
+    event_structs: &BTreeSet<String>,
+) -> VMResult<()> {
+    let type_params = view.signature_at(view.function_instantiation_at(idx).type_parameters);
+    let struct_handle_idx = match type_params.0.first() {
+        Some(SignatureToken::Struct(idx)) | Some(SignatureToken::StructInstantiation(idx, _)) => {
+            *idx
+        },
+        _ => {
+            return Err(metadata_validation_error(
+                "event::emit must be called with a struct type",
+            ))
+        },
+    };
+
+    let struct_handle = view.struct_handle_at(struct_handle_idx);
+    let struct_name = view.identifier_at(struct_handle.name);
+    if view.self_handle_idx() != Some(struct_handle.module) {
+        return Err(metadata_validation_error(&format!(
+            "event::emit can only be called with a struct defined in the calling module: {}",
+            struct_name
+        )));
+    }
+    if !event_structs.contains(struct_name.as_str()) {
+        return Err(metadata_validation_error(&format!(
+            "missing #[event] attribute on struct: {}",
+            struct_name
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::Mutex;
+use aptos_mvhashmap::types::TxnIndex;
+use crossbeam::utils::CachePadded;
+use dashmap::DashMap;
+use std::{
+    fmt,
+    fmt::Debug,
+    hash::Hash,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Records, for a single block executed in parallel, which transactions were re-executed, which
+/// keys caused validation failures and which read dependencies made transactions wait. Recording
+/// is opt-in, as it adds synchronization on the hot path of execution and validation.
+pub struct ConflictProfiler<K> {
+    executions: Vec<CachePadded<AtomicUsize>>,
+    // Txn index -> lower txn indices it had to wait for during execution.
+    dependencies: Vec<CachePadded<Mutex<Vec<TxnIndex>>>>,
+    key_conflicts: DashMap<K, KeyConflicts>,
+}
+
+/// Conflicts attributed to a single key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct KeyConflicts {
+    /// Number of aborts caused by a failed validation of a read of the key.
+    pub validation_failures: usize,
+    /// Number of times an execution had to wait for a pending write to the key.
+    pub dependency_waits: usize,
+}
+
+impl KeyConflicts {
+    pub fn total(&self) -> usize {
+        self.validation_failures + self.dependency_waits
+    }
+}
+
+/// Summary of the conflicts encountered while executing a block.
+#[derive(Clone, Debug)]
+pub struct BlockConflictReport<K> {
+    pub num_txns: usize,
+    pub num_executions: usize,
+    /// Keys ordered by the number of conflicts they caused, most conflicting first.
+    pub hot_keys: Vec<(K, KeyConflicts)>,
+    /// Transactions executed more than once with their number of re-executions, most
+    /// re-executed first.
+    pub reexecutions: Vec<(TxnIndex, usize)>,
+    /// The longest chain of transactions where each waited on a write of the previous one,
+    /// in index order. Such a chain is executed sequentially and bounds the available
+    /// parallelism. Empty if no transaction had to wait.
+    pub longest_dependency_chain: Vec<TxnIndex>,
+}
+
+impl<K: Clone + Debug + Hash + Eq> ConflictProfiler<K> {
+    pub fn new(num_txns: usize) -> Self {
+        Self {
+            executions: (0..num_txns)
+                .map(|_| CachePadded::new(AtomicUsize::new(0)))
+                .collect(),
+            dependencies: (0..num_txns)
+                .map(|_| CachePadded::new(Mutex::new(Vec::new())))
+                .collect(),
+            key_conflicts: DashMap::new(),
+        }
+    }
+
+    /// Records that an incarnation of txn_idx was executed.
+    pub fn record_execution(&self, txn_idx: TxnIndex) {
+        self.executions[txn_idx as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records that a transaction was aborted because the validation of its read of key failed.
+    pub fn record_validation_failure(&self, key: &K) {
+        self.key_conflicts
+            .entry(key.clone())
+            .or_default()
+            .validation_failures += 1;
+    }
+
+    /// Records that the execution of txn_idx had to wait for dep_idx to write key.
+    pub fn record_dependency(&self, txn_idx: TxnIndex, dep_idx: TxnIndex, key: &K) {
+        self.dependencies[txn_idx as usize].lock().push(dep_idx);
+        self.key_conflicts
+            .entry(key.clone())
+            .or_default()
+            .dependency_waits += 1;
+    }
+
+    /// Summarizes the recorded conflicts, keeping at most max_entries hot keys and re-executed
+    /// transactions.
+    pub fn report(&self, max_entries: usize) -> BlockConflictReport<K> {
+        let executions: Vec<usize> = self
+            .executions
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .collect();
+
+        let mut hot_keys: Vec<(K, KeyConflicts)> = self
+            .key_conflicts
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect();
+        hot_keys.sort_by(|(_, a), (_, b)| b.total().cmp(&a.total()));
+        hot_keys.truncate(max_entries);
+
+        let mut reexecutions: Vec<(TxnIndex, usize)> = executions
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 1)
+            .map(|(idx, count)| (idx as TxnIndex, count - 1))
+            .collect();
+        reexecutions.sort_by(|(idx_a, a), (idx_b, b)| b.cmp(a).then(idx_a.cmp(idx_b)));
+        reexecutions.truncate(max_entries);
+
+        BlockConflictReport {
+            num_txns: executions.len(),
+            num_executions: executions.iter().sum(),
+            hot_keys,
+            reexecutions,
+            longest_dependency_chain: self.longest_dependency_chain(),
+        }
+    }
+
+    fn longest_dependency_chain(&self) -> Vec<TxnIndex> {
+        // Dependencies always point to lower indices, so processing transactions in index order
+        // visits every transaction after all of its dependencies.
+        let num_txns = self.dependencies.len();
+        let mut chain_len = vec![1usize; num_txns];
+        let mut predecessor: Vec<Option<TxnIndex>> = vec![None; num_txns];
+        for idx in 0..num_txns {
+            for dep_idx in self.dependencies[idx].lock().iter() {
+                if chain_len[*dep_idx as usize] + 1 > chain_len[idx] {
+                    chain_len[idx] = chain_len[*dep_idx as usize] + 1;
+                    predecessor[idx] = Some(*dep_idx);
+                }
+            }
+        }
+
+        let mut chain = Vec::new();
+        let mut next = (0..num_txns)
+            .max_by_key(|idx| chain_len[*idx])
+            .filter(|idx| chain_len[*idx] > 1)
+            .map(|idx| idx as TxnIndex);
+        while let Some(idx) = next {
+            chain.push(idx);
+            next = predecessor[idx as usize];
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+impl<K: Debug> fmt::Display for BlockConflictReport<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} executions for {} transactions",
+            self.num_executions, self.num_txns
+        )?;
+        writeln!(f, "hot keys:")?;
+        for (key, conflicts) in &self.hot_keys {
+            writeln!(
+                f,
+                "  {:?}: {} validation failures, {} dependency waits",
+                key, conflicts.validation_failures, conflicts.dependency_waits
+            )?;
+        }
+        writeln!(f, "re-executed transactions:")?;
+        for (txn_idx, count) in &self.reexecutions {
+            writeln!(f, "  {}: {} re-executions", txn_idx, count)?;
+        }
+        write!(
+            f,
+            "longest dependency chain ({}): {:?}",
+            self.longest_dependency_chain.len(),
+            self.longest_dependency_chain
+        )
+    }
+}
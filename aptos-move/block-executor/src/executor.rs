@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    conflict_profiler::ConflictProfiler,
     counters,
     counters::{
         PARALLEL_EXECUTION_SECONDS, RAYON_EXECUTION_SECONDS, TASK_EXECUTE_SECONDS,
@@ -36,6 +37,9 @@ use std::{
     },
 };
 
+/// Maximum number of hot keys and re-executed transactions logged per block when profiling.
+const MAX_PROFILED_CONFLICTS: usize = 10;
+
 struct CommitGuard<'a> {
     post_commit_txs: &'a Vec<Sender<u32>>,
     worker_idx: usize,
@@ -75,6 +79,7 @@ pub struct BlockExecutor<T, E, S, L, X> {
     executor_thread_pool: Arc<ThreadPool>,
     maybe_block_gas_limit: Option<u64>,
     transaction_commit_hook: Option<L>,
+    conflict_profiling: bool,
     phantom: PhantomData<(T, E, S, L, X)>,
 }
 
@@ -104,10 +109,19 @@ where
             executor_thread_pool,
             maybe_block_gas_limit,
             transaction_commit_hook,
+            conflict_profiling: false,
             phantom: PhantomData,
         }
     }
 
+    /// Enables recording the conflicts of every block executed in parallel, i.e. the keys that
+    /// failed validation or caused dependency waits, the re-executed transactions and the longest
+    /// dependency chain. The report is logged after each block.
+    pub fn with_conflict_profiling(mut self, conflict_profiling: bool) -> Self {
+        self.conflict_profiling = conflict_profiling;
+        self
+    }
+
     fn execute(
         &self,
         version: Version,
@@ -117,12 +131,16 @@ where
         scheduler: &Scheduler,
         executor: &E,
         base_view: &S,
+        conflict_profiler: Option<&ConflictProfiler<T::Key>>,
     ) -> SchedulerTask {
         let _timer = TASK_EXECUTE_SECONDS.start_timer();
         let (idx_to_execute, incarnation) = version;
         let txn = &signature_verified_block[idx_to_execute as usize];
 
-        let speculative_view = MVHashMapView::new(versioned_cache, scheduler);
+        if let Some(profiler) = conflict_profiler {
+            profiler.record_execution(idx_to_execute);
+        }
+        let speculative_view = MVHashMapView::new(versioned_cache, scheduler, conflict_profiler);
 
         // VM execution.
         let execute_result = executor.execute_transaction(
@@ -199,6 +217,7 @@ where
         last_input_output: &TxnLastInputOutput<T::Key, E::Output, E::Error>,
         versioned_cache: &MVHashMap<T::Key, T::Value, X>,
         scheduler: &Scheduler,
+        conflict_profiler: Option<&ConflictProfiler<T::Key>>,
     ) -> SchedulerTask {
         use MVDataError::*;
         use MVDataOutput::*;
@@ -209,8 +228,8 @@ where
             .read_set(idx_to_validate)
             .expect("[BlockSTM]: Prior read-set must be recorded");
 
-        let valid = read_set.iter().all(|r| {
-            match versioned_cache.fetch_data(r.path(), idx_to_validate) {
+        let invalid_read = read_set.iter().find(|r| {
+            let valid = match versioned_cache.fetch_data(r.path(), idx_to_validate) {
                 Ok(Versioned(version, _)) => r.validate_version(version),
                 Ok(Resolved(value)) => r.validate_resolved(value),
                 // Dependency implies a validation failure, and if the original read were to
@@ -225,13 +244,17 @@ where
                 // materializing deltas as writes in the final output preparation state. Panic
                 // is also preferable as it allows testing for this scenario.
                 Err(DeltaApplicationFailure) => r.validate_delta_application_failure(),
-            }
+            };
+            !valid
         });
 
-        let aborted = !valid && scheduler.try_abort(idx_to_validate, incarnation);
+        let aborted = invalid_read.is_some() && scheduler.try_abort(idx_to_validate, incarnation);
 
         if aborted {
             counters::SPECULATIVE_ABORT_COUNT.inc();
+            if let (Some(profiler), Some(read)) = (conflict_profiler, invalid_read) {
+                profiler.record_validation_failure(read.path());
+            }
 
             // Any logs from the aborted execution should be cleared and not reported.
             clear_speculative_txn_logs(idx_to_validate as usize);
@@ -406,6 +429,7 @@ where
         scheduler: &Scheduler,
         base_view: &S,
         role: CommitRole,
+        conflict_profiler: Option<&ConflictProfiler<T::Key>>,
     ) {
         // Make executor for each task. TODO: fast concurrent executor.
         let init_timer = VM_INIT_SECONDS.start_timer();
@@ -454,6 +478,7 @@ where
                     last_input_output,
                     versioned_cache,
                     scheduler,
+                    conflict_profiler,
                 ),
                 SchedulerTask::ExecutionTask(version_to_execute, ExecutionTaskType::Execution) => {
                     self.execute(
@@ -464,6 +489,7 @@ where
                         scheduler,
                         &executor,
                         base_view,
+                        conflict_profiler,
                     )
                 },
                 SchedulerTask::ExecutionTask(_, ExecutionTaskType::Wakeup(condvar)) => {
@@ -517,6 +543,9 @@ where
         let num_txns = signature_verified_block.len() as u32;
         let last_input_output = TxnLastInputOutput::new(num_txns);
         let scheduler = Scheduler::new(num_txns);
+        let conflict_profiler = self
+            .conflict_profiling
+            .then(|| ConflictProfiler::new(num_txns as usize));
 
         let mut roles: Vec<CommitRole> = vec![];
         let mut senders: Vec<Sender<u32>> = Vec::with_capacity(self.concurrency_level - 1);
@@ -545,12 +574,20 @@ where
                         &scheduler,
                         base_view,
                         role,
+                        conflict_profiler.as_ref(),
                     );
                 });
             }
         });
         drop(timer);
 
+        if let Some(profiler) = &conflict_profiler {
+            info!(
+                "[BlockSTM]: conflict profile\n{}",
+                profiler.report(MAX_PROFILED_CONFLICTS)
+            );
+        }
+
         let num_txns = num_txns as usize;
         // TODO: for large block sizes and many cores, extract outputs in parallel.
         let mut final_results = Vec::with_capacity(num_txns);
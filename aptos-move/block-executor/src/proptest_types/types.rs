@@ -631,10 +631,9 @@ pub struct MockEvent {
 }
 
 impl ReadWriteEvent for MockEvent {
-    fn get_event_data(&self) -> (EventKey, u64, &TypeTag, &[u8]) {
+    fn get_event_data(&self) -> (Option<(EventKey, u64)>, &TypeTag, &[u8]) {
         (
-            self.key,
-            self.sequence_number,
+            Some((self.key, self.sequence_number)),
             &self.type_tag,
             &self.event_data,
         )
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    conflict_profiler::{ConflictProfiler, KeyConflicts},
     executor::BlockExecutor,
     proptest_types::{
         baseline::BaselineOutput,
@@ -906,3 +907,37 @@ fn no_conflict_task_count() {
         assert!(matches!(s.next_task(false), SchedulerTask::Done));
     }
 }
+
+#[test]
+fn conflict_profiler_report() {
+    let profiler = ConflictProfiler::<u32>::new(5);
+    for idx in [0, 1, 1, 2, 3, 3, 3, 4] {
+        profiler.record_execution(idx);
+    }
+    // 4 -> 3 -> 1 -> 0 is the longest chain, 2 -> 0 is a shorter one.
+    profiler.record_dependency(1, 0, &7);
+    profiler.record_dependency(2, 0, &7);
+    profiler.record_dependency(3, 1, &7);
+    profiler.record_dependency(4, 3, &9);
+    profiler.record_validation_failure(&7);
+    profiler.record_validation_failure(&8);
+
+    let report = profiler.report(2);
+    assert_eq!(report.num_txns, 5);
+    assert_eq!(report.num_executions, 8);
+    assert_eq!(
+        report.hot_keys[0],
+        (7, KeyConflicts {
+            validation_failures: 1,
+            dependency_waits: 3,
+        })
+    );
+    assert_eq!(report.hot_keys.len(), 2);
+    assert_eq!(report.reexecutions, vec![(3, 2), (1, 1)]);
+    assert_eq!(report.longest_dependency_chain, vec![0, 1, 3, 4]);
+
+    let no_conflicts = ConflictProfiler::<u32>::new(3).report(2);
+    assert!(no_conflicts.hot_keys.is_empty());
+    assert!(no_conflicts.reexecutions.is_empty());
+    assert!(no_conflicts.longest_dependency_chain.is_empty());
+}
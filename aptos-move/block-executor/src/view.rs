@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    conflict_profiler::ConflictProfiler,
     counters,
     scheduler::{DependencyResult, DependencyStatus, Scheduler},
     task::Transaction,
@@ -35,6 +36,7 @@ use std::{cell::RefCell, fmt::Debug, hash::Hash, sync::Arc};
 pub(crate) struct MVHashMapView<'a, K, V: TransactionWrite, X: Executable> {
     versioned_map: &'a MVHashMap<K, V, X>,
     scheduler: &'a Scheduler,
+    conflict_profiler: Option<&'a ConflictProfiler<K>>,
     captured_reads: RefCell<Vec<ReadDescriptor<K>>>,
 }
 
@@ -61,10 +63,15 @@ impl<
         X: Executable,
     > MVHashMapView<'a, K, V, X>
 {
-    pub(crate) fn new(versioned_map: &'a MVHashMap<K, V, X>, scheduler: &'a Scheduler) -> Self {
+    pub(crate) fn new(
+        versioned_map: &'a MVHashMap<K, V, X>,
+        scheduler: &'a Scheduler,
+        conflict_profiler: Option<&'a ConflictProfiler<K>>,
+    ) -> Self {
         Self {
             versioned_map,
             scheduler,
+            conflict_profiler,
             captured_reads: RefCell::new(Vec::new()),
         }
     }
@@ -125,6 +132,9 @@ impl<
                     // `self.txn_idx` estimated to depend on a write from `dep_idx`.
                     match self.scheduler.wait_for_dependency(txn_idx, dep_idx) {
                         DependencyResult::Dependency(dep_condition) => {
+                            if let Some(profiler) = self.conflict_profiler {
+                                profiler.record_dependency(txn_idx, dep_idx, key);
+                            }
                             let _timer = counters::DEPENDENCY_WAIT_SECONDS.start_timer();
                             // Wait on a condition variable corresponding to the encountered
                             // read dependency. Once the dep_idx finishes re-execution, scheduler
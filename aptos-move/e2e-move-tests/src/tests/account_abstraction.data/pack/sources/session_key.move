@@ -0,0 +1,49 @@
+/// Lets an account authenticate its transactions with an Ed25519 session key, through account
+/// abstraction.
+module 0xcafe::session_key {
+    use std::signer;
+    use std::string::utf8;
+    use aptos_std::ed25519;
+    use aptos_framework::account_abstraction;
+
+    /// The signature was not made by the session key of the account.
+    const EINVALID_SIGNATURE: u64 = 1;
+
+    struct SessionKey has key {
+        public_key: vector<u8>,
+    }
+
+    public entry fun register(account: &signer, public_key: vector<u8>) {
+        move_to(account, SessionKey { public_key });
+        account_abstraction::add_authentication_function(
+            account,
+            @0xcafe,
+            utf8(b"session_key"),
+            utf8(b"authenticate"),
+        );
+    }
+
+    /// Registers `authenticate_private`, which the VM refuses to call since it is not public.
+    public entry fun register_private(account: &signer) {
+        account_abstraction::add_authentication_function(
+            account,
+            @0xcafe,
+            utf8(b"session_key"),
+            utf8(b"authenticate_private"),
+        );
+    }
+
+    public fun authenticate(account: &signer, signature: vector<u8>, digest: vector<u8>) acquires SessionKey {
+        let public_key = borrow_global<SessionKey>(signer::address_of(account)).public_key;
+        assert!(
+            ed25519::signature_verify_strict(
+                &ed25519::new_signature_from_bytes(signature),
+                &ed25519::new_unvalidated_public_key_from_bytes(public_key),
+                digest,
+            ),
+            EINVALID_SIGNATURE,
+        );
+    }
+
+    fun authenticate_private(_account: &signer, _signature: vector<u8>, _digest: vector<u8>) {}
+}
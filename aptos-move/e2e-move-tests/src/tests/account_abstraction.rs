@@ -0,0 +1,117 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, tests::common, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, Uniform};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::CORE_CODE_ADDRESS,
+    on_chain_config::FeatureFlag,
+    transaction::{SignedTransaction, TransactionStatus},
+};
+use move_core_types::vm_status::StatusCode;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Signs a transfer from the account with `signer`, which is passed as the opaque signature of an
+/// abstraction authenticator.
+fn transfer_txn(
+    h: &mut MoveHarness,
+    account: &Account,
+    signer: &Ed25519PrivateKey,
+) -> SignedTransaction {
+    let raw_txn = account
+        .transaction()
+        .sequence_number(h.sequence_number(account.address()))
+        .max_gas_amount(2_000_000)
+        .gas_unit_price(100)
+        .payload(aptos_stdlib::aptos_coin_transfer(CORE_CODE_ADDRESS, 1))
+        .raw();
+    let digest = HashValue::sha3_256_of(&raw_txn.signing_message().unwrap());
+    let signature = signer.sign_arbitrary_message(digest.as_ref());
+    SignedTransaction::new_abstraction(raw_txn, signature.to_bytes().to_vec())
+}
+
+#[test]
+fn test_account_abstraction_session_key() {
+    let mut h = MoveHarness::new_with_features(vec![], vec![FeatureFlag::ACCOUNT_ABSTRACTION]);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let session_key = Ed25519PrivateKey::generate(&mut rng);
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    // Abstraction authenticators are rejected while the feature is disabled.
+    let txn = transfer_txn(&mut h, &account, &session_key);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+
+    h.enable_features(vec![FeatureFlag::ACCOUNT_ABSTRACTION], vec![]);
+
+    // The account has not registered an authentication function yet.
+    let txn = transfer_txn(&mut h, &account, &session_key);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY)
+    );
+
+    assert_success!(h.publish_package(
+        &account,
+        &common::test_dir_path("account_abstraction.data/pack")
+    ));
+    assert_success!(h.run_entry_function(
+        &account,
+        str::parse("0xcafe::session_key::register").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&session_key.public_key().to_bytes().to_vec()).unwrap()],
+    ));
+
+    let txn = transfer_txn(&mut h, &account, &session_key);
+    assert_success!(h.run(txn));
+
+    // The authentication function rejects signatures by other keys, and the transaction is
+    // discarded without being charged.
+    let other_key = Ed25519PrivateKey::generate(&mut rng);
+    let sequence_number = h.sequence_number(account.address());
+    let txn = transfer_txn(&mut h, &account, &other_key);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE)
+    );
+    assert_eq!(h.sequence_number(account.address()), sequence_number);
+
+    // The authentication key of the account keeps working.
+    assert_success!(h.run_transaction_payload(
+        &account,
+        aptos_stdlib::aptos_coin_transfer(CORE_CODE_ADDRESS, 1)
+    ));
+}
+
+#[test]
+fn test_account_abstraction_private_function() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::ACCOUNT_ABSTRACTION], vec![]);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let session_key = Ed25519PrivateKey::generate(&mut rng);
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    assert_success!(h.publish_package(
+        &account,
+        &common::test_dir_path("account_abstraction.data/pack")
+    ));
+    assert_success!(h.run_entry_function(
+        &account,
+        str::parse("0xcafe::session_key::register_private").unwrap(),
+        vec![],
+        vec![],
+    ));
+
+    // Private functions cannot authenticate transactions.
+    let sequence_number = h.sequence_number(account.address());
+    let txn = transfer_txn(&mut h, &account, &session_key);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE)
+    );
+    assert_eq!(h.sequence_number(account.address()), sequence_number);
+}
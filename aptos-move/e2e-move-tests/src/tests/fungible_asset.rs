@@ -1,10 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{assert_success, tests::common, MoveHarness};
+use crate::{assert_abort, assert_success, tests::common, MoveHarness};
+use aptos_sdk::fungible_asset_client::FungibleAssetClient;
 use aptos_types::account_address::{self, AccountAddress};
-use move_core_types::{identifier::Identifier, language_storage::StructTag};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+};
 use serde::Deserialize;
+use std::str::FromStr;
 
 #[derive(Debug, Deserialize, Eq, PartialEq)]
 struct FungibleStore {
@@ -149,3 +154,127 @@ fn test_basic_fungible_token() {
     alice_store.balance = 10;
     assert_eq!(alice_store, bob_store);
 }
+
+fn primary_store_view<T: serde::de::DeserializeOwned>(
+    h: &mut MoveHarness,
+    function: &str,
+    owner: AccountAddress,
+    metadata: AccountAddress,
+) -> T {
+    let value = h
+        .execute_view_function(
+            str::parse(&format!("0x1::primary_fungible_store::{}", function)).unwrap(),
+            vec![TypeTag::from_str("0x1::fungible_asset::Metadata").unwrap()],
+            vec![
+                bcs::to_bytes(&owner).unwrap(),
+                bcs::to_bytes(&metadata).unwrap(),
+            ],
+        )
+        .unwrap()
+        .pop()
+        .unwrap();
+    bcs::from_bytes(&value).unwrap()
+}
+
+#[test]
+fn test_fungible_asset_client_payloads() {
+    let mut h = MoveHarness::new();
+
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xface").unwrap());
+
+    let mut build_options = aptos_framework::BuildOptions::default();
+    build_options
+        .named_addresses
+        .insert("example_addr".to_string(), *alice.address());
+    let result = h.publish_package_with_options(
+        &alice,
+        &common::test_dir_path("../../../move-examples/fungible_asset/managed_fungible_asset"),
+        build_options,
+    );
+    assert_success!(result);
+
+    let metadata = h
+        .execute_view_function(
+            str::parse(&format!(
+                "0x{}::coin_example::get_metadata",
+                *alice.address()
+            ))
+            .unwrap(),
+            vec![],
+            vec![],
+        )
+        .unwrap()
+        .pop()
+        .unwrap();
+    let metadata = bcs::from_bytes::<AccountAddress>(metadata.as_slice()).unwrap();
+
+    let result = h.run_entry_function(
+        &alice,
+        str::parse(&format!("0x{}::coin_example::mint", *alice.address())).unwrap(),
+        vec![],
+        vec![
+            bcs::to_bytes(alice.address()).unwrap(),
+            bcs::to_bytes(&100u64).unwrap(),
+        ],
+    );
+    assert_success!(result);
+
+    // Transfer between primary stores, creating the primary store of bob
+    let result = h.run_transaction_payload(
+        &alice,
+        FungibleAssetClient::primary_store_transfer_payload(metadata, *bob.address(), 30),
+    );
+    assert_success!(result);
+    let balance =
+        |h: &mut MoveHarness, owner| primary_store_view::<u64>(h, "balance", owner, metadata);
+    assert_eq!(balance(&mut h, *alice.address()), 70);
+    assert_eq!(balance(&mut h, *bob.address()), 30);
+
+    // The primary store addresses are derived locally
+    let alice_store = FungibleAssetClient::primary_store_address(*alice.address(), metadata);
+    let bob_store = FungibleAssetClient::primary_store_address(*bob.address(), metadata);
+    let expected_store: AccountAddress =
+        primary_store_view(&mut h, "primary_store_address", *bob.address(), metadata);
+    assert_eq!(bob_store, expected_store);
+
+    // Transfer between stores
+    let result = h.run_transaction_payload(
+        &bob,
+        FungibleAssetClient::store_transfer_payload(bob_store, alice_store, 10),
+    );
+    assert_success!(result);
+    assert_eq!(balance(&mut h, *alice.address()), 80);
+    assert_eq!(balance(&mut h, *bob.address()), 20);
+
+    // Frozen stores can't be transferred from
+    let result = h.run_entry_function(
+        &alice,
+        str::parse(&format!(
+            "0x{}::coin_example::freeze_account",
+            *alice.address()
+        ))
+        .unwrap(),
+        vec![],
+        vec![bcs::to_bytes(bob.address()).unwrap()],
+    );
+    assert_success!(result);
+    assert!(primary_store_view::<bool>(
+        &mut h,
+        "is_frozen",
+        *bob.address(),
+        metadata
+    ));
+    assert!(!primary_store_view::<bool>(
+        &mut h,
+        "is_frozen",
+        *alice.address(),
+        metadata
+    ));
+    let result = h.run_transaction_payload(
+        &bob,
+        FungibleAssetClient::primary_store_transfer_payload(metadata, *alice.address(), 10),
+    );
+    assert_abort!(result, _);
+    assert_eq!(balance(&mut h, *bob.address()), 20);
+}
@@ -42,6 +42,14 @@ fn save_profiling_results(name: &str, log: &TransactionGasLog) {
     erased.storage.textualize(&mut text, true).unwrap();
 
     fs::write(path.join("log.txt"), text).unwrap();
+
+    let mut report = String::new();
+    erased
+        .to_html_report(&mut report, name, &log.exec_io.aggregate_gas_events())
+        .unwrap();
+    fs::write(path.join("report.html"), report).unwrap();
+    fs::write(path.join("speedscope.json"), erased.to_speedscope(name)).unwrap();
+    fs::write(path.join("trace.json"), erased.to_chrome_trace()).unwrap();
 }
 
 /// Run with `cargo test test_gas -- --nocapture` to see output.
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod access_path_test;
+mod account_abstraction;
 mod aggregator;
 mod attributes;
 mod chain_id;
@@ -22,6 +23,7 @@ mod memory_quota;
 mod metadata;
 mod mint_nft;
 mod missing_gas_parameter;
+mod module_event;
 mod new_integer_types;
 mod nft_dao;
 mod offer_rotation_capability;
@@ -30,6 +32,7 @@ mod per_category_gas_limits;
 mod resource_groups;
 mod rotate_auth_key;
 mod scripts;
+mod secp256k1_ecdsa;
 mod simple_defi;
 mod smart_data_structures;
 mod stake;
@@ -38,7 +41,9 @@ mod string_args;
 mod token_event_store;
 mod token_objects;
 mod too_large;
+mod transaction_context;
 mod transaction_fee;
 mod type_too_large;
 mod vector_numeric_address;
 mod vote;
+mod webauthn;
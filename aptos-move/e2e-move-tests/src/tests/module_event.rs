@@ -0,0 +1,107 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, assert_vm_status, tests::common, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_framework::{BuildOptions, BuiltPackage};
+use aptos_language_e2e_tests::account::Account;
+use aptos_package_builder::PackageBuilder;
+use aptos_types::{account_address::AccountAddress, on_chain_config::FeatureFlag};
+use move_binary_format::CompiledModule;
+use move_core_types::{metadata::Metadata, vm_status::StatusCode};
+use tempfile::TempDir;
+
+const EVENT_SOURCE: &str = r#"
+    module 0xf00d::M {
+        use aptos_framework::event;
+
+        #[event]
+        struct Event has drop, store { value: u64 }
+
+        public entry fun emit_event(value: u64) {
+            event::emit(Event { value });
+        }
+    }
+"#;
+
+fn setup() -> (MoveHarness, Account) {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::MODULE_EVENT], vec![]);
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xf00d").unwrap());
+    (h, account)
+}
+
+fn write_package(sources: &[(&str, &str)]) -> TempDir {
+    let mut builder = PackageBuilder::new("Package");
+    for (name, source) in sources {
+        builder.add_source(name, source);
+    }
+    let framework = common::framework_dir_path("aptos-framework");
+    builder.add_local_dep("AptosFramework", &framework.display().to_string());
+    builder.write_to_temp().unwrap()
+}
+
+#[test]
+fn test_emit_module_event() {
+    let (mut h, account) = setup();
+    let path = write_package(&[("m.move", EVENT_SOURCE)]);
+    assert_success!(h.publish_package(&account, path.path()));
+    assert_success!(h.run_entry_function(
+        &account,
+        str::parse("0xf00d::M::emit_event").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&1u64).unwrap()],
+    ));
+}
+
+#[test]
+#[should_panic]
+fn test_emit_module_event_of_other_module() {
+    let (mut h, account) = setup();
+    let other_source = r#"
+        module 0xf00d::N {
+            use aptos_framework::event;
+            use 0xf00d::M;
+
+            public entry fun emit_event() {
+                event::emit(M::new_event());
+            }
+        }
+    "#;
+    let source = EVENT_SOURCE.replace(
+        "public entry fun emit_event",
+        "public fun new_event(): Event { Event { value: 0 } }\n\n        public entry fun emit_event",
+    );
+    let path = write_package(&[("m.move", &source), ("n.move", other_source)]);
+    // The extended checks reject emitting the events of another module
+    assert_success!(h.publish_package(&account, path.path()));
+}
+
+#[test]
+fn test_emit_module_event_without_attribute_in_compiled_module() {
+    let (mut h, account) = setup();
+    let path = write_package(&[("m.move", EVENT_SOURCE)]);
+    let package = BuiltPackage::build(path.path().to_path_buf(), BuildOptions::default())
+        .expect("building package must succeed");
+    let code = package.extract_code();
+    assert!(code.len() == 1);
+
+    // Remove the event attribute from the metadata
+    let mut compiled_module = CompiledModule::deserialize(&code[0]).unwrap();
+    let mut value = aptos_framework::get_metadata_from_compiled_module(&compiled_module).unwrap();
+    value.struct_attributes.clear();
+    compiled_module.metadata = vec![Metadata {
+        key: aptos_framework::APTOS_METADATA_KEY_V1.to_vec(),
+        value: bcs::to_bytes(&value).unwrap(),
+    }];
+    let mut code = vec![];
+    compiled_module.serialize(&mut code).unwrap();
+
+    let metadata = package
+        .extract_metadata()
+        .expect("extracting package metadata must succeed");
+    let result = h.run_transaction_payload(
+        &account,
+        aptos_stdlib::code_publish_package_txn(bcs::to_bytes(&metadata).unwrap(), vec![code]),
+    );
+    assert_vm_status!(result, StatusCode::CONSTRAINT_NOT_SATISFIED);
+}
@@ -0,0 +1,80 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::{secp256k1_ecdsa::Secp256k1EcdsaPrivateKey, Uniform};
+use aptos_language_e2e_tests::account::Account;
+use aptos_sdk::types::Secp256k1EcdsaAccount;
+use aptos_types::{
+    account_config::{AccountResource, CORE_CODE_ADDRESS},
+    on_chain_config::FeatureFlag,
+    transaction::{SignedTransaction, TransactionStatus},
+};
+use move_core_types::{parser::parse_struct_tag, vm_status::StatusCode};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Creates an on-chain account for the secp256k1 key, returning an account with the same address
+/// that can be used to build transactions.
+fn create_secp256k1_account(h: &mut MoveHarness, account: &Secp256k1EcdsaAccount) -> Account {
+    let harness_account = h.new_account_at(account.address());
+    let struct_tag = parse_struct_tag("0x1::account::Account").unwrap();
+    let resource = h
+        .read_resource::<AccountResource>(&account.address(), struct_tag.clone())
+        .unwrap();
+    let resource = AccountResource::new(
+        resource.sequence_number(),
+        account.authentication_key().to_vec(),
+        resource.coin_register_events().clone(),
+        resource.key_rotation_events().clone(),
+    );
+    h.set_resource(account.address(), struct_tag, &resource);
+    harness_account
+}
+
+fn transfer_txn(
+    h: &mut MoveHarness,
+    harness_account: &Account,
+    account: &Secp256k1EcdsaAccount,
+) -> SignedTransaction {
+    let raw_txn = harness_account
+        .transaction()
+        .sequence_number(h.sequence_number(harness_account.address()))
+        .max_gas_amount(2_000_000)
+        .gas_unit_price(100)
+        .payload(aptos_stdlib::aptos_coin_transfer(CORE_CODE_ADDRESS, 1))
+        .raw();
+    account.sign_transaction(raw_txn)
+}
+
+#[test]
+fn test_secp256k1_ecdsa_transaction() {
+    let mut h =
+        MoveHarness::new_with_features(vec![], vec![FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR]);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let account = Secp256k1EcdsaAccount::generate(&mut rng);
+    let harness_account = create_secp256k1_account(&mut h, &account);
+
+    // Secp256k1 signatures are rejected while the feature is disabled.
+    let txn = transfer_txn(&mut h, &harness_account, &account);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+
+    h.enable_features(vec![FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR], vec![]);
+    let txn = transfer_txn(&mut h, &harness_account, &account);
+    assert_success!(h.run(txn));
+
+    // The signature must be made by the key the account authenticates with.
+    let other = Secp256k1EcdsaAccount::new(
+        account.address(),
+        Secp256k1EcdsaPrivateKey::generate(&mut rng),
+        0,
+    );
+    let txn = transfer_txn(&mut h, &harness_account, &other);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY)
+    );
+}
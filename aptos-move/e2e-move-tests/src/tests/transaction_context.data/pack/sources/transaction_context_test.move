@@ -0,0 +1,39 @@
+module 0x1::transaction_context_test {
+    use std::option;
+    use std::string::String;
+    use aptos_framework::transaction_context;
+
+    /// Since tests in e2e-move-tests/ can only call entry functions which don't have return values, we must store
+    /// the results we are interested in inside this resource, which we can read back in our e2e-move-tests/ test.
+    struct TransactionContextStore has key {
+        sender: address,
+        secondary_signers: vector<address>,
+        fee_payer: address,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        chain_id: u8,
+        account_address: address,
+        module_name: String,
+        function_name: String,
+        type_arg_names: vector<String>,
+        args: vector<vector<u8>>,
+    }
+
+    /// Stores the context of the transaction calling it, including its own payload.
+    public entry fun store_transaction_context<T>(s: &signer, _arg1: u64, _arg2: vector<u8>) {
+        let payload = option::destroy_some(transaction_context::entry_function_payload());
+        move_to(s, TransactionContextStore {
+            sender: transaction_context::sender(),
+            secondary_signers: transaction_context::secondary_signers(),
+            fee_payer: transaction_context::fee_payer(),
+            max_gas_amount: transaction_context::max_gas_amount(),
+            gas_unit_price: transaction_context::gas_unit_price(),
+            chain_id: transaction_context::chain_id(),
+            account_address: transaction_context::account_address(&payload),
+            module_name: transaction_context::module_name(&payload),
+            function_name: transaction_context::function_name(&payload),
+            type_arg_names: transaction_context::type_arg_names(&payload),
+            args: transaction_context::args(&payload),
+        });
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, tests::common, MoveHarness};
+use aptos_types::account_address::AccountAddress;
+use move_core_types::{language_storage::TypeTag, parser::parse_struct_tag};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TransactionContextStore {
+    sender: AccountAddress,
+    secondary_signers: Vec<AccountAddress>,
+    fee_payer: AccountAddress,
+    max_gas_amount: u64,
+    gas_unit_price: u64,
+    chain_id: u8,
+    account_address: AccountAddress,
+    module_name: String,
+    function_name: String,
+    type_arg_names: Vec<String>,
+    args: Vec<Vec<u8>>,
+}
+
+#[test]
+fn test_transaction_context_entry_function_payload() {
+    let mut harness = MoveHarness::new();
+    let account = harness.new_account_at(AccountAddress::ONE);
+    assert_success!(harness.publish_package(
+        &account,
+        &common::test_dir_path("transaction_context.data/pack")
+    ));
+
+    let args = vec![
+        bcs::to_bytes(&42u64).unwrap(),
+        bcs::to_bytes(&vec![1u8, 2, 3]).unwrap(),
+    ];
+    assert_success!(harness.run_entry_function(
+        &account,
+        str::parse("0x1::transaction_context_test::store_transaction_context").unwrap(),
+        vec![TypeTag::U64],
+        args.clone(),
+    ));
+
+    let store = harness
+        .read_resource::<TransactionContextStore>(
+            account.address(),
+            parse_struct_tag("0x1::transaction_context_test::TransactionContextStore").unwrap(),
+        )
+        .unwrap();
+    assert_eq!(store.sender, *account.address());
+    assert!(store.secondary_signers.is_empty());
+    assert_eq!(store.fee_payer, *account.address());
+    assert_eq!(store.max_gas_amount, 2_000_000);
+    assert_eq!(store.gas_unit_price, 100);
+    assert_eq!(store.chain_id, 4);
+    assert_eq!(store.account_address, AccountAddress::ONE);
+    assert_eq!(store.module_name, "transaction_context_test");
+    assert_eq!(store.function_name, "store_transaction_context");
+    assert_eq!(store.type_arg_names, vec!["u64".to_string()]);
+    assert_eq!(store.args, args);
+}
@@ -0,0 +1,81 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_language_e2e_tests::account::Account;
+use aptos_sdk::types::{LocalPasskey, PasskeyAccount};
+use aptos_types::{
+    account_config::{AccountResource, CORE_CODE_ADDRESS},
+    on_chain_config::FeatureFlag,
+    transaction::{SignedTransaction, TransactionStatus},
+};
+use move_core_types::{parser::parse_struct_tag, vm_status::StatusCode};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Creates an on-chain account for the passkey, returning an account with the same address that
+/// can be used to build transactions.
+fn create_passkey_account(h: &mut MoveHarness, passkey: &PasskeyAccount<LocalPasskey>) -> Account {
+    let account = h.new_account_at(passkey.address());
+    let struct_tag = parse_struct_tag("0x1::account::Account").unwrap();
+    let resource = h
+        .read_resource::<AccountResource>(&passkey.address(), struct_tag.clone())
+        .unwrap();
+    let resource = AccountResource::new(
+        resource.sequence_number(),
+        passkey.authentication_key().to_vec(),
+        resource.coin_register_events().clone(),
+        resource.key_rotation_events().clone(),
+    );
+    h.set_resource(passkey.address(), struct_tag, &resource);
+    account
+}
+
+fn transfer_txn(
+    h: &mut MoveHarness,
+    account: &Account,
+    passkey: &PasskeyAccount<LocalPasskey>,
+) -> SignedTransaction {
+    let raw_txn = account
+        .transaction()
+        .sequence_number(h.sequence_number(account.address()))
+        .max_gas_amount(2_000_000)
+        .gas_unit_price(100)
+        .payload(aptos_stdlib::aptos_coin_transfer(CORE_CODE_ADDRESS, 1))
+        .raw();
+    passkey.sign_transaction(raw_txn).unwrap()
+}
+
+#[test]
+fn test_passkey_transaction() {
+    let mut h = MoveHarness::new_with_features(vec![], vec![FeatureFlag::WEBAUTHN_SIGNATURE]);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let passkey = PasskeyAccount::from_authenticator(LocalPasskey::generate(
+        &mut rng,
+        "wallet.example".to_string(),
+    ));
+    let account = create_passkey_account(&mut h, &passkey);
+
+    // Passkey signatures are rejected while the feature is disabled.
+    let txn = transfer_txn(&mut h, &account, &passkey);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+
+    h.enable_features(vec![FeatureFlag::WEBAUTHN_SIGNATURE], vec![]);
+    let txn = transfer_txn(&mut h, &account, &passkey);
+    assert_success!(h.run(txn));
+
+    // The assertion must be made by the key the account authenticates with.
+    let other = PasskeyAccount::new(
+        passkey.address(),
+        LocalPasskey::generate(&mut rng, "wallet.example".to_string()),
+        0,
+    );
+    let txn = transfer_txn(&mut h, &account, &other);
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::INVALID_AUTH_KEY)
+    );
+}
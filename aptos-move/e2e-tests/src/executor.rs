@@ -612,7 +612,7 @@ impl FakeExecutor {
 
         // Check if we emit the expected event for block metadata, there might be more events for transaction fees.
         let event = outputs[0].events()[0].clone();
-        assert_eq!(event.key(), &new_block_event_key());
+        assert_eq!(event.event_key(), Some(&new_block_event_key()));
         assert!(bcs::from_bytes::<NewBlockEvent>(event.event_data()).is_ok());
 
         let mut results = vec![];
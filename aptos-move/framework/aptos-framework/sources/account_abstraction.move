@@ -0,0 +1,113 @@
+/// Account abstraction lets an account replace the native signature check of its transactions
+/// with an authentication function written in Move, e.g. to support session keys, spending limits
+/// or social recovery.
+///
+/// An account registers a public function with the signature
+/// `public fun authenticate(account: &signer, signature: vector<u8>, digest: vector<u8>)`. The
+/// transactions of the account can then carry an abstraction authenticator with opaque signature
+/// bytes. When validating these transactions, the VM calls the registered function, with a bounded
+/// amount of gas, passing it the signer of the account, these bytes and the sha3-256 digest of the
+/// signing message of the transaction. Changes made by the function are discarded. If the function
+/// aborts, runs out of gas, or is not a public function with this signature, the transaction is
+/// rejected with an invalid signature, and is not charged. Otherwise, the gas used by the function
+/// is charged to the transaction.
+///
+/// Registering a function does not disable the authentication key of the account, which can still
+/// be used to sign transactions.
+module aptos_framework::account_abstraction {
+    use std::error;
+    use std::features;
+    use std::signer;
+    use std::string::String;
+
+    /// The account abstraction feature is not enabled.
+    const EACCOUNT_ABSTRACTION_NOT_ENABLED: u64 = 1;
+    /// The account has not registered an authentication function.
+    const EAUTHENTICATION_FUNCTION_NOT_FOUND: u64 = 2;
+
+    /// The function authenticating the transactions of the account it is stored under.
+    struct AuthenticationFunction has key, drop {
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    }
+
+    /// Authenticate the transactions of `account` with `module_address::module_name::function_name`,
+    /// replacing the function registered before, if any. The function must be public, with the
+    /// signature `(&signer, vector<u8>, vector<u8>)`: the transactions of the account are rejected
+    /// otherwise.
+    public entry fun add_authentication_function(
+        account: &signer,
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    ) acquires AuthenticationFunction {
+        assert!(
+            features::account_abstraction_enabled(),
+            error::invalid_state(EACCOUNT_ABSTRACTION_NOT_ENABLED),
+        );
+        let addr = signer::address_of(account);
+        if (exists<AuthenticationFunction>(addr)) {
+            move_from<AuthenticationFunction>(addr);
+        };
+        move_to(account, AuthenticationFunction { module_address, module_name, function_name });
+    }
+
+    /// Stop authenticating the transactions of `account` with its registered function.
+    public entry fun remove_authentication_function(account: &signer) acquires AuthenticationFunction {
+        let addr = signer::address_of(account);
+        assert!(exists<AuthenticationFunction>(addr), error::not_found(EAUTHENTICATION_FUNCTION_NOT_FOUND));
+        move_from<AuthenticationFunction>(addr);
+    }
+
+    #[view]
+    /// Return whether the account at `addr` has registered an authentication function.
+    public fun using_account_abstraction(addr: address): bool {
+        exists<AuthenticationFunction>(addr)
+    }
+
+    #[view]
+    /// Return the address, module name and function name of the authentication function
+    /// registered by the account at `addr`.
+    public fun authentication_function(addr: address): (address, String, String) acquires AuthenticationFunction {
+        assert!(exists<AuthenticationFunction>(addr), error::not_found(EAUTHENTICATION_FUNCTION_NOT_FOUND));
+        let function = borrow_global<AuthenticationFunction>(addr);
+        (function.module_address, function.module_name, function.function_name)
+    }
+
+    #[test_only]
+    use std::string::utf8;
+
+    #[test(fx = @std, account = @0xcafe)]
+    fun test_add_and_remove_authentication_function(
+        fx: &signer,
+        account: &signer,
+    ) acquires AuthenticationFunction {
+        features::change_feature_flags(fx, vector[features::get_account_abstraction_feature()], vector[]);
+        let addr = signer::address_of(account);
+        assert!(!using_account_abstraction(addr), 0);
+
+        add_authentication_function(account, @0xcafe, utf8(b"auth"), utf8(b"authenticate"));
+        add_authentication_function(account, @0xcafe, utf8(b"auth"), utf8(b"authenticate_v2"));
+        assert!(using_account_abstraction(addr), 1);
+        let (module_address, module_name, function_name) = authentication_function(addr);
+        assert!(module_address == @0xcafe, 2);
+        assert!(module_name == utf8(b"auth"), 3);
+        assert!(function_name == utf8(b"authenticate_v2"), 4);
+
+        remove_authentication_function(account);
+        assert!(!using_account_abstraction(addr), 5);
+    }
+
+    #[test(account = @0xcafe)]
+    #[expected_failure(abort_code = 0x30001, location = Self)]
+    fun test_add_authentication_function_not_enabled(account: &signer) acquires AuthenticationFunction {
+        add_authentication_function(account, @0xcafe, utf8(b"auth"), utf8(b"authenticate"));
+    }
+
+    #[test(account = @0xcafe)]
+    #[expected_failure(abort_code = 0x60002, location = Self)]
+    fun test_remove_missing_authentication_function(account: &signer) acquires AuthenticationFunction {
+        remove_authentication_function(account);
+    }
+}
@@ -0,0 +1,35 @@
+spec aptos_framework::account_abstraction {
+    spec module {
+        pragma verify = true;
+        pragma aborts_if_is_strict;
+    }
+
+    spec add_authentication_function(
+        account: &signer,
+        module_address: address,
+        module_name: String,
+        function_name: String,
+    ) {
+        let addr = signer::address_of(account);
+        aborts_if !features::spec_account_abstraction_enabled();
+        ensures exists<AuthenticationFunction>(addr);
+        ensures global<AuthenticationFunction>(addr).module_address == module_address;
+        ensures global<AuthenticationFunction>(addr).module_name == module_name;
+        ensures global<AuthenticationFunction>(addr).function_name == function_name;
+    }
+
+    spec remove_authentication_function(account: &signer) {
+        let addr = signer::address_of(account);
+        aborts_if !exists<AuthenticationFunction>(addr);
+        ensures !exists<AuthenticationFunction>(addr);
+    }
+
+    spec using_account_abstraction(addr: address): bool {
+        aborts_if false;
+        ensures result == exists<AuthenticationFunction>(addr);
+    }
+
+    spec authentication_function(addr: address): (address, String, String) {
+        aborts_if !exists<AuthenticationFunction>(addr);
+    }
+}
@@ -2,14 +2,32 @@
 /// `EventHandle`s with unique GUIDs. It contains a counter for the number
 /// of `EventHandle`s it generates. An `EventHandle` is used to count the number of
 /// events emitted to a handle and emit events to the event store.
+/// Module events, emitted via `emit`, are not bound to an event handle and are identified
+/// by their type only.
 module aptos_framework::event {
     use std::bcs;
+    use std::error;
+    use std::features;
 
     use aptos_framework::guid::GUID;
 
     friend aptos_framework::account;
     friend aptos_framework::object;
 
+    /// Module events are not supported.
+    const EMODULE_EVENT_NOT_SUPPORTED: u64 = 1;
+
+    /// Emit a module event with payload `msg`. The event is identified by the type `T`, which
+    /// must be a struct with the `#[event]` attribute defined in the calling module. This is
+    /// enforced when the calling module is published.
+    public fun emit<T: store + drop>(msg: T) {
+        assert!(features::module_event_enabled(), error::invalid_state(EMODULE_EVENT_NOT_SUPPORTED));
+        write_module_event_to_store<T>(msg);
+    }
+
+    /// Log `msg` with the event stream identified by `T`
+    native fun write_module_event_to_store<T: drop + store>(msg: T);
+
     /// A handle for an event such that:
     /// 1. Other modules can emit events to this handle.
     /// 2. Storage can use this handle to prove the total number of events that happened in the past.
@@ -55,6 +73,15 @@ module aptos_framework::event {
         EventHandle<T> { counter: _, guid: _ } = handle;
     }
 
+    #[test_only]
+    public native fun emitted_events<T: drop + store>(): vector<T>;
+
+    #[test_only]
+    public fun was_event_emitted<T: drop + store>(msg: &T): bool {
+        use std::vector;
+        vector::contains(&emitted_events<T>(), msg)
+    }
+
     #[test_only]
     public native fun emitted_events_by_handle<T: drop + store>(handle: &EventHandle<T>): vector<T>;
 
@@ -63,4 +90,27 @@ module aptos_framework::event {
         use std::vector;
         vector::contains(&emitted_events_by_handle(handle), msg)
     }
+
+    #[test_only]
+    #[event]
+    struct TestEvent has copy, drop, store {
+        value: u64,
+    }
+
+    #[test(fx = @std)]
+    fun test_emit(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_module_event_feature()], vector[]);
+
+        emit(TestEvent { value: 1 });
+        emit(TestEvent { value: 2 });
+        assert!(emitted_events<TestEvent>() == vector[TestEvent { value: 1 }, TestEvent { value: 2 }], 0);
+        assert!(was_event_emitted(&TestEvent { value: 2 }), 1);
+        assert!(!was_event_emitted(&TestEvent { value: 3 }), 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x30001, location = Self)]
+    fun test_emit_without_feature() {
+        emit(TestEvent { value: 1 });
+    }
 }
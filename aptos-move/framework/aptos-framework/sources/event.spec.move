@@ -10,6 +10,15 @@ spec aptos_framework::event {
         ensures [concrete] handle_ref.counter == old(handle_ref.counter) + 1;
     }
 
+    spec emit {
+        aborts_if !features::spec_is_enabled(features::MODULE_EVENT);
+    }
+
+    /// Native function use opaque.
+    spec write_module_event_to_store<T: drop + store>(msg: T) {
+        pragma opaque;
+    }
+
     /// Native function use opaque.
     spec write_to_event_store<T: drop + store>(guid: vector<u8>, count: u64, msg: T) {
         pragma opaque;
@@ -1,16 +1,34 @@
 module aptos_framework::transaction_context {
 
     use std::features;
+    use std::option::{Self, Option};
+    use std::string::{Self, String};
+    use std::vector;
 
     /// AUID feature is not supported.
     const EAUID_NOT_SUPPORTED: u64 = 1;
 
+    /// The transaction context extension feature is not enabled.
+    const ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED: u64 = 2;
+
+    /// The transaction context is only available during the execution of a user transaction.
+    const ETRANSACTION_CONTEXT_NOT_AVAILABLE: u64 = 3;
+
     /// A wrapper denoting aptos unique identifer (AUID)
     /// for storing an address
     struct AUID has drop, store {
         unique_address: address
     }
 
+    /// The entry function called by a transaction.
+    struct EntryFunctionPayload has copy, drop {
+        account_address: address,
+        module_name: String,
+        function_name: String,
+        ty_arg_names: vector<String>,
+        args: vector<vector<u8>>,
+    }
+
     /// Return the transaction hash of the current transaction.
     native fun get_txn_hash(): vector<u8>;
 
@@ -55,6 +73,95 @@ module aptos_framework::transaction_context {
         auid.unique_address
     }
 
+    /// Return the sender of the current transaction.
+    public fun sender(): address {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        sender_internal()
+    }
+    native fun sender_internal(): address;
+
+    /// Return the secondary signers of the current transaction, empty unless it is a multi-agent transaction.
+    public fun secondary_signers(): vector<address> {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        secondary_signers_internal()
+    }
+    native fun secondary_signers_internal(): vector<address>;
+
+    /// Return the account paying for the gas of the current transaction: the fee payer if there is one,
+    /// and the sender otherwise.
+    public fun fee_payer(): address {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        fee_payer_internal()
+    }
+    native fun fee_payer_internal(): address;
+
+    /// Return the maximum amount of gas units the current transaction can use.
+    public fun max_gas_amount(): u64 {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        max_gas_amount_internal()
+    }
+    native fun max_gas_amount_internal(): u64;
+
+    /// Return the gas unit price of the current transaction, in Octa.
+    public fun gas_unit_price(): u64 {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        gas_unit_price_internal()
+    }
+    native fun gas_unit_price_internal(): u64;
+
+    /// Return the id of the chain the current transaction is executed on.
+    public fun chain_id(): u8 {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        chain_id_internal()
+    }
+    native fun chain_id_internal(): u8;
+
+    /// Return the entry function called by the current transaction, or none if it executes a script or
+    /// a multisig transaction.
+    public fun entry_function_payload(): Option<EntryFunctionPayload> {
+        assert!(features::transaction_context_extension_enabled(), ETRANSACTION_CONTEXT_EXTENSION_NOT_ENABLED);
+        let (is_entry_function, account_address, module_name, function_name, ty_arg_names, args) =
+            entry_function_payload_internal();
+        if (!is_entry_function) {
+            return option::none()
+        };
+        option::some(EntryFunctionPayload {
+            account_address,
+            module_name,
+            function_name,
+            ty_arg_names: vector::map(ty_arg_names, |name| string::utf8(name)),
+            args,
+        })
+    }
+    /// Return whether the current transaction calls an entry function, and the parts of its payload, with
+    /// the type arguments as UTF-8 bytes. The parts are empty if it does not.
+    native fun entry_function_payload_internal(): (bool, address, String, String, vector<vector<u8>>, vector<vector<u8>>);
+
+    /// Return the address of the account the entry function is published under.
+    public fun account_address(payload: &EntryFunctionPayload): address {
+        payload.account_address
+    }
+
+    /// Return the name of the module declaring the entry function.
+    public fun module_name(payload: &EntryFunctionPayload): String {
+        payload.module_name
+    }
+
+    /// Return the name of the entry function.
+    public fun function_name(payload: &EntryFunctionPayload): String {
+        payload.function_name
+    }
+
+    /// Return the type arguments of the call, in the same format as `type_info::type_name`.
+    public fun type_arg_names(payload: &EntryFunctionPayload): vector<String> {
+        payload.ty_arg_names
+    }
+
+    /// Return the BCS-serialized arguments of the call, excluding the signers.
+    public fun args(payload: &EntryFunctionPayload): vector<vector<u8>> {
+        payload.args
+    }
+
     #[test(fx = @std)]
     fun test_auid_uniquess(fx: signer) {
         use std::features;
@@ -80,4 +187,17 @@ module aptos_framework::transaction_context {
             i = i + 1;
         };
     }
+
+    #[test]
+    #[expected_failure(abort_code = 2, location = Self)]
+    fun test_sender_requires_feature() {
+        sender();
+    }
+
+    #[test(fx = @std)]
+    #[expected_failure(abort_code = 3, location = Self)]
+    fun test_sender_outside_user_transaction(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_transaction_context_extension_feature()], vector[]);
+        sender();
+    }
 }
@@ -5,6 +5,7 @@ module aptos_framework::transaction_validation {
     use std::vector;
 
     use aptos_framework::account;
+    use aptos_framework::account_abstraction;
     use aptos_framework::aptos_coin::AptosCoin;
     use aptos_framework::chain_id;
     use aptos_framework::coin;
@@ -85,7 +86,7 @@ module aptos_framework::transaction_validation {
         let transaction_sender = signer::address_of(&sender);
         assert!(account::exists_at(transaction_sender), error::invalid_argument(PROLOGUE_EACCOUNT_DOES_NOT_EXIST));
         assert!(
-            txn_authentication_key == account::get_authentication_key(transaction_sender),
+            is_valid_authentication_key(transaction_sender, txn_authentication_key),
             error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
         );
 
@@ -184,8 +185,10 @@ module aptos_framework::transaction_validation {
                 invariant i <= num_secondary_signers;
                 invariant forall j in 0..i:
                     account::exists_at(secondary_signer_addresses[j])
-                    && secondary_signer_public_key_hashes[j]
-                       == account::get_authentication_key(secondary_signer_addresses[j]);
+                    && spec_is_valid_authentication_key(
+                        secondary_signer_addresses[j],
+                        secondary_signer_public_key_hashes[j],
+                    );
             };
             (i < num_secondary_signers)
         }) {
@@ -194,7 +197,7 @@ module aptos_framework::transaction_validation {
 
             let signer_public_key_hash = *vector::borrow(&secondary_signer_public_key_hashes, i);
             assert!(
-                signer_public_key_hash == account::get_authentication_key(secondary_address),
+                is_valid_authentication_key(secondary_address, signer_public_key_hash),
                 error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
             );
             i = i + 1;
@@ -227,11 +230,22 @@ module aptos_framework::transaction_validation {
         );
         multi_agent_common_prologue(secondary_signer_addresses, secondary_signer_public_key_hashes);
         assert!(
-            fee_payer_public_key_hash == account::get_authentication_key(fee_payer_address),
+            is_valid_authentication_key(fee_payer_address, fee_payer_public_key_hash),
             error::invalid_argument(PROLOGUE_EINVALID_ACCOUNT_AUTH_KEY),
         );
     }
 
+    /// Check the authentication key the VM derived from the authenticator of a signer. An empty key
+    /// stands for an abstraction authenticator, whose signature the VM checks after the prologue with
+    /// the authentication function registered by the account.
+    fun is_valid_authentication_key(addr: address, txn_authentication_key: vector<u8>): bool {
+        if (vector::is_empty(&txn_authentication_key)) {
+            features::account_abstraction_enabled() && account_abstraction::using_account_abstraction(addr)
+        } else {
+            txn_authentication_key == account::get_authentication_key(addr)
+        }
+    }
+
     /// Epilogue function is run after a transaction is successfully executed.
     /// Called by the Adapter
     fun epilogue(
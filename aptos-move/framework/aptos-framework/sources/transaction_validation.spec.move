@@ -43,7 +43,7 @@ spec aptos_framework::transaction_validation {
         let transaction_sender = signer::address_of(sender);
         aborts_if !account::exists_at(transaction_sender);
         aborts_if !(txn_sequence_number >= global<Account>(transaction_sender).sequence_number);
-        aborts_if !(txn_authentication_key == global<Account>(transaction_sender).authentication_key);
+        aborts_if !spec_is_valid_authentication_key(transaction_sender, txn_authentication_key);
         aborts_if !(txn_sequence_number < (1u64 << 63));
 
         let max_transaction_fee = txn_gas_price * txn_max_gas_units;
@@ -108,8 +108,10 @@ spec aptos_framework::transaction_validation {
         // If any account does not exist, or public key hash does not match, abort.
         aborts_if exists i in 0..num_secondary_signers:
             !account::exists_at(secondary_signer_addresses[i])
-                || secondary_signer_public_key_hashes[i] !=
-                account::get_authentication_key(secondary_signer_addresses[i]);
+                || !spec_is_valid_authentication_key(
+                    secondary_signer_addresses[i],
+                    secondary_signer_public_key_hashes[i],
+                );
     }
 
     spec multi_agent_common_prologue(
@@ -174,10 +176,25 @@ spec aptos_framework::transaction_validation {
         };
 
         aborts_if !account::exists_at(gas_payer);
-        aborts_if !(fee_payer_public_key_hash == account::get_authentication_key(gas_payer));
+        aborts_if !spec_is_valid_authentication_key(gas_payer, fee_payer_public_key_hash);
         aborts_if !features::spec_fee_payer_enabled();
     }
 
+    spec fun spec_is_valid_authentication_key(addr: address, txn_authentication_key: vector<u8>): bool {
+        if (len(txn_authentication_key) == 0) {
+            features::spec_account_abstraction_enabled()
+                && exists<account_abstraction::AuthenticationFunction>(addr)
+        } else {
+            txn_authentication_key == account::get_authentication_key(addr)
+        }
+    }
+
+    spec is_valid_authentication_key(addr: address, txn_authentication_key: vector<u8>): bool {
+        pragma opaque;
+        aborts_if len(txn_authentication_key) != 0 && !account::exists_at(addr);
+        ensures result == spec_is_valid_authentication_key(addr, txn_authentication_key);
+    }
+
         /// Abort according to the conditions.
     /// `AptosCoinCapabilities` and `CoinInfo` should exists.
     /// Skip transaction_fee::burn_fee verification.
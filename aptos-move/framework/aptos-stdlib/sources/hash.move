@@ -1,5 +1,6 @@
 /// Cryptographic hashes:
 /// - Keccak-256: see https://keccak.team/keccak.html
+/// - Poseidon over the BN254 and BLS12-381 scalar fields: see https://eprint.iacr.org/2019/458.pdf
 ///
 /// In addition, SHA2-256 and SHA3-256 are available in `std::hash`. Note that SHA3-256 is a variant of Keccak: it is
 /// NOT the same as Keccak-256.
@@ -17,6 +18,9 @@ module aptos_std::aptos_hash {
     /// A newly-added native function is not yet enabled.
     const E_NATIVE_FUN_NOT_AVAILABLE: u64 = 1;
 
+    /// The number of Poseidon inputs is not between 1 and 16, or an input is not a canonically-serialized scalar.
+    const E_INVALID_POSEIDON_INPUT: u64 = 2;
+
     //
     // Functions
     //
@@ -74,6 +78,35 @@ module aptos_std::aptos_hash {
         blake2b_256_internal(bytes)
     }
 
+    /// Returns the Poseidon hash of `inputs` over the BN254 scalar field, as used in circom/circomlib.
+    ///
+    /// Each input must be a BN254 scalar serialized into 32 bytes with the least significant byte first,
+    /// and the output is serialized the same way. The permutation uses the `x^5` S-box, 8 full rounds,
+    /// width `length(inputs) + 1` and the partial round numbers recommended for 128-bit security.
+    ///
+    /// Aborts with `std::error::invalid_argument(E_INVALID_POSEIDON_INPUT)` if `inputs` is empty,
+    /// has more than 16 elements, or contains a byte array that is not a canonical scalar.
+    public fun poseidon_bn254(inputs: vector<vector<u8>>): vector<u8> {
+        if(!features::poseidon_natives_enabled()) {
+            abort(std::error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        poseidon_bn254_internal(inputs)
+    }
+
+    /// Returns the Poseidon hash of `inputs` over the BLS12-381 scalar field.
+    ///
+    /// Same as `poseidon_bn254`, except that inputs and output are BLS12-381 scalars. The round numbers are
+    /// derived for the BLS12-381 scalar field, and the round constants and MDS matrices are those generated for
+    /// it by the Poseidon reference implementation (https://extgit.iaik.tugraz.at/krypto/hadeshash).
+    public fun poseidon_bls12381(inputs: vector<vector<u8>>): vector<u8> {
+        if(!features::poseidon_natives_enabled()) {
+            abort(std::error::invalid_state(E_NATIVE_FUN_NOT_AVAILABLE))
+        };
+
+        poseidon_bls12381_internal(inputs)
+    }
+
     //
     // Private native functions
     //
@@ -94,6 +127,12 @@ module aptos_std::aptos_hash {
     /// Returns the BLAKE2B-256 hash of `bytes`.
     native fun blake2b_256_internal(bytes: vector<u8>): vector<u8>;
 
+    /// Returns the Poseidon hash of `inputs` over the BN254 scalar field.
+    native fun poseidon_bn254_internal(inputs: vector<vector<u8>>): vector<u8>;
+
+    /// Returns the Poseidon hash of `inputs` over the BLS12-381 scalar field.
+    native fun poseidon_bls12381_internal(inputs: vector<vector<u8>>): vector<u8>;
+
     //
     // Testing
     //
@@ -250,4 +289,121 @@ module aptos_std::aptos_hash {
             i = i + 1;
         };
     }
+
+    #[test_only]
+    fun poseidon_test_inputs(num: u64): vector<vector<u8>> {
+        let inputs = vector[];
+        let i = 1;
+        while (i <= num) {
+            let input = std::bcs::to_bytes(&(i as u256));
+            std::vector::push_back(&mut inputs, input);
+            i = i + 1;
+        };
+        inputs
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 196609, location = Self)]
+    fun poseidon_bn254_aborts(fx: signer) {
+        // We disable the feature to make sure the `poseidon_bn254` call aborts
+        features::change_feature_flags(&fx, vector[], vector[features::get_poseidon_natives_feature()]);
+
+        poseidon_bn254(poseidon_test_inputs(1));
+    }
+
+    #[test(fx = @aptos_std)]
+    fun poseidon_bn254_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_poseidon_natives_feature()], vector[]);
+        let inputs = vector[
+        poseidon_test_inputs(1),
+        poseidon_test_inputs(2),
+        poseidon_test_inputs(4),
+        poseidon_test_inputs(16),
+        ];
+
+        // From circomlibjs, e.g. `poseidon([1, 2])` is 0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a,
+        // here serialized with the least significant byte first.
+        let outputs = vector[
+        x"33018202c57d898b84338b16d1a4960e133c6a4d656cfec1bd62a9ea00611729",
+        x"9a1817447a60199e51453274f217362acfe962966b4cf63d4190d6e7f5c05c11",
+        x"65042565df25a5ba3d66e01cbb0ee637980b51e440face9dd7fdc1b67d869c29",
+        x"65b719b996fb2fde06ecf2f1d7af08ae49f9ff9980a481821066bb1c559a1516",
+        ];
+
+        let i = 0;
+        while (i < std::vector::length(&inputs)) {
+            let input = *std::vector::borrow(&inputs, i);
+            let hash_expected = *std::vector::borrow(&outputs, i);
+            let hash = poseidon_bn254(input);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    fun poseidon_bls12381_test(fx: signer) {
+        // We need to enable the feature in order for the native call to be allowed.
+        features::change_feature_flags(&fx, vector[features::get_poseidon_natives_feature()], vector[]);
+        let inputs = vector[
+        poseidon_test_inputs(2),
+        poseidon_test_inputs(4),
+        ];
+
+        // The test vectors of the `x5_255_3` and `x5_255_5` instances of the Poseidon reference implementation
+        // (https://extgit.iaik.tugraz.at/krypto/hadeshash): the first output of the permutation of `[0, 1, 2]` is
+        // 0x28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a, and that of `[0, 1, 2, 3, 4]` is
+        // 0x2a918b9c9f9bd7bb509331c81e297b5707f6fc7393dcee1b13901a0b22202e18, here serialized with the least
+        // significant byte first.
+        let outputs = vector[
+        x"8aa7d27d314e4bcbe4e9182cbe6671d6c9f5988c1ead5355a046c20f4219ce28",
+        x"182e20220b1a90131beedc9373fcf607577b291ec8319350bbd79b9f9c8b912a",
+        ];
+
+        let i = 0;
+        while (i < std::vector::length(&inputs)) {
+            let input = *std::vector::borrow(&inputs, i);
+            let hash_expected = *std::vector::borrow(&outputs, i);
+            let hash = poseidon_bls12381(input);
+
+            assert!(hash_expected == hash, 1);
+
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun poseidon_bn254_aborts_on_non_canonical_input(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_natives_feature()], vector[]);
+
+        // The BN254 scalar field order, which is not a canonical scalar.
+        poseidon_bn254(vector[x"010000f093f5e1439170b97948e833285d588181b64550b829a031e1724e6430"]);
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun poseidon_bls12381_aborts_on_wrong_input_size(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_natives_feature()], vector[]);
+
+        poseidon_bls12381(vector[x"0100"]);
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun poseidon_bn254_aborts_on_too_many_inputs(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_natives_feature()], vector[]);
+
+        poseidon_bn254(poseidon_test_inputs(17));
+    }
+
+    #[test(fx = @aptos_std)]
+    #[expected_failure(abort_code = 0x010002, location = Self)]
+    fun poseidon_bn254_aborts_on_empty_input(fx: signer) {
+        features::change_feature_flags(&fx, vector[features::get_poseidon_natives_feature()], vector[]);
+
+        poseidon_bn254(vector[]);
+    }
 }
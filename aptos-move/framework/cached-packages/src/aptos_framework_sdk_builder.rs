@@ -35,6 +35,18 @@ type Bytes = Vec<u8>;
 #[cfg_attr(feature = "fuzzing", derive(proptest_derive::Arbitrary))]
 #[cfg_attr(feature = "fuzzing", proptest(no_params))]
 pub enum EntryFunctionCall {
+    /// Authenticate the transactions of `account` with `module_address::module_name::function_name`,
+    /// replacing the function registered before, if any. The function must be public, with the
+    /// signature `(&signer, vector<u8>, vector<u8>)`: the transactions of the account fail otherwise.
+    AccountAbstractionAddAuthenticationFunction {
+        module_address: AccountAddress,
+        module_name: Vec<u8>,
+        function_name: Vec<u8>,
+    },
+
+    /// Stop authenticating the transactions of `account` with its registered function.
+    AccountAbstractionRemoveAuthenticationFunction {},
+
     /// Offers rotation capability on behalf of `account` to the account at address `recipient_address`.
     /// An account can delegate its rotation capability to only one other address at one time. If the account
     /// has an existing rotation capability offer, calling this function will update the rotation capability offer with
@@ -871,6 +883,18 @@ impl EntryFunctionCall {
     pub fn encode(self) -> TransactionPayload {
         use EntryFunctionCall::*;
         match self {
+            AccountAbstractionAddAuthenticationFunction {
+                module_address,
+                module_name,
+                function_name,
+            } => account_abstraction_add_authentication_function(
+                module_address,
+                module_name,
+                function_name,
+            ),
+            AccountAbstractionRemoveAuthenticationFunction {} => {
+                account_abstraction_remove_authentication_function()
+            },
             AccountOfferRotationCapability {
                 rotation_capability_sig_bytes,
                 account_scheme,
@@ -1405,6 +1429,48 @@ impl EntryFunctionCall {
     }
 }
 
+/// Authenticate the transactions of `account` with `module_address::module_name::function_name`,
+/// replacing the function registered before, if any. The function must be public, with the
+/// signature `(&signer, vector<u8>, vector<u8>)`: the transactions of the account fail otherwise.
+pub fn account_abstraction_add_authentication_function(
+    module_address: AccountAddress,
+    module_name: Vec<u8>,
+    function_name: Vec<u8>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("account_abstraction").to_owned(),
+        ),
+        ident_str!("add_authentication_function").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&module_address).unwrap(),
+            bcs::to_bytes(&module_name).unwrap(),
+            bcs::to_bytes(&function_name).unwrap(),
+        ],
+    ))
+}
+
+/// Stop authenticating the transactions of `account` with its registered function.
+pub fn account_abstraction_remove_authentication_function() -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("account_abstraction").to_owned(),
+        ),
+        ident_str!("remove_authentication_function").to_owned(),
+        vec![],
+        vec![],
+    ))
+}
+
 /// Offers rotation capability on behalf of `account` to the account at address `recipient_address`.
 /// An account can delegate its rotation capability to only one other address at one time. If the account
 /// has an existing rotation capability offer, calling this function will update the rotation capability offer with
@@ -3888,6 +3954,32 @@ pub fn vesting_vest_many(contract_addresses: Vec<AccountAddress>) -> Transaction
 }
 mod decoder {
     use super::*;
+    pub fn account_abstraction_add_authentication_function(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(
+                EntryFunctionCall::AccountAbstractionAddAuthenticationFunction {
+                    module_address: bcs::from_bytes(script.args().get(0)?).ok()?,
+                    module_name: bcs::from_bytes(script.args().get(1)?).ok()?,
+                    function_name: bcs::from_bytes(script.args().get(2)?).ok()?,
+                },
+            )
+        } else {
+            None
+        }
+    }
+
+    pub fn account_abstraction_remove_authentication_function(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(_script) = payload {
+            Some(EntryFunctionCall::AccountAbstractionRemoveAuthenticationFunction {})
+        } else {
+            None
+        }
+    }
+
     pub fn account_offer_rotation_capability(
         payload: &TransactionPayload,
     ) -> Option<EntryFunctionCall> {
@@ -5327,6 +5419,14 @@ type EntryFunctionDecoderMap = std::collections::HashMap<
 static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMap> =
     once_cell::sync::Lazy::new(|| {
         let mut map: EntryFunctionDecoderMap = std::collections::HashMap::new();
+        map.insert(
+            "account_abstraction_add_authentication_function".to_string(),
+            Box::new(decoder::account_abstraction_add_authentication_function),
+        );
+        map.insert(
+            "account_abstraction_remove_authentication_function".to_string(),
+            Box::new(decoder::account_abstraction_remove_authentication_function),
+        );
         map.insert(
             "account_offer_rotation_capability".to_string(),
             Box::new(decoder::account_offer_rotation_capability),
@@ -220,6 +220,73 @@ module std::features {
         is_enabled(BULLETPROOFS_NATIVES)
     }
 
+    /// Whether emitting module events (i.e., events not bound to an event handle) is enabled.
+    /// This is needed because of the introduction of new native functions.
+    /// Lifetime: transient
+    const MODULE_EVENT: u64 = 25;
+
+    public fun get_module_event_feature(): u64 { MODULE_EVENT }
+
+    public fun module_event_enabled(): bool acquires Features {
+        is_enabled(MODULE_EVENT)
+    }
+
+    /// Whether the generic algebra implementation for BN254 operations are enabled.
+    ///
+    /// Lifetime: transient
+    const BN254_STRUCTURES: u64 = 26;
+    public fun get_bn254_strutures_feature(): u64 { BN254_STRUCTURES }
+    public fun bn254_structures_enabled(): bool acquires Features {
+        is_enabled(BN254_STRUCTURES)
+    }
+
+    /// Whether the Poseidon hash natives are enabled.
+    ///
+    /// Lifetime: transient
+    const POSEIDON_NATIVES: u64 = 27;
+    public fun get_poseidon_natives_feature(): u64 { POSEIDON_NATIVES }
+    public fun poseidon_natives_enabled(): bool acquires Features {
+        is_enabled(POSEIDON_NATIVES)
+    }
+
+    /// Whether transactions can be authenticated with secp256r1 ECDSA signatures in WebAuthn
+    /// assertions (i.e., passkeys).
+    ///
+    /// Lifetime: transient
+    const WEBAUTHN_SIGNATURE: u64 = 28;
+    public fun get_webauthn_signature_feature(): u64 { WEBAUTHN_SIGNATURE }
+    public fun webauthn_signature_enabled(): bool acquires Features {
+        is_enabled(WEBAUTHN_SIGNATURE)
+    }
+
+    /// Whether transactions can be authenticated with Ethereum-style secp256k1 ECDSA signatures.
+    ///
+    /// Lifetime: transient
+    const SECP256K1_ECDSA_AUTHENTICATOR: u64 = 29;
+    public fun get_secp256k1_ecdsa_authenticator_feature(): u64 { SECP256K1_ECDSA_AUTHENTICATOR }
+    public fun secp256k1_ecdsa_authenticator_enabled(): bool acquires Features {
+        is_enabled(SECP256K1_ECDSA_AUTHENTICATOR)
+    }
+
+    /// Whether the transaction context natives describing the user transaction and its payload are enabled.
+    ///
+    /// Lifetime: transient
+    const TRANSACTION_CONTEXT_EXTENSION: u64 = 30;
+    public fun get_transaction_context_extension_feature(): u64 { TRANSACTION_CONTEXT_EXTENSION }
+    public fun transaction_context_extension_enabled(): bool acquires Features {
+        is_enabled(TRANSACTION_CONTEXT_EXTENSION)
+    }
+
+    /// Whether accounts can register a Move function that authenticates their transactions in place
+    /// of the native signature check.
+    ///
+    /// Lifetime: transient
+    const ACCOUNT_ABSTRACTION: u64 = 31;
+    public fun get_account_abstraction_feature(): u64 { ACCOUNT_ABSTRACTION }
+    public fun account_abstraction_enabled(): bool acquires Features {
+        is_enabled(ACCOUNT_ABSTRACTION)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
@@ -64,4 +64,14 @@ spec std::features {
         aborts_if [abstract] false;
         ensures [abstract] result == spec_partial_governance_voting_enabled();
     }
+
+    spec fun spec_account_abstraction_enabled(): bool {
+        spec_is_enabled(ACCOUNT_ABSTRACTION)
+    }
+
+    spec account_abstraction_enabled {
+        pragma opaque;
+        aborts_if [abstract] false;
+        ensures [abstract] result == spec_account_abstraction_enabled();
+    }
 }
@@ -3,12 +3,13 @@
 
 use crate::{
     docgen::DocgenOptions,
-    extended_checks,
+    extended_checks, get_metadata_from_compiled_module,
     natives::code::{ModuleMetadata, MoveOption, PackageDep, PackageMetadata, UpgradePolicy},
     zip_metadata, zip_metadata_str, RuntimeModuleMetadataV1, APTOS_METADATA_KEY,
     APTOS_METADATA_KEY_V1, METADATA_V1_MIN_FILE_FORMAT_VERSION,
 };
 use anyhow::bail;
+use aptos_sdk_builder::rust_bindings::BindingsModule;
 use aptos_types::{account_address::AccountAddress, transaction::EntryABI};
 use clap::Parser;
 use codespan_reporting::{
@@ -19,7 +20,7 @@ use itertools::Itertools;
 use move_binary_format::CompiledModule;
 use move_command_line_common::files::MOVE_COMPILED_EXTENSION;
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
-use move_core_types::{language_storage::ModuleId, metadata::Metadata};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId, metadata::Metadata};
 use move_model::model::GlobalEnv;
 use move_package::{
     compilation::{compiled_package::CompiledPackage, package_layout::CompiledPackageLayout},
@@ -243,6 +244,12 @@ impl BuiltPackage {
         })
     }
 
+    /// Returns the modules of this package (with their view functions), from
+    /// which typed Rust bindings can be generated.
+    pub fn extract_bindings_modules(&self) -> Vec<BindingsModule> {
+        self.modules().map(bindings_module).collect()
+    }
+
     /// Returns an iterator for all compiled proper (non-script) modules.
     pub fn modules(&self) -> impl Iterator<Item = &CompiledModule> {
         self.package
@@ -414,3 +421,19 @@ fn inject_runtime_metadata(
     }
     Ok(())
 }
+
+/// Creates the module to generate typed Rust bindings for, with the view functions
+/// declared in the module metadata.
+pub fn bindings_module(module: &CompiledModule) -> BindingsModule {
+    let view_functions = get_metadata_from_compiled_module(module)
+        .map(|metadata| {
+            metadata
+                .fun_attributes
+                .into_iter()
+                .filter(|(_, attributes)| attributes.iter().any(|attr| attr.is_view_function()))
+                .filter_map(|(name, _)| Identifier::new(name).ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    BindingsModule::new(module, view_functions)
+}
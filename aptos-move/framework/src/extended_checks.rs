@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{KnownAttribute, RuntimeModuleMetadataV1};
-use move_binary_format::file_format::{Ability, AbilitySet, Visibility};
+use move_binary_format::{
+    binary_views::BinaryIndexedView,
+    file_format::{Ability, AbilitySet, Bytecode, SignatureToken, Visibility},
+};
 use move_core_types::{
     account_address::AccountAddress,
     errmap::{ErrorDescription, ErrorMapping},
@@ -18,7 +21,11 @@ use move_model::{
     symbol::Symbol,
     ty::{PrimitiveType, ReferenceKind, Type},
 };
-use std::{collections::BTreeMap, rc::Rc, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+    str::FromStr,
+};
 use thiserror::Error;
 
 const INIT_MODULE_FUN: &str = "init_module";
@@ -29,6 +36,9 @@ const RESOURCE_GROUP_MEMBER: &str = "resource_group_member";
 const RESOURCE_GROUP_NAME: &str = "group";
 const RESOURCE_GROUP_SCOPE: &str = "scope";
 const VIEW_FUN_ATTRIBUTE: &str = "view";
+const EVENT_STRUCT_ATTRIBUTE: &str = "event";
+const EVENT_MODULE_NAME: &str = "event";
+const EMIT_FUNCTION_NAME: &str = "emit";
 
 /// Run the extended context checker on target modules in the environment and returns a map
 /// from module to extended runtime metadata. Any errors during context checking are reported to
@@ -66,6 +76,8 @@ impl<'a> ExtendedChecker<'a> {
                 self.check_and_record_resource_groups(module);
                 self.check_and_record_resource_group_members(module);
                 self.check_and_record_view_functions(module);
+                self.check_and_record_events(module);
+                self.check_emit_event_calls(module);
                 self.check_entry_functions(module);
                 self.check_init_module(module);
                 self.build_error_map(module)
@@ -428,6 +440,106 @@ impl<'a> ExtendedChecker<'a> {
     }
 }
 
+// ----------------------------------------------------------------------------------
+// Events
+
+impl<'a> ExtendedChecker<'a> {
+    // An event struct should have the abilities required by `0x1::event::emit`
+    fn check_and_record_events(&mut self, module: &ModuleEnv) {
+        let module_id = self.get_runtime_module_id(module);
+        for ref struct_ in module.get_structs() {
+            if !self.has_struct_attribute(struct_, EVENT_STRUCT_ATTRIBUTE) {
+                continue;
+            }
+            let abilities = struct_.get_abilities();
+            if !abilities.has_ability(Ability::Drop) || !abilities.has_ability(Ability::Store) {
+                self.env.error(
+                    &struct_.get_loc(),
+                    "event struct should have drop and store abilities",
+                );
+                continue;
+            }
+            self.output
+                .entry(module_id.clone())
+                .or_default()
+                .struct_attributes
+                .entry(self.name_string(struct_.get_name()).to_string())
+                .or_default()
+                .push(KnownAttribute::event());
+        }
+    }
+
+    // Module events can only be emitted by the module defining them, i.e., each call to
+    // `0x1::event::emit<T>` should instantiate `T` with an event struct of the calling module.
+    fn check_emit_event_calls(&self, module: &ModuleEnv) {
+        let compiled_module = match module.get_verified_module() {
+            Some(compiled_module) => compiled_module,
+            None => return,
+        };
+        let view = BinaryIndexedView::Module(compiled_module);
+        let event_structs = module
+            .get_structs()
+            .filter(|struct_| self.has_struct_attribute(struct_, EVENT_STRUCT_ATTRIBUTE))
+            .map(|struct_| self.name_string(struct_.get_name()).to_string())
+            .collect::<BTreeSet<_>>();
+
+        for ref fun in module.get_functions() {
+            let code = match fun.get_bytecode() {
+                Some(code) => code,
+                None => continue,
+            };
+            for (offset, bytecode) in code.iter().enumerate() {
+                let instantiation = match bytecode {
+                    Bytecode::CallGeneric(idx) => view.function_instantiation_at(*idx),
+                    _ => continue,
+                };
+                let function_handle = view.function_handle_at(instantiation.handle);
+                let module_handle = view.module_handle_at(function_handle.module);
+                if view.address_identifier_at(module_handle.address) != &AccountAddress::ONE
+                    || view.identifier_at(module_handle.name).as_str() != EVENT_MODULE_NAME
+                    || view.identifier_at(function_handle.name).as_str() != EMIT_FUNCTION_NAME
+                {
+                    continue;
+                }
+
+                let loc = fun
+                    .get_bytecode_loc(offset as u16)
+                    .unwrap_or_else(|| fun.get_loc());
+                let type_params = view.signature_at(instantiation.type_parameters);
+                let struct_handle = match type_params.0.first() {
+                    Some(SignatureToken::Struct(idx))
+                    | Some(SignatureToken::StructInstantiation(idx, _)) => {
+                        view.struct_handle_at(*idx)
+                    },
+                    _ => {
+                        self.env
+                            .error(&loc, "`0x1::event::emit` must be called with a struct type");
+                        continue;
+                    },
+                };
+                let struct_name = view.identifier_at(struct_handle.name).as_str();
+                if view.self_handle_idx() != Some(struct_handle.module) {
+                    self.env.error(
+                        &loc,
+                        &format!(
+                            "`0x1::event::emit` can only be called with a struct defined in the calling module, found: {}",
+                            struct_name
+                        ),
+                    );
+                } else if !event_structs.contains(struct_name) {
+                    self.env.error(
+                        &loc,
+                        &format!(
+                            "`0x1::event::emit` can only be called with a struct that has the #[{}] attribute, found: {}",
+                            EVENT_STRUCT_ATTRIBUTE, struct_name
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------------
 // Error Map
 
@@ -485,6 +597,16 @@ impl<'a> ExtendedChecker<'a> {
         })
     }
 
+    fn has_struct_attribute(&self, struct_: &StructEnv, attr_name: &str) -> bool {
+        struct_.get_attributes().iter().any(|attr| {
+            if let Attribute::Apply(_, name, _) = attr {
+                self.env.symbol_pool().string(*name).as_str() == attr_name
+            } else {
+                false
+            }
+        })
+    }
+
     fn get_runtime_module_id(&self, module: &ModuleEnv<'_>) -> ModuleId {
         let name = module.get_name();
         let addr =
@@ -66,6 +66,7 @@ pub enum KnownAttributeKind {
     ViewFunction = 1,
     ResourceGroup = 2,
     ResourceGroupMember = 3,
+    Event = 4,
 }
 
 impl KnownAttribute {
@@ -118,6 +119,17 @@ impl KnownAttribute {
     pub fn is_resource_group_member(&self) -> bool {
         self.kind == KnownAttributeKind::ResourceGroupMember as u8
     }
+
+    pub fn event() -> Self {
+        Self {
+            kind: KnownAttributeKind::Event as u8,
+            args: vec![],
+        }
+    }
+
+    pub fn is_event(&self) -> bool {
+        self.kind == KnownAttributeKind::Event as u8
+    }
 }
 
 /// Extract metadata from the VM, upgrading V0 to V1 representation as needed
@@ -324,6 +336,26 @@ pub fn is_valid_resource_group_member(
     })
 }
 
+pub fn is_valid_event(
+    structs: &BTreeMap<Identifier, Struct>,
+    struct_: &str,
+) -> Result<(), AttributeValidationError> {
+    if let Ok(ident_struct) = Identifier::new(struct_) {
+        if let Some(mod_struct) = structs.get(&ident_struct) {
+            if mod_struct.abilities.has_ability(Ability::Drop)
+                && mod_struct.abilities.has_ability(Ability::Store)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(AttributeValidationError {
+        key: struct_.to_string(),
+        attribute: KnownAttributeKind::Event as u8,
+    })
+}
+
 pub fn verify_module_metadata(
     module: &CompiledModule,
     features: &Features,
@@ -365,6 +397,10 @@ pub fn verify_module_metadata(
 
     for (struct_, attrs) in &metadata.struct_attributes {
         for attr in attrs {
+            if attr.is_event() {
+                is_valid_event(&structs, struct_)?;
+                continue;
+            }
             if features.are_resource_groups_enabled() {
                 if attr.is_resource_group() && attr.get_resource_group().is_some() {
                     is_valid_resource_group(&structs, struct_)?;
@@ -47,6 +47,27 @@ pub fn double_internal(
             square,
             ALGEBRA_ARK_BLS12_381_FQ12_SQUARE
         ),
+        Some(Structure::BN254G1) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::G1Projective,
+            double,
+            ALGEBRA_ARK_BN254_G1_PROJ_DOUBLE
+        ),
+        Some(Structure::BN254G2) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::G2Projective,
+            double,
+            ALGEBRA_ARK_BN254_G2_PROJ_DOUBLE
+        ),
+        Some(Structure::BN254Gt) => ark_unary_op_internal!(
+            context,
+            args,
+            ark_bn254::Fq12,
+            square,
+            ALGEBRA_ARK_BN254_FQ12_SQUARE
+        ),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -35,6 +35,11 @@ fn feature_flag_of_group_scalar_mul(
         | (Some(Structure::BLS12381Gt), Some(Structure::BLS12381Fr)) => {
             Some(FeatureFlag::BLS12_381_STRUCTURES)
         },
+        (Some(Structure::BN254G1), Some(Structure::BN254Fr))
+        | (Some(Structure::BN254G2), Some(Structure::BN254Fr))
+        | (Some(Structure::BN254Gt), Some(Structure::BN254Fr)) => {
+            Some(FeatureFlag::BN254_STRUCTURES)
+        },
         _ => None,
     }
 }
@@ -134,6 +139,43 @@ pub fn scalar_mul_internal(
             let new_handle = store_element!(context, new_element)?;
             Ok(smallvec![Value::u64(new_handle as u64)])
         },
+        (Some(Structure::BN254G1), Some(Structure::BN254Fr)) => {
+            ark_scalar_mul_internal!(
+                context,
+                args,
+                ark_bn254::G1Projective,
+                ark_bn254::Fr,
+                mul_bigint,
+                ALGEBRA_ARK_BN254_G1_PROJ_SCALAR_MUL
+            )
+        },
+        (Some(Structure::BN254G2), Some(Structure::BN254Fr)) => {
+            ark_scalar_mul_internal!(
+                context,
+                args,
+                ark_bn254::G2Projective,
+                ark_bn254::Fr,
+                mul_bigint,
+                ALGEBRA_ARK_BN254_G2_PROJ_SCALAR_MUL
+            )
+        },
+        (Some(Structure::BN254Gt), Some(Structure::BN254Fr)) => {
+            let scalar_handle = safely_pop_arg!(args, u64) as usize;
+            let element_handle = safely_pop_arg!(args, u64) as usize;
+            safe_borrow_element!(
+                context,
+                element_handle,
+                ark_bn254::Fq12,
+                element_ptr,
+                element
+            );
+            safe_borrow_element!(context, scalar_handle, ark_bn254::Fr, scalar_ptr, scalar);
+            let scalar_bigint: ark_ff::BigInteger256 = (*scalar).into();
+            context.charge(ALGEBRA_ARK_BN254_FQ12_POW_U256)?;
+            let new_element = element.pow(scalar_bigint);
+            let new_handle = store_element!(context, new_element)?;
+            Ok(smallvec![Value::u64(new_handle as u64)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -220,6 +262,28 @@ pub fn multi_scalar_mul_internal(
                 ark_bls12_381::Fr
             )
         },
+        (Some(Structure::BN254G1), Some(Structure::BN254Fr)) => {
+            ark_msm_internal!(
+                context,
+                args,
+                ALGEBRA_ARK_BN254_G1_PROJ_TO_AFFINE.per::<Arg>(),
+                ALGEBRA_ARK_BN254_G1_PROJ_ADD.per::<Arg>(),
+                ALGEBRA_ARK_BN254_G1_PROJ_DOUBLE.per::<Arg>(),
+                ark_bn254::G1Projective,
+                ark_bn254::Fr
+            )
+        },
+        (Some(Structure::BN254G2), Some(Structure::BN254Fr)) => {
+            ark_msm_internal!(
+                context,
+                args,
+                ALGEBRA_ARK_BN254_G2_PROJ_TO_AFFINE.per::<Arg>(),
+                ALGEBRA_ARK_BN254_G2_PROJ_ADD.per::<Arg>(),
+                ALGEBRA_ARK_BN254_G2_PROJ_DOUBLE.per::<Arg>(),
+                ark_bn254::G2Projective,
+                ark_bn254::Fr
+            )
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -3,7 +3,7 @@
 use crate::{
     abort_unless_feature_flag_enabled,
     natives::cryptography::algebra::{
-        abort_invariant_violated, AlgebraContext, Structure, BLS12381_R_SCALAR,
+        abort_invariant_violated, AlgebraContext, Structure, BLS12381_R_SCALAR, BN254_R_SCALAR,
         MOVE_ABORT_CODE_NOT_IMPLEMENTED,
     },
     safe_borrow_element, structure_from_ty_arg,
@@ -27,6 +27,9 @@ fn feature_flag_of_casting(
         (Some(Structure::BLS12381Fq12), Some(Structure::BLS12381Gt)) => {
             Some(FeatureFlag::BLS12_381_STRUCTURES)
         },
+        (Some(Structure::BN254Fq12), Some(Structure::BN254Gt)) => {
+            Some(FeatureFlag::BN254_STRUCTURES)
+        },
         _ => None,
     }
 }
@@ -58,6 +61,16 @@ pub fn downcast_internal(
                 Ok(smallvec![Value::bool(false), Value::u64(handle as u64)])
             }
         },
+        (Some(Structure::BN254Fq12), Some(Structure::BN254Gt)) => {
+            let handle = safely_pop_arg!(args, u64) as usize;
+            safe_borrow_element!(context, handle, ark_bn254::Fq12, element_ptr, element);
+            context.charge(ALGEBRA_ARK_BN254_FQ12_POW_U256)?;
+            if element.pow(BN254_R_SCALAR.0) == ark_bn254::Fq12::one() {
+                Ok(smallvec![Value::bool(true), Value::u64(handle as u64)])
+            } else {
+                Ok(smallvec![Value::bool(false), Value::u64(handle as u64)])
+            }
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -78,6 +91,10 @@ pub fn upcast_internal(
             let handle = safely_pop_arg!(args, u64);
             Ok(smallvec![Value::u64(handle)])
         },
+        (Some(Structure::BN254Gt), Some(Structure::BN254Fq12)) => {
+            let handle = safely_pop_arg!(args, u64);
+            Ok(smallvec![Value::u64(handle)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -4,7 +4,8 @@ use crate::{
     abort_unless_arithmetics_enabled_for_structure, abort_unless_feature_flag_enabled,
     natives::cryptography::algebra::{
         feature_flag_from_structure, AlgebraContext, Structure, BLS12381_GT_GENERATOR,
-        BLS12381_Q12_LENDIAN, BLS12381_R_LENDIAN, E_TOO_MUCH_MEMORY_USED, MEMORY_LIMIT_IN_BYTES,
+        BLS12381_Q12_LENDIAN, BLS12381_R_LENDIAN, BN254_GT_GENERATOR, BN254_Q12_LENDIAN,
+        BN254_Q_LENDIAN, BN254_R_LENDIAN, E_TOO_MUCH_MEMORY_USED, MEMORY_LIMIT_IN_BYTES,
         MOVE_ABORT_CODE_NOT_IMPLEMENTED,
     },
     store_element, structure_from_ty_arg,
@@ -65,6 +66,30 @@ pub fn zero_internal(
             one,
             ALGEBRA_ARK_BLS12_381_FQ12_ONE
         ),
+        Some(Structure::BN254Fr) => {
+            ark_constant_op_internal!(context, ark_bn254::Fr, zero, ALGEBRA_ARK_BN254_FR_ZERO)
+        },
+        Some(Structure::BN254Fq) => {
+            ark_constant_op_internal!(context, ark_bn254::Fq, zero, ALGEBRA_ARK_BN254_FQ_ZERO)
+        },
+        Some(Structure::BN254Fq12) => {
+            ark_constant_op_internal!(context, ark_bn254::Fq12, zero, ALGEBRA_ARK_BN254_FQ12_ZERO)
+        },
+        Some(Structure::BN254G1) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G1Projective,
+            zero,
+            ALGEBRA_ARK_BN254_G1_PROJ_INFINITY
+        ),
+        Some(Structure::BN254G2) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G2Projective,
+            zero,
+            ALGEBRA_ARK_BN254_G2_PROJ_INFINITY
+        ),
+        Some(Structure::BN254Gt) => {
+            ark_constant_op_internal!(context, ark_bn254::Fq12, one, ALGEBRA_ARK_BN254_FQ12_ONE)
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -109,6 +134,33 @@ pub fn one_internal(
             let handle = store_element!(context, element)?;
             Ok(smallvec![Value::u64(handle as u64)])
         },
+        Some(Structure::BN254Fr) => {
+            ark_constant_op_internal!(context, ark_bn254::Fr, one, ALGEBRA_ARK_BN254_FR_ONE)
+        },
+        Some(Structure::BN254Fq) => {
+            ark_constant_op_internal!(context, ark_bn254::Fq, one, ALGEBRA_ARK_BN254_FQ_ONE)
+        },
+        Some(Structure::BN254Fq12) => {
+            ark_constant_op_internal!(context, ark_bn254::Fq12, one, ALGEBRA_ARK_BN254_FQ12_ONE)
+        },
+        Some(Structure::BN254G1) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G1Projective,
+            generator,
+            ALGEBRA_ARK_BN254_G1_PROJ_GENERATOR
+        ),
+        Some(Structure::BN254G2) => ark_constant_op_internal!(
+            context,
+            ark_bn254::G2Projective,
+            generator,
+            ALGEBRA_ARK_BN254_G2_PROJ_GENERATOR
+        ),
+        Some(Structure::BN254Gt) => {
+            context.charge(ALGEBRA_ARK_BN254_FQ12_CLONE)?;
+            let element = *Lazy::force(&BN254_GT_GENERATOR);
+            let handle = store_element!(context, element)?;
+            Ok(smallvec![Value::u64(handle as u64)])
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -133,6 +185,12 @@ pub fn order_internal(
         Some(Structure::BLS12381Fq12) => {
             Ok(smallvec![Value::vector_u8(BLS12381_Q12_LENDIAN.clone())])
         },
+        Some(Structure::BN254Fr)
+        | Some(Structure::BN254G1)
+        | Some(Structure::BN254G2)
+        | Some(Structure::BN254Gt) => Ok(smallvec![Value::vector_u8(BN254_R_LENDIAN.clone())]),
+        Some(Structure::BN254Fq) => Ok(smallvec![Value::vector_u8(BN254_Q_LENDIAN.clone())]),
+        Some(Structure::BN254Fq12) => Ok(smallvec![Value::vector_u8(BN254_Q12_LENDIAN.clone())]),
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
@@ -67,6 +67,30 @@ pub fn eq_internal(
             ark_bls12_381::Fq12,
             ALGEBRA_ARK_BLS12_381_FQ12_EQ
         ),
+        Some(Structure::BN254Fr) => {
+            ark_eq_internal!(context, args, ark_bn254::Fr, ALGEBRA_ARK_BN254_FR_EQ)
+        },
+        Some(Structure::BN254Fq) => {
+            ark_eq_internal!(context, args, ark_bn254::Fq, ALGEBRA_ARK_BN254_FQ_EQ)
+        },
+        Some(Structure::BN254Fq12) => {
+            ark_eq_internal!(context, args, ark_bn254::Fq12, ALGEBRA_ARK_BN254_FQ12_EQ)
+        },
+        Some(Structure::BN254G1) => ark_eq_internal!(
+            context,
+            args,
+            ark_bn254::G1Projective,
+            ALGEBRA_ARK_BN254_G1_PROJ_EQ
+        ),
+        Some(Structure::BN254G2) => ark_eq_internal!(
+            context,
+            args,
+            ark_bn254::G2Projective,
+            ALGEBRA_ARK_BN254_G2_PROJ_EQ
+        ),
+        Some(Structure::BN254Gt) => {
+            ark_eq_internal!(context, args, ark_bn254::Fq12, ALGEBRA_ARK_BN254_FQ12_EQ)
+        },
         _ => Err(SafeNativeError::Abort {
             abort_code: MOVE_ABORT_CODE_NOT_IMPLEMENTED,
         }),
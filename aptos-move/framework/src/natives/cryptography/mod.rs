@@ -6,6 +6,7 @@ pub mod bls12381;
 pub mod bulletproofs;
 pub mod ed25519;
 pub mod multi_ed25519;
+pub mod poseidon;
 pub mod ristretto255;
 pub mod ristretto255_point;
 pub mod ristretto255_scalar;
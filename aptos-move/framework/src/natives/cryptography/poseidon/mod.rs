@@ -0,0 +1,299 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Poseidon hash function (https://eprint.iacr.org/2019/458.pdf) over the BN254 and BLS12-381
+//! scalar fields, instantiated with the `x^5` S-box and the round numbers required for 128-bit
+//! security in each field.
+//!
+//! Round constants and MDS matrices are the standard published tables: circomlib's for BN254 and
+//! those of the reference implementation (hadeshash) for BLS12-381. They are parsed once per width
+//! and then cached.
+
+mod bls12381;
+mod bn254;
+
+use ark_ff::{Field, PrimeField};
+use once_cell::sync::OnceCell;
+
+/// The maximum number of field elements that can be hashed at once.
+pub const MAX_INPUTS: usize = 16;
+
+struct PoseidonParameters<F: PrimeField> {
+    width: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+    round_constants: Vec<F>,
+    mds: Vec<Vec<F>>,
+}
+
+/// Parses a big-endian, `0x`-prefixed hexadecimal constant from the parameter tables.
+fn parse_constant<F: PrimeField>(constant: &str) -> F {
+    let bytes = hex::decode(constant.trim_start_matches("0x")).expect("constants are valid hex");
+    F::from_be_bytes_mod_order(&bytes)
+}
+
+impl<F: PrimeField> PoseidonParameters<F> {
+    fn new(
+        width: usize,
+        (full_rounds, partial_rounds): (usize, usize),
+        round_constants: &[&str],
+        mds: &[&[&str]],
+    ) -> Self {
+        Self {
+            width,
+            full_rounds,
+            partial_rounds,
+            round_constants: round_constants.iter().map(|c| parse_constant(c)).collect(),
+            mds: mds
+                .iter()
+                .map(|row| row.iter().map(|c| parse_constant(c)).collect())
+                .collect(),
+        }
+    }
+
+    fn permute(&self, state: &mut [F]) {
+        let half_full_rounds = self.full_rounds / 2;
+        for round in 0..self.full_rounds + self.partial_rounds {
+            for (i, element) in state.iter_mut().enumerate() {
+                *element += self.round_constants[round * self.width + i];
+            }
+
+            if round < half_full_rounds || round >= half_full_rounds + self.partial_rounds {
+                state.iter_mut().for_each(sbox);
+            } else {
+                sbox(&mut state[0]);
+            }
+
+            let mixed = mul_matrix_vector(&self.mds, state);
+            state.copy_from_slice(&mixed);
+        }
+    }
+
+    /// Hashes `inputs` by permuting `[0, inputs...]` and returning the first element.
+    fn hash(&self, inputs: &[F]) -> F {
+        let mut state = Vec::with_capacity(self.width);
+        state.push(F::zero());
+        state.extend_from_slice(inputs);
+        self.permute(&mut state);
+        state[0]
+    }
+}
+
+fn sbox<F: Field>(x: &mut F) {
+    let x2 = x.square();
+    *x *= x2.square();
+}
+
+fn mul_matrix_vector<F: Field>(matrix: &[Vec<F>], vector: &[F]) -> Vec<F> {
+    matrix
+        .iter()
+        .map(|row| row.iter().zip(vector.iter()).map(|(m, v)| *m * v).sum())
+        .collect()
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const BN254_PARAMETERS_INIT: OnceCell<PoseidonParameters<ark_bn254::Fr>> = OnceCell::new();
+static BN254_PARAMETERS: [OnceCell<PoseidonParameters<ark_bn254::Fr>>; MAX_INPUTS] =
+    [BN254_PARAMETERS_INIT; MAX_INPUTS];
+
+#[allow(clippy::declare_interior_mutable_const)]
+const BLS12381_PARAMETERS_INIT: OnceCell<PoseidonParameters<ark_bls12_381::Fr>> = OnceCell::new();
+static BLS12381_PARAMETERS: [OnceCell<PoseidonParameters<ark_bls12_381::Fr>>; MAX_INPUTS] =
+    [BLS12381_PARAMETERS_INIT; MAX_INPUTS];
+
+/// Poseidon hash of 1 to `MAX_INPUTS` BN254 scalars.
+pub fn poseidon_bn254(inputs: &[ark_bn254::Fr]) -> ark_bn254::Fr {
+    debug_assert!(!inputs.is_empty() && inputs.len() <= MAX_INPUTS);
+    let index = inputs.len() - 1;
+    BN254_PARAMETERS[index]
+        .get_or_init(|| {
+            PoseidonParameters::new(
+                inputs.len() + 1,
+                bn254::ROUNDS[index],
+                bn254::ROUND_CONSTANTS[index],
+                bn254::MDS[index],
+            )
+        })
+        .hash(inputs)
+}
+
+/// Poseidon hash of 1 to `MAX_INPUTS` BLS12-381 scalars.
+pub fn poseidon_bls12381(inputs: &[ark_bls12_381::Fr]) -> ark_bls12_381::Fr {
+    debug_assert!(!inputs.is_empty() && inputs.len() <= MAX_INPUTS);
+    let index = inputs.len() - 1;
+    BLS12381_PARAMETERS[index]
+        .get_or_init(|| {
+            PoseidonParameters::new(
+                inputs.len() + 1,
+                bls12381::ROUNDS[index],
+                bls12381::ROUND_CONSTANTS[index],
+                bls12381::MDS[index],
+            )
+        })
+        .hash(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::BigInteger;
+
+    const SECURITY_LEVEL: f64 = 128.0;
+    const ALPHA: f64 = 5.0;
+
+    fn log2_modulus<F: PrimeField>() -> f64 {
+        let bytes = F::MODULUS.to_bytes_be();
+        let top = bytes
+            .iter()
+            .take(8)
+            .fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+        (top as f64).log2() + (8 * (bytes.len() - 8)) as f64
+    }
+
+    /// Follows `sat_inequiv_alpha` in the reference `calc_round_numbers.py`.
+    fn is_secure(field_bits: f64, log2_p: f64, width: usize, full: usize, partial: usize) -> bool {
+        let (t, r_f, r_p, m) = (width as f64, full as f64, partial as f64, SECURITY_LEVEL);
+        let log_alpha = |x: f64| x.log2() / ALPHA.log2();
+
+        // Statistical, interpolation and Groebner basis attacks.
+        let statistical = if m <= (log2_p.floor() - (ALPHA - 1.0) / 2.0) * (t + 1.0) {
+            6.0
+        } else {
+            10.0
+        };
+        let interpolation =
+            1.0 + (log_alpha(2.0) * m.min(field_bits)).ceil() + log_alpha(t).ceil() - r_p;
+        let groebner_1 = log_alpha(2.0) * m.min(log2_p) - r_p;
+        let groebner_2 = t - 1.0 + log_alpha(2.0) * (m / (t + 1.0)).min(log2_p / 2.0) - r_p;
+        let groebner_3 = (t - 2.0 + m / (2.0 * ALPHA.log2()) - r_p) / (t - 1.0);
+
+        [
+            statistical,
+            interpolation,
+            groebner_1,
+            groebner_2,
+            groebner_3,
+        ]
+        .iter()
+        .all(|bound| r_f >= bound.ceil())
+    }
+
+    /// Returns the cheapest secure round numbers with the security margin, and the number of
+    /// partial rounds rounded up to a multiple of `width`.
+    fn derive_rounds<F: PrimeField>(width: usize) -> (usize, usize) {
+        let field_bits = F::MODULUS_BIT_SIZE as f64;
+        let log2_p = log2_modulus::<F>();
+        let mut best: Option<(usize, usize, usize)> = None;
+        for partial in 1..500 {
+            for full in (4..100).step_by(2) {
+                if !is_secure(field_bits, log2_p, width, full, partial) {
+                    continue;
+                }
+                let full = full + 2;
+                let partial = (partial as f64 * 1.075).ceil() as usize;
+                let cost = width * full + partial;
+                if best.map_or(true, |(best_cost, _, _)| cost < best_cost) {
+                    best = Some((cost, full, partial));
+                }
+            }
+        }
+        let (_, full, partial) = best.unwrap();
+        (full, (partial + width - 1) / width * width)
+    }
+
+    #[test]
+    fn test_round_numbers() {
+        for width in 2..=MAX_INPUTS + 1 {
+            assert_eq!(
+                bn254::ROUNDS[width - 2],
+                derive_rounds::<ark_bn254::Fr>(width)
+            );
+            assert_eq!(
+                bls12381::ROUNDS[width - 2],
+                derive_rounds::<ark_bls12_381::Fr>(width)
+            );
+        }
+    }
+
+    /// Checks that each table has `R_F + R_P` rounds of constants and a square MDS matrix for its
+    /// width, and that all constants are canonical field elements.
+    fn check_tables<F: PrimeField>(
+        rounds: &[(usize, usize)],
+        round_constants: &[&[&str]],
+        mds: &[&[&[&str]]],
+    ) {
+        for (index, (full, partial)) in rounds.iter().enumerate() {
+            let width = index + 2;
+            assert_eq!(round_constants[index].len(), (full + partial) * width);
+            assert_eq!(mds[index].len(), width);
+            let constants = round_constants[index]
+                .iter()
+                .chain(mds[index].iter().flat_map(|row| {
+                    assert_eq!(row.len(), width);
+                    row.iter()
+                }));
+            for constant in constants {
+                // Constants are reduced when parsed, so only canonical ones round-trip.
+                let bytes = hex::decode(constant.trim_start_matches("0x")).unwrap();
+                let parsed: F = parse_constant(constant);
+                assert_eq!(parsed.into_bigint().to_bytes_be(), bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tables() {
+        check_tables::<ark_bn254::Fr>(&bn254::ROUNDS, &bn254::ROUND_CONSTANTS, &bn254::MDS);
+        check_tables::<ark_bls12_381::Fr>(
+            &bls12381::ROUNDS,
+            &bls12381::ROUND_CONSTANTS,
+            &bls12381::MDS,
+        );
+    }
+
+    #[test]
+    fn test_bn254_circomlib_vectors() {
+        // circomlibjs `poseidon([1, 1, ...])` for 1 to 12 inputs.
+        let expected = [
+            "0x29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133",
+            "0x007af346e2d304279e79e0a9f3023f771294a78acb70e73f90afe27cad401e81",
+            "0x02c0066e10a72abd2b33c3b214cb3e81bcb1b6e30961cd23c202b18673bf2543",
+            "0x082c9c370a0d24f4416fbc414a37681f78442d27d86385991c17d6fc0c4b7d71",
+            "0x10389605ae688d4f14db853122c47d66a803c72b41589cb1bf868741b206b9bb",
+            "0x2a73f679328c3eab724aa3e5bdbf50b39035d7729f135b9709890f85c5dc5e76",
+            "0x2276310aa7f3343a284214139d9da959be2a31b2c708a5f81954b265e53a30b8",
+            "0x177e1453c446e1b07d2b4233425147095c4fcabb233d230b6d46a214d95b2884",
+            "0x0e8fee2fe49da30fdeeb48c42ebb44cc6ee7055f61fbca5e313b8a5fca834c47",
+            "0x2ec4c65e6378ab8c7330854f4a7077c1ff9260e44885c4b81dd131ad3a86cd96",
+            "0x00713d41eca635f117d4ecbceb5f3a66dc4142eb70b56765bc358f1bec40bb9b",
+            "0x14390be0baef249bd47c65ddac65c2e52e8513c081c1cd72c98006098e9a8fbe",
+        ];
+        for (i, expected) in expected.iter().enumerate() {
+            let inputs = vec![ark_bn254::Fr::from(1u64); i + 1];
+            assert_eq!(poseidon_bn254(&inputs), parse_constant(expected));
+        }
+
+        // circomlibjs `poseidon([1, 2])`.
+        let inputs = [ark_bn254::Fr::from(1u64), ark_bn254::Fr::from(2u64)];
+        assert_eq!(
+            poseidon_bn254(&inputs),
+            parse_constant("0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a")
+        );
+    }
+
+    #[test]
+    fn test_bls12381_reference_vectors() {
+        // The first output of the `x5_255_3` and `x5_255_5` permutation test vectors of the
+        // reference implementation, on the states `[0, 1, 2]` and `[0, 1, 2, 3, 4]`.
+        let inputs: Vec<ark_bls12_381::Fr> = (1..=2u64).map(Into::into).collect();
+        assert_eq!(
+            poseidon_bls12381(&inputs),
+            parse_constant("0x28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a")
+        );
+        let inputs: Vec<ark_bls12_381::Fr> = (1..=4u64).map(Into::into).collect();
+        assert_eq!(
+            poseidon_bls12381(&inputs),
+            parse_constant("0x2a918b9c9f9bd7bb509331c81e297b5707f6fc7393dcee1b13901a0b22202e18")
+        );
+    }
+}
@@ -6,23 +6,65 @@ use aptos_native_interface::{
     safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError,
     SafeNativeResult,
 };
+use aptos_types::contract_event::ContractEvent;
 #[cfg(feature = "testing")]
+use aptos_types::event::EventKey;
+use better_any::{Tid, TidAble};
 use move_binary_format::errors::PartialVMError;
-use move_core_types::account_address::AccountAddress;
 #[cfg(feature = "testing")]
-use move_core_types::vm_status::StatusCode;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::{language_storage::TypeTag, vm_status::StatusCode};
 use move_vm_runtime::native_functions::NativeFunction;
 #[cfg(feature = "testing")]
 use move_vm_types::values::{Reference, Struct, StructRef};
 use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
-use serde::Serialize;
 use smallvec::{smallvec, SmallVec};
 use std::collections::VecDeque;
 
-#[derive(Serialize)]
-pub struct GUID {
-    creation_num: u64,
-    addr: AccountAddress,
+/// Cached emitted events, in the order they were emitted. Events are emitted either to an
+/// event handle, or as module events identified by their type only.
+#[derive(Default, Tid)]
+pub struct NativeEventContext {
+    events: Vec<ContractEvent>,
+}
+
+impl NativeEventContext {
+    pub fn into_events(self) -> Vec<ContractEvent> {
+        self.events
+    }
+
+    #[cfg(feature = "testing")]
+    fn emitted_events(&self, event_key: &EventKey, ty_tag: &TypeTag) -> Vec<&[u8]> {
+        self.events
+            .iter()
+            .filter(|event| event.event_key() == Some(event_key) && event.type_tag() == ty_tag)
+            .map(|event| event.event_data())
+            .collect()
+    }
+
+    #[cfg(feature = "testing")]
+    fn emitted_module_events(&self, ty_tag: &TypeTag) -> Vec<&[u8]> {
+        self.events
+            .iter()
+            .filter(|event| !event.is_v0() && event.type_tag() == ty_tag)
+            .map(|event| event.event_data())
+            .collect()
+    }
+}
+
+/// Serializes the event payload `msg` of type `ty`, returning its type tag and BCS bytes.
+fn serialize_event(
+    context: &SafeNativeContext,
+    ty: &Type,
+    msg: &Value,
+) -> SafeNativeResult<(TypeTag, Vec<u8>)> {
+    let ty_tag = context.type_to_type_tag(ty)?;
+    let ty_layout = context.type_to_type_layout(ty)?;
+    let blob = msg.simple_serialize(&ty_layout).ok_or_else(|| {
+        PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+            .with_message("Event serialization failure".to_string())
+    })?;
+    Ok((ty_tag, blob))
 }
 
 /***************************************************************************************************
@@ -51,9 +93,13 @@ fn native_write_to_event_store(
             + EVENT_WRITE_TO_EVENT_STORE_PER_ABSTRACT_VALUE_UNIT * context.abs_val_size(&msg),
     )?;
 
-    if !context.save_event(guid, seq_num, ty, msg)? {
-        return Err(SafeNativeError::Abort { abort_code: 0 });
-    }
+    let key = bcs::from_bytes(guid.as_slice()).map_err(|_| {
+        SafeNativeError::InvariantViolation(PartialVMError::new(StatusCode::EVENT_KEY_MISMATCH))
+    })?;
+    let (ty_tag, blob) = serialize_event(context, &ty, &msg)?;
+    let ctx = context.extensions_mut().get_mut::<NativeEventContext>();
+    ctx.events
+        .push(ContractEvent::new(key, seq_num, ty_tag, blob));
 
     Ok(smallvec![])
 }
@@ -85,15 +131,89 @@ fn native_emitted_events_by_handle(
         .next()
         .ok_or_else(|| PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR))?
         .value_as::<AccountAddress>()?;
-    let guid = GUID { creation_num, addr };
-    let events = context.emitted_events(
-        bcs::to_bytes(&guid)
-            .map_err(|_| PartialVMError::new(StatusCode::VALUE_SERIALIZATION_ERROR))?,
-        ty,
-    )?;
+    let key = EventKey::new(creation_num, addr);
+    let ty_tag = context.type_to_type_tag(&ty)?;
+    let ty_layout = context.type_to_type_layout(&ty)?;
+    let ctx = context.extensions().get::<NativeEventContext>();
+    let events = ctx
+        .emitted_events(&key, &ty_tag)
+        .into_iter()
+        .map(|blob| {
+            Value::simple_deserialize(blob, &ty_layout).ok_or_else(|| {
+                SafeNativeError::InvariantViolation(PartialVMError::new(
+                    StatusCode::VALUE_DESERIALIZATION_ERROR,
+                ))
+            })
+        })
+        .collect::<SafeNativeResult<Vec<Value>>>()?;
+    Ok(smallvec![Value::vector_for_testing_only(events)])
+}
+
+#[cfg(feature = "testing")]
+fn native_emitted_events(
+    context: &mut SafeNativeContext,
+    mut ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.is_empty());
+
+    let ty = ty_args.pop().unwrap();
+    let ty_tag = context.type_to_type_tag(&ty)?;
+    let ty_layout = context.type_to_type_layout(&ty)?;
+    let ctx = context.extensions().get::<NativeEventContext>();
+    let events = ctx
+        .emitted_module_events(&ty_tag)
+        .into_iter()
+        .map(|blob| {
+            Value::simple_deserialize(blob, &ty_layout).ok_or_else(|| {
+                SafeNativeError::InvariantViolation(PartialVMError::new(
+                    StatusCode::VALUE_DESERIALIZATION_ERROR,
+                ))
+            })
+        })
+        .collect::<SafeNativeResult<Vec<Value>>>()?;
     Ok(smallvec![Value::vector_for_testing_only(events)])
 }
 
+/***************************************************************************************************
+ * native fun write_module_event_to_store
+ *
+ *   gas cost: base_cost + per_abstract_value_unit * abstract_size(msg)
+ *
+ **************************************************************************************************/
+#[inline]
+fn native_write_module_event_to_store(
+    context: &mut SafeNativeContext,
+    mut ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.len() == 1);
+
+    let ty = ty_args.pop().unwrap();
+    let msg = arguments.pop_back().unwrap();
+
+    context.charge(
+        EVENT_WRITE_MODULE_EVENT_TO_STORE_BASE
+            + EVENT_WRITE_MODULE_EVENT_TO_STORE_PER_ABSTRACT_VALUE_UNIT
+                * context.abs_val_size(&msg),
+    )?;
+
+    let (ty_tag, blob) = serialize_event(context, &ty, &msg)?;
+    // Module events are identified by their type, which must be a struct
+    if !matches!(ty_tag, TypeTag::Struct(_)) {
+        return Err(SafeNativeError::Abort {
+            abort_code: super::status::NFE_EXPECTED_STRUCT_TYPE_TAG,
+        });
+    }
+    let ctx = context.extensions_mut().get_mut::<NativeEventContext>();
+    ctx.events
+        .push(ContractEvent::new_module_event(ty_tag, blob));
+
+    Ok(smallvec![])
+}
+
 /***************************************************************************************************
  * module
  *
@@ -104,15 +224,24 @@ pub fn make_all(
     let mut natives = vec![];
 
     #[cfg(feature = "testing")]
-    natives.extend([(
-        "emitted_events_by_handle",
-        native_emitted_events_by_handle as RawSafeNative,
-    )]);
-
-    natives.extend([(
-        "write_to_event_store",
-        native_write_to_event_store as RawSafeNative,
-    )]);
+    natives.extend([
+        (
+            "emitted_events_by_handle",
+            native_emitted_events_by_handle as RawSafeNative,
+        ),
+        ("emitted_events", native_emitted_events),
+    ]);
+
+    natives.extend([
+        (
+            "write_to_event_store",
+            native_write_to_event_store as RawSafeNative,
+        ),
+        (
+            "write_module_event_to_store",
+            native_write_module_event_to_store,
+        ),
+    ]);
 
     builder.make_named_natives(natives)
 }
@@ -1,12 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::natives::cryptography::poseidon;
 use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
 use aptos_native_interface::{
-    safely_assert_eq, safely_pop_arg, RawSafeNative, SafeNativeBuilder, SafeNativeContext,
-    SafeNativeResult,
+    safely_assert_eq, safely_pop_arg, safely_pop_vec_arg, RawSafeNative, SafeNativeBuilder,
+    SafeNativeContext, SafeNativeError, SafeNativeResult,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use move_binary_format::errors::PartialVMError;
+use move_core_types::{
+    gas_algebra::{InternalGas, InternalGasPerByte, NumArgs, NumBytes},
+    vm_status::StatusCode,
 };
-use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
 use move_vm_runtime::native_functions::NativeFunction;
 use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
 use ripemd::Digest as OtherDigest;
@@ -15,6 +21,13 @@ use smallvec::{smallvec, SmallVec};
 use std::{collections::VecDeque, hash::Hasher};
 use tiny_keccak::{Hasher as KeccakHasher, Keccak};
 
+pub mod abort_codes {
+    /// Abort code when the number of Poseidon inputs is not supported, or an input is not a
+    /// canonically-serialized scalar (leading 0x01 == INVALID_ARGUMENT)
+    /// NOTE: This must match the code in the Move implementation
+    pub const NFE_INVALID_POSEIDON_INPUT: u64 = 0x01_0002;
+}
+
 /***************************************************************************************************
  * native fun sip_hash
  *
@@ -156,6 +169,87 @@ fn native_ripemd160(
     Ok(smallvec![Value::vector_u8(output)])
 }
 
+/***************************************************************************************************
+ * native fun poseidon_bn254_internal / poseidon_bls12381_internal
+ *
+ *   gas cost: base_cost + unit_cost * (num_inputs + 1)^2
+ *
+ **************************************************************************************************/
+fn deserialize_poseidon_inputs<F: CanonicalDeserialize>(
+    inputs: Vec<Vec<u8>>,
+) -> SafeNativeResult<Vec<F>> {
+    if inputs.is_empty() || inputs.len() > poseidon::MAX_INPUTS {
+        return Err(SafeNativeError::Abort {
+            abort_code: abort_codes::NFE_INVALID_POSEIDON_INPUT,
+        });
+    }
+
+    inputs
+        .iter()
+        .map(|bytes| {
+            if bytes.len() != 32 {
+                return None;
+            }
+            F::deserialize_uncompressed(bytes.as_slice()).ok()
+        })
+        .collect::<Option<Vec<F>>>()
+        .ok_or(SafeNativeError::Abort {
+            abort_code: abort_codes::NFE_INVALID_POSEIDON_INPUT,
+        })
+}
+
+fn serialize_poseidon_output<F: CanonicalSerialize>(output: F) -> SafeNativeResult<Value> {
+    let mut bytes = vec![];
+    output
+        .serialize_uncompressed(&mut bytes)
+        .map_err(|_| PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR))?;
+    Ok(Value::vector_u8(bytes))
+}
+
+fn native_poseidon_bn254(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    safely_assert_eq!(_ty_args.len(), 0);
+    safely_assert_eq!(args.len(), 1);
+
+    let inputs = safely_pop_vec_arg!(args, Vec<u8>);
+
+    let width = inputs.len() as u64 + 1;
+    context.charge(
+        HASH_POSEIDON_BN254_BASE
+            + HASH_POSEIDON_BN254_PER_WIDTH_SQUARED * NumArgs::new(width * width),
+    )?;
+
+    let inputs = deserialize_poseidon_inputs::<ark_bn254::Fr>(inputs)?;
+    let output = poseidon::poseidon_bn254(&inputs);
+
+    Ok(smallvec![serialize_poseidon_output(output)?])
+}
+
+fn native_poseidon_bls12381(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    safely_assert_eq!(_ty_args.len(), 0);
+    safely_assert_eq!(args.len(), 1);
+
+    let inputs = safely_pop_vec_arg!(args, Vec<u8>);
+
+    let width = inputs.len() as u64 + 1;
+    context.charge(
+        HASH_POSEIDON_BLS12381_BASE
+            + HASH_POSEIDON_BLS12381_PER_WIDTH_SQUARED * NumArgs::new(width * width),
+    )?;
+
+    let inputs = deserialize_poseidon_inputs::<ark_bls12_381::Fr>(inputs)?;
+    let output = poseidon::poseidon_bls12381(&inputs);
+
+    Ok(smallvec![serialize_poseidon_output(output)?])
+}
+
 /***************************************************************************************************
  * module
  *
@@ -170,6 +264,8 @@ pub fn make_all(
         ("sha3_512_internal", native_sha3_512),
         ("ripemd160_internal", native_ripemd160),
         ("blake2b_256_internal", native_blake2b_256),
+        ("poseidon_bn254_internal", native_poseidon_bn254),
+        ("poseidon_bls12381_internal", native_poseidon_bls12381),
     ];
 
     builder.make_named_natives(natives)
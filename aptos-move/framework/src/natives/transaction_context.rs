@@ -3,15 +3,60 @@
 
 use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
 use aptos_native_interface::{
-    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeResult,
+    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeError, SafeNativeResult,
+};
+use aptos_types::transaction::{
+    authenticator::{AuthenticationKey, AuthenticationKeyPreimage},
+    EntryFunction,
 };
-use aptos_types::transaction::authenticator::{AuthenticationKey, AuthenticationKeyPreimage};
 use better_any::{Tid, TidAble};
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{NumArgs, NumBytes},
+};
 use move_vm_runtime::native_functions::NativeFunction;
-use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    values::{Struct, Value, Vector},
+};
 use smallvec::{smallvec, SmallVec};
-use std::collections::VecDeque;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Abort code when the natives describing the user transaction are called outside of the
+/// execution of a user transaction, e.g. in a block prologue.
+/// NOTE: this must match the code in the Move implementation.
+pub const ETRANSACTION_CONTEXT_NOT_AVAILABLE: u64 = 3;
+
+/// The parts of a user transaction exposed to Move code executed on its behalf.
+#[derive(Clone, Debug)]
+pub struct UserTransactionContext {
+    sender: AccountAddress,
+    secondary_signers: Vec<AccountAddress>,
+    fee_payer: AccountAddress,
+    max_gas_amount: u64,
+    gas_unit_price: u64,
+    entry_function_payload: Option<Arc<EntryFunction>>,
+}
+
+impl UserTransactionContext {
+    pub fn new(
+        sender: AccountAddress,
+        secondary_signers: Vec<AccountAddress>,
+        fee_payer: AccountAddress,
+        max_gas_amount: u64,
+        gas_unit_price: u64,
+        entry_function_payload: Option<Arc<EntryFunction>>,
+    ) -> Self {
+        Self {
+            sender,
+            secondary_signers,
+            fee_payer,
+            max_gas_amount,
+            gas_unit_price,
+            entry_function_payload,
+        }
+    }
+}
 
 /// The native transaction context extension. This needs to be attached to the
 /// NativeContextExtensions value which is passed into session functions, so its accessible from
@@ -23,17 +68,25 @@ pub struct NativeTransactionContext {
     auid_counter: u64,
     script_hash: Vec<u8>,
     chain_id: u8,
+    /// Only set when executing a user transaction.
+    user_transaction_context_opt: Option<UserTransactionContext>,
 }
 
 impl NativeTransactionContext {
     /// Create a new instance of a native transaction context. This must be passed in via an
     /// extension into VM session functions.
-    pub fn new(txn_hash: Vec<u8>, script_hash: Vec<u8>, chain_id: u8) -> Self {
+    pub fn new(
+        txn_hash: Vec<u8>,
+        script_hash: Vec<u8>,
+        chain_id: u8,
+        user_transaction_context_opt: Option<UserTransactionContext>,
+    ) -> Self {
         Self {
             txn_hash,
             auid_counter: 0,
             script_hash,
             chain_id,
+            user_transaction_context_opt,
         }
     }
 
@@ -42,6 +95,19 @@ impl NativeTransactionContext {
     }
 }
 
+fn get_user_transaction_context<'a>(
+    context: &'a SafeNativeContext,
+) -> SafeNativeResult<&'a UserTransactionContext> {
+    context
+        .extensions()
+        .get::<NativeTransactionContext>()
+        .user_transaction_context_opt
+        .as_ref()
+        .ok_or(SafeNativeError::Abort {
+            abort_code: ETRANSACTION_CONTEXT_NOT_AVAILABLE,
+        })
+}
+
 /***************************************************************************************************
  * native fun get_txn_hash
  *
@@ -111,6 +177,191 @@ fn native_get_script_hash(
     )])
 }
 
+/***************************************************************************************************
+ * native fun sender_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_sender_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_SENDER_BASE)?;
+
+    let user_transaction_context = get_user_transaction_context(context)?;
+    Ok(smallvec![Value::address(user_transaction_context.sender)])
+}
+
+/***************************************************************************************************
+ * native fun secondary_signers_internal
+ *
+ *   gas cost: base_cost + per_signer * num_secondary_signers
+ *
+ **************************************************************************************************/
+fn native_secondary_signers_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_SECONDARY_SIGNERS_BASE)?;
+
+    let secondary_signers = get_user_transaction_context(context)?
+        .secondary_signers
+        .clone();
+    context.charge(
+        TRANSACTION_CONTEXT_SECONDARY_SIGNERS_PER_SIGNER
+            * NumArgs::new(secondary_signers.len() as u64),
+    )?;
+
+    Ok(smallvec![Value::vector_address(secondary_signers)])
+}
+
+/***************************************************************************************************
+ * native fun fee_payer_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_fee_payer_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_FEE_PAYER_BASE)?;
+
+    let user_transaction_context = get_user_transaction_context(context)?;
+    Ok(smallvec![Value::address(
+        user_transaction_context.fee_payer
+    )])
+}
+
+/***************************************************************************************************
+ * native fun max_gas_amount_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_max_gas_amount_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_MAX_GAS_AMOUNT_BASE)?;
+
+    let user_transaction_context = get_user_transaction_context(context)?;
+    Ok(smallvec![Value::u64(
+        user_transaction_context.max_gas_amount
+    )])
+}
+
+/***************************************************************************************************
+ * native fun gas_unit_price_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_gas_unit_price_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_GAS_UNIT_PRICE_BASE)?;
+
+    let user_transaction_context = get_user_transaction_context(context)?;
+    Ok(smallvec![Value::u64(
+        user_transaction_context.gas_unit_price
+    )])
+}
+
+/***************************************************************************************************
+ * native fun chain_id_internal
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_chain_id_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_CHAIN_ID_BASE)?;
+
+    let chain_id = context
+        .extensions()
+        .get::<NativeTransactionContext>()
+        .chain_id;
+    Ok(smallvec![Value::u8(chain_id)])
+}
+
+fn create_string_value(s: String) -> Value {
+    Value::struct_(Struct::pack(vec![Value::vector_u8(s.into_bytes())]))
+}
+
+fn create_vector_u8_vector_value(
+    vectors: impl IntoIterator<Item = Vec<u8>>,
+) -> SafeNativeResult<Value> {
+    Ok(Vector::pack(
+        &Type::Vector(Box::new(Type::U8)),
+        vectors.into_iter().map(Value::vector_u8).collect(),
+    )?)
+}
+
+/***************************************************************************************************
+ * native fun entry_function_payload_internal
+ *
+ *   gas cost: base_cost + per_byte * (length of names, type arguments and arguments)
+ *
+ **************************************************************************************************/
+fn native_entry_function_payload_internal(
+    context: &mut SafeNativeContext,
+    mut _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(TRANSACTION_CONTEXT_ENTRY_FUNCTION_PAYLOAD_BASE)?;
+
+    let entry_function = match &get_user_transaction_context(context)?.entry_function_payload {
+        Some(entry_function) => entry_function.clone(),
+        None => {
+            return Ok(smallvec![
+                Value::bool(false),
+                Value::address(AccountAddress::ZERO),
+                create_string_value(String::new()),
+                create_string_value(String::new()),
+                create_vector_u8_vector_value(vec![])?,
+                create_vector_u8_vector_value(vec![])?,
+            ])
+        },
+    };
+
+    let module_name = entry_function.module().name().to_string();
+    let function_name = entry_function.function().to_string();
+    let ty_arg_names: Vec<String> = entry_function
+        .ty_args()
+        .iter()
+        .map(|ty| ty.to_string())
+        .collect();
+    let args = entry_function.args().to_vec();
+
+    let num_bytes = module_name.len()
+        + function_name.len()
+        + ty_arg_names.iter().map(|name| name.len()).sum::<usize>()
+        + args.iter().map(|arg| arg.len()).sum::<usize>();
+    context.charge(
+        TRANSACTION_CONTEXT_ENTRY_FUNCTION_PAYLOAD_PER_BYTE * NumBytes::new(num_bytes as u64),
+    )?;
+
+    Ok(smallvec![
+        Value::bool(true),
+        Value::address(*entry_function.module().address()),
+        create_string_value(module_name),
+        create_string_value(function_name),
+        create_vector_u8_vector_value(ty_arg_names.into_iter().map(String::into_bytes))?,
+        create_vector_u8_vector_value(args)?,
+    ])
+}
+
 /***************************************************************************************************
  * module
  *
@@ -122,6 +373,19 @@ pub fn make_all(
         ("get_script_hash", native_get_script_hash as RawSafeNative),
         ("generate_unique_address", native_generate_unique_address),
         ("get_txn_hash", native_get_txn_hash),
+        ("sender_internal", native_sender_internal),
+        (
+            "secondary_signers_internal",
+            native_secondary_signers_internal,
+        ),
+        ("fee_payer_internal", native_fee_payer_internal),
+        ("max_gas_amount_internal", native_max_gas_amount_internal),
+        ("gas_unit_price_internal", native_gas_unit_price_internal),
+        ("chain_id_internal", native_chain_id_internal),
+        (
+            "entry_function_payload_internal",
+            native_entry_function_payload_internal,
+        ),
     ];
 
     builder.make_named_natives(natives)
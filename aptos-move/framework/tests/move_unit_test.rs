@@ -4,7 +4,7 @@
 
 use aptos_framework::path_in_crate;
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
-use aptos_types::on_chain_config::{Features, TimedFeatures};
+use aptos_types::on_chain_config::{FeatureFlag, Features, TimedFeatures};
 use aptos_vm::natives;
 use move_cli::base::test::{run_move_unit_tests, UnitTestResult};
 use move_unit_test::UnitTestingConfig;
@@ -36,13 +36,16 @@ fn run_tests_for_pkg(path_to_pkg: impl Into<String>) {
 pub fn aptos_test_natives() -> NativeFunctionTable {
     // By side effect, configure for unit tests
     natives::configure_for_unit_test();
+    // BN254 structures are not enabled by default, but the aptos-stdlib unit tests cover them
+    let mut features = Features::default();
+    features.enable(FeatureFlag::BN254_STRUCTURES);
     // move_stdlib has the testing feature enabled to include debug native functions
     natives::aptos_natives(
         LATEST_GAS_FEATURE_VERSION,
         NativeGasParameters::zeros(),
         MiscGasParameters::zeros(),
         TimedFeatures::enable_all(),
-        Features::default(),
+        features,
     )
 }
 
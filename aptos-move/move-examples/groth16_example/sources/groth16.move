@@ -1,6 +1,6 @@
 /// Generic implementation of Groth16 (proof verification) as defined in https://eprint.iacr.org/2016/260.pdf, Section 3.2.
 /// Actual proof verifiers can be constructed using the pairings supported in the generic algebra module.
-/// See the test cases in this module for an example of constructing with BLS12-381 curves.
+/// See the test cases in this module for examples of constructing with BLS12-381 and BN254 curves.
 module groth16_example::groth16 {
     use aptos_std::crypto_algebra::{Element, from_u64, multi_scalar_mul, eq, multi_pairing, upcast, pairing, add, zero};
 
@@ -80,6 +80,8 @@ module groth16_example::groth16 {
     use aptos_std::crypto_algebra::{deserialize, enable_cryptography_algebra_natives};
     #[test_only]
     use aptos_std::bls12381_algebra::{Fr, FormatFrLsb, FormatG1Compr, FormatG2Compr, FormatFq12LscLsb, G1, G2, Gt, Fq12, FormatGt};
+    #[test_only]
+    use aptos_std::bn254_algebra::{Fr as BnFr, FormatFrLsb as BnFormatFrLsb, FormatG1Compr as BnFormatG1Compr, FormatG2Compr as BnFormatG2Compr, G1 as BnG1, G2 as BnG2, Gt as BnGt};
 
     #[test(fx = @std)]
     fun test_verify_proof_with_bls12381(fx: signer) {
@@ -175,4 +177,69 @@ module groth16_example::groth16 {
             &proof_c,
         ), 1);
     }
+
+    #[test(fx = @std)]
+    fun test_verify_proof_with_bn254(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        // Below is a single-input proof constructed from known discrete logs of all the verification key and proof elements,
+        // so that `e(A,B) = e(alpha,beta) + e(ic_0 + input * ic_1, gamma) + e(C,delta)` holds.
+        let vk_alpha_g1 = std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"f598ccddc4a974f473e4ef22b3759e33cb3dcdb2ccbb481a413133922fbc2308"));
+        let vk_beta_g2 = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"abc2d7f2d98ee6be91be2629a1323daa2873c7dc39e4ef3000ed6a8c8b6f9a1f006cb359648a6ef5b3ce05f381b32cd0680e92294ab60ec808b4b75bb8ad79a8"));
+        let vk_gamma_g2 = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"a1b34de74d51ba02758ed17231d291195d26f489cb5ac57d354057682a30060d06121fc88ecce0568d843f0ee541d92d0ac6e6a14069bc1b1a226527678c6d99"));
+        let vk_delta_g2 = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"6edcbc80e1452960df3372f0b192cc7a9664bf399f644d9aae6f17e5f3fc87127f8ddfb0763b9dec22e25e859c7bc21b6a14d1976f3c5b5c613f92026fe958a5"));
+        let vk_gamma_abc_g1: vector<Element<BnG1>> = vector[
+            std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"4ef5848ea56f5be572613805858a4a946c281f376c1699e56e5504c9d3eba42b")),
+            std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"01c4aa66e7819400fe1c6855cac2ccdbacdfb6a5b2f623d713db8e1c580da008")),
+        ];
+        let public_inputs: vector<Element<BnFr>> = vector[
+            std::option::extract(&mut deserialize<BnFr, BnFormatFrLsb>(&x"1c2c993d2788df81437b9a9f2af66083f69802ace7b674066c090a649033db07")),
+        ];
+        let proof_a = std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"9965fbae3bb441dcbd89a6657a5a3ca97ffad9702920386a6adf6735cb9a88a6"));
+        let proof_b = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"9ece99911173f2ed65a307021c3f1e0bf1b518be95aca0b91d12c173eb1be6109f3687ebbda5b2e5fcd4b9781ceb22635f6618820c9794162146e562ec9e44ab"));
+        let proof_c = std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"0949e08a2cdf59d97ae9bf7ccdadb86fd5dd71fca99d961753b830d2da5abd13"));
+        assert!(verify_proof<BnG1, BnG2, BnGt, BnFr>(
+            &vk_alpha_g1,
+            &vk_beta_g2,
+            &vk_gamma_g2,
+            &vk_delta_g2,
+            &vk_gamma_abc_g1,
+            &public_inputs,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+        ), 1);
+    }
+
+    #[test(fx = @std)]
+    fun test_verify_proof_prepared_with_bn254(fx: signer) {
+        enable_cryptography_algebra_natives(&fx);
+
+        // Same proof as in `test_verify_proof_with_bn254()`, verified with a prepared verification key.
+        let vk_alpha_g1 = std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"f598ccddc4a974f473e4ef22b3759e33cb3dcdb2ccbb481a413133922fbc2308"));
+        let vk_beta_g2 = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"abc2d7f2d98ee6be91be2629a1323daa2873c7dc39e4ef3000ed6a8c8b6f9a1f006cb359648a6ef5b3ce05f381b32cd0680e92294ab60ec808b4b75bb8ad79a8"));
+        let pvk_alpha_g1_beta_g2 = pairing<BnG1, BnG2, BnGt>(&vk_alpha_g1, &vk_beta_g2);
+        let pvk_gamma_g2_neg = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"a1b34de74d51ba02758ed17231d291195d26f489cb5ac57d354057682a30060d06121fc88ecce0568d843f0ee541d92d0ac6e6a14069bc1b1a226527678c6d19"));
+        let pvk_delta_g2_neg = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"6edcbc80e1452960df3372f0b192cc7a9664bf399f644d9aae6f17e5f3fc87127f8ddfb0763b9dec22e25e859c7bc21b6a14d1976f3c5b5c613f92026fe95825"));
+        let vk_gamma_abc_g1: vector<Element<BnG1>> = vector[
+            std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"4ef5848ea56f5be572613805858a4a946c281f376c1699e56e5504c9d3eba42b")),
+            std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"01c4aa66e7819400fe1c6855cac2ccdbacdfb6a5b2f623d713db8e1c580da008")),
+        ];
+        let public_inputs: vector<Element<BnFr>> = vector[
+            std::option::extract(&mut deserialize<BnFr, BnFormatFrLsb>(&x"1c2c993d2788df81437b9a9f2af66083f69802ace7b674066c090a649033db07")),
+        ];
+        let proof_a = std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"9965fbae3bb441dcbd89a6657a5a3ca97ffad9702920386a6adf6735cb9a88a6"));
+        let proof_b = std::option::extract(&mut deserialize<BnG2, BnFormatG2Compr>(&x"9ece99911173f2ed65a307021c3f1e0bf1b518be95aca0b91d12c173eb1be6109f3687ebbda5b2e5fcd4b9781ceb22635f6618820c9794162146e562ec9e44ab"));
+        let proof_c = std::option::extract(&mut deserialize<BnG1, BnFormatG1Compr>(&x"0949e08a2cdf59d97ae9bf7ccdadb86fd5dd71fca99d961753b830d2da5abd13"));
+        assert!(verify_proof_prepared<BnG1, BnG2, BnGt, BnFr>(
+            &pvk_alpha_g1_beta_g2,
+            &pvk_gamma_g2_neg,
+            &pvk_delta_g2_neg,
+            &vk_gamma_abc_g1,
+            &public_inputs,
+            &proof_a,
+            &proof_b,
+            &proof_c,
+        ), 1);
+    }
 }
@@ -4,7 +4,7 @@
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
-    on_chain_config::{Features, TimedFeatures},
+    on_chain_config::{FeatureFlag, Features, TimedFeatures},
 };
 use aptos_vm::natives;
 use move_cli::base::test::{run_move_unit_tests, UnitTestResult};
@@ -50,12 +50,15 @@ pub fn run_tests_for_pkg(
 
 pub fn aptos_test_natives() -> NativeFunctionTable {
     natives::configure_for_unit_test();
+    // BN254 structures are not enabled by default, but the groth16 example covers them
+    let mut features = Features::default();
+    features.enable(FeatureFlag::BN254_STRUCTURES);
     natives::aptos_natives(
         LATEST_GAS_FEATURE_VERSION,
         NativeGasParameters::zeros(),
         MiscGasParameters::zeros(),
         TimedFeatures::enable_all(),
-        Features::default(),
+        features,
     )
 }
 
@@ -20,7 +20,7 @@ use aptos_gas_schedule::{
 use aptos_types::{
     account_config::{self, aptos_test_root_address, events::NewEpochEvent, CORE_CODE_ADDRESS},
     chain_id::ChainId,
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, ContractEventV0},
     on_chain_config::{
         FeatureFlag, Features, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig,
         TimedFeatures, APTOS_MAX_KNOWN_VERSION,
@@ -418,6 +418,13 @@ pub fn default_features() -> Vec<FeatureFlag> {
         FeatureFlag::APTOS_UNIQUE_IDENTIFIERS,
         FeatureFlag::GAS_PAYER_ENABLED,
         FeatureFlag::BULLETPROOFS_NATIVES,
+        FeatureFlag::MODULE_EVENT,
+        FeatureFlag::BN254_STRUCTURES,
+        FeatureFlag::POSEIDON_NATIVES,
+        FeatureFlag::WEBAUTHN_SIGNATURE,
+        FeatureFlag::SECP256K1_ECDSA_AUTHENTICATOR,
+        FeatureFlag::TRANSACTION_CONTEXT_EXTENSION,
+        FeatureFlag::ACCOUNT_ABSTRACTION,
     ]
 }
 
@@ -629,8 +636,9 @@ fn emit_new_block_and_epoch_event(session: &mut SessionExt) {
 
 /// Verify the consistency of the genesis `WriteSet`
 fn verify_genesis_write_set(events: &[ContractEvent]) {
-    let new_epoch_events: Vec<&ContractEvent> = events
+    let new_epoch_events: Vec<&ContractEventV0> = events
         .iter()
+        .filter_map(|e| e.v0().ok())
         .filter(|e| e.key() == &NewEpochEvent::event_key())
         .collect();
     assert_eq!(
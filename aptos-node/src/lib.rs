@@ -166,6 +166,7 @@ pub fn load_seed(input: &str) -> Result<Option<[u8; 32]>, FromHexError> {
 
 /// Runtime handle to ensure that all inner runtimes stay in scope
 pub struct AptosHandle {
+    _admin_service_runtime: Option<Runtime>,
     _api_runtime: Option<Runtime>,
     _backup_runtime: Option<Runtime>,
     _consensus_runtime: Option<Runtime>,
@@ -469,7 +470,7 @@ pub fn setup_environment_and_start_node(
     services::start_node_inspection_service(&node_config, peers_and_metadata.clone());
 
     // Set up the storage database and any RocksDB checkpoints
-    let (aptos_db, db_rw, backup_service, genesis_waypoint) =
+    let (aptos_db, db_rw, backup_service, admin_service, genesis_waypoint) =
         storage::initialize_database_and_checkpoints(&mut node_config)?;
 
     // Set the Aptos VM configurations
@@ -562,6 +563,7 @@ pub fn setup_environment_and_start_node(
     });
 
     Ok(AptosHandle {
+        _admin_service_runtime: admin_service,
         _api_runtime: api_runtime,
         _backup_runtime: backup_service,
         _consensus_runtime: consensus_runtime,
@@ -9,36 +9,45 @@ use aptos_logger::{debug, info};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_types::waypoint::Waypoint;
 use aptos_vm::AptosVM;
-use std::{fs, net::SocketAddr, path::Path, sync::Arc, time::Instant};
+use std::{fs, path::Path, sync::Arc, time::Instant};
 use tokio::runtime::Runtime;
 
 #[cfg(not(feature = "consensus-only-perf-test"))]
 pub(crate) fn bootstrap_db(
     aptos_db: AptosDB,
-    backup_service_address: SocketAddr,
-) -> (Arc<AptosDB>, DbReaderWriter, Option<Runtime>) {
+    node_config: &NodeConfig,
+) -> (
+    Arc<AptosDB>,
+    DbReaderWriter,
+    Option<Runtime>,
+    Option<Runtime>,
+) {
+    use aptos_admin_service::start_admin_service;
     use aptos_backup_service::start_backup_service;
 
     let (aptos_db, db_rw) = DbReaderWriter::wrap(aptos_db);
-    let db_backup_service = start_backup_service(backup_service_address, aptos_db.clone());
-    (aptos_db, db_rw, Some(db_backup_service))
+    let db_backup_service =
+        start_backup_service(node_config.storage.backup_service_address, aptos_db.clone());
+    let admin_service = start_admin_service(node_config, aptos_db.clone());
+    (aptos_db, db_rw, Some(db_backup_service), admin_service)
 }
 
 /// In consensus-only mode, return a in-memory based [FakeAptosDB] and
-/// do not run the backup service.
+/// do not run the backup or admin services.
 #[cfg(feature = "consensus-only-perf-test")]
 pub(crate) fn bootstrap_db(
     aptos_db: AptosDB,
-    _backup_service_address: SocketAddr,
+    _node_config: &NodeConfig,
 ) -> (
     Arc<aptos_db::fake_aptosdb::FakeAptosDB>,
     DbReaderWriter,
     Option<Runtime>,
+    Option<Runtime>,
 ) {
     use aptos_db::fake_aptosdb::FakeAptosDB;
 
     let (aptos_db, db_rw) = DbReaderWriter::wrap(FakeAptosDB::new(aptos_db));
-    (aptos_db, db_rw, None)
+    (aptos_db, db_rw, None, None)
 }
 
 /// Creates a RocksDb checkpoint for the consensus_db, state_sync_db,
@@ -83,11 +92,17 @@ fn create_rocksdb_checkpoint_and_change_working_dir(
 }
 
 /// Creates any rocksdb checkpoints, opens the storage database,
-/// starts the backup service, handles genesis initialization and returns
-/// the various handles.
+/// starts the backup and admin services, handles genesis initialization
+/// and returns the various handles.
 pub fn initialize_database_and_checkpoints(
     node_config: &mut NodeConfig,
-) -> anyhow::Result<(Arc<dyn DbReader>, DbReaderWriter, Option<Runtime>, Waypoint)> {
+) -> anyhow::Result<(
+    Arc<dyn DbReader>,
+    DbReaderWriter,
+    Option<Runtime>,
+    Option<Runtime>,
+    Waypoint,
+)> {
     // If required, create RocksDB checkpoints and change the working directory.
     // This is test-only.
     if let Some(working_dir) = node_config.base.working_dir.clone() {
@@ -106,8 +121,7 @@ pub fn initialize_database_and_checkpoints(
         node_config.storage.max_num_nodes_per_lru_cache_shard,
     )
     .map_err(|err| anyhow!("DB failed to open {}", err))?;
-    let (aptos_db, db_rw, backup_service) =
-        bootstrap_db(aptos_db, node_config.storage.backup_service_address);
+    let (aptos_db, db_rw, backup_service, admin_service) = bootstrap_db(aptos_db, node_config);
 
     // TODO: handle non-genesis waypoints for state sync!
     // If there's a genesis txn and waypoint, commit it if the result matches.
@@ -125,5 +139,11 @@ pub fn initialize_database_and_checkpoints(
         instant.elapsed().as_millis()
     );
 
-    Ok((aptos_db, db_rw, backup_service, genesis_waypoint))
+    Ok((
+        aptos_db,
+        db_rw,
+        backup_service,
+        admin_service,
+        genesis_waypoint,
+    ))
 }
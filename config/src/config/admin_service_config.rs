@@ -0,0 +1,177 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::{config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig},
+    utils,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdminServiceConfig {
+    pub enabled: bool,
+    pub address: String,
+    pub port: u16,
+    // If empty, all requests will be rejected
+    pub authentication_configs: Vec<AuthenticationConfig>,
+    // The directory in which all DB checkpoints are created (requests
+    // may only specify checkpoint paths relative to this directory).
+    pub checkpoint_dir: PathBuf,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthenticationConfig {
+    // The SHA256 hash (hex encoded) of the passcode expected in each request
+    PasscodeSha256(String),
+}
+
+impl Default for AdminServiceConfig {
+    fn default() -> AdminServiceConfig {
+        AdminServiceConfig {
+            enabled: false,
+            address: "127.0.0.1".to_string(),
+            port: 9102,
+            authentication_configs: vec![],
+            checkpoint_dir: PathBuf::from("/opt/aptos/data/db_checkpoints"),
+        }
+    }
+}
+
+impl AdminServiceConfig {
+    pub fn randomize_ports(&mut self) {
+        self.port = utils::get_available_port();
+    }
+}
+
+impl ConfigSanitizer for AdminServiceConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
+        _node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let admin_service_config = &node_config.admin_service;
+
+        // Verify that the admin service is never enabled without authentication
+        if admin_service_config.enabled && admin_service_config.authentication_configs.is_empty() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The admin service must have at least one authentication config when enabled!"
+                    .to_string(),
+            ));
+        }
+
+        // Verify that the checkpoint directory is an absolute path
+        if admin_service_config.enabled && !admin_service_config.checkpoint_dir.is_absolute() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The admin service checkpoint directory must be an absolute path! Found: {:?}",
+                    admin_service_config.checkpoint_dir
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_disabled_service_config() {
+        // Create a disabled admin service config without authentication
+        let mut node_config = NodeConfig {
+            admin_service: AdminServiceConfig {
+                enabled: false,
+                authentication_configs: vec![],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the configuration is sanitized successfully
+        AdminServiceConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sanitize_valid_service_config() {
+        // Create an enabled admin service config with authentication
+        let mut node_config = NodeConfig {
+            admin_service: AdminServiceConfig {
+                enabled: true,
+                authentication_configs: vec![AuthenticationConfig::PasscodeSha256(
+                    "test_passcode_hash".to_string(),
+                )],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the configuration is sanitized successfully
+        AdminServiceConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sanitize_missing_authentication() {
+        // Create an enabled admin service config without authentication
+        let mut node_config = NodeConfig {
+            admin_service: AdminServiceConfig {
+                enabled: true,
+                authentication_configs: vec![],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = AdminServiceConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_relative_checkpoint_dir() {
+        // Create an enabled admin service config with a relative checkpoint directory
+        let mut node_config = NodeConfig {
+            admin_service: AdminServiceConfig {
+                enabled: true,
+                authentication_configs: vec![AuthenticationConfig::PasscodeSha256(
+                    "test_passcode_hash".to_string(),
+                )],
+                checkpoint_dir: PathBuf::from("db_checkpoints"),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = AdminServiceConfig::sanitize(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+}
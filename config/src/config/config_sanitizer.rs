@@ -1,12 +1,17 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::config::{
-    node_config_loader::NodeType,
-    utils::{are_failpoints_enabled, get_config_name},
-    ApiConfig, BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig,
-    IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig, NodeConfig,
-    PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig,
+use crate::{
+    config::{
+        node_config_loader::NodeType,
+        utils::{are_failpoints_enabled, get_config_name},
+        AdminServiceConfig, ApiConfig, BaseConfig, ConsensusConfig, DiscoveryMethod, Error,
+        ExecutionConfig, Identity, IndexerConfig, IndexerGrpcConfig, InspectionServiceConfig,
+        LoggerConfig, MempoolConfig, NetworkConfig, NodeConfig, OutboundQueueConfig,
+        PeerMonitoringServiceConfig, ProtocolPriorityClass, StateSyncConfig, StorageConfig,
+        TrafficRecorderConfig, PROTOCOL_NAMES,
+    },
+    network_id::NetworkId,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -42,6 +47,7 @@ impl ConfigSanitizer for NodeConfig {
         chain_id: ChainId,
     ) -> Result<(), Error> {
         // Sanitize all of the sub-configs
+        AdminServiceConfig::sanitize(node_config, node_type, chain_id)?;
         ApiConfig::sanitize(node_config, node_type, chain_id)?;
         BaseConfig::sanitize(node_config, node_type, chain_id)?;
         ConsensusConfig::sanitize(node_config, node_type, chain_id)?;
@@ -139,6 +145,33 @@ fn sanitize_fullnode_network_configs(
             ));
         }
 
+        // Verify that peer exchange discovery is only used on the public network
+        let uses_peer_exchange = fullnode_network_config
+            .discovery_methods()
+            .into_iter()
+            .any(|method| matches!(method, DiscoveryMethod::PeerExchange(_)));
+        if uses_peer_exchange && network_id != NetworkId::Public {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "Peer exchange discovery is only supported on the public network! Found: {}",
+                    network_id
+                ),
+            ));
+        }
+
+        // Verify the outbound queue config
+        sanitize_outbound_queue_config(&sanitizer_name, &fullnode_network_config.outbound_queue)?;
+
+        // Verify the traffic recorder config
+        sanitize_traffic_recorder_config(
+            &sanitizer_name,
+            &fullnode_network_config.traffic_recorder,
+        )?;
+
+        // Verify the identity key rotation config
+        sanitize_identity_key_rotation_config(&sanitizer_name, fullnode_network_config)?;
+
         // Prepare the network id
         fullnode_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -146,6 +179,113 @@ fn sanitize_fullnode_network_configs(
     Ok(())
 }
 
+/// Sanitize the outbound queue config of a network
+fn sanitize_outbound_queue_config(
+    sanitizer_name: &str,
+    outbound_queue_config: &OutboundQueueConfig,
+) -> Result<(), Error> {
+    // Verify that the queue can hold messages and that every round makes progress
+    if outbound_queue_config.max_queued_messages == 0 || outbound_queue_config.quantum_bytes == 0 {
+        return Err(Error::ConfigSanitizerFailed(
+            sanitizer_name.to_string(),
+            "The outbound queue size and quantum must be greater than 0!".into(),
+        ));
+    }
+
+    // Verify that no priority class is starved
+    for priority_class in ProtocolPriorityClass::all() {
+        if outbound_queue_config.weight(*priority_class) == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "The outbound queue weight of each priority class must be greater than 0! Found 0 for: {}",
+                    priority_class.as_str()
+                ),
+            ));
+        }
+    }
+
+    // Verify that the quotas are for known protocols, and that no protocol is blocked entirely
+    for (protocol_name, quota) in &outbound_queue_config.protocol_bandwidth_quotas {
+        if !PROTOCOL_NAMES.contains(&protocol_name.as_str()) {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "Unknown protocol in the outbound bandwidth quotas: {}",
+                    protocol_name
+                ),
+            ));
+        }
+        if *quota == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name.to_string(),
+                format!(
+                    "The outbound bandwidth quota of a protocol must be greater than 0! Found 0 for: {}",
+                    protocol_name
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Sanitize the traffic recorder config of a network
+fn sanitize_traffic_recorder_config(
+    sanitizer_name: &str,
+    traffic_recorder_config: &TrafficRecorderConfig,
+) -> Result<(), Error> {
+    // Verify that the recorder can hold and write records (if it is enabled)
+    if traffic_recorder_config.enabled
+        && (traffic_recorder_config.max_file_size_bytes == 0
+            || traffic_recorder_config.max_num_files == 0
+            || traffic_recorder_config.max_pending_records == 0)
+    {
+        return Err(Error::ConfigSanitizerFailed(
+            sanitizer_name.to_string(),
+            "The traffic recorder file size, number of files and pending records must be greater than 0!".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Sanitize the identity key rotation config of a network
+fn sanitize_identity_key_rotation_config(
+    sanitizer_name: &str,
+    network_config: &NetworkConfig,
+) -> Result<(), Error> {
+    let identity_key_rotation_config = &network_config.identity_key_rotation;
+    if !identity_key_rotation_config.enabled {
+        return Ok(());
+    }
+
+    // Verify that the identity key can change at runtime
+    if !matches!(
+        network_config.identity,
+        Identity::FromStorage(_) | Identity::FromFile(_)
+    ) {
+        return Err(Error::ConfigSanitizerFailed(
+            sanitizer_name.to_string(),
+            "Identity key rotation requires the identity to be loaded from storage or a file!"
+                .into(),
+        ));
+    }
+
+    // Verify that the key is reloaded and that expired connections are drained
+    if identity_key_rotation_config.key_reload_interval_ms == 0
+        || identity_key_rotation_config.max_drained_connections_per_interval == 0
+    {
+        return Err(Error::ConfigSanitizerFailed(
+            sanitizer_name.to_string(),
+            "The identity key reload interval and max drained connections must be greater than 0!"
+                .into(),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Sanitize the validator network config according to the node role and chain ID
 fn sanitize_validator_network_config(
     node_config: &mut NodeConfig,
@@ -189,6 +329,18 @@ fn sanitize_validator_network_config(
             ));
         }
 
+        // Verify the outbound queue config
+        sanitize_outbound_queue_config(&sanitizer_name, &validator_network_config.outbound_queue)?;
+
+        // Verify the traffic recorder config
+        sanitize_traffic_recorder_config(
+            &sanitizer_name,
+            &validator_network_config.traffic_recorder,
+        )?;
+
+        // Verify the identity key rotation config
+        sanitize_identity_key_rotation_config(&sanitizer_name, validator_network_config)?;
+
         // Prepare the network id
         validator_network_config.set_listen_address_and_prepare_identity()?;
     }
@@ -199,7 +351,8 @@ fn sanitize_validator_network_config(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{config::NetworkConfig, network_id::NetworkId};
+    use crate::config::{IdentityKeyRotationConfig, PeerExchangeDiscovery};
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_sanitize_missing_pfn_network_configs() {
@@ -285,6 +438,163 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_peer_exchange_vfn_network() {
+        // Create a node config with peer exchange discovery on the VFN network
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Vfn,
+                discovery_method: DiscoveryMethod::PeerExchange(PeerExchangeDiscovery::default()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::ValidatorFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_outbound_queue_config() {
+        // Create a node config with a zero weight for the mempool priority class
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_queue: OutboundQueueConfig {
+                    mempool_weight: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with a zero bandwidth quota for a protocol
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_queue: OutboundQueueConfig {
+                    protocol_bandwidth_quotas: BTreeMap::from([(
+                        "MempoolDirectSend".to_string(),
+                        0,
+                    )]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with a bandwidth quota for an unknown protocol
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                outbound_queue: OutboundQueueConfig {
+                    protocol_bandwidth_quotas: BTreeMap::from([(
+                        "UnknownDirectSend".to_string(),
+                        1024,
+                    )]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_traffic_recorder_config() {
+        // Create a node config with an enabled traffic recorder that keeps no files
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                traffic_recorder: TrafficRecorderConfig {
+                    enabled: true,
+                    max_num_files: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_identity_key_rotation_config() {
+        // Create a node config with identity key rotation enabled for an identity in the config
+        let mut node_config = NodeConfig {
+            full_node_networks: vec![NetworkConfig {
+                network_id: NetworkId::Public,
+                identity_key_rotation: IdentityKeyRotationConfig {
+                    enabled: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Load the identity from a file, and verify that the config is now valid
+        node_config.full_node_networks[0].identity = Identity::from_file("identity.yaml".into());
+        sanitize_fullnode_network_configs(
+            &mut node_config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap();
+    }
+
     #[test]
     fn test_sanitize_missing_validator_network_config() {
         // Create a node config with an empty validator network config
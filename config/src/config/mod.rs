@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // All modules should be declared below
+mod admin_service_config;
 mod api_config;
 mod base_config;
 mod config_optimizer;
@@ -30,6 +31,7 @@ mod storage_config;
 mod utils;
 
 // All public usage statements should be declared below
+pub use admin_service_config::*;
 pub use api_config::*;
 pub use base_config::*;
 pub use consensus_config::*;
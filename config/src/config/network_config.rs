@@ -23,7 +23,7 @@ use rand::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     fmt,
     path::PathBuf,
@@ -121,6 +121,12 @@ pub struct NetworkConfig {
     pub max_message_size: usize,
     /// The maximum number of parallel message deserialization tasks that can run (per application)
     pub max_parallel_deserialization_tasks: Option<usize>,
+    /// The configuration of the outbound message queue of each peer
+    pub outbound_queue: OutboundQueueConfig,
+    /// The configuration of the (opt-in) network traffic recorder
+    pub traffic_recorder: TrafficRecorderConfig,
+    /// The configuration for rotating the identity key without a restart
+    pub identity_key_rotation: IdentityKeyRotationConfig,
 }
 
 impl Default for NetworkConfig {
@@ -162,6 +168,9 @@ impl NetworkConfig {
             outbound_rx_buffer_size_bytes: Some(OUTBOUND_TCP_RX_BUFFER_SIZE),
             outbound_tx_buffer_size_bytes: Some(OUTBOUND_TCP_TX_BUFFER_SIZE),
             max_parallel_deserialization_tasks: None,
+            outbound_queue: OutboundQueueConfig::default(),
+            traffic_recorder: TrafficRecorderConfig::default(),
+            identity_key_rotation: IdentityKeyRotationConfig::default(),
         };
 
         // Configure the number of parallel deserialization tasks
@@ -183,24 +192,28 @@ impl NetworkConfig {
     }
 
     pub fn identity_key(&self) -> x25519::PrivateKey {
-        let key = match &self.identity {
-            Identity::FromConfig(config) => Some(config.key.private_key()),
+        self.load_identity_key()
+            .expect("identity key should be present")
+    }
+
+    /// Loads the identity key from the configured identity source. Unlike
+    /// `identity_key()`, this does not panic, so it can be used to reload
+    /// the key at runtime (e.g., after the key is rotated in secure storage).
+    pub fn load_identity_key(&self) -> anyhow::Result<x25519::PrivateKey> {
+        match &self.identity {
+            Identity::FromConfig(config) => Ok(config.key.private_key()),
             Identity::FromStorage(config) => {
                 let storage: Storage = (&config.backend).into();
-                let key = storage
-                    .export_private_key(&config.key_name)
-                    .expect("Unable to read key");
-                let key = x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())
-                    .expect("Unable to convert key");
-                Some(key)
+                let key = storage.export_private_key(&config.key_name)?;
+                let key = x25519::PrivateKey::from_ed25519_private_bytes(&key.to_bytes())?;
+                Ok(key)
             },
             Identity::FromFile(config) => {
-                let identity_blob: IdentityBlob = IdentityBlob::from_file(&config.path).unwrap();
-                Some(identity_blob.network_private_key)
+                let identity_blob: IdentityBlob = IdentityBlob::from_file(&config.path)?;
+                Ok(identity_blob.network_private_key)
             },
-            Identity::None => None,
-        };
-        key.expect("identity key should be present")
+            Identity::None => Err(anyhow::anyhow!("No identity is configured!")),
+        }
     }
 
     pub fn identity_from_storage(&self) -> IdentityFromStorage {
@@ -344,6 +357,7 @@ pub enum DiscoveryMethod {
     Onchain,
     File(FileDiscovery),
     Rest(RestDiscovery),
+    PeerExchange(PeerExchangeDiscovery),
     None,
 }
 
@@ -361,6 +375,193 @@ pub struct RestDiscovery {
     pub interval_secs: u64,
 }
 
+/// Peer exchange discovery (only supported on the public network). Connected
+/// peers periodically share samples of the public peers they have dialed.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields, rename_all = "snake_case")]
+pub struct PeerExchangeDiscovery {
+    /// The interval (secs) between sending peer samples to connected peers
+    pub interval_secs: u64,
+    /// The maximum number of peers to include in a single peer sample
+    pub max_peers_per_sample: usize,
+    /// The minimum interval (secs) between peer samples received from the same
+    /// peer. Samples that arrive more frequently are dropped.
+    pub min_sample_interval_secs: u64,
+}
+
+impl Default for PeerExchangeDiscovery {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60,
+            max_peers_per_sample: 16,
+            min_sample_interval_secs: 30,
+        }
+    }
+}
+
+/// The priority class of an application protocol. The outbound message queue of
+/// each peer serves the classes in this order (i.e., `Consensus` first), and
+/// shares the bandwidth between the classes according to their weights.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolPriorityClass {
+    Consensus,
+    StateSync,
+    Mempool,
+    Other,
+}
+
+impl ProtocolPriorityClass {
+    /// Returns all priority classes (in priority order)
+    pub fn all() -> &'static [ProtocolPriorityClass] {
+        &[
+            ProtocolPriorityClass::Consensus,
+            ProtocolPriorityClass::StateSync,
+            ProtocolPriorityClass::Mempool,
+            ProtocolPriorityClass::Other,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtocolPriorityClass::Consensus => "consensus",
+            ProtocolPriorityClass::StateSync => "state_sync",
+            ProtocolPriorityClass::Mempool => "mempool",
+            ProtocolPriorityClass::Other => "other",
+        }
+    }
+}
+
+/// The names of all application protocols (i.e., `ProtocolId::as_str()` in the
+/// network crate), used to validate the protocol bandwidth quotas.
+pub const PROTOCOL_NAMES: &[&str] = &[
+    "ConsensusRpcBcs",
+    "ConsensusDirectSendBcs",
+    "MempoolDirectSend",
+    "StateSyncDirectSend",
+    "DiscoveryDirectSend",
+    "HealthCheckerRpc",
+    "ConsensusDirectSendJson",
+    "ConsensusRpcJson",
+    "StorageServiceRpc",
+    "MempoolRpc",
+    "PeerMonitoringServiceRpc",
+    "ConsensusRpcCompressed",
+    "ConsensusDirectSendCompressed",
+];
+
+/// The outbound message queue of each peer. Messages are queued per protocol
+/// and scheduled using weighted fair queueing (i.e., deficit round robin) over
+/// the protocol priority classes.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutboundQueueConfig {
+    /// The maximum number of messages queued for a single peer. Once the queue
+    /// is full, the applications sending to the peer are back-pressured.
+    pub max_queued_messages: usize,
+    /// The number of bytes a class with a weight of 1 can send in each
+    /// scheduling round. Classes with higher weights send proportionally more.
+    pub quantum_bytes: u64,
+    /// The scheduling weight of the consensus priority class
+    pub consensus_weight: u32,
+    /// The scheduling weight of the state sync priority class
+    pub state_sync_weight: u32,
+    /// The scheduling weight of the mempool priority class
+    pub mempool_weight: u32,
+    /// The scheduling weight of all other protocols (e.g., health checks)
+    pub other_weight: u32,
+    /// Optional bandwidth quotas (in bytes per second) for individual
+    /// protocols, keyed by protocol name (e.g., "MempoolDirectSend").
+    pub protocol_bandwidth_quotas: BTreeMap<String, u64>,
+}
+
+impl OutboundQueueConfig {
+    /// Returns the scheduling weight of the given priority class
+    pub fn weight(&self, priority_class: ProtocolPriorityClass) -> u32 {
+        match priority_class {
+            ProtocolPriorityClass::Consensus => self.consensus_weight,
+            ProtocolPriorityClass::StateSync => self.state_sync_weight,
+            ProtocolPriorityClass::Mempool => self.mempool_weight,
+            ProtocolPriorityClass::Other => self.other_weight,
+        }
+    }
+}
+
+impl Default for OutboundQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_queued_messages: 1024,
+            quantum_bytes: 64 * 1024, // 64 KiB
+            consensus_weight: 8,
+            state_sync_weight: 4,
+            mempool_weight: 2,
+            other_weight: 1,
+            protocol_bandwidth_quotas: BTreeMap::new(),
+        }
+    }
+}
+
+/// The network traffic recorder writes all inbound and outbound messages of
+/// the network to rotating local files, to help debug incidents offline.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrafficRecorderConfig {
+    /// Whether the traffic of the network should be recorded
+    pub enabled: bool,
+    /// The directory to write the recording files to
+    pub recording_dir: PathBuf,
+    /// The maximum size (in bytes) of a single recording file before it is rotated
+    pub max_file_size_bytes: u64,
+    /// The maximum number of recording files to keep (the oldest files are deleted)
+    pub max_num_files: usize,
+    /// The maximum number of records waiting to be written. If the limit is
+    /// reached, new records are dropped (to avoid blocking the network).
+    pub max_pending_records: usize,
+}
+
+impl Default for TrafficRecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recording_dir: PathBuf::from("/opt/aptos/data/network_recordings"),
+            max_file_size_bytes: 256 * 1024 * 1024, // 256 MiB
+            max_num_files: 8,
+            max_pending_records: 10_000,
+        }
+    }
+}
+
+/// Identity key rotation allows the identity key of the network to be replaced
+/// without restarting the node. The key is periodically reloaded from its source
+/// (i.e., secure storage or the identity file). When the key changes, the previous
+/// key is still accepted for inbound connections until the grace period elapses,
+/// after which the connections established with it are drained.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdentityKeyRotationConfig {
+    /// Whether the identity key should be reloaded at runtime
+    pub enabled: bool,
+    /// The interval (ms) at which the identity key is reloaded
+    pub key_reload_interval_ms: u64,
+    /// The duration (ms) for which the previous identity key is accepted after a rotation.
+    /// This should cover the time it takes for the new on-chain addresses to take effect.
+    pub previous_key_grace_period_ms: u64,
+    /// The maximum number of connections established with an expired identity key
+    /// that are closed at each reload interval (to avoid dropping all peers at once).
+    pub max_drained_connections_per_interval: usize,
+}
+
+impl Default for IdentityKeyRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key_reload_interval_ms: 60_000,                // 1 minute
+            previous_key_grace_period_ms: 4 * 60 * 60_000, // 4 hours
+            max_drained_connections_per_interval: 4,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RateLimitConfig {
@@ -156,12 +156,17 @@ pub struct StorageServiceConfig {
     pub max_network_chunk_bytes: u64,
     /// Maximum period (ms) of pending optimistic fetch requests
     pub max_optimistic_fetch_period_ms: u64,
+    /// Maximum number of pending requests per subscription stream. Streams
+    /// that exceed this are dropped and their pending requests rejected.
+    pub max_pending_requests_per_subscription_stream: u64,
     /// Maximum number of state keys and values per chunk
     pub max_state_chunk_size: u64,
     /// Maximum number of transactions per chunk
     pub max_transaction_chunk_size: u64,
     /// Maximum number of transaction outputs per chunk
     pub max_transaction_output_chunk_size: u64,
+    /// Maximum period (ms) a subscription stream can go without serving data
+    pub max_subscription_period_ms: u64,
     /// Minimum time (secs) to ignore peers after too many invalid requests
     pub min_time_to_ignore_peers_secs: u64,
     /// The interval (ms) to refresh the request moderator state
@@ -180,9 +185,11 @@ impl Default for StorageServiceConfig {
             max_network_channel_size: 4000,
             max_network_chunk_bytes: MAX_MESSAGE_SIZE as u64,
             max_optimistic_fetch_period_ms: 5000, // 5 seconds
+            max_pending_requests_per_subscription_stream: 30,
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
+            max_subscription_period_ms: 30_000, // 30 seconds
             min_time_to_ignore_peers_secs: 300, // 5 minutes
             request_moderator_refresh_interval_ms: 1000, // 1 second
             storage_summary_refresh_interval_ms: 100, // Optimal for <= 10 blocks per second
@@ -193,6 +200,10 @@ impl Default for StorageServiceConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DataStreamingServiceConfig {
+    /// Whether or not to use subscription streams (instead of optimistic
+    /// fetch requests) when continuously syncing new data.
+    pub enable_subscription_streaming: bool,
+
     /// The interval (milliseconds) at which to refresh the global data summary.
     pub global_summary_refresh_interval_ms: u64,
 
@@ -215,6 +226,11 @@ pub struct DataStreamingServiceConfig {
     /// memory. Once the number grows beyond this value, garbage collection occurs.
     pub max_notification_id_mappings: u64,
 
+    /// Maximum number of consecutive subscription requests that can be made
+    /// along a single subscription stream. Once this limit is reached, the
+    /// subscription stream is restarted (e.g., to rotate the serving peer).
+    pub max_num_consecutive_subscriptions: u64,
+
     /// The interval (milliseconds) at which to check the progress of each stream.
     pub progress_check_interval_ms: u64,
 }
@@ -222,12 +238,14 @@ pub struct DataStreamingServiceConfig {
 impl Default for DataStreamingServiceConfig {
     fn default() -> Self {
         Self {
+            enable_subscription_streaming: false,
             global_summary_refresh_interval_ms: 50,
             max_concurrent_requests: MAX_CONCURRENT_REQUESTS,
             max_concurrent_state_requests: MAX_CONCURRENT_STATE_REQUESTS,
             max_data_stream_channel_sizes: 300,
             max_request_retry: 5,
             max_notification_id_mappings: 300,
+            max_num_consecutive_subscriptions: 40, // At ~3 requests per second, this should last ~13 seconds
             progress_check_interval_ms: 50,
         }
     }
@@ -256,10 +274,14 @@ pub struct AptosDataClientConfig {
     pub max_transaction_chunk_size: u64,
     /// Maximum number of transaction outputs per chunk
     pub max_transaction_output_chunk_size: u64,
+    /// Maximum version lag we'll tolerate when sending subscription requests
+    pub max_subscription_version_lag: u64,
     /// Timeout (in ms) when waiting for an optimistic fetch response
     pub optimistic_fetch_timeout_ms: u64,
     /// First timeout (in ms) when waiting for a response
     pub response_timeout_ms: u64,
+    /// Timeout (in ms) when waiting for a subscription response
+    pub subscription_response_timeout_ms: u64,
     /// Interval (in ms) between data summary poll loop executions
     pub summary_poll_loop_interval_ms: u64,
     /// Whether or not to request compression for incoming data
@@ -279,8 +301,10 @@ impl Default for AptosDataClientConfig {
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
-            optimistic_fetch_timeout_ms: 5000, // 5 seconds
-            response_timeout_ms: 10_000,       // 10 seconds
+            max_subscription_version_lag: 50_000, // Assumes 5K TPS for 10 seconds, which should be plenty
+            optimistic_fetch_timeout_ms: 5000,    // 5 seconds
+            response_timeout_ms: 10_000,          // 10 seconds
+            subscription_response_timeout_ms: 15_000, // 15 seconds (longer than a regular timeout because of pipelining)
             summary_poll_loop_interval_ms: 200,
             use_compression: true,
         }
@@ -289,10 +313,51 @@ impl Default for AptosDataClientConfig {
 
 impl ConfigSanitizer for StateSyncConfig {
     fn sanitize(
-        _node_config: &mut NodeConfig,
+        node_config: &mut NodeConfig,
+        node_type: NodeType,
+        chain_id: ChainId,
+    ) -> Result<(), Error> {
+        // Sanitize the storage service config
+        StorageServiceConfig::sanitize(node_config, node_type, chain_id)
+    }
+}
+
+impl ConfigSanitizer for StorageServiceConfig {
+    fn sanitize(
+        node_config: &mut NodeConfig,
         _node_type: NodeType,
         _chain_id: ChainId,
     ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let state_sync_config = &node_config.state_sync;
+        let max_pending_requests = state_sync_config
+            .storage_service
+            .max_pending_requests_per_subscription_stream;
+
+        // Verify that subscription streams can hold at least one pending request
+        if max_pending_requests == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "max_pending_requests_per_subscription_stream must be greater than 0!".into(),
+            ));
+        }
+
+        // Verify that the subscription streams of our peers (which send up
+        // to max_concurrent_requests along each stream) won't be dropped.
+        let max_concurrent_requests = state_sync_config
+            .data_streaming_service
+            .max_concurrent_requests;
+        if max_pending_requests < max_concurrent_requests {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "max_pending_requests_per_subscription_stream ({}) must be at least \
+                     max_concurrent_requests ({}) of the data streaming service!",
+                    max_pending_requests, max_concurrent_requests
+                ),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -576,6 +641,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sanitize_zero_pending_subscription_requests() {
+        // Create a node config that allows no pending subscription requests
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                storage_service: StorageServiceConfig {
+                    max_pending_requests_per_subscription_stream: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            StateSyncConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_too_few_pending_subscription_requests() {
+        // Create a node config that allows fewer pending subscription
+        // requests than the number of concurrent data streaming requests.
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                data_streaming_service: DataStreamingServiceConfig {
+                    max_concurrent_requests: 10,
+                    ..Default::default()
+                },
+                storage_service: StorageServiceConfig {
+                    max_pending_requests_per_subscription_stream: 9,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            StateSyncConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Allow as many pending requests as concurrent requests and verify that it succeeds
+        node_config
+            .state_sync
+            .storage_service
+            .max_pending_requests_per_subscription_stream = 10;
+        StateSyncConfig::sanitize(&mut node_config, NodeType::Validator, ChainId::mainnet())
+            .unwrap();
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
@@ -53,13 +53,21 @@ use aptos_network::{
     protocols::{
         network,
         network::{Event, NetworkEvents, NewNetworkEvents, NewNetworkSender},
-        wire::handshake::v1::ProtocolIdSet,
+        wire::{
+            handshake::v1::ProtocolIdSet,
+            messaging::v1::{DirectSendMsg, NetworkMessage},
+        },
+    },
+    traffic_recorder::{
+        decode_inbound_messages, read_recording_dir, TrafficDirection, TrafficRecord,
+        TrafficRecordWriter,
     },
     transport::ConnectionMetadata,
     ProtocolId,
 };
 use aptos_safety_rules::{PersistentSafetyStorage, SafetyRulesManager};
 use aptos_secure_storage::Storage;
+use aptos_temppath::TempPath;
 use aptos_types::{
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
@@ -78,6 +86,7 @@ use futures::{
 use maplit::hashmap;
 use std::{
     iter::FromIterator,
+    path::Path,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -1827,3 +1836,119 @@ pub fn forking_retrieval_test() {
         3,
     );
 }
+
+#[test]
+/// Verify that recorded inbound traffic can be replayed into the round manager
+fn replay_recorded_inbound_traffic() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 2, None);
+    timed_block_on(&runtime, async {
+        // Process the round 1 proposal on the proposer
+        let proposal_msg = nodes[0].next_proposal().await;
+        let proposal_id = proposal_msg.proposal().id();
+        nodes[0]
+            .round_manager
+            .process_proposal_msg(proposal_msg)
+            .await
+            .unwrap();
+
+        // Record the proposal received by the other node, and replay it
+        let proposal_msg = nodes[1].next_proposal().await;
+        let recording_dir = TempPath::new();
+        record_inbound_consensus_messages(recording_dir.path(), vec![(
+            proposal_msg.proposer(),
+            ConsensusMsg::ProposalMsg(Box::new(proposal_msg)),
+        )]);
+        let records = read_recording_dir(recording_dir.path(), None).unwrap();
+        assert_eq!(
+            replay_inbound_consensus_traffic(&mut nodes[1], &records).await,
+            1
+        );
+
+        // Record the votes received by the proposer (from both nodes)
+        let mut votes = vec![];
+        for _ in 0..2 {
+            let vote_msg = nodes[0].next_vote().await;
+            assert_eq!(vote_msg.vote().vote_data().proposed().id(), proposal_id);
+            votes.push((
+                vote_msg.vote().author(),
+                ConsensusMsg::VoteMsg(Box::new(vote_msg)),
+            ));
+        }
+        let recording_dir = TempPath::new();
+        record_inbound_consensus_messages(recording_dir.path(), votes);
+
+        // Replay the votes and verify that round 2 starts
+        let records = read_recording_dir(recording_dir.path(), None).unwrap();
+        assert_eq!(
+            replay_inbound_consensus_traffic(&mut nodes[0], &records).await,
+            2
+        );
+        let proposal_msg = nodes[0].next_proposal().await;
+        assert_eq!(proposal_msg.proposal().round(), 2);
+        assert_eq!(proposal_msg.proposal().parent_id(), proposal_id);
+    });
+}
+
+/// Writes the given consensus messages to a recording in the given directory
+/// (as inbound direct send messages from the given senders).
+fn record_inbound_consensus_messages(recording_dir: &Path, messages: Vec<(Author, ConsensusMsg)>) {
+    let mut writer = TrafficRecordWriter::new(
+        recording_dir.to_path_buf(),
+        NetworkId::Validator,
+        1024 * 1024,
+        1,
+    )
+    .unwrap();
+    let protocol_id = DIRECT_SEND[0];
+    for (sender, message) in messages {
+        let raw_msg = protocol_id.to_bytes(&message).unwrap();
+        writer
+            .write(&TrafficRecord {
+                timestamp_usecs: 0,
+                network_id: NetworkId::Validator,
+                peer_id: sender,
+                direction: TrafficDirection::Inbound,
+                protocol_id: Some(protocol_id),
+                size: raw_msg.len() as u64,
+                message: NetworkMessage::DirectSendMsg(DirectSendMsg {
+                    protocol_id,
+                    priority: 0,
+                    raw_msg,
+                }),
+            })
+            .unwrap();
+    }
+    writer.flush().unwrap();
+}
+
+/// Replays the recorded inbound consensus messages into the round manager of
+/// the given node (in recording order). Messages that are not handled by the
+/// round manager are skipped. Returns the number of replayed messages.
+async fn replay_inbound_consensus_traffic(
+    node: &mut NodeSetup,
+    records: &[TrafficRecord],
+) -> usize {
+    let protocol_ids: Vec<_> = DIRECT_SEND.iter().chain(RPC.iter()).copied().collect();
+    let messages = decode_inbound_messages::<ConsensusMsg>(records, &protocol_ids).unwrap();
+
+    let mut num_replayed_messages = 0;
+    for (sender, message) in messages {
+        let result = match message {
+            ConsensusMsg::ProposalMsg(proposal_msg) => {
+                node.round_manager.process_proposal_msg(*proposal_msg).await
+            },
+            ConsensusMsg::VoteMsg(vote_msg) => node.round_manager.process_vote_msg(*vote_msg).await,
+            ConsensusMsg::SyncInfo(sync_info) => {
+                node.round_manager
+                    .process_sync_info_msg(*sync_info, sender)
+                    .await
+            },
+            _ => continue,
+        };
+        result.unwrap();
+        num_replayed_messages += 1;
+    }
+    num_replayed_messages
+}
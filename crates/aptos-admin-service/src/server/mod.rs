@@ -0,0 +1,237 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::{AdminServiceConfig, AuthenticationConfig, NodeConfig};
+use aptos_db::AptosDB;
+use aptos_logger::{error, info};
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::{SocketAddr, ToSocketAddrs},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+use subtle::ConstantTimeEq;
+use tokio::runtime::Runtime;
+
+#[cfg(test)]
+mod tests;
+
+// The list of endpoints offered by the admin service
+pub const DB_CHECKPOINT_PATH: &str = "/debug/db/checkpoint";
+
+// The list of headers and query parameters accepted by the admin service.
+// Note: the passcode is sent as a header (and not a query parameter) to
+// avoid leaking it into the request logs.
+pub const PASSCODE_HEADER: &str = "X-Aptos-Admin-Passcode";
+pub const PATH_PARAM: &str = "path";
+
+// Useful string constants
+pub const HEADER_CONTENT_TYPE: &str = "Content-Type";
+pub const CONTENT_TYPE_TEXT: &str = "text/plain";
+pub const INVALID_ENDPOINT_MESSAGE: &str = "The requested endpoint is invalid!";
+pub const INVALID_PATH_MESSAGE: &str =
+    "The checkpoint path must be relative to the checkpoint directory!";
+pub const MISSING_PATH_MESSAGE: &str = "The checkpoint path must be provided!";
+pub const UNAUTHENTICATED_MESSAGE: &str = "The request could not be authenticated!";
+
+/// Starts the admin service that listens on the configured address
+/// and handles various endpoint requests. Returns None if the admin
+/// service is disabled.
+pub fn start_admin_service(node_config: &NodeConfig, aptos_db: Arc<AptosDB>) -> Option<Runtime> {
+    let admin_service_config = node_config.admin_service.clone();
+    if !admin_service_config.enabled {
+        return None;
+    }
+
+    // Fetch the service port and address
+    let service_port = admin_service_config.port;
+    let service_address = admin_service_config.address.clone();
+
+    // Create the admin service socket address
+    let address: SocketAddr = (service_address.as_str(), service_port)
+        .to_socket_addrs()
+        .unwrap_or_else(|_| {
+            panic!(
+                "Failed to parse {}:{} as address",
+                service_address, service_port
+            )
+        })
+        .next()
+        .unwrap();
+
+    // Create a runtime for the admin service
+    let runtime = aptos_runtimes::spawn_named_runtime("admin".into(), None);
+
+    // Create the service function that handles the endpoint requests
+    let make_service = make_service_fn(move |_conn| {
+        let admin_service_config = admin_service_config.clone();
+        let aptos_db = aptos_db.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                serve_requests(request, admin_service_config.clone(), aptos_db.clone())
+            }))
+        }
+    });
+
+    // Spawn the admin service on the runtime
+    runtime.spawn(async move {
+        let server = Server::bind(&address).serve(make_service);
+        info!("Started the admin service at: {:?}", address);
+        if let Err(error) = server.await {
+            error!("The admin service failed! Error: {:?}", error);
+        }
+    });
+
+    Some(runtime)
+}
+
+/// A simple helper function that handles each endpoint request
+async fn serve_requests(
+    req: Request<Body>,
+    admin_service_config: AdminServiceConfig,
+    aptos_db: Arc<AptosDB>,
+) -> Result<Response<Body>, hyper::Error> {
+    // Parse the query parameters of the request
+    let query_params = get_query_params(&req);
+
+    // Verify the request is authenticated before handling it
+    let (status_code, body) = if !is_authenticated(&admin_service_config, &req) {
+        (StatusCode::FORBIDDEN, Body::from(UNAUTHENTICATED_MESSAGE))
+    } else {
+        match (req.method(), req.uri().path()) {
+            (&Method::POST, DB_CHECKPOINT_PATH) => {
+                // /debug/db/checkpoint
+                // Creates a checkpoint of the database while the node is running
+                handle_db_checkpoint_request(&query_params, &admin_service_config, aptos_db).await
+            },
+            _ => {
+                // Handle the invalid path
+                (StatusCode::NOT_FOUND, Body::from(INVALID_ENDPOINT_MESSAGE))
+            },
+        }
+    };
+
+    // Build the response
+    let response = Response::builder()
+        .header(HEADER_CONTENT_TYPE, CONTENT_TYPE_TEXT)
+        .status(status_code)
+        .body(body);
+
+    // Return the processed response
+    Ok(response.unwrap_or_else(|error| {
+        // Log the internal error
+        error!("Error encountered when generating response: {:?}", error);
+
+        // Return a failure response
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        response
+    }))
+}
+
+/// Handles a request to create an online checkpoint of the database.
+/// The checkpoint is always created inside the configured checkpoint directory.
+async fn handle_db_checkpoint_request(
+    query_params: &HashMap<String, String>,
+    admin_service_config: &AdminServiceConfig,
+    aptos_db: Arc<AptosDB>,
+) -> (StatusCode, Body) {
+    // Fetch the checkpoint path from the query parameters
+    let checkpoint_path = match query_params.get(PATH_PARAM) {
+        Some(path) if !path.is_empty() => {
+            match get_checkpoint_path(&admin_service_config.checkpoint_dir, path) {
+                Some(checkpoint_path) => checkpoint_path,
+                None => return (StatusCode::BAD_REQUEST, Body::from(INVALID_PATH_MESSAGE)),
+            }
+        },
+        _ => return (StatusCode::BAD_REQUEST, Body::from(MISSING_PATH_MESSAGE)),
+    };
+
+    // Create the checkpoint. This is a blocking operation, so
+    // we spawn it on a dedicated thread.
+    info!("Creating an online DB checkpoint at: {:?}", checkpoint_path);
+    let checkpoint_path_clone = checkpoint_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        aptos_db.create_online_checkpoint(checkpoint_path_clone)
+    })
+    .await;
+
+    // Process the result
+    match result {
+        Ok(Ok(version)) => (
+            StatusCode::OK,
+            Body::from(format!(
+                "Created a DB checkpoint at version {} in {:?}",
+                version, checkpoint_path
+            )),
+        ),
+        Ok(Err(error)) => {
+            error!("Failed to create the DB checkpoint! Error: {:?}", error);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Body::from(format!("Failed to create the DB checkpoint: {:?}", error)),
+            )
+        },
+        Err(error) => {
+            error!("The DB checkpoint task failed! Error: {:?}", error);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Body::from(format!("The DB checkpoint task failed: {:?}", error)),
+            )
+        },
+    }
+}
+
+/// Returns the location of the checkpoint inside the checkpoint directory. Returns
+/// None if the given path is absolute or could otherwise escape the directory.
+fn get_checkpoint_path(checkpoint_dir: &Path, path: &str) -> Option<PathBuf> {
+    let path = Path::new(path);
+    if path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        Some(checkpoint_dir.join(path))
+    } else {
+        None
+    }
+}
+
+/// Returns the query parameters of the given request
+fn get_query_params(req: &Request<Body>) -> HashMap<String, String> {
+    req.uri()
+        .query()
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns true iff the request satisfies at least one of the
+/// authentication configs. If no configs exist, the request is rejected.
+/// Passcode hashes are compared in constant time.
+fn is_authenticated(admin_service_config: &AdminServiceConfig, req: &Request<Body>) -> bool {
+    let passcode = req
+        .headers()
+        .get(PASSCODE_HEADER)
+        .and_then(|passcode| passcode.to_str().ok());
+    admin_service_config
+        .authentication_configs
+        .iter()
+        .any(|authentication_config| match authentication_config {
+            AuthenticationConfig::PasscodeSha256(passcode_sha256) => passcode
+                .and_then(|passcode| {
+                    let passcode_hash = Sha256::digest(passcode.as_bytes());
+                    let expected_hash = hex::decode(passcode_sha256).ok()?;
+                    Some(passcode_hash.as_slice().ct_eq(&expected_hash).into())
+                })
+                .unwrap_or(false),
+        })
+}
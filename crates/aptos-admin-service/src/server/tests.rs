@@ -0,0 +1,312 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::{
+    serve_requests, DB_CHECKPOINT_PATH, INVALID_ENDPOINT_MESSAGE, INVALID_PATH_MESSAGE,
+    MISSING_PATH_MESSAGE, PASSCODE_HEADER, UNAUTHENTICATED_MESSAGE,
+};
+use aptos_config::config::{AdminServiceConfig, AuthenticationConfig};
+use aptos_db::{
+    test_helper::{arb_blocks_to_commit, update_in_memory_state},
+    AptosDB,
+};
+use aptos_proptest_helpers::ValueGenerator;
+use aptos_storage_interface::{DbReader, DbWriter};
+use aptos_temppath::TempPath;
+use aptos_types::transaction::Version;
+use hyper::{body, Body, Method, Request, Response, StatusCode};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+// Useful test constants
+const TEST_CHECKPOINT_NAME: &str = "test_checkpoint";
+const TEST_PASSCODE: &str = "test_passcode";
+
+#[tokio::test]
+async fn test_unauthenticated_requests() {
+    // Create an admin service config without any authentication configs
+    let mut admin_service_config = AdminServiceConfig {
+        enabled: true,
+        ..Default::default()
+    };
+
+    // Verify that requests are rejected
+    let uri = format!("{}?path=/tmp", DB_CHECKPOINT_PATH);
+    let (status_code, response_body) = send_request(
+        &admin_service_config,
+        create_empty_db(),
+        Method::POST,
+        &uri,
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::FORBIDDEN);
+    assert_eq!(response_body, UNAUTHENTICATED_MESSAGE);
+
+    // Add a passcode authentication config
+    admin_service_config.authentication_configs = vec![create_passcode_config()];
+
+    // Verify that requests with a missing or invalid passcode are rejected
+    for passcode in [None, Some("invalid")] {
+        let (status_code, response_body) = send_request(
+            &admin_service_config,
+            create_empty_db(),
+            Method::POST,
+            &uri,
+            passcode,
+        )
+        .await;
+        assert_eq!(status_code, StatusCode::FORBIDDEN);
+        assert_eq!(response_body, UNAUTHENTICATED_MESSAGE);
+    }
+
+    // Verify that a passcode in the query parameters is ignored
+    let uri = format!(
+        "{}?passcode={}&path=/tmp",
+        DB_CHECKPOINT_PATH, TEST_PASSCODE
+    );
+    let (status_code, response_body) = send_request(
+        &admin_service_config,
+        create_empty_db(),
+        Method::POST,
+        &uri,
+        None,
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::FORBIDDEN);
+    assert_eq!(response_body, UNAUTHENTICATED_MESSAGE);
+}
+
+#[tokio::test]
+async fn test_invalid_endpoint_requests() {
+    // Create an admin service config with a passcode
+    let admin_service_config = create_admin_service_config();
+
+    // Verify that an invalid path is rejected
+    let (status_code, response_body) = send_request(
+        &admin_service_config,
+        create_empty_db(),
+        Method::POST,
+        "/invalid_path",
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::NOT_FOUND);
+    assert_eq!(response_body, INVALID_ENDPOINT_MESSAGE);
+
+    // Verify that an invalid method is rejected
+    let uri = format!("{}?path=/tmp", DB_CHECKPOINT_PATH);
+    let (status_code, response_body) = send_request(
+        &admin_service_config,
+        create_empty_db(),
+        Method::GET,
+        &uri,
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::NOT_FOUND);
+    assert_eq!(response_body, INVALID_ENDPOINT_MESSAGE);
+}
+
+#[tokio::test]
+async fn test_db_checkpoint_requests() {
+    // Create an admin service config with a passcode and checkpoint directory
+    let checkpoint_dir = TempPath::new();
+    let admin_service_config = create_checkpoint_admin_service_config(&checkpoint_dir);
+
+    // Verify that a request without a checkpoint path is rejected
+    let (status_code, response_body) = send_request(
+        &admin_service_config,
+        create_empty_db(),
+        Method::POST,
+        DB_CHECKPOINT_PATH,
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::BAD_REQUEST);
+    assert_eq!(response_body, MISSING_PATH_MESSAGE);
+
+    // Verify that a checkpoint of an empty DB fails (nothing has been committed)
+    let (status_code, _) = send_request(
+        &admin_service_config,
+        create_empty_db(),
+        Method::POST,
+        &create_checkpoint_uri(TEST_CHECKPOINT_NAME),
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_db_checkpoint_invalid_paths() {
+    // Create an admin service config with a passcode and checkpoint directory
+    let checkpoint_dir = TempPath::new();
+    let admin_service_config = create_checkpoint_admin_service_config(&checkpoint_dir);
+
+    // Verify that paths outside the checkpoint directory are rejected
+    let outside_path = TempPath::new();
+    let outside_path = outside_path.path().display().to_string();
+    for path in [
+        outside_path.as_str(),
+        "..",
+        "../test_checkpoint",
+        "test_checkpoint/../../test_checkpoint",
+        "./test_checkpoint",
+    ] {
+        let (status_code, response_body) = send_request(
+            &admin_service_config,
+            create_empty_db(),
+            Method::POST,
+            &create_checkpoint_uri(path),
+            Some(TEST_PASSCODE),
+        )
+        .await;
+        assert_eq!(status_code, StatusCode::BAD_REQUEST);
+        assert_eq!(response_body, INVALID_PATH_MESSAGE);
+    }
+}
+
+#[tokio::test]
+async fn test_db_checkpoint_existing_path() {
+    // Create an admin service config with a passcode and checkpoint directory
+    let checkpoint_dir = TempPath::new();
+    let admin_service_config = create_checkpoint_admin_service_config(&checkpoint_dir);
+
+    // Create a non-empty directory at the checkpoint path
+    let checkpoint_path = checkpoint_dir.path().join(TEST_CHECKPOINT_NAME);
+    std::fs::create_dir_all(&checkpoint_path).unwrap();
+    let existing_file = checkpoint_path.join("existing_file");
+    std::fs::write(&existing_file, b"existing data").unwrap();
+
+    // Verify the checkpoint request fails and the existing data is untouched
+    let (_, aptos_db) = create_db_with_content();
+    let (status_code, _) = send_request(
+        &admin_service_config,
+        aptos_db,
+        Method::POST,
+        &create_checkpoint_uri(TEST_CHECKPOINT_NAME),
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(std::fs::read(&existing_file).unwrap(), b"existing data");
+}
+
+#[tokio::test]
+async fn test_db_checkpoint_success() {
+    // Create an admin service config with a passcode and checkpoint directory
+    let checkpoint_dir = TempPath::new();
+    let admin_service_config = create_checkpoint_admin_service_config(&checkpoint_dir);
+
+    // Create a DB with committed data
+    let (_db_path, aptos_db) = create_db_with_content();
+    let latest_version = aptos_db.get_latest_version().unwrap();
+    let latest_ledger_info = aptos_db.get_latest_ledger_info().unwrap();
+
+    // Create a checkpoint of the DB
+    let (status_code, response_body) = send_request(
+        &admin_service_config,
+        aptos_db,
+        Method::POST,
+        &create_checkpoint_uri(TEST_CHECKPOINT_NAME),
+        Some(TEST_PASSCODE),
+    )
+    .await;
+    assert_eq!(status_code, StatusCode::OK);
+    assert!(response_body.contains(&format!("at version {}", latest_version)));
+
+    // Open the checkpoint and verify it contains the committed data
+    let checkpoint_db = AptosDB::new_for_test(checkpoint_dir.path().join(TEST_CHECKPOINT_NAME));
+    assert_eq!(checkpoint_db.get_latest_version().unwrap(), latest_version);
+    assert_eq!(
+        checkpoint_db.get_latest_ledger_info().unwrap(),
+        latest_ledger_info
+    );
+}
+
+/// Creates an enabled admin service config with the test passcode
+fn create_admin_service_config() -> AdminServiceConfig {
+    AdminServiceConfig {
+        enabled: true,
+        authentication_configs: vec![create_passcode_config()],
+        ..Default::default()
+    }
+}
+
+/// Creates an enabled admin service config with the test passcode and checkpoint directory
+fn create_checkpoint_admin_service_config(checkpoint_dir: &TempPath) -> AdminServiceConfig {
+    AdminServiceConfig {
+        checkpoint_dir: checkpoint_dir.path().to_path_buf(),
+        ..create_admin_service_config()
+    }
+}
+
+/// Creates a passcode authentication config for the test passcode
+fn create_passcode_config() -> AuthenticationConfig {
+    let passcode_sha256 = hex::encode(Sha256::digest(TEST_PASSCODE.as_bytes()));
+    AuthenticationConfig::PasscodeSha256(passcode_sha256)
+}
+
+/// Creates the URI of a checkpoint request for the given path
+fn create_checkpoint_uri(checkpoint_path: &str) -> String {
+    format!("{}?path={}", DB_CHECKPOINT_PATH, checkpoint_path)
+}
+
+/// Creates an empty test DB
+fn create_empty_db() -> Arc<AptosDB> {
+    let tmp_dir = TempPath::new();
+    Arc::new(AptosDB::new_for_test(&tmp_dir))
+}
+
+/// Creates a test DB and commits random blocks to it
+fn create_db_with_content() -> (TempPath, Arc<AptosDB>) {
+    let tmp_dir = TempPath::new();
+    let aptos_db = Arc::new(AptosDB::new_for_test(&tmp_dir));
+
+    let mut in_memory_state = aptos_db.buffered_state().lock().current_state().clone();
+    let mut cur_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in
+        ValueGenerator::new().generate(arb_blocks_to_commit())
+    {
+        update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        aptos_db
+            .save_transactions(
+                &txns_to_commit,
+                cur_ver,                /* first_version */
+                cur_ver.checked_sub(1), /* base_state_version */
+                Some(&ledger_info_with_sigs),
+                true, /* sync_commit */
+                in_memory_state.clone(),
+            )
+            .unwrap();
+        cur_ver += txns_to_commit.len() as u64;
+    }
+
+    (tmp_dir, aptos_db)
+}
+
+/// Sends a request to the admin service and returns the status code and body
+async fn send_request(
+    admin_service_config: &AdminServiceConfig,
+    aptos_db: Arc<AptosDB>,
+    method: Method,
+    uri: &str,
+    passcode: Option<&str>,
+) -> (StatusCode, String) {
+    // Serve the request
+    let mut request = Request::builder().uri(uri).method(method);
+    if let Some(passcode) = passcode {
+        request = request.header(PASSCODE_HEADER, passcode);
+    }
+    let request = request.body(Body::empty()).unwrap();
+    let mut response: Response<Body> =
+        serve_requests(request, admin_service_config.clone(), aptos_db)
+            .await
+            .unwrap();
+
+    // Extract the status code and body
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body = String::from_utf8(response_body.to_vec()).unwrap();
+    (response.status(), response_body)
+}
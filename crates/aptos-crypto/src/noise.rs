@@ -63,7 +63,10 @@
 //!
 #![allow(clippy::arithmetic_side_effects)]
 
-use crate::{hash::HashValue, hkdf::Hkdf, traits::Uniform as _, x25519, ValidCryptoMaterial};
+use crate::{
+    ed25519::Ed25519Signature, hash::HashValue, hkdf::Hkdf, traits::Uniform as _, x25519,
+    ValidCryptoMaterial,
+};
 use ring::aead::{self, Aad, LessSafeKey, UnboundKey};
 use sha2::Digest;
 use std::{
@@ -268,6 +271,16 @@ impl NoiseConfig {
         self.public_key
     }
 
+    /// Signs the given message with the static private key, using XEdDSA
+    /// (see [`x25519::PrivateKey::sign_xeddsa`]).
+    pub fn sign_xeddsa(
+        &self,
+        rng: &mut (impl rand::RngCore + rand::CryptoRng),
+        message: &[u8],
+    ) -> Ed25519Signature {
+        self.private_key.sign_xeddsa(message, rng)
+    }
+
     //
     // Initiator
     // ---------
@@ -394,7 +407,7 @@ impl NoiseConfig {
 
         // split
         let (k1, k2) = hkdf(&ck, None)?;
-        let session = NoiseSession::new(k1, k2, rs);
+        let session = NoiseSession::new(k1, k2, rs, self.public_key);
 
         //
         Ok((plaintext.to_vec(), session))
@@ -547,7 +560,7 @@ impl NoiseConfig {
 
         // split
         let (k1, k2) = hkdf(&ck, None)?;
-        let session = NoiseSession::new(k2, k1, rs);
+        let session = NoiseSession::new(k2, k1, rs, self.public_key);
 
         //
         Ok(session)
@@ -587,6 +600,8 @@ pub struct NoiseSession {
     valid: bool,
     /// the public key of the other peer
     remote_public_key: x25519::PublicKey,
+    /// the public key used on our side of the session
+    local_public_key: x25519::PublicKey,
     /// key used to encrypt messages to the other peer
     write_key: Vec<u8>,
     /// associated nonce (in practice the maximum u64 value cannot be reached)
@@ -598,10 +613,16 @@ pub struct NoiseSession {
 }
 
 impl NoiseSession {
-    fn new(write_key: Vec<u8>, read_key: Vec<u8>, remote_public_key: x25519::PublicKey) -> Self {
+    fn new(
+        write_key: Vec<u8>,
+        read_key: Vec<u8>,
+        remote_public_key: x25519::PublicKey,
+        local_public_key: x25519::PublicKey,
+    ) -> Self {
         Self {
             valid: true,
             remote_public_key,
+            local_public_key,
             write_key,
             write_nonce: 0,
             read_key,
@@ -616,6 +637,7 @@ impl NoiseSession {
             vec![0u8; 32],
             vec![0u8; 32],
             [0u8; x25519::PUBLIC_KEY_SIZE].into(),
+            [0u8; x25519::PUBLIC_KEY_SIZE].into(),
         )
     }
 
@@ -624,6 +646,11 @@ impl NoiseSession {
         self.remote_public_key
     }
 
+    /// obtain local static public key (i.e., the key used to establish the session)
+    pub fn get_local_static(&self) -> x25519::PublicKey {
+        self.local_public_key
+    }
+
     /// encrypts a message for the other peers (post-handshake)
     /// the function encrypts in place, and returns the authentication tag as result
     pub fn write_message_in_place(&mut self, message: &mut [u8]) -> Result<Vec<u8>, NoiseError> {
@@ -0,0 +1,62 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for Ethereum-style ECDSA signatures over the secp256k1 curve, with
+//! Keccak-256 as the message digest, so that keys held by existing Ethereum wallets can be used.
+//!
+//! Public keys are encoded as uncompressed SEC1 points and signatures as the 64-byte
+//! concatenation `r || s`, without the recovery id. Signature verification rejects signatures
+//! whose `s` component is not in the lower half of the curve order, so that signatures are not
+//! malleable.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256k1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message).unwrap();
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of the Secp256k1EcdsaPrivateKey
+pub const PRIVATE_KEY_LENGTH: usize = libsecp256k1::util::SECRET_KEY_SIZE;
+/// The length of the Secp256k1EcdsaPublicKey, as an uncompressed SEC1 point
+pub const PUBLIC_KEY_LENGTH: usize = libsecp256k1::util::FULL_PUBLIC_KEY_SIZE;
+/// The length of the Secp256k1EcdsaSignature
+pub const SIGNATURE_LENGTH: usize = libsecp256k1::util::SIGNATURE_SIZE;
+
+pub mod secp256k1_ecdsa_keys;
+pub mod secp256k1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256k1_ecdsa_keys::keypair_strategy;
+pub use secp256k1_ecdsa_keys::{
+    Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPrivateKey as PrivateKey, Secp256k1EcdsaPublicKey,
+    Secp256k1EcdsaPublicKey as PublicKey,
+};
+pub use secp256k1_ecdsa_sigs::{Secp256k1EcdsaSignature, Secp256k1EcdsaSignature as Signature};
+
+/// Hashes a message with Keccak-256 into the digest that is actually signed.
+fn message_digest(message: &[u8]) -> libsecp256k1::Message {
+    use tiny_keccak::{Hasher, Keccak};
+
+    let mut digest = [0u8; libsecp256k1::util::MESSAGE_SIZE];
+    let mut keccak = Keccak::v256();
+    keccak.update(message);
+    keccak.finalize(&mut digest);
+    libsecp256k1::Message::parse(&digest)
+}
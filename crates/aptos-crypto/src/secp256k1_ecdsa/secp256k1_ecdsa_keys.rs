@@ -0,0 +1,247 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256k1 ECDSA private keys and public keys.
+
+#[cfg(any(test, feature = "fuzzing"))]
+use crate::test_utils::{self, KeyPair};
+use crate::{
+    hash::CryptoHash,
+    secp256k1_ecdsa::{
+        message_digest, Secp256k1EcdsaSignature, PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH,
+    },
+    traits::*,
+};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use core::convert::TryFrom;
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest::prelude::*;
+use serde::Serialize;
+use std::fmt;
+
+/// A secp256k1 ECDSA private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct Secp256k1EcdsaPrivateKey(pub(crate) libsecp256k1::SecretKey);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(Secp256k1EcdsaPrivateKey: Clone);
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for Secp256k1EcdsaPrivateKey {
+    fn clone(&self) -> Self {
+        let serialized: &[u8] = &(self.to_bytes());
+        Secp256k1EcdsaPrivateKey::try_from(serialized).unwrap()
+    }
+}
+
+/// A secp256k1 ECDSA public key
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Secp256k1EcdsaPublicKey(pub(crate) libsecp256k1::PublicKey);
+
+impl Secp256k1EcdsaPrivateKey {
+    /// The length of the Secp256k1EcdsaPrivateKey
+    pub const LENGTH: usize = PRIVATE_KEY_LENGTH;
+
+    /// Serialize a Secp256k1EcdsaPrivateKey.
+    pub fn to_bytes(&self) -> [u8; PRIVATE_KEY_LENGTH] {
+        self.0.serialize()
+    }
+
+    /// Signs an arbitrary message, which is hashed with Keccak-256 before signing. The returned
+    /// signature is always normalized to its low-s form.
+    pub fn sign_arbitrary_message(&self, message: &[u8]) -> Secp256k1EcdsaSignature {
+        let (mut signature, _recovery_id) = libsecp256k1::sign(&message_digest(message), &self.0);
+        signature.normalize_s();
+        Secp256k1EcdsaSignature(signature)
+    }
+}
+
+impl Secp256k1EcdsaPublicKey {
+    /// The length of the Secp256k1EcdsaPublicKey
+    pub const LENGTH: usize = PUBLIC_KEY_LENGTH;
+
+    /// Serialize a Secp256k1EcdsaPublicKey as an uncompressed SEC1 point.
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_LENGTH] {
+        self.0.serialize()
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl PrivateKey for Secp256k1EcdsaPrivateKey {
+    type PublicKeyMaterial = Secp256k1EcdsaPublicKey;
+}
+
+impl SigningKey for Secp256k1EcdsaPrivateKey {
+    type SignatureMaterial = Secp256k1EcdsaSignature;
+    type VerifyingKeyMaterial = Secp256k1EcdsaPublicKey;
+
+    fn sign<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+    ) -> Result<Secp256k1EcdsaSignature, CryptoMaterialError> {
+        Ok(Secp256k1EcdsaPrivateKey::sign_arbitrary_message(
+            self,
+            signing_message(message)?.as_ref(),
+        ))
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    fn sign_arbitrary_message(&self, message: &[u8]) -> Secp256k1EcdsaSignature {
+        Secp256k1EcdsaPrivateKey::sign_arbitrary_message(self, message)
+    }
+}
+
+impl Uniform for Secp256k1EcdsaPrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng,
+    {
+        // Rejection-sample 32 bytes until they encode a non-zero scalar below the curve order.
+        loop {
+            let mut bytes = [0u8; PRIVATE_KEY_LENGTH];
+            rng.fill_bytes(&mut bytes);
+            if let Ok(key) = libsecp256k1::SecretKey::parse(&bytes) {
+                return Secp256k1EcdsaPrivateKey(key);
+            }
+        }
+    }
+}
+
+impl PartialEq<Self> for Secp256k1EcdsaPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Secp256k1EcdsaPrivateKey {}
+
+impl TryFrom<&[u8]> for Secp256k1EcdsaPrivateKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a Secp256k1EcdsaPrivateKey. This method will check that the key is exactly 32
+    /// bytes long and encodes a non-zero scalar below the order of the curve.
+    fn try_from(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256k1EcdsaPrivateKey, CryptoMaterialError> {
+        if bytes.len() != PRIVATE_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        libsecp256k1::SecretKey::parse_slice(bytes)
+            .map(Secp256k1EcdsaPrivateKey)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+}
+
+impl Length for Secp256k1EcdsaPrivateKey {
+    fn length(&self) -> usize {
+        Self::LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1EcdsaPrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+impl From<&Secp256k1EcdsaPrivateKey> for Secp256k1EcdsaPublicKey {
+    fn from(private_key: &Secp256k1EcdsaPrivateKey) -> Self {
+        Secp256k1EcdsaPublicKey(libsecp256k1::PublicKey::from_secret_key(&private_key.0))
+    }
+}
+
+impl PublicKey for Secp256k1EcdsaPublicKey {
+    type PrivateKeyMaterial = Secp256k1EcdsaPrivateKey;
+}
+
+impl std::hash::Hash for Secp256k1EcdsaPublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_pubkey = self.to_bytes();
+        state.write(&encoded_pubkey);
+    }
+}
+
+impl PartialEq for Secp256k1EcdsaPublicKey {
+    fn eq(&self, other: &Secp256k1EcdsaPublicKey) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for Secp256k1EcdsaPublicKey {}
+
+impl VerifyingKey for Secp256k1EcdsaPublicKey {
+    type SignatureMaterial = Secp256k1EcdsaSignature;
+    type SigningKeyMaterial = Secp256k1EcdsaPrivateKey;
+}
+
+impl fmt::Display for Secp256k1EcdsaPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for Secp256k1EcdsaPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256k1EcdsaPublicKey({})", self)
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1EcdsaPublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a Secp256k1EcdsaPublicKey. Only the uncompressed SEC1 encoding is accepted, so
+    /// that every public key has a single byte representation (and thus authentication key). The
+    /// point is checked to lie on the curve.
+    fn try_from(bytes: &[u8]) -> std::result::Result<Secp256k1EcdsaPublicKey, CryptoMaterialError> {
+        if bytes.len() != PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        if bytes[0] != libsecp256k1::util::TAG_PUBKEY_FULL {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        libsecp256k1::PublicKey::parse_slice(bytes, Some(libsecp256k1::PublicKeyFormat::Full))
+            .map(Secp256k1EcdsaPublicKey)
+            .map_err(|_| CryptoMaterialError::PointNotOnCurveError)
+    }
+}
+
+impl Length for Secp256k1EcdsaPublicKey {
+    fn length(&self) -> usize {
+        PUBLIC_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1EcdsaPublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+/////////////
+// Fuzzing //
+/////////////
+
+/// Produces a uniformly random secp256k1 ECDSA keypair from a seed
+#[cfg(any(test, feature = "fuzzing"))]
+pub fn keypair_strategy(
+) -> impl Strategy<Value = KeyPair<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>> {
+    test_utils::uniform_keypair_strategy::<Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey>()
+}
+
+/// Produces a uniformly random secp256k1 ECDSA public key
+#[cfg(any(test, feature = "fuzzing"))]
+impl proptest::arbitrary::Arbitrary for Secp256k1EcdsaPublicKey {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        keypair_strategy().prop_map(|v| v.public_key).boxed()
+    }
+}
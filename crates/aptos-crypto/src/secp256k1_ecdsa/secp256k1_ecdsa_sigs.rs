@@ -0,0 +1,142 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256k1 ECDSA signatures.
+
+use crate::{
+    hash::CryptoHash,
+    secp256k1_ecdsa::{
+        message_digest, Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey, SIGNATURE_LENGTH,
+    },
+    traits::*,
+};
+use anyhow::{anyhow, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
+use core::convert::TryFrom;
+use serde::Serialize;
+use std::fmt;
+
+/// A secp256k1 ECDSA signature
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Secp256k1EcdsaSignature(pub(crate) libsecp256k1::Signature);
+
+impl Secp256k1EcdsaSignature {
+    /// The length of the Secp256k1EcdsaSignature
+    pub const LENGTH: usize = SIGNATURE_LENGTH;
+
+    /// Serialize a Secp256k1EcdsaSignature as `r || s`.
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        self.0.serialize()
+    }
+
+    /// Deserialize a Secp256k1EcdsaSignature without any malleability checks, apart from expected
+    /// signature size and both components being below the order of the curve.
+    pub(crate) fn from_bytes_unchecked(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256k1EcdsaSignature, CryptoMaterialError> {
+        if bytes.len() != SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        libsecp256k1::Signature::parse_standard_slice(bytes)
+            .map(Secp256k1EcdsaSignature)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+
+    /// Check for correct size and signature malleability issues.
+    ///
+    /// Given a valid ECDSA signature `(r, s)`, the signature `(r, n - s)` is also valid for the
+    /// same message and key, where `n` is the order of the curve. To prevent third parties from
+    /// mauling signatures, we only accept the signature with `s <= n / 2`.
+    pub fn check_s_malleability(bytes: &[u8]) -> std::result::Result<(), CryptoMaterialError> {
+        let signature = Secp256k1EcdsaSignature::from_bytes_unchecked(bytes)?;
+        if signature.0.s.is_high() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(())
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for Secp256k1EcdsaSignature {
+    type SigningKeyMaterial = Secp256k1EcdsaPrivateKey;
+    type VerifyingKeyMaterial = Secp256k1EcdsaPublicKey;
+
+    /// Verifies that the provided signature is valid for the provided message, after hashing the
+    /// message with Keccak-256.
+    fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256k1EcdsaPublicKey,
+    ) -> Result<()> {
+        Self::verify_arbitrary_msg(self, &signing_message(message)?, public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`. The
+    /// message is hashed with Keccak-256 before verification, and high-s signatures are rejected.
+    fn verify_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_key: &Secp256k1EcdsaPublicKey,
+    ) -> Result<()> {
+        Secp256k1EcdsaSignature::check_s_malleability(&self.to_bytes())?;
+        if !libsecp256k1::verify(&message_digest(message), &self.0, &public_key.0) {
+            return Err(anyhow!("Secp256k1 ECDSA signature verification failed"));
+        }
+        Ok(())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Length for Secp256k1EcdsaSignature {
+    fn length(&self) -> usize {
+        SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256k1EcdsaSignature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for Secp256k1EcdsaSignature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256k1EcdsaSignature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Secp256k1EcdsaSignature, CryptoMaterialError> {
+        Secp256k1EcdsaSignature::check_s_malleability(bytes)?;
+        Secp256k1EcdsaSignature::from_bytes_unchecked(bytes)
+    }
+}
+
+impl PartialEq for Secp256k1EcdsaSignature {
+    fn eq(&self, other: &Secp256k1EcdsaSignature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Secp256k1EcdsaSignature {}
+
+impl fmt::Display for Secp256k1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for Secp256k1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256k1EcdsaSignature({})", self)
+    }
+}
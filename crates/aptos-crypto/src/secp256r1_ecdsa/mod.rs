@@ -0,0 +1,50 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for ECDSA signatures over the NIST P-256 (secp256r1) curve, with
+//! SHA2-256 as the message digest, as produced by WebAuthn authenticators (i.e., passkeys).
+//!
+//! Public keys are encoded as uncompressed SEC1 points and signatures as the 64-byte
+//! concatenation `r || s`. Signature verification rejects signatures whose `s` component is not
+//! in the lower half of the curve order, so that signatures are not malleable.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto_derive::{CryptoHasher, BCSCryptoHash};
+//! use aptos_crypto::{
+//!     secp256r1_ecdsa::*,
+//!     traits::{Signature, SigningKey, Uniform},
+//!     test_utils::KeyPair
+//! };
+//! use rand_core::OsRng;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
+//! pub struct TestCryptoDocTest(String);
+//! let message = TestCryptoDocTest("Test message".to_string());
+//!
+//! let mut rng = OsRng;
+//! let kp = KeyPair::<Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey>::generate(&mut rng);
+//!
+//! let signature = kp.private_key.sign(&message).unwrap();
+//! assert!(signature.verify(&message, &kp.public_key).is_ok());
+//! ```
+
+/// The length of the Secp256r1EcdsaPrivateKey
+pub const PRIVATE_KEY_LENGTH: usize = 32;
+/// The length of the Secp256r1EcdsaPublicKey, as an uncompressed SEC1 point
+pub const PUBLIC_KEY_LENGTH: usize = 65;
+/// The length of the Secp256r1EcdsaSignature
+pub const SIGNATURE_LENGTH: usize = 64;
+
+pub mod secp256r1_ecdsa_keys;
+pub mod secp256r1_ecdsa_sigs;
+
+#[cfg(any(test, feature = "fuzzing"))]
+pub use secp256r1_ecdsa_keys::keypair_strategy;
+pub use secp256r1_ecdsa_keys::{
+    Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPrivateKey as PrivateKey, Secp256r1EcdsaPublicKey,
+    Secp256r1EcdsaPublicKey as PublicKey,
+};
+pub use secp256r1_ecdsa_sigs::{Secp256r1EcdsaSignature, Secp256r1EcdsaSignature as Signature};
@@ -0,0 +1,141 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file implements traits for secp256r1 ECDSA signatures.
+
+use crate::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, SIGNATURE_LENGTH},
+    traits::*,
+};
+use anyhow::{anyhow, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
+use core::convert::TryFrom;
+use p256::ecdsa::signature::Verifier;
+use serde::Serialize;
+use std::fmt;
+
+/// A secp256r1 ECDSA signature
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Secp256r1EcdsaSignature(pub(crate) p256::ecdsa::Signature);
+
+impl Secp256r1EcdsaSignature {
+    /// The length of the Secp256r1EcdsaSignature
+    pub const LENGTH: usize = SIGNATURE_LENGTH;
+
+    /// Serialize a Secp256r1EcdsaSignature as `r || s`.
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_LENGTH] {
+        self.0.to_bytes().into()
+    }
+
+    /// Deserialize a Secp256r1EcdsaSignature without any malleability checks, apart from expected
+    /// signature size and both components being non-zero scalars.
+    pub(crate) fn from_bytes_unchecked(
+        bytes: &[u8],
+    ) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        if bytes.len() != SIGNATURE_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        p256::ecdsa::Signature::from_slice(bytes)
+            .map(Secp256r1EcdsaSignature)
+            .map_err(|_| CryptoMaterialError::DeserializationError)
+    }
+
+    /// Check for correct size and signature malleability issues.
+    ///
+    /// Given a valid ECDSA signature `(r, s)`, the signature `(r, n - s)` is also valid for the
+    /// same message and key, where `n` is the order of the curve. To prevent third parties from
+    /// mauling signatures, we only accept the signature with `s <= n / 2`.
+    pub fn check_s_malleability(bytes: &[u8]) -> std::result::Result<(), CryptoMaterialError> {
+        let signature = Secp256r1EcdsaSignature::from_bytes_unchecked(bytes)?;
+        if signature.0.normalize_s().is_some() {
+            return Err(CryptoMaterialError::CanonicalRepresentationError);
+        }
+        Ok(())
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl Signature for Secp256r1EcdsaSignature {
+    type SigningKeyMaterial = Secp256r1EcdsaPrivateKey;
+    type VerifyingKeyMaterial = Secp256r1EcdsaPublicKey;
+
+    /// Verifies that the provided signature is valid for the provided message, after hashing the
+    /// message with SHA2-256.
+    fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        Self::verify_arbitrary_msg(self, &signing_message(message)?, public_key)
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`. The
+    /// message is hashed with SHA2-256 before verification, and high-s signatures are rejected.
+    fn verify_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        Secp256r1EcdsaSignature::check_s_malleability(&self.to_bytes())?;
+        public_key
+            .0
+            .verify(message, &self.0)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl Length for Secp256r1EcdsaSignature {
+    fn length(&self) -> usize {
+        SIGNATURE_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Secp256r1EcdsaSignature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl std::hash::Hash for Secp256r1EcdsaSignature {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let encoded_signature = self.to_bytes();
+        state.write(&encoded_signature);
+    }
+}
+
+impl TryFrom<&[u8]> for Secp256r1EcdsaSignature {
+    type Error = CryptoMaterialError;
+
+    fn try_from(bytes: &[u8]) -> std::result::Result<Secp256r1EcdsaSignature, CryptoMaterialError> {
+        Secp256r1EcdsaSignature::check_s_malleability(bytes)?;
+        Secp256r1EcdsaSignature::from_bytes_unchecked(bytes)
+    }
+}
+
+impl PartialEq for Secp256r1EcdsaSignature {
+    fn eq(&self, other: &Secp256r1EcdsaSignature) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Secp256r1EcdsaSignature {}
+
+impl fmt::Display for Secp256r1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for Secp256r1EcdsaSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secp256r1EcdsaSignature({})", self)
+    }
+}
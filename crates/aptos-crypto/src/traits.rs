@@ -320,4 +320,12 @@ pub(crate) mod private {
     impl Sealed for crate::bls12381::PublicKey {}
     impl Sealed for crate::bls12381::Signature {}
     impl Sealed for crate::bls12381::ProofOfPossession {}
+
+    impl Sealed for crate::secp256k1_ecdsa::Secp256k1EcdsaPrivateKey {}
+    impl Sealed for crate::secp256k1_ecdsa::Secp256k1EcdsaPublicKey {}
+    impl Sealed for crate::secp256k1_ecdsa::Secp256k1EcdsaSignature {}
+
+    impl Sealed for crate::secp256r1_ecdsa::Secp256r1EcdsaPrivateKey {}
+    impl Sealed for crate::secp256r1_ecdsa::Secp256r1EcdsaPublicKey {}
+    impl Sealed for crate::secp256r1_ecdsa::Secp256r1EcdsaSignature {}
 }
@@ -0,0 +1,136 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    secp256k1_ecdsa::{
+        keypair_strategy, Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey,
+        Secp256k1EcdsaSignature, PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
+    },
+    test_utils::{TestAptosCrypto, TEST_SEED},
+    traits::*,
+    CryptoMaterialError,
+};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The order of the secp256k1 curve, big-endian.
+const ORDER: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE,
+    0xBA, 0xAE, 0xDC, 0xE6, 0xAF, 0x48, 0xA0, 0x3B, 0xBF, 0xD2, 0x5E, 0x8C, 0xD0, 0x36, 0x41, 0x41,
+];
+/// Returns `(r, n - s)` for the signature `(r, s)`, which verifies for the same message and key.
+fn negate_s(signature: &[u8]) -> Vec<u8> {
+    let mut mauled = signature.to_vec();
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = ORDER[i] as i16 - signature[32 + i] as i16 - borrow;
+        borrow = i16::from(diff < 0);
+        mauled[32 + i] = diff.rem_euclid(256) as u8;
+    }
+    mauled
+}
+
+proptest! {
+    #[test]
+    fn test_sign_and_verify(
+        keypair in keypair_strategy(),
+        message in ".*",
+    ) {
+        let message = TestAptosCrypto(message);
+        let signature = keypair.private_key.sign(&message).unwrap();
+        prop_assert_eq!(signature.to_bytes().len(), SIGNATURE_LENGTH);
+        prop_assert!(signature.verify(&message, &keypair.public_key).is_ok());
+
+        // Signing always produces the low-s form, whose negation is rejected.
+        let mauled = negate_s(&signature.to_bytes());
+        prop_assert_eq!(
+            Secp256k1EcdsaSignature::try_from(mauled.as_slice()),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+        let mauled = Secp256k1EcdsaSignature::from_bytes_unchecked(&mauled).unwrap();
+        prop_assert!(mauled.verify(&message, &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_serialization_round_trip(keypair in keypair_strategy()) {
+        let private_key_bytes = keypair.private_key.to_bytes();
+        prop_assert_eq!(private_key_bytes.len(), PRIVATE_KEY_LENGTH);
+        let private_key = Secp256k1EcdsaPrivateKey::try_from(&private_key_bytes[..]).unwrap();
+        prop_assert_eq!(&private_key, &keypair.private_key);
+
+        let public_key_bytes = keypair.public_key.to_bytes();
+        prop_assert_eq!(public_key_bytes.len(), PUBLIC_KEY_LENGTH);
+        let public_key = Secp256k1EcdsaPublicKey::try_from(&public_key_bytes[..]).unwrap();
+        prop_assert_eq!(&public_key, &keypair.public_key);
+        prop_assert_eq!(Secp256k1EcdsaPublicKey::from(&private_key), public_key);
+    }
+}
+
+#[test]
+fn test_wrong_message_or_key() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let private_key = Secp256k1EcdsaPrivateKey::generate(&mut rng);
+    let other_key = Secp256k1EcdsaPrivateKey::generate(&mut rng);
+    let message = TestAptosCrypto("Test message".to_string());
+    let signature = private_key.sign(&message).unwrap();
+
+    let other_message = TestAptosCrypto("Other message".to_string());
+    assert!(signature
+        .verify(&other_message, &private_key.public_key())
+        .is_err());
+    assert!(signature.verify(&message, &other_key.public_key()).is_err());
+}
+
+#[test]
+fn test_invalid_encodings() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let public_key = Secp256k1EcdsaPrivateKey::generate(&mut rng).public_key();
+    let bytes = public_key.to_bytes();
+
+    assert_eq!(
+        Secp256k1EcdsaPublicKey::try_from(&bytes[..PUBLIC_KEY_LENGTH - 1]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+
+    // Only the uncompressed encoding is accepted.
+    let mut compressed = bytes;
+    compressed[0] = 0x02;
+    assert_eq!(
+        Secp256k1EcdsaPublicKey::try_from(&compressed[..]),
+        Err(CryptoMaterialError::CanonicalRepresentationError)
+    );
+
+    let mut off_curve = bytes;
+    off_curve[PUBLIC_KEY_LENGTH - 1] ^= 1;
+    assert_eq!(
+        Secp256k1EcdsaPublicKey::try_from(&off_curve[..]),
+        Err(CryptoMaterialError::PointNotOnCurveError)
+    );
+
+    assert_eq!(
+        Secp256k1EcdsaPrivateKey::try_from(&[0u8; PRIVATE_KEY_LENGTH][..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+    assert_eq!(
+        Secp256k1EcdsaSignature::try_from(&[1u8; SIGNATURE_LENGTH - 1][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+}
+
+#[test]
+fn test_message_is_hashed_with_keccak() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let private_key = Secp256k1EcdsaPrivateKey::generate(&mut rng);
+    let signature = private_key.sign_arbitrary_message(b"");
+
+    // Keccak-256 of the empty string, as computed by Ethereum tooling.
+    let digest =
+        hex::decode("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap();
+    let digest = libsecp256k1::Message::parse_slice(&digest).unwrap();
+    assert!(libsecp256k1::verify(
+        &digest,
+        &signature.0,
+        &private_key.public_key().0
+    ));
+}
@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    secp256r1_ecdsa::{
+        keypair_strategy, Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey,
+        Secp256r1EcdsaSignature, PRIVATE_KEY_LENGTH, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH,
+    },
+    test_utils::{TestAptosCrypto, TEST_SEED},
+    traits::*,
+    CryptoMaterialError,
+};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// The order of the P-256 curve, big-endian.
+const ORDER: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+    0xBC, 0xE6, 0xFA, 0xAD, 0xA7, 0x17, 0x9E, 0x84, 0xF3, 0xB9, 0xCA, 0xC2, 0xFC, 0x63, 0x25, 0x51,
+];
+
+/// Returns `(r, n - s)` for the signature `(r, s)`, which verifies for the same message and key.
+fn negate_s(signature: &[u8]) -> Vec<u8> {
+    let mut mauled = signature.to_vec();
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = ORDER[i] as i16 - signature[32 + i] as i16 - borrow;
+        borrow = i16::from(diff < 0);
+        mauled[32 + i] = diff.rem_euclid(256) as u8;
+    }
+    mauled
+}
+
+proptest! {
+    #[test]
+    fn test_sign_and_verify(
+        keypair in keypair_strategy(),
+        message in ".*",
+    ) {
+        let message = TestAptosCrypto(message);
+        let signature = keypair.private_key.sign(&message).unwrap();
+        prop_assert_eq!(signature.to_bytes().len(), SIGNATURE_LENGTH);
+        prop_assert!(signature.verify(&message, &keypair.public_key).is_ok());
+
+        // Signing always produces the low-s form, whose negation is rejected.
+        let mauled = negate_s(&signature.to_bytes());
+        prop_assert_eq!(
+            Secp256r1EcdsaSignature::try_from(mauled.as_slice()),
+            Err(CryptoMaterialError::CanonicalRepresentationError)
+        );
+        let mauled = Secp256r1EcdsaSignature::from_bytes_unchecked(&mauled).unwrap();
+        prop_assert!(mauled.verify(&message, &keypair.public_key).is_err());
+    }
+
+    #[test]
+    fn test_serialization_round_trip(keypair in keypair_strategy()) {
+        let private_key_bytes = keypair.private_key.to_bytes();
+        prop_assert_eq!(private_key_bytes.len(), PRIVATE_KEY_LENGTH);
+        let private_key = Secp256r1EcdsaPrivateKey::try_from(&private_key_bytes[..]).unwrap();
+        prop_assert_eq!(&private_key, &keypair.private_key);
+
+        let public_key_bytes = keypair.public_key.to_bytes();
+        prop_assert_eq!(public_key_bytes.len(), PUBLIC_KEY_LENGTH);
+        let public_key = Secp256r1EcdsaPublicKey::try_from(&public_key_bytes[..]).unwrap();
+        prop_assert_eq!(&public_key, &keypair.public_key);
+        prop_assert_eq!(Secp256r1EcdsaPublicKey::from(&private_key), public_key);
+    }
+}
+
+#[test]
+fn test_wrong_message_or_key() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let private_key = Secp256r1EcdsaPrivateKey::generate(&mut rng);
+    let other_key = Secp256r1EcdsaPrivateKey::generate(&mut rng);
+    let message = TestAptosCrypto("Test message".to_string());
+    let signature = private_key.sign(&message).unwrap();
+
+    let other_message = TestAptosCrypto("Other message".to_string());
+    assert!(signature
+        .verify(&other_message, &private_key.public_key())
+        .is_err());
+    assert!(signature.verify(&message, &other_key.public_key()).is_err());
+}
+
+#[test]
+fn test_invalid_encodings() {
+    let mut rng = StdRng::from_seed(TEST_SEED);
+    let public_key = Secp256r1EcdsaPrivateKey::generate(&mut rng).public_key();
+    let bytes = public_key.to_bytes();
+
+    assert_eq!(
+        Secp256r1EcdsaPublicKey::try_from(&bytes[..PUBLIC_KEY_LENGTH - 1]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+
+    // Only the uncompressed encoding is accepted.
+    let mut compressed = bytes;
+    compressed[0] = 0x02;
+    assert_eq!(
+        Secp256r1EcdsaPublicKey::try_from(&compressed[..]),
+        Err(CryptoMaterialError::CanonicalRepresentationError)
+    );
+
+    let mut off_curve = bytes;
+    off_curve[PUBLIC_KEY_LENGTH - 1] ^= 1;
+    assert_eq!(
+        Secp256r1EcdsaPublicKey::try_from(&off_curve[..]),
+        Err(CryptoMaterialError::PointNotOnCurveError)
+    );
+
+    assert_eq!(
+        Secp256r1EcdsaPrivateKey::try_from(&[0u8; PRIVATE_KEY_LENGTH][..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+    assert_eq!(
+        Secp256r1EcdsaSignature::try_from(&[1u8; SIGNATURE_LENGTH - 1][..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+}
@@ -0,0 +1,57 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ed25519::Ed25519Signature, test_utils::TEST_SEED, traits::ValidCryptoMaterial, x25519,
+    Uniform as _,
+};
+use rand::SeedableRng;
+use std::convert::TryFrom;
+
+#[test]
+fn xeddsa_sign_and_verify() {
+    let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED);
+    let message = b"xeddsa test message";
+
+    // Half of the keys have an Edwards form with a negative sign bit,
+    // so we test enough keys to cover both cases.
+    for _ in 0..32 {
+        let private_key = x25519::PrivateKey::generate(&mut rng);
+        let public_key = private_key.public_key();
+
+        let signature = private_key.sign_xeddsa(message, &mut rng);
+        public_key.verify_xeddsa(message, &signature).unwrap();
+
+        // Signatures are randomized, but always valid
+        let other_signature = private_key.sign_xeddsa(message, &mut rng);
+        assert_ne!(signature, other_signature);
+        public_key.verify_xeddsa(message, &other_signature).unwrap();
+
+        // Verification fails for a different message
+        assert!(public_key
+            .verify_xeddsa(b"another message", &signature)
+            .is_err());
+    }
+}
+
+#[test]
+fn xeddsa_verify_fails_for_other_keys() {
+    let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED);
+    let message = b"xeddsa test message";
+    let private_key = x25519::PrivateKey::generate(&mut rng);
+    let other_public_key = x25519::PrivateKey::generate(&mut rng).public_key();
+
+    // Verification fails for a different public key
+    let signature = private_key.sign_xeddsa(message, &mut rng);
+    assert!(other_public_key.verify_xeddsa(message, &signature).is_err());
+
+    // Verification fails for a tampered signature
+    let mut signature_bytes = signature.to_bytes();
+    signature_bytes[0] ^= 1;
+    if let Ok(tampered_signature) = Ed25519Signature::try_from(&signature_bytes[..]) {
+        assert!(private_key
+            .public_key()
+            .verify_xeddsa(message, &tampered_signature)
+            .is_err());
+    }
+}
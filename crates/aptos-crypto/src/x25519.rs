@@ -32,13 +32,21 @@
 //!
 
 use crate::{
-    traits::{self, CryptoMaterialError, ValidCryptoMaterial, ValidCryptoMaterialStringExt},
+    ed25519::{Ed25519PublicKey, Ed25519Signature, ED25519_SIGNATURE_LENGTH},
+    traits::{
+        self, CryptoMaterialError, Signature, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
+    },
     x25519,
 };
+use anyhow::{anyhow, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE, montgomery::MontgomeryPoint, scalar::Scalar,
+};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
 use std::convert::{TryFrom, TryInto};
 //
 // Underlying Implementation
@@ -121,6 +129,64 @@ impl PrivateKey {
             Ok(potential_x25519)
         }
     }
+
+    /// Signs the given message with this key, following the XEdDSA scheme
+    /// (see https://signal.org/docs/specifications/xeddsa/). The signature is
+    /// an Ed25519 signature under the Edwards form of the public key, and can
+    /// be verified using [`PublicKey::verify_xeddsa`].
+    ///
+    /// This allows the holder of an identity key to sign messages that anyone
+    /// knowing the public key can verify. Callers MUST domain separate the
+    /// signed messages from any other use of the key (e.g., by signing a
+    /// [`traits::signing_message`]).
+    pub fn sign_xeddsa<R>(&self, message: &[u8], rng: &mut R) -> Ed25519Signature
+    where
+        R: RngCore + CryptoRng,
+    {
+        // Calculate the Edwards key pair with a positive (sign bit 0) public key
+        let montgomery_scalar = Scalar::from_bytes_mod_order(self.0.to_bytes());
+        let edwards_point = (&montgomery_scalar * &ED25519_BASEPOINT_TABLE).compress();
+        let private_scalar = if edwards_point.as_bytes()[31] & 0x80 != 0 {
+            -montgomery_scalar
+        } else {
+            montgomery_scalar
+        };
+        let public_point = (&private_scalar * &ED25519_BASEPOINT_TABLE).compress();
+
+        // Derive the nonce from the private scalar, the message and fresh randomness
+        let mut randomness = [0u8; 64];
+        rng.fill_bytes(&mut randomness);
+        let mut nonce_prefix = [0xFFu8; 32];
+        nonce_prefix[0] = 0xFE;
+        let nonce = scalar_from_sha512(&[
+            &nonce_prefix,
+            private_scalar.as_bytes(),
+            message,
+            &randomness,
+        ]);
+        let nonce_point = (&nonce * &ED25519_BASEPOINT_TABLE).compress();
+
+        // Compute the (standard Ed25519) challenge and the signature
+        let challenge =
+            scalar_from_sha512(&[nonce_point.as_bytes(), public_point.as_bytes(), message]);
+        let s = nonce + challenge * private_scalar;
+
+        let mut signature = [0u8; ED25519_SIGNATURE_LENGTH];
+        signature[..32].copy_from_slice(nonce_point.as_bytes());
+        signature[32..].copy_from_slice(s.as_bytes());
+        Ed25519Signature::try_from(&signature[..]).expect("XEdDSA signatures are canonical")
+    }
+}
+
+/// Returns the SHA512 hash of the concatenated inputs, reduced to a scalar
+fn scalar_from_sha512(inputs: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for input in inputs {
+        hasher.update(input);
+    }
+    let mut hash = [0u8; 64];
+    hash.copy_from_slice(&hasher.finalize());
+    Scalar::from_bytes_mod_order_wide(&hash)
 }
 
 impl PublicKey {
@@ -144,6 +210,26 @@ impl PublicKey {
 
         Ok(x25519::PublicKey::from(ed_point.to_montgomery().to_bytes()))
     }
+
+    /// Verifies an XEdDSA signature of the given message (see
+    /// [`PrivateKey::sign_xeddsa`]).
+    pub fn verify_xeddsa(&self, message: &[u8], signature: &Ed25519Signature) -> Result<()> {
+        signature.verify_arbitrary_msg(message, &self.to_xeddsa_public_key()?)
+    }
+
+    /// Returns the Edwards form (with sign bit 0) of this public key, as
+    /// used by XEdDSA. Non-canonical encodings are rejected.
+    fn to_xeddsa_public_key(&self) -> Result<Ed25519PublicKey> {
+        let edwards_point = MontgomeryPoint(self.0)
+            .to_edwards(0)
+            .ok_or_else(|| anyhow!("The public key is not a valid curve point"))?;
+        if edwards_point.to_montgomery().to_bytes() != self.0 {
+            return Err(anyhow!("The public key is not canonically encoded"));
+        }
+        Ok(Ed25519PublicKey::try_from(
+            &edwards_point.compress().to_bytes()[..],
+        )?)
+    }
 }
 
 //
@@ -29,6 +29,12 @@ impl serde::Serialize for AccountSignature {
                 account_signature::Signature::MultiEd25519(v) => {
                     struct_ser.serialize_field("multiEd25519", v)?;
                 }
+                account_signature::Signature::Secp256r1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256r1Ecdsa", v)?;
+                }
+                account_signature::Signature::Secp256k1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256k1Ecdsa", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -45,6 +51,10 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
             "ed25519",
             "multi_ed25519",
             "multiEd25519",
+            "secp256r1_ecdsa",
+            "secp256r1Ecdsa",
+            "secp256k1_ecdsa",
+            "secp256k1Ecdsa",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -52,6 +62,8 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
             Type,
             Ed25519,
             MultiEd25519,
+            Secp256r1Ecdsa,
+            Secp256k1Ecdsa,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -76,6 +88,8 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                             "type" => Ok(GeneratedField::Type),
                             "ed25519" => Ok(GeneratedField::Ed25519),
                             "multiEd25519" | "multi_ed25519" => Ok(GeneratedField::MultiEd25519),
+                            "secp256r1Ecdsa" | "secp256r1_ecdsa" => Ok(GeneratedField::Secp256r1Ecdsa),
+                            "secp256k1Ecdsa" | "secp256k1_ecdsa" => Ok(GeneratedField::Secp256k1Ecdsa),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -117,6 +131,20 @@ impl<'de> serde::Deserialize<'de> for AccountSignature {
                                 return Err(serde::de::Error::duplicate_field("multiEd25519"));
                             }
                             signature__ = map.next_value::<::std::option::Option<_>>()?.map(account_signature::Signature::MultiEd25519)
+;
+                        }
+                        GeneratedField::Secp256r1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256r1Ecdsa"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(account_signature::Signature::Secp256r1Ecdsa)
+;
+                        }
+                        GeneratedField::Secp256k1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256k1Ecdsa"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(account_signature::Signature::Secp256k1Ecdsa)
 ;
                         }
                     }
@@ -140,6 +168,8 @@ impl serde::Serialize for account_signature::Type {
             Self::Unspecified => "TYPE_UNSPECIFIED",
             Self::Ed25519 => "TYPE_ED25519",
             Self::MultiEd25519 => "TYPE_MULTI_ED25519",
+            Self::Secp256r1Ecdsa => "TYPE_SECP256R1_ECDSA",
+            Self::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
         };
         serializer.serialize_str(variant)
     }
@@ -154,6 +184,8 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
             "TYPE_UNSPECIFIED",
             "TYPE_ED25519",
             "TYPE_MULTI_ED25519",
+            "TYPE_SECP256R1_ECDSA",
+            "TYPE_SECP256K1_ECDSA",
         ];
 
         struct GeneratedVisitor;
@@ -199,6 +231,8 @@ impl<'de> serde::Deserialize<'de> for account_signature::Type {
                     "TYPE_UNSPECIFIED" => Ok(account_signature::Type::Unspecified),
                     "TYPE_ED25519" => Ok(account_signature::Type::Ed25519),
                     "TYPE_MULTI_ED25519" => Ok(account_signature::Type::MultiEd25519),
+                    "TYPE_SECP256R1_ECDSA" => Ok(account_signature::Type::Secp256r1Ecdsa),
+                    "TYPE_SECP256K1_ECDSA" => Ok(account_signature::Type::Secp256k1Ecdsa),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -1563,6 +1597,9 @@ impl serde::Serialize for Event {
         if !self.data.is_empty() {
             len += 1;
         }
+        if self.kind != 0 {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.Event", len)?;
         if let Some(v) = self.key.as_ref() {
             struct_ser.serialize_field("key", v)?;
@@ -1579,6 +1616,11 @@ impl serde::Serialize for Event {
         if !self.data.is_empty() {
             struct_ser.serialize_field("data", &self.data)?;
         }
+        if self.kind != 0 {
+            let v = event::EventKind::from_i32(self.kind)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.kind)))?;
+            struct_ser.serialize_field("kind", &v)?;
+        }
         struct_ser.end()
     }
 }
@@ -1596,6 +1638,7 @@ impl<'de> serde::Deserialize<'de> for Event {
             "type_str",
             "typeStr",
             "data",
+            "kind",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -1605,6 +1648,7 @@ impl<'de> serde::Deserialize<'de> for Event {
             Type,
             TypeStr,
             Data,
+            Kind,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -1631,6 +1675,7 @@ impl<'de> serde::Deserialize<'de> for Event {
                             "type" => Ok(GeneratedField::Type),
                             "typeStr" | "type_str" => Ok(GeneratedField::TypeStr),
                             "data" => Ok(GeneratedField::Data),
+                            "kind" => Ok(GeneratedField::Kind),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -1655,6 +1700,7 @@ impl<'de> serde::Deserialize<'de> for Event {
                 let mut r#type__ = None;
                 let mut type_str__ = None;
                 let mut data__ = None;
+                let mut kind__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::Key => {
@@ -1689,6 +1735,12 @@ impl<'de> serde::Deserialize<'de> for Event {
                             }
                             data__ = Some(map.next_value()?);
                         }
+                        GeneratedField::Kind => {
+                            if kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("kind"));
+                            }
+                            kind__ = Some(map.next_value::<event::EventKind>()? as i32);
+                        }
                     }
                 }
                 Ok(Event {
@@ -1697,12 +1749,89 @@ impl<'de> serde::Deserialize<'de> for Event {
                     r#type: r#type__,
                     type_str: type_str__.unwrap_or_default(),
                     data: data__.unwrap_or_default(),
+                    kind: kind__.unwrap_or_default(),
                 })
             }
         }
         deserializer.deserialize_struct("aptos.transaction.v1.Event", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for event::EventKind {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "EVENT_KIND_UNSPECIFIED",
+            Self::Handle => "EVENT_KIND_HANDLE",
+            Self::Module => "EVENT_KIND_MODULE",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for event::EventKind {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "EVENT_KIND_UNSPECIFIED",
+            "EVENT_KIND_HANDLE",
+            "EVENT_KIND_MODULE",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = event::EventKind;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(event::EventKind::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(event::EventKind::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "EVENT_KIND_UNSPECIFIED" => Ok(event::EventKind::Unspecified),
+                    "EVENT_KIND_HANDLE" => Ok(event::EventKind::Handle),
+                    "EVENT_KIND_MODULE" => Ok(event::EventKind::Module),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
 impl serde::Serialize for EventKey {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4832,6 +4961,272 @@ impl<'de> serde::Deserialize<'de> for ScriptWriteSet {
         deserializer.deserialize_struct("aptos.transaction.v1.ScriptWriteSet", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for Secp256k1EcdsaSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.Secp256k1EcdsaSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field("publicKey", pbjson::private::base64::encode(&self.public_key).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Secp256k1EcdsaSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "public_key",
+            "publicKey",
+            "signature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" | "public_key" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Secp256k1EcdsaSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.Secp256k1EcdsaSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Secp256k1EcdsaSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(Secp256k1EcdsaSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.Secp256k1EcdsaSignature", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Secp256r1EcdsaSignature {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.public_key.is_empty() {
+            len += 1;
+        }
+        if !self.signature.is_empty() {
+            len += 1;
+        }
+        if !self.authenticator_data.is_empty() {
+            len += 1;
+        }
+        if !self.client_data_json.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.Secp256r1EcdsaSignature", len)?;
+        if !self.public_key.is_empty() {
+            struct_ser.serialize_field("publicKey", pbjson::private::base64::encode(&self.public_key).as_str())?;
+        }
+        if !self.signature.is_empty() {
+            struct_ser.serialize_field("signature", pbjson::private::base64::encode(&self.signature).as_str())?;
+        }
+        if !self.authenticator_data.is_empty() {
+            struct_ser.serialize_field("authenticatorData", pbjson::private::base64::encode(&self.authenticator_data).as_str())?;
+        }
+        if !self.client_data_json.is_empty() {
+            struct_ser.serialize_field("clientDataJson", pbjson::private::base64::encode(&self.client_data_json).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Secp256r1EcdsaSignature {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "public_key",
+            "publicKey",
+            "signature",
+            "authenticator_data",
+            "authenticatorData",
+            "client_data_json",
+            "clientDataJson",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PublicKey,
+            Signature,
+            AuthenticatorData,
+            ClientDataJson,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "publicKey" | "public_key" => Ok(GeneratedField::PublicKey),
+                            "signature" => Ok(GeneratedField::Signature),
+                            "authenticatorData" | "authenticator_data" => Ok(GeneratedField::AuthenticatorData),
+                            "clientDataJson" | "client_data_json" => Ok(GeneratedField::ClientDataJson),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Secp256r1EcdsaSignature;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.Secp256r1EcdsaSignature")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<Secp256r1EcdsaSignature, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut public_key__ = None;
+                let mut signature__ = None;
+                let mut authenticator_data__ = None;
+                let mut client_data_json__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PublicKey => {
+                            if public_key__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("publicKey"));
+                            }
+                            public_key__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Signature => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signature"));
+                            }
+                            signature__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::AuthenticatorData => {
+                            if authenticator_data__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("authenticatorData"));
+                            }
+                            authenticator_data__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ClientDataJson => {
+                            if client_data_json__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientDataJson"));
+                            }
+                            client_data_json__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(Secp256r1EcdsaSignature {
+                    public_key: public_key__.unwrap_or_default(),
+                    signature: signature__.unwrap_or_default(),
+                    authenticator_data: authenticator_data__.unwrap_or_default(),
+                    client_data_json: client_data_json__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.Secp256r1EcdsaSignature", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for Signature {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -4866,6 +5261,12 @@ impl serde::Serialize for Signature {
                 signature::Signature::FeePayer(v) => {
                     struct_ser.serialize_field("feePayer", v)?;
                 }
+                signature::Signature::Secp256r1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256r1Ecdsa", v)?;
+                }
+                signature::Signature::Secp256k1Ecdsa(v) => {
+                    struct_ser.serialize_field("secp256k1Ecdsa", v)?;
+                }
             }
         }
         struct_ser.end()
@@ -4886,6 +5287,10 @@ impl<'de> serde::Deserialize<'de> for Signature {
             "multiAgent",
             "fee_payer",
             "feePayer",
+            "secp256r1_ecdsa",
+            "secp256r1Ecdsa",
+            "secp256k1_ecdsa",
+            "secp256k1Ecdsa",
         ];
 
         #[allow(clippy::enum_variant_names)]
@@ -4895,6 +5300,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
             MultiEd25519,
             MultiAgent,
             FeePayer,
+            Secp256r1Ecdsa,
+            Secp256k1Ecdsa,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -4921,6 +5328,8 @@ impl<'de> serde::Deserialize<'de> for Signature {
                             "multiEd25519" | "multi_ed25519" => Ok(GeneratedField::MultiEd25519),
                             "multiAgent" | "multi_agent" => Ok(GeneratedField::MultiAgent),
                             "feePayer" | "fee_payer" => Ok(GeneratedField::FeePayer),
+                            "secp256r1Ecdsa" | "secp256r1_ecdsa" => Ok(GeneratedField::Secp256r1Ecdsa),
+                            "secp256k1Ecdsa" | "secp256k1_ecdsa" => Ok(GeneratedField::Secp256k1Ecdsa),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -4976,6 +5385,20 @@ impl<'de> serde::Deserialize<'de> for Signature {
                                 return Err(serde::de::Error::duplicate_field("feePayer"));
                             }
                             signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::FeePayer)
+;
+                        }
+                        GeneratedField::Secp256r1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256r1Ecdsa"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::Secp256r1Ecdsa)
+;
+                        }
+                        GeneratedField::Secp256k1Ecdsa => {
+                            if signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secp256k1Ecdsa"));
+                            }
+                            signature__ = map.next_value::<::std::option::Option<_>>()?.map(signature::Signature::Secp256k1Ecdsa)
 ;
                         }
                     }
@@ -5001,6 +5424,8 @@ impl serde::Serialize for signature::Type {
             Self::MultiEd25519 => "TYPE_MULTI_ED25519",
             Self::MultiAgent => "TYPE_MULTI_AGENT",
             Self::FeePayer => "TYPE_FEE_PAYER",
+            Self::Secp256r1Ecdsa => "TYPE_SECP256R1_ECDSA",
+            Self::Secp256k1Ecdsa => "TYPE_SECP256K1_ECDSA",
         };
         serializer.serialize_str(variant)
     }
@@ -5017,6 +5442,8 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
             "TYPE_MULTI_ED25519",
             "TYPE_MULTI_AGENT",
             "TYPE_FEE_PAYER",
+            "TYPE_SECP256R1_ECDSA",
+            "TYPE_SECP256K1_ECDSA",
         ];
 
         struct GeneratedVisitor;
@@ -5064,6 +5491,8 @@ impl<'de> serde::Deserialize<'de> for signature::Type {
                     "TYPE_MULTI_ED25519" => Ok(signature::Type::MultiEd25519),
                     "TYPE_MULTI_AGENT" => Ok(signature::Type::MultiAgent),
                     "TYPE_FEE_PAYER" => Ok(signature::Type::FeePayer),
+                    "TYPE_SECP256R1_ECDSA" => Ok(signature::Type::Secp256r1Ecdsa),
+                    "TYPE_SECP256K1_ECDSA" => Ok(signature::Type::Secp256k1Ecdsa),
                     _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
                 }
             }
@@ -307,6 +307,21 @@ impl Client {
         self.json(response).await
     }
 
+    /// Executes the view function, returning the BCS encoded return values
+    pub async fn view_bcs(
+        &self,
+        request: &ViewRequest,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Vec<u8>>>> {
+        let mut url = self.build_path("view")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("ledger_version={}", version).as_str()));
+        }
+
+        let response = self.post_bcs(url, serde_json::to_value(request)?).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn simulate(
         &self,
         txn: &SignedTransaction,
@@ -1196,7 +1211,7 @@ impl Client {
                 .into_iter()
                 .map(|event| {
                     let version = event.transaction_version;
-                    let sequence_number = event.event.sequence_number();
+                    let sequence_number = event.event.v0()?.sequence_number();
 
                     Ok(VersionedNewBlockEvent {
                         event: bcs::from_bytes(event.event.event_data())?,
@@ -35,6 +35,7 @@ use crate::{
 };
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
+    secp256k1_ecdsa::{Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature},
     signing_message, ValidCryptoMaterialStringExt,
 };
 use aptos_global_constants::adjust_gas_headroom;
@@ -162,17 +163,22 @@ async fn construction_combine(
 
     let signature = &request.signatures[0];
 
-    if signature.signature_type != SignatureType::Ed25519
-        || signature.public_key.curve_type != CurveType::Edwards25519
-    {
-        return Err(ApiError::InvalidSignatureType);
-    }
-
-    let public_key: Ed25519PublicKey =
-        decode_key(&signature.public_key.hex_bytes, "Ed25519PublicKey")?;
-    let signature: Ed25519Signature = decode_key(&signature.hex_bytes, "Ed25519Signature")?;
-
-    let signed_txn = SignedTransaction::new(unsigned_txn, public_key, signature);
+    let signed_txn = match (signature.signature_type, signature.public_key.curve_type) {
+        (SignatureType::Ed25519, CurveType::Edwards25519) => {
+            let public_key: Ed25519PublicKey =
+                decode_key(&signature.public_key.hex_bytes, "Ed25519PublicKey")?;
+            let signature: Ed25519Signature = decode_key(&signature.hex_bytes, "Ed25519Signature")?;
+            SignedTransaction::new(unsigned_txn, public_key, signature)
+        },
+        (SignatureType::Ecdsa, CurveType::Secp256k1) => {
+            let public_key: Secp256k1EcdsaPublicKey =
+                decode_key(&signature.public_key.hex_bytes, "Secp256k1EcdsaPublicKey")?;
+            let signature: Secp256k1EcdsaSignature =
+                decode_key(&signature.hex_bytes, "Secp256k1EcdsaSignature")?;
+            SignedTransaction::new_secp256k1_ecdsa(unsigned_txn, public_key, signature)
+        },
+        _ => return Err(ApiError::InvalidSignatureType),
+    };
 
     Ok(ConstructionCombineResponse {
         signed_transaction: encode_bcs(&signed_txn)?,
@@ -193,9 +199,19 @@ async fn construction_derive(
     debug!("/construction/derive {:?}", request);
     check_network(request.network_identifier, &server_context)?;
 
-    let public_key: Ed25519PublicKey =
-        decode_key(&request.public_key.hex_bytes, "Ed25519PublicKey")?;
-    let address = AuthenticationKey::ed25519(&public_key).derived_address();
+    let authentication_key = match request.public_key.curve_type {
+        CurveType::Edwards25519 => {
+            let public_key: Ed25519PublicKey =
+                decode_key(&request.public_key.hex_bytes, "Ed25519PublicKey")?;
+            AuthenticationKey::ed25519(&public_key)
+        },
+        CurveType::Secp256k1 => {
+            let public_key: Secp256k1EcdsaPublicKey =
+                decode_key(&request.public_key.hex_bytes, "Secp256k1EcdsaPublicKey")?;
+            AuthenticationKey::secp256k1_ecdsa(&public_key)
+        },
+    };
+    let address = authentication_key.derived_address();
 
     Ok(ConstructionDeriveResponse {
         account_identifier: AccountIdentifier::base_account(address),
@@ -345,24 +361,35 @@ async fn simulate_transaction(
     // TODO: Only single signer supported
     let public_key =
         if let Some(public_key) = options.public_keys.as_ref().and_then(|inner| inner.first()) {
-            Ed25519PublicKey::from_encoded_string(&public_key.hex_bytes).map_err(|err| {
-                ApiError::InvalidInput(Some(format!(
-                    "Public key provided is not parsable {:?}",
-                    err
-                )))
-            })?
+            public_key
         } else {
             return Err(ApiError::InvalidInput(Some(
                 "Must provide public_keys for simulation otherwise it can't simulate!".to_string(),
             )));
         };
+    let parse_error = |err| {
+        ApiError::InvalidInput(Some(format!(
+            "Public key provided is not parsable {:?}",
+            err
+        )))
+    };
 
     // Sign the transaction with a dummy signature of all zeros as required by the API
-    let signed_transaction = SignedTransaction::new(
-        unsigned_transaction,
-        public_key,
-        Ed25519Signature::try_from([0u8; 64].as_ref()).expect("Zero signature should always work"),
-    );
+    let signed_transaction = match public_key.curve_type {
+        CurveType::Edwards25519 => SignedTransaction::new(
+            unsigned_transaction,
+            Ed25519PublicKey::from_encoded_string(&public_key.hex_bytes).map_err(parse_error)?,
+            Ed25519Signature::try_from([0u8; 64].as_ref())
+                .expect("Zero signature should always work"),
+        ),
+        CurveType::Secp256k1 => SignedTransaction::new_secp256k1_ecdsa(
+            unsigned_transaction,
+            Secp256k1EcdsaPublicKey::from_encoded_string(&public_key.hex_bytes)
+                .map_err(parse_error)?,
+            Secp256k1EcdsaSignature::try_from([0u8; 64].as_ref())
+                .expect("Zero signature should always work"),
+        ),
+    };
 
     // Simulate, filling in the fields that aren't being currently handled
     // This API will always succeed unless 2 conditions
@@ -1234,7 +1261,15 @@ async fn construction_payloads(
     let payload = SigningPayload {
         account_identifier: AccountIdentifier::base_account(sender),
         hex_bytes: signing_message,
-        signature_type: Some(SignatureType::Ed25519),
+        signature_type: Some(
+            request
+                .public_keys
+                .as_ref()
+                .and_then(|keys| keys.first())
+                .map_or(SignatureType::Ed25519, |key| {
+                    key.curve_type.signature_type()
+                }),
+        ),
     };
 
     // Transaction is both the unsigned transaction and the payload
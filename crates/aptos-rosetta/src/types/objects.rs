@@ -23,7 +23,10 @@ use crate::{
 };
 use anyhow::anyhow;
 use aptos_cached_packages::aptos_stdlib;
-use aptos_crypto::{ed25519::Ed25519PublicKey, ValidCryptoMaterialStringExt};
+use aptos_crypto::{
+    ed25519::Ed25519PublicKey, secp256k1_ecdsa::Secp256k1EcdsaPublicKey,
+    ValidCryptoMaterialStringExt,
+};
 use aptos_logger::warn;
 use aptos_rest_client::aptos_api_types::{TransactionOnChainData, U64};
 use aptos_types::{
@@ -150,12 +153,24 @@ pub struct CurrencyMetadata {
     pub move_type: String,
 }
 
-/// Various signing curves supported by Rosetta.  We only use [`CurveType::Edwards25519`]
+/// Various signing curves supported by Rosetta.  We only use [`CurveType::Edwards25519`] and
+/// [`CurveType::Secp256k1`]
 /// [API Spec](https://www.rosetta-api.org/docs/models/CurveType.html)
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CurveType {
     Edwards25519,
+    Secp256k1,
+}
+
+impl CurveType {
+    /// The signature type used to sign with keys on this curve
+    pub fn signature_type(self) -> SignatureType {
+        match self {
+            CurveType::Edwards25519 => SignatureType::Ed25519,
+            CurveType::Secp256k1 => SignatureType::Ecdsa,
+        }
+    }
 }
 
 /// A representation of a single account change in a transaction
@@ -789,6 +804,31 @@ impl TryFrom<PublicKey> for Ed25519PublicKey {
     }
 }
 
+impl TryFrom<Secp256k1EcdsaPublicKey> for PublicKey {
+    type Error = anyhow::Error;
+
+    fn try_from(public_key: Secp256k1EcdsaPublicKey) -> Result<Self, Self::Error> {
+        Ok(PublicKey {
+            hex_bytes: public_key.to_encoded_string()?,
+            curve_type: CurveType::Secp256k1,
+        })
+    }
+}
+
+impl TryFrom<PublicKey> for Secp256k1EcdsaPublicKey {
+    type Error = anyhow::Error;
+
+    fn try_from(public_key: PublicKey) -> Result<Self, Self::Error> {
+        if public_key.curve_type != CurveType::Secp256k1 {
+            return Err(anyhow!("Invalid curve type"));
+        }
+
+        Ok(Secp256k1EcdsaPublicKey::from_encoded_string(
+            &public_key.hex_bytes,
+        )?)
+    }
+}
+
 /// Signature containing the signed payload and the encoded signed payload
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/Signature.html)
@@ -805,13 +845,14 @@ pub struct Signature {
 }
 
 /// Cryptographic signature type used for signing transactions.  Aptos only uses
-/// [`SignatureType::Ed25519`]
+/// [`SignatureType::Ed25519`] and [`SignatureType::Ecdsa`] (secp256k1, as `r || s`)
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/SignatureType.html)
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SignatureType {
     Ed25519,
+    Ecdsa,
 }
 
 /// Signing payload should be signed by the client with their own private key
@@ -1722,9 +1763,8 @@ async fn parse_delegation_pool_resource_changes(
                         event
                     } else {
                         warn!(
-                            "Failed to parse withdraw undelegated event! Skipping for {}:{}",
-                            e.key().get_creator_address(),
-                            e.key().get_creation_number()
+                            "Failed to parse withdraw undelegated event! Skipping for {:?}",
+                            e.event_key()
                         );
                         continue;
                     };
@@ -1817,8 +1857,8 @@ fn filter_events<F: Fn(&EventKey, &ContractEvent) -> Option<T>, T>(
 ) -> Vec<T> {
     events
         .iter()
-        .filter(|event| event.key() == event_key)
-        .sorted_by(|a, b| a.sequence_number().cmp(&b.sequence_number()))
+        .filter(|event| event.event_key() == Some(event_key))
+        .sorted_by_key(|event| event.v0().map(|event| event.sequence_number()).ok())
         .filter_map(|event| parser(event_key, event))
         .collect()
 }
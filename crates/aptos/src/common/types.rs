@@ -1655,6 +1655,35 @@ impl TransactionOptions {
             },
         }
 
+        // Generate the HTML report and the speedscope & Chrome trace exports.
+        let erased = gas_log.to_erased();
+        let mut report = String::new();
+        erased
+            .to_html_report(
+                &mut report,
+                &format!("Transaction {}", hash),
+                &gas_log.exec_io.aggregate_gas_events(),
+            )
+            .map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to render gas report: {:?}", err))
+            })?;
+        create_dir!();
+        for (extension, content) in [
+            ("html", report),
+            ("speedscope.json", erased.to_speedscope(&raw_file_name)),
+            ("trace.json", erased.to_chrome_trace()),
+        ] {
+            let file_path = Path::join(dir, format!("{}.{}", raw_file_name, extension));
+            std::fs::write(&file_path, content).map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Failed to write gas profile to file {} : {:?}",
+                    file_path.display(),
+                    err
+                ))
+            })?;
+            println!("Gas profile saved to {}", file_path.display());
+        }
+
         println!();
 
         // Generate the transaction summary
@@ -56,6 +56,8 @@ use itertools::Itertools;
 use move_cli::{self, base::test::UnitTestResult};
 use move_command_line_common::env::MOVE_HOME;
 use move_core_types::{identifier::Identifier, language_storage::ModuleId, u256::U256};
+#[cfg(feature = "debug-adapter")]
+use move_debug_adapter::{DebugAdapter, SourceIndex};
 use move_package::{source_package::layout::SourcePackageLayout, BuildConfig};
 use move_unit_test::UnitTestingConfig;
 pub use package_hooks::*;
@@ -440,6 +442,12 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
+
+    /// Serve the Debug Adapter Protocol on this port of localhost, and run the tests one at a
+    /// time under the control of the debugger connecting to it
+    #[cfg(feature = "debug-adapter")]
+    #[clap(long, value_name = "PORT")]
+    pub dap: Option<u16>,
 }
 
 #[async_trait]
@@ -477,26 +485,48 @@ impl CliCommand<&'static str> for TestPackage {
             }
         }
         let path = self.move_options.get_package_path()?;
-        let result = move_cli::base::test::run_move_unit_tests(
-            path.as_path(),
-            config.clone(),
-            UnitTestingConfig {
-                filter: self.filter.clone(),
-                report_stacktrace_on_abort: true,
-                report_storage_on_error: self.dump_state,
-                ignore_compile_warnings: self.ignore_compile_warnings,
-                ..UnitTestingConfig::default_with_bound(None)
-            },
-            // TODO(Gas): we may want to switch to non-zero costs in the future
-            aptos_debug_natives::aptos_debug_natives(
-                NativeGasParameters::zeros(),
-                MiscGasParameters::zeros(),
-            ),
-            None,
-            self.compute_coverage,
-            &mut std::io::stdout(),
-        )
-        .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))?;
+        #[cfg_attr(not(feature = "debug-adapter"), allow(unused_mut))]
+        let mut unit_test_config = UnitTestingConfig {
+            filter: self.filter.clone(),
+            report_stacktrace_on_abort: true,
+            report_storage_on_error: self.dump_state,
+            ignore_compile_warnings: self.ignore_compile_warnings,
+            ..UnitTestingConfig::default_with_bound(None)
+        };
+        #[cfg(feature = "debug-adapter")]
+        if self.dap.is_some() {
+            unit_test_config.num_threads = 1;
+        }
+        let run_tests = {
+            let path = path.clone();
+            let config = config.clone();
+            let compute_coverage = self.compute_coverage;
+            move || {
+                move_cli::base::test::run_move_unit_tests(
+                    path.as_path(),
+                    config,
+                    unit_test_config,
+                    // TODO(Gas): we may want to switch to non-zero costs in the future
+                    aptos_debug_natives::aptos_debug_natives(
+                        NativeGasParameters::zeros(),
+                        MiscGasParameters::zeros(),
+                    ),
+                    None,
+                    compute_coverage,
+                    &mut std::io::stdout(),
+                )
+            }
+        };
+        #[cfg(feature = "debug-adapter")]
+        let result = match self.dap {
+            Some(port) => test_source_index(path.as_path(), &config)
+                .and_then(|sources| DebugAdapter::new(sources).serve_tcp(port, run_tests)),
+            None => run_tests(),
+        };
+        #[cfg(not(feature = "debug-adapter"))]
+        let result = run_tests();
+        let result = result
+            .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))?;
 
         // Print coverage summary if --coverage is set
         if self.compute_coverage {
@@ -519,6 +549,31 @@ impl CliCommand<&'static str> for TestPackage {
     }
 }
 
+/// Indexes the sources of a package and its dependencies, compiled like for unit tests, so that a
+/// debugger can map the bytecode executed by the tests back to them.
+#[cfg(feature = "debug-adapter")]
+fn test_source_index(path: &Path, config: &BuildConfig) -> anyhow::Result<SourceIndex> {
+    let package = BuildConfig {
+        test_mode: true,
+        dev_mode: true,
+        ..config.clone()
+    }
+    .compile_package(path, &mut Vec::new())?;
+
+    let mut sources = SourceIndex::default();
+    for (name, address) in &package.compiled_package_info.address_alias_instantiation {
+        sources.add_named_address(name.as_str(), *address);
+    }
+    let units = package
+        .root_compiled_units
+        .iter()
+        .chain(package.deps_compiled_units.iter().map(|(_, unit)| unit));
+    for unit in units {
+        sources.add_compiled_unit(&unit.unit, &unit.source_path)?;
+    }
+    Ok(sources)
+}
+
 #[async_trait]
 impl CliCommand<()> for TransactionalTestOpts {
     fn command_name(&self) -> &'static str {
@@ -7,6 +7,8 @@ use crate::common::types::{CliError, CliTypedResult};
 /// here and make minor modifications.
 ///
 use clap::Parser;
+#[cfg(feature = "debug-adapter")]
+use move_debug_adapter::{DebugAdapter, SourceIndex};
 use std::{
     io::{self, Write},
     num::NonZeroUsize,
@@ -51,6 +53,12 @@ pub struct TransactionalTestOpts {
     /// Pattern to match the test files
     #[clap(long, default_value = r".*\.(mvir|move)$")]
     pub pattern: String,
+
+    /// Serve the Debug Adapter Protocol on this port of localhost, and run the tests one at a
+    /// time under the control of the debugger connecting to it
+    #[cfg(feature = "debug-adapter")]
+    #[clap(long, value_name = "PORT")]
+    pub dap: Option<u16>,
 }
 
 /// Helper function to iterate through all the files in the given directory, skipping hidden files,
@@ -105,16 +113,41 @@ pub(crate) fn runner(options: &TransactionalTestOpts, reqs: &[Requirements]) ->
         return Ok(());
     }
 
-    match run_tests(options, tests) {
+    #[cfg(feature = "debug-adapter")]
+    let result = match options.dap {
+        Some(port) => debug_tests(options, tests, port),
+        None => run_tests(options, tests).map_err(io_error),
+    };
+    #[cfg(not(feature = "debug-adapter"))]
+    let result = run_tests(options, tests).map_err(io_error);
+    match result {
         Ok(true) => Ok(()),
         Ok(false) => process::exit(101),
-        Err(e) => Err(CliError::UnexpectedError(format!(
-            "error: io error when running tests: {:?}",
-            e
-        ))),
+        Err(e) => Err(e),
     }
 }
 
+fn io_error(e: io::Error) -> CliError {
+    CliError::UnexpectedError(format!("error: io error when running tests: {:?}", e))
+}
+
+/// Runs the tests under the control of a debugger. Breakpoints are set on functions, as the
+/// modules published by the tests are not known in advance.
+#[cfg(feature = "debug-adapter")]
+fn debug_tests(
+    options: &TransactionalTestOpts,
+    tests: Vec<Test>,
+    port: u16,
+) -> CliTypedResult<bool> {
+    let options = TransactionalTestOpts {
+        test_threads: NonZeroUsize::new(1).unwrap(),
+        ..options.clone()
+    };
+    DebugAdapter::new(SourceIndex::default())
+        .serve_tcp(port, move || Ok(run_tests(&options, tests)?))
+        .map_err(|e| CliError::UnexpectedError(format!("error: failed to debug tests: {:#}", e)))
+}
+
 fn run_tests(options: &TransactionalTestOpts, tests: Vec<Test>) -> io::Result<bool> {
     let total = tests.len();
 
@@ -1286,7 +1286,13 @@ impl CliCommand<TransactionSummary> for UpdateConsensusKey {
 
 /// Update the current validator's network and fullnode network addresses
 ///
-/// This will take effect in the next epoch
+/// This will take effect in the next epoch.
+///
+/// To rotate a network key without downtime, update the addresses in two phases:
+/// 1. Publish the new key, and keep the previous key (with `--previous-validator-network-public-key`
+///    and/or `--previous-full-node-network-public-key`). Once this takes effect, store the new key
+///    in the node's secure storage, and the node will switch to it at runtime.
+/// 2. Publish only the new key, once the node has switched to it.
 #[derive(Parser)]
 pub struct UpdateValidatorNetworkAddresses {
     #[clap(flatten)]
@@ -1297,6 +1303,18 @@ pub struct UpdateValidatorNetworkAddresses {
     pub(crate) operator_config_file_args: OperatorConfigFileArgs,
     #[clap(flatten)]
     pub(crate) validator_network_addresses_args: ValidatorNetworkAddressesArgs,
+
+    /// Previous validator x25519 public network key, to keep during a key rotation
+    ///
+    /// The validator host is published with both the previous and the new key.
+    #[clap(long, value_parser = x25519::PublicKey::from_encoded_string)]
+    pub(crate) previous_validator_network_public_key: Option<x25519::PublicKey>,
+
+    /// Previous full node x25519 public network key, to keep during a key rotation
+    ///
+    /// The fullnode host is published with both the previous and the new key.
+    #[clap(long, value_parser = x25519::PublicKey::from_encoded_string)]
+    pub(crate) previous_full_node_network_public_key: Option<x25519::PublicKey>,
 }
 
 #[async_trait]
@@ -1319,11 +1337,18 @@ impl CliCommand<TransactionSummary> for UpdateValidatorNetworkAddresses {
         ) = self
             .validator_network_addresses_args
             .get_network_configs(&validator_config)?;
-        let validator_network_addresses =
-            vec![validator_host.as_network_address(validator_network_public_key)?];
+        let validator_network_addresses = network_addresses_for_rotation(
+            validator_host,
+            validator_network_public_key,
+            self.previous_validator_network_public_key,
+        )?;
         let full_node_network_addresses =
             match (full_node_host.as_ref(), full_node_network_public_key) {
-                (Some(host), Some(public_key)) => vec![host.as_network_address(public_key)?],
+                (Some(host), Some(public_key)) => network_addresses_for_rotation(
+                    host,
+                    public_key,
+                    self.previous_full_node_network_public_key,
+                )?,
                 (None, None) => vec![],
                 _ => {
                     return Err(CliError::CommandArgumentError(
@@ -1332,6 +1357,13 @@ impl CliCommand<TransactionSummary> for UpdateValidatorNetworkAddresses {
                     ))
                 },
             };
+        if full_node_network_addresses.is_empty()
+            && self.previous_full_node_network_public_key.is_some()
+        {
+            return Err(CliError::CommandArgumentError(
+                "A previous fullnode network key requires fullnode addresses.".to_string(),
+            ));
+        }
 
         self.txn_options
             .submit_transaction(aptos_stdlib::stake_update_network_and_fullnode_addresses(
@@ -1345,6 +1377,24 @@ impl CliCommand<TransactionSummary> for UpdateValidatorNetworkAddresses {
     }
 }
 
+/// Returns the network addresses of the host for the given key. During a key
+/// rotation, the host is also published with the previous key. The previous key
+/// comes first, as peers dial the addresses in order (and the node only switches
+/// to the new key after the addresses take effect).
+fn network_addresses_for_rotation(
+    host: &HostAndPort,
+    public_key: x25519::PublicKey,
+    previous_public_key: Option<x25519::PublicKey>,
+) -> CliTypedResult<Vec<NetworkAddress>> {
+    match previous_public_key {
+        Some(previous_public_key) if previous_public_key != public_key => Ok(vec![
+            host.as_network_address(previous_public_key)?,
+            host.as_network_address(public_key)?,
+        ]),
+        _ => Ok(vec![host.as_network_address(public_key)?]),
+    }
+}
+
 /// Analyze the performance of one or more validators
 #[derive(Parser)]
 pub struct AnalyzeValidatorPerformance {
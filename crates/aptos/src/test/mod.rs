@@ -492,6 +492,8 @@ impl CliTestFramework {
                 full_node_host: None,
                 full_node_network_public_key: None,
             },
+            previous_validator_network_public_key: None,
+            previous_full_node_network_public_key: None,
         }
         .execute()
         .await
@@ -850,6 +852,8 @@ impl CliTestFramework {
             ignore_compile_warnings: false,
             compute_coverage: false,
             dump_state: false,
+            #[cfg(feature = "debug-adapter")]
+            dap: None,
         }
         .execute()
         .await
@@ -217,9 +217,16 @@ impl CoinActivity {
             CoinEvent::WithdrawCoinEvent(inner) => inner.amount.clone(),
             CoinEvent::DepositCoinEvent(inner) => inner.amount.clone(),
         };
+        let event_guid = event
+            .guid
+            .as_ref()
+            .expect("Coin events are emitted to an event stream");
+        let event_sequence_number = event
+            .sequence_number
+            .expect("Coin events are emitted to an event stream");
         let event_move_guid = EventGuidResource {
-            addr: event.guid.account_address.to_string(),
-            creation_num: event.guid.creation_number.0 as i64,
+            addr: event_guid.account_address.to_string(),
+            creation_num: event_guid.creation_number.0 as i64,
         };
         let coin_type =
             event_to_coin_type
@@ -233,10 +240,10 @@ impl CoinActivity {
 
         Self {
             transaction_version: txn_version,
-            event_account_address: standardize_address(&event.guid.account_address.to_string()),
-            event_creation_number: event.guid.creation_number.0 as i64,
-            event_sequence_number: event.sequence_number.0 as i64,
-            owner_address: standardize_address(&event.guid.account_address.to_string()),
+            event_account_address: standardize_address(&event_guid.account_address.to_string()),
+            event_creation_number: event_guid.creation_number.0 as i64,
+            event_sequence_number: event_sequence_number.0 as i64,
+            owner_address: standardize_address(&event_guid.account_address.to_string()),
             coin_type,
             amount,
             activity_type: event_type.to_string(),
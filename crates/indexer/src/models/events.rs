@@ -40,22 +40,25 @@ pub struct EventQuery {
 }
 
 impl Event {
+    /// Module events are not emitted to an event stream, so they are skipped as they cannot be
+    /// keyed by one.
     pub fn from_event(
         event: &APIEvent,
         transaction_version: i64,
         transaction_block_height: i64,
         event_index: i64,
-    ) -> Self {
-        Event {
-            account_address: standardize_address(&event.guid.account_address.to_string()),
-            creation_number: event.guid.creation_number.0 as i64,
-            sequence_number: event.sequence_number.0 as i64,
+    ) -> Option<Self> {
+        let (guid, sequence_number) = event.guid.as_ref().zip(event.sequence_number)?;
+        Some(Event {
+            account_address: standardize_address(&guid.account_address.to_string()),
+            creation_number: guid.creation_number.0 as i64,
+            sequence_number: sequence_number.0 as i64,
             transaction_version,
             transaction_block_height,
             type_: event.typ.to_string(),
             data: event.data.clone(),
             event_index: Some(event_index),
-        }
+        })
     }
 
     pub fn from_events(
@@ -66,7 +69,7 @@ impl Event {
         events
             .iter()
             .enumerate()
-            .map(|(index, event)| {
+            .filter_map(|(index, event)| {
                 Self::from_event(
                     event,
                     transaction_version,
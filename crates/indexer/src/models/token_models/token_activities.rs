@@ -87,9 +87,16 @@ impl TokenActivity {
         txn_timestamp: chrono::NaiveDateTime,
         event_index: i64,
     ) -> Self {
-        let event_account_address = standardize_address(&event.guid.account_address.to_string());
-        let event_creation_number = event.guid.creation_number.0 as i64;
-        let event_sequence_number = event.sequence_number.0 as i64;
+        let event_guid = event
+            .guid
+            .as_ref()
+            .expect("Token events are emitted to an event stream");
+        let event_account_address = standardize_address(&event_guid.account_address.to_string());
+        let event_creation_number = event_guid.creation_number.0 as i64;
+        let event_sequence_number = event
+            .sequence_number
+            .expect("Token events are emitted to an event stream")
+            .0 as i64;
         let token_activity_helper = match token_event {
             TokenEvent::MintTokenEvent(inner) => TokenActivityHelper {
                 token_data_id: &inner.id,
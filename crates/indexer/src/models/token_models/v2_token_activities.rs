@@ -14,6 +14,7 @@ use crate::{
     database::PgPoolConnection, models::coin_models::v2_fungible_asset_utils::FungibleAssetEvent,
     schema::token_activities_v2, util::standardize_address,
 };
+use anyhow::Context;
 use aptos_api_types::Event as APIEvent;
 use bigdecimal::{BigDecimal, One, Zero};
 use field_count::FieldCount;
@@ -78,8 +79,12 @@ impl TokenActivityV2 {
         if let Some(fa_event) =
             &FungibleAssetEvent::from_event(event_type.as_str(), &event.data, txn_version)?
         {
+            let event_guid = event
+                .guid
+                .as_ref()
+                .context("Token events are emitted to an event stream")?;
             let event_account_address =
-                standardize_address(&event.guid.account_address.to_string());
+                standardize_address(&event_guid.account_address.to_string());
             // The event account address will also help us find fungible store which tells us where to find
             // the metadata
             if let Some(metadata) = token_v2_metadata.get(&event_account_address) {
@@ -149,8 +154,12 @@ impl TokenActivityV2 {
         if let Some(token_event) =
             &V2TokenEvent::from_event(event_type.as_str(), &event.data, txn_version)?
         {
+            let event_guid = event
+                .guid
+                .as_ref()
+                .context("Token events are emitted to an event stream")?;
             let event_account_address =
-                standardize_address(&event.guid.account_address.to_string());
+                standardize_address(&event_guid.account_address.to_string());
             // burn and mint events are attached to the collection. The rest should be attached to the token
             let token_data_id = match token_event {
                 V2TokenEvent::MintEvent(inner) => inner.get_token_address(),
@@ -225,8 +234,12 @@ impl TokenActivityV2 {
         if let Some(token_event) =
             &TokenEvent::from_event(event_type.as_str(), &event.data, txn_version)?
         {
+            let event_guid = event
+                .guid
+                .as_ref()
+                .context("Token events are emitted to an event stream")?;
             let event_account_address =
-                standardize_address(&event.guid.account_address.to_string());
+                standardize_address(&event_guid.account_address.to_string());
             let token_activity_helper = match token_event {
                 TokenEvent::MintTokenEvent(inner) => TokenActivityHelperV1 {
                     token_data_id_struct: inner.id.clone(),
@@ -6,8 +6,9 @@ use aptos_api_types::{
     EntryFunctionPayload, Event, GenesisPayload, MoveAbility, MoveFunction,
     MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveModule, MoveModuleBytecode,
     MoveModuleId, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
-    MultiEd25519Signature, MultisigPayload, MultisigTransactionPayload, ScriptPayload, Transaction,
-    TransactionInfo, TransactionPayload, TransactionSignature, WriteSet, WriteSetChange,
+    MultiEd25519Signature, MultisigPayload, MultisigTransactionPayload, ScriptPayload,
+    Secp256k1EcdsaSignature, Secp256r1EcdsaSignature, Transaction, TransactionInfo,
+    TransactionPayload, TransactionSignature, WriteSet, WriteSetChange,
 };
 use aptos_bitvec::BitVec;
 use aptos_logger::warn;
@@ -488,16 +489,26 @@ pub fn convert_multisig_payload(
 }
 
 pub fn convert_event(event: &Event) -> transaction::Event {
-    let event_key: aptos_types::event::EventKey = event.guid.into();
+    // Module events are not emitted to an event stream, so they have no key
+    let kind = match event.guid {
+        Some(_) => transaction::event::EventKind::Handle,
+        None => transaction::event::EventKind::Module,
+    };
     transaction::Event {
-        key: Some(transaction::EventKey {
-            creation_number: event_key.get_creation_number(),
-            account_address: event_key.get_creator_address().to_string(),
+        key: event.guid.map(|guid| {
+            let event_key: aptos_types::event::EventKey = guid.into();
+            transaction::EventKey {
+                creation_number: event_key.get_creation_number(),
+                account_address: event_key.get_creator_address().to_string(),
+            }
         }),
-        sequence_number: event.sequence_number.0,
+        sequence_number: event
+            .sequence_number
+            .map_or(0, |sequence_number| sequence_number.0),
         r#type: Some(convert_move_type(&event.typ)),
         type_str: event.typ.to_string(),
         data: event.data.to_string(),
+        kind: kind as i32,
     }
 }
 
@@ -556,28 +567,62 @@ pub fn convert_multi_ed25519_signature(
     }
 }
 
+pub fn convert_secp256r1_ecdsa_signature(
+    sig: &Secp256r1EcdsaSignature,
+) -> transaction::Secp256r1EcdsaSignature {
+    transaction::Secp256r1EcdsaSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+        authenticator_data: sig.authenticator_data.0.clone(),
+        client_data_json: sig.client_data_json.0.clone(),
+    }
+}
+
+pub fn convert_secp256k1_ecdsa_signature(
+    sig: &Secp256k1EcdsaSignature,
+) -> transaction::Secp256k1EcdsaSignature {
+    transaction::Secp256k1EcdsaSignature {
+        public_key: sig.public_key.0.clone(),
+        signature: sig.signature.0.clone(),
+    }
+}
+
 pub fn convert_account_signature(
     account_signature: &AccountSignature,
 ) -> transaction::AccountSignature {
-    let r#type = match account_signature {
-        AccountSignature::Ed25519Signature(_) => transaction::account_signature::Type::Ed25519,
-        AccountSignature::MultiEd25519Signature(_) => {
-            transaction::account_signature::Type::MultiEd25519
-        },
-    };
-    let signature = match account_signature {
-        AccountSignature::Ed25519Signature(s) => {
-            transaction::account_signature::Signature::Ed25519(convert_ed25519_signature(s))
-        },
-        AccountSignature::MultiEd25519Signature(s) => {
-            transaction::account_signature::Signature::MultiEd25519(
+    let (r#type, signature) = match account_signature {
+        AccountSignature::Ed25519Signature(s) => (
+            transaction::account_signature::Type::Ed25519,
+            Some(transaction::account_signature::Signature::Ed25519(
+                convert_ed25519_signature(s),
+            )),
+        ),
+        AccountSignature::MultiEd25519Signature(s) => (
+            transaction::account_signature::Type::MultiEd25519,
+            Some(transaction::account_signature::Signature::MultiEd25519(
                 convert_multi_ed25519_signature(s),
-            )
+            )),
+        ),
+        AccountSignature::Secp256r1EcdsaSignature(s) => (
+            transaction::account_signature::Type::Secp256r1Ecdsa,
+            Some(transaction::account_signature::Signature::Secp256r1Ecdsa(
+                convert_secp256r1_ecdsa_signature(s),
+            )),
+        ),
+        AccountSignature::Secp256k1EcdsaSignature(s) => (
+            transaction::account_signature::Type::Secp256k1Ecdsa,
+            Some(transaction::account_signature::Signature::Secp256k1Ecdsa(
+                convert_secp256k1_ecdsa_signature(s),
+            )),
+        ),
+        // The stream protocol has no representation for abstraction signatures yet.
+        AccountSignature::AbstractionSignature(_) => {
+            (transaction::account_signature::Type::Unspecified, None)
         },
     };
     transaction::AccountSignature {
         r#type: r#type as i32,
-        signature: Some(signature),
+        signature,
     }
 }
 
@@ -595,16 +640,24 @@ pub fn convert_transaction_signature(
         },
         TransactionSignature::MultiAgentSignature(_) => transaction::signature::Type::MultiAgent,
         TransactionSignature::FeePayerSignature(_) => transaction::signature::Type::FeePayer,
+        TransactionSignature::Secp256r1EcdsaSignature(_) => {
+            transaction::signature::Type::Secp256r1Ecdsa
+        },
+        TransactionSignature::Secp256k1EcdsaSignature(_) => {
+            transaction::signature::Type::Secp256k1Ecdsa
+        },
+        // The stream protocol has no representation for abstraction signatures yet.
+        TransactionSignature::AbstractionSignature(_) => transaction::signature::Type::Unspecified,
     };
 
     let signature = match signature {
-        TransactionSignature::Ed25519Signature(s) => {
-            transaction::signature::Signature::Ed25519(convert_ed25519_signature(s))
-        },
-        TransactionSignature::MultiEd25519Signature(s) => {
-            transaction::signature::Signature::MultiEd25519(convert_multi_ed25519_signature(s))
-        },
-        TransactionSignature::MultiAgentSignature(s) => {
+        TransactionSignature::Ed25519Signature(s) => Some(
+            transaction::signature::Signature::Ed25519(convert_ed25519_signature(s)),
+        ),
+        TransactionSignature::MultiEd25519Signature(s) => Some(
+            transaction::signature::Signature::MultiEd25519(convert_multi_ed25519_signature(s)),
+        ),
+        TransactionSignature::MultiAgentSignature(s) => Some(
             transaction::signature::Signature::MultiAgent(transaction::MultiAgentSignature {
                 sender: Some(convert_account_signature(&s.sender)),
                 secondary_signer_addresses: s
@@ -617,9 +670,9 @@ pub fn convert_transaction_signature(
                     .iter()
                     .map(convert_account_signature)
                     .collect(),
-            })
-        },
-        TransactionSignature::FeePayerSignature(s) => {
+            }),
+        ),
+        TransactionSignature::FeePayerSignature(s) => Some(
             transaction::signature::Signature::FeePayer(transaction::FeePayerSignature {
                 sender: Some(convert_account_signature(&s.sender)),
                 secondary_signer_addresses: s
@@ -634,13 +687,20 @@ pub fn convert_transaction_signature(
                     .collect(),
                 fee_payer_address: s.fee_payer_address.to_string(),
                 fee_payer_signer: Some(convert_account_signature(&s.fee_payer_signer)),
-            })
-        },
+            }),
+        ),
+        TransactionSignature::Secp256r1EcdsaSignature(s) => Some(
+            transaction::signature::Signature::Secp256r1Ecdsa(convert_secp256r1_ecdsa_signature(s)),
+        ),
+        TransactionSignature::Secp256k1EcdsaSignature(s) => Some(
+            transaction::signature::Signature::Secp256k1Ecdsa(convert_secp256k1_ecdsa_signature(s)),
+        ),
+        TransactionSignature::AbstractionSignature(_) => None,
     };
 
     Some(transaction::Signature {
         r#type: r#type as i32,
-        signature: Some(signature),
+        signature,
     })
 }
 
@@ -93,10 +93,13 @@ impl AccountTransaction {
     }
 
     /// Base case, record event account address. We don't really have to worry about
-    /// objects here because it'll be taken care of in the resource section
+    /// objects here because it'll be taken care of in the resource section. Module events are
+    /// not emitted to an event stream, so they have no account address
     fn from_event(event: &Event, txn_version: i64) -> HashMap<AccountTransactionPK, Self> {
-        let account_address =
-            standardize_address(event.key.as_ref().unwrap().account_address.as_str());
+        let Some(key) = event.key.as_ref() else {
+            return HashMap::new();
+        };
+        let account_address = standardize_address(key.account_address.as_str());
         HashMap::from([((account_address.clone(), txn_version), Self {
             transaction_version: txn_version,
             account_address,
@@ -41,24 +41,25 @@ pub struct EventQuery {
 }
 
 impl Event {
+    /// Module events are not emitted to an event stream, so they are skipped as they cannot be
+    /// keyed by one.
     pub fn from_event(
         event: &EventPB,
         transaction_version: i64,
         transaction_block_height: i64,
         event_index: i64,
-    ) -> Self {
-        Event {
-            account_address: standardize_address(
-                event.key.as_ref().unwrap().account_address.as_str(),
-            ),
-            creation_number: event.key.as_ref().unwrap().creation_number as i64,
+    ) -> Option<Self> {
+        let key = event.key.as_ref()?;
+        Some(Event {
+            account_address: standardize_address(key.account_address.as_str()),
+            creation_number: key.creation_number as i64,
             sequence_number: event.sequence_number as i64,
             transaction_version,
             transaction_block_height,
             type_: event.type_str.clone(),
             data: serde_json::from_str(event.data.as_str()).unwrap(),
             event_index: Some(event_index),
-        }
+        })
     }
 
     pub fn from_events(
@@ -69,7 +70,7 @@ impl Event {
         events
             .iter()
             .enumerate()
-            .map(|(index, event)| {
+            .filter_map(|(index, event)| {
                 Self::from_event(
                     event,
                     transaction_version,
@@ -10,7 +10,9 @@ use aptos_protos::transaction::v1::{
     account_signature::Signature as AccountSignatureEnum, signature::Signature as SignatureEnum,
     AccountSignature as ProtoAccountSignature, Ed25519Signature as Ed25519SignaturePB,
     FeePayerSignature as ProtoFeePayerSignature, MultiAgentSignature as ProtoMultiAgentSignature,
-    MultiEd25519Signature as ProtoMultiEd25519Signature, Signature as TransactionSignaturePB,
+    MultiEd25519Signature as ProtoMultiEd25519Signature,
+    Secp256k1EcdsaSignature as ProtoSecp256k1EcdsaSignature,
+    Secp256r1EcdsaSignature as ProtoSecp256r1EcdsaSignature, Signature as TransactionSignaturePB,
 };
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
@@ -79,6 +81,24 @@ impl Signature {
                 transaction_version,
                 transaction_block_height,
             ),
+            SignatureEnum::Secp256r1Ecdsa(sig) => Ok(vec![Self::parse_secp256r1_ecdsa_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                true,
+                0,
+                None,
+            )]),
+            SignatureEnum::Secp256k1Ecdsa(sig) => Ok(vec![Self::parse_secp256k1_ecdsa_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+                true,
+                0,
+                None,
+            )]),
         }
     }
 
@@ -88,6 +108,8 @@ impl Signature {
             SignatureEnum::MultiEd25519(_) => String::from("multi_ed25519_signature"),
             SignatureEnum::MultiAgent(_) => String::from("multi_agent_signature"),
             SignatureEnum::FeePayer(_) => String::from("fee_payer_signature"),
+            SignatureEnum::Secp256r1Ecdsa(_) => String::from("secp256r1_ecdsa_signature"),
+            SignatureEnum::Secp256k1Ecdsa(_) => String::from("secp256k1_ecdsa_signature"),
         }
     }
 
@@ -116,6 +138,56 @@ impl Signature {
         }
     }
 
+    fn parse_secp256r1_ecdsa_signature(
+        s: &ProtoSecp256r1EcdsaSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Self {
+        let signer = standardize_address(override_address.unwrap_or(sender));
+        Self {
+            transaction_version,
+            transaction_block_height,
+            signer,
+            is_sender_primary,
+            type_: String::from("secp256r1_ecdsa_signature"),
+            public_key: format!("0x{}", hex::encode(s.public_key.as_slice())),
+            threshold: 1,
+            public_key_indices: serde_json::Value::Array(vec![]),
+            signature: format!("0x{}", hex::encode(s.signature.as_slice())),
+            multi_agent_index,
+            multi_sig_index: 0,
+        }
+    }
+
+    fn parse_secp256k1_ecdsa_signature(
+        s: &ProtoSecp256k1EcdsaSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+        is_sender_primary: bool,
+        multi_agent_index: i64,
+        override_address: Option<&String>,
+    ) -> Self {
+        let signer = standardize_address(override_address.unwrap_or(sender));
+        Self {
+            transaction_version,
+            transaction_block_height,
+            signer,
+            is_sender_primary,
+            type_: String::from("secp256k1_ecdsa_signature"),
+            public_key: format!("0x{}", hex::encode(s.public_key.as_slice())),
+            threshold: 1,
+            public_key_indices: serde_json::Value::Array(vec![]),
+            signature: format!("0x{}", hex::encode(s.signature.as_slice())),
+            multi_agent_index,
+            multi_sig_index: 0,
+        }
+    }
+
     fn parse_multi_signature(
         s: &ProtoMultiEd25519Signature,
         sender: &String,
@@ -271,6 +343,28 @@ impl Signature {
                 multi_agent_index,
                 override_address,
             ),
+            AccountSignatureEnum::Secp256r1Ecdsa(sig) => {
+                vec![Self::parse_secp256r1_ecdsa_signature(
+                    sig,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    is_sender_primary,
+                    multi_agent_index,
+                    override_address,
+                )]
+            },
+            AccountSignatureEnum::Secp256k1Ecdsa(sig) => {
+                vec![Self::parse_secp256k1_ecdsa_signature(
+                    sig,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    is_sender_primary,
+                    multi_agent_index,
+                    override_address,
+                )]
+            },
         }
     }
 }
@@ -10,8 +10,8 @@ import type { U64 } from './U64';
  * An event from a transaction
  */
 export type Event = {
-    guid: EventGuid;
-    sequence_number: U64;
+    guid?: EventGuid;
+    sequence_number?: U64;
     type: MoveType;
     /**
      * The JSON representation of the event
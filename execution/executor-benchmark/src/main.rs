@@ -147,6 +147,10 @@ struct Opt {
 
     #[clap(long)]
     use_native_executor: bool,
+
+    /// Log the conflicting keys, re-executions and dependency chains of every executed block
+    #[clap(long)]
+    profile_conflicts: bool,
 }
 
 impl Opt {
@@ -332,6 +336,9 @@ fn main() {
 
     AptosVM::set_concurrency_level_once(opt.concurrency_level());
     AptosVM::set_num_shards_once(opt.pipeline_opt.num_executor_shards);
+    if opt.profile_conflicts {
+        AptosVM::set_conflict_profiling_once();
+    }
     NativeExecutor::set_concurrency_level_once(opt.concurrency_level());
 
     if opt.use_native_executor {
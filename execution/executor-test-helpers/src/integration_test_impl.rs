@@ -487,7 +487,11 @@ pub fn test_execution_with_storage_impl() -> Arc<AptosDB> {
     assert_eq!(account3_received_events_batch1.len(), 10);
     // Account3 has one extra deposit event from being minted to.
     assert_eq!(
-        account3_received_events_batch1[0].event.sequence_number(),
+        account3_received_events_batch1[0]
+            .event
+            .v0()
+            .unwrap()
+            .sequence_number(),
         16
     );
 
@@ -503,7 +507,11 @@ pub fn test_execution_with_storage_impl() -> Arc<AptosDB> {
         .unwrap();
     assert_eq!(account3_received_events_batch2.len(), 7);
     assert_eq!(
-        account3_received_events_batch2[0].event.sequence_number(),
+        account3_received_events_batch2[0]
+            .event
+            .v0()
+            .unwrap()
+            .sequence_number(),
         6
     );
 
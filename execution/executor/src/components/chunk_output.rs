@@ -17,6 +17,7 @@ use aptos_storage_interface::{
 use aptos_types::{
     account_config::CORE_CODE_ADDRESS,
     block_executor::partitioner::{ExecutableTransactions, SubBlocksForShard},
+    contract_event::ContractEvent,
     transaction::{
         analyzed_transaction::AnalyzedTransaction, ExecutionStatus, Transaction, TransactionOutput,
         TransactionStatus,
@@ -30,7 +31,7 @@ use aptos_vm::{
     AptosVM, VMExecutor,
 };
 use fail::fail_point;
-use move_core_types::vm_status::StatusCode;
+use move_core_types::{language_storage::TypeTag, vm_status::StatusCode};
 use once_cell::sync::Lazy;
 use std::{ops::Deref, sync::Arc, time::Duration};
 
@@ -395,11 +396,23 @@ pub fn update_counters_for_processed_chunk(
         }
 
         for event in output.events() {
-            let is_core = event.key().get_creator_address() == CORE_CODE_ADDRESS;
-            let creation_number = if is_core && detailed_counters {
-                event.key().get_creation_number().to_string()
-            } else {
-                "event".to_string()
+            let (is_core, creation_number) = match event {
+                ContractEvent::V0(event) => {
+                    let is_core = event.key().get_creator_address() == CORE_CODE_ADDRESS;
+                    let creation_number = if is_core && detailed_counters {
+                        event.key().get_creation_number().to_string()
+                    } else {
+                        "event".to_string()
+                    };
+                    (is_core, creation_number)
+                },
+                ContractEvent::V1(event) => {
+                    let is_core = matches!(
+                        event.type_tag(),
+                        TypeTag::Struct(tag) if tag.address == CORE_CODE_ADDRESS
+                    );
+                    (is_core, "module_event".to_string())
+                },
             };
             metrics::APTOS_PROCESSED_USER_TRANSACTIONS_CORE_EVENTS
                 .with_label_values(&[
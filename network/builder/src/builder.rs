@@ -12,10 +12,10 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS,
-        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
-        NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, NetworkConfig, OutboundQueueConfig, Peer, PeerExchangeDiscovery, PeerRole,
+        PeerSet, RoleType, CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS,
+        MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE,
+        MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
@@ -27,6 +27,7 @@ use aptos_network::{
     application::storage::PeersAndMetadata,
     connectivity_manager::{builder::ConnectivityManagerBuilder, ConnectivityRequest},
     constants::MAX_MESSAGE_SIZE,
+    identity_key_rotator::IdentityKeyRotator,
     logging::NetworkSchema,
     peer_manager::{
         builder::{AuthenticationMode, PeerManagerBuilder},
@@ -39,8 +40,12 @@ use aptos_network::{
             NewNetworkSender,
         },
     },
+    traffic_recorder::TrafficRecorder,
+};
+use aptos_network_discovery::{
+    peer_exchange::{self, PeerExchange},
+    DiscoveryChangeListener,
 };
-use aptos_network_discovery::DiscoveryChangeListener;
 use aptos_time_service::TimeService;
 use aptos_types::{chain_id::ChainId, network_address::NetworkAddress};
 use std::{clone::Clone, collections::HashSet, sync::Arc, time::Duration};
@@ -66,6 +71,8 @@ pub struct NetworkBuilder {
     discovery_listeners: Option<Vec<DiscoveryChangeListener>>,
     connectivity_manager_builder: Option<ConnectivityManagerBuilder>,
     health_checker_builder: Option<HealthCheckerBuilder>,
+    peer_exchange: Option<PeerExchange>,
+    identity_key_rotator: Option<IdentityKeyRotator>,
     peer_manager_builder: PeerManagerBuilder,
     peers_and_metadata: Arc<PeersAndMetadata>,
 }
@@ -88,6 +95,8 @@ impl NetworkBuilder {
         max_concurrent_network_reqs: usize,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_queue_config: OutboundQueueConfig,
+        traffic_recorder: Option<TrafficRecorder>,
     ) -> Self {
         // A network cannot exist without a PeerManager
         // TODO:  construct this in create and pass it to new() as a parameter. The complication is manual construction of NetworkBuilder in various tests.
@@ -105,6 +114,8 @@ impl NetworkBuilder {
             enable_proxy_protocol,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            outbound_queue_config,
+            traffic_recorder,
         );
 
         NetworkBuilder {
@@ -115,6 +126,8 @@ impl NetworkBuilder {
             discovery_listeners: None,
             connectivity_manager_builder: None,
             health_checker_builder: None,
+            peer_exchange: None,
+            identity_key_rotator: None,
             peer_manager_builder,
             peers_and_metadata,
         }
@@ -145,6 +158,8 @@ impl NetworkBuilder {
             MAX_CONCURRENT_NETWORK_REQS,
             MAX_INBOUND_CONNECTIONS,
             TCPBufferCfg::default(),
+            OutboundQueueConfig::default(),
+            None, /* Disable traffic recording */
         );
 
         builder.add_connectivity_manager(
@@ -182,6 +197,10 @@ impl NetworkBuilder {
 
         let network_context = NetworkContext::new(role, config.network_id, peer_id);
 
+        // Start the traffic recorder (if recording is enabled)
+        let traffic_recorder = TrafficRecorder::start(network_context, &config.traffic_recorder)
+            .expect("Failed to start the network traffic recorder!");
+
         let mut network_builder = NetworkBuilder::new(
             chain_id,
             peers_and_metadata.clone(),
@@ -201,6 +220,8 @@ impl NetworkBuilder {
                 config.outbound_rx_buffer_size_bytes,
                 config.outbound_tx_buffer_size_bytes,
             ),
+            config.outbound_queue.clone(),
+            traffic_recorder,
         );
 
         network_builder.add_connection_monitoring(
@@ -224,8 +245,22 @@ impl NetworkBuilder {
             config.mutual_authentication,
         );
 
+        // Reload the identity key at runtime (if key rotation is enabled)
+        if config.identity_key_rotation.enabled {
+            network_builder.add_identity_key_rotator(config);
+        }
+
         network_builder.discovery_listeners = Some(Vec::new());
         for discovery_method in config.discovery_methods() {
+            // Peer exchange runs as a network application (instead of a listener)
+            if let DiscoveryMethod::PeerExchange(peer_exchange_discovery) = discovery_method {
+                network_builder.add_peer_exchange(
+                    peer_exchange_discovery,
+                    config.max_parallel_deserialization_tasks,
+                );
+                continue;
+            }
+
             let reconfig_listener = if *discovery_method == DiscoveryMethod::Onchain {
                 Some(
                     reconfig_subscription_service
@@ -304,6 +339,22 @@ impl NetworkBuilder {
                 .into_iter()
                 .for_each(|listener| listener.start(executor))
         }
+
+        if let Some(peer_exchange) = self.peer_exchange.take() {
+            peer_exchange.start(executor);
+            debug!(
+                NetworkSchema::new(&self.network_context),
+                "{} Started peer exchange", self.network_context
+            );
+        }
+
+        if let Some(identity_key_rotator) = self.identity_key_rotator.take() {
+            executor.spawn(identity_key_rotator.start());
+            debug!(
+                NetworkSchema::new(&self.network_context),
+                "{} Started identity key rotator", self.network_context
+            );
+        }
         self
     }
 
@@ -401,7 +452,7 @@ impl NetworkBuilder {
                 Duration::from_secs(rest_discovery.interval_secs),
                 self.time_service.clone(),
             ),
-            DiscoveryMethod::None => return,
+            DiscoveryMethod::PeerExchange(_) | DiscoveryMethod::None => return,
         };
 
         self.discovery_listeners
@@ -410,6 +461,60 @@ impl NetworkBuilder {
             .push(listener);
     }
 
+    /// Add peer exchange discovery to the network.
+    fn add_peer_exchange(
+        &mut self,
+        peer_exchange_discovery: &PeerExchangeDiscovery,
+        max_parallel_deserialization_tasks: Option<usize>,
+    ) -> &mut Self {
+        assert!(
+            self.peer_exchange.is_none(),
+            "Peer exchange discovery can only be added once!"
+        );
+        let conn_mgr_reqs_tx = self
+            .conn_mgr_reqs_tx()
+            .expect("ConnectivityManager must exist");
+        let (network_sender, network_events) = self.add_client_and_service(
+            &peer_exchange::peer_exchange_network_config(),
+            max_parallel_deserialization_tasks,
+        );
+        self.peer_exchange = Some(PeerExchange::new(
+            self.network_context(),
+            self.time_service.clone(),
+            self.peer_manager_builder.identity_keys(),
+            network_sender,
+            network_events,
+            self.peers_and_metadata.clone(),
+            conn_mgr_reqs_tx,
+            peer_exchange_discovery.clone(),
+        ));
+        debug!(
+            NetworkSchema::new(&self.network_context),
+            "{} Created peer exchange", self.network_context
+        );
+        self
+    }
+
+    /// Add an identity key rotator to the network. The rotator reloads the
+    /// identity key from the configured identity (e.g., secure storage).
+    fn add_identity_key_rotator(&mut self, config: &NetworkConfig) -> &mut Self {
+        let network_config = config.clone();
+        self.identity_key_rotator = Some(IdentityKeyRotator::new(
+            self.network_context(),
+            self.time_service.clone(),
+            self.peer_manager_builder.identity_keys(),
+            Box::new(move || network_config.load_identity_key()),
+            self.peers_and_metadata.clone(),
+            ConnectionRequestSender::new(self.peer_manager_builder.connection_reqs_tx()),
+            config.identity_key_rotation.clone(),
+        ));
+        debug!(
+            NetworkSchema::new(&self.network_context),
+            "{} Created identity key rotator", self.network_context
+        );
+        self
+    }
+
     /// Add a HealthChecker to the network.
     fn add_connection_monitoring(
         &mut self,
@@ -36,3 +36,13 @@ pub static NETWORK_KEY_MISMATCH: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Counter of pending network events for peer exchange discovery
+pub static PENDING_PEER_EXCHANGE_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_discovery_pending_peer_exchange_events",
+        "Number of pending peer exchange network events by state",
+        &["state"]
+    )
+    .unwrap()
+});
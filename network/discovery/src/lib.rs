@@ -27,6 +27,7 @@ use tokio::runtime::Handle;
 
 mod counters;
 mod file;
+pub mod peer_exchange;
 mod rest;
 mod validator_set;
 
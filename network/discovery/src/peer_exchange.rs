@@ -0,0 +1,837 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Peer exchange discovery for public fullnodes.
+//!
+//! Nodes periodically share a random sample of the peers they have dialed (and
+//! the addresses they were dialed at) with their connected peers. Received
+//! samples are rate limited and bounded per peer, and are then forwarded to the
+//! ConnectivityManager, which validates and scores the candidates (see
+//! `aptos_network::connectivity_manager::peer_exchange`).
+//!
+//! Each sample is signed (using XEdDSA) by the identity key of the node that
+//! created it (i.e., its origin), and the origin peer id must be derived from
+//! that key. This binds a sample to its origin independently of the connection
+//! it was received on, so candidates are always attributed to the origin.
+//! Samples must also be fresh, and the timestamps of the samples created by an
+//! origin must strictly increase, so that old samples cannot be replayed.
+//! Moreover, all shared addresses contain the noise public key of the sampled
+//! peer, so that dialing a forged address fails the Noise handshake.
+
+use crate::counters::{DISCOVERY_COUNTS, PENDING_PEER_EXCHANGE_NETWORK_EVENTS};
+use anyhow::ensure;
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::{
+    config::{Peer, PeerExchangeDiscovery, PeerRole, PeerSet},
+    network_id::{NetworkContext, PeerNetworkId},
+};
+use aptos_crypto::{ed25519::Ed25519Signature, traits::signing_message, x25519};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_logger::prelude::*;
+use aptos_network::{
+    application::{
+        interface::{NetworkClient, NetworkClientInterface},
+        storage::PeersAndMetadata,
+    },
+    connectivity_manager::ConnectivityRequest,
+    counters::inc_by_with_context,
+    logging::NetworkSchema,
+    noise::IdentityKeys,
+    protocols::network::{
+        Event, NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
+        NetworkServiceConfig,
+    },
+    ProtocolId,
+};
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::{
+    account_address::from_identity_public_key, network_address::NetworkAddress, PeerId,
+};
+use futures::{channel::oneshot, StreamExt};
+use maplit::hashmap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Handle;
+
+/// The maximum number of pending peer samples (per peer) waiting to be processed.
+/// Older samples are dropped in favour of newer ones.
+const MAX_PENDING_SAMPLES_PER_PEER: usize = 1;
+
+/// The maximum number of addresses to share (and accept) for a single peer
+const MAX_ADDRESSES_PER_PEER: usize = 3;
+
+/// The maximum age of a peer sample. Older samples are dropped.
+const MAX_SAMPLE_AGE: Duration = Duration::from_secs(10 * 60);
+
+/// The maximum clock skew tolerated for samples with timestamps in the future
+const MAX_SAMPLE_CLOCK_SKEW: Duration = Duration::from_secs(30);
+
+/// Returns a network application config for the peer exchange client and service
+pub fn peer_exchange_network_config() -> NetworkApplicationConfig {
+    let direct_send_protocols = vec![ProtocolId::DiscoveryDirectSend];
+    let rpc_protocols = vec![]; // Peer exchange doesn't use RPC
+
+    let network_client_config =
+        NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
+    let network_service_config = NetworkServiceConfig::new(
+        direct_send_protocols,
+        rpc_protocols,
+        aptos_channel::Config::new(MAX_PENDING_SAMPLES_PER_PEER)
+            .queue_style(QueueStyle::LIFO)
+            .counters(&PENDING_PEER_EXCHANGE_NETWORK_EVENTS),
+    );
+    NetworkApplicationConfig::new(network_client_config, network_service_config)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum PeerExchangeMsg {
+    PeerSample(SignedPeerSample),
+}
+
+/// A sample of the peers (and their addresses) that the origin has dialed
+#[derive(
+    Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, CryptoHasher, BCSCryptoHash,
+)]
+pub struct PeerSample {
+    /// The peer that created the sample
+    pub origin: PeerId,
+    /// The time (in microseconds since the unix epoch) the sample was created at
+    pub timestamp_usecs: u64,
+    pub peers: Vec<(PeerId, Vec<NetworkAddress>)>,
+}
+
+/// A peer sample signed (using XEdDSA) by the identity key of its origin
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SignedPeerSample {
+    pub sample: PeerSample,
+    /// The identity public key of the origin (the origin peer id is derived from it)
+    pub origin_public_key: x25519::PublicKey,
+    pub signature: Ed25519Signature,
+}
+
+impl SignedPeerSample {
+    /// Signs the sample with the current identity key
+    pub fn sign(sample: PeerSample, identity_keys: &IdentityKeys) -> anyhow::Result<Self> {
+        let message = signing_message(&sample)?;
+        let (origin_public_key, signature) = identity_keys.sign_with_current_key(&message);
+        Ok(Self {
+            sample,
+            origin_public_key,
+            signature,
+        })
+    }
+
+    /// Verifies that the origin peer id is derived from the origin public key,
+    /// and that the sample was signed by the corresponding private key.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let expected_origin = from_identity_public_key(self.origin_public_key);
+        ensure!(
+            self.sample.origin == expected_origin,
+            "The origin {} is not derived from the origin public key (expected {})",
+            self.sample.origin,
+            expected_origin
+        );
+        let message = signing_message(&self.sample)?;
+        self.origin_public_key
+            .verify_xeddsa(&message, &self.signature)
+    }
+}
+
+/// The actor running the peer exchange protocol
+pub struct PeerExchange {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    identity_keys: Arc<IdentityKeys>,
+    network_client: NetworkClient<PeerExchangeMsg>,
+    network_events: NetworkEvents<PeerExchangeMsg>,
+    conn_mgr_reqs_tx: aptos_channels::Sender<ConnectivityRequest>,
+    config: PeerExchangeDiscovery,
+    /// The last time a peer sample was accepted from each peer
+    last_sample_times: HashMap<PeerId, Instant>,
+    /// The timestamp (usecs) of the last sample accepted from each origin
+    last_origin_timestamps: HashMap<PeerId, u64>,
+}
+
+impl PeerExchange {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        identity_keys: Arc<IdentityKeys>,
+        network_sender: NetworkSender<PeerExchangeMsg>,
+        network_events: NetworkEvents<PeerExchangeMsg>,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        conn_mgr_reqs_tx: aptos_channels::Sender<ConnectivityRequest>,
+        config: PeerExchangeDiscovery,
+    ) -> Self {
+        let network_client = NetworkClient::new(
+            vec![ProtocolId::DiscoveryDirectSend],
+            vec![],
+            hashmap! {network_context.network_id() => network_sender},
+            peers_and_metadata,
+        );
+        Self {
+            network_context,
+            time_service,
+            identity_keys,
+            network_client,
+            network_events,
+            conn_mgr_reqs_tx,
+            config,
+            last_sample_times: HashMap::new(),
+            last_origin_timestamps: HashMap::new(),
+        }
+    }
+
+    pub fn start(self, executor: &Handle) {
+        spawn_named!("PeerExchange", executor, self.run());
+    }
+
+    async fn run(mut self) {
+        info!(
+            NetworkSchema::new(&self.network_context),
+            "{} Starting PeerExchange Discovery", self.network_context
+        );
+
+        let ticker = self
+            .time_service
+            .interval(Duration::from_secs(self.config.interval_secs));
+        tokio::pin!(ticker);
+
+        loop {
+            futures::select! {
+                _ = ticker.select_next_some() => {
+                    self.send_peer_samples().await;
+                },
+                maybe_event = self.network_events.next() => {
+                    // Shutdown the actor when the network shuts down
+                    match maybe_event {
+                        Some(event) => self.handle_network_event(event),
+                        None => break,
+                    }
+                },
+            }
+        }
+
+        warn!(
+            NetworkSchema::new(&self.network_context),
+            "{} PeerExchange Discovery actor terminated", self.network_context
+        );
+    }
+
+    /// Fetches a sample of dialed peers from the connectivity manager and
+    /// sends it to all connected peers (excluding each receiver from its sample).
+    async fn send_peer_samples(&mut self) {
+        // Samples can only be attributed to us if our peer id is derived
+        // from our identity key (otherwise, receivers drop them).
+        let origin = self.network_context.peer_id();
+        if origin != from_identity_public_key(self.identity_keys.current_public_key()) {
+            self.increment_counter("sample_unattributable");
+            sample!(
+                SampleRate::Duration(Duration::from_secs(600)),
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Not sending peer samples: the peer id is not derived from the identity key",
+                    self.network_context
+                )
+            );
+            return;
+        }
+
+        // Request a peer sample from the connectivity manager
+        let (sample_tx, sample_rx) = oneshot::channel();
+        let request =
+            ConnectivityRequest::GetPeerExchangeSample(self.config.max_peers_per_sample, sample_tx);
+        if let Err(error) = self.conn_mgr_reqs_tx.try_send(request) {
+            self.increment_counter("sample_request_failure");
+            warn!(
+                NetworkSchema::new(&self.network_context),
+                "{} Failed to request a peer sample: {:?}", self.network_context, error
+            );
+            return;
+        }
+        let sampled_peers = match sample_rx.await {
+            Ok(sampled_peers) => sampled_peers,
+            Err(error) => {
+                self.increment_counter("sample_request_failure");
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Failed to receive a peer sample: {:?}", self.network_context, error
+                );
+                return;
+            },
+        };
+
+        // Send the sample to all connected peers
+        let connected_peers = match self.network_client.get_available_peers() {
+            Ok(connected_peers) => connected_peers,
+            Err(error) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Failed to get the connected peers: {:?}", self.network_context, error
+                );
+                return;
+            },
+        };
+        for peer in connected_peers
+            .into_iter()
+            .filter(|peer| peer.network_id() == self.network_context.network_id())
+        {
+            let timestamp_usecs = self.time_service.now_unix_time().as_micros() as u64;
+            let peer_sample =
+                create_peer_sample(origin, timestamp_usecs, &sampled_peers, &peer.peer_id());
+            if peer_sample.peers.is_empty() {
+                continue;
+            }
+            match SignedPeerSample::sign(peer_sample, &self.identity_keys) {
+                Ok(signed_sample) => self.send_peer_sample(peer, signed_sample),
+                Err(error) => {
+                    self.increment_counter("sample_signing_failure");
+                    warn!(
+                        NetworkSchema::new(&self.network_context),
+                        "{} Failed to sign a peer sample: {:?}", self.network_context, error
+                    );
+                },
+            }
+        }
+    }
+
+    fn send_peer_sample(&self, peer: PeerNetworkId, peer_sample: SignedPeerSample) {
+        trace!(
+            NetworkSchema::new(&self.network_context).remote_peer(&peer.peer_id()),
+            "{} Sending a peer sample of size {} to peer: {}",
+            self.network_context,
+            peer_sample.sample.peers.len(),
+            peer.peer_id().short_str()
+        );
+        let message = PeerExchangeMsg::PeerSample(peer_sample);
+        if let Err(error) = self.network_client.send_to_peer(message, peer) {
+            self.increment_counter("sample_send_failure");
+            warn!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer.peer_id()),
+                "{} Failed to send a peer sample to peer: {}, error: {:?}",
+                self.network_context,
+                peer.peer_id().short_str(),
+                error
+            );
+        }
+    }
+
+    fn handle_network_event(&mut self, event: Event<PeerExchangeMsg>) {
+        match event {
+            Event::Message(peer_id, PeerExchangeMsg::PeerSample(peer_sample)) => {
+                self.handle_peer_sample(peer_id, peer_sample)
+            },
+            Event::RpcRequest(peer_id, _, _, _) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    "{} Unexpected RPC request from peer: {}",
+                    self.network_context,
+                    peer_id.short_str()
+                );
+            },
+            Event::NewPeer(_) | Event::LostPeer(_) => {}, // Connection events are ignored
+        }
+    }
+
+    /// Handles a peer sample received from the given peer. Samples received
+    /// more frequently than the minimum sample interval are dropped, as are
+    /// samples with an invalid signature, stale samples and replayed samples.
+    /// Oversized samples are truncated, and the candidates are attributed to
+    /// the origin of the sample. The remaining validation is done by the
+    /// connectivity manager.
+    fn handle_peer_sample(&mut self, peer_id: PeerId, signed_sample: SignedPeerSample) {
+        // Rate limit the samples received from the peer
+        let now = self.time_service.now();
+        let min_sample_interval = Duration::from_secs(self.config.min_sample_interval_secs);
+        if let Some(last_sample_time) = self.last_sample_times.get(&peer_id) {
+            if now.saturating_duration_since(*last_sample_time) < min_sample_interval {
+                self.increment_counter("sample_rate_limited");
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(10)),
+                    warn!(
+                        NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                        "{} Dropping a rate limited peer sample from peer: {}",
+                        self.network_context,
+                        peer_id.short_str()
+                    )
+                );
+                return;
+            }
+        }
+        self.last_sample_times.retain(|_, last_sample_time| {
+            now.saturating_duration_since(*last_sample_time) < min_sample_interval
+        });
+        self.last_sample_times.insert(peer_id, now);
+
+        // Verify the signature of the sample
+        if let Err(error) = signed_sample.verify() {
+            self.increment_counter("sample_invalid_signature");
+            sample!(
+                SampleRate::Duration(Duration::from_secs(10)),
+                warn!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    "{} Dropping a peer sample with an invalid signature from peer: {}, error: {:?}",
+                    self.network_context,
+                    peer_id.short_str(),
+                    error
+                )
+            );
+            return;
+        }
+        let peer_sample = signed_sample.sample;
+        let origin = peer_sample.origin;
+
+        // Verify the sample is fresh
+        let now_usecs = self.time_service.now_unix_time().as_micros() as u64;
+        let min_timestamp_usecs = now_usecs.saturating_sub(MAX_SAMPLE_AGE.as_micros() as u64);
+        let max_timestamp_usecs =
+            now_usecs.saturating_add(MAX_SAMPLE_CLOCK_SKEW.as_micros() as u64);
+        if peer_sample.timestamp_usecs < min_timestamp_usecs
+            || peer_sample.timestamp_usecs > max_timestamp_usecs
+        {
+            self.increment_counter("sample_stale");
+            sample!(
+                SampleRate::Duration(Duration::from_secs(10)),
+                warn!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    "{} Dropping a stale peer sample (origin: {}, timestamp: {}) from peer: {}",
+                    self.network_context,
+                    origin.short_str(),
+                    peer_sample.timestamp_usecs,
+                    peer_id.short_str()
+                )
+            );
+            return;
+        }
+
+        // Verify the sample is not replayed (i.e., the timestamps of each origin increase)
+        if let Some(last_timestamp_usecs) = self.last_origin_timestamps.get(&origin) {
+            if peer_sample.timestamp_usecs <= *last_timestamp_usecs {
+                self.increment_counter("sample_replayed");
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(10)),
+                    warn!(
+                        NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                        "{} Dropping a replayed peer sample (origin: {}) from peer: {}",
+                        self.network_context,
+                        origin.short_str(),
+                        peer_id.short_str()
+                    )
+                );
+                return;
+            }
+        }
+        // Older timestamps are rejected as stale, so they no longer need to be tracked
+        self.last_origin_timestamps
+            .retain(|_, last_timestamp_usecs| *last_timestamp_usecs >= min_timestamp_usecs);
+        self.last_origin_timestamps
+            .insert(origin, peer_sample.timestamp_usecs);
+
+        // Bound the size of the sample
+        if peer_sample.peers.len() > self.config.max_peers_per_sample {
+            self.increment_counter("sample_truncated");
+        }
+        let reported_peers: PeerSet = peer_sample
+            .peers
+            .into_iter()
+            .take(self.config.max_peers_per_sample)
+            .map(|(peer_id, mut addresses)| {
+                addresses.truncate(MAX_ADDRESSES_PER_PEER);
+                (peer_id, Peer::from_addrs(PeerRole::Upstream, addresses))
+            })
+            .collect();
+        if reported_peers.is_empty() {
+            return;
+        }
+
+        // Forward the candidates (attributed to the origin) to the connectivity manager
+        let request = ConnectivityRequest::AddPeerExchangeCandidates(origin, reported_peers);
+        if let Err(error) = self.conn_mgr_reqs_tx.try_send(request) {
+            self.increment_counter("send_failure");
+            warn!(
+                NetworkSchema::new(&self.network_context),
+                "{} Failed to send peer exchange candidates: {:?}", self.network_context, error
+            );
+        }
+    }
+
+    fn increment_counter(&self, metric: &str) {
+        inc_by_with_context(&DISCOVERY_COUNTS, &self.network_context, metric, 1);
+    }
+}
+
+/// Creates the peer sample to send to the given receiver (the receiver
+/// itself is excluded from the sample).
+fn create_peer_sample(
+    origin: PeerId,
+    timestamp_usecs: u64,
+    sampled_peers: &PeerSet,
+    receiver: &PeerId,
+) -> PeerSample {
+    let peers = sampled_peers
+        .iter()
+        .filter(|(peer_id, _)| *peer_id != receiver)
+        .map(|(peer_id, peer)| {
+            let addresses = peer
+                .addresses
+                .iter()
+                .take(MAX_ADDRESSES_PER_PEER)
+                .cloned()
+                .collect();
+            (*peer_id, addresses)
+        })
+        .collect();
+    PeerSample {
+        origin,
+        timestamp_usecs,
+        peers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::{
+        config::{RoleType, HANDSHAKE_VERSION},
+        network_id::NetworkId,
+    };
+    use aptos_crypto::{x25519, Uniform};
+    use aptos_netcore::transport::ConnectionOrigin;
+    use aptos_network::{
+        peer_manager::{
+            conn_notifs_channel, ConnectionRequestSender, PeerManagerRequest,
+            PeerManagerRequestSender,
+        },
+        protocols::{
+            network::{NewNetworkEvents, NewNetworkSender},
+            wire::handshake::v1::ProtocolIdSet,
+        },
+        transport::ConnectionMetadata,
+    };
+    use aptos_time_service::MockTimeService;
+    use futures::FutureExt;
+    use std::str::FromStr;
+
+    const MAX_PEERS_PER_SAMPLE: usize = 2;
+    const MIN_SAMPLE_INTERVAL_SECS: u64 = 30;
+
+    struct TestHarness {
+        mock_time: MockTimeService,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        peer_mgr_reqs_rx: aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
+        conn_mgr_reqs_rx: aptos_channels::Receiver<ConnectivityRequest>,
+    }
+
+    impl TestHarness {
+        fn new() -> (Self, PeerExchange) {
+            let identity_key = x25519::PrivateKey::generate_for_testing();
+            let peer_id = from_identity_public_key(identity_key.public_key());
+            let network_context =
+                NetworkContext::new(RoleType::FullNode, NetworkId::Public, peer_id);
+            let identity_keys = Arc::new(IdentityKeys::new(identity_key));
+            let time_service = TimeService::mock();
+
+            // Create the network sender and events
+            let (peer_mgr_reqs_tx, peer_mgr_reqs_rx) =
+                aptos_channel::new(QueueStyle::FIFO, 10, None);
+            let (connection_reqs_tx, _connection_reqs_rx) =
+                aptos_channel::new(QueueStyle::FIFO, 10, None);
+            let (_peer_mgr_notifs_tx, peer_mgr_notifs_rx) =
+                aptos_channel::new(QueueStyle::FIFO, 10, None);
+            let (_connection_notifs_tx, connection_notifs_rx) = conn_notifs_channel::new();
+            let network_sender = NetworkSender::new(
+                PeerManagerRequestSender::new(peer_mgr_reqs_tx),
+                ConnectionRequestSender::new(connection_reqs_tx),
+            );
+            let network_events = NetworkEvents::new(peer_mgr_notifs_rx, connection_notifs_rx, None);
+
+            // Create the peer exchange actor
+            let peers_and_metadata = PeersAndMetadata::new(&[network_context.network_id()]);
+            let (conn_mgr_reqs_tx, conn_mgr_reqs_rx) = aptos_channels::new_test(10);
+            let config = PeerExchangeDiscovery {
+                max_peers_per_sample: MAX_PEERS_PER_SAMPLE,
+                min_sample_interval_secs: MIN_SAMPLE_INTERVAL_SECS,
+                ..Default::default()
+            };
+            let peer_exchange = PeerExchange::new(
+                network_context,
+                time_service.clone(),
+                identity_keys,
+                network_sender,
+                network_events,
+                peers_and_metadata.clone(),
+                conn_mgr_reqs_tx,
+                config,
+            );
+
+            let harness = Self {
+                mock_time: time_service.into_mock(),
+                peers_and_metadata,
+                peer_mgr_reqs_rx,
+                conn_mgr_reqs_rx,
+            };
+            (harness, peer_exchange)
+        }
+
+        /// Returns the current unix time (in microseconds)
+        fn now_usecs(&self) -> u64 {
+            self.mock_time.now_unix_time().as_micros() as u64
+        }
+
+        /// Verifies that the next connectivity request contains the expected candidates
+        fn expect_candidates(&mut self, expected_reporter: PeerId, expected_peers: &[PeerId]) {
+            match self.conn_mgr_reqs_rx.next().now_or_never() {
+                Some(Some(ConnectivityRequest::AddPeerExchangeCandidates(reporter, peers))) => {
+                    assert_eq!(reporter, expected_reporter);
+                    assert_eq!(peers.len(), expected_peers.len());
+                    for peer_id in expected_peers {
+                        assert!(peers.contains_key(peer_id));
+                    }
+                },
+                result => panic!("Unexpected connectivity request: {:?}", result),
+            }
+        }
+
+        /// Verifies that there are no pending connectivity requests
+        fn expect_no_candidates(&mut self) {
+            assert!(self.conn_mgr_reqs_rx.next().now_or_never().is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peer_sample_rate_limiting() {
+        let (mut harness, mut peer_exchange) = TestHarness::new();
+
+        // Handle a peer sample and verify the candidates are forwarded
+        let (reporter, reporter_key) = create_test_origin();
+        let (peer_sample, sampled_peers) =
+            create_test_peer_sample(&reporter_key, harness.now_usecs(), MAX_PEERS_PER_SAMPLE);
+        peer_exchange.handle_peer_sample(reporter, peer_sample);
+        harness.expect_candidates(reporter, &sampled_peers);
+
+        // Verify that a second sample from the same peer is dropped
+        let (peer_sample, _) =
+            create_test_peer_sample(&reporter_key, harness.now_usecs() + 1, MAX_PEERS_PER_SAMPLE);
+        peer_exchange.handle_peer_sample(reporter, peer_sample.clone());
+        harness.expect_no_candidates();
+
+        // Verify that samples from other peers are not dropped
+        let (other_reporter, other_reporter_key) = create_test_origin();
+        let (other_peer_sample, other_sampled_peers) = create_test_peer_sample(
+            &other_reporter_key,
+            harness.now_usecs(),
+            MAX_PEERS_PER_SAMPLE,
+        );
+        peer_exchange.handle_peer_sample(other_reporter, other_peer_sample);
+        harness.expect_candidates(other_reporter, &other_sampled_peers);
+
+        // Elapse the minimum sample interval and verify the sample is accepted again
+        harness
+            .mock_time
+            .advance_secs_async(MIN_SAMPLE_INTERVAL_SECS)
+            .await;
+        let (peer_sample, sampled_peers) =
+            create_test_peer_sample(&reporter_key, harness.now_usecs(), MAX_PEERS_PER_SAMPLE);
+        peer_exchange.handle_peer_sample(reporter, peer_sample);
+        harness.expect_candidates(reporter, &sampled_peers);
+    }
+
+    #[tokio::test]
+    async fn test_peer_sample_verification() {
+        let (mut harness, mut peer_exchange) = TestHarness::new();
+        harness
+            .mock_time
+            .advance_secs_async(MAX_SAMPLE_AGE.as_secs())
+            .await;
+
+        // Verify that samples relayed by another peer are attributed to the origin
+        let (origin, origin_key) = create_test_origin();
+        let (peer_sample, sampled_peers) =
+            create_test_peer_sample(&origin_key, harness.now_usecs(), MAX_PEERS_PER_SAMPLE);
+        peer_exchange.handle_peer_sample(PeerId::random(), peer_sample.clone());
+        harness.expect_candidates(origin, &sampled_peers);
+
+        // Verify that replayed samples are dropped
+        peer_exchange.handle_peer_sample(PeerId::random(), peer_sample.clone());
+        harness.expect_no_candidates();
+
+        // Verify that tampered samples are dropped
+        let mut tampered_sample = peer_sample.clone();
+        tampered_sample.sample.timestamp_usecs = harness.now_usecs() + 1;
+        tampered_sample.sample.peers.truncate(1);
+        assert!(tampered_sample.verify().is_err());
+        peer_exchange.handle_peer_sample(PeerId::random(), tampered_sample);
+        harness.expect_no_candidates();
+
+        // Verify that samples whose origin isn't derived from the signing key are dropped
+        let (_, other_key) = create_test_origin();
+        let mut sample = peer_sample.sample.clone();
+        sample.timestamp_usecs = harness.now_usecs() + 2;
+        let mismatched_sample =
+            SignedPeerSample::sign(sample, &IdentityKeys::new(other_key)).unwrap();
+        assert!(mismatched_sample.verify().is_err());
+        peer_exchange.handle_peer_sample(PeerId::random(), mismatched_sample);
+        harness.expect_no_candidates();
+
+        // Verify that stale samples and samples from the future are dropped
+        let (_, stale_key) = create_test_origin();
+        let stale_timestamp = harness.now_usecs() - MAX_SAMPLE_AGE.as_micros() as u64 - 1;
+        let (stale_sample, _) = create_test_peer_sample(&stale_key, stale_timestamp, 1);
+        peer_exchange.handle_peer_sample(PeerId::random(), stale_sample);
+        harness.expect_no_candidates();
+        let future_timestamp = harness.now_usecs() + MAX_SAMPLE_CLOCK_SKEW.as_micros() as u64 + 1;
+        let (future_sample, _) = create_test_peer_sample(&stale_key, future_timestamp, 1);
+        peer_exchange.handle_peer_sample(PeerId::random(), future_sample);
+        harness.expect_no_candidates();
+
+        // Verify that newer samples from the origin are accepted
+        let (peer_sample, sampled_peers) =
+            create_test_peer_sample(&origin_key, harness.now_usecs() + 3, MAX_PEERS_PER_SAMPLE);
+        peer_exchange.handle_peer_sample(PeerId::random(), peer_sample);
+        harness.expect_candidates(origin, &sampled_peers);
+    }
+
+    #[tokio::test]
+    async fn test_peer_sample_truncation() {
+        let (mut harness, mut peer_exchange) = TestHarness::new();
+
+        // Handle an oversized peer sample
+        let (reporter, reporter_key) = create_test_origin();
+        let (peer_sample, sampled_peers) =
+            create_test_peer_sample(&reporter_key, harness.now_usecs(), MAX_PEERS_PER_SAMPLE + 5);
+        let mut sample = peer_sample.sample;
+        let (_, addresses) = sample.peers.first_mut().unwrap();
+        let address = addresses[0].clone();
+        addresses.extend(vec![address; MAX_ADDRESSES_PER_PEER + 1]);
+        let peer_sample = SignedPeerSample::sign(sample, &IdentityKeys::new(reporter_key)).unwrap();
+        peer_exchange.handle_peer_sample(reporter, peer_sample);
+
+        // Verify the number of candidates and addresses is bounded
+        match harness.conn_mgr_reqs_rx.next().now_or_never() {
+            Some(Some(ConnectivityRequest::AddPeerExchangeCandidates(_, peers))) => {
+                assert_eq!(peers.len(), MAX_PEERS_PER_SAMPLE);
+                let first_peer = peers.get(&sampled_peers[0]).unwrap();
+                assert_eq!(first_peer.addresses.len(), MAX_ADDRESSES_PER_PEER);
+                for peer in peers.values() {
+                    assert_eq!(peer.role, PeerRole::Upstream);
+                }
+            },
+            result => panic!("Unexpected connectivity request: {:?}", result),
+        }
+
+        // Verify that empty samples are not forwarded
+        let (other_reporter, other_reporter_key) = create_test_origin();
+        let (empty_sample, _) =
+            create_test_peer_sample(&other_reporter_key, harness.now_usecs(), 0);
+        peer_exchange.handle_peer_sample(other_reporter, empty_sample);
+        harness.expect_no_candidates();
+    }
+
+    #[tokio::test]
+    async fn test_send_peer_samples() {
+        let (mut harness, mut peer_exchange) = TestHarness::new();
+        let origin = peer_exchange.network_context.peer_id();
+
+        // Connect two peers that support peer exchange
+        let network_id = NetworkId::Public;
+        let connected_peers = [PeerId::random(), PeerId::random()];
+        for peer_id in connected_peers {
+            let mut connection_metadata = ConnectionMetadata::mock_with_role_and_origin(
+                peer_id,
+                PeerRole::Upstream,
+                ConnectionOrigin::Outbound,
+            );
+            connection_metadata.application_protocols =
+                ProtocolIdSet::from_iter([ProtocolId::DiscoveryDirectSend]);
+            harness
+                .peers_and_metadata
+                .insert_connection_metadata(
+                    PeerNetworkId::new(network_id, peer_id),
+                    connection_metadata,
+                )
+                .unwrap();
+        }
+
+        // Respond to the sample request with both connected peers
+        let mut conn_mgr_reqs_rx = harness.conn_mgr_reqs_rx;
+        let sample_responder = tokio::spawn(async move {
+            match conn_mgr_reqs_rx.next().await.unwrap() {
+                ConnectivityRequest::GetPeerExchangeSample(max_peers, sample_tx) => {
+                    assert_eq!(max_peers, MAX_PEERS_PER_SAMPLE);
+                    let sampled_peers = connected_peers
+                        .iter()
+                        .map(|peer_id| (*peer_id, create_test_peer()))
+                        .collect();
+                    sample_tx.send(sampled_peers).unwrap();
+                },
+                request => panic!("Unexpected connectivity request: {:?}", request),
+            }
+        });
+        peer_exchange.send_peer_samples().await;
+        sample_responder.await.unwrap();
+
+        // Verify each peer receives a sample that excludes itself
+        for _ in 0..connected_peers.len() {
+            match harness.peer_mgr_reqs_rx.next().await.unwrap() {
+                PeerManagerRequest::SendDirectSend(receiver, message) => {
+                    assert_eq!(message.protocol_id, ProtocolId::DiscoveryDirectSend);
+                    let PeerExchangeMsg::PeerSample(signed_sample) =
+                        bcs::from_bytes(&message.mdata).unwrap();
+                    signed_sample.verify().unwrap();
+                    let peer_sample = signed_sample.sample;
+                    assert_eq!(peer_sample.origin, origin);
+                    assert_eq!(peer_sample.peers.len(), 1);
+                    let (sampled_peer, _) = &peer_sample.peers[0];
+                    assert_ne!(*sampled_peer, receiver);
+                    assert!(connected_peers.contains(sampled_peer));
+                },
+                request => panic!("Unexpected peer manager request: {:?}", request),
+            }
+        }
+    }
+
+    /// Creates the identity key of a sample origin (and returns the origin peer id)
+    fn create_test_origin() -> (PeerId, x25519::PrivateKey) {
+        let identity_key = x25519::PrivateKey::generate_for_testing();
+        (
+            from_identity_public_key(identity_key.public_key()),
+            identity_key,
+        )
+    }
+
+    /// Creates a peer sample of the given size, signed by the given origin
+    /// key (and returns the sampled peers).
+    fn create_test_peer_sample(
+        origin_key: &x25519::PrivateKey,
+        timestamp_usecs: u64,
+        num_peers: usize,
+    ) -> (SignedPeerSample, Vec<PeerId>) {
+        let peers: Vec<_> = (0..num_peers)
+            .map(|_| (PeerId::random(), create_test_peer().addresses))
+            .collect();
+        let sampled_peers = peers.iter().map(|(peer_id, _)| *peer_id).collect();
+        let sample = PeerSample {
+            origin: from_identity_public_key(origin_key.public_key()),
+            timestamp_usecs,
+            peers,
+        };
+        let identity_keys = IdentityKeys::new(origin_key.clone());
+        let signed_sample = SignedPeerSample::sign(sample, &identity_keys).unwrap();
+        (signed_sample, sampled_peers)
+    }
+
+    /// Creates a test peer with a single (public) address
+    fn create_test_peer() -> Peer {
+        let public_key = x25519::PrivateKey::generate_for_testing().public_key();
+        let address = NetworkAddress::from_str("/ip4/8.8.8.8/tcp/6180")
+            .unwrap()
+            .append_prod_protos(public_key, HANDSHAKE_VERSION);
+        Peer::from_addrs(PeerRole::Upstream, vec![address])
+    }
+}
@@ -20,6 +20,16 @@
 //! and some seed addresses from our local config, we will try the onchain
 //! discovery addresses first and the local seed addresses after.
 //!
+//! Public fullnodes can also learn about candidate peers via peer exchange (i.e.,
+//! from the peers they are connected to). These candidates have the lowest dial
+//! priority, are never trusted, and are subject to additional limits (see the
+//! [`peer_exchange`] module).
+//!
+//! On networks with an outbound connection limit, the peers to dial are also
+//! prioritized by their measured latency and distance from the validators, and
+//! the worst performing outbound connections are periodically replaced (see the
+//! [`peer_selection`] module).
+//!
 //! When dialing a peer with a given list of addresses, we attempt each address
 //! in order with a capped exponential backoff delay until we eventually connect
 //! to the peer. The backoff is capped since, for validators specifically, it is
@@ -29,6 +39,16 @@
 
 use crate::{
     application::storage::PeersAndMetadata,
+    connectivity_manager::{
+        peer_exchange::{
+            PeerExchangeCandidates, Subnet, MAX_OUTBOUND_CONNECTIONS_PERCENT,
+            MAX_OUTBOUND_CONNECTIONS_PER_SUBNET,
+        },
+        peer_selection::{
+            PeerPerformance, PeerPerformanceCache, MIN_EVICTION_COST_IMPROVEMENT_SECS,
+            PEER_EVICTION_INTERVAL, RANDOM_DIAL_PERCENT,
+        },
+    },
     counters,
     logging::NetworkSchema,
     peer_manager::{self, conn_notifs_channel, ConnectionRequestSender, PeerManagerError},
@@ -36,7 +56,7 @@ use crate::{
 };
 use aptos_config::{
     config::{Peer, PeerRole, PeerSet},
-    network_id::NetworkContext,
+    network_id::{NetworkContext, PeerNetworkId},
 };
 use aptos_crypto::x25519;
 use aptos_infallible::RwLock;
@@ -61,11 +81,13 @@ use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     fmt, mem,
     sync::Arc,
-    time::{Duration, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 use tokio_retry::strategy::jitter;
 
 pub mod builder;
+pub mod peer_exchange;
+pub mod peer_selection;
 #[cfg(test)]
 mod test;
 
@@ -93,6 +115,12 @@ pub struct ConnectivityManager<TBackoff> {
     connected: HashMap<PeerId, ConnectionMetadata>,
     /// All information about peers from discovery sources.
     discovered_peers: DiscoveredPeerSet,
+    /// Candidate peers reported by other peers via peer exchange.
+    peer_exchange_candidates: PeerExchangeCandidates,
+    /// The latest measured performance of connected (and previously connected) peers.
+    peer_performance: PeerPerformanceCache,
+    /// The last time the worst performing outbound connection was evaluated for eviction.
+    last_peer_eviction_time: Instant,
     /// Channel to send connection requests to PeerManager.
     connection_reqs_tx: ConnectionRequestSender,
     /// Channel to receive notifications from PeerManager.
@@ -123,7 +151,7 @@ pub struct ConnectivityManager<TBackoff> {
 }
 
 /// Different sources for peer addresses, ordered by priority (Onchain=highest,
-/// Config=lowest).
+/// PeerExchange=lowest).
 #[repr(u8)]
 #[derive(Copy, Clone, Eq, Hash, PartialEq, Ord, PartialOrd, NumVariants, Serialize)]
 pub enum DiscoverySource {
@@ -131,6 +159,7 @@ pub enum DiscoverySource {
     File,
     Rest,
     Config,
+    PeerExchange,
 }
 
 impl fmt::Debug for DiscoverySource {
@@ -146,6 +175,7 @@ impl fmt::Display for DiscoverySource {
             DiscoverySource::File => "File",
             DiscoverySource::Config => "Config",
             DiscoverySource::Rest => "Rest",
+            DiscoverySource::PeerExchange => "PeerExchange",
         })
     }
 }
@@ -155,6 +185,12 @@ impl fmt::Display for DiscoverySource {
 pub enum ConnectivityRequest {
     /// Update set of discovered peers and associated info
     UpdateDiscoveredPeers(DiscoverySource, PeerSet),
+    /// Add the candidate peers reported by the given peer via peer exchange
+    AddPeerExchangeCandidates(PeerId, PeerSet),
+    /// Gets a random sample (of the given size) of the peers we've dialed, to
+    /// share with other peers via peer exchange.
+    #[serde(skip)]
+    GetPeerExchangeSample(usize, oneshot::Sender<PeerSet>),
     /// Gets current size of connected peers. This is useful in tests.
     #[serde(skip)]
     GetConnectedSize(oneshot::Sender<usize>),
@@ -186,12 +222,20 @@ impl DiscoveredPeerSet {
         }
     }
 
+    /// Returns true iff the peer was discovered by a trusted source
+    fn is_trusted(&self, peer_id: &PeerId) -> bool {
+        self.0
+            .get(peer_id)
+            .map_or(false, DiscoveredPeer::is_trusted)
+    }
+
     /// Converts `DiscoveredPeerSet` into a `PeerSet`, however disregards the source of discovery
+    /// (with the exception of peer exchange, which is never trusted).
     /// TODO: Provide smarter merging based on discovery source
     pub fn to_eligible_peers(&self) -> PeerSet {
         self.0
             .iter()
-            .filter(|(_, peer)| peer.is_eligible())
+            .filter(|(_, peer)| peer.is_trusted())
             .map(|(peer_id, peer)| (*peer_id, peer.into()))
             .collect()
     }
@@ -227,6 +271,16 @@ impl DiscoveredPeer {
         self.is_eligible() && !self.addrs.is_empty()
     }
 
+    /// Peers with keys from a source other than peer exchange are trusted
+    pub fn is_trusted(&self) -> bool {
+        !self.keys.trusted_union().is_empty()
+    }
+
+    /// Peers that are eligible, but not trusted, are only known via peer exchange
+    pub fn is_peer_exchange_only(&self) -> bool {
+        self.is_eligible() && !self.is_trusted()
+    }
+
     /// Updates the last time we tried to connect to this node
     pub fn set_last_dial_time(&mut self, time: SystemTime) {
         self.last_dial_time = time;
@@ -260,7 +314,11 @@ impl PartialOrd for DiscoveredPeer {
 
 impl From<&DiscoveredPeer> for Peer {
     fn from(peer: &DiscoveredPeer) -> Self {
-        Peer::new(peer.addrs.union(), peer.keys.union(), peer.role)
+        Peer::new(
+            peer.addrs.trusted_union(),
+            peer.keys.trusted_union(),
+            peer.role,
+        )
     }
 }
 
@@ -334,10 +392,13 @@ where
 
         let mut connmgr = Self {
             network_context,
-            time_service,
+            time_service: time_service.clone(),
             peers_and_metadata,
             connected: HashMap::new(),
             discovered_peers: DiscoveredPeerSet::default(),
+            peer_exchange_candidates: PeerExchangeCandidates::default(),
+            peer_performance: PeerPerformanceCache::default(),
+            last_peer_eviction_time: time_service.now(),
             connection_reqs_tx,
             connection_notifs_rx,
             requests_rx,
@@ -443,7 +504,10 @@ where
             let stale_peers = self
                 .connected
                 .iter()
-                .filter(|(peer_id, _)| !trusted_peers.contains_key(peer_id))
+                .filter(|(peer_id, _)| {
+                    !trusted_peers.contains_key(peer_id)
+                        && !self.peer_exchange_candidates.contains(peer_id)
+                })
                 .filter_map(|(peer_id, metadata)| {
                     // If we're using server only auth, we need to not evict unknown peers
                     // TODO: We should prevent `Unknown` from discovery sources
@@ -496,7 +560,10 @@ where
             let stale_peer_dials: Vec<AccountAddress> = self
                 .dial_queue
                 .keys()
-                .filter(|peer_id| !trusted_peers.contains_key(peer_id))
+                .filter(|peer_id| {
+                    !trusted_peers.contains_key(peer_id)
+                        && !self.peer_exchange_candidates.contains(peer_id)
+                })
                 .cloned()
                 .collect();
 
@@ -543,9 +610,26 @@ where
         // Shuffle so we don't get stuck on certain peers
         eligible.shuffle(&mut self.rng);
 
-        // Sort by peer priority
-        eligible
-            .sort_by(|(_, peer), (_, other)| peer.partial_cmp(other).unwrap_or(Ordering::Equal));
+        // Sort by peer priority (and prioritize trusted peers over peer exchange
+        // candidates, lower cost peers over higher cost peers, and higher scored
+        // candidates over other peer exchange candidates).
+        eligible.sort_by(|(peer_id, peer), (other_id, other)| {
+            peer.partial_cmp(other)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| {
+                    peer.is_peer_exchange_only()
+                        .cmp(&other.is_peer_exchange_only())
+                })
+                .then_with(|| self.peer_performance.compare(peer_id, other_id))
+                .then_with(|| {
+                    let score = self.peer_exchange_candidates.score(peer_id);
+                    let other_score = self.peer_exchange_candidates.score(other_id);
+                    other_score.cmp(&score)
+                })
+        });
+
+        // Limit the dials to peers only known via peer exchange
+        let eligible = self.limit_peer_exchange_dials(eligible);
 
         // Limit the number of dialed connections from a Full Node
         // This does not limit the number of incoming connections
@@ -567,14 +651,195 @@ where
             num_eligible
         };
 
-        // Take peers to connect to in priority order
-        eligible
+        // Take peers to connect to in priority order, but reserve some of the
+        // dials for randomly chosen peers (to keep the connections diverse).
+        let num_random = to_connect * RANDOM_DIAL_PERCENT / 100;
+        let (prioritized, remaining) = eligible.split_at(to_connect - num_random);
+        let random = remaining.choose_multiple(&mut self.rng, num_random);
+        prioritized
             .iter()
-            .take(to_connect)
+            .chain(random)
             .map(|(peer_id, peer)| (**peer_id, (*peer).clone()))
             .collect()
     }
 
+    /// Updates the performance of all connected peers using the latest
+    /// measurements of the peer monitoring service. The performance of
+    /// peers that are no longer connected or discovered is removed.
+    fn update_peer_performance(&mut self) {
+        let network_id = self.network_context.network_id();
+        for peer_id in self.connected.keys() {
+            let peer_network_id = PeerNetworkId::new(network_id, *peer_id);
+            if let Ok(peer_metadata) = self
+                .peers_and_metadata
+                .get_metadata_for_peer(peer_network_id)
+            {
+                if let Some(performance) = PeerPerformance::from_monitoring_metadata(
+                    &peer_metadata.get_peer_monitoring_metadata(),
+                ) {
+                    self.peer_performance.update(*peer_id, performance);
+                }
+            }
+        }
+
+        let connected = &self.connected;
+        let discovered_peers = &self.discovered_peers;
+        self.peer_performance.retain(|peer_id| {
+            connected.contains_key(peer_id) || discovered_peers.0.contains_key(peer_id)
+        });
+    }
+
+    /// Evicts the worst performing outbound connection (if one should be
+    /// evicted). This is done at most once every eviction interval, so that
+    /// the replacement peers can be measured before evicting again.
+    async fn evict_worst_outbound_peer(&mut self) {
+        let now = self.time_service.now();
+        if now.duration_since(self.last_peer_eviction_time) < PEER_EVICTION_INTERVAL {
+            return;
+        }
+        self.last_peer_eviction_time = now;
+
+        if let Some(peer_id) = self.choose_peer_to_evict() {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Evicting worst performing outbound connection to peer {} (cost: {})",
+                self.network_context,
+                peer_id.short_str(),
+                self.peer_performance.cost(&peer_id)
+            );
+
+            if let Err(disconnect_error) = self.connection_reqs_tx.disconnect_peer(peer_id).await {
+                info!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    error = %disconnect_error,
+                    "{} Failed to evict connection to peer {}, error: {}",
+                    self.network_context,
+                    peer_id.short_str(),
+                    disconnect_error
+                );
+            }
+        }
+    }
+
+    /// Chooses the worst performing outbound connection to evict. A connection
+    /// is only evicted if the outbound connection limit has been reached, the
+    /// peer has been measured, and there is a peer available to dial with a
+    /// sufficiently lower (measured or estimated) cost. The chosen peer is
+    /// marked as recently dialed, to avoid redialing it immediately.
+    fn choose_peer_to_evict(&mut self) -> Option<PeerId> {
+        // Only evict connections if all outbound connections are in use
+        let outbound_connection_limit = self.outbound_connection_limit?;
+        let outbound_peers: Vec<_> = self
+            .connected
+            .iter()
+            .filter(|(_, metadata)| metadata.origin == ConnectionOrigin::Outbound)
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        if outbound_peers.len() + self.dial_queue.len() < outbound_connection_limit {
+            return None;
+        }
+
+        // Identify the worst performing (measured) outbound connection
+        let (worst_peer_id, worst_cost) = outbound_peers
+            .into_iter()
+            .filter_map(|peer_id| {
+                self.peer_performance
+                    .get(&peer_id)
+                    .map(|performance| (peer_id, performance.cost()))
+            })
+            .max_by(|(_, cost), (_, other_cost)| cost.total_cmp(other_cost))?;
+
+        // Identify the best peer that could replace the connection
+        let roles_to_dial = self
+            .network_context
+            .network_id()
+            .upstream_roles(&self.network_context.role());
+        let best_cost = self
+            .discovered_peers
+            .0
+            .iter()
+            .filter(|(peer_id, peer)| {
+                peer.is_eligible_to_be_dialed()
+                    && peer.is_trusted()
+                    && !self.connected.contains_key(peer_id)
+                    && !self.dial_queue.contains_key(peer_id)
+                    && !peer.has_dialed_recently(TRY_DIAL_BACKOFF_TIME)
+                    && roles_to_dial.contains(&peer.role)
+            })
+            .map(|(peer_id, _)| self.peer_performance.cost(peer_id))
+            .min_by(|cost, other_cost| cost.total_cmp(other_cost))?;
+        if worst_cost - best_cost < MIN_EVICTION_COST_IMPROVEMENT_SECS {
+            return None;
+        }
+
+        // Mark the peer as recently dialed
+        if let Some(discovered_peer) = self.discovered_peers.get_mut(&worst_peer_id) {
+            discovered_peer.set_last_dial_time(SystemTime::now());
+        }
+        Some(worst_peer_id)
+    }
+
+    /// Filters the given (ordered) eligible peers to ensure that peers only
+    /// known via peer exchange don't take more than a bounded percentage of the
+    /// outbound connections, and that they are spread across different subnets.
+    /// This makes it harder for an attacker to eclipse the node.
+    fn limit_peer_exchange_dials<'a>(
+        &self,
+        eligible: Vec<(&'a PeerId, &'a DiscoveredPeer)>,
+    ) -> Vec<(&'a PeerId, &'a DiscoveredPeer)> {
+        // Count the outbound connections and pending dials to peer exchange candidates
+        let mut num_peer_exchange_peers = 0;
+        let mut num_peers_per_subnet: HashMap<Subnet, usize> = HashMap::new();
+        let outbound_peers = self
+            .connected
+            .iter()
+            .filter(|(_, metadata)| metadata.origin == ConnectionOrigin::Outbound)
+            .map(|(peer_id, _)| peer_id)
+            .chain(self.dial_queue.keys());
+        for peer_id in outbound_peers {
+            let is_peer_exchange_only = self
+                .discovered_peers
+                .0
+                .get(peer_id)
+                .map_or(false, DiscoveredPeer::is_peer_exchange_only);
+            if is_peer_exchange_only {
+                num_peer_exchange_peers += 1;
+                if let Some(subnet) = self.peer_exchange_candidates.subnet(peer_id) {
+                    *num_peers_per_subnet.entry(subnet.clone()).or_default() += 1;
+                }
+            }
+        }
+
+        // Filter out the peer exchange candidates that exceed the limits
+        let max_peer_exchange_peers = self
+            .outbound_connection_limit
+            .map(|limit| limit * MAX_OUTBOUND_CONNECTIONS_PERCENT / 100);
+        eligible
+            .into_iter()
+            .filter(|(peer_id, peer)| {
+                if !peer.is_peer_exchange_only() {
+                    return true;
+                }
+                if let Some(max_peer_exchange_peers) = max_peer_exchange_peers {
+                    if num_peer_exchange_peers >= max_peer_exchange_peers {
+                        return false;
+                    }
+                }
+                let subnet = match self.peer_exchange_candidates.subnet(peer_id) {
+                    Some(subnet) => subnet.clone(),
+                    None => return false,
+                };
+                let num_subnet_peers = num_peers_per_subnet.entry(subnet).or_default();
+                if *num_subnet_peers >= MAX_OUTBOUND_CONNECTIONS_PER_SUBNET {
+                    return false;
+                }
+                *num_subnet_peers += 1;
+                num_peer_exchange_peers += 1;
+                true
+            })
+            .collect()
+    }
+
     fn queue_dial_peer<'a>(
         &'a mut self,
         peer_id: PeerId,
@@ -669,6 +934,9 @@ where
         self.cancel_stale_dials().await;
         // Disconnect from connected peers that are no longer eligible.
         self.close_stale_connections().await;
+        // Replace the worst performing outbound connection (if required).
+        self.update_peer_performance();
+        self.evict_worst_outbound_peer().await;
         // Dial peers which are eligible but are neither connected nor queued for dialing in the
         // future.
         self.dial_eligible_peers(pending_dials);
@@ -698,6 +966,25 @@ where
                 );
                 self.handle_update_discovered_peers(src, discovered_peers);
             },
+            ConnectivityRequest::AddPeerExchangeCandidates(reporter, reported_peers) => {
+                trace!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&reporter),
+                    "{} Received peer exchange candidates from peer: {}",
+                    self.network_context,
+                    reporter.short_str(),
+                );
+                self.handle_peer_exchange_candidates(reporter, reported_peers);
+            },
+            ConnectivityRequest::GetPeerExchangeSample(max_peers, sender) => {
+                let sample = self.get_peer_exchange_sample(max_peers);
+                if sender.send(sample).is_err() {
+                    warn!(
+                        NetworkSchema::new(&self.network_context),
+                        "{} Failed to send the peer exchange sample! The receiver was dropped.",
+                        self.network_context
+                    );
+                }
+            },
             ConnectivityRequest::GetDialQueueSize(sender) => {
                 sender.send(self.dial_queue.len()).unwrap();
             },
@@ -707,6 +994,61 @@ where
         }
     }
 
+    /// Validates and adds the candidates reported by the given peer, and
+    /// updates the peer exchange discovered peers accordingly.
+    fn handle_peer_exchange_candidates(&mut self, reporter: PeerId, reported_peers: PeerSet) {
+        let self_peer_id = self.network_context.peer_id();
+        let now = self.time_service.now();
+        let connected = &self.connected;
+        let discovered_peers = &self.discovered_peers;
+
+        // Remove the expired candidates and the candidates that are now trusted
+        let mut candidates_updated = self
+            .peer_exchange_candidates
+            .remove_expired(|peer_id| connected.contains_key(peer_id), now);
+        candidates_updated |= self
+            .peer_exchange_candidates
+            .remove_known(|peer_id| discovered_peers.is_trusted(peer_id));
+
+        // Add the new candidates
+        candidates_updated |= self.peer_exchange_candidates.add_candidates(
+            reporter,
+            reported_peers,
+            |peer_id| *peer_id == self_peer_id || discovered_peers.is_trusted(peer_id),
+            now,
+        );
+
+        if candidates_updated {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&reporter),
+                "{} Peer exchange candidates updated by peer: {}, num candidates: {}",
+                self.network_context,
+                reporter.short_str(),
+                self.peer_exchange_candidates.len(),
+            );
+            let candidates = self.peer_exchange_candidates.to_peer_set();
+            self.handle_update_discovered_peers(DiscoverySource::PeerExchange, candidates);
+        }
+    }
+
+    /// Returns a random sample of the peers we've successfully dialed. Only
+    /// outbound connections are shared because their addresses are known to be
+    /// reachable (and the peers authenticated themselves when we dialed them).
+    fn get_peer_exchange_sample(&mut self, max_peers: usize) -> PeerSet {
+        let outbound_connections: Vec<_> = self
+            .connected
+            .values()
+            .filter(|metadata| metadata.origin == ConnectionOrigin::Outbound)
+            .collect();
+        outbound_connections
+            .choose_multiple(&mut self.rng, max_peers)
+            .map(|metadata| {
+                let peer = Peer::from_addrs(PeerRole::Upstream, vec![metadata.addr.clone()]);
+                (metadata.remote_peer_id, peer)
+            })
+            .collect()
+    }
+
     fn handle_update_discovered_peers(
         &mut self,
         src: DiscoverySource,
@@ -756,6 +1098,14 @@ where
                 .0
                 .entry(peer_id)
                 .or_insert_with(|| DiscoveredPeer::new(discovered_peer.role));
+
+            // Peers that were only known via peer exchange take the role
+            // given by the first trusted source (peer exchange roles are
+            // never trusted).
+            if src != DiscoverySource::PeerExchange && !peer.is_trusted() {
+                peer.role = discovered_peer.role;
+            }
+
             let mut peer_updated = false;
             // Update peer's pubkeys
             if peer.keys.update(src, discovered_peer.keys) {
@@ -935,6 +1285,15 @@ impl DiscoverySource {
     }
 }
 
+/// Returns an iterator over the given source buckets, excluding peer exchange
+fn trusted_sources<T>(buckets: &[T]) -> impl Iterator<Item = &T> {
+    buckets
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != DiscoverySource::PeerExchange.as_usize())
+        .map(|(_, bucket)| bucket)
+}
+
 ///////////////
 // Addresses //
 ///////////////
@@ -968,9 +1327,10 @@ impl Addresses {
         self.0.iter().flatten().nth(idx)
     }
 
-    /// The Union isn't stable, and order is completely disregarded
-    fn union(&self) -> Vec<NetworkAddress> {
-        let set: HashSet<_> = self.0.iter().flatten().cloned().collect();
+    /// The Union isn't stable, and order is completely disregarded. Note:
+    /// addresses from peer exchange are excluded, as they're never trusted.
+    fn trusted_union(&self) -> Vec<NetworkAddress> {
+        let set: HashSet<_> = trusted_sources(&self.0).flatten().cloned().collect();
         set.into_iter().collect()
     }
 }
@@ -1016,8 +1376,9 @@ impl PublicKeys {
         self.update(src, HashSet::new())
     }
 
-    fn union(&self) -> HashSet<x25519::PublicKey> {
-        self.0.iter().flatten().copied().collect()
+    /// Note: keys from peer exchange are excluded, as they're never trusted
+    fn trusted_union(&self) -> HashSet<x25519::PublicKey> {
+        trusted_sources(&self.0).flatten().copied().collect()
     }
 }
 
@@ -0,0 +1,284 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Candidate peers learned via the peer exchange discovery protocol.
+//!
+//! Unlike the other discovery sources, peer exchange candidates are reported
+//! by arbitrary (and potentially malicious) remote peers. Thus, candidates are
+//! validated, bounded and scored before they are handed to the dialer:
+//!
+//! 1. Every candidate address must be a well-formed, publicly routable address
+//!    that contains the noise public key of the candidate. This ensures that
+//!    dialing a forged address fails the Noise handshake.
+//! 2. Each reporter may only contribute a bounded number of candidates, the
+//!    number of candidates per subnet is bounded and the total number of
+//!    candidates is bounded. This prevents address flooding.
+//! 3. Candidates are scored by the number of distinct peers that reported them.
+//!    Together with the outbound limits enforced by the ConnectivityManager
+//!    (see `MAX_OUTBOUND_CONNECTIONS_PERCENT` and
+//!    `MAX_OUTBOUND_CONNECTIONS_PER_SUBNET`), this makes eclipse attempts harder.
+//!
+//! Note: candidates are never added to the trusted peers set. Otherwise, any
+//! reported peer would bypass the inbound connection limit for unknown peers.
+
+use aptos_config::config::{Peer, PeerRole, PeerSet};
+use aptos_types::{
+    network_address::{NetworkAddress, Protocol},
+    PeerId,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+/// The maximum number of addresses to keep for a single candidate
+pub const MAX_ADDRESSES_PER_CANDIDATE: usize = 3;
+
+/// The maximum number of candidates to keep in total
+pub const MAX_CANDIDATES: usize = 1000;
+
+/// The maximum number of candidates a single reporter can contribute to
+pub const MAX_CANDIDATES_PER_REPORTER: usize = 64;
+
+/// The maximum number of candidates to keep per subnet
+pub const MAX_CANDIDATES_PER_SUBNET: usize = 4;
+
+/// The duration after which a candidate (that is not connected) expires if it
+/// has not been reported again.
+pub const CANDIDATE_EXPIRATION: Duration = Duration::from_secs(3600);
+
+/// The maximum percentage of the outbound connection limit that can be used to
+/// connect to peer exchange candidates (the rest is reserved for trusted peers).
+pub const MAX_OUTBOUND_CONNECTIONS_PERCENT: usize = 50;
+
+/// The maximum number of outbound connections (including pending dials) to peer
+/// exchange candidates in the same subnet.
+pub const MAX_OUTBOUND_CONNECTIONS_PER_SUBNET: usize = 1;
+
+/// The subnet of a network address. IPv4 addresses are bucketed by /24, IPv6
+/// addresses by /48, and DNS addresses by name.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Subnet {
+    Ipv4([u8; 3]),
+    Ipv6([u16; 3]),
+    Dns(String),
+}
+
+impl Subnet {
+    /// Returns the subnet of the given address (if the address has an IP or DNS base)
+    pub fn from_address(address: &NetworkAddress) -> Option<Subnet> {
+        match address.as_slice().first()? {
+            Protocol::Ip4(ip) => {
+                let octets = ip.octets();
+                Some(Subnet::Ipv4([octets[0], octets[1], octets[2]]))
+            },
+            Protocol::Ip6(ip) => {
+                let segments = ip.segments();
+                Some(Subnet::Ipv6([segments[0], segments[1], segments[2]]))
+            },
+            Protocol::Dns(name) | Protocol::Dns4(name) | Protocol::Dns6(name) => {
+                Some(Subnet::Dns(name.to_string()))
+            },
+            _ => None,
+        }
+    }
+}
+
+/// A single candidate peer reported via peer exchange
+#[derive(Clone, Debug)]
+struct Candidate {
+    /// The addresses of the candidate (set by the first reporter)
+    addresses: Vec<NetworkAddress>,
+    /// The subnet of the first candidate address
+    subnet: Subnet,
+    /// The distinct peers that reported the candidate
+    reporters: HashSet<PeerId>,
+    /// The last time the candidate was reported
+    last_reported: Instant,
+}
+
+/// The set of candidate peers reported via peer exchange
+#[derive(Clone, Debug, Default)]
+pub struct PeerExchangeCandidates {
+    candidates: HashMap<PeerId, Candidate>,
+}
+
+impl PeerExchangeCandidates {
+    /// Adds the peers reported by the given reporter as candidates. Peers
+    /// for which `is_known_peer` returns true are ignored. Returns true iff
+    /// the candidates were updated.
+    ///
+    /// Note: the addresses of existing candidates are never replaced by later
+    /// reports (a later report only increases the score of the candidate).
+    /// Otherwise, a malicious reporter could overwrite valid addresses.
+    pub fn add_candidates<F: Fn(&PeerId) -> bool>(
+        &mut self,
+        reporter: PeerId,
+        reported_peers: PeerSet,
+        is_known_peer: F,
+        now: Instant,
+    ) -> bool {
+        let mut candidates_updated = false;
+        let mut num_reported = self.num_candidates_reported_by(&reporter);
+
+        for (peer_id, peer) in reported_peers {
+            if is_known_peer(&peer_id) || peer_id == reporter {
+                continue;
+            }
+
+            // Update the reporters of existing candidates
+            if let Some(candidate) = self.candidates.get_mut(&peer_id) {
+                if candidate.reporters.contains(&reporter) {
+                    candidate.last_reported = now;
+                } else if num_reported < MAX_CANDIDATES_PER_REPORTER {
+                    candidate.reporters.insert(reporter);
+                    candidate.last_reported = now;
+                    num_reported += 1;
+                    candidates_updated = true;
+                }
+                continue;
+            }
+
+            // Verify the reporter and candidate limits
+            if num_reported >= MAX_CANDIDATES_PER_REPORTER
+                || self.candidates.len() >= MAX_CANDIDATES
+            {
+                continue;
+            }
+
+            // Validate the candidate addresses
+            let addresses: Vec<_> = peer
+                .addresses
+                .into_iter()
+                .filter(is_valid_candidate_address)
+                .take(MAX_ADDRESSES_PER_CANDIDATE)
+                .collect();
+            let subnet = match addresses.first().and_then(Subnet::from_address) {
+                Some(subnet) => subnet,
+                None => continue, // There are no valid addresses
+            };
+
+            // Verify the subnet limit
+            if self.num_candidates_in_subnet(&subnet) >= MAX_CANDIDATES_PER_SUBNET {
+                continue;
+            }
+
+            // Add the new candidate
+            self.candidates.insert(peer_id, Candidate {
+                addresses,
+                subnet,
+                reporters: HashSet::from([reporter]),
+                last_reported: now,
+            });
+            num_reported += 1;
+            candidates_updated = true;
+        }
+
+        candidates_updated
+    }
+
+    /// Removes all candidates that have not been reported within the
+    /// expiration duration (unless `is_connected` returns true for the
+    /// candidate). Returns true iff any candidates were removed.
+    pub fn remove_expired<F: Fn(&PeerId) -> bool>(
+        &mut self,
+        is_connected: F,
+        now: Instant,
+    ) -> bool {
+        let num_candidates = self.candidates.len();
+        self.candidates.retain(|peer_id, candidate| {
+            is_connected(peer_id)
+                || now.saturating_duration_since(candidate.last_reported) < CANDIDATE_EXPIRATION
+        });
+        num_candidates != self.candidates.len()
+    }
+
+    /// Removes all candidates for which `is_known_peer` returns true (e.g.,
+    /// peers that were later discovered by a trusted source). Returns true
+    /// iff any candidates were removed.
+    pub fn remove_known<F: Fn(&PeerId) -> bool>(&mut self, is_known_peer: F) -> bool {
+        let num_candidates = self.candidates.len();
+        self.candidates.retain(|peer_id, _| !is_known_peer(peer_id));
+        num_candidates != self.candidates.len()
+    }
+
+    /// Returns true iff the given peer is a candidate
+    pub fn contains(&self, peer_id: &PeerId) -> bool {
+        self.candidates.contains_key(peer_id)
+    }
+
+    /// Returns the number of candidates
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Returns true iff there are no candidates
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    /// Returns the score of the given candidate (i.e., the number of
+    /// distinct peers that reported it). Non-candidates have a score of 0.
+    pub fn score(&self, peer_id: &PeerId) -> usize {
+        self.candidates
+            .get(peer_id)
+            .map(|candidate| candidate.reporters.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns the subnet of the given candidate
+    pub fn subnet(&self, peer_id: &PeerId) -> Option<&Subnet> {
+        self.candidates
+            .get(peer_id)
+            .map(|candidate| &candidate.subnet)
+    }
+
+    /// Returns the candidates as a peer set. Reported roles are never
+    /// trusted, so all candidates are assigned the `Upstream` role.
+    pub fn to_peer_set(&self) -> PeerSet {
+        self.candidates
+            .iter()
+            .map(|(peer_id, candidate)| {
+                let peer = Peer::from_addrs(PeerRole::Upstream, candidate.addresses.clone());
+                (*peer_id, peer)
+            })
+            .collect()
+    }
+
+    fn num_candidates_reported_by(&self, reporter: &PeerId) -> usize {
+        self.candidates
+            .values()
+            .filter(|candidate| candidate.reporters.contains(reporter))
+            .count()
+    }
+
+    fn num_candidates_in_subnet(&self, subnet: &Subnet) -> usize {
+        self.candidates
+            .values()
+            .filter(|candidate| &candidate.subnet == subnet)
+            .count()
+    }
+}
+
+/// Returns true iff the given address can be dialed as a peer exchange
+/// candidate, i.e., it is a valid AptosNet address that contains a noise
+/// public key and (if it has an IP) the IP is publicly routable.
+pub fn is_valid_candidate_address(address: &NetworkAddress) -> bool {
+    if !address.is_aptosnet_addr() || address.find_noise_proto().is_none() {
+        return false;
+    }
+
+    match address.find_ip_addr() {
+        Some(IpAddr::V4(ip)) => {
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_multicast()
+                || ip.is_broadcast())
+        },
+        Some(IpAddr::V6(ip)) => !(ip.is_unspecified() || ip.is_loopback() || ip.is_multicast()),
+        None => true, // DNS addresses are resolved when dialing
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Latency- and reliability-aware selection of outbound peers.
+//!
+//! The peer monitoring service periodically measures the ping latency of each
+//! connected peer and fetches the distance of the peer from the validator set.
+//! The ConnectivityManager caches these measurements (so that they outlive the
+//! connections) and uses them to:
+//!
+//! 1. Prioritize dials to peers with a low cost, i.e., peers with a low latency
+//!    that are close to the validators. A fraction of the dials is made to peers
+//!    chosen at random, to ensure the connections remain diverse.
+//! 2. Periodically evict the worst performing outbound connection, if the
+//!    outbound connection limit has been reached and a better peer is available.
+//!
+//! Peers that haven't been measured yet are assigned an estimated cost. This
+//! ensures that unmeasured peers are explored whenever the connected peers
+//! perform worse than the estimate.
+
+use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
+use aptos_types::PeerId;
+use std::{cmp::Ordering, collections::HashMap, time::Duration};
+
+/// The percentage of dials (in each connectivity check) made to random peers
+pub const RANDOM_DIAL_PERCENT: usize = 25;
+
+/// The interval at which the worst performing outbound connection may be evicted
+pub const PEER_EVICTION_INTERVAL: Duration = Duration::from_secs(300);
+
+/// The minimum cost difference (in seconds) between the worst outbound
+/// connection and the best available peer for the connection to be evicted.
+/// This avoids churning connections for negligible improvements.
+pub const MIN_EVICTION_COST_IMPROVEMENT_SECS: f64 = 0.05;
+
+/// The cost (in seconds) of each hop between a peer and the validators
+pub const DISTANCE_FROM_VALIDATORS_COST_SECS: f64 = 0.05;
+
+/// The latency (in seconds) assumed for peers whose latency is unknown
+pub const UNKNOWN_PING_LATENCY_SECS: f64 = 0.2;
+
+/// The distance from the validators assumed for peers whose distance is unknown
+pub const UNKNOWN_DISTANCE_FROM_VALIDATORS: u64 = 2;
+
+/// The performance of a peer, as measured by the peer monitoring service
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeerPerformance {
+    average_ping_latency_secs: Option<f64>,
+    distance_from_validators: Option<u64>,
+}
+
+impl PeerPerformance {
+    pub fn new(
+        average_ping_latency_secs: Option<f64>,
+        distance_from_validators: Option<u64>,
+    ) -> Self {
+        Self {
+            average_ping_latency_secs,
+            distance_from_validators,
+        }
+    }
+
+    /// Returns the performance in the given monitoring metadata (or None, if
+    /// the peer hasn't been measured yet).
+    pub fn from_monitoring_metadata(metadata: &PeerMonitoringMetadata) -> Option<Self> {
+        let performance = Self::new(
+            metadata.average_ping_latency_secs,
+            metadata
+                .latest_network_info_response
+                .as_ref()
+                .map(|response| response.distance_from_validators),
+        );
+        if performance == Self::default() {
+            None
+        } else {
+            Some(performance)
+        }
+    }
+
+    /// Returns the cost of the peer (lower is better). The cost is the ping
+    /// latency (in seconds), plus a fixed cost for each hop from the validators.
+    pub fn cost(&self) -> f64 {
+        let latency = self
+            .average_ping_latency_secs
+            .unwrap_or(UNKNOWN_PING_LATENCY_SECS);
+        let distance = self
+            .distance_from_validators
+            .unwrap_or(UNKNOWN_DISTANCE_FROM_VALIDATORS);
+        latency + (distance as f64) * DISTANCE_FROM_VALIDATORS_COST_SECS
+    }
+}
+
+/// The latest measured performance of each peer
+#[derive(Clone, Debug, Default)]
+pub struct PeerPerformanceCache {
+    performance: HashMap<PeerId, PeerPerformance>,
+}
+
+impl PeerPerformanceCache {
+    /// Updates the measured performance of the given peer
+    pub fn update(&mut self, peer_id: PeerId, performance: PeerPerformance) {
+        self.performance.insert(peer_id, performance);
+    }
+
+    /// Returns the measured performance of the given peer
+    pub fn get(&self, peer_id: &PeerId) -> Option<&PeerPerformance> {
+        self.performance.get(peer_id)
+    }
+
+    /// Returns the cost of the given peer. Peers that haven't been measured
+    /// are assigned the estimated cost of an unknown peer.
+    pub fn cost(&self, peer_id: &PeerId) -> f64 {
+        self.performance
+            .get(peer_id)
+            .copied()
+            .unwrap_or_default()
+            .cost()
+    }
+
+    /// Compares the given peers by cost (lower cost first)
+    pub fn compare(&self, peer_id: &PeerId, other_id: &PeerId) -> Ordering {
+        self.cost(peer_id).total_cmp(&self.cost(other_id))
+    }
+
+    /// Removes the performance of all peers for which `keep` returns false
+    pub fn retain<F: Fn(&PeerId) -> bool>(&mut self, keep: F) {
+        self.performance.retain(|peer_id, _| keep(peer_id));
+    }
+}
@@ -2,22 +2,31 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::*;
+use super::{
+    peer_exchange::{CANDIDATE_EXPIRATION, MAX_CANDIDATES_PER_REPORTER, MAX_CANDIDATES_PER_SUBNET},
+    *,
+};
 use crate::{
     peer::DisconnectReason,
     peer_manager::{conn_notifs_channel, ConnectionNotification, ConnectionRequest},
     transport::ConnectionMetadata,
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
-use aptos_config::config::{Peer, PeerRole, PeerSet, HANDSHAKE_VERSION};
+use aptos_config::{
+    config::{Peer, PeerRole, PeerSet, RoleType, HANDSHAKE_VERSION},
+    network_id::NetworkId,
+};
 use aptos_crypto::{test_utils::TEST_SEED, x25519, Uniform};
 use aptos_logger::info;
+use aptos_peer_monitoring_service_types::{
+    response::NetworkInformationResponse, PeerMonitoringMetadata,
+};
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{account_address::AccountAddress, network_address::NetworkAddress};
 use futures::{executor::block_on, future, SinkExt};
 use maplit::{hashmap, hashset};
 use rand::rngs::StdRng;
-use std::{io, str::FromStr};
+use std::{collections::BTreeMap, io, str::FromStr};
 use tokio_retry::strategy::FixedInterval;
 
 const MAX_TEST_CONNECTIONS: usize = 3;
@@ -82,7 +91,13 @@ struct TestHarness {
 
 impl TestHarness {
     fn new(seeds: PeerSet) -> (Self, ConnectivityManager<FixedInterval>) {
-        let network_context = NetworkContext::mock();
+        Self::new_with_network_context(seeds, NetworkContext::mock())
+    }
+
+    fn new_with_network_context(
+        seeds: PeerSet,
+        network_context: NetworkContext,
+    ) -> (Self, ConnectivityManager<FixedInterval>) {
         let time_service = TimeService::mock();
         let (connection_reqs_tx, connection_reqs_rx) =
             aptos_channel::new(QueueStyle::FIFO, 1, None);
@@ -906,3 +921,348 @@ async fn test_stale_peers_vfn_inbound() {
         mock.expect_disconnect_fail(peer_id_2, connection_metadata_2.addr)
     );
 }
+
+#[test]
+fn test_peer_exchange_candidates_are_untrusted() {
+    // Create a connectivity manager for a public fullnode
+    let (mock, mut conn_mgr) = create_public_fullnode_harness();
+    let trusted_peers = mock
+        .peers_and_metadata
+        .get_trusted_peers(&NetworkId::Public)
+        .unwrap();
+
+    // Report a valid candidate, an invalid candidate (loopback), and ourselves
+    let reporter = PeerId::random();
+    let (candidate_id, candidate) = peer_exchange_candidate("8.8.8.8");
+    let (invalid_id, invalid_candidate) = peer_exchange_candidate("127.0.0.1");
+    let (_, self_candidate) = peer_exchange_candidate("8.8.4.4");
+    let self_peer_id = mock.network_context.peer_id();
+    conn_mgr.handle_peer_exchange_candidates(reporter, hashmap! {
+        candidate_id => candidate,
+        invalid_id => invalid_candidate,
+        self_peer_id => self_candidate,
+    });
+
+    // Verify that only the valid candidate is dialable, and that it isn't trusted
+    assert!(conn_mgr.peer_exchange_candidates.contains(&candidate_id));
+    assert!(!conn_mgr.peer_exchange_candidates.contains(&invalid_id));
+    assert!(!conn_mgr.peer_exchange_candidates.contains(&self_peer_id));
+    assert!(trusted_peers.read().is_empty());
+    let peers_to_dial: Vec<_> = conn_mgr
+        .choose_peers_to_dial()
+        .into_iter()
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    assert_eq!(peers_to_dial, vec![candidate_id]);
+
+    // Discover the candidate via a trusted source and verify it is no longer a candidate
+    let (_, trusted_peer) = peer_exchange_candidate("8.8.8.8");
+    let trusted_peer = Peer::new(
+        trusted_peer.addresses,
+        trusted_peer.keys,
+        PeerRole::PreferredUpstream,
+    );
+    conn_mgr.handle_update_discovered_peers(
+        DiscoverySource::Config,
+        hashmap! {candidate_id => trusted_peer},
+    );
+    conn_mgr.handle_peer_exchange_candidates(reporter, PeerSet::new());
+    assert!(!conn_mgr.peer_exchange_candidates.contains(&candidate_id));
+    assert_eq!(
+        trusted_peers.read().get(&candidate_id).unwrap().role,
+        PeerRole::PreferredUpstream
+    );
+}
+
+#[test]
+fn test_peer_exchange_flooding_limits() {
+    // Create a connectivity manager for a public fullnode
+    let (_mock, mut conn_mgr) = create_public_fullnode_harness();
+
+    // Flood candidates (in different subnets) from a single reporter
+    let reporter = PeerId::random();
+    let reported_peers: PeerSet = (0..MAX_CANDIDATES_PER_REPORTER + 10)
+        .map(|i| peer_exchange_candidate(&format!("8.{}.{}.1", i / 256, i % 256)))
+        .collect();
+    conn_mgr.handle_peer_exchange_candidates(reporter, reported_peers);
+
+    // Verify the candidates from the reporter are bounded
+    assert_eq!(
+        conn_mgr.peer_exchange_candidates.len(),
+        MAX_CANDIDATES_PER_REPORTER
+    );
+
+    // Flood candidates (in the same subnet) from another reporter
+    let other_reporter = PeerId::random();
+    let reported_peers: PeerSet = (0..MAX_CANDIDATES_PER_SUBNET + 10)
+        .map(|i| peer_exchange_candidate(&format!("9.9.9.{}", i + 1)))
+        .collect();
+    conn_mgr.handle_peer_exchange_candidates(other_reporter, reported_peers);
+
+    // Verify the candidates in the subnet are bounded
+    assert_eq!(
+        conn_mgr.peer_exchange_candidates.len(),
+        MAX_CANDIDATES_PER_REPORTER + MAX_CANDIDATES_PER_SUBNET
+    );
+}
+
+#[test]
+fn test_peer_exchange_dial_limits() {
+    // Create a connectivity manager for a public fullnode
+    let (_mock, mut conn_mgr) = create_public_fullnode_harness();
+
+    // Report candidate A (from two reporters) and candidates B and C (in the same subnet)
+    let (peer_id_a, peer_a) = peer_exchange_candidate("8.8.8.8");
+    let (peer_id_b, peer_b) = peer_exchange_candidate("9.9.9.1");
+    let (peer_id_c, peer_c) = peer_exchange_candidate("9.9.9.2");
+    conn_mgr.handle_peer_exchange_candidates(PeerId::random(), hashmap! {
+        peer_id_a => peer_a.clone(),
+        peer_id_b => peer_b,
+        peer_id_c => peer_c,
+    });
+    conn_mgr.handle_peer_exchange_candidates(PeerId::random(), hashmap! {
+        peer_id_a => peer_a,
+    });
+    assert_eq!(conn_mgr.peer_exchange_candidates.score(&peer_id_a), 2);
+
+    // Verify that only the highest scored candidate is dialed (candidates
+    // can only use a fraction of the outbound connections).
+    let peers_to_dial: Vec<_> = conn_mgr
+        .choose_peers_to_dial()
+        .into_iter()
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    assert_eq!(peers_to_dial, vec![peer_id_a]);
+
+    // Remove the outbound connection limit and verify that only a single
+    // candidate is dialed per subnet.
+    conn_mgr.outbound_connection_limit = None;
+    let peers_to_dial: Vec<_> = conn_mgr
+        .choose_peers_to_dial()
+        .into_iter()
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    assert_eq!(peers_to_dial.len(), 2);
+    assert_eq!(peers_to_dial[0], peer_id_a);
+    assert!(peers_to_dial[1] == peer_id_b || peers_to_dial[1] == peer_id_c);
+}
+
+#[test]
+fn test_peer_exchange_candidate_expiration() {
+    // Create a connectivity manager for a public fullnode
+    let (mock, mut conn_mgr) = create_public_fullnode_harness();
+
+    // Report two candidates and connect to one of them
+    let (peer_id_a, peer_a) = peer_exchange_candidate("8.8.8.8");
+    let (peer_id_b, peer_b) = peer_exchange_candidate("9.9.9.9");
+    conn_mgr.handle_peer_exchange_candidates(PeerId::random(), hashmap! {
+        peer_id_a => peer_a,
+        peer_id_b => peer_b,
+    });
+    let connection_metadata = ConnectionMetadata::mock_with_role_and_origin(
+        peer_id_a,
+        PeerRole::Upstream,
+        ConnectionOrigin::Outbound,
+    );
+    conn_mgr.handle_control_notification(ConnectionNotification::NewPeer(
+        connection_metadata,
+        mock.network_context,
+    ));
+
+    // Elapse the expiration time and handle a new report
+    mock.mock_time.advance(CANDIDATE_EXPIRATION);
+    conn_mgr.handle_peer_exchange_candidates(PeerId::random(), PeerSet::new());
+
+    // Verify that only the unconnected candidate expired
+    assert!(conn_mgr.peer_exchange_candidates.contains(&peer_id_a));
+    assert!(!conn_mgr.peer_exchange_candidates.contains(&peer_id_b));
+    assert!(!conn_mgr.discovered_peers.0.contains_key(&peer_id_b));
+}
+
+#[test]
+fn test_peer_exchange_sample() {
+    // Create a connectivity manager for a public fullnode
+    let (mock, mut conn_mgr) = create_public_fullnode_harness();
+
+    // Connect an outbound and an inbound peer
+    let (outbound_peer_id, outbound_peer) = peer_exchange_candidate("8.8.8.8");
+    let outbound_address = outbound_peer.addresses[0].clone();
+    for (peer_id, origin, address) in [
+        (
+            outbound_peer_id,
+            ConnectionOrigin::Outbound,
+            outbound_address.clone(),
+        ),
+        (
+            PeerId::random(),
+            ConnectionOrigin::Inbound,
+            network_address("/ip4/9.9.9.9/tcp/6180"),
+        ),
+    ] {
+        let mut connection_metadata =
+            ConnectionMetadata::mock_with_role_and_origin(peer_id, PeerRole::Unknown, origin);
+        connection_metadata.addr = address;
+        conn_mgr.handle_control_notification(ConnectionNotification::NewPeer(
+            connection_metadata,
+            mock.network_context,
+        ));
+    }
+
+    // Verify that only the outbound peer (and its dialed address) is sampled
+    let sample = conn_mgr.get_peer_exchange_sample(10);
+    assert_eq!(sample.len(), 1);
+    assert_eq!(sample.get(&outbound_peer_id).unwrap().addresses, vec![
+        outbound_address
+    ]);
+
+    // Verify the sample size is bounded
+    assert!(conn_mgr.get_peer_exchange_sample(0).is_empty());
+}
+
+#[test]
+fn test_peer_selection_prefers_low_cost_peers() {
+    // Create a connectivity manager for a public fullnode
+    let (_mock, mut conn_mgr) = create_public_fullnode_harness();
+
+    // Discover several peers and measure their latencies
+    let latencies = [0.5, 0.1, 0.3, 0.05, 0.2];
+    let mut peer_ids = vec![];
+    let mut discovered_peers = PeerSet::new();
+    for (i, latency) in latencies.iter().enumerate() {
+        let (peer_id, peer) = peer_exchange_candidate(&format!("8.8.{}.1", i));
+        conn_mgr
+            .peer_performance
+            .update(peer_id, PeerPerformance::new(Some(*latency), Some(1)));
+        discovered_peers.insert(peer_id, peer);
+        peer_ids.push(peer_id);
+    }
+    conn_mgr.handle_update_discovered_peers(DiscoverySource::Config, discovered_peers);
+
+    // Verify that the lowest latency peers are dialed (in order)
+    let peers_to_dial: Vec<_> = conn_mgr
+        .choose_peers_to_dial()
+        .into_iter()
+        .map(|(peer_id, _)| peer_id)
+        .collect();
+    assert_eq!(peers_to_dial, vec![peer_ids[3], peer_ids[1], peer_ids[4]]);
+}
+
+#[test]
+fn test_peer_selection_simulation() {
+    // Create a connectivity manager for a public fullnode (with a deterministic rng)
+    let (mock, mut conn_mgr) = create_public_fullnode_harness();
+    let outbound_connection_limit = 8;
+    conn_mgr.outbound_connection_limit = Some(outbound_connection_limit);
+    conn_mgr.rng = SmallRng::seed_from_u64(0);
+
+    // Discover good peers (low latency and close to the validators) and bad peers
+    let num_bad_peers = 2 * outbound_connection_limit;
+    let mut good_peers = HashSet::new();
+    let mut peer_measurements = HashMap::new();
+    let mut discovered_peers = PeerSet::new();
+    for i in 0..outbound_connection_limit + num_bad_peers {
+        let (peer_id, peer) = peer_exchange_candidate(&format!("8.8.{}.1", i));
+        if i < outbound_connection_limit {
+            good_peers.insert(peer_id);
+            peer_measurements.insert(peer_id, (0.05, 1));
+        } else {
+            peer_measurements.insert(peer_id, (0.5, 3));
+        }
+        discovered_peers.insert(peer_id, peer);
+    }
+    conn_mgr.handle_update_discovered_peers(DiscoverySource::Config, discovered_peers);
+
+    // Simulate several connectivity checks. Each chosen peer is connected
+    // successfully and then measured by the peer monitoring service.
+    for round in 0..num_bad_peers + 1 {
+        let peers_to_dial = conn_mgr.choose_peers_to_dial();
+        if round == 0 {
+            assert_eq!(peers_to_dial.len(), outbound_connection_limit);
+        } else {
+            assert!(peers_to_dial.len() <= 1);
+        }
+        for (peer_id, _) in peers_to_dial {
+            let (latency, distance) = *peer_measurements.get(&peer_id).unwrap();
+            connect_and_measure_peer(&mock, &mut conn_mgr, peer_id, latency, distance);
+        }
+
+        // Evict the worst performing peer (if any)
+        conn_mgr.update_peer_performance();
+        if let Some(peer_id) = conn_mgr.choose_peer_to_evict() {
+            assert!(!good_peers.contains(&peer_id));
+            conn_mgr.handle_control_notification(ConnectionNotification::LostPeer(
+                ConnectionMetadata::mock_with_role_and_origin(
+                    peer_id,
+                    PeerRole::Upstream,
+                    ConnectionOrigin::Outbound,
+                ),
+                mock.network_context,
+                DisconnectReason::ConnectionLost,
+            ));
+        }
+    }
+
+    // Verify that the node converged to the good peers (and remains stable)
+    let connected_peers: HashSet<_> = conn_mgr.get_connected_peers().into_keys().collect();
+    assert_eq!(connected_peers, good_peers);
+    assert!(conn_mgr.choose_peer_to_evict().is_none());
+    assert!(conn_mgr.choose_peers_to_dial().is_empty());
+}
+
+/// Simulates a successful outbound connection to the given peer, and
+/// the measurements of the peer by the peer monitoring service.
+fn connect_and_measure_peer(
+    mock: &TestHarness,
+    conn_mgr: &mut ConnectivityManager<FixedInterval>,
+    peer_id: PeerId,
+    average_ping_latency_secs: f64,
+    distance_from_validators: u64,
+) {
+    let connection_metadata = ConnectionMetadata::mock_with_role_and_origin(
+        peer_id,
+        PeerRole::Upstream,
+        ConnectionOrigin::Outbound,
+    );
+    let peer_network_id = PeerNetworkId::new(NetworkId::Public, peer_id);
+    mock.peers_and_metadata
+        .insert_connection_metadata(peer_network_id, connection_metadata.clone())
+        .unwrap();
+    let network_info_response = NetworkInformationResponse {
+        connected_peers: BTreeMap::new(),
+        distance_from_validators,
+    };
+    mock.peers_and_metadata
+        .update_peer_monitoring_metadata(
+            peer_network_id,
+            PeerMonitoringMetadata::new(
+                Some(average_ping_latency_secs),
+                Some(network_info_response),
+                None,
+                None,
+            ),
+        )
+        .unwrap();
+    conn_mgr.handle_control_notification(ConnectionNotification::NewPeer(
+        connection_metadata,
+        mock.network_context,
+    ));
+}
+
+/// Creates a test harness for a public fullnode
+fn create_public_fullnode_harness() -> (TestHarness, ConnectivityManager<FixedInterval>) {
+    let network_context =
+        NetworkContext::new(RoleType::FullNode, NetworkId::Public, PeerId::random());
+    TestHarness::new_with_network_context(HashMap::new(), network_context)
+}
+
+/// Creates a peer exchange candidate with a single address at the given IP
+fn peer_exchange_candidate(ip: &str) -> (PeerId, Peer) {
+    let pubkey = x25519::PrivateKey::generate_for_testing().public_key();
+    let address = NetworkAddress::from_str(&format!("/ip4/{}/tcp/6180", ip))
+        .unwrap()
+        .append_prod_protos(pubkey, HANDSHAKE_VERSION);
+    (
+        PeerId::random(),
+        Peer::from_addrs(PeerRole::Upstream, vec![address]),
+    )
+}
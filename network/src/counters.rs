@@ -268,6 +268,68 @@ pub fn inbound_rpc_handler_latency(
     ])
 }
 
+pub static APTOS_NETWORK_OUTBOUND_QUEUE_DELAY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_network_outbound_queue_delay_seconds",
+        "Time outbound messages spend in the peer outbound queue, in seconds",
+        &["role_type", "network_id", "peer_id", "protocol_id"]
+    )
+    .unwrap()
+});
+
+/// Returns the outbound queue delay histogram for the given protocol (messages
+/// without a protocol, e.g., errors, are labeled as "Error").
+pub fn outbound_queue_delay(
+    network_context: &NetworkContext,
+    protocol_id: Option<ProtocolId>,
+) -> Histogram {
+    APTOS_NETWORK_OUTBOUND_QUEUE_DELAY.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        protocol_id.map_or("Error", |protocol_id| protocol_id.as_str()),
+    ])
+}
+
+pub static APTOS_NETWORK_TRAFFIC_RECORDER_RECORDS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_traffic_recorder_records",
+        "Number of network messages recorded (or dropped) by the traffic recorder",
+        &["role_type", "network_id", "peer_id", "state"]
+    )
+    .unwrap()
+});
+
+pub fn traffic_recorder_records(
+    network_context: &NetworkContext,
+    state: &'static str,
+) -> IntCounter {
+    APTOS_NETWORK_TRAFFIC_RECORDER_RECORDS.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        state,
+    ])
+}
+
+pub static APTOS_NETWORK_IDENTITY_KEY_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_identity_key_events",
+        "Number of identity key rotations, expirations and drained connections",
+        &["role_type", "network_id", "peer_id", "event"]
+    )
+    .unwrap()
+});
+
+pub fn identity_key_events(network_context: &NetworkContext, event: &'static str) -> IntCounter {
+    APTOS_NETWORK_IDENTITY_KEY_EVENTS.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        event,
+    ])
+}
+
 pub static APTOS_NETWORK_DIRECT_SEND_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "aptos_network_direct_send_messages",
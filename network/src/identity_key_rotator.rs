@@ -0,0 +1,271 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The identity key rotator allows the identity key of a network to be rotated
+//! without restarting the node.
+//!
+//! The rotator periodically reloads the identity key (e.g., from secure storage).
+//! When the key changes, the new key is used for all new outbound connections and
+//! the previous key is still accepted for inbound connections until its grace
+//! period expires. This gives the rest of the network time to learn about the new
+//! key (i.e., via the on-chain network addresses). Once the previous key expires,
+//! the connections established with it are drained (a few at a time).
+
+use crate::{
+    application::storage::PeersAndMetadata, counters, logging::NetworkSchema, noise::IdentityKeys,
+    peer_manager::ConnectionRequestSender,
+};
+use aptos_config::{config::IdentityKeyRotationConfig, network_id::NetworkContext};
+use aptos_crypto::x25519;
+use aptos_logger::prelude::*;
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use futures::StreamExt;
+use std::{sync::Arc, time::Duration};
+
+/// Loads the latest identity key (e.g., from secure storage or the identity file)
+pub type IdentityKeyLoader = Box<dyn Fn() -> anyhow::Result<x25519::PrivateKey> + Send>;
+
+pub struct IdentityKeyRotator {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    identity_keys: Arc<IdentityKeys>,
+    key_loader: IdentityKeyLoader,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    connection_reqs_tx: ConnectionRequestSender,
+    config: IdentityKeyRotationConfig,
+}
+
+impl IdentityKeyRotator {
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        identity_keys: Arc<IdentityKeys>,
+        key_loader: IdentityKeyLoader,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        connection_reqs_tx: ConnectionRequestSender,
+        config: IdentityKeyRotationConfig,
+    ) -> Self {
+        Self {
+            network_context,
+            time_service,
+            identity_keys,
+            key_loader,
+            peers_and_metadata,
+            connection_reqs_tx,
+            config,
+        }
+    }
+
+    /// Periodically reloads the identity key and drains the
+    /// connections established with expired keys.
+    pub async fn start(mut self) {
+        info!(
+            NetworkSchema::new(&self.network_context),
+            "{} Identity key rotator started", self.network_context
+        );
+
+        let ticker = self
+            .time_service
+            .interval(Duration::from_millis(self.config.key_reload_interval_ms));
+        tokio::pin!(ticker);
+
+        while ticker.next().await.is_some() {
+            self.reload_identity_key();
+            self.drain_connections().await;
+        }
+
+        warn!(
+            NetworkSchema::new(&self.network_context),
+            "{} Identity key rotator terminated", self.network_context
+        );
+    }
+
+    /// Reloads the identity key, and rotates to it if it has changed
+    fn reload_identity_key(&mut self) {
+        let new_key = match (self.key_loader)() {
+            Ok(new_key) => new_key,
+            Err(error) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    error = %error,
+                    "{} Failed to reload the identity key: {}",
+                    self.network_context,
+                    error
+                );
+                return;
+            },
+        };
+
+        let new_public_key = new_key.public_key();
+        let previous_public_key = self.identity_keys.current_public_key();
+        let expiration_time = self.time_service.now()
+            + Duration::from_millis(self.config.previous_key_grace_period_ms);
+        if self.identity_keys.rotate(new_key, expiration_time) {
+            info!(
+                NetworkSchema::new(&self.network_context),
+                "{} Rotated the identity key from {} to {}. The previous key will be accepted for {} ms.",
+                self.network_context,
+                previous_public_key,
+                new_public_key,
+                self.config.previous_key_grace_period_ms
+            );
+            counters::identity_key_events(&self.network_context, "rotated").inc();
+        }
+    }
+
+    /// Removes the expired keys, and closes (some of) the connections that
+    /// were established with a key that is no longer accepted.
+    async fn drain_connections(&mut self) {
+        for expired_public_key in self
+            .identity_keys
+            .remove_expired_keys(self.time_service.now())
+        {
+            info!(
+                NetworkSchema::new(&self.network_context),
+                "{} The previous identity key {} has expired",
+                self.network_context,
+                expired_public_key
+            );
+            counters::identity_key_events(&self.network_context, "expired").inc();
+        }
+
+        // Identify the connections established with keys that are no longer accepted
+        let connected_peers = match self.peers_and_metadata.get_connected_peers_and_metadata() {
+            Ok(connected_peers) => connected_peers,
+            Err(error) => {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    error = %error,
+                    "{} Failed to get the connected peers: {}",
+                    self.network_context,
+                    error
+                );
+                return;
+            },
+        };
+        let network_id = self.network_context.network_id();
+        let drained_peers: Vec<PeerId> = connected_peers
+            .into_iter()
+            .filter(|(peer_network_id, peer_metadata)| {
+                peer_network_id.network_id() == network_id
+                    && peer_metadata
+                        .get_connection_metadata()
+                        .local_public_key
+                        .map_or(false, |local_public_key| {
+                            !self.identity_keys.is_accepted(&local_public_key)
+                        })
+            })
+            .map(|(peer_network_id, _)| peer_network_id.peer_id())
+            .take(self.config.max_drained_connections_per_interval)
+            .collect();
+
+        // Close the connections (the connectivity manager will reconnect using the current key)
+        for peer_id in drained_peers {
+            info!(
+                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                "{} Closing the connection to peer {} (established with an expired identity key)",
+                self.network_context,
+                peer_id.short_str()
+            );
+            counters::identity_key_events(&self.network_context, "drained").inc();
+
+            if let Err(error) = self.connection_reqs_tx.disconnect_peer(peer_id).await {
+                warn!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    error = %error,
+                    "{} Failed to close the connection to peer {}: {}",
+                    self.network_context,
+                    peer_id.short_str(),
+                    error
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{peer_manager::ConnectionRequest, transport::ConnectionMetadata};
+    use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+    use aptos_config::network_id::PeerNetworkId;
+    use aptos_crypto::Uniform;
+    use futures::{future::join, FutureExt};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::sync::atomic::{AtomicU8, Ordering};
+
+    /// Generates a deterministic key from the given seed
+    fn create_key(seed: u8) -> x25519::PrivateKey {
+        x25519::PrivateKey::generate(&mut StdRng::from_seed([seed; 32]))
+    }
+
+    #[tokio::test]
+    async fn test_rotate_and_drain_connections() {
+        // Create the rotator with a key loader that returns the key for the current seed
+        let network_context = NetworkContext::mock();
+        let time_service = TimeService::mock();
+        let mock_time = time_service.clone().into_mock();
+        let identity_keys = Arc::new(IdentityKeys::new(create_key(0)));
+        let key_seed = Arc::new(AtomicU8::new(0));
+        let loader_key_seed = key_seed.clone();
+        let peers_and_metadata = PeersAndMetadata::new(&[network_context.network_id()]);
+        let (connection_reqs_tx, mut connection_reqs_rx) =
+            aptos_channel::new(QueueStyle::FIFO, 10, None);
+        let config = IdentityKeyRotationConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut rotator = IdentityKeyRotator::new(
+            network_context,
+            time_service,
+            identity_keys.clone(),
+            Box::new(move || Ok(create_key(loader_key_seed.load(Ordering::SeqCst)))),
+            peers_and_metadata.clone(),
+            ConnectionRequestSender::new(connection_reqs_tx),
+            config.clone(),
+        );
+
+        // Reload the (unchanged) key and verify nothing is rotated
+        let previous_public_key = create_key(0).public_key();
+        rotator.reload_identity_key();
+        assert_eq!(identity_keys.current_public_key(), previous_public_key);
+
+        // Connect to a peer using the current key
+        let peer_id = PeerId::random();
+        let mut connection_metadata = ConnectionMetadata::mock(peer_id);
+        connection_metadata.local_public_key = Some(previous_public_key);
+        peers_and_metadata
+            .insert_connection_metadata(
+                PeerNetworkId::new(network_context.network_id(), peer_id),
+                connection_metadata,
+            )
+            .unwrap();
+
+        // Rotate the key and verify that the connection is not drained
+        key_seed.store(1, Ordering::SeqCst);
+        rotator.reload_identity_key();
+        assert_eq!(
+            identity_keys.current_public_key(),
+            create_key(1).public_key()
+        );
+        assert!(identity_keys.is_accepted(&previous_public_key));
+        rotator.drain_connections().await;
+        assert!(connection_reqs_rx.next().now_or_never().is_none());
+
+        // Elapse the grace period and verify the connection is drained
+        mock_time.advance(Duration::from_millis(config.previous_key_grace_period_ms));
+        let handle_disconnect = async {
+            match connection_reqs_rx.next().await.unwrap() {
+                ConnectionRequest::DisconnectPeer(drained_peer_id, response_tx) => {
+                    assert_eq!(drained_peer_id, peer_id);
+                    response_tx.send(Ok(())).unwrap();
+                },
+                request => panic!("Unexpected connection request: {:?}", request),
+            }
+        };
+        join(rotator.drain_connections(), handle_disconnect).await;
+        assert!(!identity_keys.is_accepted(&previous_public_key));
+    }
+}
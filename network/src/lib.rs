@@ -16,11 +16,13 @@ pub mod connectivity_manager;
 pub mod constants;
 pub mod counters;
 pub mod error;
+pub mod identity_key_rotator;
 pub mod logging;
 pub mod noise;
 pub mod peer;
 pub mod peer_manager;
 pub mod protocols;
+pub mod traffic_recorder;
 pub mod transport;
 
 #[cfg(feature = "fuzzing")]
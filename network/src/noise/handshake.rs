@@ -14,7 +14,7 @@
 use crate::{
     application::storage::PeersAndMetadata,
     logging::NetworkSchema,
-    noise::{error::NoiseHandshakeError, stream::NoiseStream},
+    noise::{error::NoiseHandshakeError, identity_keys::IdentityKeys, stream::NoiseStream},
 };
 use aptos_config::{
     config::{Peer, PeerRole},
@@ -146,8 +146,8 @@ impl HandshakeAuthMode {
 pub struct NoiseUpgrader {
     /// The validator's network context
     pub network_context: NetworkContext,
-    /// Config for executing Noise handshakes. Includes our static private key.
-    noise_config: noise::NoiseConfig,
+    /// Configs for executing Noise handshakes. Includes our static private key(s).
+    identity_keys: Arc<IdentityKeys>,
     /// Handshake authentication can be either mutual or server-only authentication.
     auth_mode: HandshakeAuthMode,
 }
@@ -166,14 +166,29 @@ impl NoiseUpgrader {
         network_context: NetworkContext,
         key: x25519::PrivateKey,
         auth_mode: HandshakeAuthMode,
+    ) -> Self {
+        Self::new_with_identity_keys(network_context, Arc::new(IdentityKeys::new(key)), auth_mode)
+    }
+
+    /// Create a new NoiseConfig with the provided (shared) identity keys and
+    /// authentication mode. This allows the identity keys to be rotated at runtime.
+    pub fn new_with_identity_keys(
+        network_context: NetworkContext,
+        identity_keys: Arc<IdentityKeys>,
+        auth_mode: HandshakeAuthMode,
     ) -> Self {
         Self {
             network_context,
-            noise_config: noise::NoiseConfig::new(key),
+            identity_keys,
             auth_mode,
         }
     }
 
+    /// Returns the identity keys used by the handshakes
+    pub fn identity_keys(&self) -> Arc<IdentityKeys> {
+        self.identity_keys.clone()
+    }
+
     /// Perform an outbound protocol upgrade on this connection.
     ///
     /// This runs the "client" side of the Noise IK handshake to establish a
@@ -206,9 +221,10 @@ impl NoiseUpgrader {
         let payload = time_provider();
 
         // craft first handshake message  (-> e, es, s, ss)
+        // note: outbound connections always use the current identity key
+        let noise_config = self.identity_keys.current();
         let mut rng = rand::rngs::OsRng;
-        let initiator_state = self
-            .noise_config
+        let initiator_state = noise_config
             .initiate_connection(
                 &mut rng,
                 prologue_msg,
@@ -251,8 +267,7 @@ impl NoiseUpgrader {
             self.network_context,
             remote_public_key,
         );
-        let (_, session) = self
-            .noise_config
+        let (_, session) = noise_config
             .finalize_connection(initiator_state, &server_response)
             .map_err(NoiseHandshakeError::ClientFinalizeFailed)?;
 
@@ -347,18 +362,21 @@ impl NoiseUpgrader {
             return Err(NoiseHandshakeError::SelfDialDetected);
         }
 
-        // verify that this is indeed our public key
-        if self_expected_public_key != self.noise_config.public_key().as_slice() {
-            return Err(NoiseHandshakeError::ClientExpectingDifferentPubkey(
-                remote_peer_short,
-                hex::encode(self_expected_public_key),
-            ));
-        }
+        // verify that this is indeed one of our public keys (i.e., the current
+        // key or a previous key that is still accepted during a key rotation)
+        let noise_config = self
+            .identity_keys
+            .find(self_expected_public_key)
+            .ok_or_else(|| {
+                NoiseHandshakeError::ClientExpectingDifferentPubkey(
+                    remote_peer_short,
+                    hex::encode(self_expected_public_key),
+                )
+            })?;
 
         // parse it
         let (prologue, client_init_message) = client_message.split_at(Self::PROLOGUE_SIZE);
-        let (remote_public_key, handshake_state, payload) = self
-            .noise_config
+        let (remote_public_key, handshake_state, payload) = noise_config
             .parse_client_init_message(prologue, client_init_message)
             .map_err(|err| NoiseHandshakeError::ServerParseClient(remote_peer_short, err))?;
 
@@ -455,8 +473,7 @@ impl NoiseUpgrader {
         // construct the response
         let mut rng = rand::rngs::OsRng;
         let mut server_response = [0u8; Self::SERVER_MESSAGE_SIZE];
-        let session = self
-            .noise_config
+        let session = noise_config
             .respond_to_client(&mut rng, handshake_state, None, &mut server_response)
             .map_err(|err| {
                 NoiseHandshakeError::BuildServerHandshakeMessageFailed(remote_peer_short, err)
@@ -738,13 +755,40 @@ mod test {
         let ((mut client, _), (server, server_public_key)) = build_peers(true, None);
 
         // swap in a different keypair, so the connection will be unauthenticated
-        client.noise_config = noise::NoiseConfig::new(client_private_key);
+        client.identity_keys = Arc::new(IdentityKeys::new(client_private_key));
         let (client_res, server_res) = perform_handshake(&client, &server, server_public_key);
 
         client_res.unwrap_err();
         server_res.unwrap_err();
     }
 
+    #[test]
+    fn test_handshake_rotated_server_key() {
+        let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED_2);
+        let (new_server_private_key, new_server_public_key) = create_key_pair(&mut rng);
+
+        // rotate the server key, and keep accepting the previous key
+        let ((client, _), (server, previous_server_public_key)) = build_peers(false, None);
+        let now = std::time::Instant::now();
+        assert!(server.identity_keys().rotate(new_server_private_key, now));
+
+        // the client can connect using either the previous or the new server key
+        for server_public_key in [previous_server_public_key, new_server_public_key] {
+            let (client_res, server_res) = perform_handshake(&client, &server, server_public_key);
+            let (client_stream, _) = client_res.unwrap();
+            let (server_stream, _, _) = server_res.unwrap();
+            assert_eq!(client_stream.get_remote_static(), server_public_key);
+            assert_eq!(server_stream.get_local_static(), server_public_key);
+        }
+
+        // once the previous key expires, the client can no longer connect using it
+        server.identity_keys().remove_expired_keys(now);
+        let (client_res, server_res) =
+            perform_handshake(&client, &server, previous_server_public_key);
+        client_res.unwrap_err();
+        server_res.unwrap_err();
+    }
+
     #[test]
     fn test_handshake_unauthed_peerid_fails_mutual_auth() {
         let mut rng = ::rand::rngs::StdRng::from_seed(TEST_SEED_2);
@@ -0,0 +1,190 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The set of identity keys (i.e., x25519 static keys) used by the Noise handshakes.
+//!
+//! Outbound connections are always established with the current key. To support
+//! rotating the identity key at runtime, the previous keys are still accepted for
+//! inbound connections (i.e., for peers that dial an address with a previous key)
+//! until they expire.
+
+use aptos_crypto::{ed25519::Ed25519Signature, noise, x25519};
+use aptos_infallible::RwLock;
+use rand::rngs::OsRng;
+use std::{sync::Arc, time::Instant};
+
+/// A previous identity key that is still accepted until its expiration time
+struct PreviousKey {
+    noise_config: Arc<noise::NoiseConfig>,
+    expiration_time: Instant,
+}
+
+struct IdentityKeySet {
+    current: Arc<noise::NoiseConfig>,
+    previous: Vec<PreviousKey>,
+}
+
+/// The identity keys of a network, shared between the transport and the key rotator
+pub struct IdentityKeys {
+    keys: RwLock<IdentityKeySet>,
+}
+
+impl IdentityKeys {
+    pub fn new(key: x25519::PrivateKey) -> Self {
+        Self {
+            keys: RwLock::new(IdentityKeySet {
+                current: Arc::new(noise::NoiseConfig::new(key)),
+                previous: vec![],
+            }),
+        }
+    }
+
+    /// Returns the noise config of the current key (used for outbound connections)
+    pub fn current(&self) -> Arc<noise::NoiseConfig> {
+        self.keys.read().current.clone()
+    }
+
+    /// Returns the public key of the current key
+    pub fn current_public_key(&self) -> x25519::PublicKey {
+        self.keys.read().current.public_key()
+    }
+
+    /// Signs the given message with the current key (using XEdDSA), and
+    /// returns the public key of the current key along with the signature.
+    /// The message must be domain separated from other uses of the key.
+    pub fn sign_with_current_key(&self, message: &[u8]) -> (x25519::PublicKey, Ed25519Signature) {
+        let current = self.current();
+        (
+            current.public_key(),
+            current.sign_xeddsa(&mut OsRng, message),
+        )
+    }
+
+    /// Returns the noise config of the accepted key (i.e., the current key
+    /// or a previous key) with the given public key, if one exists.
+    pub fn find(&self, public_key: &[u8]) -> Option<Arc<noise::NoiseConfig>> {
+        let keys = self.keys.read();
+        if keys.current.public_key().as_slice() == public_key {
+            return Some(keys.current.clone());
+        }
+        keys.previous
+            .iter()
+            .find(|previous_key| previous_key.noise_config.public_key().as_slice() == public_key)
+            .map(|previous_key| previous_key.noise_config.clone())
+    }
+
+    /// Returns true iff the given public key is the current key or a previous key
+    pub fn is_accepted(&self, public_key: &x25519::PublicKey) -> bool {
+        self.find(public_key.as_slice()).is_some()
+    }
+
+    /// Returns the public keys of the previous keys that are still accepted
+    pub fn previous_public_keys(&self) -> Vec<x25519::PublicKey> {
+        self.keys
+            .read()
+            .previous
+            .iter()
+            .map(|previous_key| previous_key.noise_config.public_key())
+            .collect()
+    }
+
+    /// Replaces the current key with the given key. The current key is still
+    /// accepted until the given expiration time. Returns false if the given
+    /// key is already the current key (in which case nothing changes).
+    pub fn rotate(&self, new_key: x25519::PrivateKey, expiration_time: Instant) -> bool {
+        let new_public_key = new_key.public_key();
+
+        let mut keys = self.keys.write();
+        if keys.current.public_key() == new_public_key {
+            return false;
+        }
+
+        // If the new key was previously used (e.g., the rotation was rolled back),
+        // it's no longer a previous key.
+        keys.previous
+            .retain(|previous_key| previous_key.noise_config.public_key() != new_public_key);
+
+        let previous_key = std::mem::replace(
+            &mut keys.current,
+            Arc::new(noise::NoiseConfig::new(new_key)),
+        );
+        keys.previous.push(PreviousKey {
+            noise_config: previous_key,
+            expiration_time,
+        });
+        true
+    }
+
+    /// Removes the previous keys that have expired, and returns their public keys
+    pub fn remove_expired_keys(&self, now: Instant) -> Vec<x25519::PublicKey> {
+        let mut keys = self.keys.write();
+        let (expired_keys, previous_keys): (Vec<_>, Vec<_>) = std::mem::take(&mut keys.previous)
+            .into_iter()
+            .partition(|previous_key| previous_key.expiration_time <= now);
+        keys.previous = previous_keys;
+
+        expired_keys
+            .into_iter()
+            .map(|expired_key| expired_key.noise_config.public_key())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::Uniform;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::time::Duration;
+
+    #[test]
+    fn test_rotate_and_expire_keys() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let first_key = x25519::PrivateKey::generate(&mut rng);
+        let first_public_key = first_key.public_key();
+        let second_key = x25519::PrivateKey::generate(&mut rng);
+        let second_public_key = second_key.public_key();
+
+        // Create the identity keys and verify only the first key is accepted
+        let identity_keys = IdentityKeys::new(first_key);
+        assert_eq!(identity_keys.current_public_key(), first_public_key);
+        assert!(identity_keys.is_accepted(&first_public_key));
+        assert!(!identity_keys.is_accepted(&second_public_key));
+
+        // Rotating to the current key (i.e., the same key regenerated) does nothing
+        let now = Instant::now();
+        let same_key = x25519::PrivateKey::generate(&mut StdRng::from_seed([0u8; 32]));
+        assert!(!identity_keys.rotate(same_key, now));
+        assert!(identity_keys.previous_public_keys().is_empty());
+
+        // Rotate to the second key and verify both keys are accepted
+        let expiration_time = now + Duration::from_secs(10);
+        assert!(identity_keys.rotate(second_key, expiration_time));
+        assert_eq!(identity_keys.current_public_key(), second_public_key);
+        assert_eq!(identity_keys.previous_public_keys(), vec![first_public_key]);
+        assert_eq!(
+            identity_keys
+                .find(first_public_key.as_slice())
+                .unwrap()
+                .public_key(),
+            first_public_key
+        );
+        assert!(identity_keys.is_accepted(&second_public_key));
+
+        // Verify the first key is not removed before it expires
+        assert!(identity_keys.remove_expired_keys(now).is_empty());
+        assert!(identity_keys.is_accepted(&first_public_key));
+
+        // Verify signatures are made with the current key
+        let (public_key, signature) = identity_keys.sign_with_current_key(b"message");
+        assert_eq!(public_key, second_public_key);
+        assert!(public_key.verify_xeddsa(b"message", &signature).is_ok());
+
+        // Verify the first key is removed once it expires
+        assert_eq!(identity_keys.remove_expired_keys(expiration_time), vec![
+            first_public_key
+        ]);
+        assert!(!identity_keys.is_accepted(&first_public_key));
+        assert!(identity_keys.is_accepted(&second_public_key));
+    }
+}
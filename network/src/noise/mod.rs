@@ -103,6 +103,7 @@
 
 pub mod error;
 pub mod handshake;
+pub mod identity_keys;
 pub mod stream;
 
 #[cfg(any(test, feature = "fuzzing"))]
@@ -110,3 +111,4 @@ pub mod fuzzing;
 
 pub use error::NoiseHandshakeError;
 pub use handshake::{AntiReplayTimestamps, HandshakeAuthMode, NoiseUpgrader};
+pub use identity_keys::IdentityKeys;
@@ -61,6 +61,11 @@ impl<TSocket> NoiseStream<TSocket> {
     pub fn get_remote_static(&self) -> x25519::PublicKey {
         self.session.get_remote_static()
     }
+
+    /// Pull out our static public key used for this stream
+    pub fn get_local_static(&self) -> x25519::PublicKey {
+        self.session.get_local_static()
+    }
 }
 
 //
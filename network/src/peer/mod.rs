@@ -31,11 +31,12 @@ use crate::{
             MultiplexMessageStream, NetworkMessage, Priority, ReadError, WriteError,
         },
     },
+    traffic_recorder::{TrafficDirection, TrafficRecorder},
     transport::{self, Connection, ConnectionMetadata},
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::OutboundQueueConfig, network_id::NetworkContext};
 use aptos_logger::prelude::*;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
@@ -44,6 +45,7 @@ use bytes::Bytes;
 use futures::{
     self,
     channel::oneshot,
+    future::{self, FutureExt},
     io::{AsyncRead, AsyncWrite},
     stream::StreamExt,
     SinkExt,
@@ -56,12 +58,19 @@ use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
 };
 
+mod outbound_queue;
 #[cfg(test)]
 mod test;
 
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
 
+pub use outbound_queue::OutboundMessage;
+use outbound_queue::OutboundQueue;
+
+/// The maximum number of scheduled messages waiting to be written to the wire
+const MAX_PENDING_MULTIPLEX_MESSAGES: usize = 16;
+
 /// Requests [`Peer`] receives from the [`PeerManager`](crate::peer_manager::PeerManager).
 #[derive(Debug)]
 pub enum PeerRequest {
@@ -136,6 +145,10 @@ pub struct Peer<TSocket> {
     max_frame_size: usize,
     /// The maximum size of an inbound or outbound request message
     max_message_size: usize,
+    /// The config of the outbound message queue (i.e., protocol priorities and quotas)
+    outbound_queue_config: OutboundQueueConfig,
+    /// The recorder of the network traffic (if recording is enabled)
+    traffic_recorder: Option<TrafficRecorder>,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
 }
@@ -158,6 +171,8 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        outbound_queue_config: OutboundQueueConfig,
+        traffic_recorder: Option<TrafficRecorder>,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
@@ -190,6 +205,8 @@ where
             state: State::Connected,
             max_frame_size,
             max_message_size,
+            outbound_queue_config,
+            traffic_recorder,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
         }
     }
@@ -218,7 +235,7 @@ where
 
         // Start writer "process" as a separate task. We receive two handles to
         // communicate with the task:
-        //   1. `write_reqs_tx`: Queue of pending NetworkMessages to write.
+        //   1. `write_reqs_tx`: Queue of pending OutboundMessages to write.
         //   2. `close_tx`: Handle to close the task and underlying connection.
         let (mut write_reqs_tx, writer_close_tx) = Self::start_writer_task(
             &self.executor,
@@ -228,6 +245,8 @@ where
             writer,
             self.max_frame_size,
             self.max_message_size,
+            self.outbound_queue_config.clone(),
+            self.traffic_recorder.clone(),
         );
 
         // Start main Peer event loop.
@@ -294,10 +313,11 @@ where
     // Start a new task on the given executor which is responsible for writing outbound messages on
     // the wire. The function returns two channels which can be used to send instructions to the
     // task:
-    // 1. The first channel is used to send outbound NetworkMessages to the task
+    // 1. The first channel is used to send outbound messages to the task
     // 2. The second channel is used to instruct the task to close the connection and terminate.
-    // If outbound messages are queued when the task receives a close instruction, it discards
-    // them and immediately closes the connection.
+    // Outbound messages are scheduled by the task according to the priorities and bandwidth
+    // quotas of their protocols (see `OutboundQueue`). If outbound messages are queued when the
+    // task receives a close instruction, it discards them and immediately closes the connection.
     fn start_writer_task(
         executor: &Handle,
         time_service: TimeService,
@@ -306,17 +326,25 @@ where
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
         max_frame_size: usize,
         max_message_size: usize,
-    ) -> (aptos_channels::Sender<NetworkMessage>, oneshot::Sender<()>) {
+        outbound_queue_config: OutboundQueueConfig,
+        traffic_recorder: Option<TrafficRecorder>,
+    ) -> (aptos_channels::Sender<OutboundMessage>, oneshot::Sender<()>) {
         let remote_peer_id = connection_metadata.remote_peer_id;
-        let (write_reqs_tx, mut write_reqs_rx): (aptos_channels::Sender<NetworkMessage>, _) =
+        let (write_reqs_tx, mut write_reqs_rx): (aptos_channels::Sender<OutboundMessage>, _) =
             aptos_channels::new(1024, &counters::PENDING_WIRE_MESSAGES);
         let (close_tx, mut close_rx) = oneshot::channel();
 
-        let (mut msg_tx, msg_rx) = aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_MESSAGE);
+        // Messages are only handed to the writer task once they are scheduled, so the
+        // channel is kept small to ensure the outbound queue determines the send order.
+        let (mut msg_tx, msg_rx) = aptos_channels::new(
+            MAX_PENDING_MULTIPLEX_MESSAGES,
+            &counters::PENDING_MULTIPLEX_MESSAGE,
+        );
         let (stream_msg_tx, stream_msg_rx) =
             aptos_channels::new(1024, &counters::PENDING_MULTIPLEX_STREAM);
 
         // this task ends when the multiplex task ends (by dropping the senders)
+        let writer_time_service = time_service.clone();
         let writer_task = async move {
             let mut stream = select(msg_rx, stream_msg_rx);
             let log_context =
@@ -343,7 +371,7 @@ where
                 writer.close().await?;
                 Ok(()) as Result<(), WriteError>
             };
-            match time_service
+            match writer_time_service
                 .timeout(transport::TRANSPORT_TIMEOUT, flush_and_close)
                 .await
             {
@@ -376,29 +404,83 @@ where
             }
         };
         let multiplex_task = async move {
+            let mut outbound_queue = OutboundQueue::new(outbound_queue_config);
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
             loop {
-                futures::select! {
-                    message = write_reqs_rx.select_next_some() => {
-                        // either channel full would block the other one
-                        let result = if outbound_stream.should_stream(&message) {
-                            outbound_stream.stream_message(message).await
-                        } else {
-                            msg_tx.send(MultiplexMessage::Message(message)).await.map_err(|_| anyhow::anyhow!("Writer task ended"))
-                        };
-                        if let Err(err) = result {
-                            warn!(
-                                error = %err,
-                                "{} Error in sending message to peer: {}",
-                                network_context,
-                                remote_peer_id.short_str(),
-                            );
-                        }
-                    },
-                    _ = close_rx => {
+                // Move all pending write requests into the outbound queue
+                while !outbound_queue.is_full() {
+                    match write_reqs_rx.next().now_or_never() {
+                        Some(Some(message)) => outbound_queue.push(message, time_service.now()),
+                        _ => break,
+                    }
+                }
+
+                // Write the next scheduled message (if any)
+                if let Some((message, queueing_delay)) = outbound_queue.pop(time_service.now()) {
+                    counters::outbound_queue_delay(&network_context, message.protocol_id)
+                        .observe(queueing_delay.as_secs_f64());
+                    if let Some(traffic_recorder) = &traffic_recorder {
+                        traffic_recorder.record(
+                            time_service.now_unix_time(),
+                            remote_peer_id,
+                            TrafficDirection::Outbound,
+                            message.protocol_id,
+                            &message.message,
+                        );
+                    }
+                    let message = message.message;
+
+                    // either channel full would block the other one
+                    let result = if outbound_stream.should_stream(&message) {
+                        outbound_stream.stream_message(message).await
+                    } else {
+                        msg_tx
+                            .send(MultiplexMessage::Message(message))
+                            .await
+                            .map_err(|_| anyhow::anyhow!("Writer task ended"))
+                    };
+                    if let Err(err) = result {
+                        warn!(
+                            error = %err,
+                            "{} Error in sending message to peer: {}",
+                            network_context,
+                            remote_peer_id.short_str(),
+                        );
+                    }
+
+                    // Stop writing if the connection is closing
+                    if !matches!(close_rx.try_recv(), Ok(None)) {
                         break;
                     }
+                    continue;
+                }
+
+                // Otherwise, wait for a new message, a quota refill or the close instruction
+                let mut quota_refill = match outbound_queue.time_until_sendable(time_service.now())
+                {
+                    Some(duration) => time_service.sleep(duration).boxed(),
+                    None => future::pending::<()>().boxed(),
+                }
+                .fuse();
+                if outbound_queue.is_full() {
+                    // Stop reading write requests (to back-pressure the senders)
+                    futures::select! {
+                        _ = quota_refill => {},
+                        _ = close_rx => {
+                            break;
+                        }
+                    }
+                } else {
+                    futures::select! {
+                        message = write_reqs_rx.select_next_some() => {
+                            outbound_queue.push(message, time_service.now());
+                        },
+                        _ = quota_refill => {},
+                        _ = close_rx => {
+                            break;
+                        }
+                    }
                 }
             }
         };
@@ -411,6 +493,7 @@ where
         &mut self,
         message: NetworkMessage,
     ) -> Result<(), PeerManagerError> {
+        self.record_inbound_message(&message);
         match message {
             NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message),
             NetworkMessage::Error(error_msg) => {
@@ -466,7 +549,7 @@ where
     async fn handle_inbound_message(
         &mut self,
         message: Result<MultiplexMessage, ReadError>,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        write_reqs_tx: &mut aptos_channels::Sender<OutboundMessage>,
     ) -> Result<(), PeerManagerError> {
         trace!(
             NetworkSchema::new(&self.network_context)
@@ -488,7 +571,9 @@ where
                     let error_code = ErrorCode::parsing_error(*message_type, *protocol_id);
                     let message = NetworkMessage::Error(error_code);
 
-                    write_reqs_tx.send(message).await?;
+                    write_reqs_tx
+                        .send(OutboundMessage::new(None, message))
+                        .await?;
                     return Err(err.into());
                 },
                 ReadError::IoError(_) => {
@@ -507,6 +592,27 @@ where
         }
     }
 
+    /// Records the given inbound message (if recording is enabled)
+    fn record_inbound_message(&self, message: &NetworkMessage) {
+        if let Some(traffic_recorder) = &self.traffic_recorder {
+            let protocol_id = match message {
+                NetworkMessage::Error(_) => None,
+                NetworkMessage::RpcRequest(request) => Some(request.protocol_id),
+                NetworkMessage::RpcResponse(response) => self
+                    .outbound_rpcs
+                    .pending_request_protocol_id(response.request_id),
+                NetworkMessage::DirectSendMsg(message) => Some(message.protocol_id),
+            };
+            traffic_recorder.record(
+                self.time_service.now_unix_time(),
+                self.remote_peer_id(),
+                TrafficDirection::Inbound,
+                protocol_id,
+                message,
+            );
+        }
+    }
+
     /// Handle an inbound DirectSendMsg from the remote peer. There's not much to
     /// do here other than bump some counters and forward the message up to the
     /// PeerManager.
@@ -547,7 +653,7 @@ where
     async fn handle_outbound_request(
         &mut self,
         request: PeerRequest,
-        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+        write_reqs_tx: &mut aptos_channels::Sender<OutboundMessage>,
     ) {
         trace!(
             "Peer {} PeerRequest::{:?}",
@@ -571,7 +677,10 @@ where
                     raw_msg: Vec::from(message.mdata.as_ref()),
                 });
 
-                match write_reqs_tx.send(message).await {
+                match write_reqs_tx
+                    .send(OutboundMessage::new(Some(protocol_id), message))
+                    .await
+                {
                     Ok(_) => {
                         counters::direct_send_messages(&self.network_context, SENT_LABEL).inc();
                         counters::direct_send_bytes(&self.network_context, SENT_LABEL)
@@ -0,0 +1,416 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The outbound message queue of a single peer.
+//!
+//! All protocols share the same connection to a peer. To prevent bursts of
+//! messages for one protocol (e.g., mempool broadcasts or state sync chunks)
+//! from delaying the messages of more important protocols (e.g., consensus
+//! votes), outbound messages are queued per protocol and scheduled using
+//! deficit round robin (i.e., weighted fair queueing):
+//!
+//! 1. Each protocol belongs to a priority class (see [`ProtocolId::priority_class`]).
+//!    In each scheduling round, the queue of each protocol can send up to a
+//!    quantum of bytes, proportional to the weight of its priority class.
+//! 2. Queues are always served in priority order, so higher priority messages
+//!    are sent first whenever their queue has not exhausted its quantum.
+//! 3. Protocols can optionally be limited to a bandwidth quota (in bytes per
+//!    second). Queues that exceed their quota are skipped until it refills.
+
+use crate::{protocols::wire::messaging::v1::NetworkMessage, ProtocolId};
+use aptos_config::config::{OutboundQueueConfig, ProtocolPriorityClass};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// The size (in bytes) assumed for messages without a payload (e.g., errors)
+const MIN_MESSAGE_SIZE: u64 = 64;
+
+/// A message to write to the wire, tagged with the protocol it belongs to
+#[derive(Debug)]
+pub struct OutboundMessage {
+    /// The protocol of the message (or None, if the message doesn't belong to
+    /// a protocol, e.g., error messages).
+    pub protocol_id: Option<ProtocolId>,
+    pub message: NetworkMessage,
+}
+
+impl OutboundMessage {
+    pub fn new(protocol_id: Option<ProtocolId>, message: NetworkMessage) -> Self {
+        Self {
+            protocol_id,
+            message,
+        }
+    }
+
+    /// Returns the size of the message payload (in bytes)
+    fn size(&self) -> u64 {
+        (self.message.data_len() as u64).max(MIN_MESSAGE_SIZE)
+    }
+}
+
+/// A token bucket limiting the bandwidth of a single protocol. The bucket holds
+/// at most one second of bandwidth, and a message is only sent once it fits in
+/// the available bytes. Messages larger than one second of bandwidth are sent
+/// when the bucket is full, putting the bucket into debt (which must be repaid
+/// before the next message is sent).
+#[derive(Debug)]
+struct BandwidthQuota {
+    bytes_per_sec: u64,
+    available_bytes: i64,
+    last_refill_time: Instant,
+}
+
+impl BandwidthQuota {
+    fn new(bytes_per_sec: u64, now: Instant) -> Self {
+        Self {
+            bytes_per_sec,
+            available_bytes: bytes_per_sec as i64,
+            last_refill_time: now,
+        }
+    }
+
+    /// Refills the bucket according to the time elapsed since the last refill
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill_time);
+        let refill_bytes = elapsed.as_nanos() * self.bytes_per_sec as u128 / 1_000_000_000;
+        if refill_bytes > 0 {
+            let available_bytes = self.available_bytes as i128 + refill_bytes as i128;
+            self.available_bytes = available_bytes.min(self.bytes_per_sec as i128) as i64;
+            self.last_refill_time = now;
+        }
+    }
+
+    /// Returns the number of available bytes required to send a message of the given size
+    fn required_bytes(&self, num_bytes: u64) -> i64 {
+        num_bytes.min(self.bytes_per_sec) as i64
+    }
+
+    /// Returns true iff a message of the given size can be sent
+    fn can_send(&self, num_bytes: u64) -> bool {
+        self.available_bytes >= self.required_bytes(num_bytes)
+    }
+
+    fn consume(&mut self, num_bytes: u64) {
+        self.available_bytes = self.available_bytes.saturating_sub(num_bytes as i64);
+    }
+
+    /// Returns the time until a message of the given size can be sent
+    fn time_until_available(&self, num_bytes: u64) -> Duration {
+        if self.can_send(num_bytes) {
+            return Duration::ZERO;
+        }
+        let missing_bytes =
+            (self.required_bytes(num_bytes) as i128 - self.available_bytes as i128) as u128;
+        let nanos = (missing_bytes * 1_000_000_000 + self.bytes_per_sec as u128 - 1)
+            / self.bytes_per_sec as u128;
+        Duration::from_nanos(nanos as u64)
+    }
+}
+
+/// The queued messages of a single protocol
+#[derive(Debug)]
+struct ProtocolQueue {
+    protocol_id: Option<ProtocolId>,
+    priority_class: ProtocolPriorityClass,
+    /// The queued messages and the times they were queued
+    messages: VecDeque<(OutboundMessage, Instant)>,
+    /// The number of bytes the queue can send in the current round
+    deficit: u64,
+    /// The number of bytes added to the deficit in each round
+    quantum: u64,
+    quota: Option<BandwidthQuota>,
+}
+
+impl ProtocolQueue {
+    /// Returns true iff the queue has messages and the next message fits in its quota
+    fn is_sendable(&self) -> bool {
+        match self.head_size() {
+            Some(head_size) => self
+                .quota
+                .as_ref()
+                .map_or(true, |quota| quota.can_send(head_size)),
+            None => false,
+        }
+    }
+
+    fn head_size(&self) -> Option<u64> {
+        self.messages.front().map(|(message, _)| message.size())
+    }
+}
+
+/// The outbound message queue of a single peer
+#[derive(Debug)]
+pub struct OutboundQueue {
+    /// The queues of each protocol, ordered by priority class
+    queues: Vec<ProtocolQueue>,
+    /// The index of the queue of each protocol
+    queue_indices: HashMap<Option<ProtocolId>, usize>,
+    config: OutboundQueueConfig,
+    num_queued_messages: usize,
+}
+
+impl OutboundQueue {
+    pub fn new(config: OutboundQueueConfig) -> Self {
+        Self {
+            queues: vec![],
+            queue_indices: HashMap::new(),
+            config,
+            num_queued_messages: 0,
+        }
+    }
+
+    /// Returns the number of queued messages
+    pub fn len(&self) -> usize {
+        self.num_queued_messages
+    }
+
+    /// Returns true iff there are no queued messages
+    pub fn is_empty(&self) -> bool {
+        self.num_queued_messages == 0
+    }
+
+    /// Returns true iff the maximum number of messages is queued
+    pub fn is_full(&self) -> bool {
+        self.num_queued_messages >= self.config.max_queued_messages
+    }
+
+    /// Queues the given message
+    pub fn push(&mut self, message: OutboundMessage, now: Instant) {
+        let queue_index = self.get_or_create_queue(message.protocol_id, now);
+        self.queues[queue_index].messages.push_back((message, now));
+        self.num_queued_messages += 1;
+    }
+
+    /// Removes and returns the next message to send (and the time it spent in
+    /// the queue). Returns None if all queues are empty or exceed their quota.
+    pub fn pop(&mut self, now: Instant) -> Option<(OutboundMessage, Duration)> {
+        self.refill_quotas(now);
+
+        loop {
+            // Send the head of the first (i.e., highest priority) queue that
+            // has enough deficit, and track the rounds required otherwise.
+            let mut num_rounds_required = None;
+            for queue in self.queues.iter_mut() {
+                if !queue.is_sendable() {
+                    if queue.messages.is_empty() {
+                        queue.deficit = 0;
+                    }
+                    continue;
+                }
+
+                let head_size = queue.head_size()?;
+                if queue.deficit >= head_size {
+                    let (message, queued_time) = queue.messages.pop_front()?;
+                    queue.deficit -= head_size;
+                    if queue.messages.is_empty() {
+                        queue.deficit = 0;
+                    }
+                    if let Some(quota) = queue.quota.as_mut() {
+                        quota.consume(head_size);
+                    }
+                    self.num_queued_messages -= 1;
+                    return Some((message, now.saturating_duration_since(queued_time)));
+                }
+
+                let num_rounds = (head_size - queue.deficit + queue.quantum - 1) / queue.quantum;
+                num_rounds_required = Some(
+                    num_rounds_required
+                        .map_or(num_rounds, |required: u64| required.min(num_rounds)),
+                );
+            }
+
+            // Start as many rounds as required for a queue to send its head
+            let num_rounds = num_rounds_required?;
+            for queue in self.queues.iter_mut().filter(|queue| queue.is_sendable()) {
+                queue.deficit += num_rounds * queue.quantum;
+            }
+        }
+    }
+
+    /// Returns the time until a queued message can be sent, i.e., until the
+    /// first non-empty queue is within its quota again. Returns None if all
+    /// queues are empty.
+    pub fn time_until_sendable(&mut self, now: Instant) -> Option<Duration> {
+        self.refill_quotas(now);
+        self.queues
+            .iter()
+            .filter_map(|queue| {
+                let head_size = queue.head_size()?;
+                Some(queue.quota.as_ref().map_or(Duration::ZERO, |quota| {
+                    quota.time_until_available(head_size)
+                }))
+            })
+            .min()
+    }
+
+    fn refill_quotas(&mut self, now: Instant) {
+        for quota in self
+            .queues
+            .iter_mut()
+            .filter_map(|queue| queue.quota.as_mut())
+        {
+            quota.refill(now);
+        }
+    }
+
+    /// Returns the index of the queue for the given protocol (creating the
+    /// queue if it doesn't exist yet).
+    fn get_or_create_queue(&mut self, protocol_id: Option<ProtocolId>, now: Instant) -> usize {
+        if let Some(queue_index) = self.queue_indices.get(&protocol_id) {
+            return *queue_index;
+        }
+
+        // Messages without a protocol (e.g., errors) are small and urgent
+        let priority_class = protocol_id
+            .map(ProtocolId::priority_class)
+            .unwrap_or(ProtocolPriorityClass::Consensus);
+        let quantum = self.config.quantum_bytes * self.config.weight(priority_class) as u64;
+        let quota = protocol_id
+            .and_then(|protocol_id| {
+                self.config
+                    .protocol_bandwidth_quotas
+                    .get(protocol_id.as_str())
+            })
+            .map(|bytes_per_sec| BandwidthQuota::new(*bytes_per_sec, now));
+        self.queues.push(ProtocolQueue {
+            protocol_id,
+            priority_class,
+            messages: VecDeque::new(),
+            deficit: 0,
+            quantum,
+            quota,
+        });
+
+        // Keep the queues ordered by priority class (and protocol, for determinism)
+        self.queues.sort_by_key(|queue| {
+            (
+                queue.priority_class,
+                queue.protocol_id.map(|protocol_id| protocol_id as u8),
+            )
+        });
+        self.queue_indices = self
+            .queues
+            .iter()
+            .enumerate()
+            .map(|(queue_index, queue)| (queue.protocol_id, queue_index))
+            .collect();
+        self.queue_indices[&protocol_id]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::protocols::wire::messaging::v1::{DirectSendMsg, ErrorCode};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_priority_order() {
+        // Queue mempool, state sync and consensus messages (in that order)
+        let mut outbound_queue = OutboundQueue::new(OutboundQueueConfig::default());
+        let now = Instant::now();
+        for protocol_id in [
+            ProtocolId::MempoolDirectSend,
+            ProtocolId::StateSyncDirectSend,
+            ProtocolId::ConsensusDirectSendBcs,
+        ] {
+            outbound_queue.push(create_message(protocol_id, 1024), now);
+        }
+        outbound_queue.push(
+            OutboundMessage::new(None, NetworkMessage::Error(ErrorCode::parsing_error(0, 0))),
+            now,
+        );
+
+        // Verify the messages are sent in priority order
+        let sent_protocols: Vec<_> = (0..4)
+            .map(|_| outbound_queue.pop(now).unwrap().0.protocol_id)
+            .collect();
+        assert_eq!(sent_protocols, vec![
+            None,
+            Some(ProtocolId::ConsensusDirectSendBcs),
+            Some(ProtocolId::StateSyncDirectSend),
+            Some(ProtocolId::MempoolDirectSend),
+        ]);
+        assert!(outbound_queue.is_empty());
+        assert!(outbound_queue.pop(now).is_none());
+    }
+
+    #[test]
+    fn test_weighted_fair_queueing() {
+        // Saturate the queue with consensus and mempool messages
+        let config = OutboundQueueConfig::default();
+        let message_size = config.quantum_bytes;
+        let mut outbound_queue = OutboundQueue::new(config.clone());
+        let now = Instant::now();
+        for _ in 0..100 {
+            outbound_queue.push(
+                create_message(ProtocolId::ConsensusDirectSendBcs, message_size),
+                now,
+            );
+            outbound_queue.push(
+                create_message(ProtocolId::MempoolDirectSend, message_size),
+                now,
+            );
+        }
+
+        // Verify the bandwidth is shared according to the class weights
+        let num_sent_messages = (config.consensus_weight + config.mempool_weight) as usize;
+        let num_consensus_messages = (0..num_sent_messages)
+            .filter(|_| {
+                outbound_queue.pop(now).unwrap().0.protocol_id
+                    == Some(ProtocolId::ConsensusDirectSendBcs)
+            })
+            .count();
+        assert_eq!(num_consensus_messages, config.consensus_weight as usize);
+
+        // Verify that mempool messages are not starved
+        assert_eq!(outbound_queue.len(), 200 - num_sent_messages);
+    }
+
+    #[test]
+    fn test_bandwidth_quota() {
+        // Create an outbound queue with a mempool quota of 10 KiB/s
+        let quota = 10 * 1024;
+        let config = OutboundQueueConfig {
+            protocol_bandwidth_quotas: BTreeMap::from([(
+                ProtocolId::MempoolDirectSend.as_str().to_string(),
+                quota,
+            )]),
+            ..Default::default()
+        };
+        let mut outbound_queue = OutboundQueue::new(config);
+
+        // Queue two mempool messages (each the size of the quota)
+        let now = Instant::now();
+        for _ in 0..2 {
+            outbound_queue.push(create_message(ProtocolId::MempoolDirectSend, quota), now);
+        }
+        outbound_queue.push(create_message(ProtocolId::StateSyncDirectSend, quota), now);
+
+        // Verify that only a single mempool message can be sent (but other
+        // protocols are not affected).
+        assert!(outbound_queue.pop(now).is_some());
+        assert!(outbound_queue.pop(now).is_some());
+        assert!(outbound_queue.pop(now).is_none());
+        assert_eq!(outbound_queue.len(), 1);
+
+        // Verify the quota refills over time
+        let time_until_sendable = outbound_queue.time_until_sendable(now).unwrap();
+        assert_eq!(time_until_sendable, Duration::from_secs(1));
+        let (_, queueing_delay) = outbound_queue.pop(now + time_until_sendable).unwrap();
+        assert_eq!(queueing_delay, time_until_sendable);
+        assert!(outbound_queue.time_until_sendable(now).is_none());
+    }
+
+    /// Creates a direct send message with a payload of the given size
+    fn create_message(protocol_id: ProtocolId, size: u64) -> OutboundMessage {
+        let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id,
+            priority: 0,
+            raw_msg: vec![0; size as usize],
+        });
+        OutboundMessage::new(Some(protocol_id), message)
+    }
+}
@@ -5,7 +5,7 @@
 use crate::{
     application::storage::PeersAndMetadata,
     counters,
-    noise::{stream::NoiseStream, HandshakeAuthMode},
+    noise::{stream::NoiseStream, HandshakeAuthMode, IdentityKeys},
     peer_manager::{
         conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
         PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
@@ -14,11 +14,15 @@ use crate::{
         network::{NetworkClientConfig, NetworkServiceConfig},
         wire::handshake::v1::ProtocolIdSet,
     },
+    traffic_recorder::TrafficRecorder,
     transport::{self, AptosNetTransport, Connection, APTOS_TCP_TRANSPORT},
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{OutboundQueueConfig, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
@@ -49,7 +53,7 @@ pub enum AuthenticationMode {
 struct TransportContext {
     chain_id: ChainId,
     supported_protocols: ProtocolIdSet,
-    authentication_mode: AuthenticationMode,
+    mutual_authentication: bool,
     peers_and_metadata: Arc<PeersAndMetadata>,
     enable_proxy_protocol: bool,
 }
@@ -79,6 +83,8 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    outbound_queue_config: OutboundQueueConfig,
+    traffic_recorder: Option<TrafficRecorder>,
 }
 
 impl PeerManagerContext {
@@ -102,6 +108,8 @@ impl PeerManagerContext {
         max_message_size: usize,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_queue_config: OutboundQueueConfig,
+        traffic_recorder: Option<TrafficRecorder>,
     ) -> Self {
         Self {
             pm_reqs_tx,
@@ -119,6 +127,8 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            outbound_queue_config,
+            traffic_recorder,
         }
     }
 
@@ -152,6 +162,7 @@ enum TransportPeerManager {
 pub struct PeerManagerBuilder {
     network_context: NetworkContext,
     time_service: TimeService,
+    identity_keys: Arc<IdentityKeys>,
     transport_context: Option<TransportContext>,
     peer_manager_context: Option<PeerManagerContext>,
     // TODO(philiphayes): better support multiple listening addrs
@@ -177,6 +188,8 @@ impl PeerManagerBuilder {
         enable_proxy_protocol: bool,
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
+        outbound_queue_config: OutboundQueueConfig,
+        traffic_recorder: Option<TrafficRecorder>,
     ) -> Self {
         // Setup channel to send requests to peer manager.
         let (pm_reqs_tx, pm_reqs_rx) = aptos_channel::new(
@@ -188,13 +201,20 @@ impl PeerManagerBuilder {
         let (connection_reqs_tx, connection_reqs_rx) =
             aptos_channel::new(QueueStyle::FIFO, channel_size, None);
 
+        // The identity keys are shared with the transport (so they can be rotated at runtime)
+        let (identity_key, mutual_authentication) = match authentication_mode {
+            AuthenticationMode::MaybeMutual(key) => (key, false),
+            AuthenticationMode::Mutual(key) => (key, true),
+        };
+
         Self {
             network_context,
             time_service,
+            identity_keys: Arc::new(IdentityKeys::new(identity_key)),
             transport_context: Some(TransportContext {
                 chain_id,
                 supported_protocols: ProtocolIdSet::empty(),
-                authentication_mode,
+                mutual_authentication,
                 peers_and_metadata: peers_and_metadata.clone(),
                 enable_proxy_protocol,
             }),
@@ -212,6 +232,8 @@ impl PeerManagerBuilder {
                 max_message_size,
                 inbound_connection_limit,
                 tcp_buffer_cfg,
+                outbound_queue_config,
+                traffic_recorder,
             )),
             peer_manager: None,
             listen_address,
@@ -222,6 +244,10 @@ impl PeerManagerBuilder {
         self.listen_address.clone()
     }
 
+    pub fn identity_keys(&self) -> Arc<IdentityKeys> {
+        self.identity_keys.clone()
+    }
+
     pub fn connection_reqs_tx(&self) -> aptos_channel::Sender<PeerId, ConnectionRequest> {
         self.peer_manager_context
             .as_ref()
@@ -256,15 +282,10 @@ impl PeerManagerBuilder {
         let chain_id = transport_context.chain_id;
         let enable_proxy_protocol = transport_context.enable_proxy_protocol;
 
-        let (key, auth_mode) = match transport_context.authentication_mode {
-            AuthenticationMode::MaybeMutual(key) => (
-                key,
-                HandshakeAuthMode::maybe_mutual(transport_context.peers_and_metadata),
-            ),
-            AuthenticationMode::Mutual(key) => (
-                key,
-                HandshakeAuthMode::mutual(transport_context.peers_and_metadata),
-            ),
+        let auth_mode = if transport_context.mutual_authentication {
+            HandshakeAuthMode::mutual(transport_context.peers_and_metadata)
+        } else {
+            HandshakeAuthMode::maybe_mutual(transport_context.peers_and_metadata)
         };
 
         let mut aptos_tcp_transport = APTOS_TCP_TRANSPORT.clone();
@@ -278,7 +299,7 @@ impl PeerManagerBuilder {
                         aptos_tcp_transport,
                         self.network_context,
                         self.time_service.clone(),
-                        key,
+                        self.identity_keys.clone(),
                         auth_mode,
                         HANDSHAKE_VERSION,
                         chain_id,
@@ -294,7 +315,7 @@ impl PeerManagerBuilder {
                     MemoryTransport,
                     self.network_context,
                     self.time_service.clone(),
-                    key,
+                    self.identity_keys.clone(),
                     auth_mode,
                     HANDSHAKE_VERSION,
                     chain_id,
@@ -346,6 +367,8 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.outbound_queue_config,
+            pm_context.traffic_recorder,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
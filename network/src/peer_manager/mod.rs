@@ -16,6 +16,7 @@ use crate::{
     counters::{self},
     logging::*,
     peer::{Peer, PeerNotification, PeerRequest},
+    traffic_recorder::TrafficRecorder,
     transport::{
         Connection, ConnectionId, ConnectionMetadata, TSocket as TransportTSocket,
         TRANSPORT_TIMEOUT,
@@ -23,7 +24,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::network_id::{NetworkContext, PeerNetworkId};
+use aptos_config::{
+    config::OutboundQueueConfig,
+    network_id::{NetworkContext, PeerNetworkId},
+};
 use aptos_logger::prelude::*;
 use aptos_netcore::transport::{ConnectionOrigin, Transport};
 use aptos_short_hex_str::AsShortHexStr;
@@ -119,6 +123,10 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// The config of the outbound message queue of each peer
+    outbound_queue_config: OutboundQueueConfig,
+    /// The recorder of the network traffic (if recording is enabled)
+    traffic_recorder: Option<TrafficRecorder>,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -147,6 +155,8 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        outbound_queue_config: OutboundQueueConfig,
+        traffic_recorder: Option<TrafficRecorder>,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -189,6 +199,8 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            outbound_queue_config,
+            traffic_recorder,
         }
     }
 
@@ -673,6 +685,8 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            self.outbound_queue_config.clone(),
+            self.traffic_recorder.clone(),
         );
         self.executor.spawn(peer.start());
 
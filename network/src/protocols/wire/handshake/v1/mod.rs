@@ -16,7 +16,10 @@
 use crate::counters::{start_serialization_timer, DESERIALIZATION_LABEL, SERIALIZATION_LABEL};
 use anyhow::anyhow;
 use aptos_compression::metrics::CompressionClient;
-use aptos_config::{config::MAX_APPLICATION_MESSAGE_SIZE, network_id::NetworkId};
+use aptos_config::{
+    config::{ProtocolPriorityClass, MAX_APPLICATION_MESSAGE_SIZE},
+    network_id::NetworkId,
+};
 use aptos_types::chain_id::ChainId;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
@@ -48,7 +51,7 @@ pub enum ProtocolId {
     ConsensusDirectSendBcs = 1,
     MempoolDirectSend = 2,
     StateSyncDirectSend = 3,
-    DiscoveryDirectSend = 4, // Currently unused
+    DiscoveryDirectSend = 4, // Used by peer exchange discovery
     HealthCheckerRpc = 5,
     ConsensusDirectSendJson = 6, // Json provides flexibility for backwards compatible upgrade
     ConsensusRpcJson = 7,
@@ -105,6 +108,36 @@ impl ProtocolId {
         ]
     }
 
+    /// Returns the protocol ID with the given name (if one exists)
+    pub fn from_name(name: &str) -> Option<ProtocolId> {
+        ProtocolId::all()
+            .iter()
+            .find(|protocol_id| protocol_id.as_str() == name)
+            .copied()
+    }
+
+    /// Returns the priority class of the protocol, used to schedule
+    /// outbound messages (see `peer::outbound_queue`).
+    pub fn priority_class(self) -> ProtocolPriorityClass {
+        match self {
+            ProtocolId::ConsensusRpcBcs
+            | ProtocolId::ConsensusDirectSendBcs
+            | ProtocolId::ConsensusDirectSendJson
+            | ProtocolId::ConsensusRpcJson
+            | ProtocolId::ConsensusRpcCompressed
+            | ProtocolId::ConsensusDirectSendCompressed => ProtocolPriorityClass::Consensus,
+            ProtocolId::StateSyncDirectSend | ProtocolId::StorageServiceRpc => {
+                ProtocolPriorityClass::StateSync
+            },
+            ProtocolId::MempoolDirectSend | ProtocolId::MempoolRpc => {
+                ProtocolPriorityClass::Mempool
+            },
+            ProtocolId::DiscoveryDirectSend
+            | ProtocolId::HealthCheckerRpc
+            | ProtocolId::PeerMonitoringServiceRpc => ProtocolPriorityClass::Other,
+        }
+    }
+
     /// Specifies how to encode messages for a given `ProtocolId`
     fn encoding(self) -> Encoding {
         match self {
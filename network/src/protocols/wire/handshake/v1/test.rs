@@ -183,3 +183,16 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn test_protocol_names() {
+    // Ensure the protocol names known to the config match the protocol IDs
+    let protocol_names: Vec<_> = ProtocolId::all()
+        .iter()
+        .map(|protocol_id| protocol_id.as_str())
+        .collect();
+    assert_eq!(protocol_names, aptos_config::config::PROTOCOL_NAMES);
+    for protocol_name in protocol_names {
+        assert!(ProtocolId::from_name(protocol_name).is_some());
+    }
+}
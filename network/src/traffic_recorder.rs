@@ -0,0 +1,441 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! An opt-in recorder of the inbound and outbound network traffic.
+//!
+//! When enabled (see [`TrafficRecorderConfig`]), every [`NetworkMessage`] read
+//! or written by a [`Peer`](crate::peer::Peer) is handed to the recorder, which
+//! writes it (along with the protocol, peer, timestamp and size) to a rotating
+//! set of local recording files. Records are written by a dedicated thread, and
+//! are dropped if the thread falls behind, so recording never blocks the network.
+//!
+//! Each recording file is a sequence of records, where each record is a BCS
+//! serialized [`TrafficRecord`] prefixed by its length (as a little endian u32).
+//! Recordings can be read back using [`read_recording_file`] and
+//! [`read_recording_dir`], and the payloads decoded using [`TrafficRecord::decode`]
+//! (or [`decode_inbound_messages`], to replay the inbound stream of a protocol).
+
+use crate::{counters, protocols::wire::messaging::v1::NetworkMessage, ProtocolId};
+use anyhow::{ensure, Context};
+use aptos_config::{
+    config::TrafficRecorderConfig,
+    network_id::{NetworkContext, NetworkId},
+};
+use aptos_logger::prelude::*;
+use aptos_types::PeerId;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, SyncSender, TrySendError},
+    thread,
+    time::Duration,
+};
+
+/// The file extension of recording files
+const RECORDING_FILE_EXTENSION: &str = "rec";
+
+/// The direction of a recorded message
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TrafficDirection {
+    Inbound,
+    Outbound,
+}
+
+/// A single recorded network message
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TrafficRecord {
+    /// The time the message was recorded (in microseconds since the unix epoch)
+    pub timestamp_usecs: u64,
+    pub network_id: NetworkId,
+    /// The remote peer the message was received from (or sent to)
+    pub peer_id: PeerId,
+    pub direction: TrafficDirection,
+    /// The protocol of the message (or None, if unknown, e.g., for errors)
+    pub protocol_id: Option<ProtocolId>,
+    /// The size of the message payload (in bytes)
+    pub size: u64,
+    pub message: NetworkMessage,
+}
+
+impl TrafficRecord {
+    /// Returns the payload of the message (or None, for error messages)
+    pub fn payload(&self) -> Option<&[u8]> {
+        match &self.message {
+            NetworkMessage::Error(_) => None,
+            NetworkMessage::RpcRequest(request) => Some(&request.raw_request),
+            NetworkMessage::RpcResponse(response) => Some(&response.raw_response),
+            NetworkMessage::DirectSendMsg(message) => Some(&message.raw_msg),
+        }
+    }
+
+    /// Decodes the payload of the message using the encoding of its protocol
+    /// (e.g., BCS or compressed BCS).
+    pub fn decode<T: DeserializeOwned>(&self) -> anyhow::Result<T> {
+        let protocol_id = self
+            .protocol_id
+            .context("The protocol of the record is unknown")?;
+        let payload = self
+            .payload()
+            .context("The record does not contain a payload")?;
+        protocol_id.from_bytes(payload)
+    }
+}
+
+/// Writes records to a rotating set of recording files in a directory
+pub struct TrafficRecordWriter {
+    recording_dir: PathBuf,
+    network_id: NetworkId,
+    max_file_size_bytes: u64,
+    max_num_files: usize,
+    current_file: Option<(BufWriter<File>, u64)>,
+    next_file_index: u64,
+}
+
+impl TrafficRecordWriter {
+    pub fn new(
+        recording_dir: PathBuf,
+        network_id: NetworkId,
+        max_file_size_bytes: u64,
+        max_num_files: usize,
+    ) -> anyhow::Result<Self> {
+        fs::create_dir_all(&recording_dir)?;
+
+        // Continue the file numbering of any existing recordings
+        let next_file_index = list_recording_files(&recording_dir, Some(network_id))?
+            .last()
+            .and_then(|path| parse_file_index(path))
+            .map_or(0, |file_index| file_index + 1);
+
+        Ok(Self {
+            recording_dir,
+            network_id,
+            max_file_size_bytes,
+            max_num_files,
+            current_file: None,
+            next_file_index,
+        })
+    }
+
+    /// Writes the given record, rotating the recording file if it is full
+    pub fn write(&mut self, record: &TrafficRecord) -> anyhow::Result<()> {
+        let bytes = bcs::to_bytes(record)?;
+        let record_size = (bytes.len() + 4) as u64;
+
+        // Rotate the recording file if the record doesn't fit
+        let file_is_full = self.current_file.as_ref().map_or(true, |(_, file_size)| {
+            *file_size > 0 && file_size + record_size > self.max_file_size_bytes
+        });
+        if file_is_full {
+            self.rotate()?;
+        }
+
+        let (file, file_size) = self
+            .current_file
+            .as_mut()
+            .context("The recording file is missing")?;
+        file.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        file.write_all(&bytes)?;
+        *file_size += record_size;
+        Ok(())
+    }
+
+    /// Flushes the current recording file
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        if let Some((file, _)) = self.current_file.as_mut() {
+            file.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Starts a new recording file and deletes the oldest files (if required)
+    fn rotate(&mut self) -> anyhow::Result<()> {
+        self.flush()?;
+
+        let file_name = format!(
+            "{}-{:010}.{}",
+            self.network_id.as_str(),
+            self.next_file_index,
+            RECORDING_FILE_EXTENSION
+        );
+        let file = File::create(self.recording_dir.join(file_name))?;
+        self.current_file = Some((BufWriter::new(file), 0));
+        self.next_file_index += 1;
+
+        let recording_files = list_recording_files(&self.recording_dir, Some(self.network_id))?;
+        let num_files_to_delete = recording_files.len().saturating_sub(self.max_num_files);
+        for path in recording_files.iter().take(num_files_to_delete) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A handle to record the traffic of a network. Records are written by a
+/// dedicated thread, which exits once all handles are dropped.
+#[derive(Clone)]
+pub struct TrafficRecorder {
+    network_context: NetworkContext,
+    record_tx: SyncSender<TrafficRecord>,
+}
+
+impl TrafficRecorder {
+    /// Starts a new recorder for the given network, if recording is enabled
+    pub fn start(
+        network_context: NetworkContext,
+        config: &TrafficRecorderConfig,
+    ) -> anyhow::Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let mut writer = TrafficRecordWriter::new(
+            config.recording_dir.clone(),
+            network_context.network_id(),
+            config.max_file_size_bytes,
+            config.max_num_files,
+        )?;
+        let (record_tx, record_rx) =
+            mpsc::sync_channel::<TrafficRecord>(config.max_pending_records);
+        thread::Builder::new()
+            .name(format!("traffic-rec-{}", network_context.network_id()))
+            .spawn(move || {
+                while let Ok(record) = record_rx.recv() {
+                    let mut result = writer.write(&record);
+
+                    // Write all pending records before flushing
+                    while result.is_ok() {
+                        match record_rx.try_recv() {
+                            Ok(record) => result = writer.write(&record),
+                            Err(_) => break,
+                        }
+                    }
+                    if let Err(error) = result.and_then(|_| writer.flush()) {
+                        error!(
+                            "{} Failed to write the network traffic recording! Error: {:?}",
+                            network_context, error
+                        );
+                    }
+                }
+            })?;
+
+        Ok(Some(Self {
+            network_context,
+            record_tx,
+        }))
+    }
+
+    /// Records the given message (or drops it, if too many records are pending)
+    pub fn record(
+        &self,
+        timestamp: Duration,
+        peer_id: PeerId,
+        direction: TrafficDirection,
+        protocol_id: Option<ProtocolId>,
+        message: &NetworkMessage,
+    ) {
+        let record = TrafficRecord {
+            timestamp_usecs: timestamp.as_micros() as u64,
+            network_id: self.network_context.network_id(),
+            peer_id,
+            direction,
+            protocol_id,
+            size: message.data_len() as u64,
+            message: message.clone(),
+        };
+        match self.record_tx.try_send(record) {
+            Ok(()) => counters::traffic_recorder_records(&self.network_context, "recorded").inc(),
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                counters::traffic_recorder_records(&self.network_context, "dropped").inc()
+            },
+        }
+    }
+}
+
+/// Reads all records in the given recording file (in recording order)
+pub fn read_recording_file(path: &Path) -> anyhow::Result<Vec<TrafficRecord>> {
+    let mut file = File::open(path)?;
+    let mut records = vec![];
+    loop {
+        let mut length_bytes = [0u8; 4];
+        match file.read_exact(&mut length_bytes) {
+            Ok(()) => (),
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(error.into()),
+        }
+
+        let mut record_bytes = vec![0u8; u32::from_le_bytes(length_bytes) as usize];
+        match file.read_exact(&mut record_bytes) {
+            Ok(()) => records.push(bcs::from_bytes(&record_bytes)?),
+            // The last record may be truncated (e.g., if the node crashed)
+            Err(error) if error.kind() == ErrorKind::UnexpectedEof => break,
+            Err(error) => return Err(error.into()),
+        }
+    }
+    Ok(records)
+}
+
+/// Reads all records in the given recording directory (in recording order).
+/// If a network ID is specified, only the recordings of the network are read.
+pub fn read_recording_dir(
+    recording_dir: &Path,
+    network_id: Option<NetworkId>,
+) -> anyhow::Result<Vec<TrafficRecord>> {
+    ensure!(
+        recording_dir.is_dir(),
+        "The recording directory does not exist: {:?}",
+        recording_dir
+    );
+
+    let mut records = vec![];
+    for path in list_recording_files(recording_dir, network_id)? {
+        records.extend(read_recording_file(&path)?);
+    }
+    Ok(records)
+}
+
+/// Decodes the inbound messages of the given protocols (in recording order),
+/// e.g., to replay a recorded inbound stream into a single component in a test
+/// harness. Returns the sender and the decoded message of each record.
+pub fn decode_inbound_messages<T: DeserializeOwned>(
+    records: &[TrafficRecord],
+    protocol_ids: &[ProtocolId],
+) -> anyhow::Result<Vec<(PeerId, T)>> {
+    records
+        .iter()
+        .filter(|record| {
+            record.direction == TrafficDirection::Inbound
+                && record
+                    .protocol_id
+                    .map_or(false, |protocol_id| protocol_ids.contains(&protocol_id))
+        })
+        .map(|record| Ok((record.peer_id, record.decode()?)))
+        .collect()
+}
+
+/// Returns the recording files in the given directory, ordered by network and
+/// file index (i.e., the oldest files first).
+fn list_recording_files(
+    recording_dir: &Path,
+    network_id: Option<NetworkId>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut recording_files = vec![];
+    for entry in fs::read_dir(recording_dir)? {
+        let path = entry?.path();
+        let is_recording = path.extension().and_then(|extension| extension.to_str())
+            == Some(RECORDING_FILE_EXTENSION);
+        let is_network_recording = network_id.map_or(true, |network_id| {
+            path.file_name()
+                .and_then(|file_name| file_name.to_str())
+                .map_or(false, |file_name| {
+                    file_name.starts_with(&format!("{}-", network_id.as_str()))
+                })
+        });
+        if is_recording && is_network_recording && parse_file_index(&path).is_some() {
+            recording_files.push(path);
+        }
+    }
+    recording_files.sort();
+    Ok(recording_files)
+}
+
+/// Returns the index of the given recording file
+fn parse_file_index(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.rsplit_once('-')?.1.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::wire::messaging::v1::{DirectSendMsg, ErrorCode};
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_write_and_read_recording() {
+        // Create a writer with small recording files
+        let recording_dir = TempPath::new();
+        let max_num_files = 3;
+        let mut writer = TrafficRecordWriter::new(
+            recording_dir.path().to_path_buf(),
+            NetworkId::Validator,
+            1024,
+            max_num_files,
+        )
+        .unwrap();
+
+        // Write enough records to rotate the files several times
+        let records: Vec<_> = (0..100).map(create_record).collect();
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+        writer.flush().unwrap();
+
+        // Verify that only the newest files are kept
+        let recording_files =
+            list_recording_files(recording_dir.path(), Some(NetworkId::Validator)).unwrap();
+        assert_eq!(recording_files.len(), max_num_files);
+
+        // Verify that the newest records are read back in order
+        let read_records = read_recording_dir(recording_dir.path(), None).unwrap();
+        assert!(!read_records.is_empty());
+        assert!(read_records.len() < records.len());
+        assert_eq!(
+            read_records,
+            records[records.len() - read_records.len()..].to_vec()
+        );
+
+        // Verify that a new writer continues the numbering of the files
+        let mut writer = TrafficRecordWriter::new(
+            recording_dir.path().to_path_buf(),
+            NetworkId::Validator,
+            1024,
+            max_num_files,
+        )
+        .unwrap();
+        writer.write(&create_record(100)).unwrap();
+        writer.flush().unwrap();
+        let read_records = read_recording_dir(recording_dir.path(), None).unwrap();
+        assert_eq!(read_records.last().unwrap(), &create_record(100));
+    }
+
+    #[test]
+    fn test_decode_record() {
+        // Create a record for a compressed consensus message
+        let protocol_id = ProtocolId::ConsensusDirectSendCompressed;
+        let payload = vec![1u64, 2, 3];
+        let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id,
+            priority: 0,
+            raw_msg: protocol_id.to_bytes(&payload).unwrap(),
+        });
+        let mut record = create_record(0);
+        record.protocol_id = Some(protocol_id);
+        record.message = message;
+
+        // Verify the payload is decoded
+        assert_eq!(record.decode::<Vec<u64>>().unwrap(), payload);
+
+        // Verify that error messages can't be decoded
+        record.message = NetworkMessage::Error(ErrorCode::parsing_error(0, 0));
+        assert!(record.decode::<Vec<u64>>().is_err());
+    }
+
+    /// Creates a test record with the given timestamp
+    fn create_record(timestamp_usecs: u64) -> TrafficRecord {
+        let protocol_id = ProtocolId::MempoolDirectSend;
+        TrafficRecord {
+            timestamp_usecs,
+            network_id: NetworkId::Validator,
+            peer_id: PeerId::ZERO,
+            direction: TrafficDirection::Inbound,
+            protocol_id: Some(protocol_id),
+            size: 100,
+            message: NetworkMessage::DirectSendMsg(DirectSendMsg {
+                protocol_id,
+                priority: 0,
+                raw_msg: vec![0; 100],
+            }),
+        }
+    }
+}
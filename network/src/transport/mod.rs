@@ -4,7 +4,9 @@
 
 use crate::{
     logging::NetworkSchema,
-    noise::{stream::NoiseStream, AntiReplayTimestamps, HandshakeAuthMode, NoiseUpgrader},
+    noise::{
+        stream::NoiseStream, AntiReplayTimestamps, HandshakeAuthMode, IdentityKeys, NoiseUpgrader,
+    },
     protocols::{
         identity::exchange_handshake,
         wire::handshake::v1::{HandshakeMsg, MessagingProtocolVersion, ProtocolIdSet},
@@ -106,6 +108,10 @@ pub struct ConnectionMetadata {
     pub messaging_protocol: MessagingProtocolVersion,
     pub application_protocols: ProtocolIdSet,
     pub role: PeerRole,
+    /// Our identity public key used for the connection (if known). This
+    /// allows connections to be drained after an identity key rotation.
+    #[serde(default)]
+    pub local_public_key: Option<x25519::PublicKey>,
 }
 
 impl ConnectionMetadata {
@@ -126,6 +132,7 @@ impl ConnectionMetadata {
             messaging_protocol,
             application_protocols,
             role,
+            local_public_key: None,
         }
     }
 
@@ -152,6 +159,7 @@ impl ConnectionMetadata {
             addr: NetworkAddress::mock(),
             messaging_protocol: MessagingProtocolVersion::V1,
             application_protocols: ProtocolIdSet::empty(),
+            local_public_key: None,
         }
     }
 }
@@ -317,18 +325,17 @@ async fn upgrade_inbound<T: TSocket>(
         })?;
 
     // return successful connection
-    Ok(Connection {
-        socket,
-        metadata: ConnectionMetadata::new(
-            remote_peer_id,
-            CONNECTION_ID_GENERATOR.next(),
-            addr,
-            origin,
-            messaging_protocol,
-            application_protocols,
-            peer_role,
-        ),
-    })
+    let mut metadata = ConnectionMetadata::new(
+        remote_peer_id,
+        CONNECTION_ID_GENERATOR.next(),
+        addr,
+        origin,
+        messaging_protocol,
+        application_protocols,
+        peer_role,
+    );
+    metadata.local_public_key = Some(socket.get_local_static());
+    Ok(Connection { socket, metadata })
 }
 
 /// Upgrade an outbound connection. This means we run a Noise IK handshake for
@@ -392,18 +399,17 @@ pub async fn upgrade_outbound<T: TSocket>(
         })?;
 
     // return successful connection
-    Ok(Connection {
-        socket,
-        metadata: ConnectionMetadata::new(
-            remote_peer_id,
-            CONNECTION_ID_GENERATOR.next(),
-            addr,
-            origin,
-            messaging_protocol,
-            application_protocols,
-            peer_role,
-        ),
-    })
+    let mut metadata = ConnectionMetadata::new(
+        remote_peer_id,
+        CONNECTION_ID_GENERATOR.next(),
+        addr,
+        origin,
+        messaging_protocol,
+        application_protocols,
+        peer_role,
+    );
+    metadata.local_public_key = Some(socket.get_local_static());
+    Ok(Connection { socket, metadata })
 }
 
 /// The common AptosNet Transport.
@@ -422,7 +428,7 @@ pub struct AptosNetTransport<TTransport> {
     base_transport: TTransport,
     ctxt: Arc<UpgradeContext>,
     time_service: TimeService,
-    identity_pubkey: x25519::PublicKey,
+    identity_keys: Arc<IdentityKeys>,
     enable_proxy_protocol: bool,
 }
 
@@ -438,7 +444,7 @@ where
         base_transport: TTransport,
         network_context: NetworkContext,
         time_service: TimeService,
-        identity_key: x25519::PrivateKey,
+        identity_keys: Arc<IdentityKeys>,
         auth_mode: HandshakeAuthMode,
         handshake_version: u8,
         chain_id: ChainId,
@@ -449,10 +455,12 @@ where
         let mut supported_protocols = BTreeMap::new();
         supported_protocols.insert(SUPPORTED_MESSAGING_PROTOCOL, application_protocols);
 
-        let identity_pubkey = identity_key.public_key();
-
         let upgrade_context = UpgradeContext::new(
-            NoiseUpgrader::new(network_context, identity_key, auth_mode),
+            NoiseUpgrader::new_with_identity_keys(
+                network_context,
+                identity_keys.clone(),
+                auth_mode,
+            ),
             handshake_version,
             supported_protocols,
             chain_id,
@@ -463,7 +471,7 @@ where
             base_transport,
             ctxt: Arc::new(upgrade_context),
             time_service,
-            identity_pubkey,
+            identity_keys,
             enable_proxy_protocol,
         }
     }
@@ -602,8 +610,10 @@ where
         // (e.g., `/memory/<port>` with no trailers), so we don't need to do any
         // parsing here.
         let (listener, listen_addr) = self.base_transport.listen_on(addr)?;
-        let listen_addr =
-            listen_addr.append_prod_protos(self.identity_pubkey, self.ctxt.handshake_version);
+        let listen_addr = listen_addr.append_prod_protos(
+            self.identity_keys.current_public_key(),
+            self.ctxt.handshake_version,
+        );
 
         // need to move a ctxt into stream task
         let ctxt = self.ctxt.clone();
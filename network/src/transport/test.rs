@@ -168,7 +168,7 @@ where
         base_transport.clone(),
         listener_network_context,
         time_service.clone(),
-        listener_key,
+        Arc::new(IdentityKeys::new(listener_key)),
         listener_auth_mode,
         HANDSHAKE_VERSION,
         chain_id,
@@ -180,7 +180,7 @@ where
         base_transport,
         dialer_network_context,
         time_service.clone(),
-        dialer_key,
+        Arc::new(IdentityKeys::new(dialer_key)),
         dialer_auth_mode,
         HANDSHAKE_VERSION,
         chain_id,
@@ -0,0 +1,257 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A tool for inspecting the network traffic recordings written by the
+//! traffic recorder (see `aptos_network::traffic_recorder`). The payload of
+//! each record is decoded according to its protocol (e.g., consensus messages
+//! are decoded using compressed BCS or BCS) and printed in human-readable form.
+
+use anyhow::{bail, Result};
+use aptos_config::network_id::NetworkId;
+use aptos_consensus::network_interface::ConsensusMsg;
+use aptos_mempool::MempoolSyncMsg;
+use aptos_network::{
+    protocols::health_checker::HealthCheckerMsg,
+    traffic_recorder::{read_recording_dir, read_recording_file, TrafficDirection, TrafficRecord},
+    ProtocolId,
+};
+use aptos_network_discovery::peer_exchange::PeerExchangeMsg;
+use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_storage_service_types::StorageServiceMessage;
+use aptos_types::PeerId;
+use clap::{Parser, ValueEnum};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// Inspect network traffic recordings
+#[derive(Parser)]
+#[clap(
+    name = "aptos-network-traffic-tool",
+    about = "Inspect network traffic recordings"
+)]
+pub enum TrafficTool {
+    /// Decode and print each recorded message
+    Decode(DecodeArgs),
+    /// Print the number of messages and bytes recorded for each protocol
+    Summary(RecordingArgs),
+}
+
+impl TrafficTool {
+    pub fn run(self) -> Result<()> {
+        match self {
+            TrafficTool::Decode(args) => {
+                for record in args.recording.read_records()? {
+                    println!("{}", format_record(&record, args.verbose));
+                }
+            },
+            TrafficTool::Summary(args) => {
+                let mut summary: BTreeMap<(String, String), (u64, u64)> = BTreeMap::new();
+                for record in args.read_records()? {
+                    let (num_messages, num_bytes) = summary
+                        .entry((protocol_name(&record), format!("{:?}", record.direction)))
+                        .or_default();
+                    *num_messages += 1;
+                    *num_bytes += record.size;
+                }
+                for ((protocol, direction), (num_messages, num_bytes)) in summary {
+                    println!(
+                        "{} {}: {} messages, {} bytes",
+                        protocol, direction, num_messages, num_bytes
+                    );
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+/// The recording to read, and the filters to apply to its records
+#[derive(Parser)]
+pub struct RecordingArgs {
+    /// The recording file, or the directory containing the recording files
+    #[clap(long, value_parser)]
+    pub recording_path: PathBuf,
+    /// Only read the records of the given network (e.g., validator, vfn or public)
+    #[clap(long)]
+    pub network_id: Option<NetworkId>,
+    /// Only read the records of the given peer
+    #[clap(long)]
+    pub peer_id: Option<PeerId>,
+    /// Only read the records of the given protocol (e.g., ConsensusRpcBcs)
+    #[clap(long)]
+    pub protocol: Option<String>,
+    /// Only read the records in the given direction
+    #[clap(long, value_enum)]
+    pub direction: Option<Direction>,
+}
+
+impl RecordingArgs {
+    /// Reads the records of the recording that match the filters (in recording order)
+    pub fn read_records(&self) -> Result<Vec<TrafficRecord>> {
+        let protocol_id = match &self.protocol {
+            Some(protocol_name) => match ProtocolId::from_name(protocol_name) {
+                Some(protocol_id) => Some(protocol_id),
+                None => bail!("Unknown protocol: {}", protocol_name),
+            },
+            None => None,
+        };
+
+        let records = if self.recording_path.is_dir() {
+            read_recording_dir(&self.recording_path, self.network_id)?
+        } else {
+            read_recording_file(&self.recording_path)?
+        };
+        Ok(records
+            .into_iter()
+            .filter(|record| {
+                self.network_id
+                    .map_or(true, |network_id| record.network_id == network_id)
+                    && self
+                        .peer_id
+                        .map_or(true, |peer_id| record.peer_id == peer_id)
+                    && protocol_id
+                        .map_or(true, |protocol_id| record.protocol_id == Some(protocol_id))
+                    && self.direction.map_or(true, |direction| {
+                        let record_direction = match record.direction {
+                            TrafficDirection::Inbound => Direction::Inbound,
+                            TrafficDirection::Outbound => Direction::Outbound,
+                        };
+                        record_direction == direction
+                    })
+            })
+            .collect())
+    }
+}
+
+#[derive(Parser)]
+pub struct DecodeArgs {
+    #[clap(flatten)]
+    pub recording: RecordingArgs,
+    /// Pretty print the decoded messages (across multiple lines)
+    #[clap(long)]
+    pub verbose: bool,
+}
+
+/// Formats the given record (and its decoded payload) in human-readable form
+pub fn format_record(record: &TrafficRecord, verbose: bool) -> String {
+    let payload = match decode_payload(record, verbose) {
+        Ok(payload) => payload,
+        Err(error) => format!(
+            "<undecodable: {}> 0x{}",
+            error,
+            hex::encode(record.payload().unwrap_or_default())
+        ),
+    };
+    format!(
+        "[{}] {} {:?} peer={} protocol={} size={} {}",
+        record.timestamp_usecs,
+        record.network_id,
+        record.direction,
+        record.peer_id.short_str(),
+        protocol_name(record),
+        record.size,
+        payload
+    )
+}
+
+/// Decodes the payload of the given record according to its protocol
+pub fn decode_payload(record: &TrafficRecord, verbose: bool) -> Result<String> {
+    let protocol_id = match record.protocol_id {
+        Some(protocol_id) => protocol_id,
+        None => return Ok(format!("{:?}", record.message)),
+    };
+    match protocol_id {
+        ProtocolId::ConsensusRpcBcs
+        | ProtocolId::ConsensusDirectSendBcs
+        | ProtocolId::ConsensusDirectSendJson
+        | ProtocolId::ConsensusRpcJson
+        | ProtocolId::ConsensusRpcCompressed
+        | ProtocolId::ConsensusDirectSendCompressed => {
+            format_message(&record.decode::<ConsensusMsg>()?, verbose)
+        },
+        ProtocolId::MempoolDirectSend | ProtocolId::MempoolRpc => {
+            format_message(&record.decode::<MempoolSyncMsg>()?, verbose)
+        },
+        ProtocolId::StorageServiceRpc => {
+            format_message(&record.decode::<StorageServiceMessage>()?, verbose)
+        },
+        ProtocolId::PeerMonitoringServiceRpc => {
+            format_message(&record.decode::<PeerMonitoringServiceMessage>()?, verbose)
+        },
+        ProtocolId::HealthCheckerRpc => {
+            format_message(&record.decode::<HealthCheckerMsg>()?, verbose)
+        },
+        ProtocolId::DiscoveryDirectSend => {
+            format_message(&record.decode::<PeerExchangeMsg>()?, verbose)
+        },
+        ProtocolId::StateSyncDirectSend => {
+            bail!("The message type of {} is unknown", protocol_id.as_str())
+        },
+    }
+}
+
+fn format_message<T: std::fmt::Debug>(message: &T, verbose: bool) -> Result<String> {
+    Ok(if verbose {
+        format!("{:#?}", message)
+    } else {
+        format!("{:?}", message)
+    })
+}
+
+fn protocol_name(record: &TrafficRecord) -> String {
+    record.protocol_id.map_or_else(
+        || "Unknown".into(),
+        |protocol_id| protocol_id.as_str().into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_network::protocols::wire::messaging::v1::{DirectSendMsg, NetworkMessage};
+    use aptos_network_discovery::peer_exchange::PeerSample;
+
+    #[test]
+    fn test_decode_payload() {
+        // Create a record for a peer exchange message
+        let protocol_id = ProtocolId::DiscoveryDirectSend;
+        let message = PeerExchangeMsg::PeerSample(PeerSample {
+            peers: vec![(PeerId::ONE, vec![])],
+        });
+        let raw_msg = protocol_id.to_bytes(&message).unwrap();
+        let mut record = TrafficRecord {
+            timestamp_usecs: 0,
+            network_id: NetworkId::Public,
+            peer_id: PeerId::ZERO,
+            direction: TrafficDirection::Inbound,
+            protocol_id: Some(protocol_id),
+            size: raw_msg.len() as u64,
+            message: NetworkMessage::DirectSendMsg(DirectSendMsg {
+                protocol_id,
+                priority: 0,
+                raw_msg,
+            }),
+        };
+
+        // Verify the payload is decoded
+        assert_eq!(
+            decode_payload(&record, false).unwrap(),
+            format!("{:?}", message)
+        );
+
+        // Verify that undecodable payloads are printed as hex
+        record.message = NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id,
+            priority: 0,
+            raw_msg: vec![0xFF],
+        });
+        assert!(decode_payload(&record, false).is_err());
+        assert!(format_record(&record, false).contains("0xff"));
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_network_traffic_tool::TrafficTool;
+use clap::Parser;
+
+fn main() -> Result<()> {
+    TrafficTool::parse().run()
+}
@@ -0,0 +1,373 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bcs,
+    move_types::{
+        identifier::Identifier,
+        language_storage::{ModuleId, StructTag, TypeTag},
+    },
+    rest_client::{
+        aptos_api_types::{AptosErrorCode, MoveType, ViewRequest},
+        error::RestError,
+        Client as ApiClient, PendingTransaction,
+    },
+    transaction_builder::TransactionBuilder,
+    types::{
+        account_address::{create_derived_object_address, AccountAddress},
+        chain_id::ChainId,
+        transaction::{EntryFunction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use anyhow::{bail, Context, Result};
+use serde::de::DeserializeOwned;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A client for fungible assets, i.e., assets defined by a `0x1::fungible_asset::Metadata`
+/// object and held in `0x1::fungible_asset::FungibleStore` objects. Every account has a
+/// primary store per asset (see `0x1::primary_fungible_store`), and may own any number of
+/// secondary stores.
+#[derive(Clone, Debug)]
+pub struct FungibleAssetClient<'a> {
+    api_client: &'a ApiClient,
+}
+
+impl<'a> FungibleAssetClient<'a> {
+    pub fn new(api_client: &'a ApiClient) -> Self {
+        Self { api_client }
+    }
+
+    /// Reads the metadata object at the given address.
+    pub async fn get_metadata(&self, metadata: AccountAddress) -> Result<FungibleAssetMetadata> {
+        let metadata_type = metadata_struct_tag();
+        Ok(FungibleAssetMetadata {
+            address: metadata,
+            name: self
+                .view("fungible_asset", "name", &metadata_type, metadata)
+                .await?,
+            symbol: self
+                .view("fungible_asset", "symbol", &metadata_type, metadata)
+                .await?,
+            decimals: self
+                .view("fungible_asset", "decimals", &metadata_type, metadata)
+                .await?,
+            supply: self
+                .view("fungible_asset", "supply", &metadata_type, metadata)
+                .await?,
+            maximum: self
+                .view("fungible_asset", "maximum", &metadata_type, metadata)
+                .await?,
+        })
+    }
+
+    /// Returns the balance of the primary store of `owner` (zero if the store doesn't exist).
+    pub async fn get_primary_balance(
+        &self,
+        owner: AccountAddress,
+        metadata: AccountAddress,
+    ) -> Result<u64> {
+        self.view_primary_store("balance", owner, metadata).await
+    }
+
+    /// Returns whether the primary store of `owner` is frozen (false if the store doesn't exist).
+    pub async fn is_primary_store_frozen(
+        &self,
+        owner: AccountAddress,
+        metadata: AccountAddress,
+    ) -> Result<bool> {
+        self.view_primary_store("is_frozen", owner, metadata).await
+    }
+
+    /// Returns the balance of the given (primary or secondary) store.
+    pub async fn get_store_balance(&self, store: AccountAddress) -> Result<u64> {
+        self.view("fungible_asset", "balance", &store_struct_tag(), store)
+            .await
+    }
+
+    /// Returns whether the given (primary or secondary) store is frozen.
+    pub async fn is_store_frozen(&self, store: AccountAddress) -> Result<bool> {
+        self.view("fungible_asset", "is_frozen", &store_struct_tag(), store)
+            .await
+    }
+
+    /// Returns the address of the metadata object of the assets held in the given store.
+    pub async fn get_store_metadata(&self, store: AccountAddress) -> Result<AccountAddress> {
+        self.view(
+            "fungible_asset",
+            "store_metadata",
+            &store_struct_tag(),
+            store,
+        )
+        .await
+    }
+
+    /// Returns the balances of an asset that is available both as a coin (of `coin_type`) and as
+    /// a fungible asset (of `metadata`), e.g., while the asset is being migrated.
+    pub async fn get_paired_balance(
+        &self,
+        owner: AccountAddress,
+        coin_type: &str,
+        metadata: AccountAddress,
+    ) -> Result<PairedBalance> {
+        let coin = match self
+            .api_client
+            .get_account_balance_bcs(owner, coin_type)
+            .await
+        {
+            Ok(response) => response.into_inner(),
+            // The account may not have a coin store for the asset
+            Err(RestError::Api(api_error))
+                if matches!(
+                    api_error.error.error_code,
+                    AptosErrorCode::AccountNotFound | AptosErrorCode::ResourceNotFound
+                ) =>
+            {
+                0
+            },
+            Err(error) => return Err(error).context("Failed to get coin balance"),
+        };
+        let fungible_asset = self.get_primary_balance(owner, metadata).await?;
+        Ok(PairedBalance {
+            coin,
+            fungible_asset,
+        })
+    }
+
+    /// Transfers `amount` of the asset from the primary store of `from_account` to the primary
+    /// store of `to_account` (which is created if it doesn't exist).
+    pub async fn transfer(
+        &self,
+        from_account: &mut LocalAccount,
+        metadata: AccountAddress,
+        to_account: AccountAddress,
+        amount: u64,
+        options: Option<TransferOptions>,
+    ) -> Result<PendingTransaction> {
+        self.submit(
+            from_account,
+            Self::primary_store_transfer_payload(metadata, to_account, amount),
+            options,
+        )
+        .await
+        .context("Failed to submit transfer transaction")
+    }
+
+    /// Transfers `amount` of the asset between two stores. The `from_store` must be owned by
+    /// `from_account`, and the stores must hold the same asset.
+    pub async fn transfer_between_stores(
+        &self,
+        from_account: &mut LocalAccount,
+        from_store: AccountAddress,
+        to_store: AccountAddress,
+        amount: u64,
+        options: Option<TransferOptions>,
+    ) -> Result<PendingTransaction> {
+        self.submit(
+            from_account,
+            Self::store_transfer_payload(from_store, to_store, amount),
+            options,
+        )
+        .await
+        .context("Failed to submit store transfer transaction")
+    }
+
+    /// Returns the address of the primary store of `owner`, whether it exists or not.
+    pub fn primary_store_address(
+        owner: AccountAddress,
+        metadata: AccountAddress,
+    ) -> AccountAddress {
+        create_derived_object_address(owner, metadata)
+    }
+
+    /// Returns the payload of `0x1::primary_fungible_store::transfer`.
+    pub fn primary_store_transfer_payload(
+        metadata: AccountAddress,
+        to_account: AccountAddress,
+        amount: u64,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            framework_module_id("primary_fungible_store"),
+            Identifier::new("transfer").unwrap(),
+            vec![TypeTag::Struct(Box::new(metadata_struct_tag()))],
+            vec![
+                bcs::to_bytes(&metadata).unwrap(),
+                bcs::to_bytes(&to_account).unwrap(),
+                bcs::to_bytes(&amount).unwrap(),
+            ],
+        ))
+    }
+
+    /// Returns the payload of `0x1::fungible_asset::transfer`.
+    pub fn store_transfer_payload(
+        from_store: AccountAddress,
+        to_store: AccountAddress,
+        amount: u64,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            framework_module_id("fungible_asset"),
+            Identifier::new("transfer").unwrap(),
+            vec![TypeTag::Struct(Box::new(store_struct_tag()))],
+            vec![
+                bcs::to_bytes(&from_store).unwrap(),
+                bcs::to_bytes(&to_store).unwrap(),
+                bcs::to_bytes(&amount).unwrap(),
+            ],
+        ))
+    }
+
+    async fn submit(
+        &self,
+        from_account: &mut LocalAccount,
+        payload: TransactionPayload,
+        options: Option<TransferOptions>,
+    ) -> Result<PendingTransaction> {
+        let options = options.unwrap_or_default();
+
+        let chain_id = self
+            .api_client
+            .get_index()
+            .await
+            .context("Failed to get chain ID")?
+            .inner()
+            .chain_id;
+        let transaction_builder = TransactionBuilder::new(
+            payload,
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + options.timeout_secs,
+            ChainId::new(chain_id),
+        )
+        .sender(from_account.address())
+        .sequence_number(from_account.sequence_number())
+        .max_gas_amount(options.max_gas_amount)
+        .gas_unit_price(options.gas_unit_price);
+        let signed_txn = from_account.sign_with_transaction_builder(transaction_builder);
+        Ok(self.api_client.submit(&signed_txn).await?.into_inner())
+    }
+
+    async fn view_primary_store<T: DeserializeOwned>(
+        &self,
+        function: &str,
+        owner: AccountAddress,
+        metadata: AccountAddress,
+    ) -> Result<T> {
+        let request = ViewRequest {
+            function: format!("0x1::primary_fungible_store::{}", function).parse()?,
+            type_arguments: vec![MoveType::Struct(metadata_struct_tag().into())],
+            arguments: vec![
+                owner.to_hex_literal().into(),
+                metadata.to_hex_literal().into(),
+            ],
+        };
+        self.view_request(request).await
+    }
+
+    /// Calls a view function that takes an object of type `object_type` as its only argument.
+    async fn view<T: DeserializeOwned>(
+        &self,
+        module: &str,
+        function: &str,
+        object_type: &StructTag,
+        object: AccountAddress,
+    ) -> Result<T> {
+        let request = ViewRequest {
+            function: format!("0x1::{}::{}", module, function).parse()?,
+            type_arguments: vec![MoveType::Struct(object_type.into())],
+            arguments: vec![object.to_hex_literal().into()],
+        };
+        self.view_request(request).await
+    }
+
+    async fn view_request<T: DeserializeOwned>(&self, request: ViewRequest) -> Result<T> {
+        let function = request.function.to_string();
+        let values = self
+            .api_client
+            .view_bcs(&request, None)
+            .await
+            .with_context(|| format!("Failed to call view function {}", function))?
+            .into_inner();
+        if values.len() != 1 {
+            bail!(
+                "Expected a single return value from {}, but got {}",
+                function,
+                values.len()
+            );
+        }
+        bcs::from_bytes(&values[0])
+            .with_context(|| format!("Failed to deserialize the return value of {}", function))
+    }
+}
+
+fn framework_module_id(module: &str) -> ModuleId {
+    ModuleId::new(AccountAddress::ONE, Identifier::new(module).unwrap())
+}
+
+fn framework_struct_tag(module: &str, name: &str) -> StructTag {
+    StructTag {
+        address: AccountAddress::ONE,
+        module: Identifier::new(module).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn metadata_struct_tag() -> StructTag {
+    framework_struct_tag("fungible_asset", "Metadata")
+}
+
+fn store_struct_tag() -> StructTag {
+    framework_struct_tag("fungible_asset", "FungibleStore")
+}
+
+/// The metadata of a fungible asset
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FungibleAssetMetadata {
+    /// The address of the metadata object
+    pub address: AccountAddress,
+    pub name: String,
+    pub symbol: String,
+    /// The number of decimals used for display purposes
+    pub decimals: u8,
+    /// The current supply, or `None` if the supply isn't tracked
+    pub supply: Option<u128>,
+    /// The maximum supply, or `None` if the supply is unlimited
+    pub maximum: Option<u128>,
+}
+
+/// The balances of an asset that is available both as a coin and as a fungible asset
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PairedBalance {
+    /// The balance in the `0x1::coin::CoinStore` of the account
+    pub coin: u64,
+    /// The balance in the primary fungible store of the account
+    pub fungible_asset: u64,
+}
+
+impl PairedBalance {
+    pub fn total(&self) -> u128 {
+        self.coin as u128 + self.fungible_asset as u128
+    }
+}
+
+pub struct TransferOptions {
+    pub max_gas_amount: u64,
+
+    pub gas_unit_price: u64,
+
+    /// This is the number of seconds from now you're willing to wait for the
+    /// transaction to be committed.
+    pub timeout_secs: u64,
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        Self {
+            max_gas_amount: 5_000,
+            gas_unit_price: 100,
+            timeout_secs: 10,
+        }
+    }
+}
@@ -9,9 +9,11 @@
 //! This SDK provides all the necessary components for building on top of the Aptos Blockchain. Some of the important modules are:
 //!
 //! * `crypto` - Types used for signing and verifying
+//! * `fungible_asset_client` - Helpers for balances and transfers of fungible assets
 //! * `move_types` - Includes types used when interacting with the Move VM
 //! * `rest_client` - The Aptos API Client, used for sending requests to the Aptos Blockchain.
 //! * `transaction_builder` - Includes helpers for constructing transactions
+//! * `transaction_manager` - Manages sequence numbers when submitting many transactions concurrently
 //! * `types` - Includes types for Aptos on-chain data structures
 //!
 //! ## Example
@@ -24,6 +26,8 @@ pub use bcs;
 
 pub mod coin_client;
 
+pub mod fungible_asset_client;
+
 pub mod crypto {
     pub use aptos_crypto::*;
 }
@@ -38,4 +42,6 @@ pub mod rest_client {
 
 pub mod transaction_builder;
 
+pub mod transaction_manager;
+
 pub mod types;
@@ -0,0 +1,349 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The transaction manager allows many transactions to be submitted concurrently
+//! from a single account.
+//!
+//! The manager hands out sequence numbers and tracks the transactions that are in
+//! flight. If a transaction is rejected by mempool or expires, all the transactions
+//! with higher sequence numbers are stuck. On resync, the manager fetches the on-chain
+//! sequence number, resubmits the stuck transactions (with a fresh expiration) and,
+//! if a transaction is rejected again, fills the gap with a no-op transaction.
+
+use crate::{
+    rest_client::{aptos_api_types::AptosErrorCode, error::RestError, Client as ApiClient, State},
+    transaction_builder::{aptos_stdlib, TransactionBuilder},
+    types::{
+        account_address::AccountAddress,
+        transaction::{SignedTransaction, TransactionPayload},
+        LocalAccount,
+    },
+};
+use anyhow::{Context, Result};
+use aptos_infallible::Mutex;
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub struct TransactionManagerConfig {
+    /// This is the number of seconds from now that resubmitted (and gap filling)
+    /// transactions are valid for.
+    pub resubmission_timeout_secs: u64,
+
+    /// Whether to fill the gap with a no-op transaction when a stuck transaction
+    /// is rejected again on resubmission.
+    pub fill_gaps: bool,
+}
+
+impl Default for TransactionManagerConfig {
+    fn default() -> Self {
+        Self {
+            resubmission_timeout_secs: 30,
+            fill_gaps: true,
+        }
+    }
+}
+
+/// The outcome of a resync with the on-chain state
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResyncSummary {
+    /// The on-chain sequence number of the account
+    pub on_chain_sequence_number: u64,
+    /// The number of in-flight transactions that were committed
+    pub num_committed: usize,
+    /// The sequence numbers of the stuck transactions that were resubmitted
+    pub resubmitted: Vec<u64>,
+    /// The sequence numbers of the gaps that were filled with no-op transactions
+    pub filled: Vec<u64>,
+}
+
+/// A transaction that was submitted, but is not yet known to be committed
+#[derive(Clone, Debug)]
+struct InFlightTransaction {
+    txn: SignedTransaction,
+    /// Whether the submission was rejected (e.g., by mempool)
+    rejected: bool,
+}
+
+/// Tracks the in-flight transactions of an account by sequence number
+#[derive(Debug, Default)]
+struct InFlightTransactions {
+    transactions: BTreeMap<u64, InFlightTransaction>,
+}
+
+impl InFlightTransactions {
+    fn insert(&mut self, txn: SignedTransaction) {
+        self.transactions
+            .insert(txn.sequence_number(), InFlightTransaction {
+                txn,
+                rejected: false,
+            });
+    }
+
+    fn mark_rejected(&mut self, sequence_number: u64) {
+        if let Some(in_flight_txn) = self.transactions.get_mut(&sequence_number) {
+            in_flight_txn.rejected = true;
+        }
+    }
+
+    /// Removes the transactions below the on-chain sequence number (i.e., the
+    /// committed transactions), and returns the number of removed transactions.
+    fn remove_committed(&mut self, on_chain_sequence_number: u64) -> usize {
+        let in_flight = self.transactions.split_off(&on_chain_sequence_number);
+        let num_committed = self.transactions.len();
+        self.transactions = in_flight;
+        num_committed
+    }
+
+    /// Returns the transactions that will never be committed (i.e., they were
+    /// rejected or expired), ordered by sequence number.
+    fn stuck(&self, ledger_timestamp_secs: u64) -> Vec<SignedTransaction> {
+        self.transactions
+            .values()
+            .filter(|in_flight_txn| {
+                in_flight_txn.rejected
+                    || in_flight_txn.txn.expiration_timestamp_secs() <= ledger_timestamp_secs
+            })
+            .map(|in_flight_txn| in_flight_txn.txn.clone())
+            .collect()
+    }
+
+    fn sequence_numbers(&self) -> Vec<u64> {
+        self.transactions.keys().copied().collect()
+    }
+}
+
+struct TransactionManagerState {
+    account: LocalAccount,
+    in_flight: InFlightTransactions,
+}
+
+pub struct TransactionManager {
+    api_client: ApiClient,
+    state: Mutex<TransactionManagerState>,
+    config: TransactionManagerConfig,
+}
+
+impl TransactionManager {
+    pub fn new(
+        api_client: ApiClient,
+        account: LocalAccount,
+        config: TransactionManagerConfig,
+    ) -> Self {
+        Self {
+            api_client,
+            state: Mutex::new(TransactionManagerState {
+                account,
+                in_flight: InFlightTransactions::default(),
+            }),
+            config,
+        }
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.state.lock().account.address()
+    }
+
+    /// Returns the sequence number that will be assigned to the next transaction
+    pub fn next_sequence_number(&self) -> u64 {
+        self.state.lock().account.sequence_number()
+    }
+
+    /// Returns the sequence numbers of the transactions in flight
+    pub fn in_flight_sequence_numbers(&self) -> Vec<u64> {
+        self.state.lock().in_flight.sequence_numbers()
+    }
+
+    /// Signs the transaction with the next sequence number and submits it. If
+    /// the submission fails, the transaction is resubmitted on the next resync.
+    pub async fn submit(&self, builder: TransactionBuilder) -> Result<SignedTransaction> {
+        let signed_txn = {
+            let mut state = self.state.lock();
+            let signed_txn = state.account.sign_with_transaction_builder(builder);
+            state.in_flight.insert(signed_txn.clone());
+            signed_txn
+        };
+
+        if let Err(error) = self.api_client.submit_bcs(&signed_txn).await {
+            self.state
+                .lock()
+                .in_flight
+                .mark_rejected(signed_txn.sequence_number());
+            return Err(error).context(format!(
+                "Failed to submit transaction with sequence number {}",
+                signed_txn.sequence_number()
+            ));
+        }
+        Ok(signed_txn)
+    }
+
+    /// Resyncs with the on-chain state: removes the committed transactions,
+    /// resubmits the stuck transactions and fills the gaps (if enabled).
+    pub async fn resync(&self) -> Result<ResyncSummary> {
+        let address = self.address();
+        let (on_chain_sequence_number, ledger_state) =
+            self.get_on_chain_sequence_number(address).await?;
+        let ledger_timestamp_secs = Duration::from_micros(ledger_state.timestamp_usecs).as_secs();
+
+        let (num_committed, stuck_txns) = {
+            let mut state = self.state.lock();
+            let num_committed = state.in_flight.remove_committed(on_chain_sequence_number);
+
+            // The account may have been used outside of the manager
+            if state.account.sequence_number() < on_chain_sequence_number {
+                *state.account.sequence_number_mut() = on_chain_sequence_number;
+            }
+            (num_committed, state.in_flight.stuck(ledger_timestamp_secs))
+        };
+
+        let mut summary = ResyncSummary {
+            on_chain_sequence_number,
+            num_committed,
+            ..Default::default()
+        };
+        for stuck_txn in stuck_txns {
+            let sequence_number = stuck_txn.sequence_number();
+            let resubmitted_txn = self.resign(&stuck_txn, None);
+            if self.resubmit(resubmitted_txn).await.is_ok() {
+                summary.resubmitted.push(sequence_number);
+                continue;
+            }
+
+            if self.config.fill_gaps {
+                // Fill the gap with a no-op transaction (i.e., a transfer of zero coins to self)
+                let filler_txn = self.resign(
+                    &stuck_txn,
+                    Some(aptos_stdlib::aptos_account_transfer(address, 0)),
+                );
+                self.resubmit(filler_txn).await.with_context(|| {
+                    format!(
+                        "Failed to fill the gap at sequence number {}",
+                        sequence_number
+                    )
+                })?;
+                summary.filled.push(sequence_number);
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Signs the payload of the given transaction (or the given payload) again,
+    /// with the same sequence number and a fresh expiration.
+    fn resign(
+        &self,
+        txn: &SignedTransaction,
+        payload: Option<TransactionPayload>,
+    ) -> SignedTransaction {
+        let expiration_timestamp_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + self.config.resubmission_timeout_secs;
+        let raw_txn = TransactionBuilder::new(
+            payload.unwrap_or_else(|| txn.payload().clone()),
+            expiration_timestamp_secs,
+            txn.chain_id(),
+        )
+        .sender(txn.sender())
+        .sequence_number(txn.sequence_number())
+        .max_gas_amount(txn.max_gas_amount())
+        .gas_unit_price(txn.gas_unit_price())
+        .build();
+        self.state.lock().account.sign_transaction(raw_txn)
+    }
+
+    async fn resubmit(&self, signed_txn: SignedTransaction) -> Result<()> {
+        let sequence_number = signed_txn.sequence_number();
+        self.state.lock().in_flight.insert(signed_txn.clone());
+        if let Err(error) = self.api_client.submit_bcs(&signed_txn).await {
+            self.state.lock().in_flight.mark_rejected(sequence_number);
+            return Err(error).context(format!(
+                "Failed to resubmit transaction with sequence number {}",
+                sequence_number
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the on-chain sequence number of the account (zero if the account
+    /// doesn't exist yet), and the ledger state it was read at.
+    async fn get_on_chain_sequence_number(&self, address: AccountAddress) -> Result<(u64, State)> {
+        match self.api_client.get_account_bcs(address).await {
+            Ok(response) => {
+                let (account, state) = response.into_parts();
+                Ok((account.sequence_number(), state))
+            },
+            Err(RestError::Api(api_error))
+                if matches!(api_error.error.error_code, AptosErrorCode::AccountNotFound) =>
+            {
+                let state = api_error
+                    .state
+                    .context("Missing ledger state in the account not found error")?;
+                Ok((0, state))
+            },
+            Err(error) => Err(error).context("Failed to get the account sequence number"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{chain_id::ChainId, transaction::Script};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn create_signed_transactions(num_transactions: u64) -> Vec<SignedTransaction> {
+        let mut account = LocalAccount::generate(&mut StdRng::from_seed([0u8; 32]));
+        (0..num_transactions)
+            .map(|sequence_number| {
+                let builder = TransactionBuilder::new(
+                    TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+                    100 + sequence_number,
+                    ChainId::test(),
+                );
+                let signed_txn = account.sign_with_transaction_builder(builder);
+                assert_eq!(signed_txn.sequence_number(), sequence_number);
+                signed_txn
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_track_in_flight_transactions() {
+        let mut in_flight = InFlightTransactions::default();
+        for signed_txn in create_signed_transactions(5) {
+            in_flight.insert(signed_txn);
+        }
+        assert!(in_flight.stuck(0).is_empty());
+
+        // Commit the first two transactions
+        assert_eq!(in_flight.remove_committed(2), 2);
+        assert_eq!(in_flight.sequence_numbers(), vec![2, 3, 4]);
+        assert_eq!(in_flight.remove_committed(2), 0);
+
+        // Reject a transaction and verify it is stuck
+        in_flight.mark_rejected(3);
+        let stuck_sequence_numbers = |in_flight: &InFlightTransactions, ledger_timestamp_secs| {
+            in_flight
+                .stuck(ledger_timestamp_secs)
+                .iter()
+                .map(|txn| txn.sequence_number())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(stuck_sequence_numbers(&in_flight, 0), vec![3]);
+
+        // Expire the transaction with sequence number 2 (i.e., creating a gap)
+        assert_eq!(stuck_sequence_numbers(&in_flight, 102), vec![2, 3]);
+        assert_eq!(stuck_sequence_numbers(&in_flight, 104), vec![2, 3, 4]);
+
+        // Replacing a rejected transaction (i.e., resubmission) clears the rejection
+        let resubmitted_txn = create_signed_transactions(5).remove(3);
+        in_flight.insert(resubmitted_txn);
+        assert_eq!(stuck_sequence_numbers(&in_flight, 0), Vec::<u64>::new());
+
+        // Commit all the transactions
+        assert_eq!(in_flight.remove_committed(5), 3);
+        assert!(in_flight.sequence_numbers().is_empty());
+    }
+}
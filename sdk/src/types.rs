@@ -4,21 +4,163 @@
 
 use crate::{
     crypto::{
-        ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
-        traits::Uniform,
+        ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+        multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+        secp256k1_ecdsa::{Secp256k1EcdsaPrivateKey, Secp256k1EcdsaPublicKey},
+        secp256r1_ecdsa::{
+            Secp256r1EcdsaPrivateKey, Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature,
+        },
+        traits::{signing_message, Signature, Uniform},
     },
     transaction_builder::TransactionBuilder,
     types::{
         account_address::AccountAddress,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, SignedTransaction},
+        transaction::{
+            authenticator::{AccountAuthenticator, AuthenticationKey, TransactionAuthenticator},
+            webauthn::{CollectedClientData, PartialAuthenticatorAssertionResponse},
+            RawTransaction, RawTransactionWithData, SignedTransaction,
+        },
     },
 };
-use anyhow::Result;
+use anyhow::{bail, ensure, format_err, Result};
 use aptos_types::event::EventKey;
 pub use aptos_types::*;
 use bip39::{Language, Mnemonic, Seed};
 use ed25519_dalek_bip32::{DerivationPath, ExtendedSecretKey};
-use std::str::FromStr;
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, str::FromStr};
+
+/// A signer of transactions. The signer can hold its keys locally (e.g., a
+/// `LocalAccount`), or delegate the signing to a remote service or an HSM.
+pub trait TransactionSigner {
+    /// Returns the address of the account the signer signs for
+    fn address(&self) -> AccountAddress;
+
+    /// Signs the given signing message (i.e., the salted BCS bytes of a raw
+    /// transaction), and returns the authenticator for the account.
+    fn sign_message(&self, signing_message: &[u8]) -> Result<AccountAuthenticator>;
+}
+
+/// Signs the raw transaction with the given (single) signer
+pub fn sign_transaction<S: TransactionSigner + ?Sized>(
+    signer: &S,
+    raw_txn: RawTransaction,
+) -> Result<SignedTransaction> {
+    ensure_sender(signer, &raw_txn)?;
+    let authenticator = match signer.sign_message(&raw_txn.signing_message()?)? {
+        AccountAuthenticator::Ed25519 {
+            public_key,
+            signature,
+        } => TransactionAuthenticator::ed25519(public_key, signature),
+        AccountAuthenticator::MultiEd25519 {
+            public_key,
+            signature,
+        } => TransactionAuthenticator::multi_ed25519(public_key, signature),
+        AccountAuthenticator::Secp256r1Ecdsa {
+            public_key,
+            signature,
+        } => TransactionAuthenticator::secp256r1_ecdsa(public_key, signature),
+        AccountAuthenticator::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        } => TransactionAuthenticator::secp256k1_ecdsa(public_key, signature),
+        AccountAuthenticator::Abstraction { signature } => {
+            TransactionAuthenticator::abstraction(signature)
+        },
+    };
+    Ok(SignedTransaction::new_with_authenticator(
+        raw_txn,
+        authenticator,
+    ))
+}
+
+/// Signs the raw transaction with the sender and all secondary signers
+pub fn sign_multi_agent_transaction<A, S>(
+    sender: &A,
+    secondary_signers: &[&S],
+    raw_txn: RawTransaction,
+) -> Result<SignedTransaction>
+where
+    A: TransactionSigner + ?Sized,
+    S: TransactionSigner + ?Sized,
+{
+    ensure_sender(sender, &raw_txn)?;
+    let secondary_signer_addresses: Vec<_> = secondary_signers
+        .iter()
+        .map(|signer| signer.address())
+        .collect();
+    let message = RawTransactionWithData::new_multi_agent(
+        raw_txn.clone(),
+        secondary_signer_addresses.clone(),
+    );
+    let signing_message = signing_message(&message)?;
+
+    let sender_authenticator = sender.sign_message(&signing_message)?;
+    let secondary_authenticators = secondary_signers
+        .iter()
+        .map(|signer| signer.sign_message(&signing_message))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(SignedTransaction::new_multi_agent(
+        raw_txn,
+        sender_authenticator,
+        secondary_signer_addresses,
+        secondary_authenticators,
+    ))
+}
+
+/// Signs the raw transaction with the sender, all secondary signers and the fee payer
+pub fn sign_fee_payer_transaction<A, S, F>(
+    sender: &A,
+    secondary_signers: &[&S],
+    fee_payer_signer: &F,
+    raw_txn: RawTransaction,
+) -> Result<SignedTransaction>
+where
+    A: TransactionSigner + ?Sized,
+    S: TransactionSigner + ?Sized,
+    F: TransactionSigner + ?Sized,
+{
+    ensure_sender(sender, &raw_txn)?;
+    let secondary_signer_addresses: Vec<_> = secondary_signers
+        .iter()
+        .map(|signer| signer.address())
+        .collect();
+    let fee_payer_address = fee_payer_signer.address();
+    let message = RawTransactionWithData::new_fee_payer(
+        raw_txn.clone(),
+        secondary_signer_addresses.clone(),
+        fee_payer_address,
+    );
+    let signing_message = signing_message(&message)?;
+
+    let sender_authenticator = sender.sign_message(&signing_message)?;
+    let secondary_authenticators = secondary_signers
+        .iter()
+        .map(|signer| signer.sign_message(&signing_message))
+        .collect::<Result<Vec<_>>>()?;
+    let fee_payer_authenticator = fee_payer_signer.sign_message(&signing_message)?;
+    Ok(SignedTransaction::new_fee_payer(
+        raw_txn,
+        sender_authenticator,
+        secondary_signer_addresses,
+        secondary_authenticators,
+        fee_payer_address,
+        fee_payer_authenticator,
+    ))
+}
+
+fn ensure_sender<S: TransactionSigner + ?Sized>(
+    sender: &S,
+    raw_txn: &RawTransaction,
+) -> Result<()> {
+    ensure!(
+        sender.address() == raw_txn.sender(),
+        "The signer address {} does not match the transaction sender {}",
+        sender.address(),
+        raw_txn.sender()
+    );
+    Ok(())
+}
 
 /// LocalAccount represents an account on the Aptos blockchain. Internally it
 /// holds the private / public key pair and the address of the account. You can
@@ -100,63 +242,37 @@ impl LocalAccount {
         self.sign_transaction(raw_txn)
     }
 
-    pub fn sign_multi_agent_with_transaction_builder(
+    pub fn sign_multi_agent_with_transaction_builder<S: TransactionSigner + ?Sized>(
         &mut self,
-        secondary_signers: Vec<&Self>,
+        secondary_signers: Vec<&S>,
         builder: TransactionBuilder,
     ) -> SignedTransaction {
-        let secondary_signer_addresses = secondary_signers
-            .iter()
-            .map(|signer| signer.address())
-            .collect();
-        let secondary_signer_privkeys = secondary_signers
-            .iter()
-            .map(|signer| signer.private_key())
-            .collect();
         let raw_txn = builder
             .sender(self.address())
             .sequence_number(self.sequence_number())
             .build();
         *self.sequence_number_mut() += 1;
-        raw_txn
-            .sign_multi_agent(
-                self.private_key(),
-                secondary_signer_addresses,
-                secondary_signer_privkeys,
-            )
+        sign_multi_agent_transaction(&*self, &secondary_signers, raw_txn)
             .expect("Signing multi agent txn failed")
-            .into_inner()
     }
 
-    pub fn sign_fee_payer_with_transaction_builder(
+    pub fn sign_fee_payer_with_transaction_builder<S, F>(
         &mut self,
-        secondary_signers: Vec<&Self>,
-        fee_payer_signer: &Self,
+        secondary_signers: Vec<&S>,
+        fee_payer_signer: &F,
         builder: TransactionBuilder,
-    ) -> SignedTransaction {
-        let secondary_signer_addresses = secondary_signers
-            .iter()
-            .map(|signer| signer.address())
-            .collect();
-        let secondary_signer_privkeys = secondary_signers
-            .iter()
-            .map(|signer| signer.private_key())
-            .collect();
+    ) -> SignedTransaction
+    where
+        S: TransactionSigner + ?Sized,
+        F: TransactionSigner + ?Sized,
+    {
         let raw_txn = builder
             .sender(self.address())
             .sequence_number(self.sequence_number())
             .build();
         *self.sequence_number_mut() += 1;
-        raw_txn
-            .sign_fee_payer(
-                self.private_key(),
-                secondary_signer_addresses,
-                secondary_signer_privkeys,
-                fee_payer_signer.address(),
-                fee_payer_signer.private_key(),
-            )
-            .expect("Signing multi agent txn failed")
-            .into_inner()
+        sign_fee_payer_transaction(&*self, &secondary_signers, fee_payer_signer, raw_txn)
+            .expect("Signing fee payer txn failed")
     }
 
     pub fn address(&self) -> AccountAddress {
@@ -196,6 +312,389 @@ impl LocalAccount {
     }
 }
 
+impl TransactionSigner for LocalAccount {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn sign_message(&self, signing_message: &[u8]) -> Result<AccountAuthenticator> {
+        Ok(self.key.sign_message(signing_message))
+    }
+}
+
+/// A k-of-n MultiEd25519 account. Transactions can either be signed directly
+/// (if enough private keys are held locally), or by collecting the partial
+/// signatures of the other parties via a `MultiEd25519SignatureCollector`.
+#[derive(Debug)]
+pub struct MultiEd25519Account {
+    /// Address of the account.
+    address: AccountAddress,
+    /// Keys of the account.
+    key: MultiEd25519AccountKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl MultiEd25519Account {
+    pub fn new(address: AccountAddress, key: MultiEd25519AccountKey, sequence_number: u64) -> Self {
+        Self {
+            address,
+            key,
+            sequence_number,
+        }
+    }
+
+    /// Generate a new k-of-n account locally (holding all n private keys). Note: This
+    /// function does not actually create an account on the Aptos blockchain.
+    pub fn generate<R>(rng: &mut R, threshold: usize, num_keys: usize) -> Result<Self>
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        let key = MultiEd25519AccountKey::generate(rng, threshold, num_keys)?;
+        let address = key.authentication_key().derived_address();
+
+        Ok(Self::new(address, key, 0))
+    }
+
+    /// Signs the transaction with the private keys held locally. Fails if
+    /// fewer than threshold private keys are held.
+    pub fn sign_transaction(&self, txn: RawTransaction) -> Result<SignedTransaction> {
+        sign_transaction(self, txn)
+    }
+
+    pub fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> Result<SignedTransaction> {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        self.sign_transaction(raw_txn)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn key(&self) -> &MultiEd25519AccountKey {
+        &self.key
+    }
+
+    pub fn public_key(&self) -> &MultiEd25519PublicKey {
+        self.key.public_key()
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        self.key.authentication_key()
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
+
+impl TransactionSigner for MultiEd25519Account {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn sign_message(&self, signing_message: &[u8]) -> Result<AccountAuthenticator> {
+        self.key.sign_message(signing_message)
+    }
+}
+
+/// A WebAuthn authenticator holding the secp256r1 private key of a passkey,
+/// e.g., a platform authenticator or a security key reached through a browser.
+///
+/// Authenticators return DER-encoded signatures that may have a high `s`,
+/// implementations should convert them with `normalize_passkey_signature`.
+pub trait PasskeyAuthenticator {
+    /// Returns the public key of the passkey
+    fn public_key(&self) -> &Secp256r1EcdsaPublicKey;
+
+    /// Requests an assertion for the given challenge (i.e., the signing message
+    /// of a transaction), and returns its signature and the data it covers.
+    fn get_assertion(&self, challenge: &[u8]) -> Result<PartialAuthenticatorAssertionResponse>;
+}
+
+/// An account whose key is a passkey. Transactions are signed by requesting a
+/// WebAuthn assertion from its `PasskeyAuthenticator`.
+#[derive(Debug)]
+pub struct PasskeyAccount<A> {
+    /// Address of the account.
+    address: AccountAddress,
+    /// Authenticator holding the key of the account.
+    authenticator: A,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl<A: PasskeyAuthenticator> PasskeyAccount<A> {
+    pub fn new(address: AccountAddress, authenticator: A, sequence_number: u64) -> Self {
+        Self {
+            address,
+            authenticator,
+            sequence_number,
+        }
+    }
+
+    /// Creates the local representation of the account derived from the
+    /// passkey. Note: This function does not actually create an account on
+    /// the Aptos blockchain.
+    pub fn from_authenticator(authenticator: A) -> Self {
+        let address =
+            AuthenticationKey::secp256r1_ecdsa(authenticator.public_key()).derived_address();
+        Self::new(address, authenticator, 0)
+    }
+
+    pub fn sign_transaction(&self, txn: RawTransaction) -> Result<SignedTransaction> {
+        sign_transaction(self, txn)
+    }
+
+    pub fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> Result<SignedTransaction> {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        self.sign_transaction(raw_txn)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn authenticator(&self) -> &A {
+        &self.authenticator
+    }
+
+    pub fn public_key(&self) -> &Secp256r1EcdsaPublicKey {
+        self.authenticator.public_key()
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::secp256r1_ecdsa(self.public_key())
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
+
+impl<A: PasskeyAuthenticator> TransactionSigner for PasskeyAccount<A> {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn sign_message(&self, signing_message: &[u8]) -> Result<AccountAuthenticator> {
+        Ok(AccountAuthenticator::secp256r1_ecdsa(
+            self.public_key().clone(),
+            self.authenticator.get_assertion(signing_message)?,
+        ))
+    }
+}
+
+/// A software passkey that holds its private key locally, and builds the
+/// WebAuthn assertions itself as a browser and authenticator would. This is
+/// mostly useful for testing and for headless clients.
+#[derive(Debug)]
+pub struct LocalPasskey {
+    private_key: Secp256r1EcdsaPrivateKey,
+    public_key: Secp256r1EcdsaPublicKey,
+    /// The relying party the passkey is scoped to, e.g., "wallet.example"
+    rp_id: String,
+}
+
+impl LocalPasskey {
+    /// The "user present" and "user verified" flags of the authenticator data
+    const FLAGS: u8 = 0x05;
+
+    pub fn new(private_key: Secp256r1EcdsaPrivateKey, rp_id: String) -> Self {
+        let public_key = Secp256r1EcdsaPublicKey::from(&private_key);
+        Self {
+            private_key,
+            public_key,
+            rp_id,
+        }
+    }
+
+    pub fn generate<R>(rng: &mut R, rp_id: String) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        Self::new(Secp256r1EcdsaPrivateKey::generate(rng), rp_id)
+    }
+
+    pub fn private_key(&self) -> &Secp256r1EcdsaPrivateKey {
+        &self.private_key
+    }
+
+    pub fn rp_id(&self) -> &str {
+        &self.rp_id
+    }
+}
+
+impl PasskeyAuthenticator for LocalPasskey {
+    fn public_key(&self) -> &Secp256r1EcdsaPublicKey {
+        &self.public_key
+    }
+
+    fn get_assertion(&self, challenge: &[u8]) -> Result<PartialAuthenticatorAssertionResponse> {
+        // RP ID hash, flags and a zero signature counter
+        let mut authenticator_data = Sha256::digest(self.rp_id.as_bytes()).to_vec();
+        authenticator_data.push(Self::FLAGS);
+        authenticator_data.extend_from_slice(&0u32.to_be_bytes());
+
+        let client_data_json =
+            CollectedClientData::new_get(challenge, format!("https://{}", self.rp_id))
+                .to_json_bytes();
+        let signature = self.private_key.sign_arbitrary_message(
+            &PartialAuthenticatorAssertionResponse::verification_data(
+                &authenticator_data,
+                &client_data_json,
+            ),
+        );
+        Ok(PartialAuthenticatorAssertionResponse::new(
+            signature,
+            authenticator_data,
+            client_data_json,
+        ))
+    }
+}
+
+/// Converts the signature of a WebAuthn assertion, as returned by the
+/// authenticator, into a signature that Aptos accepts.
+///
+/// Authenticators return ASN.1 DER-encoded ECDSA signatures, and most of them
+/// do not normalize the `s` component. Aptos only accepts signatures whose `s`
+/// is in the lower half of the curve order `n`, so that signatures cannot be
+/// mauled, hence a signature `(r, s)` with a high `s` is replaced by the
+/// equally valid `(r, n - s)`. Signatures encoded as the 64 bytes `r || s` are
+/// accepted as well.
+pub fn normalize_passkey_signature(signature: &[u8]) -> Result<Secp256r1EcdsaSignature> {
+    let signature = p256::ecdsa::Signature::from_der(signature)
+        .or_else(|_| p256::ecdsa::Signature::from_slice(signature))
+        .map_err(|_| format_err!("Invalid secp256r1 ECDSA signature"))?;
+    let signature = signature.normalize_s().unwrap_or(signature);
+    Ok(Secp256r1EcdsaSignature::try_from(
+        signature.to_bytes().as_slice(),
+    )?)
+}
+
+/// An account whose key is an Ethereum-style secp256k1 ECDSA key, e.g., a key
+/// imported from an Ethereum wallet. Keys held remotely (e.g., by a hardware
+/// wallet) can be used by implementing `TransactionSigner` instead.
+#[derive(Debug)]
+pub struct Secp256k1EcdsaAccount {
+    /// Address of the account.
+    address: AccountAddress,
+    private_key: Secp256k1EcdsaPrivateKey,
+    public_key: Secp256k1EcdsaPublicKey,
+    /// Latest known sequence number of the account, it can be different from validator.
+    sequence_number: u64,
+}
+
+impl Secp256k1EcdsaAccount {
+    pub fn new(
+        address: AccountAddress,
+        private_key: Secp256k1EcdsaPrivateKey,
+        sequence_number: u64,
+    ) -> Self {
+        let public_key = Secp256k1EcdsaPublicKey::from(&private_key);
+        Self {
+            address,
+            private_key,
+            public_key,
+            sequence_number,
+        }
+    }
+
+    /// Creates the local representation of the account derived from the
+    /// private key. Note: This function does not actually create an account
+    /// on the Aptos blockchain.
+    pub fn from_private_key(private_key: Secp256k1EcdsaPrivateKey) -> Self {
+        let public_key = Secp256k1EcdsaPublicKey::from(&private_key);
+        let address = AuthenticationKey::secp256k1_ecdsa(&public_key).derived_address();
+        Self::new(address, private_key, 0)
+    }
+
+    /// Generate a new account locally. Note: This function does not actually
+    /// create an account on the Aptos blockchain.
+    pub fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        Self::from_private_key(Secp256k1EcdsaPrivateKey::generate(rng))
+    }
+
+    pub fn sign_transaction(&self, txn: RawTransaction) -> SignedTransaction {
+        sign_transaction(self, txn).expect("Signing a txn can't fail")
+    }
+
+    pub fn sign_with_transaction_builder(
+        &mut self,
+        builder: TransactionBuilder,
+    ) -> SignedTransaction {
+        let raw_txn = builder
+            .sender(self.address())
+            .sequence_number(self.sequence_number())
+            .build();
+        *self.sequence_number_mut() += 1;
+        self.sign_transaction(raw_txn)
+    }
+
+    pub fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    pub fn private_key(&self) -> &Secp256k1EcdsaPrivateKey {
+        &self.private_key
+    }
+
+    pub fn public_key(&self) -> &Secp256k1EcdsaPublicKey {
+        &self.public_key
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        AuthenticationKey::secp256k1_ecdsa(&self.public_key)
+    }
+
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub fn sequence_number_mut(&mut self) -> &mut u64 {
+        &mut self.sequence_number
+    }
+}
+
+impl TransactionSigner for Secp256k1EcdsaAccount {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn sign_message(&self, signing_message: &[u8]) -> Result<AccountAuthenticator> {
+        let signature = self.private_key.sign_arbitrary_message(signing_message);
+        Ok(AccountAuthenticator::secp256k1_ecdsa(
+            self.public_key.clone(),
+            signature,
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub struct AccountKey {
     private_key: Ed25519PrivateKey,
@@ -234,6 +733,12 @@ impl AccountKey {
     pub fn authentication_key(&self) -> AuthenticationKey {
         self.authentication_key
     }
+
+    /// Signs the given signing message and returns the authenticator
+    pub fn sign_message(&self, signing_message: &[u8]) -> AccountAuthenticator {
+        let signature = self.private_key.sign_arbitrary_message(signing_message);
+        AccountAuthenticator::ed25519(self.public_key.clone(), signature)
+    }
 }
 
 impl From<Ed25519PrivateKey> for AccountKey {
@@ -242,9 +747,441 @@ impl From<Ed25519PrivateKey> for AccountKey {
     }
 }
 
+/// The keys of a k-of-n MultiEd25519 account. This holds the public keys of all
+/// n parties, but only the private keys of the parties that sign locally.
+#[derive(Debug)]
+pub struct MultiEd25519AccountKey {
+    public_key: MultiEd25519PublicKey,
+    /// The locally held private keys, by their index in the public key
+    private_keys: BTreeMap<usize, Ed25519PrivateKey>,
+    authentication_key: AuthenticationKey,
+}
+
+impl MultiEd25519AccountKey {
+    /// Creates the account key with the given private keys (and their indices
+    /// in the public key). Each private key must match the public key at its index.
+    pub fn new(
+        public_key: MultiEd25519PublicKey,
+        private_keys: Vec<(usize, Ed25519PrivateKey)>,
+    ) -> Result<Self> {
+        let mut indexed_private_keys = BTreeMap::new();
+        for (index, private_key) in private_keys {
+            let expected_public_key = public_key
+                .public_keys()
+                .get(index)
+                .ok_or_else(|| format_err!("Private key index {} is out of range", index))?;
+            ensure!(
+                &Ed25519PublicKey::from(&private_key) == expected_public_key,
+                "The private key does not match the public key at index {}",
+                index
+            );
+            ensure!(
+                indexed_private_keys.insert(index, private_key).is_none(),
+                "Duplicate private key index {}",
+                index
+            );
+        }
+        let authentication_key = AuthenticationKey::multi_ed25519(&public_key);
+
+        Ok(Self {
+            public_key,
+            private_keys: indexed_private_keys,
+            authentication_key,
+        })
+    }
+
+    /// Generates a new k-of-n account key that holds all n private keys
+    pub fn generate<R>(rng: &mut R, threshold: usize, num_keys: usize) -> Result<Self>
+    where
+        R: ::rand_core::RngCore + ::rand_core::CryptoRng,
+    {
+        let private_keys: Vec<_> = (0..num_keys)
+            .map(|_| Ed25519PrivateKey::generate(rng))
+            .collect();
+        let public_keys = private_keys.iter().map(Ed25519PublicKey::from).collect();
+        let public_key = MultiEd25519PublicKey::new(public_keys, threshold)?;
+        Self::new(public_key, private_keys.into_iter().enumerate().collect())
+    }
+
+    pub fn public_key(&self) -> &MultiEd25519PublicKey {
+        &self.public_key
+    }
+
+    pub fn threshold(&self) -> usize {
+        *self.public_key.threshold()
+    }
+
+    pub fn authentication_key(&self) -> AuthenticationKey {
+        self.authentication_key
+    }
+
+    /// Returns the indices of the private keys held locally
+    pub fn private_key_indices(&self) -> Vec<usize> {
+        self.private_keys.keys().copied().collect()
+    }
+
+    /// Signs the given signing message with all the private keys held locally,
+    /// and returns the partial signatures (with their indices).
+    pub fn sign_partial(&self, signing_message: &[u8]) -> Vec<(usize, Ed25519Signature)> {
+        self.private_keys
+            .iter()
+            .map(|(index, private_key)| {
+                (*index, private_key.sign_arbitrary_message(signing_message))
+            })
+            .collect()
+    }
+
+    /// Signs the given signing message with (up to threshold) private keys held
+    /// locally. Fails if fewer than threshold private keys are held.
+    pub fn sign_message(&self, signing_message: &[u8]) -> Result<AccountAuthenticator> {
+        let mut collector =
+            MultiEd25519SignatureCollector::new(self.public_key.clone(), signing_message.to_vec());
+        for (index, signature) in self
+            .sign_partial(signing_message)
+            .into_iter()
+            .take(self.threshold())
+        {
+            collector.add_signature(index, signature)?;
+        }
+        collector.finish()
+    }
+}
+
+/// Collects the partial signatures of a k-of-n MultiEd25519 account from
+/// several parties (e.g., via `MultiEd25519AccountKey::sign_partial`), and
+/// combines them into an authenticator once the threshold is met.
+#[derive(Clone, Debug)]
+pub struct MultiEd25519SignatureCollector {
+    public_key: MultiEd25519PublicKey,
+    signing_message: Vec<u8>,
+    signatures: BTreeMap<usize, Ed25519Signature>,
+}
+
+impl MultiEd25519SignatureCollector {
+    pub fn new(public_key: MultiEd25519PublicKey, signing_message: Vec<u8>) -> Self {
+        Self {
+            public_key,
+            signing_message,
+            signatures: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the signing message that each party must sign
+    pub fn signing_message(&self) -> &[u8] {
+        &self.signing_message
+    }
+
+    /// Adds the partial signature of the party at the given index. The
+    /// signature is verified against the public key of that party.
+    pub fn add_signature(&mut self, index: usize, signature: Ed25519Signature) -> Result<()> {
+        let public_key = self
+            .public_key
+            .public_keys()
+            .get(index)
+            .ok_or_else(|| format_err!("Signature index {} is out of range", index))?;
+        signature
+            .verify_arbitrary_msg(&self.signing_message, public_key)
+            .map_err(|error| format_err!("Invalid signature at index {}: {}", index, error))?;
+        self.signatures.insert(index, signature);
+        Ok(())
+    }
+
+    /// Returns the number of (valid) signatures collected so far
+    pub fn num_signatures(&self) -> usize {
+        self.signatures.len()
+    }
+
+    /// Returns true iff enough signatures have been collected
+    pub fn is_complete(&self) -> bool {
+        self.num_signatures() >= *self.public_key.threshold()
+    }
+
+    /// Combines the collected signatures into an authenticator
+    pub fn finish(self) -> Result<AccountAuthenticator> {
+        if !self.is_complete() {
+            bail!(
+                "Not enough signatures collected: {} of {}",
+                self.num_signatures(),
+                self.public_key.threshold()
+            );
+        }
+        let signature = MultiEd25519Signature::new(
+            self.signatures
+                .into_iter()
+                .map(|(index, signature)| (signature, index))
+                .collect(),
+        )?;
+        Ok(AccountAuthenticator::multi_ed25519(
+            self.public_key,
+            signature,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{
+        chain_id::ChainId,
+        transaction::{Script, TransactionPayload},
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn create_raw_transaction(sender: AccountAddress) -> RawTransaction {
+        RawTransaction::new(
+            sender,
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            1_000,
+            100,
+            u64::MAX,
+            ChainId::test(),
+        )
+    }
+
+    #[test]
+    fn test_sign_multi_ed25519_transaction() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let account = MultiEd25519Account::generate(&mut rng, 2, 3).unwrap();
+        assert_eq!(
+            account.address(),
+            AuthenticationKey::multi_ed25519(account.public_key()).derived_address()
+        );
+
+        // Sign the transaction locally and verify the signature
+        let signed_txn = account
+            .sign_transaction(create_raw_transaction(account.address()))
+            .unwrap();
+        assert!(matches!(
+            signed_txn.authenticator_ref(),
+            TransactionAuthenticator::MultiEd25519 { .. }
+        ));
+        signed_txn.check_signature().unwrap();
+
+        // Signing fails if fewer than threshold private keys are held
+        let partial_key =
+            MultiEd25519AccountKey::new(account.public_key().clone(), vec![]).unwrap();
+        let partial_account = MultiEd25519Account::new(account.address(), partial_key, 0);
+        assert!(partial_account
+            .sign_transaction(create_raw_transaction(account.address()))
+            .is_err());
+
+        // Signing fails if the signer is not the sender
+        assert!(account
+            .sign_transaction(create_raw_transaction(AccountAddress::random()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_collect_multi_ed25519_signatures() {
+        // Create three parties, each holding a single key of a 2-of-3 account
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let private_keys: Vec<_> = (0..3)
+            .map(|_| Ed25519PrivateKey::generate(&mut rng))
+            .collect();
+        let public_key = MultiEd25519PublicKey::new(
+            private_keys.iter().map(Ed25519PublicKey::from).collect(),
+            2,
+        )
+        .unwrap();
+        let parties: Vec<_> = private_keys
+            .into_iter()
+            .enumerate()
+            .map(|(index, private_key)| {
+                MultiEd25519AccountKey::new(public_key.clone(), vec![(index, private_key)]).unwrap()
+            })
+            .collect();
+
+        // Verify a private key must match the public key at its index
+        let wrong_key = Ed25519PrivateKey::generate(&mut rng);
+        assert!(MultiEd25519AccountKey::new(public_key.clone(), vec![(0, wrong_key)]).is_err());
+
+        // Collect the signatures of the first and last parties
+        let address = AuthenticationKey::multi_ed25519(&public_key).derived_address();
+        let raw_txn = create_raw_transaction(address);
+        let mut collector = MultiEd25519SignatureCollector::new(
+            public_key.clone(),
+            raw_txn.signing_message().unwrap(),
+        );
+        for (index, signature) in parties[0].sign_partial(collector.signing_message()) {
+            collector.add_signature(index, signature).unwrap();
+        }
+        assert!(!collector.is_complete());
+        assert!(collector.clone().finish().is_err());
+
+        // Invalid and out of range signatures are rejected
+        let (_, signature) = parties[2].sign_partial(collector.signing_message())[0].clone();
+        assert!(collector.add_signature(1, signature.clone()).is_err());
+        assert!(collector.add_signature(3, signature.clone()).is_err());
+        collector.add_signature(2, signature).unwrap();
+        assert!(collector.is_complete());
+
+        // Build the transaction and verify the signature
+        let authenticator = match collector.finish().unwrap() {
+            AccountAuthenticator::MultiEd25519 {
+                public_key,
+                signature,
+            } => TransactionAuthenticator::multi_ed25519(public_key, signature),
+            authenticator => panic!("Unexpected authenticator: {:?}", authenticator),
+        };
+        SignedTransaction::new_with_authenticator(raw_txn, authenticator)
+            .check_signature()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sign_passkey_transaction() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let passkey = LocalPasskey::generate(&mut rng, "wallet.example".to_string());
+        let account = PasskeyAccount::from_authenticator(passkey);
+        assert_eq!(
+            account.address(),
+            AuthenticationKey::secp256r1_ecdsa(account.public_key()).derived_address()
+        );
+
+        // Sign the transaction locally and verify the assertion
+        let signed_txn = account
+            .sign_transaction(create_raw_transaction(account.address()))
+            .unwrap();
+        assert!(matches!(
+            signed_txn.authenticator_ref(),
+            TransactionAuthenticator::Secp256r1Ecdsa { .. }
+        ));
+        signed_txn.check_signature().unwrap();
+
+        // A passkey can also co-sign a multi-agent transaction
+        let sender = LocalAccount::generate(&mut rng);
+        let signed_txn = sign_multi_agent_transaction(
+            &sender,
+            &[&account],
+            create_raw_transaction(sender.address()),
+        )
+        .unwrap();
+        signed_txn.check_signature().unwrap();
+
+        // An assertion for another transaction does not verify
+        let other_txn = create_raw_transaction(account.address());
+        let assertion = account
+            .authenticator()
+            .get_assertion(b"another signing message")
+            .unwrap();
+        let authenticator =
+            TransactionAuthenticator::secp256r1_ecdsa(account.public_key().clone(), assertion);
+        assert!(
+            SignedTransaction::new_with_authenticator(other_txn, authenticator)
+                .check_signature()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_normalize_high_s_passkey_signature() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let passkey = LocalPasskey::generate(&mut rng, "wallet.example".to_string());
+        let account = PasskeyAccount::from_authenticator(passkey);
+        let raw_txn = create_raw_transaction(account.address());
+        let assertion = account
+            .authenticator()
+            .get_assertion(&signing_message(&raw_txn).unwrap())
+            .unwrap();
+
+        // Replace the signature by its high-s counterpart, DER-encoded as an
+        // authenticator would return it
+        let signature =
+            p256::ecdsa::Signature::from_slice(&assertion.signature().to_bytes()).unwrap();
+        let (r, s) = signature.split_scalars();
+        let high_s_signature = p256::ecdsa::Signature::from_scalars(r, -s).unwrap();
+        assert!(Secp256r1EcdsaSignature::try_from(high_s_signature.to_bytes().as_slice()).is_err());
+
+        // Once normalized, the signature is the original one and the assertion verifies
+        let normalized = normalize_passkey_signature(high_s_signature.to_der().as_bytes()).unwrap();
+        assert_eq!(&normalized, assertion.signature());
+        let assertion = PartialAuthenticatorAssertionResponse::new(
+            normalized,
+            assertion.authenticator_data().to_vec(),
+            assertion.client_data_json().to_vec(),
+        );
+        let authenticator =
+            TransactionAuthenticator::secp256r1_ecdsa(account.public_key().clone(), assertion);
+        SignedTransaction::new_with_authenticator(raw_txn, authenticator)
+            .check_signature()
+            .unwrap();
+
+        // Low-s signatures are kept as is, in both encodings
+        assert_eq!(
+            normalize_passkey_signature(signature.to_der().as_bytes()).unwrap(),
+            normalize_passkey_signature(&signature.to_bytes()).unwrap()
+        );
+        assert!(normalize_passkey_signature(&[0u8; 10]).is_err());
+    }
+
+    #[test]
+    fn test_sign_secp256k1_ecdsa_transaction() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let account = Secp256k1EcdsaAccount::generate(&mut rng);
+        assert_eq!(
+            account.address(),
+            account.authentication_key().derived_address()
+        );
+
+        let signed_txn = account.sign_transaction(create_raw_transaction(account.address()));
+        assert!(matches!(
+            signed_txn.authenticator_ref(),
+            TransactionAuthenticator::Secp256k1Ecdsa { .. }
+        ));
+        signed_txn.check_signature().unwrap();
+
+        // A secp256k1 account can also pay for another account's transaction
+        let sender = LocalAccount::generate(&mut rng);
+        let signed_txn = sign_fee_payer_transaction::<_, LocalAccount, _>(
+            &sender,
+            &[],
+            &account,
+            create_raw_transaction(sender.address()),
+        )
+        .unwrap();
+        signed_txn.check_signature().unwrap();
+    }
+
+    #[test]
+    fn test_sign_multi_agent_and_fee_payer_transactions() {
+        let mut rng = StdRng::from_seed([0u8; 32]);
+        let mut sender = LocalAccount::generate(&mut rng);
+        let secondary_signer = MultiEd25519Account::generate(&mut rng, 1, 2).unwrap();
+        let fee_payer = LocalAccount::generate(&mut rng);
+
+        // Sign a multi-agent transaction with mixed signers
+        let secondary_signers: Vec<&dyn TransactionSigner> = vec![&secondary_signer, &fee_payer];
+        let signed_txn = sign_multi_agent_transaction(
+            &sender,
+            &secondary_signers,
+            create_raw_transaction(sender.address()),
+        )
+        .unwrap();
+        assert_eq!(
+            signed_txn.authenticator().secondary_signer_addreses(),
+            vec![secondary_signer.address(), fee_payer.address()]
+        );
+        signed_txn.check_signature().unwrap();
+
+        // Sign a fee payer transaction with the builder helper
+        let builder = TransactionBuilder::new(
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            u64::MAX,
+            ChainId::test(),
+        );
+        let signed_txn = sender.sign_fee_payer_with_transaction_builder(
+            vec![&secondary_signer],
+            &fee_payer,
+            builder,
+        );
+        assert_eq!(sender.sequence_number(), 1);
+        assert_eq!(
+            signed_txn.authenticator().fee_payer_address(),
+            Some(fee_payer.address())
+        );
+        signed_txn.check_signature().unwrap();
+    }
 
     #[test]
     fn test_recover_account_from_derive_path() {
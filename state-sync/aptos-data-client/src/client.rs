@@ -6,7 +6,7 @@ use crate::{
     global_summary::GlobalDataSummary,
     interface::{
         AptosDataClientInterface, Response, ResponseCallback, ResponseContext, ResponseError,
-        ResponseId,
+        ResponseId, SubscriptionRequestMetadata,
     },
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
@@ -21,7 +21,7 @@ use aptos_config::{
     network_id::PeerNetworkId,
 };
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
-use aptos_infallible::RwLock;
+use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::{debug, info, sample, sample::SampleRate, trace, warn};
 use aptos_network::{application::interface::NetworkClient, protocols::network::RpcError};
 use aptos_storage_interface::DbReader;
@@ -30,7 +30,10 @@ use aptos_storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
+        StateValuesWithMultiProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
+        SubscribeTransactionOutputsWithProofRequest,
+        SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
+        SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
@@ -40,7 +43,10 @@ use aptos_time_service::TimeService;
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueChunkWithProof, StateValuesWithMultiProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
@@ -72,6 +78,8 @@ const PEER_LOG_FREQ_SECS: u64 = 10;
 /// and/or threads.
 #[derive(Clone, Debug)]
 pub struct AptosDataClient {
+    /// The state of the active subscription stream (if any).
+    active_subscription_state: Arc<Mutex<Option<SubscriptionState>>>,
     /// Config for AptosNet data client.
     data_client_config: AptosDataClientConfig,
     /// The underlying AptosNet storage service client.
@@ -95,6 +103,7 @@ impl AptosDataClient {
     ) -> (Self, DataSummaryPoller) {
         // Create the data client
         let data_client = Self {
+            active_subscription_state: Arc::new(Mutex::new(None)),
             data_client_config,
             storage_service_client: storage_service_client.clone(),
             peer_states: Arc::new(RwLock::new(PeerStates::new(
@@ -186,15 +195,70 @@ impl AptosDataClient {
             self.identify_serviceable(regular_peers, request)
         };
 
+        // Subscription requests must all be sent to the same peer
+        if request.data_request.is_subscription_request() {
+            return self.choose_peer_for_subscription_request(request, serviceable_peers);
+        }
+
         // Randomly select a peer to handle the request
-        serviceable_peers
-            .choose(&mut rand::thread_rng())
-            .copied()
-            .ok_or_else(|| {
-                Error::DataIsUnavailable(
-                    format!("No connected peers are advertising that they can serve this data! Request: {:?}",request),
-                )
-            })
+        choose_random_peer(request, serviceable_peers)
+    }
+
+    /// Choose a peer that can service the given subscription request.
+    /// All requests along the same subscription stream are sent to the
+    /// same peer. If that peer can no longer service the stream, an
+    /// error is returned (and the stream must be restarted).
+    fn choose_peer_for_subscription_request(
+        &self,
+        request: &StorageServiceRequest,
+        serviceable_peers: Vec<PeerNetworkId>,
+    ) -> crate::error::Result<PeerNetworkId, Error> {
+        // Get the stream ID from the request
+        let request_stream_id = match &request.data_request {
+            DataRequest::SubscribeTransactionOutputsWithProof(request) => {
+                request.subscription_stream_metadata.subscription_stream_id
+            },
+            DataRequest::SubscribeTransactionsWithProof(request) => {
+                request.subscription_stream_metadata.subscription_stream_id
+            },
+            DataRequest::SubscribeTransactionsOrOutputsWithProof(request) => {
+                request.subscription_stream_metadata.subscription_stream_id
+            },
+            data_request => {
+                return Err(Error::UnexpectedErrorEncountered(format!(
+                    "Invalid subscription request type found: {:?}",
+                    data_request
+                )))
+            },
+        };
+
+        // If there is an active subscription and the request ID matches the
+        // active stream ID, use the same peer (as long as it is still serviceable).
+        let mut active_subscription_state = self.active_subscription_state.lock();
+        if let Some(subscription_state) = active_subscription_state.take() {
+            if subscription_state.subscription_stream_id == request_stream_id {
+                let peer_network_id = subscription_state.peer_network_id;
+                return if serviceable_peers.contains(&peer_network_id) {
+                    // The previously chosen peer can still service the request
+                    *active_subscription_state = Some(subscription_state);
+                    Ok(peer_network_id)
+                } else {
+                    // The previously chosen peer can no longer service the
+                    // request, so the stream state is reset (by the take above).
+                    Err(Error::DataIsUnavailable(format!(
+                        "The peer that we were previously subscribing to can no longer service \
+                        the subscriptions! Peer: {:?}, request: {:?}",
+                        peer_network_id, request
+                    )))
+                };
+            }
+        }
+
+        // Otherwise, choose a new peer to handle the subscription request
+        let peer_network_id = choose_random_peer(request, serviceable_peers)?;
+        *active_subscription_state =
+            Some(SubscriptionState::new(peer_network_id, request_stream_id));
+        Ok(peer_network_id)
     }
 
     /// Identifies the peers in the given set of prospective peers
@@ -511,6 +575,15 @@ impl AptosDataClient {
     pub(crate) fn get_peer_states(&self) -> PeerStates {
         self.peer_states.read().clone()
     }
+
+    /// Returns the peer of the active subscription stream for testing
+    #[cfg(test)]
+    pub(crate) fn get_active_subscription_peer(&self) -> Option<PeerNetworkId> {
+        self.active_subscription_state
+            .lock()
+            .as_ref()
+            .map(|subscription_state| subscription_state.peer_network_id)
+    }
 }
 
 #[async_trait]
@@ -614,6 +687,21 @@ impl AptosDataClientInterface for AptosDataClient {
             .await
     }
 
+    async fn get_state_values_with_multi_proof(
+        &self,
+        version: Version,
+        state_keys: Vec<StateKey>,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<StateValuesWithMultiProof>> {
+        let data_request =
+            DataRequest::GetStateValuesWithMultiProof(StateValuesWithMultiProofRequest {
+                version,
+                state_keys,
+            });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_transaction_outputs_with_proof(
         &self,
         proof_version: Version,
@@ -668,6 +756,80 @@ impl AptosDataClientInterface for AptosDataClient {
         self.create_and_send_storage_request(request_timeout_ms, data_request)
             .await
     }
+
+    async fn subscribe_to_transaction_outputs_with_proof(
+        &self,
+        subscription_request_metadata: SubscriptionRequestMetadata,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<(TransactionOutputListWithProof, LedgerInfoWithSignatures)>>
+    {
+        let data_request = DataRequest::SubscribeTransactionOutputsWithProof(
+            SubscribeTransactionOutputsWithProofRequest {
+                subscription_stream_metadata: create_subscription_stream_metadata(
+                    subscription_request_metadata,
+                ),
+                subscription_stream_index: subscription_request_metadata.subscription_stream_index,
+            },
+        );
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
+    async fn subscribe_to_transactions_with_proof(
+        &self,
+        subscription_request_metadata: SubscriptionRequestMetadata,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<(TransactionListWithProof, LedgerInfoWithSignatures)>> {
+        let data_request =
+            DataRequest::SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest {
+                subscription_stream_metadata: create_subscription_stream_metadata(
+                    subscription_request_metadata,
+                ),
+                subscription_stream_index: subscription_request_metadata.subscription_stream_index,
+                include_events,
+            });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
+    async fn subscribe_to_transactions_or_outputs_with_proof(
+        &self,
+        subscription_request_metadata: SubscriptionRequestMetadata,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>>
+    {
+        let data_request = DataRequest::SubscribeTransactionsOrOutputsWithProof(
+            SubscribeTransactionsOrOutputsWithProofRequest {
+                subscription_stream_metadata: create_subscription_stream_metadata(
+                    subscription_request_metadata,
+                ),
+                subscription_stream_index: subscription_request_metadata.subscription_stream_index,
+                include_events,
+                max_num_output_reductions: self.get_max_num_output_reductions(),
+            },
+        );
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+}
+
+/// The state of the active subscription stream, i.e.,
+/// the stream ID and the peer chosen to service it.
+#[derive(Clone, Debug)]
+struct SubscriptionState {
+    peer_network_id: PeerNetworkId,
+    subscription_stream_id: u64,
+}
+
+impl SubscriptionState {
+    fn new(peer_network_id: PeerNetworkId, subscription_stream_id: u64) -> Self {
+        Self {
+            peer_network_id,
+            subscription_stream_id,
+        }
+    }
 }
 
 /// The AptosNet-specific request context needed to update a peer's scoring.
@@ -697,6 +859,34 @@ impl fmt::Debug for AptosNetResponseCallback {
     }
 }
 
+/// Randomly selects a peer from the given serviceable peers
+fn choose_random_peer(
+    request: &StorageServiceRequest,
+    serviceable_peers: Vec<PeerNetworkId>,
+) -> crate::error::Result<PeerNetworkId, Error> {
+    serviceable_peers
+        .choose(&mut rand::thread_rng())
+        .copied()
+        .ok_or_else(|| {
+            Error::DataIsUnavailable(format!(
+                "No connected peers are advertising that they can serve this data! Request: {:?}",
+                request
+            ))
+        })
+}
+
+/// Creates the storage service subscription stream metadata
+/// from the given subscription request metadata.
+fn create_subscription_stream_metadata(
+    subscription_request_metadata: SubscriptionRequestMetadata,
+) -> SubscriptionStreamMetadata {
+    SubscriptionStreamMetadata {
+        known_version_at_stream_start: subscription_request_metadata.known_version_at_stream_start,
+        known_epoch_at_stream_start: subscription_request_metadata.known_epoch_at_stream_start,
+        subscription_stream_id: subscription_request_metadata.subscription_stream_id,
+    }
+}
+
 /// Updates the metrics for the number of connected peers (priority and regular)
 fn update_connected_peer_metrics(num_priority_peers: usize, num_regular_peers: usize) {
     // Log the number of connected peers
@@ -5,7 +5,10 @@ use crate::{error, error::Error, global_summary::GlobalDataSummary};
 use aptos_storage_service_types::{responses::TransactionOrOutputListWithProof, Epoch};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueChunkWithProof, StateValuesWithMultiProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
@@ -87,6 +90,18 @@ pub trait AptosDataClientInterface {
         request_timeout_ms: u64,
     ) -> error::Result<Response<StateValueChunkWithProof>>;
 
+    /// Fetches the values of the given state keys at the specified version,
+    /// together with a single multi-proof that authenticates all of them
+    /// against the state root at that version. In some cases, values may be
+    /// returned for only a prefix of the keys (e.g., to tolerate network or
+    /// chunk limits). If the data cannot be fetched, an error is returned.
+    async fn get_state_values_with_multi_proof(
+        &self,
+        version: Version,
+        state_keys: Vec<StateKey>,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<StateValuesWithMultiProof>>;
+
     /// Fetches a transaction output list with proof, with transaction
     /// outputs from start to end versions (inclusive). The proof is relative
     /// to the specified `proof_version`. In some cases, fewer outputs may be
@@ -129,6 +144,52 @@ pub trait AptosDataClientInterface {
         include_events: bool,
         request_timeout_ms: u64,
     ) -> error::Result<Response<TransactionOrOutputListWithProof>>;
+
+    /// Subscribes to new transaction output lists with proofs. Subscriptions
+    /// start at `known_version + 1` and `known_epoch` (inclusive), as
+    /// specified by the stream metadata. The end version and proof version
+    /// are specified by the server. If the data cannot be fetched, an
+    /// error is returned.
+    async fn subscribe_to_transaction_outputs_with_proof(
+        &self,
+        subscription_request_metadata: SubscriptionRequestMetadata,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionOutputListWithProof, LedgerInfoWithSignatures)>>;
+
+    /// Subscribes to new transaction lists with proofs. Subscriptions start
+    /// at `known_version + 1` and `known_epoch` (inclusive), as specified
+    /// by the subscription metadata. If `include_events` is true,
+    /// events are included in the proof. The end version and proof version
+    /// are specified by the server. If the data cannot be fetched, an
+    /// error is returned.
+    async fn subscribe_to_transactions_with_proof(
+        &self,
+        subscription_request_metadata: SubscriptionRequestMetadata,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionListWithProof, LedgerInfoWithSignatures)>>;
+
+    /// Subscribes to new transaction or output lists with proofs. Subscriptions
+    /// start at `known_version + 1` and `known_epoch` (inclusive), as
+    /// specified by the subscription metadata. If `include_events` is true,
+    /// events are included in the proof. The end version and proof version
+    /// are specified by the server. If the data cannot be fetched, an error
+    /// is returned.
+    async fn subscribe_to_transactions_or_outputs_with_proof(
+        &self,
+        subscription_request_metadata: SubscriptionRequestMetadata,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>>;
+}
+
+/// Subscription stream metadata associated with each subscription request
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SubscriptionRequestMetadata {
+    pub known_version_at_stream_start: u64, // The highest known version at the start of the stream
+    pub known_epoch_at_stream_start: u64,   // The highest known epoch at the start of the stream
+    pub subscription_stream_id: u64,        // The unique id of the subscription stream
+    pub subscription_stream_index: u64,     // The index of the request in the subscription stream
 }
 
 /// A response error that users of the Aptos Data Client can use to notify
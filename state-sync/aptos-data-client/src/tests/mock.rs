@@ -5,7 +5,7 @@ use crate::{
     client::AptosDataClient,
     error::Result,
     global_summary::GlobalDataSummary,
-    interface::{AptosDataClientInterface, Response},
+    interface::{AptosDataClientInterface, Response, SubscriptionRequestMetadata},
     poller::DataSummaryPoller,
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
@@ -32,7 +32,10 @@ use aptos_storage_service_types::{
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueChunkWithProof, StateValuesWithMultiProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
     PeerId,
 };
@@ -245,6 +248,13 @@ mock! {
             request_timeout_ms: u64,
         ) -> Result<Response<StateValueChunkWithProof>>;
 
+        async fn get_state_values_with_multi_proof(
+            &self,
+            version: Version,
+            state_keys: Vec<StateKey>,
+            request_timeout_ms: u64,
+        ) -> Result<Response<StateValuesWithMultiProof>>;
+
         async fn get_transaction_outputs_with_proof(
             &self,
             proof_version: Version,
@@ -270,6 +280,26 @@ mock! {
             include_events: bool,
             request_timeout_ms: u64,
         ) -> Result<Response<TransactionOrOutputListWithProof>>;
+
+        async fn subscribe_to_transaction_outputs_with_proof(
+            &self,
+            subscription_request_metadata: SubscriptionRequestMetadata,
+            request_timeout_ms: u64,
+        ) -> Result<Response<(TransactionOutputListWithProof, LedgerInfoWithSignatures)>>;
+
+        async fn subscribe_to_transactions_with_proof(
+            &self,
+            subscription_request_metadata: SubscriptionRequestMetadata,
+            include_events: bool,
+            request_timeout_ms: u64,
+        ) -> Result<Response<(TransactionListWithProof, LedgerInfoWithSignatures)>>;
+
+        async fn subscribe_to_transactions_or_outputs_with_proof(
+            &self,
+            subscription_request_metadata: SubscriptionRequestMetadata,
+            include_events: bool,
+            request_timeout_ms: u64,
+        ) -> Result<Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>>;
     }
 }
 
@@ -11,7 +11,8 @@ use aptos_config::{
 };
 use aptos_storage_service_types::requests::{
     DataRequest, NewTransactionOutputsWithProofRequest, NewTransactionsWithProofRequest,
-    StorageServiceRequest, TransactionOutputsWithProofRequest,
+    StorageServiceRequest, SubscribeTransactionOutputsWithProofRequest, SubscriptionStreamMetadata,
+    TransactionOutputsWithProofRequest,
 };
 use claims::assert_matches;
 
@@ -252,6 +253,73 @@ async fn prioritized_peer_optimistic_fetch_selection() {
     }
 }
 
+#[tokio::test]
+async fn prioritized_peer_subscription_selection() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+
+    // Create test data
+    let known_version = 1000;
+    let known_epoch = 5;
+
+    // Add two priority peers and advertise the data for both peers
+    let priority_peer_1 = mock_network.add_peer(true);
+    let priority_peer_2 = mock_network.add_peer(true);
+    client.update_summary(
+        priority_peer_1,
+        utils::create_storage_summary(known_version),
+    );
+    client.update_summary(
+        priority_peer_2,
+        utils::create_storage_summary(known_version),
+    );
+
+    // Send the first request of a new subscription stream and get the chosen peer
+    let subscription_stream_id = 10;
+    let storage_request =
+        create_subscription_request(known_version, known_epoch, subscription_stream_id, 0);
+    let chosen_peer = client.choose_peer_for_request(&storage_request).unwrap();
+    assert_eq!(client.get_active_subscription_peer(), Some(chosen_peer));
+
+    // Verify that all requests along the same stream are sent to the same peer
+    for subscription_stream_index in 1..10 {
+        let storage_request = create_subscription_request(
+            known_version,
+            known_epoch,
+            subscription_stream_id,
+            subscription_stream_index,
+        );
+        assert_eq!(
+            client.choose_peer_for_request(&storage_request),
+            Ok(chosen_peer)
+        );
+    }
+
+    // Disconnect the chosen peer and verify the next request on the stream fails
+    mock_network.disconnect_peer(chosen_peer);
+    let storage_request =
+        create_subscription_request(known_version, known_epoch, subscription_stream_id, 10);
+    assert_matches!(
+        client.choose_peer_for_request(&storage_request),
+        Err(Error::DataIsUnavailable(_))
+    );
+    assert_eq!(client.get_active_subscription_peer(), None);
+
+    // Start a new subscription stream and verify the other peer is chosen
+    let other_peer = if chosen_peer == priority_peer_1 {
+        priority_peer_2
+    } else {
+        priority_peer_1
+    };
+    let storage_request =
+        create_subscription_request(known_version, known_epoch, subscription_stream_id + 1, 0);
+    assert_eq!(
+        client.choose_peer_for_request(&storage_request),
+        Ok(other_peer)
+    );
+    assert_eq!(client.get_active_subscription_peer(), Some(other_peer));
+}
+
 #[tokio::test]
 async fn validator_peer_prioritization() {
     ::aptos_logger::Logger::init_for_testing();
@@ -324,3 +392,23 @@ async fn pfn_peer_prioritization() {
     assert_eq!(priority_peers, vec![outbound_peer]);
     assert_eq!(regular_peers, vec![inbound_peer]);
 }
+
+/// Creates a subscription request for transaction outputs
+fn create_subscription_request(
+    known_version: u64,
+    known_epoch: u64,
+    subscription_stream_id: u64,
+    subscription_stream_index: u64,
+) -> StorageServiceRequest {
+    let data_request = DataRequest::SubscribeTransactionOutputsWithProof(
+        SubscribeTransactionOutputsWithProofRequest {
+            subscription_stream_metadata: SubscriptionStreamMetadata {
+                known_version_at_stream_start: known_version,
+                known_epoch_at_stream_start: known_epoch,
+                subscription_stream_id,
+            },
+            subscription_stream_index,
+        },
+    );
+    StorageServiceRequest::new(data_request, true)
+}
@@ -202,7 +202,11 @@ impl EventSubscriptionService {
         let mut event_subscription_ids_to_notify = HashSet::new();
 
         for event in events.iter() {
-            let event_key = event.key();
+            // Module events don't have an event key, so they can't be subscribed to
+            let event_key = match event.event_key() {
+                Some(event_key) => event_key,
+                None => continue,
+            };
 
             // Process all subscriptions for the current event
             if let Some(subscription_ids) = self.event_key_subscriptions.get(event_key) {
@@ -46,6 +46,9 @@ pub enum DataClientRequest {
     TransactionOutputsWithProof(TransactionOutputsWithProofRequest),
     NewTransactionsOrOutputsWithProof(NewTransactionsOrOutputsWithProofRequest),
     TransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest),
+    SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest),
+    SubscribeTransactionOutputsWithProof(SubscribeTransactionOutputsWithProofRequest),
+    SubscribeTransactionsOrOutputsWithProof(SubscribeTransactionsOrOutputsWithProofRequest),
 }
 
 impl DataClientRequest {
@@ -61,8 +64,22 @@ impl DataClientRequest {
             Self::TransactionOutputsWithProof(_) => "transaction_outputs_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::SubscribeTransactionsWithProof(_) => "subscribe_transactions_with_proof",
+            Self::SubscribeTransactionOutputsWithProof(_) => {
+                "subscribe_transaction_outputs_with_proof"
+            },
+            Self::SubscribeTransactionsOrOutputsWithProof(_) => {
+                "subscribe_transactions_or_outputs_with_proof"
+            },
         }
     }
+
+    /// Returns true iff the request is a subscription request
+    pub fn is_subscription_request(&self) -> bool {
+        matches!(self, Self::SubscribeTransactionsWithProof(_))
+            || matches!(self, Self::SubscribeTransactionOutputsWithProof(_))
+            || matches!(self, Self::SubscribeTransactionsOrOutputsWithProof(_))
+    }
 }
 
 /// A request for fetching states values.
@@ -103,6 +120,35 @@ pub struct NewTransactionOutputsWithProofRequest {
     pub known_epoch: Epoch,
 }
 
+/// A client request for subscribing to transactions with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscribeTransactionsWithProofRequest {
+    pub known_version: Version,
+    pub known_epoch: Epoch,
+    pub include_events: bool,
+    pub subscription_stream_id: u64,
+    pub subscription_stream_index: u64,
+}
+
+/// A client request for subscribing to transaction outputs with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscribeTransactionOutputsWithProofRequest {
+    pub known_version: Version,
+    pub known_epoch: Epoch,
+    pub subscription_stream_id: u64,
+    pub subscription_stream_index: u64,
+}
+
+/// A client request for subscribing to transactions or outputs with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscribeTransactionsOrOutputsWithProofRequest {
+    pub known_version: Version,
+    pub known_epoch: Epoch,
+    pub include_events: bool,
+    pub subscription_stream_id: u64,
+    pub subscription_stream_index: u64,
+}
+
 /// A client request for fetching the number of states at a version.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct NumberOfStatesRequest {
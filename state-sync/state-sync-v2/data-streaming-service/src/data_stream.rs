@@ -8,8 +8,10 @@ use crate::{
         DataClientRequest, DataNotification, DataPayload, EpochEndingLedgerInfosRequest,
         NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
         NewTransactionsWithProofRequest, NotificationId, NumberOfStatesRequest,
-        StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
-        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+        StateValuesWithProofRequest, SubscribeTransactionOutputsWithProofRequest,
+        SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+        TransactionsWithProofRequest,
     },
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
@@ -23,6 +25,7 @@ use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary},
     interface::{
         AptosDataClientInterface, Response, ResponseContext, ResponseError, ResponsePayload,
+        SubscriptionRequestMetadata,
     },
 };
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
@@ -121,7 +124,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         let data_stream_listener = DataStreamListener::new(data_stream_id, notification_receiver);
 
         // Create a new stream engine
-        let stream_engine = StreamEngine::new(stream_request, advertised_data)?;
+        let stream_engine = StreamEngine::new(data_stream_config, stream_request, advertised_data)?;
 
         // Create a new data stream
         let data_stream = Self {
@@ -285,6 +288,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         // request type and the number of previous failures.
         let request_timeout_ms = if is_optimistic_fetch_request(&data_client_request) {
             self.data_client_config.optimistic_fetch_timeout_ms
+        } else if data_client_request.is_subscription_request() {
+            self.data_client_config.subscription_response_timeout_ms
         } else if !request_retry {
             self.data_client_config.response_timeout_ms
         } else {
@@ -405,6 +410,13 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                         if sanity_check_client_response(client_request, &client_response) {
                             self.send_data_notification_to_client(client_request, client_response)
                                 .await?;
+                        } else if client_request.is_subscription_request() {
+                            self.notify_bad_response(
+                                &client_response.context,
+                                ResponseError::InvalidPayloadDataType,
+                            );
+                            self.handle_subscription_failure(client_request)?;
+                            break;
                         } else {
                             self.handle_sanity_check_failure(
                                 client_request,
@@ -414,9 +426,13 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                         }
                     },
                     Err(error) => {
-                        // If the error was a timeout and the request was an optimistic fetch
-                        // we need to notify the stream engine and not retry the request.
-                        if matches!(
+                        // If the request was a subscription request we need to notify the
+                        // stream engine and not retry the request (the stream is restarted).
+                        // Likewise, if the error was a timeout and the request was an
+                        // optimistic fetch, we need to notify the stream engine.
+                        if client_request.is_subscription_request() {
+                            self.handle_subscription_failure(client_request)?;
+                        } else if matches!(
                             error,
                             aptos_data_client::error::Error::TimeoutWaitingForResponse(_)
                         ) && is_optimistic_fetch_request(client_request)
@@ -456,6 +472,29 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         Ok(pending_client_response)
     }
 
+    /// Handles a subscription request that failed (e.g., due to an error or an
+    /// invalid response). All pending subscription requests are dropped, and
+    /// the stream engine is notified so that a new subscription stream is started.
+    fn handle_subscription_failure(
+        &mut self,
+        data_client_request: &DataClientRequest,
+    ) -> Result<(), Error> {
+        warn!(LogSchema::new(LogEntry::ReceivedDataResponse)
+            .stream_id(self.data_stream_id)
+            .event(LogEvent::Error)
+            .message(&format!(
+                "Subscription request failed! Resetting the subscription stream. Request: {:?}",
+                data_client_request
+            )));
+
+        // Clear the pending subscription requests (they belong to the failed stream)
+        self.get_sent_data_requests()?.clear();
+
+        // Notify the stream engine of the failure
+        self.stream_engine
+            .notify_subscription_failure(data_client_request)
+    }
+
     /// Handles a client response that failed sanity checks
     fn handle_sanity_check_failure(
         &mut self,
@@ -778,6 +817,27 @@ fn sanity_check_client_response(
                 ResponsePayload::TransactionOutputsWithProof(_)
             )
         },
+        DataClientRequest::SubscribeTransactionOutputsWithProof(_) => {
+            matches!(
+                data_client_response.payload,
+                ResponsePayload::NewTransactionOutputsWithProof(_)
+            )
+        },
+        DataClientRequest::SubscribeTransactionsWithProof(_) => {
+            matches!(
+                data_client_response.payload,
+                ResponsePayload::NewTransactionsWithProof(_)
+            )
+        },
+        DataClientRequest::SubscribeTransactionsOrOutputsWithProof(_) => {
+            matches!(
+                data_client_response.payload,
+                ResponsePayload::NewTransactionsWithProof(_)
+            ) || matches!(
+                data_client_response.payload,
+                ResponsePayload::NewTransactionOutputsWithProof(_)
+            )
+        },
     }
 }
 
@@ -848,6 +908,26 @@ fn spawn_request_task<T: AptosDataClientInterface + Send + Clone + 'static>(
             DataClientRequest::StateValuesWithProof(request) => {
                 get_states_values_with_proof(aptos_data_client, request, request_timeout_ms).await
             },
+            DataClientRequest::SubscribeTransactionOutputsWithProof(request) => {
+                subscribe_to_transaction_outputs_with_proof(
+                    aptos_data_client,
+                    request,
+                    request_timeout_ms,
+                )
+                .await
+            },
+            DataClientRequest::SubscribeTransactionsWithProof(request) => {
+                subscribe_to_transactions_with_proof(aptos_data_client, request, request_timeout_ms)
+                    .await
+            },
+            DataClientRequest::SubscribeTransactionsOrOutputsWithProof(request) => {
+                subscribe_to_transactions_or_outputs_with_proof(
+                    aptos_data_client,
+                    request,
+                    request_timeout_ms,
+                )
+                .await
+            },
             DataClientRequest::TransactionOutputsWithProof(request) => {
                 get_transaction_outputs_with_proof(aptos_data_client, request, request_timeout_ms)
                     .await
@@ -976,6 +1056,73 @@ async fn get_number_of_states<T: AptosDataClientInterface + Send + Clone + 'stat
         .map(|response| response.map(ResponsePayload::from))
 }
 
+async fn subscribe_to_transaction_outputs_with_proof<
+    T: AptosDataClientInterface + Send + Clone + 'static,
+>(
+    aptos_data_client: T,
+    request: SubscribeTransactionOutputsWithProofRequest,
+    request_timeout_ms: u64,
+) -> Result<Response<ResponsePayload>, aptos_data_client::error::Error> {
+    let subscription_request_metadata = SubscriptionRequestMetadata {
+        known_version_at_stream_start: request.known_version,
+        known_epoch_at_stream_start: request.known_epoch,
+        subscription_stream_id: request.subscription_stream_id,
+        subscription_stream_index: request.subscription_stream_index,
+    };
+    let client_response = aptos_data_client.subscribe_to_transaction_outputs_with_proof(
+        subscription_request_metadata,
+        request_timeout_ms,
+    );
+    client_response
+        .await
+        .map(|response| response.map(ResponsePayload::from))
+}
+
+async fn subscribe_to_transactions_with_proof<
+    T: AptosDataClientInterface + Send + Clone + 'static,
+>(
+    aptos_data_client: T,
+    request: SubscribeTransactionsWithProofRequest,
+    request_timeout_ms: u64,
+) -> Result<Response<ResponsePayload>, aptos_data_client::error::Error> {
+    let subscription_request_metadata = SubscriptionRequestMetadata {
+        known_version_at_stream_start: request.known_version,
+        known_epoch_at_stream_start: request.known_epoch,
+        subscription_stream_id: request.subscription_stream_id,
+        subscription_stream_index: request.subscription_stream_index,
+    };
+    let client_response = aptos_data_client.subscribe_to_transactions_with_proof(
+        subscription_request_metadata,
+        request.include_events,
+        request_timeout_ms,
+    );
+    client_response
+        .await
+        .map(|response| response.map(ResponsePayload::from))
+}
+
+async fn subscribe_to_transactions_or_outputs_with_proof<
+    T: AptosDataClientInterface + Send + Clone + 'static,
+>(
+    aptos_data_client: T,
+    request: SubscribeTransactionsOrOutputsWithProofRequest,
+    request_timeout_ms: u64,
+) -> Result<Response<ResponsePayload>, aptos_data_client::error::Error> {
+    let subscription_request_metadata = SubscriptionRequestMetadata {
+        known_version_at_stream_start: request.known_version,
+        known_epoch_at_stream_start: request.known_epoch,
+        subscription_stream_id: request.subscription_stream_id,
+        subscription_stream_index: request.subscription_stream_index,
+    };
+    let client_response = aptos_data_client.subscribe_to_transactions_or_outputs_with_proof(
+        subscription_request_metadata,
+        request.include_events,
+        request_timeout_ms,
+    );
+    let (context, payload) = client_response.await?.into_parts();
+    Ok(Response::new(context, ResponsePayload::try_from(payload)?))
+}
+
 async fn get_transaction_outputs_with_proof<
     T: AptosDataClientInterface + Send + Clone + 'static,
 >(
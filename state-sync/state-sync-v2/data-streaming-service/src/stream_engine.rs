@@ -8,12 +8,15 @@ use crate::{
         DataClientRequest::{
             EpochEndingLedgerInfos, NewTransactionOutputsWithProof,
             NewTransactionsOrOutputsWithProof, NewTransactionsWithProof, NumberOfStates,
-            StateValuesWithProof, TransactionOutputsWithProof, TransactionsOrOutputsWithProof,
-            TransactionsWithProof,
+            StateValuesWithProof, SubscribeTransactionOutputsWithProof,
+            SubscribeTransactionsOrOutputsWithProof, SubscribeTransactionsWithProof,
+            TransactionOutputsWithProof, TransactionsOrOutputsWithProof, TransactionsWithProof,
         },
         DataNotification, DataPayload, EpochEndingLedgerInfosRequest,
         NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
         NewTransactionsWithProofRequest, NumberOfStatesRequest, StateValuesWithProofRequest,
+        SubscribeTransactionOutputsWithProofRequest,
+        SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
     },
@@ -23,6 +26,7 @@ use crate::{
         Epoch, GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest, StreamRequest,
     },
 };
+use aptos_config::config::DataStreamingServiceConfig;
 use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary},
     interface::ResponsePayload,
@@ -91,6 +95,18 @@ pub trait DataStreamEngine {
         Err(Error::UnexpectedErrorEncountered(format!("Received an optimistic fetch request timeout but no request was sent! Reported request: {:?}", client_request)))
     }
 
+    /// Notifies the data stream engine that a failure was encountered when
+    /// trying to send a subscription request (e.g., a timeout or an error).
+    ///
+    /// Note: Most engines shouldn't process these notifications, so a default
+    /// implementation that returns an error is provided.
+    fn notify_subscription_failure(
+        &mut self,
+        client_request: &DataClientRequest,
+    ) -> Result<(), Error> {
+        Err(Error::UnexpectedErrorEncountered(format!("Received a subscription request failure but no request was sent! Reported request: {:?}", client_request)))
+    }
+
     /// Transforms a given data client response (for the previously sent
     /// request) into a data notification to be sent along the data stream.
     /// Note: this call may return `None`, in which case, no notification needs
@@ -117,19 +133,23 @@ pub enum StreamEngine {
 
 impl StreamEngine {
     pub fn new(
+        data_streaming_config: DataStreamingServiceConfig,
         stream_request: &StreamRequest,
         advertised_data: &AdvertisedData,
     ) -> Result<Self, Error> {
         match stream_request {
-            StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
-                Ok(ContinuousTransactionStreamEngine::new(stream_request)?.into())
-            },
-            StreamRequest::ContinuouslyStreamTransactions(_) => {
-                Ok(ContinuousTransactionStreamEngine::new(stream_request)?.into())
-            },
-            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
-                Ok(ContinuousTransactionStreamEngine::new(stream_request)?.into())
-            },
+            StreamRequest::ContinuouslyStreamTransactionOutputs(_) => Ok(
+                ContinuousTransactionStreamEngine::new(data_streaming_config, stream_request)?
+                    .into(),
+            ),
+            StreamRequest::ContinuouslyStreamTransactions(_) => Ok(
+                ContinuousTransactionStreamEngine::new(data_streaming_config, stream_request)?
+                    .into(),
+            ),
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => Ok(
+                ContinuousTransactionStreamEngine::new(data_streaming_config, stream_request)?
+                    .into(),
+            ),
             StreamRequest::GetAllStates(request) => Ok(StateStreamEngine::new(request)?.into()),
             StreamRequest::GetAllEpochEndingLedgerInfos(request) => {
                 Ok(EpochEndingStreamEngine::new(request, advertised_data)?.into())
@@ -335,12 +355,86 @@ impl DataStreamEngine for StateStreamEngine {
     }
 }
 
+/// The state of an active subscription stream (i.e., a stream of pipelined
+/// subscription requests sent to a single peer).
+#[derive(Clone, Debug)]
+pub struct SubscriptionStream {
+    known_version_at_stream_start: Version, // The known version at the stream start
+    known_epoch_at_stream_start: Epoch,     // The known epoch at the stream start
+    subscription_stream_id: u64,            // The unique id of the subscription stream
+    next_subscription_stream_index: u64,    // The next request index to send
+    max_subscription_stream_index: u64,     // The last request index of the stream
+}
+
+impl SubscriptionStream {
+    pub fn new(
+        data_streaming_config: DataStreamingServiceConfig,
+        known_version_at_stream_start: Version,
+        known_epoch_at_stream_start: Epoch,
+        subscription_stream_id: u64,
+    ) -> Result<Self, Error> {
+        let max_subscription_stream_index = data_streaming_config
+            .max_num_consecutive_subscriptions
+            .checked_sub(1)
+            .ok_or_else(|| {
+                Error::IntegerOverflow("Max subscription stream index has overflown!".into())
+            })?;
+
+        Ok(Self {
+            known_version_at_stream_start,
+            known_epoch_at_stream_start,
+            subscription_stream_id,
+            next_subscription_stream_index: 0,
+            max_subscription_stream_index,
+        })
+    }
+
+    /// Returns true iff all requests for the subscription stream have been sent
+    pub fn all_requests_sent(&self) -> bool {
+        self.next_subscription_stream_index > self.max_subscription_stream_index
+    }
+
+    /// Returns the next subscription stream index and increments it
+    pub fn get_and_increment_next_index(&mut self) -> Result<u64, Error> {
+        let next_index = self.next_subscription_stream_index;
+        self.next_subscription_stream_index = next_index.checked_add(1).ok_or_else(|| {
+            Error::IntegerOverflow("Next subscription stream index has overflown!".into())
+        })?;
+        Ok(next_index)
+    }
+
+    pub fn get_known_version_at_stream_start(&self) -> Version {
+        self.known_version_at_stream_start
+    }
+
+    pub fn get_known_epoch_at_stream_start(&self) -> Epoch {
+        self.known_epoch_at_stream_start
+    }
+
+    pub fn get_max_subscription_stream_index(&self) -> u64 {
+        self.max_subscription_stream_index
+    }
+
+    pub fn get_subscription_stream_id(&self) -> u64 {
+        self.subscription_stream_id
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ContinuousTransactionStreamEngine {
+    // The data streaming service config
+    pub data_streaming_config: DataStreamingServiceConfig,
+
     // The original stream request made by the client (i.e., a continuous
     // transaction or transaction output stream request).
     pub request: StreamRequest,
 
+    // The currently active subscription stream (if any)
+    pub active_subscription_stream: Option<SubscriptionStream>,
+
+    // The id generator for new subscription streams
+    subscription_stream_id_generator: Arc<U64IdGenerator>,
+
     // The target ledger info that we're currently syncing to
     pub current_target_ledger_info: Option<LedgerInfoWithSignatures>,
 
@@ -364,7 +458,10 @@ pub struct ContinuousTransactionStreamEngine {
 }
 
 impl ContinuousTransactionStreamEngine {
-    fn new(stream_request: &StreamRequest) -> Result<Self, Error> {
+    fn new(
+        data_streaming_config: DataStreamingServiceConfig,
+        stream_request: &StreamRequest,
+    ) -> Result<Self, Error> {
         let (next_version, next_epoch) = match stream_request {
             StreamRequest::ContinuouslyStreamTransactions(request) => {
                 Self::calculate_next_version_and_epoch(request.known_version, request.known_epoch)?
@@ -378,8 +475,16 @@ impl ContinuousTransactionStreamEngine {
             request => invalid_stream_request!(request),
         };
 
+        // Seed the subscription stream ids randomly, so that different data
+        // streams are unlikely to reuse the ids of older subscription streams.
+        let subscription_stream_id_generator =
+            Arc::new(U64IdGenerator::new_with_value(rand::random::<u32>() as u64));
+
         Ok(ContinuousTransactionStreamEngine {
+            data_streaming_config,
             request: stream_request.clone(),
+            active_subscription_stream: None,
+            subscription_stream_id_generator,
             current_target_ledger_info: None,
             end_of_epoch_requested: false,
             optimistic_fetch_requested: false,
@@ -556,6 +661,109 @@ impl ContinuousTransactionStreamEngine {
         Ok(data_client_request)
     }
 
+    fn create_notification_for_subscription_data(
+        &mut self,
+        subscription_stream_index: u64,
+        client_response_payload: ResponsePayload,
+        notification_id_generator: Arc<U64IdGenerator>,
+    ) -> Result<DataNotification, Error> {
+        // The subscription data always follows the last version sent along the stream
+        let (next_stream_version, _) = self.next_stream_version_and_epoch;
+        let known_version = next_stream_version
+            .checked_sub(1)
+            .ok_or_else(|| Error::IntegerOverflow("Known version has overflown!".into()))?;
+        let data_notification = self.create_notification_for_optimistic_fetch_data(
+            known_version,
+            client_response_payload,
+            notification_id_generator,
+        )?;
+
+        // If this was the last response of the subscription stream, end the stream
+        if let Some(active_subscription_stream) = &self.active_subscription_stream {
+            if subscription_stream_index
+                >= active_subscription_stream.get_max_subscription_stream_index()
+            {
+                self.active_subscription_stream = None;
+            }
+        }
+
+        Ok(data_notification)
+    }
+
+    fn create_subscription_stream_requests(
+        &mut self,
+        max_number_of_requests: u64,
+    ) -> Result<Vec<DataClientRequest>, Error> {
+        // Start a new subscription stream if there isn't one
+        if self.active_subscription_stream.is_none() {
+            let (next_request_version, known_epoch) = self.next_request_version_and_epoch;
+            let known_version = next_request_version
+                .checked_sub(1)
+                .ok_or_else(|| Error::IntegerOverflow("Known version has overflown!".into()))?;
+            self.active_subscription_stream = Some(SubscriptionStream::new(
+                self.data_streaming_config,
+                known_version,
+                known_epoch,
+                self.subscription_stream_id_generator.next(),
+            )?);
+        }
+
+        // Create the subscription requests (pipelined along the same stream)
+        let active_subscription_stream =
+            self.active_subscription_stream.as_mut().ok_or_else(|| {
+                Error::UnexpectedErrorEncountered("No active subscription stream found!".into())
+            })?;
+        let known_version = active_subscription_stream.get_known_version_at_stream_start();
+        let known_epoch = active_subscription_stream.get_known_epoch_at_stream_start();
+        let subscription_stream_id = active_subscription_stream.get_subscription_stream_id();
+
+        let mut client_requests = vec![];
+        for _ in 0..max_number_of_requests {
+            if active_subscription_stream.all_requests_sent() {
+                break; // We've hit the end of the subscription stream
+            }
+
+            let subscription_stream_index =
+                active_subscription_stream.get_and_increment_next_index()?;
+            let client_request = match &self.request {
+                StreamRequest::ContinuouslyStreamTransactions(request) => {
+                    SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest {
+                        known_version,
+                        known_epoch,
+                        include_events: request.include_events,
+                        subscription_stream_id,
+                        subscription_stream_index,
+                    })
+                },
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                    SubscribeTransactionOutputsWithProof(
+                        SubscribeTransactionOutputsWithProofRequest {
+                            known_version,
+                            known_epoch,
+                            subscription_stream_id,
+                            subscription_stream_index,
+                        },
+                    )
+                },
+                StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
+                    SubscribeTransactionsOrOutputsWithProof(
+                        SubscribeTransactionsOrOutputsWithProofRequest {
+                            known_version,
+                            known_epoch,
+                            include_events: request.include_events,
+                            subscription_stream_id,
+                            subscription_stream_index,
+                        },
+                    )
+                },
+                request => invalid_stream_request!(request),
+            };
+            client_requests.push(client_request);
+        }
+
+        Ok(client_requests)
+    }
+
     fn handle_epoch_ending_response(
         &mut self,
         response_payload: ResponsePayload,
@@ -741,6 +949,11 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
             return Ok(vec![]); // We are waiting for a blocking response type
         }
 
+        // If there's an active subscription stream, continue to pipeline requests
+        if self.active_subscription_stream.is_some() {
+            return self.create_subscription_stream_requests(max_number_of_requests);
+        }
+
         // If we don't have a syncing target, try to select one
         let (next_request_version, next_request_epoch) = self.next_request_version_and_epoch;
         if self.current_target_ledger_info.is_none() {
@@ -816,6 +1029,9 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
             )?;
             self.update_request_tracking(&client_requests, &target_ledger_info)?;
             client_requests
+        } else if self.data_streaming_config.enable_subscription_streaming {
+            // We don't have a target, start a new subscription stream
+            self.create_subscription_stream_requests(max_number_of_requests)?
         } else {
             // We don't have a target, send a single optimistic fetch request
             let optimistic_fetch_request = self.create_optimistic_fetch_request()?;
@@ -905,6 +1121,39 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
         Ok(())
     }
 
+    fn notify_subscription_failure(
+        &mut self,
+        client_request: &DataClientRequest,
+    ) -> Result<(), Error> {
+        if !client_request.is_subscription_request() {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Received a subscription failure for a non-subscription request! Request: {:?}",
+                client_request
+            )));
+        }
+
+        // Reset the active subscription stream (a new one will be started)
+        if self.active_subscription_stream.take().is_none() {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Received a subscription failure but no stream is active! Request: {:?}",
+                client_request
+            )));
+        }
+        info!(
+            (LogSchema::new(LogEntry::ReceivedDataResponse)
+                .event(LogEvent::Error)
+                .message(&format!(
+                    "Subscription request failed, resetting the subscription stream! Request: {:?}",
+                    client_request
+                )))
+        );
+
+        // Reset the next request version and epoch to the stream version and epoch
+        self.next_request_version_and_epoch = self.next_stream_version_and_epoch;
+
+        Ok(())
+    }
+
     fn transform_client_response_into_notification(
         &mut self,
         client_request: &DataClientRequest,
@@ -957,6 +1206,39 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 },
                 request => invalid_stream_request!(request),
             },
+            SubscribeTransactionsWithProof(request) => match &self.request {
+                StreamRequest::ContinuouslyStreamTransactions(_) => {
+                    let data_notification = self.create_notification_for_subscription_data(
+                        request.subscription_stream_index,
+                        client_response_payload,
+                        notification_id_generator,
+                    )?;
+                    Ok(Some(data_notification))
+                },
+                request => invalid_stream_request!(request),
+            },
+            SubscribeTransactionOutputsWithProof(request) => match &self.request {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                    let data_notification = self.create_notification_for_subscription_data(
+                        request.subscription_stream_index,
+                        client_response_payload,
+                        notification_id_generator,
+                    )?;
+                    Ok(Some(data_notification))
+                },
+                request => invalid_stream_request!(request),
+            },
+            SubscribeTransactionsOrOutputsWithProof(request) => match &self.request {
+                StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                    let data_notification = self.create_notification_for_subscription_data(
+                        request.subscription_stream_index,
+                        client_response_payload,
+                        notification_id_generator,
+                    )?;
+                    Ok(Some(data_notification))
+                },
+                request => invalid_stream_request!(request),
+            },
             TransactionsWithProof(request) => match &self.request {
                 StreamRequest::ContinuouslyStreamTransactions(_) => {
                     let data_notification = self.create_notification_for_continuous_data(
@@ -3,12 +3,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    data_notification::{DataClientRequest, EpochEndingLedgerInfosRequest},
+    data_notification::{
+        DataClientRequest, EpochEndingLedgerInfosRequest,
+        SubscribeTransactionOutputsWithProofRequest,
+    },
     error::Error,
     stream_engine::{DataStreamEngine, EpochEndingStreamEngine, StreamEngine},
-    streaming_client::{GetAllEpochEndingLedgerInfosRequest, StreamRequest},
-    tests::utils::initialize_logger,
+    streaming_client::{
+        ContinuouslyStreamTransactionOutputsRequest, GetAllEpochEndingLedgerInfosRequest,
+        StreamRequest,
+    },
+    tests::utils::{create_ledger_info, create_output_list_with_proof, initialize_logger},
 };
+use aptos_config::config::DataStreamingServiceConfig;
 use aptos_data_client::{
     global_summary::{GlobalDataSummary, OptimalChunkSizes},
     interface::ResponsePayload,
@@ -144,7 +151,11 @@ fn test_epoch_ending_stream_engine() {
 
     // Try to create a stream engine where there is no advertised data
     // and verify an error is returned.
-    let result = StreamEngine::new(&stream_request, &GlobalDataSummary::empty().advertised_data);
+    let result = StreamEngine::new(
+        DataStreamingServiceConfig::default(),
+        &stream_request,
+        &GlobalDataSummary::empty().advertised_data,
+    );
     assert_matches!(result, Err(Error::DataIsUnavailable(_)));
 
     // Create a data summary with various advertised epoch ranges (highest is one)
@@ -158,7 +169,11 @@ fn test_epoch_ending_stream_engine() {
     ];
 
     // Try to create a stream engine where the highest epoch is one
-    let result = StreamEngine::new(&stream_request, &global_data_summary.advertised_data);
+    let result = StreamEngine::new(
+        DataStreamingServiceConfig::default(),
+        &stream_request,
+        &global_data_summary.advertised_data,
+    );
     assert_ok!(result);
 
     // Create a global data summary with non-zero advertised epoch ranges
@@ -173,7 +188,13 @@ fn test_epoch_ending_stream_engine() {
     ];
 
     // Create a new data stream engine and verify the highest epoch is chosen
-    match StreamEngine::new(&stream_request, &global_data_summary.advertised_data).unwrap() {
+    match StreamEngine::new(
+        DataStreamingServiceConfig::default(),
+        &stream_request,
+        &global_data_summary.advertised_data,
+    )
+    .unwrap()
+    {
         StreamEngine::EpochEndingStreamEngine(stream_engine) => {
             assert_eq!(stream_engine.end_epoch, 1000);
         },
@@ -186,6 +207,108 @@ fn test_epoch_ending_stream_engine() {
     }
 }
 
+#[test]
+fn test_subscription_stream_requests() {
+    initialize_logger();
+
+    // Create a continuous output stream request
+    let known_version = 100;
+    let known_epoch = 5;
+    let stream_request = StreamRequest::ContinuouslyStreamTransactionOutputs(
+        ContinuouslyStreamTransactionOutputsRequest {
+            known_version,
+            known_epoch,
+            target: None,
+        },
+    );
+
+    // Create a global data summary where the highest synced version is already known
+    let mut global_data_summary = GlobalDataSummary::empty();
+    global_data_summary.advertised_data.synced_ledger_infos =
+        vec![create_ledger_info(known_version, known_epoch, false)];
+
+    // Create a continuous stream engine with subscription streaming enabled
+    let max_num_consecutive_subscriptions = 3;
+    let data_streaming_config = DataStreamingServiceConfig {
+        enable_subscription_streaming: true,
+        max_num_consecutive_subscriptions,
+        ..Default::default()
+    };
+    let mut stream_engine = match StreamEngine::new(
+        data_streaming_config,
+        &stream_request,
+        &global_data_summary.advertised_data,
+    )
+    .unwrap()
+    {
+        StreamEngine::ContinuousTransactionStreamEngine(stream_engine) => stream_engine,
+        unexpected_engine => {
+            panic!(
+                "Expected continuous transaction stream engine but got {:?}",
+                unexpected_engine
+            );
+        },
+    };
+
+    // Create a batch of client requests and verify they belong to a single subscription stream
+    let client_requests = stream_engine
+        .create_data_client_requests(10, &global_data_summary)
+        .unwrap();
+    assert_eq!(
+        client_requests.len() as u64,
+        max_num_consecutive_subscriptions
+    );
+    let subscription_stream_id =
+        verify_subscription_requests(&client_requests, known_version, known_epoch, None);
+
+    // Verify no more requests are created (the subscription stream is exhausted)
+    let client_requests_after = stream_engine
+        .create_data_client_requests(10, &global_data_summary)
+        .unwrap();
+    assert!(client_requests_after.is_empty());
+
+    // Handle a response for the first request and verify the stream progresses
+    let num_versions = 10;
+    let target_ledger_info = create_ledger_info(known_version + num_versions, known_epoch, false);
+    let client_response_payload = ResponsePayload::NewTransactionOutputsWithProof((
+        create_output_list_with_proof(known_version + 1, known_version + num_versions),
+        target_ledger_info,
+    ));
+    let data_notification = stream_engine
+        .transform_client_response_into_notification(
+            &client_requests[0],
+            client_response_payload,
+            create_notification_id_generator(),
+        )
+        .unwrap();
+    assert!(data_notification.is_some());
+    assert_eq!(
+        stream_engine.next_stream_version_and_epoch,
+        (known_version + num_versions + 1, known_epoch)
+    );
+
+    // Notify the stream engine of a subscription failure
+    stream_engine
+        .notify_subscription_failure(&client_requests[1])
+        .unwrap();
+    assert!(stream_engine.active_subscription_stream.is_none());
+
+    // Verify a new subscription stream is started at the latest stream version
+    let client_requests = stream_engine
+        .create_data_client_requests(10, &global_data_summary)
+        .unwrap();
+    assert_eq!(
+        client_requests.len() as u64,
+        max_num_consecutive_subscriptions
+    );
+    verify_subscription_requests(
+        &client_requests,
+        known_version + num_versions,
+        known_epoch,
+        Some(subscription_stream_id),
+    );
+}
+
 #[test]
 fn test_update_epoch_ending_stream_progress() {
     // Create a new data stream engine
@@ -258,7 +381,13 @@ fn create_epoch_ending_stream_engine(start_epoch: u64, end_epoch: u64) -> EpochE
         .epoch_ending_ledger_infos = vec![CompleteDataRange::new(start_epoch, end_epoch).unwrap()];
 
     // Create a new epoch ending stream engine
-    match StreamEngine::new(&stream_request, &global_data_summary.advertised_data).unwrap() {
+    match StreamEngine::new(
+        DataStreamingServiceConfig::default(),
+        &stream_request,
+        &global_data_summary.advertised_data,
+    )
+    .unwrap()
+    {
         StreamEngine::EpochEndingStreamEngine(stream_engine) => stream_engine,
         unexpected_engine => {
             panic!(
@@ -269,6 +398,41 @@ fn create_epoch_ending_stream_engine(start_epoch: u64, end_epoch: u64) -> EpochE
     }
 }
 
+/// Verifies the given requests are subscription requests for a single stream
+/// (with the expected known version and epoch, and sequential indices) and
+/// returns the stream id. If an old stream id is given, the stream id must differ.
+fn verify_subscription_requests(
+    client_requests: &[DataClientRequest],
+    known_version: u64,
+    known_epoch: u64,
+    old_subscription_stream_id: Option<u64>,
+) -> u64 {
+    let mut subscription_stream_id = None;
+    for (index, client_request) in client_requests.iter().enumerate() {
+        match client_request {
+            DataClientRequest::SubscribeTransactionOutputsWithProof(
+                SubscribeTransactionOutputsWithProofRequest {
+                    known_version: request_known_version,
+                    known_epoch: request_known_epoch,
+                    subscription_stream_id: request_stream_id,
+                    subscription_stream_index,
+                },
+            ) => {
+                assert_eq!(*request_known_version, known_version);
+                assert_eq!(*request_known_epoch, known_epoch);
+                assert_eq!(*subscription_stream_index, index as u64);
+                assert_eq!(
+                    *subscription_stream_id.get_or_insert(*request_stream_id),
+                    *request_stream_id
+                );
+                assert_ne!(Some(*request_stream_id), old_subscription_stream_id);
+            },
+            request => panic!("Expected a subscription request but got: {:?}", request),
+        }
+    }
+    subscription_stream_id.unwrap()
+}
+
 fn create_epoch_ending_chunk_sizes(epoch_chunk_size: u64) -> GlobalDataSummary {
     let mut optimal_chunk_sizes = OptimalChunkSizes::empty();
     optimal_chunk_sizes.epoch_chunk_size = epoch_chunk_size;
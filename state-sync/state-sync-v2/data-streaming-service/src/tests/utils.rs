@@ -9,6 +9,7 @@ use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary, OptimalChunkSizes},
     interface::{
         AptosDataClientInterface, Response, ResponseCallback, ResponseContext, ResponseError,
+        SubscriptionRequestMetadata,
     },
 };
 use aptos_infallible::Mutex;
@@ -17,8 +18,9 @@ use aptos_storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
-        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+        StateValuesWithMultiProofRequest, StateValuesWithProofRequest,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+        TransactionsWithProofRequest,
     },
     responses::{CompleteDataRange, TransactionOrOutputListWithProof},
     Epoch,
@@ -30,10 +32,10 @@ use aptos_types::{
     chain_id::ChainId,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    proof::SparseMerkleRangeProof,
+    proof::{SparseMerkleMultiProof, SparseMerkleRangeProof},
     state_store::{
         state_key::StateKey,
-        state_value::{StateValue, StateValueChunkWithProof},
+        state_value::{StateValue, StateValueChunkWithProof, StateValuesWithMultiProof},
     },
     transaction::{
         RawTransaction, Script, SignedTransaction, Transaction, TransactionListWithProof,
@@ -281,6 +283,34 @@ impl AptosDataClientInterface for MockAptosDataClient {
         Ok(create_data_client_response(state_value_chunk_with_proof))
     }
 
+    async fn get_state_values_with_multi_proof(
+        &self,
+        version: Version,
+        state_keys: Vec<StateKey>,
+        request_timeout_ms: u64,
+    ) -> Result<Response<StateValuesWithMultiProof>, aptos_data_client::error::Error> {
+        self.verify_request_timeout(
+            request_timeout_ms,
+            false,
+            DataRequest::GetStateValuesWithMultiProof(StateValuesWithMultiProofRequest {
+                version,
+                state_keys: state_keys.clone(),
+            }),
+        );
+        self.emulate_network_latencies();
+
+        // Return the state keys without values and an empty proof
+        let state_values = state_keys
+            .into_iter()
+            .map(|state_key| (state_key, None))
+            .collect();
+        let state_values_with_multi_proof = StateValuesWithMultiProof::new(
+            state_values,
+            SparseMerkleMultiProof::new(vec![], vec![]),
+        );
+        Ok(create_data_client_response(state_values_with_multi_proof))
+    }
+
     async fn get_epoch_ending_ledger_infos(
         &self,
         start_epoch: Epoch,
@@ -612,6 +642,41 @@ impl AptosDataClientInterface for MockAptosDataClient {
         };
         Ok(create_data_client_response(transactions_or_outputs))
     }
+
+    async fn subscribe_to_transaction_outputs_with_proof(
+        &self,
+        _subscription_request_metadata: SubscriptionRequestMetadata,
+        _request_timeout_ms: u64,
+    ) -> aptos_data_client::error::Result<
+        Response<(TransactionOutputListWithProof, LedgerInfoWithSignatures)>,
+    > {
+        // Subscriptions are not yet emulated by the mock client
+        Err(self.emulate_optimistic_fetch_expiration())
+    }
+
+    async fn subscribe_to_transactions_with_proof(
+        &self,
+        _subscription_request_metadata: SubscriptionRequestMetadata,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> aptos_data_client::error::Result<
+        Response<(TransactionListWithProof, LedgerInfoWithSignatures)>,
+    > {
+        // Subscriptions are not yet emulated by the mock client
+        Err(self.emulate_optimistic_fetch_expiration())
+    }
+
+    async fn subscribe_to_transactions_or_outputs_with_proof(
+        &self,
+        _subscription_request_metadata: SubscriptionRequestMetadata,
+        _include_events: bool,
+        _request_timeout_ms: u64,
+    ) -> aptos_data_client::error::Result<
+        Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>,
+    > {
+        // Subscriptions are not yet emulated by the mock client
+        Err(self.emulate_optimistic_fetch_expiration())
+    }
 }
 
 #[derive(Debug)]
@@ -7,19 +7,21 @@ use crate::{
     metrics,
     metrics::{
         increment_counter, start_timer, LRU_CACHE_HIT, LRU_CACHE_PROBE, OPTIMISTIC_FETCH_ADD,
+        SUBSCRIPTION_ADD, SUBSCRIPTION_FAILURE,
     },
     moderator::RequestModerator,
     network::ResponseSender,
     optimistic_fetch::OptimisticFetchRequest,
     storage::StorageReaderInterface,
+    subscription::{SubscriptionRequest, SubscriptionStreamRequests},
 };
 use aptos_config::network_id::PeerNetworkId;
 use aptos_infallible::Mutex;
 use aptos_logger::{debug, error, sample, sample::SampleRate, trace, warn};
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest,
-        StorageServiceRequest, TransactionOutputsWithProofRequest,
+        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithMultiProofRequest,
+        StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     responses::{
@@ -32,7 +34,7 @@ use aptos_types::transaction::Version;
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use lru::LruCache;
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 /// Storage server constants
 const INVALID_REQUEST_LOG_FREQUENCY_SECS: u64 = 5; // The frequency to log invalid requests (secs)
@@ -49,6 +51,7 @@ pub struct Handler<T> {
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
 }
 
@@ -59,6 +62,7 @@ impl<T: StorageReaderInterface> Handler<T> {
         lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
         request_moderator: Arc<RequestModerator>,
         storage: T,
+        subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
         time_service: TimeService,
     ) -> Self {
         Self {
@@ -67,6 +71,7 @@ impl<T: StorageReaderInterface> Handler<T> {
             optimistic_fetches,
             lru_response_cache,
             request_moderator,
+            subscriptions,
             time_service,
         }
     }
@@ -92,6 +97,12 @@ impl<T: StorageReaderInterface> Handler<T> {
             return;
         }
 
+        // Handle any subscription requests
+        if request.data_request.is_subscription_request() {
+            self.handle_subscription_request(peer_network_id, request, response_sender);
+            return;
+        }
+
         // Process the request and return the response to the client
         let response = self.process_request(&peer_network_id, request.clone(), false);
         self.send_response(request, response, response_sender);
@@ -232,6 +243,69 @@ impl<T: StorageReaderInterface> Handler<T> {
         );
     }
 
+    /// Handles the given subscription request
+    pub fn handle_subscription_request(
+        &self,
+        peer_network_id: PeerNetworkId,
+        request: StorageServiceRequest,
+        response_sender: ResponseSender,
+    ) {
+        // Create the subscription request
+        let subscription_request =
+            SubscriptionRequest::new(request.clone(), response_sender, self.time_service.clone());
+
+        // Add the request to the subscription stream of the peer (or
+        // create a new stream if the request belongs to a new stream).
+        let mut subscriptions = self.subscriptions.lock();
+        match subscriptions.get_mut(&peer_network_id) {
+            Some(subscription_stream_requests)
+                if subscription_stream_requests.subscription_stream_id()
+                    == subscription_request.subscription_stream_id() =>
+            {
+                // Add the request to the existing stream
+                if let Err((error, subscription_request)) =
+                    subscription_stream_requests.add_subscription_request(subscription_request)
+                {
+                    // The request is invalid for the stream. Notify the peer.
+                    sample!(
+                        SampleRate::Duration(Duration::from_secs(INVALID_REQUEST_LOG_FREQUENCY_SECS)),
+                        warn!(LogSchema::new(LogEntry::SubscriptionRequest)
+                            .error(&error)
+                            .peer_network_id(&peer_network_id)
+                            .request(&request)
+                        );
+                    );
+                    increment_counter(
+                        &metrics::SUBSCRIPTION_EVENTS,
+                        peer_network_id.network_id(),
+                        SUBSCRIPTION_FAILURE.into(),
+                    );
+                    self.send_response(
+                        request,
+                        Err(StorageServiceError::InvalidRequest(error.to_string())),
+                        subscription_request.get_response_sender(),
+                    );
+                    return;
+                }
+            },
+            _ => {
+                // The request starts a new stream (any existing stream is dropped)
+                let subscription_stream_requests = SubscriptionStreamRequests::new(
+                    subscription_request,
+                    self.time_service.clone(),
+                );
+                subscriptions.insert(peer_network_id, subscription_stream_requests);
+            },
+        }
+
+        // Update the subscription metrics
+        increment_counter(
+            &metrics::SUBSCRIPTION_EVENTS,
+            peer_network_id.network_id(),
+            SUBSCRIPTION_ADD.into(),
+        );
+    }
+
     /// Processes a storage service request for which the response
     /// might already be cached.
     fn process_cachable_request(
@@ -260,6 +334,9 @@ impl<T: StorageReaderInterface> Handler<T> {
             DataRequest::GetStateValuesWithProof(request) => {
                 self.get_state_value_chunk_with_proof(request)
             },
+            DataRequest::GetStateValuesWithMultiProof(request) => {
+                self.get_state_values_with_multi_proof(request)
+            },
             DataRequest::GetEpochEndingLedgerInfos(request) => {
                 self.get_epoch_ending_ledger_infos(request)
             },
@@ -306,6 +383,19 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_state_values_with_multi_proof(
+        &self,
+        request: &StateValuesWithMultiProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let state_values_with_multi_proof = self
+            .storage
+            .get_state_values_with_multi_proof(request.version, &request.state_keys)?;
+
+        Ok(DataResponse::StateValuesWithMultiProof(
+            state_values_with_multi_proof,
+        ))
+    }
+
     fn get_epoch_ending_ledger_infos(
         &self,
         request: &EpochEndingLedgerInfoRequest,
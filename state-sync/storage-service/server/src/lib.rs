@@ -31,8 +31,9 @@ use handler::Handler;
 use lru::LruCache;
 use moderator::RequestModerator;
 use optimistic_fetch::OptimisticFetchRequest;
-use std::{ops::Deref, sync::Arc, time::Duration};
+use std::{collections::HashMap, ops::Deref, sync::Arc, time::Duration};
 use storage::StorageReaderInterface;
+use subscription::SubscriptionStreamRequests;
 use thiserror::Error;
 use tokio::runtime::Handle;
 
@@ -44,6 +45,7 @@ mod moderator;
 pub mod network;
 mod optimistic_fetch;
 pub mod storage;
+mod subscription;
 mod utils;
 
 #[cfg(test)]
@@ -79,6 +81,9 @@ pub struct StorageServiceServer<T> {
     // A moderator for incoming peer requests
     request_moderator: Arc<RequestModerator>,
 
+    // A set of active subscription streams for peers waiting for new data
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+
     // The listener for notifications from state sync
     storage_service_listener: Option<StorageServiceNotificationListener>,
 }
@@ -115,6 +120,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             storage_service_config,
             time_service.clone(),
         ));
+        let subscriptions = Arc::new(Mutex::new(HashMap::new()));
         let storage_service_listener = Some(storage_service_listener);
 
         Self {
@@ -127,13 +133,14 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             lru_response_cache,
             optimistic_fetches,
             request_moderator,
+            subscriptions,
             storage_service_listener,
         }
     }
 
     /// Spawns all continuously running utility tasks
     async fn spawn_continuous_storage_summary_tasks(&mut self) {
-        // Create a channel to notify the optimistic fetch
+        // Create a channel to notify the optimistic fetch and subscription
         // handler about updates to the cached storage summary.
         let (cached_summary_update_notifier, cached_summary_update_listener) =
             aptos_channel::new(QueueStyle::LIFO, CACHED_SUMMARY_UPDATE_CHANNEL_SIZE, None);
@@ -142,8 +149,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         self.spawn_storage_summary_refresher(cached_summary_update_notifier)
             .await;
 
-        // Spawn the optimistic fetch handler
-        self.spawn_optimistic_fetch_handler(cached_summary_update_listener)
+        // Spawn the optimistic fetch and subscription handler
+        self.spawn_optimistic_fetch_and_subscription_handler(cached_summary_update_listener)
             .await;
 
         // Spawn the refresher for the request moderator
@@ -211,8 +218,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             .await;
     }
 
-    /// Spawns a non-terminating task that handles optimistic fetches
-    async fn spawn_optimistic_fetch_handler(
+    /// Spawns a non-terminating task that handles optimistic fetches and subscriptions
+    async fn spawn_optimistic_fetch_and_subscription_handler(
         &mut self,
         mut cached_summary_update_listener: aptos_channel::Receiver<
             (),
@@ -227,6 +234,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         let lru_response_cache = self.lru_response_cache.clone();
         let request_moderator = self.request_moderator.clone();
         let storage = self.storage.clone();
+        let subscriptions = self.subscriptions.clone();
         let time_service = self.time_service.clone();
 
         // Spawn the task
@@ -239,11 +247,11 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                 let ticker = time_service.interval(duration);
                 futures::pin_mut!(ticker);
 
-                // Continuously handle the optimistic fetches
+                // Continuously handle the optimistic fetches and subscriptions
                 loop {
                     futures::select! {
                         _ = ticker.select_next_some() => {
-                            // Handle the optimistic fetches periodically
+                            // Handle the optimistic fetches and subscriptions periodically
                             handle_active_optimistic_fetches(
                                 bounded_executor.clone(),
                                 cached_storage_server_summary.clone(),
@@ -252,6 +260,18 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                                 lru_response_cache.clone(),
                                 request_moderator.clone(),
                                 storage.clone(),
+                                subscriptions.clone(),
+                                time_service.clone(),
+                            ).await;
+                            handle_active_subscriptions(
+                                bounded_executor.clone(),
+                                cached_storage_server_summary.clone(),
+                                config,
+                                optimistic_fetches.clone(),
+                                lru_response_cache.clone(),
+                                request_moderator.clone(),
+                                storage.clone(),
+                                subscriptions.clone(),
                                 time_service.clone(),
                             ).await;
                         },
@@ -260,7 +280,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                                 .message(&format!("Received cache update notification! Highest synced version: {:?}", notification.highest_synced_version))
                             );
 
-                            // Handle the optimistic fetches because of a cache update
+                            // Handle the optimistic fetches and subscriptions because of a cache update
                             handle_active_optimistic_fetches(
                                 bounded_executor.clone(),
                                 cached_storage_server_summary.clone(),
@@ -269,6 +289,18 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                                 lru_response_cache.clone(),
                                 request_moderator.clone(),
                                 storage.clone(),
+                                subscriptions.clone(),
+                                time_service.clone(),
+                            ).await;
+                            handle_active_subscriptions(
+                                bounded_executor.clone(),
+                                cached_storage_server_summary.clone(),
+                                config,
+                                optimistic_fetches.clone(),
+                                lru_response_cache.clone(),
+                                request_moderator.clone(),
+                                storage.clone(),
+                                subscriptions.clone(),
                                 time_service.clone(),
                             ).await;
                         },
@@ -335,6 +367,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             let optimistic_fetches = self.optimistic_fetches.clone();
             let lru_response_cache = self.lru_response_cache.clone();
             let request_moderator = self.request_moderator.clone();
+            let subscriptions = self.subscriptions.clone();
             let time_service = self.time_service.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
@@ -344,6 +377,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                         lru_response_cache,
                         request_moderator,
                         storage,
+                        subscriptions,
                         time_service,
                     )
                     .process_request_and_respond(
@@ -369,6 +403,14 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
     ) -> Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>> {
         self.optimistic_fetches.clone()
     }
+
+    #[cfg(test)]
+    /// Returns a copy of the active subscriptions for test purposes
+    pub(crate) fn get_subscriptions(
+        &self,
+    ) -> Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>> {
+        self.subscriptions.clone()
+    }
 }
 
 /// Handles the active optimistic fetches and logs any
@@ -381,6 +423,7 @@ async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
 ) {
     if let Err(error) = optimistic_fetch::handle_active_optimistic_fetches(
@@ -391,6 +434,7 @@ async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
         lru_response_cache,
         request_moderator,
         storage,
+        subscriptions,
         time_service,
     )
     .await
@@ -401,6 +445,38 @@ async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
     }
 }
 
+/// Handles the active subscriptions and logs any
+/// errors that were encountered.
+async fn handle_active_subscriptions<T: StorageReaderInterface>(
+    bounded_exector: BoundedExecutor,
+    cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
+    optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
+    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_moderator: Arc<RequestModerator>,
+    storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    time_service: TimeService,
+) {
+    if let Err(error) = subscription::handle_active_subscriptions(
+        bounded_exector,
+        cached_storage_server_summary,
+        config,
+        optimistic_fetches,
+        lru_response_cache,
+        request_moderator,
+        storage,
+        subscriptions,
+        time_service,
+    )
+    .await
+    {
+        error!(LogSchema::new(LogEntry::SubscriptionRefresh)
+            .error(&error)
+            .message("Failed to handle active subscriptions!"));
+    }
+}
+
 /// Refreshes the cached storage server summary and sends
 /// a notification via the given channel. If an error
 /// occurs, it is logged.
@@ -457,7 +533,7 @@ pub(crate) fn refresh_cached_storage_summary<T: StorageReaderInterface>(
     }
 }
 
-/// A simple notification sent to the optimistic fetch handler that the
+/// A simple notification sent to the optimistic fetch and subscription handler that the
 /// cached storage summary has been updated with the specified version.
 pub struct CachedSummaryUpdateNotification {
     highest_synced_version: Option<u64>,
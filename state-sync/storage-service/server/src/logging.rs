@@ -47,4 +47,7 @@ pub enum LogEntry {
     SentStorageResponse,
     StorageServiceError,
     StorageSummaryRefresh,
+    SubscriptionRefresh,
+    SubscriptionRequest,
+    SubscriptionResponse,
 }
@@ -14,6 +14,9 @@ pub const LRU_CACHE_HIT: &str = "lru_cache_hit";
 pub const LRU_CACHE_PROBE: &str = "lru_cache_probe";
 pub const OPTIMISTIC_FETCH_ADD: &str = "optimistic_fetch_add";
 pub const OPTIMISTIC_FETCH_EXPIRE: &str = "optimistic_fetch_expire";
+pub const SUBSCRIPTION_ADD: &str = "subscription_add";
+pub const SUBSCRIPTION_EXPIRE: &str = "subscription_expire";
+pub const SUBSCRIPTION_FAILURE: &str = "subscription_failure";
 
 /// Gauge for tracking the number of actively ignored peers
 pub static IGNORED_PEER_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
@@ -116,6 +119,36 @@ pub static STORAGE_RESPONSES_SENT: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauge for tracking the number of active subscription streams
+pub static SUBSCRIPTION_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_storage_service_server_subscription_count",
+        "Gauge for tracking the number of active subscription streams",
+        &["network_id"]
+    )
+    .unwrap()
+});
+
+/// Counter for subscription request events
+pub static SUBSCRIPTION_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_service_server_subscription_event",
+        "Counters related to subscription events",
+        &["network_id", "event"]
+    )
+    .unwrap()
+});
+
+/// Time it takes to process a subscription request
+pub static SUBSCRIPTION_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_storage_service_server_subscription_latency",
+        "Time it takes to process a subscription request",
+        &["network_id", "request_type"]
+    )
+    .unwrap()
+});
+
 /// Time it takes to process a storage request
 pub static STORAGE_REQUEST_PROCESSING_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
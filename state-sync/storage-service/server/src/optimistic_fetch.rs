@@ -8,6 +8,7 @@ use crate::{
     moderator::RequestModerator,
     network::ResponseSender,
     storage::StorageReaderInterface,
+    subscription::SubscriptionStreamRequests,
     utils, LogEntry, LogSchema,
 };
 use aptos_bounded_executor::BoundedExecutor;
@@ -181,6 +182,7 @@ pub(crate) async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
 ) -> Result<(), Error> {
     // Update the number of active optimistic fetches
@@ -195,6 +197,7 @@ pub(crate) async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
         lru_response_cache.clone(),
         request_moderator.clone(),
         storage.clone(),
+        subscriptions.clone(),
         time_service.clone(),
     )
     .await?;
@@ -208,6 +211,7 @@ pub(crate) async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
         lru_response_cache,
         request_moderator,
         storage,
+        subscriptions,
         time_service,
         peers_with_ready_optimistic_fetches,
     )
@@ -226,6 +230,7 @@ async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
     peers_with_ready_optimistic_fetches: Vec<(PeerNetworkId, LedgerInfoWithSignatures)>,
 ) {
@@ -241,6 +246,7 @@ async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
             let lru_response_cache = lru_response_cache.clone();
             let request_moderator = request_moderator.clone();
             let storage = storage.clone();
+            let subscriptions = subscriptions.clone();
             let time_service = time_service.clone();
 
             // Spawn a blocking task to handle the optimistic fetch
@@ -251,10 +257,11 @@ async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
                     let optimistic_fetch_request = optimistic_fetch.request.clone();
 
                     // Notify the peer of the new data
-                    if let Err(error) = utils::notify_peer_of_new_data(
+                    if let Err(error) = notify_peer_of_new_data(
                         cached_storage_server_summary.clone(),
                         config,
                         optimistic_fetches.clone(),
+                        subscriptions.clone(),
                         lru_response_cache.clone(),
                         request_moderator.clone(),
                         storage.clone(),
@@ -294,6 +301,7 @@ pub(crate) async fn get_peers_with_ready_optimistic_fetches<T: StorageReaderInte
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
 ) -> aptos_storage_service_types::Result<Vec<(PeerNetworkId, LedgerInfoWithSignatures)>, Error> {
     // Fetch the latest storage summary and highest synced version
@@ -318,6 +326,7 @@ pub(crate) async fn get_peers_with_ready_optimistic_fetches<T: StorageReaderInte
         lru_response_cache,
         request_moderator,
         storage,
+        subscriptions,
         time_service,
         highest_synced_ledger_info,
         highest_synced_version,
@@ -348,6 +357,7 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
     highest_synced_ledger_info: LedgerInfoWithSignatures,
     highest_synced_version: Version,
@@ -390,6 +400,7 @@ async fn identify_expired_invalid_and_ready_fetches<T: StorageReaderInterface>(
             lru_response_cache,
             request_moderator,
             storage,
+            subscriptions,
             time_service,
             highest_synced_ledger_info,
             highest_synced_version,
@@ -415,6 +426,7 @@ async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
     highest_synced_ledger_info: LedgerInfoWithSignatures,
     highest_synced_version: Version,
@@ -440,6 +452,7 @@ async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface
         let lru_response_cache = lru_response_cache.clone();
         let request_moderator = request_moderator.clone();
         let storage = storage.clone();
+        let subscriptions = subscriptions.clone();
         let time_service = time_service.clone();
         let peers_with_invalid_optimistic_fetches = peers_with_invalid_optimistic_fetches.clone();
         let peers_with_ready_optimistic_fetches = peers_with_ready_optimistic_fetches.clone();
@@ -461,6 +474,7 @@ async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface
                             request_moderator.clone(),
                             &peer_network_id,
                             storage.clone(),
+                            subscriptions.clone(),
                             time_service.clone(),
                         ) {
                             Ok(epoch_ending_ledger_info) => epoch_ending_ledger_info,
@@ -515,6 +529,43 @@ async fn identify_ready_and_invalid_optimistic_fetches<T: StorageReaderInterface
     )
 }
 
+/// Notifies a peer of new data according to the target ledger info
+/// and the given optimistic fetch request.
+fn notify_peer_of_new_data<T: StorageReaderInterface>(
+    cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
+    optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_moderator: Arc<RequestModerator>,
+    storage: T,
+    time_service: TimeService,
+    peer_network_id: &PeerNetworkId,
+    optimistic_fetch: OptimisticFetchRequest,
+    target_ledger_info: LedgerInfoWithSignatures,
+) -> aptos_storage_service_types::Result<(), Error> {
+    // Create the storage request for the missing data
+    let missing_data_request =
+        optimistic_fetch.get_storage_request_for_missing_data(config, &target_ledger_info)?;
+
+    // Notify the peer of the new data
+    utils::notify_peer_of_new_data(
+        cached_storage_server_summary,
+        optimistic_fetches,
+        subscriptions,
+        lru_response_cache,
+        request_moderator,
+        storage,
+        time_service,
+        peer_network_id,
+        missing_data_request,
+        target_ledger_info,
+        optimistic_fetch.get_response_sender(),
+    )?;
+
+    Ok(())
+}
+
 /// Removes the expired optimistic fetches from the active map
 fn removed_expired_optimistic_fetches(
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
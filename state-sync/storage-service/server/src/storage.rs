@@ -10,7 +10,10 @@ use aptos_storage_service_types::responses::{
 };
 use aptos_types::{
     epoch_change::EpochChangeProof,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueChunkWithProof, StateValuesWithMultiProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use serde::Serialize;
@@ -88,6 +91,16 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         start_index: u64,
         end_index: u64,
     ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error>;
+
+    /// Returns the state values of the given `state_keys` at the specified
+    /// version, with a single proof for all of them. In some cases, the
+    /// values of less state keys may be returned (e.g., due to network or
+    /// chunk limits).
+    fn get_state_values_with_multi_proof(
+        &self,
+        version: u64,
+        state_keys: &[StateKey],
+    ) -> aptos_storage_service_types::Result<StateValuesWithMultiProof, Error>;
 }
 
 /// The underlying implementation of the StorageReaderInterface, used by the
@@ -494,6 +507,63 @@ impl StorageReaderInterface for StorageReader {
             version, start_index, end_index
         )))
     }
+
+    fn get_state_values_with_multi_proof(
+        &self,
+        version: u64,
+        state_keys: &[StateKey],
+    ) -> aptos_storage_service_types::Result<StateValuesWithMultiProof, Error> {
+        if state_keys.is_empty() {
+            return Err(Error::InvalidRequest(
+                "At least one state key must be requested!".into(),
+            ));
+        }
+
+        // Calculate the number of state values to fetch
+        let expected_num_state_values = state_keys.len() as u64;
+        let max_num_state_values = self.config.max_state_chunk_size;
+        let mut num_state_values_to_fetch = min(expected_num_state_values, max_num_state_values);
+
+        // Attempt to serve the request
+        while num_state_values_to_fetch >= 1 {
+            let (state_values, proof) = self
+                .storage
+                .get_state_values_with_multi_proof_by_version(
+                    &state_keys[..num_state_values_to_fetch as usize],
+                    version,
+                )
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            let state_values_with_multi_proof = StateValuesWithMultiProof::new(state_values, proof);
+            if num_state_values_to_fetch == 1 {
+                return Ok(state_values_with_multi_proof); // We cannot return less than a single item
+            }
+
+            // Attempt to divide up the request if it overflows the message size
+            let (overflow_frame, num_bytes) = check_overflow_network_frame(
+                &state_values_with_multi_proof,
+                self.config.max_network_chunk_bytes,
+            )?;
+            if !overflow_frame {
+                return Ok(state_values_with_multi_proof);
+            } else {
+                increment_network_frame_overflow(
+                    DataResponse::StateValuesWithMultiProof(state_values_with_multi_proof)
+                        .get_label(),
+                );
+                let new_num_state_values_to_fetch = num_state_values_to_fetch / 2;
+                debug!("The request for {:?} state values was too large (num bytes: {:?}). Retrying with {:?}.",
+                    num_state_values_to_fetch, num_bytes, new_num_state_values_to_fetch);
+                num_state_values_to_fetch = new_num_state_values_to_fetch; // Try again with half the amount of data
+            }
+        }
+
+        Err(Error::UnexpectedErrorEncountered(format!(
+            "Unable to serve the get_state_values_with_multi_proof request! Version: {:?}, \
+            number of state keys: {:?}. The data cannot fit into a single network frame!",
+            version,
+            state_keys.len()
+        )))
+    }
 }
 
 /// Calculate `(start..=end).len()`. Returns an error if `end < start` or
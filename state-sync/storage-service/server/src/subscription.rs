@@ -0,0 +1,770 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    metrics,
+    metrics::{increment_counter, SUBSCRIPTION_EXPIRE, SUBSCRIPTION_FAILURE},
+    moderator::RequestModerator,
+    network::ResponseSender,
+    optimistic_fetch::OptimisticFetchRequest,
+    storage::StorageReaderInterface,
+    utils, LogEntry, LogSchema,
+};
+use aptos_bounded_executor::BoundedExecutor;
+use aptos_config::{
+    config::StorageServiceConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_infallible::Mutex;
+use aptos_logger::{error, warn};
+use aptos_storage_service_types::{
+    requests::{
+        DataRequest, StorageServiceRequest, SubscriptionStreamMetadata,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+        TransactionsWithProofRequest,
+    },
+    responses::{DataResponse, StorageServerSummary, StorageServiceResponse},
+    StorageServiceError,
+};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use arc_swap::ArcSwap;
+use dashmap::DashMap;
+use lru::LruCache;
+use std::{
+    cmp::min,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Instant,
+};
+
+/// A single subscription request that is part of a subscription stream
+pub struct SubscriptionRequest {
+    request: StorageServiceRequest,
+    response_sender: ResponseSender,
+    request_start_time: Instant,
+}
+
+impl SubscriptionRequest {
+    pub fn new(
+        request: StorageServiceRequest,
+        response_sender: ResponseSender,
+        time_service: TimeService,
+    ) -> Self {
+        Self {
+            request,
+            response_sender,
+            request_start_time: time_service.now(),
+        }
+    }
+
+    /// Returns the response sender and consumes the request
+    pub fn get_response_sender(self) -> ResponseSender {
+        self.response_sender
+    }
+
+    /// Sends the given error to the peer and consumes the request
+    pub fn send_error(self, error: &Error) {
+        let storage_service_error = match error {
+            Error::InvalidRequest(error) => StorageServiceError::InvalidRequest(error.clone()),
+            Error::TooManyInvalidRequests(error) => {
+                StorageServiceError::TooManyInvalidRequests(error.clone())
+            },
+            error => StorageServiceError::InternalError(error.to_string()),
+        };
+        self.response_sender.send(Err(storage_service_error));
+    }
+
+    /// Creates a new storage service request to satisfy the subscription
+    /// request using the new data at the specified `target_ledger_info`.
+    /// The `known_version` is the highest version already served along
+    /// the subscription stream.
+    pub fn get_storage_request_for_missing_data(
+        &self,
+        config: StorageServiceConfig,
+        known_version: Version,
+        target_ledger_info: &LedgerInfoWithSignatures,
+    ) -> aptos_storage_service_types::Result<StorageServiceRequest, Error> {
+        // Calculate the number of versions to fetch
+        let target_version = target_ledger_info.ledger_info().version();
+        let mut num_versions_to_fetch =
+            target_version.checked_sub(known_version).ok_or_else(|| {
+                Error::UnexpectedErrorEncountered(
+                    "Number of versions to fetch has overflown!".into(),
+                )
+            })?;
+
+        // Bound the number of versions to fetch by the maximum chunk size
+        num_versions_to_fetch = min(
+            num_versions_to_fetch,
+            self.max_chunk_size_for_request(config),
+        );
+
+        // Calculate the start and end versions
+        let start_version = known_version.checked_add(1).ok_or_else(|| {
+            Error::UnexpectedErrorEncountered("Start version has overflown!".into())
+        })?;
+        let end_version = known_version
+            .checked_add(num_versions_to_fetch)
+            .ok_or_else(|| {
+                Error::UnexpectedErrorEncountered("End version has overflown!".into())
+            })?;
+
+        // Create the storage request
+        let data_request = match &self.request.data_request {
+            DataRequest::SubscribeTransactionOutputsWithProof(_) => {
+                DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+                    proof_version: target_version,
+                    start_version,
+                    end_version,
+                })
+            },
+            DataRequest::SubscribeTransactionsWithProof(request) => {
+                DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+                    proof_version: target_version,
+                    start_version,
+                    end_version,
+                    include_events: request.include_events,
+                })
+            },
+            DataRequest::SubscribeTransactionsOrOutputsWithProof(request) => {
+                DataRequest::GetTransactionsOrOutputsWithProof(
+                    TransactionsOrOutputsWithProofRequest {
+                        proof_version: target_version,
+                        start_version,
+                        end_version,
+                        include_events: request.include_events,
+                        max_num_output_reductions: request.max_num_output_reductions,
+                    },
+                )
+            },
+            request => unreachable!("Unexpected subscription request: {:?}", request),
+        };
+        let storage_request =
+            StorageServiceRequest::new(data_request, self.request.use_compression);
+        Ok(storage_request)
+    }
+
+    /// Returns the maximum chunk size for the request depending
+    /// on the request type.
+    fn max_chunk_size_for_request(&self, config: StorageServiceConfig) -> u64 {
+        match &self.request.data_request {
+            DataRequest::SubscribeTransactionOutputsWithProof(_) => {
+                config.max_transaction_output_chunk_size
+            },
+            DataRequest::SubscribeTransactionsWithProof(_) => config.max_transaction_chunk_size,
+            DataRequest::SubscribeTransactionsOrOutputsWithProof(_) => {
+                config.max_transaction_output_chunk_size
+            },
+            request => unreachable!("Unexpected subscription request: {:?}", request),
+        }
+    }
+
+    /// Returns the subscription stream metadata for the request
+    pub fn subscription_stream_metadata(&self) -> SubscriptionStreamMetadata {
+        match &self.request.data_request {
+            DataRequest::SubscribeTransactionOutputsWithProof(request) => {
+                request.subscription_stream_metadata
+            },
+            DataRequest::SubscribeTransactionsWithProof(request) => {
+                request.subscription_stream_metadata
+            },
+            DataRequest::SubscribeTransactionsOrOutputsWithProof(request) => {
+                request.subscription_stream_metadata
+            },
+            request => unreachable!("Unexpected subscription request: {:?}", request),
+        }
+    }
+
+    /// Returns the index of the request in the subscription stream
+    pub fn subscription_stream_index(&self) -> u64 {
+        match &self.request.data_request {
+            DataRequest::SubscribeTransactionOutputsWithProof(request) => {
+                request.subscription_stream_index
+            },
+            DataRequest::SubscribeTransactionsWithProof(request) => {
+                request.subscription_stream_index
+            },
+            DataRequest::SubscribeTransactionsOrOutputsWithProof(request) => {
+                request.subscription_stream_index
+            },
+            request => unreachable!("Unexpected subscription request: {:?}", request),
+        }
+    }
+
+    /// Returns the id of the subscription stream the request belongs to
+    pub fn subscription_stream_id(&self) -> u64 {
+        self.subscription_stream_metadata().subscription_stream_id
+    }
+}
+
+/// The set of pending requests for a single subscription stream. Requests
+/// are served in index order, and each request is served with the data
+/// immediately following the data served to the previous request. Thus,
+/// the number of pending requests acts as the flow control window.
+pub struct SubscriptionStreamRequests {
+    subscription_stream_metadata: SubscriptionStreamMetadata, // The metadata for the stream
+    highest_known_version: u64, // The highest version already served along the stream
+    highest_known_epoch: u64,   // The highest epoch already served along the stream
+    next_index_to_serve: u64,   // The index of the next request to serve
+    request_in_flight: bool,    // Whether a popped request is still being served
+    pending_subscription_requests: BTreeMap<u64, SubscriptionRequest>, // The pending requests (by index)
+    last_stream_update_time: Instant, // The last time the stream was created or served data
+    time_service: TimeService,
+}
+
+impl SubscriptionStreamRequests {
+    pub fn new(subscription_request: SubscriptionRequest, time_service: TimeService) -> Self {
+        // Extract the relevant information from the request
+        let subscription_stream_metadata = subscription_request.subscription_stream_metadata();
+        let subscription_stream_index = subscription_request.subscription_stream_index();
+
+        // Create the pending requests
+        let mut pending_subscription_requests = BTreeMap::new();
+        pending_subscription_requests.insert(subscription_stream_index, subscription_request);
+
+        Self {
+            subscription_stream_metadata,
+            highest_known_version: subscription_stream_metadata.known_version_at_stream_start,
+            highest_known_epoch: subscription_stream_metadata.known_epoch_at_stream_start,
+            next_index_to_serve: 0,
+            request_in_flight: false,
+            pending_subscription_requests,
+            last_stream_update_time: time_service.now(),
+            time_service,
+        }
+    }
+
+    /// Adds the given subscription request to the stream. If the request
+    /// is invalid for the stream, the error and request are returned.
+    pub fn add_subscription_request(
+        &mut self,
+        subscription_request: SubscriptionRequest,
+    ) -> Result<(), (Error, SubscriptionRequest)> {
+        // Verify the request belongs to this stream
+        let subscription_stream_id = subscription_request.subscription_stream_id();
+        if subscription_stream_id != self.subscription_stream_id() {
+            let error = Error::InvalidRequest(format!(
+                "The subscription request stream ID ({:?}) does not match the expected ID ({:?})!",
+                subscription_stream_id,
+                self.subscription_stream_id()
+            ));
+            return Err((error, subscription_request));
+        }
+
+        // Verify the request index hasn't already been served
+        let subscription_stream_index = subscription_request.subscription_stream_index();
+        if subscription_stream_index < self.next_index_to_serve {
+            let error = Error::InvalidRequest(format!(
+                "The subscription request index ({:?}) has already been served! Next index: {:?}",
+                subscription_stream_index, self.next_index_to_serve
+            ));
+            return Err((error, subscription_request));
+        }
+
+        // Verify the request index isn't already pending
+        if self
+            .pending_subscription_requests
+            .contains_key(&subscription_stream_index)
+        {
+            let error = Error::InvalidRequest(format!(
+                "A subscription request with index ({:?}) is already pending!",
+                subscription_stream_index
+            ));
+            return Err((error, subscription_request));
+        }
+
+        // Add the request to the pending requests
+        self.pending_subscription_requests
+            .insert(subscription_stream_index, subscription_request);
+        Ok(())
+    }
+
+    /// Returns a reference to the next request to serve along the
+    /// stream (if it has been received and no request is being served).
+    pub fn first_pending_request(&self) -> Option<&SubscriptionRequest> {
+        if self.request_in_flight {
+            return None;
+        }
+        self.pending_subscription_requests
+            .get(&self.next_index_to_serve)
+    }
+
+    /// Removes and returns the next request to serve along the stream
+    /// (if it has been received and no request is being served). The
+    /// next index to serve is advanced immediately, so that duplicate
+    /// requests for the popped index are rejected while it is served.
+    /// No other request is popped until the stream has been updated
+    /// with the data that was served.
+    pub fn pop_first_pending_request(&mut self) -> Option<SubscriptionRequest> {
+        if self.request_in_flight {
+            return None;
+        }
+        let subscription_request = self
+            .pending_subscription_requests
+            .remove(&self.next_index_to_serve)?;
+        self.next_index_to_serve += 1;
+        self.request_in_flight = true;
+        Some(subscription_request)
+    }
+
+    /// Removes all pending requests from the stream and sends the given
+    /// error to each of them. This is done before the stream is dropped,
+    /// so that peers don't have to wait for their requests to time out.
+    pub fn reject_pending_requests(&mut self, error: &Error) {
+        for (_, subscription_request) in std::mem::take(&mut self.pending_subscription_requests) {
+            subscription_request.send_error(error);
+        }
+    }
+
+    /// Returns the highest version and epoch already served along the stream
+    pub fn get_highest_known_version_and_epoch(&self) -> (u64, u64) {
+        (self.highest_known_version, self.highest_known_epoch)
+    }
+
+    /// Returns the number of pending requests along the stream
+    pub fn num_pending_requests(&self) -> usize {
+        self.pending_subscription_requests.len()
+    }
+
+    /// Returns the id of the subscription stream
+    pub fn subscription_stream_id(&self) -> u64 {
+        self.subscription_stream_metadata.subscription_stream_id
+    }
+
+    /// Returns true iff the stream has not served any data within the timeout
+    pub fn is_expired(&self, timeout_ms: u64) -> bool {
+        let current_time = self.time_service.now();
+        let elapsed_time = current_time
+            .duration_since(self.last_stream_update_time)
+            .as_millis();
+        elapsed_time > timeout_ms as u128
+    }
+
+    /// Updates the highest known version and epoch of the stream using
+    /// the data response that was just served, and allows the stream
+    /// to serve the request at the next index.
+    pub fn update_known_version_and_epoch(
+        &mut self,
+        data_response: &DataResponse,
+    ) -> Result<(), Error> {
+        // Determine the number of versions served and the target ledger info
+        let (num_versions_served, target_ledger_info) = match data_response {
+            DataResponse::NewTransactionOutputsWithProof((outputs_with_proof, ledger_info)) => (
+                outputs_with_proof.transactions_and_outputs.len(),
+                ledger_info,
+            ),
+            DataResponse::NewTransactionsWithProof((transactions_with_proof, ledger_info)) => {
+                (transactions_with_proof.transactions.len(), ledger_info)
+            },
+            DataResponse::NewTransactionsOrOutputsWithProof((
+                (transactions_with_proof, outputs_with_proof),
+                ledger_info,
+            )) => {
+                if let Some(transactions_with_proof) = transactions_with_proof {
+                    (transactions_with_proof.transactions.len(), ledger_info)
+                } else if let Some(outputs_with_proof) = outputs_with_proof {
+                    (
+                        outputs_with_proof.transactions_and_outputs.len(),
+                        ledger_info,
+                    )
+                } else {
+                    return Err(Error::UnexpectedErrorEncountered(
+                        "Found an empty transaction or output response!".into(),
+                    ));
+                }
+            },
+            data_response => {
+                return Err(Error::UnexpectedErrorEncountered(format!(
+                    "Unexpected subscription data response: {:?}",
+                    data_response
+                )))
+            },
+        };
+
+        // Update the highest known version
+        self.highest_known_version = self
+            .highest_known_version
+            .checked_add(num_versions_served as u64)
+            .ok_or_else(|| {
+                Error::UnexpectedErrorEncountered("Highest known version has overflown!".into())
+            })?;
+
+        // Update the highest known epoch if we've reached the end of the epoch
+        let target_ledger_info = target_ledger_info.ledger_info();
+        if self.highest_known_version == target_ledger_info.version()
+            && target_ledger_info.ends_epoch()
+        {
+            self.highest_known_epoch =
+                self.highest_known_epoch.checked_add(1).ok_or_else(|| {
+                    Error::UnexpectedErrorEncountered("Highest known epoch has overflown!".into())
+                })?;
+        }
+
+        // Allow the next request to be served and update the last stream update time
+        self.request_in_flight = false;
+        self.last_stream_update_time = self.time_service.now();
+
+        Ok(())
+    }
+}
+
+/// Handles the active subscription streams by serving the next request
+/// of each stream (if new data is available for the stream).
+pub(crate) async fn handle_active_subscriptions<T: StorageReaderInterface>(
+    bounded_executor: BoundedExecutor,
+    cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
+    optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
+    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_moderator: Arc<RequestModerator>,
+    storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    time_service: TimeService,
+) -> Result<(), Error> {
+    // Update the number of active subscription streams
+    update_subscription_metrics(subscriptions.clone());
+
+    // Remove the expired and invalid subscription streams
+    remove_expired_and_invalid_subscriptions(config, subscriptions.clone());
+
+    // Fetch the latest storage summary and highest synced version
+    let latest_storage_summary = cached_storage_server_summary.load().clone();
+    let highest_synced_ledger_info = match &latest_storage_summary.data_summary.synced_ledger_info {
+        Some(ledger_info) => ledger_info.clone(),
+        None => return Ok(()),
+    };
+
+    // Remove the next request to serve for each stream with new data
+    let ready_subscription_requests =
+        get_ready_subscription_requests(subscriptions.clone(), &highest_synced_ledger_info);
+
+    // Serve each ready request
+    for (peer_network_id, subscription_request, highest_known_version, highest_known_epoch) in
+        ready_subscription_requests
+    {
+        // Clone all required components for the task
+        let cached_storage_server_summary = cached_storage_server_summary.clone();
+        let highest_synced_ledger_info = highest_synced_ledger_info.clone();
+        let optimistic_fetches = optimistic_fetches.clone();
+        let lru_response_cache = lru_response_cache.clone();
+        let request_moderator = request_moderator.clone();
+        let storage = storage.clone();
+        let subscriptions = subscriptions.clone();
+        let time_service = time_service.clone();
+
+        // Spawn a blocking task to serve the request. We do this
+        // because each request requires reading from storage.
+        bounded_executor
+            .spawn_blocking(move || {
+                // Get the request start time, stream id and request
+                let request_start_time = subscription_request.request_start_time;
+                let subscription_stream_id = subscription_request.subscription_stream_id();
+                let storage_request = subscription_request.request.clone();
+
+                // Serve the request and update the stream
+                let result = serve_subscription_request(
+                    cached_storage_server_summary,
+                    config,
+                    optimistic_fetches,
+                    lru_response_cache,
+                    request_moderator,
+                    storage,
+                    subscriptions.clone(),
+                    time_service.clone(),
+                    &peer_network_id,
+                    subscription_request,
+                    highest_known_version,
+                    highest_known_epoch,
+                    highest_synced_ledger_info,
+                );
+                let mut subscriptions = subscriptions.lock();
+                match result {
+                    Ok(data_response) => {
+                        // Update the stream (if it hasn't been replaced in the meantime)
+                        if let Some(subscription_stream_requests) =
+                            subscriptions.get_mut(&peer_network_id)
+                        {
+                            if subscription_stream_requests.subscription_stream_id()
+                                == subscription_stream_id
+                            {
+                                if let Err(error) = subscription_stream_requests
+                                    .update_known_version_and_epoch(&data_response)
+                                {
+                                    error!(LogSchema::new(LogEntry::SubscriptionResponse)
+                                        .error(&error)
+                                        .peer_network_id(&peer_network_id)
+                                        .request(&storage_request)
+                                        .message("Failed to update the subscription stream!"));
+                                    subscription_stream_requests.reject_pending_requests(&error);
+                                    subscriptions.remove(&peer_network_id);
+                                }
+                            }
+                        }
+                    },
+                    Err(error) => {
+                        // Log the error and remove the stream (the peer will need to restart it).
+                        // The failed request has already been sent the error.
+                        warn!(LogSchema::new(LogEntry::SubscriptionResponse)
+                            .error(&error)
+                            .peer_network_id(&peer_network_id)
+                            .request(&storage_request)
+                            .message("Failed to serve the subscription request!"));
+                        increment_counter(
+                            &metrics::SUBSCRIPTION_EVENTS,
+                            peer_network_id.network_id(),
+                            SUBSCRIPTION_FAILURE.into(),
+                        );
+                        if let Some(subscription_stream_requests) =
+                            subscriptions.get_mut(&peer_network_id)
+                        {
+                            if subscription_stream_requests.subscription_stream_id()
+                                == subscription_stream_id
+                            {
+                                subscription_stream_requests.reject_pending_requests(&error);
+                                subscriptions.remove(&peer_network_id);
+                            }
+                        }
+                    },
+                }
+
+                // Update the subscription latency metric
+                let subscription_duration = time_service.now().duration_since(request_start_time);
+                metrics::observe_value_with_label(
+                    &metrics::SUBSCRIPTION_LATENCIES,
+                    peer_network_id.network_id(),
+                    &storage_request.get_label(),
+                    subscription_duration.as_secs_f64(),
+                );
+            })
+            .await;
+    }
+
+    Ok(())
+}
+
+/// Identifies the subscription streams with new data to serve, and removes
+/// the next request to serve from each of them. Returns the peer, request
+/// and highest known version and epoch for each ready request.
+fn get_ready_subscription_requests(
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    highest_synced_ledger_info: &LedgerInfoWithSignatures,
+) -> Vec<(PeerNetworkId, SubscriptionRequest, u64, u64)> {
+    let highest_synced_version = highest_synced_ledger_info.ledger_info().version();
+
+    let mut ready_subscription_requests = vec![];
+    for (peer_network_id, subscription_stream_requests) in subscriptions.lock().iter_mut() {
+        // Check if we have synced beyond the highest known version of the stream
+        let (highest_known_version, highest_known_epoch) =
+            subscription_stream_requests.get_highest_known_version_and_epoch();
+        if highest_known_version < highest_synced_version {
+            if let Some(subscription_request) =
+                subscription_stream_requests.pop_first_pending_request()
+            {
+                ready_subscription_requests.push((
+                    *peer_network_id,
+                    subscription_request,
+                    highest_known_version,
+                    highest_known_epoch,
+                ));
+            }
+        }
+    }
+
+    ready_subscription_requests
+}
+
+/// Serves the given subscription request using the data after the
+/// highest known version (and epoch). Returns the data response
+/// that was sent to the peer. If the request can't be served, an
+/// error response is sent to the peer and the error is returned.
+fn serve_subscription_request<T: StorageReaderInterface>(
+    cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
+    optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
+    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_moderator: Arc<RequestModerator>,
+    storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    time_service: TimeService,
+    peer_network_id: &PeerNetworkId,
+    subscription_request: SubscriptionRequest,
+    highest_known_version: u64,
+    highest_known_epoch: u64,
+    highest_synced_ledger_info: LedgerInfoWithSignatures,
+) -> aptos_storage_service_types::Result<DataResponse, Error> {
+    // Determine the target ledger info and create the storage request for the missing data
+    let missing_data_request = get_target_and_missing_data_request(
+        cached_storage_server_summary.clone(),
+        config,
+        optimistic_fetches.clone(),
+        lru_response_cache.clone(),
+        request_moderator.clone(),
+        storage.clone(),
+        subscriptions.clone(),
+        time_service.clone(),
+        peer_network_id,
+        &subscription_request,
+        highest_known_version,
+        highest_known_epoch,
+        highest_synced_ledger_info,
+    );
+    let (target_ledger_info, missing_data_request) = match missing_data_request {
+        Ok(target_and_missing_data_request) => target_and_missing_data_request,
+        Err(error) => {
+            subscription_request.send_error(&error);
+            return Err(error);
+        },
+    };
+
+    // Notify the peer of the new data
+    utils::notify_peer_of_new_data(
+        cached_storage_server_summary,
+        optimistic_fetches,
+        subscriptions,
+        lru_response_cache,
+        request_moderator,
+        storage,
+        time_service,
+        peer_network_id,
+        missing_data_request,
+        target_ledger_info,
+        subscription_request.get_response_sender(),
+    )
+}
+
+/// Determines the target ledger info for the given subscription request,
+/// and creates the storage request for the data missing up to the target.
+fn get_target_and_missing_data_request<T: StorageReaderInterface>(
+    cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
+    config: StorageServiceConfig,
+    optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
+    lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
+    request_moderator: Arc<RequestModerator>,
+    storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    time_service: TimeService,
+    peer_network_id: &PeerNetworkId,
+    subscription_request: &SubscriptionRequest,
+    highest_known_version: u64,
+    highest_known_epoch: u64,
+    highest_synced_ledger_info: LedgerInfoWithSignatures,
+) -> aptos_storage_service_types::Result<(LedgerInfoWithSignatures, StorageServiceRequest), Error> {
+    // Determine the target ledger info for the request
+    let target_ledger_info =
+        if highest_known_epoch < highest_synced_ledger_info.ledger_info().epoch() {
+            // Fetch the epoch ending ledger info from storage (the
+            // peer needs to sync to their epoch ending ledger info).
+            let epoch_ending_ledger_info = utils::get_epoch_ending_ledger_info(
+                cached_storage_server_summary,
+                optimistic_fetches,
+                highest_known_epoch,
+                lru_response_cache,
+                request_moderator,
+                peer_network_id,
+                storage,
+                subscriptions,
+                time_service,
+            )?;
+
+            // Check that the stream hasn't been created with an invalid
+            // version and epoch (i.e., one that doesn't respect an epoch boundary).
+            if epoch_ending_ledger_info.ledger_info().version() <= highest_known_version {
+                return Err(Error::InvalidRequest(
+                    "Mismatch between known version and epoch!".into(),
+                ));
+            }
+            epoch_ending_ledger_info
+        } else {
+            highest_synced_ledger_info
+        };
+
+    // Create the storage request for the missing data
+    let missing_data_request = subscription_request.get_storage_request_for_missing_data(
+        config,
+        highest_known_version,
+        &target_ledger_info,
+    )?;
+
+    Ok((target_ledger_info, missing_data_request))
+}
+
+/// Removes the expired and invalid subscription streams. A stream is
+/// invalid if the peer has exceeded the maximum number of pending requests.
+/// The pending requests of each removed stream are rejected, so that the
+/// peer doesn't have to wait for them to time out.
+fn remove_expired_and_invalid_subscriptions(
+    config: StorageServiceConfig,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+) {
+    subscriptions
+        .lock()
+        .retain(|peer_network_id, subscription_stream_requests| {
+            // Remove the stream if it has expired
+            if subscription_stream_requests.is_expired(config.max_subscription_period_ms) {
+                increment_counter(
+                    &metrics::SUBSCRIPTION_EVENTS,
+                    peer_network_id.network_id(),
+                    SUBSCRIPTION_EXPIRE.into(),
+                );
+                subscription_stream_requests.reject_pending_requests(
+                    &Error::UnexpectedErrorEncountered(
+                        "The subscription stream has expired!".into(),
+                    ),
+                );
+                return false;
+            }
+
+            // Remove the stream if there are too many pending requests
+            let num_pending_requests = subscription_stream_requests.num_pending_requests() as u64;
+            if num_pending_requests > config.max_pending_requests_per_subscription_stream {
+                let error = Error::InvalidRequest(format!(
+                    "Too many pending subscription requests! Found: {:?}, max: {:?}",
+                    num_pending_requests, config.max_pending_requests_per_subscription_stream
+                ));
+                warn!(LogSchema::new(LogEntry::SubscriptionRefresh)
+                    .error(&error)
+                    .peer_network_id(peer_network_id)
+                    .message("Dropping invalid subscription stream!"));
+                subscription_stream_requests.reject_pending_requests(&error);
+                return false;
+            }
+
+            true
+        });
+}
+
+/// Updates the active subscription stream metrics for each network
+fn update_subscription_metrics(
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+) {
+    // Calculate the total number of subscription streams for each network
+    let mut num_validator_subscriptions = 0;
+    let mut num_vfn_subscriptions = 0;
+    let mut num_public_subscriptions = 0;
+    for peer_network_id in subscriptions.lock().keys() {
+        match peer_network_id.network_id() {
+            NetworkId::Validator => num_validator_subscriptions += 1,
+            NetworkId::Vfn => num_vfn_subscriptions += 1,
+            NetworkId::Public => num_public_subscriptions += 1,
+        }
+    }
+
+    // Update the number of active subscription streams for each network
+    metrics::set_gauge(
+        &metrics::SUBSCRIPTION_COUNT,
+        NetworkId::Validator.as_str(),
+        num_validator_subscriptions as u64,
+    );
+    metrics::set_gauge(
+        &metrics::SUBSCRIPTION_COUNT,
+        NetworkId::Vfn.as_str(),
+        num_vfn_subscriptions as u64,
+    );
+    metrics::set_gauge(
+        &metrics::SUBSCRIPTION_COUNT,
+        NetworkId::Public.as_str(),
+        num_public_subscriptions as u64,
+    );
+}
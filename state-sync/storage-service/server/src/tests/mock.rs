@@ -34,7 +34,10 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
-    proof::{AccumulatorConsistencyProof, SparseMerkleProof, TransactionAccumulatorSummary},
+    proof::{
+        AccumulatorConsistencyProof, SparseMerkleMultiProof, SparseMerkleProof,
+        TransactionAccumulatorSummary,
+    },
     state_proof::StateProof,
     state_store::{
         state_key::StateKey,
@@ -348,6 +351,12 @@ mock! {
             chunk_size: usize,
         ) -> Result<StateValueChunkWithProof>;
 
+        fn get_state_values_with_multi_proof_by_version(
+            &self,
+            state_keys: &[StateKey],
+            version: Version,
+        ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)>;
+
         fn get_epoch_snapshot_prune_window(&self) -> Result<usize>;
 
         fn is_state_merkle_pruner_enabled(&self) -> Result<bool>;
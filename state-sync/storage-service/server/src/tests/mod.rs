@@ -13,6 +13,7 @@ mod protocol_version;
 mod request_moderator;
 mod state_values;
 mod storage_summary;
+mod subscription;
 mod transaction_outputs;
 mod transactions;
 mod transactions_or_outputs;
@@ -30,7 +30,7 @@ use dashmap::DashMap;
 use futures::channel::oneshot;
 use lru::LruCache;
 use rand::{rngs::OsRng, Rng};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::runtime::Handle;
 
 #[tokio::test]
@@ -71,6 +71,7 @@ async fn test_peers_with_ready_optimistic_fetches() {
     let cached_storage_server_summary =
         Arc::new(ArcSwap::from(Arc::new(StorageServerSummary::default())));
     let lru_response_cache = Arc::new(Mutex::new(LruCache::new(0)));
+    let subscriptions = Arc::new(Mutex::new(HashMap::new()));
     let request_moderator = Arc::new(RequestModerator::new(
         AptosDataClientConfig::default(),
         cached_storage_server_summary.clone(),
@@ -89,6 +90,7 @@ async fn test_peers_with_ready_optimistic_fetches() {
             lru_response_cache.clone(),
             request_moderator.clone(),
             storage_reader.clone(),
+            subscriptions.clone(),
             time_service.clone(),
         )
         .await
@@ -109,6 +111,7 @@ async fn test_peers_with_ready_optimistic_fetches() {
             lru_response_cache.clone(),
             request_moderator.clone(),
             storage_reader.clone(),
+            subscriptions.clone(),
             time_service.clone(),
         )
         .await
@@ -135,6 +138,7 @@ async fn test_peers_with_ready_optimistic_fetches() {
             lru_response_cache,
             request_moderator,
             storage_reader,
+            subscriptions.clone(),
             time_service,
         )
         .await
@@ -164,6 +168,7 @@ async fn test_remove_expired_optimistic_fetches() {
     let cached_storage_server_summary =
         Arc::new(ArcSwap::from(Arc::new(StorageServerSummary::default())));
     let lru_response_cache = Arc::new(Mutex::new(LruCache::new(0)));
+    let subscriptions = Arc::new(Mutex::new(HashMap::new()));
     let request_moderator = Arc::new(RequestModerator::new(
         AptosDataClientConfig::default(),
         cached_storage_server_summary.clone(),
@@ -200,6 +205,7 @@ async fn test_remove_expired_optimistic_fetches() {
             lru_response_cache.clone(),
             request_moderator.clone(),
             storage.clone(),
+            subscriptions.clone(),
             time_service.clone(),
         )
         .await
@@ -233,6 +239,7 @@ async fn test_remove_expired_optimistic_fetches() {
             lru_response_cache.clone(),
             request_moderator.clone(),
             storage.clone(),
+            subscriptions.clone(),
             time_service.clone(),
         )
         .await
@@ -253,6 +260,7 @@ async fn test_remove_expired_optimistic_fetches() {
             lru_response_cache,
             request_moderator,
             storage.clone(),
+            subscriptions.clone(),
             time_service.clone(),
         )
         .await
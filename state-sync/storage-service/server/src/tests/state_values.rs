@@ -9,15 +9,15 @@ use crate::tests::{
 use aptos_config::config::StorageServiceConfig;
 use aptos_crypto::hash::HashValue;
 use aptos_storage_service_types::{
-    requests::{DataRequest, StateValuesWithProofRequest},
+    requests::{DataRequest, StateValuesWithMultiProofRequest, StateValuesWithProofRequest},
     responses::{DataResponse, StorageServiceResponse},
     StorageServiceError,
 };
 use aptos_types::{
-    proof::definition::SparseMerkleRangeProof,
+    proof::definition::{SparseMerkleMultiProof, SparseMerkleRangeProof},
     state_store::{
         state_key::StateKey,
-        state_value::{StateValue, StateValueChunkWithProof},
+        state_value::{StateValue, StateValueChunkWithProof, StateValuesWithMultiProof},
     },
 };
 use claims::assert_matches;
@@ -175,6 +175,90 @@ async fn test_get_states_with_proof_not_serviceable() {
     }
 }
 
+#[tokio::test]
+async fn test_get_states_with_multi_proof() {
+    // Test small and large requests, including one larger than the max chunk
+    let max_state_chunk_size = StorageServiceConfig::default().max_state_chunk_size;
+    for num_state_keys in [1, 100, max_state_chunk_size, max_state_chunk_size * 2] {
+        // Create test data
+        let version = 101;
+        let state_keys: Vec<_> = (0..num_state_keys)
+            .map(|i| StateKey::raw(i.to_le_bytes().to_vec()))
+            .collect();
+        let num_served_keys = num_state_keys.min(max_state_chunk_size) as usize;
+        let state_values: Vec<_> = state_keys[..num_served_keys]
+            .iter()
+            .map(|state_key| (state_key.clone(), None))
+            .collect();
+        let proof = SparseMerkleMultiProof::new(vec![], vec![HashValue::random()]);
+
+        // Create the mock db reader
+        let mut db_reader = mock::create_mock_db_reader();
+        let expected_state_keys = state_keys[..num_served_keys].to_vec();
+        let (state_values_clone, proof_clone) = (state_values.clone(), proof.clone());
+        db_reader
+            .expect_get_state_values_with_multi_proof_by_version()
+            .times(1)
+            .withf(move |state_keys: &[StateKey], requested_version: &u64| {
+                state_keys == expected_state_keys.as_slice() && *requested_version == version
+            })
+            .returning(move |_, _| Ok((state_values_clone.clone(), proof_clone.clone())));
+
+        // Create the storage client and server
+        let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+        utils::update_storage_server_summary(&mut service, version, 10);
+        tokio::spawn(service.start());
+
+        // Process a request to fetch the states with a multi-proof
+        let response = get_state_values_with_multi_proof(&mut mock_client, version, state_keys)
+            .await
+            .unwrap();
+
+        // Verify the response is correct
+        assert_matches!(response, StorageServiceResponse::RawResponse(_));
+        assert_eq!(
+            response.get_data_response().unwrap(),
+            DataResponse::StateValuesWithMultiProof(StateValuesWithMultiProof::new(
+                state_values,
+                proof
+            ))
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_get_states_with_multi_proof_invalid() {
+    // Create the storage client and server
+    let version = 101;
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, version, 10);
+    tokio::spawn(service.start());
+
+    // Test a request without any state keys
+    let response = get_state_values_with_multi_proof(&mut mock_client, version, vec![])
+        .await
+        .unwrap_err();
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+#[tokio::test]
+async fn test_get_states_with_multi_proof_not_serviceable() {
+    // Create the storage client and server (that cannot service the request)
+    let version = 101;
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, version - 1, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the states with a multi-proof
+    let state_keys = vec![StateKey::raw(vec![])];
+    let response = get_state_values_with_multi_proof(&mut mock_client, version, state_keys)
+        .await
+        .unwrap_err();
+
+    // Verify the request is not serviceable
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
 /// Creates a set of state keys and values using the specified number and size
 fn create_state_keys_and_values(
     num_keys_and_values: u64,
@@ -230,6 +314,20 @@ async fn get_state_values_with_proof(
     utils::send_storage_request(mock_client, use_compression, data_request).await
 }
 
+/// Sends a state values with multi-proof request and processes the response
+async fn get_state_values_with_multi_proof(
+    mock_client: &mut MockClient,
+    version: u64,
+    state_keys: Vec<StateKey>,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request =
+        DataRequest::GetStateValuesWithMultiProof(StateValuesWithMultiProofRequest {
+            version,
+            state_keys,
+        });
+    utils::send_storage_request(mock_client, false, data_request).await
+}
+
 /// A helper method to request a states with proof chunk using the
 /// the specified network limit.
 async fn get_states_with_proof_network_limit(network_limit_bytes: u64) {
@@ -0,0 +1,426 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network::ResponseSender,
+    subscription::{SubscriptionRequest, SubscriptionStreamRequests},
+    tests::{mock, mock::MockClient, utils},
+};
+use aptos_config::{
+    config::StorageServiceConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_infallible::Mutex;
+use aptos_storage_service_types::{
+    requests::{
+        DataRequest, StorageServiceRequest, SubscribeTransactionOutputsWithProofRequest,
+        SubscriptionStreamMetadata,
+    },
+    responses::DataResponse,
+    StorageServiceError,
+};
+use aptos_time_service::TimeService;
+use aptos_types::PeerId;
+use claims::{assert_err, assert_matches, assert_none, assert_ok, assert_some};
+use futures::channel::{oneshot, oneshot::Receiver};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+#[test]
+fn test_subscription_stream_add_requests() {
+    // Create a new subscription stream
+    let time_service = TimeService::mock();
+    let stream_id = 10;
+    let subscription_request = create_subscription_request(time_service.clone(), stream_id, 1);
+    let mut subscription_stream_requests =
+        SubscriptionStreamRequests::new(subscription_request, time_service.clone());
+
+    // Verify the first request to serve (index 0) has not been received yet
+    assert_none!(subscription_stream_requests.first_pending_request());
+
+    // Add more requests to the stream (out of order)
+    for stream_index in [3, 0, 2] {
+        let subscription_request =
+            create_subscription_request(time_service.clone(), stream_id, stream_index);
+        assert_ok!(subscription_stream_requests.add_subscription_request(subscription_request));
+    }
+    assert_eq!(subscription_stream_requests.num_pending_requests(), 4);
+
+    // Verify that duplicate requests are rejected
+    let subscription_request = create_subscription_request(time_service.clone(), stream_id, 2);
+    assert_err!(subscription_stream_requests.add_subscription_request(subscription_request));
+
+    // Verify that requests for a different stream are rejected
+    let subscription_request = create_subscription_request(time_service.clone(), stream_id + 1, 4);
+    assert_err!(subscription_stream_requests.add_subscription_request(subscription_request));
+
+    // Verify the first request to serve is now index 0
+    let subscription_request = subscription_stream_requests
+        .first_pending_request()
+        .unwrap();
+    assert_eq!(subscription_request.subscription_stream_index(), 0);
+
+    // Pop the first request
+    let subscription_request =
+        assert_some!(subscription_stream_requests.pop_first_pending_request());
+    assert_eq!(subscription_request.subscription_stream_index(), 0);
+
+    // Verify that duplicate requests for the index being served are rejected
+    let duplicate_request = create_subscription_request(time_service.clone(), stream_id, 0);
+    assert_err!(subscription_stream_requests.add_subscription_request(duplicate_request));
+
+    // Verify that no other request is served until the stream is updated
+    assert_none!(subscription_stream_requests.first_pending_request());
+    assert_none!(subscription_stream_requests.pop_first_pending_request());
+
+    // Serve the first request
+    let data_response = create_outputs_data_response(101, 110, 1000, 10);
+    assert_ok!(subscription_stream_requests.update_known_version_and_epoch(&data_response));
+
+    // Verify the highest known version was updated and the next request is index 1
+    assert_eq!(
+        subscription_stream_requests.get_highest_known_version_and_epoch(),
+        (110, 10)
+    );
+    let subscription_request = subscription_stream_requests
+        .first_pending_request()
+        .unwrap();
+    assert_eq!(subscription_request.subscription_stream_index(), 1);
+
+    // Verify that requests for indices already served are rejected
+    let subscription_request = create_subscription_request(time_service, stream_id, 0);
+    assert_err!(subscription_stream_requests.add_subscription_request(subscription_request));
+}
+
+#[test]
+fn test_subscription_stream_epoch_change() {
+    // Create a new subscription stream
+    let time_service = TimeService::mock();
+    let subscription_request = create_subscription_request(time_service.clone(), 0, 0);
+    let mut subscription_stream_requests =
+        SubscriptionStreamRequests::new(subscription_request, time_service);
+
+    // Serve the first request with data that doesn't reach the end of the epoch
+    let _ = subscription_stream_requests.pop_first_pending_request();
+    let data_response = create_outputs_data_response(101, 150, 200, 10);
+    assert_ok!(subscription_stream_requests.update_known_version_and_epoch(&data_response));
+    assert_eq!(
+        subscription_stream_requests.get_highest_known_version_and_epoch(),
+        (150, 10)
+    );
+
+    // Serve the next request with data that reaches the end of the epoch
+    let data_response = create_outputs_data_response(151, 200, 200, 10);
+    assert_ok!(subscription_stream_requests.update_known_version_and_epoch(&data_response));
+    assert_eq!(
+        subscription_stream_requests.get_highest_known_version_and_epoch(),
+        (200, 11)
+    );
+}
+
+#[tokio::test]
+async fn test_subscription_stream_expiry() {
+    // Create a new subscription stream
+    let time_service = TimeService::mock();
+    let subscription_request = create_subscription_request(time_service.clone(), 0, 0);
+    let mut subscription_stream_requests =
+        SubscriptionStreamRequests::new(subscription_request, time_service.clone());
+
+    // Elapse some time and verify the stream has not expired
+    let max_subscription_period_ms = 1000;
+    utils::elapse_time(max_subscription_period_ms / 2, &time_service).await;
+    assert!(!subscription_stream_requests.is_expired(max_subscription_period_ms));
+
+    // Serve a request and elapse more time (the stream should not expire)
+    let _ = subscription_stream_requests.pop_first_pending_request();
+    let data_response = create_outputs_data_response(101, 110, 1000, 10);
+    assert_ok!(subscription_stream_requests.update_known_version_and_epoch(&data_response));
+    utils::elapse_time(max_subscription_period_ms, &time_service).await;
+    assert!(!subscription_stream_requests.is_expired(max_subscription_period_ms));
+
+    // Elapse more time and verify the stream has now expired
+    utils::elapse_time(1, &time_service).await;
+    assert!(subscription_stream_requests.is_expired(max_subscription_period_ms));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_subscribe_transaction_outputs() {
+    // Create test data
+    let chunk_size = 10;
+    let highest_version = 100;
+    let highest_epoch = 5;
+    let lowest_version = 0;
+    let peer_version = 70;
+    let highest_ledger_info =
+        utils::create_test_ledger_info_with_sigs(highest_epoch, highest_version);
+    let output_list_with_proof_1 = utils::create_output_list_with_proof(
+        peer_version + 1,
+        peer_version + chunk_size,
+        highest_version,
+    );
+    let output_list_with_proof_2 = utils::create_output_list_with_proof(
+        peer_version + chunk_size + 1,
+        peer_version + (chunk_size * 2),
+        highest_version,
+    );
+
+    // Create the mock db reader
+    let mut db_reader =
+        mock::create_mock_db_for_optimistic_fetch(highest_ledger_info.clone(), lowest_version);
+    utils::expect_get_transaction_outputs(
+        &mut db_reader,
+        peer_version + 1,
+        chunk_size,
+        highest_version,
+        output_list_with_proof_1.clone(),
+    );
+    utils::expect_get_transaction_outputs(
+        &mut db_reader,
+        peer_version + chunk_size + 1,
+        chunk_size,
+        highest_version,
+        output_list_with_proof_2.clone(),
+    );
+
+    // Create the storage client and server
+    let storage_config = StorageServiceConfig {
+        max_transaction_output_chunk_size: chunk_size,
+        ..Default::default()
+    };
+    let (mut mock_client, service, storage_service_notifier, mock_time, _) =
+        MockClient::new(Some(db_reader), Some(storage_config));
+    let active_subscriptions = service.get_subscriptions();
+    tokio::spawn(service.start());
+
+    // Send the first two requests of a new subscription stream
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let stream_metadata = SubscriptionStreamMetadata {
+        known_version_at_stream_start: peer_version,
+        known_epoch_at_stream_start: highest_epoch,
+        subscription_stream_id: 0,
+    };
+    let mut response_receiver_1 =
+        subscribe_to_outputs_with_proof(&mut mock_client, stream_metadata, 0, peer_network_id)
+            .await;
+    let mut response_receiver_2 =
+        subscribe_to_outputs_with_proof(&mut mock_client, stream_metadata, 1, peer_network_id)
+            .await;
+
+    // Wait until the subscription requests are active
+    wait_for_pending_subscription_requests(active_subscriptions.clone(), peer_network_id, 2).await;
+
+    // Verify no subscription responses have been received yet
+    assert_none!(response_receiver_1.try_recv().unwrap());
+    assert_none!(response_receiver_2.try_recv().unwrap());
+
+    // Force the subscription handler to work
+    utils::force_optimistic_fetch_handler_to_run(
+        &mut mock_client,
+        &mock_time,
+        &storage_service_notifier,
+    )
+    .await;
+
+    // Verify a response is received for the first request
+    utils::verify_new_transaction_outputs_with_proof(
+        &mut mock_client,
+        response_receiver_1,
+        output_list_with_proof_1,
+        highest_ledger_info.clone(),
+    )
+    .await;
+
+    // Wait until the first request has been removed and the stream updated
+    wait_for_highest_known_version(
+        active_subscriptions.clone(),
+        peer_network_id,
+        peer_version + chunk_size,
+    )
+    .await;
+
+    // Force the subscription handler to work again
+    utils::advance_storage_refresh_time(&mock_time).await;
+
+    // Verify a response is received for the second request (with the next chunk of data)
+    utils::verify_new_transaction_outputs_with_proof(
+        &mut mock_client,
+        response_receiver_2,
+        output_list_with_proof_2,
+        highest_ledger_info,
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_subscription_stream_too_many_pending_requests() {
+    // Create test data
+    let highest_version = 100;
+    let highest_epoch = 5;
+    let lowest_version = 0;
+    let peer_version = 50;
+    let highest_ledger_info =
+        utils::create_test_ledger_info_with_sigs(highest_epoch, highest_version);
+
+    // Create the storage client and server
+    let db_reader = mock::create_mock_db_for_optimistic_fetch(highest_ledger_info, lowest_version);
+    let storage_config = StorageServiceConfig {
+        max_pending_requests_per_subscription_stream: 1,
+        ..Default::default()
+    };
+    let (mut mock_client, service, storage_service_notifier, mock_time, _) =
+        MockClient::new(Some(db_reader), Some(storage_config));
+    let active_subscriptions = service.get_subscriptions();
+    tokio::spawn(service.start());
+
+    // Send two requests for a new subscription stream (skipping index 0, so neither is served)
+    let peer_network_id = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let stream_metadata = SubscriptionStreamMetadata {
+        known_version_at_stream_start: peer_version,
+        known_epoch_at_stream_start: highest_epoch,
+        subscription_stream_id: 0,
+    };
+    let mut response_receivers = vec![];
+    for stream_index in [1, 2] {
+        let response_receiver = subscribe_to_outputs_with_proof(
+            &mut mock_client,
+            stream_metadata,
+            stream_index,
+            peer_network_id,
+        )
+        .await;
+        response_receivers.push(response_receiver);
+    }
+
+    // Wait until the subscription requests are active
+    wait_for_pending_subscription_requests(active_subscriptions.clone(), peer_network_id, 2).await;
+
+    // Force the subscription handler to work
+    utils::force_optimistic_fetch_handler_to_run(
+        &mut mock_client,
+        &mock_time,
+        &storage_service_notifier,
+    )
+    .await;
+
+    // Verify both pending requests are rejected
+    for response_receiver in response_receivers {
+        let response = mock_client
+            .wait_for_response(response_receiver)
+            .await
+            .unwrap_err();
+        assert_matches!(response, StorageServiceError::InvalidRequest(_));
+    }
+
+    // Verify the stream has been removed
+    assert_none!(active_subscriptions.lock().get(&peer_network_id));
+}
+
+/// Creates a transaction outputs data response for the given versions
+fn create_outputs_data_response(
+    start_version: u64,
+    end_version: u64,
+    target_version: u64,
+    target_epoch: u64,
+) -> DataResponse {
+    let output_list_with_proof =
+        utils::create_output_list_with_proof(start_version, end_version, target_version);
+    let target_ledger_info = utils::create_epoch_ending_ledger_info(target_epoch, target_version);
+    DataResponse::NewTransactionOutputsWithProof((output_list_with_proof, target_ledger_info))
+}
+
+/// Creates a new subscription request for transaction outputs
+fn create_subscription_request(
+    time_service: TimeService,
+    subscription_stream_id: u64,
+    subscription_stream_index: u64,
+) -> SubscriptionRequest {
+    // Create a storage service request
+    let data_request = create_subscription_data_request(
+        SubscriptionStreamMetadata {
+            known_version_at_stream_start: 100,
+            known_epoch_at_stream_start: 10,
+            subscription_stream_id,
+        },
+        subscription_stream_index,
+    );
+    let storage_service_request = StorageServiceRequest::new(data_request, true);
+
+    // Create the response sender
+    let (callback, _) = oneshot::channel();
+    let response_sender = ResponseSender::new(callback);
+
+    // Create and return the subscription request
+    SubscriptionRequest::new(storage_service_request, response_sender, time_service)
+}
+
+/// Creates a subscription data request for transaction outputs
+fn create_subscription_data_request(
+    subscription_stream_metadata: SubscriptionStreamMetadata,
+    subscription_stream_index: u64,
+) -> DataRequest {
+    DataRequest::SubscribeTransactionOutputsWithProof(SubscribeTransactionOutputsWithProofRequest {
+        subscription_stream_metadata,
+        subscription_stream_index,
+    })
+}
+
+/// Creates and sends a subscription request for transaction outputs
+async fn subscribe_to_outputs_with_proof(
+    mock_client: &mut MockClient,
+    subscription_stream_metadata: SubscriptionStreamMetadata,
+    subscription_stream_index: u64,
+    peer_network_id: PeerNetworkId,
+) -> Receiver<Result<bytes::Bytes, aptos_network::protocols::network::RpcError>> {
+    // Create the data request
+    let data_request =
+        create_subscription_data_request(subscription_stream_metadata, subscription_stream_index);
+    let storage_request = StorageServiceRequest::new(data_request, true);
+
+    // Send the request
+    let (peer_id, network_id) = utils::extract_peer_and_network_id(Some(peer_network_id));
+    mock_client
+        .send_request(storage_request, peer_id, network_id)
+        .await
+}
+
+/// Waits until the highest known version of the peer's stream matches the expected version
+async fn wait_for_highest_known_version(
+    active_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    peer_network_id: PeerNetworkId,
+    expected_highest_known_version: u64,
+) {
+    loop {
+        if let Some(subscription_stream_requests) =
+            active_subscriptions.lock().get(&peer_network_id)
+        {
+            let (highest_known_version, _) =
+                subscription_stream_requests.get_highest_known_version_and_epoch();
+            if highest_known_version == expected_highest_known_version {
+                return; // The stream has been updated
+            }
+        }
+
+        // Sleep for a while
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Waits until the peer's stream has the expected number of pending requests
+async fn wait_for_pending_subscription_requests(
+    active_subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
+    peer_network_id: PeerNetworkId,
+    expected_num_pending_requests: usize,
+) {
+    loop {
+        if let Some(subscription_stream_requests) =
+            active_subscriptions.lock().get(&peer_network_id)
+        {
+            if subscription_stream_requests.num_pending_requests() == expected_num_pending_requests
+            {
+                return; // We found the expected number of pending requests
+            }
+        }
+
+        // Sleep for a while
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
@@ -2,21 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    error::Error, handler::Handler, moderator::RequestModerator,
+    error::Error, handler::Handler, moderator::RequestModerator, network::ResponseSender,
     optimistic_fetch::OptimisticFetchRequest, storage::StorageReaderInterface,
+    subscription::SubscriptionStreamRequests,
 };
 use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
 use aptos_infallible::Mutex;
 use aptos_storage_service_types::{
     requests::{DataRequest, EpochEndingLedgerInfoRequest, StorageServiceRequest},
     responses::{DataResponse, StorageServerSummary, StorageServiceResponse},
+    StorageServiceError,
 };
 use aptos_time_service::TimeService;
 use aptos_types::ledger_info::LedgerInfoWithSignatures;
 use arc_swap::ArcSwap;
 use dashmap::DashMap;
 use lru::LruCache;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Gets the epoch ending ledger info at the given epoch
 pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
@@ -27,6 +29,7 @@ pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
     request_moderator: Arc<RequestModerator>,
     peer_network_id: &PeerNetworkId,
     storage: T,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     time_service: TimeService,
 ) -> aptos_storage_service_types::Result<LedgerInfoWithSignatures, Error> {
     // Create a new storage request for the epoch ending ledger info
@@ -46,6 +49,7 @@ pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
         lru_response_cache,
         request_moderator,
         storage,
+        subscriptions,
         time_service,
     );
     let storage_response = handler.process_request(peer_network_id, storage_request, true);
@@ -75,105 +79,132 @@ pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
 }
 
 /// Notifies a peer of new data according to the target ledger info.
+/// The missing data is fetched using the given storage request and the
+/// transformed data response is returned (after it has been sent). If
+/// the data can't be fetched, an error response is sent to the peer.
 ///
-/// Note: we don't need to check the size of the optimistic fetch response
-/// because: (i) each sub-part should already be checked; and (ii)
-/// optimistic fetch responses are best effort.
+/// Note: we don't need to check the size of the optimistic fetch (or
+/// subscription) response because: (i) each sub-part should already be
+/// checked; and (ii) these responses are best effort.
 pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
     cached_storage_server_summary: Arc<ArcSwap<StorageServerSummary>>,
-    config: StorageServiceConfig,
     optimistic_fetches: Arc<DashMap<PeerNetworkId, OptimisticFetchRequest>>,
+    subscriptions: Arc<Mutex<HashMap<PeerNetworkId, SubscriptionStreamRequests>>>,
     lru_response_cache: Arc<Mutex<LruCache<StorageServiceRequest, StorageServiceResponse>>>,
     request_moderator: Arc<RequestModerator>,
     storage: T,
     time_service: TimeService,
     peer_network_id: &PeerNetworkId,
-    optimistic_fetch: OptimisticFetchRequest,
+    missing_data_request: StorageServiceRequest,
     target_ledger_info: LedgerInfoWithSignatures,
-) -> aptos_storage_service_types::Result<(), Error> {
-    match optimistic_fetch.get_storage_request_for_missing_data(config, &target_ledger_info) {
-        Ok(storage_request) => {
-            // Handle the storage service request to fetch the missing data
-            let use_compression = storage_request.use_compression;
-            let handler = Handler::new(
-                cached_storage_server_summary,
-                optimistic_fetches,
-                lru_response_cache,
-                request_moderator,
-                storage,
-                time_service,
-            );
-            let storage_response =
-                handler.process_request(peer_network_id, storage_request.clone(), true);
-
-            // Transform the missing data into an optimistic fetch response
-            let transformed_data_response = match storage_response {
-                Ok(storage_response) => match storage_response.get_data_response() {
-                    Ok(DataResponse::TransactionsWithProof(transactions_with_proof)) => {
-                        DataResponse::NewTransactionsWithProof((
-                            transactions_with_proof,
-                            target_ledger_info.clone(),
-                        ))
-                    },
-                    Ok(DataResponse::TransactionOutputsWithProof(outputs_with_proof)) => {
-                        DataResponse::NewTransactionOutputsWithProof((
-                            outputs_with_proof,
-                            target_ledger_info.clone(),
-                        ))
-                    },
-                    Ok(DataResponse::TransactionsOrOutputsWithProof((
-                        transactions_with_proof,
-                        outputs_with_proof,
-                    ))) => {
-                        if let Some(transactions_with_proof) = transactions_with_proof {
-                            DataResponse::NewTransactionsOrOutputsWithProof((
-                                (Some(transactions_with_proof), None),
-                                target_ledger_info.clone(),
-                            ))
-                        } else if let Some(outputs_with_proof) = outputs_with_proof {
-                            DataResponse::NewTransactionsOrOutputsWithProof((
-                                (None, Some(outputs_with_proof)),
-                                target_ledger_info.clone(),
-                            ))
-                        } else {
-                            return Err(Error::UnexpectedErrorEncountered(
-                                "Failed to get a transaction or output response for peer!".into(),
-                            ));
-                        }
-                    },
-                    data_response => {
-                        return Err(Error::UnexpectedErrorEncountered(format!(
-                            "Failed to get appropriate data response for peer! Got: {:?}",
-                            data_response
-                        )))
-                    },
-                },
-                response => {
-                    return Err(Error::UnexpectedErrorEncountered(format!(
-                        "Failed to fetch missing data for peer! {:?}",
-                        response
-                    )))
-                },
-            };
-            let storage_response =
-                match StorageServiceResponse::new(transformed_data_response, use_compression) {
-                    Ok(storage_response) => storage_response,
-                    Err(error) => {
-                        return Err(Error::UnexpectedErrorEncountered(format!(
-                            "Failed to create transformed response! Error: {:?}",
-                            error
-                        )));
-                    },
-                };
+    response_sender: ResponseSender,
+) -> aptos_storage_service_types::Result<DataResponse, Error> {
+    // Fetch the missing data and transform it into a new data response
+    let handler = Handler::new(
+        cached_storage_server_summary,
+        optimistic_fetches,
+        lru_response_cache,
+        request_moderator,
+        storage,
+        subscriptions,
+        time_service,
+    );
+    let response = fetch_new_data_response(
+        &handler,
+        peer_network_id,
+        missing_data_request.clone(),
+        target_ledger_info,
+    );
 
-            // Send the response to the peer
+    // Send the response (or the error) to the peer
+    match response {
+        Ok((transformed_data_response, storage_response)) => {
+            handler.send_response(missing_data_request, Ok(storage_response), response_sender);
+            Ok(transformed_data_response)
+        },
+        Err(error) => {
             handler.send_response(
-                storage_request,
-                Ok(storage_response),
-                optimistic_fetch.get_response_sender(),
+                missing_data_request,
+                Err(StorageServiceError::InternalError(error.to_string())),
+                response_sender,
             );
-            Ok(())
+            Err(error)
         },
-        Err(error) => Err(error),
     }
 }
+
+/// Fetches the missing data using the given storage request and transforms
+/// it into an optimistic fetch (or subscription) response. Returns the data
+/// response and the storage response to send to the peer.
+fn fetch_new_data_response<T: StorageReaderInterface>(
+    handler: &Handler<T>,
+    peer_network_id: &PeerNetworkId,
+    missing_data_request: StorageServiceRequest,
+    target_ledger_info: LedgerInfoWithSignatures,
+) -> aptos_storage_service_types::Result<(DataResponse, StorageServiceResponse), Error> {
+    // Handle the storage service request to fetch the missing data
+    let use_compression = missing_data_request.use_compression;
+    let storage_response = handler.process_request(peer_network_id, missing_data_request, true);
+
+    // Transform the missing data into an optimistic fetch (or subscription) response
+    let transformed_data_response = match storage_response {
+        Ok(storage_response) => match storage_response.get_data_response() {
+            Ok(DataResponse::TransactionsWithProof(transactions_with_proof)) => {
+                DataResponse::NewTransactionsWithProof((
+                    transactions_with_proof,
+                    target_ledger_info.clone(),
+                ))
+            },
+            Ok(DataResponse::TransactionOutputsWithProof(outputs_with_proof)) => {
+                DataResponse::NewTransactionOutputsWithProof((
+                    outputs_with_proof,
+                    target_ledger_info.clone(),
+                ))
+            },
+            Ok(DataResponse::TransactionsOrOutputsWithProof((
+                transactions_with_proof,
+                outputs_with_proof,
+            ))) => {
+                if let Some(transactions_with_proof) = transactions_with_proof {
+                    DataResponse::NewTransactionsOrOutputsWithProof((
+                        (Some(transactions_with_proof), None),
+                        target_ledger_info.clone(),
+                    ))
+                } else if let Some(outputs_with_proof) = outputs_with_proof {
+                    DataResponse::NewTransactionsOrOutputsWithProof((
+                        (None, Some(outputs_with_proof)),
+                        target_ledger_info.clone(),
+                    ))
+                } else {
+                    return Err(Error::UnexpectedErrorEncountered(
+                        "Failed to get a transaction or output response for peer!".into(),
+                    ));
+                }
+            },
+            data_response => {
+                return Err(Error::UnexpectedErrorEncountered(format!(
+                    "Failed to get appropriate data response for peer! Got: {:?}",
+                    data_response
+                )))
+            },
+        },
+        response => {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Failed to fetch missing data for peer! {:?}",
+                response
+            )))
+        },
+    };
+    let storage_response =
+        match StorageServiceResponse::new(transformed_data_response.clone(), use_compression) {
+            Ok(storage_response) => storage_response,
+            Err(error) => {
+                return Err(Error::UnexpectedErrorEncountered(format!(
+                    "Failed to create transformed response! Error: {:?}",
+                    error
+                )));
+            },
+        };
+
+    Ok((transformed_data_response, storage_response))
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::COMPRESSION_SUFFIX_LABEL;
-use aptos_types::transaction::Version;
+use aptos_types::{state_store::state_key::StateKey, transaction::Version};
 use serde::{Deserialize, Serialize};
 
 /// A storage service request.
@@ -44,6 +44,10 @@ pub enum DataRequest {
     GetTransactionsWithProof(TransactionsWithProofRequest), // Fetches a list of transactions with a proof
     GetNewTransactionsOrOutputsWithProof(NewTransactionsOrOutputsWithProofRequest), // Optimistically fetches new transactions or outputs
     GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest), // Fetches a list of transactions or outputs with a proof
+    SubscribeTransactionOutputsWithProof(SubscribeTransactionOutputsWithProofRequest), // Subscribes to transaction outputs with a proof
+    SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest), // Subscribes to transactions with a proof
+    SubscribeTransactionsOrOutputsWithProof(SubscribeTransactionsOrOutputsWithProofRequest), // Subscribes to transactions or outputs with a proof
+    GetStateValuesWithMultiProof(StateValuesWithMultiProofRequest), // Fetches the states of a list of keys with a single proof
 }
 
 impl DataRequest {
@@ -63,6 +67,14 @@ impl DataRequest {
                 "get_new_transactions_or_outputs_with_proof"
             },
             Self::GetTransactionsOrOutputsWithProof(_) => "get_transactions_or_outputs_with_proof",
+            Self::SubscribeTransactionOutputsWithProof(_) => {
+                "subscribe_transaction_outputs_with_proof"
+            },
+            Self::SubscribeTransactionsWithProof(_) => "subscribe_transactions_with_proof",
+            Self::SubscribeTransactionsOrOutputsWithProof(_) => {
+                "subscribe_transactions_or_outputs_with_proof"
+            },
+            Self::GetStateValuesWithMultiProof(_) => "get_state_values_with_multi_proof",
         }
     }
 
@@ -79,6 +91,12 @@ impl DataRequest {
     pub fn is_protocol_version_request(&self) -> bool {
         matches!(self, &Self::GetServerProtocolVersion)
     }
+
+    pub fn is_subscription_request(&self) -> bool {
+        matches!(self, &Self::SubscribeTransactionOutputsWithProof(_))
+            || matches!(self, &Self::SubscribeTransactionsWithProof(_))
+            || matches!(self, Self::SubscribeTransactionsOrOutputsWithProof(_))
+    }
 }
 
 /// A storage service request for fetching a list of epoch ending ledger infos.
@@ -114,6 +132,14 @@ pub struct StateValuesWithProofRequest {
     pub end_index: u64,   // The index to stop fetching state values (inclusive)
 }
 
+/// A storage service request for fetching the state values of a list of
+/// state keys at a specified version, with a single proof for all of them.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct StateValuesWithMultiProofRequest {
+    pub version: u64,              // The version to fetch the state values at
+    pub state_keys: Vec<StateKey>, // The state keys to fetch the state values of
+}
+
 /// A storage service request for fetching a transaction output list with a
 /// corresponding proof.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -153,3 +179,43 @@ pub struct TransactionsOrOutputsWithProofRequest {
     pub include_events: bool, // Whether or not to include events (if transactions are returned)
     pub max_num_output_reductions: u64, // The max num of output reductions before transactions are returned
 }
+
+/// The metadata shared by all requests along a single subscription stream.
+/// A stream is identified by its ID, and starts immediately after the
+/// version and epoch known by the peer when the stream was created.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SubscriptionStreamMetadata {
+    pub known_version_at_stream_start: u64, // The highest known version at the start of the stream
+    pub known_epoch_at_stream_start: u64,   // The highest known epoch at the start of the stream
+    pub subscription_stream_id: u64,        // The unique id of the subscription stream
+}
+
+/// A storage service request for subscribing to transaction outputs with a
+/// corresponding proof. Each request along the stream is serviced (in index
+/// order) with the next chunk of outputs beyond the previously served chunk.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SubscribeTransactionOutputsWithProofRequest {
+    pub subscription_stream_metadata: SubscriptionStreamMetadata, // The metadata for the subscription stream
+    pub subscription_stream_index: u64, // The index of the request in the subscription stream
+}
+
+/// A storage service request for subscribing to transactions with a
+/// corresponding proof. Each request along the stream is serviced (in index
+/// order) with the next chunk of transactions beyond the previously served chunk.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SubscribeTransactionsWithProofRequest {
+    pub subscription_stream_metadata: SubscriptionStreamMetadata, // The metadata for the subscription stream
+    pub subscription_stream_index: u64, // The index of the request in the subscription stream
+    pub include_events: bool,           // Whether or not to include events in the response
+}
+
+/// A storage service request for subscribing to transactions or outputs
+/// with a corresponding proof. Each request along the stream is serviced (in
+/// index order) with the next chunk of data beyond the previously served chunk.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SubscribeTransactionsOrOutputsWithProofRequest {
+    pub subscription_stream_metadata: SubscriptionStreamMetadata, // The metadata for the subscription stream
+    pub subscription_stream_index: u64, // The index of the request in the subscription stream
+    pub include_events: bool,           // Whether or not to include events in the response
+    pub max_num_output_reductions: u64, // The max num of output reductions before transactions are returned
+}
@@ -2,12 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    requests::DataRequest::{
-        GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
-        GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
-        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
-        GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
-        GetTransactionsWithProof,
+    requests::{
+        DataRequest::{
+            GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
+            GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
+            GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithMultiProof,
+            GetStateValuesWithProof, GetStorageServerSummary, GetTransactionOutputsWithProof,
+            GetTransactionsOrOutputsWithProof, GetTransactionsWithProof,
+            SubscribeTransactionOutputsWithProof, SubscribeTransactionsOrOutputsWithProof,
+            SubscribeTransactionsWithProof,
+        },
+        SubscriptionStreamMetadata,
     },
     responses::Error::DegenerateRangeError,
     Epoch, StorageServiceRequest, COMPRESSION_SUFFIX_LABEL,
@@ -19,7 +24,7 @@ use aptos_config::config::{
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    state_store::state_value::{StateValueChunkWithProof, StateValuesWithMultiProof},
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use num_traits::{PrimInt, Zero};
@@ -131,6 +136,7 @@ pub enum DataResponse {
     TransactionsWithProof(TransactionListWithProof),
     NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
     TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
+    StateValuesWithMultiProof(StateValuesWithMultiProof),
 }
 
 impl DataResponse {
@@ -148,6 +154,7 @@ impl DataResponse {
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::StateValuesWithMultiProof(_) => "state_values_with_multi_proof",
         }
     }
 }
@@ -185,6 +192,21 @@ impl TryFrom<StorageServiceResponse> for StateValueChunkWithProof {
     }
 }
 
+impl TryFrom<StorageServiceResponse> for StateValuesWithMultiProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::StateValuesWithMultiProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected state_values_with_multi_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for EpochChangeProof {
     type Error = crate::responses::Error;
 
@@ -451,21 +473,9 @@ impl DataSummary {
                 .states
                 .map(|range| range.contains(*version))
                 .unwrap_or(false),
-            GetStateValuesWithProof(request) => {
-                let proof_version = request.version;
-
-                let can_serve_states = self
-                    .states
-                    .map(|range| range.contains(request.version))
-                    .unwrap_or(false);
-
-                let can_create_proof = self
-                    .synced_ledger_info
-                    .as_ref()
-                    .map(|li| li.ledger_info().version() >= proof_version)
-                    .unwrap_or(false);
-
-                can_serve_states && can_create_proof
+            GetStateValuesWithProof(request) => self.can_service_states_request(request.version),
+            GetStateValuesWithMultiProof(request) => {
+                self.can_service_states_request(request.version)
             },
             GetTransactionOutputsWithProof(request) => {
                 let desired_range =
@@ -535,9 +545,38 @@ impl DataSummary {
 
                 can_serve_txns && can_serve_outputs && can_create_proof
             },
+            SubscribeTransactionOutputsWithProof(request) => self.can_service_subscription_request(
+                aptos_data_client_config,
+                request.subscription_stream_metadata,
+            ),
+            SubscribeTransactionsWithProof(request) => self.can_service_subscription_request(
+                aptos_data_client_config,
+                request.subscription_stream_metadata,
+            ),
+            SubscribeTransactionsOrOutputsWithProof(request) => self
+                .can_service_subscription_request(
+                    aptos_data_client_config,
+                    request.subscription_stream_metadata,
+                ),
         }
     }
 
+    /// Returns true iff a request for the states at the given version can be serviced
+    fn can_service_states_request(&self, version: Version) -> bool {
+        let can_serve_states = self
+            .states
+            .map(|range| range.contains(version))
+            .unwrap_or(false);
+
+        let can_create_proof = self
+            .synced_ledger_info
+            .as_ref()
+            .map(|li| li.ledger_info().version() >= version)
+            .unwrap_or(false);
+
+        can_serve_states && can_create_proof
+    }
+
     /// Returns true iff the optimistic data request can be serviced
     fn can_service_optimistic_request(
         &self,
@@ -551,6 +590,20 @@ impl DataSummary {
             .unwrap_or(false)
     }
 
+    /// Returns true iff the subscription request can be serviced
+    fn can_service_subscription_request(
+        &self,
+        aptos_data_client_config: &AptosDataClientConfig,
+        subscription_stream_metadata: SubscriptionStreamMetadata,
+    ) -> bool {
+        let known_version = subscription_stream_metadata.known_version_at_stream_start;
+        let max_version_lag = aptos_data_client_config.max_subscription_version_lag;
+        self.synced_ledger_info
+            .as_ref()
+            .map(|li| (li.ledger_info().version() + max_version_lag) > known_version)
+            .unwrap_or(false)
+    }
+
     /// Returns the version of the synced ledger info (if one exists)
     pub fn get_synced_ledger_info_version(&self) -> Option<u64> {
         self.synced_ledger_info
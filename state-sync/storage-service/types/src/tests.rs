@@ -5,7 +5,9 @@ use crate::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
+        StateValuesWithProofRequest, SubscribeTransactionOutputsWithProofRequest,
+        SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
+        SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     responses::{CompleteDataRange, DataSummary, ProtocolMetadata},
@@ -134,6 +136,54 @@ fn test_data_summary_service_optimistic_fetch() {
     }
 }
 
+#[test]
+fn test_data_summary_service_subscription() {
+    // Create a data client config with the specified max subscription lag
+    let max_subscription_version_lag = 1000;
+    let data_client_config = AptosDataClientConfig {
+        max_subscription_version_lag,
+        ..Default::default()
+    };
+
+    // Create a data summary with the specified synced ledger info version
+    let highest_synced_version = 50_000;
+    let data_summary = DataSummary {
+        synced_ledger_info: Some(create_ledger_info_at_version(highest_synced_version)),
+        ..Default::default()
+    };
+
+    // Verify the different requests that can be serviced
+    for compression in [true, false] {
+        // Test the known versions that are within the subscription lag
+        let known_versions = vec![
+            highest_synced_version,
+            highest_synced_version + (max_subscription_version_lag / 2),
+            highest_synced_version + max_subscription_version_lag - 1,
+        ];
+        verify_can_service_subscription_requests(
+            &data_client_config,
+            &data_summary,
+            compression,
+            known_versions,
+            true,
+        );
+
+        // Test the known versions that are outside the subscription lag
+        let known_versions = vec![
+            highest_synced_version + max_subscription_version_lag,
+            highest_synced_version + max_subscription_version_lag + 1,
+            highest_synced_version + (max_subscription_version_lag * 2),
+        ];
+        verify_can_service_subscription_requests(
+            &data_client_config,
+            &data_summary,
+            compression,
+            known_versions,
+            false,
+        );
+    }
+}
+
 #[test]
 fn test_data_summary_service_transactions() {
     // Create a data client config and data summary
@@ -482,6 +532,46 @@ fn create_optimistic_fetch_request(
     StorageServiceRequest::new(data_request, use_compression)
 }
 
+/// Creates a new subscription request
+fn create_subscription_request(known_version: u64, use_compression: bool) -> StorageServiceRequest {
+    // Create the subscription stream metadata
+    let subscription_stream_metadata = SubscriptionStreamMetadata {
+        known_version_at_stream_start: known_version,
+        known_epoch_at_stream_start: 1,
+        subscription_stream_id: thread_rng().gen(),
+    };
+
+    // Generate a random number
+    let random_number: u64 = thread_rng().gen();
+    let subscription_stream_index = random_number % 10;
+
+    // Determine the data request type based on the random number
+    let data_request = if random_number % 3 == 0 {
+        DataRequest::SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest {
+            subscription_stream_metadata,
+            subscription_stream_index,
+            include_events: false,
+        })
+    } else if random_number % 3 == 1 {
+        DataRequest::SubscribeTransactionOutputsWithProof(
+            SubscribeTransactionOutputsWithProofRequest {
+                subscription_stream_metadata,
+                subscription_stream_index,
+            },
+        )
+    } else {
+        DataRequest::SubscribeTransactionsOrOutputsWithProof(
+            SubscribeTransactionsOrOutputsWithProofRequest {
+                subscription_stream_metadata,
+                subscription_stream_index,
+                include_events: false,
+                max_num_output_reductions: 0,
+            },
+        )
+    };
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 /// Creates a request for transaction outputs
 fn create_outputs_request(
     proof_version: Version,
@@ -593,6 +683,25 @@ fn verify_can_service_optimistic_fetch_requests(
     }
 }
 
+/// Verifies the serviceability of the subscription versions against
+/// the specified data summary. If `expect_service` is true, then the
+/// request should be serviceable.
+fn verify_can_service_subscription_requests(
+    data_client_config: &AptosDataClientConfig,
+    data_summary: &DataSummary,
+    compression: bool,
+    known_versions: Vec<Version>,
+    expect_service: bool,
+) {
+    for known_version in known_versions {
+        // Create the subscription request
+        let request = create_subscription_request(known_version, compression);
+
+        // Verify the serviceability of the request
+        verify_serviceability(data_client_config, data_summary, request, expect_service);
+    }
+}
+
 /// Verifies the serviceability of the state chunk request versions
 /// against the specified data summary. If `expect_service` is true,
 /// then the request should be serviceable.
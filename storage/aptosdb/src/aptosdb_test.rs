@@ -5,7 +5,9 @@ use crate::{
     get_first_seq_num_and_limit,
     pruner::{LedgerPrunerManager, StateMerklePrunerManager},
     test_helper,
-    test_helper::{arb_blocks_to_commit, put_as_state_root, put_transaction_info},
+    test_helper::{
+        arb_blocks_to_commit, put_as_state_root, put_transaction_info, update_in_memory_state,
+    },
     AptosDB, PrunerManager, StaleNodeIndexSchema,
 };
 use aptos_config::config::{
@@ -42,6 +44,57 @@ proptest! {
     }
 }
 
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(5))]
+
+    #[test]
+    fn test_create_online_checkpoint(input in arb_blocks_to_commit()) {
+        // Create a DB and commit the blocks
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let mut in_memory_state = db
+            .state_store
+            .buffered_state()
+            .lock()
+            .current_state()
+            .clone();
+        let mut cur_ver: Version = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+            update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+            db.save_transactions(
+                txns_to_commit,
+                cur_ver,                /* first_version */
+                cur_ver.checked_sub(1), /* base_state_version */
+                Some(ledger_info_with_sigs),
+                false, /* sync_commit */
+                in_memory_state.clone(),
+            )
+            .unwrap();
+            cur_ver += txns_to_commit.len() as u64;
+        }
+
+        // Create an online checkpoint and verify the committed version
+        let checkpoint_dir = TempPath::new();
+        let committed_version = db.create_online_checkpoint(checkpoint_dir.path()).unwrap();
+        prop_assert_eq!(committed_version, cur_ver - 1);
+
+        // Verify a checkpoint can't be created at an existing path
+        prop_assert!(db.create_online_checkpoint(checkpoint_dir.path()).is_err());
+
+        // Open the checkpoint and verify the latest ledger info and state
+        let checkpoint_db = AptosDB::new_for_test(checkpoint_dir.path());
+        prop_assert_eq!(checkpoint_db.get_latest_version().unwrap(), committed_version);
+        prop_assert_eq!(
+            checkpoint_db.get_latest_ledger_info().unwrap(),
+            input.last().unwrap().1.clone()
+        );
+        prop_assert_eq!(
+            checkpoint_db.get_latest_executed_trees().unwrap().state_id(),
+            db.get_latest_executed_trees().unwrap().state_id()
+        );
+    }
+}
+
 #[test]
 fn test_get_first_seq_num_and_limit() {
     assert!(get_first_seq_num_and_limit(Order::Ascending, 0, 0).is_err());
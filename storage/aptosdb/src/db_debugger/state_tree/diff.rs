@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{db_debugger::common::DbDir, AptosDB};
+use anyhow::Result;
+use aptos_config::config::{
+    RocksdbConfigs, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_storage_interface::DbReader;
+use aptos_types::transaction::Version;
+use clap::Parser;
+use owo_colors::OwoColorize;
+
+#[derive(Parser)]
+#[clap(about = "Print the state keys added, removed or modified between two state snapshots.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long)]
+    old_version: Version,
+
+    #[clap(long)]
+    new_version: Version,
+
+    #[clap(long)]
+    split_ledger_db: bool,
+
+    /// Print the old and new values of each key as well.
+    #[clap(long)]
+    print_values: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        println!(
+            "{}",
+            format!(
+                "* Diffing state snapshots at version {} and version {}. \n",
+                self.old_version, self.new_version,
+            )
+            .yellow()
+        );
+
+        let rocksdb_config = RocksdbConfigs {
+            split_ledger_db: self.split_ledger_db,
+            ..Default::default()
+        };
+        let db = AptosDB::open(
+            &self.db_dir,
+            /*readonly=*/ true,
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            rocksdb_config,
+            /*enable_indexer=*/ false,
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+
+        let diff = db.get_state_value_diff(self.old_version, self.new_version)?;
+        let (mut num_added, mut num_removed, mut num_modified) = (0, 0, 0);
+        for (state_key, old_value, new_value) in &diff {
+            let change = match (old_value, new_value) {
+                (None, Some(_)) => {
+                    num_added += 1;
+                    "added".green().to_string()
+                },
+                (Some(_), None) => {
+                    num_removed += 1;
+                    "removed".red().to_string()
+                },
+                _ => {
+                    num_modified += 1;
+                    "modified".yellow().to_string()
+                },
+            };
+            println!("{:>8} {:?}", change, state_key);
+            if self.print_values {
+                println!("    old value: {:?}", old_value);
+                println!("    new value: {:?}", new_value);
+            }
+        }
+        println!(
+            "\n{} keys changed: {} added, {} removed, {} modified.",
+            diff.len(),
+            num_added,
+            num_removed,
+            num_modified,
+        );
+
+        Ok(())
+    }
+}
@@ -317,15 +317,18 @@ impl EventStore {
             .iter()
             .enumerate()
             .try_for_each::<_, Result<_>>(|(idx, event)| {
-                if !skip_index {
-                    batch.put::<EventByKeySchema>(
-                        &(*event.key(), event.sequence_number()),
-                        &(version, idx as u64),
-                    )?;
-                    batch.put::<EventByVersionSchema>(
-                        &(*event.key(), version, event.sequence_number()),
-                        &(idx as u64),
-                    )?;
+                if let ContractEvent::V0(v0) = event {
+                    // Module events don't have an event key, and are not indexed by key
+                    if !skip_index {
+                        batch.put::<EventByKeySchema>(
+                            &(*v0.key(), v0.sequence_number()),
+                            &(version, idx as u64),
+                        )?;
+                        batch.put::<EventByVersionSchema>(
+                            &(*v0.key(), version, v0.sequence_number()),
+                            &(idx as u64),
+                        )?;
+                    }
                 }
                 batch.put::<EventSchema>(&(version, idx as u64), event)
             })?;
@@ -470,12 +473,14 @@ impl EventStore {
         let mut current_version = start;
         for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
             for (current_index, event) in (events?).into_iter().enumerate() {
-                db_batch.delete::<EventByVersionSchema>(&(
-                    *event.key(),
-                    current_version,
-                    event.sequence_number(),
-                ))?;
-                db_batch.delete::<EventByKeySchema>(&(*event.key(), event.sequence_number()))?;
+                if let ContractEvent::V0(v0) = &event {
+                    db_batch.delete::<EventByVersionSchema>(&(
+                        *v0.key(),
+                        current_version,
+                        v0.sequence_number(),
+                    ))?;
+                    db_batch.delete::<EventByKeySchema>(&(*v0.key(), v0.sequence_number()))?;
+                }
                 db_batch.delete::<EventSchema>(&(current_version, current_index as u64))?;
             }
             current_version += 1;
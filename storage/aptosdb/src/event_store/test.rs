@@ -191,9 +191,11 @@ fn test_index_get_impl(event_batches: Vec<Vec<ContractEvent>>) {
         .enumerate()
         .for_each(|(ver, batch)| {
             batch.into_iter().for_each(|e| {
-                let mut events_and_versions =
-                    events_by_event_key.entry(*e.key()).or_insert_with(Vec::new);
-                assert_eq!(events_and_versions.len() as u64, e.sequence_number());
+                let v0 = e.v0().unwrap();
+                let mut events_and_versions = events_by_event_key
+                    .entry(*v0.key())
+                    .or_insert_with(Vec::new);
+                assert_eq!(events_and_versions.len() as u64, v0.sequence_number());
                 events_and_versions.push((e, ver as Version));
             })
         });
@@ -142,6 +142,17 @@ impl LedgerDb {
             ..Default::default()
         };
         let ledger_db = Self::new(db_root_path, rocksdb_configs, /*readonly=*/ false)?;
+        std::fs::remove_dir_all(cp_root_path.as_ref().join(LEDGER_DB_FOLDER_NAME)).unwrap_or(());
+        ledger_db.create_checkpoint_from_open_db(cp_root_path, split_ledger_db)
+    }
+
+    /// Creates a checkpoint of the (already opened) ledger db at `cp_root_path`.
+    /// Note: existing files at the destination are never removed.
+    pub(crate) fn create_checkpoint_from_open_db(
+        &self,
+        cp_root_path: impl AsRef<Path>,
+        split_ledger_db: bool,
+    ) -> Result<()> {
         let cp_ledger_db_folder = cp_root_path.as_ref().join(LEDGER_DB_FOLDER_NAME);
 
         info!(
@@ -149,33 +160,26 @@ impl LedgerDb {
             "Creating ledger_db checkpoint at: {cp_ledger_db_folder:?}"
         );
 
-        std::fs::remove_dir_all(&cp_ledger_db_folder).unwrap_or(());
         if split_ledger_db {
-            std::fs::create_dir_all(&cp_ledger_db_folder).unwrap_or(());
+            std::fs::create_dir_all(&cp_ledger_db_folder)?;
         }
 
-        ledger_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(
                 cp_root_path.as_ref(),
                 split_ledger_db,
             ))?;
 
         if split_ledger_db {
-            ledger_db
-                .event_db()
+            self.event_db()
                 .create_checkpoint(cp_ledger_db_folder.join(EVENT_DB_NAME))?;
-            ledger_db
-                .transaction_accumulator_db()
+            self.transaction_accumulator_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_ACCUMULATOR_DB_NAME))?;
-            ledger_db
-                .transaction_db()
+            self.transaction_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_DB_NAME))?;
-            ledger_db
-                .transaction_info_db()
+            self.transaction_info_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_INFO_DB_NAME))?;
-            ledger_db
-                .write_set_db()
+            self.write_set_db()
                 .create_checkpoint(cp_ledger_db_folder.join(WRITE_SET_DB_NAME))?;
         }
 
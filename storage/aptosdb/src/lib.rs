@@ -88,8 +88,8 @@ use aptos_types::{
     event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     proof::{
-        accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleProofExt,
-        TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
+        accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleMultiProof,
+        SparseMerkleProofExt, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
         TransactionInfoListWithProof,
     },
     state_proof::StateProof,
@@ -120,7 +120,10 @@ use std::{
     fmt::{Debug, Formatter},
     iter::Iterator,
     path::Path,
-    sync::{mpsc, Arc},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, MutexGuard, TryLockError,
+    },
     thread,
     thread::JoinHandle,
     time::{Duration, Instant},
@@ -280,6 +283,7 @@ pub struct AptosDB {
     ledger_pruner: LedgerPrunerManager,
     _rocksdb_property_reporter: RocksdbPropertyReporter,
     ledger_commit_lock: std::sync::Mutex<()>,
+    num_online_checkpoints_in_progress: AtomicUsize,
     indexer: Option<Indexer>,
     skip_index_and_usage: bool,
 }
@@ -339,6 +343,7 @@ impl AptosDB {
                 Arc::clone(&state_merkle_db),
             ),
             ledger_commit_lock: std::sync::Mutex::new(()),
+            num_online_checkpoints_in_progress: AtomicUsize::new(0),
             indexer: None,
             skip_index_and_usage,
         }
@@ -716,7 +721,103 @@ impl AptosDB {
         Ok(())
     }
 
+    /// Creates a new physical DB checkpoint (of the ledger, state kv and state merkle dbs)
+    /// in the directory specified by `cp_path`, while the DB remains open and in use.
+    /// The directory must either not exist or be empty. Returns the committed version
+    /// the checkpoint can be opened at.
+    ///
+    /// To keep the checkpoint consistent (and openable), the pruners are paused, the
+    /// ledger commit lock is held (so that in-flight commits complete and new commits
+    /// wait), and the buffered state is flushed to the state merkle db.
+    pub fn create_online_checkpoint(&self, cp_path: impl AsRef<Path>) -> Result<Version> {
+        gauged_api("create_online_checkpoint", || {
+            // Let committers know the commit lock may be held by a checkpoint
+            self.num_online_checkpoints_in_progress
+                .fetch_add(1, Ordering::SeqCst);
+            let result = self.create_online_checkpoint_impl(cp_path.as_ref());
+            self.num_online_checkpoints_in_progress
+                .fetch_sub(1, Ordering::SeqCst);
+            result
+        })
+    }
+
+    fn create_online_checkpoint_impl(&self, cp_path: &Path) -> Result<Version> {
+        let start = Instant::now();
+        if cp_path.exists() {
+            // Existing destinations are never cleared (to avoid deleting unrelated data)
+            ensure!(
+                cp_path.is_dir() && std::fs::read_dir(cp_path)?.next().is_none(),
+                "The checkpoint path already exists and is not an empty directory: {:?}",
+                cp_path
+            );
+        }
+
+        // Pause the pruners (so that no data is deleted while the checkpoint is created)
+        let _pruner_pause_guards = [
+            self.ledger_pruner.pause_pruner(),
+            self.state_store.state_kv_pruner.pause_pruner(),
+            self.state_store.state_merkle_pruner.pause_pruner(),
+            self.state_store.epoch_snapshot_pruner.pause_pruner(),
+        ];
+
+        // Wait for any in-flight commit, block new commits and flush the buffered
+        // state to the state merkle db
+        let _commit_lock = self
+            .ledger_commit_lock
+            .lock()
+            .expect("Failed to acquire the ledger commit lock.");
+        let mut buffered_state = self.state_store.buffered_state().lock();
+        buffered_state.sync_commit();
+
+        // Fetch the committed version (the checkpoint will be truncated to this on open)
+        let committed_version = self
+            .ledger_db
+            .metadata_db()
+            .get::<DbMetadataSchema>(&DbMetadataKey::OverallCommitProgress)?
+            .ok_or_else(|| AptosDbError::NotFound("Overall commit progress".to_string()))?
+            .expect_version();
+
+        // Create the checkpoints for each DB
+        std::fs::create_dir_all(cp_path)?;
+        let split_ledger_db = self.state_kv_db.enabled_sharding();
+        self.ledger_db
+            .create_checkpoint_from_open_db(cp_path, split_ledger_db)?;
+        if split_ledger_db {
+            self.state_kv_db.create_checkpoint_from_open_db(cp_path)?;
+        }
+        self.state_merkle_db
+            .create_checkpoint_from_open_db(cp_path)?;
+        drop(buffered_state);
+
+        info!(
+            cp_path = cp_path,
+            committed_version = committed_version,
+            time_ms = %start.elapsed().as_millis(),
+            "Made online AptosDB checkpoint."
+        );
+        Ok(committed_version)
+    }
+
     // ================================== Private APIs ==================================
+    /// Acquires the ledger commit lock for a commit. Commits are never expected to run
+    /// concurrently, but they may have to wait for an online checkpoint to complete.
+    fn lock_ledger_commit(&self) -> MutexGuard<'_, ()> {
+        match self.ledger_commit_lock.try_lock() {
+            Ok(lock) => lock,
+            Err(TryLockError::WouldBlock)
+                if self
+                    .num_online_checkpoints_in_progress
+                    .load(Ordering::SeqCst)
+                    > 0 =>
+            {
+                self.ledger_commit_lock
+                    .lock()
+                    .expect("Failed to acquire the ledger commit lock.")
+            },
+            Err(_) => panic!("Concurrent committing detected."),
+        }
+    }
+
     fn get_events_by_event_key(
         &self,
         event_key: &EventKey,
@@ -767,11 +868,12 @@ impl AptosDB {
             .into_iter()
             .map(|(seq, ver, idx)| {
                 let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+                let v0 = event.v0()?;
                 ensure!(
-                    seq == event.sequence_number(),
+                    seq == v0.sequence_number(),
                     "Index broken, expected seq:{}, actual:{}",
                     seq,
-                    event.sequence_number()
+                    v0.sequence_number()
                 );
                 Ok(EventWithVersion::new(ver, event))
             })
@@ -1748,6 +1850,34 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_values_with_multi_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        gauged_api("get_state_values_with_multi_proof_by_version", || {
+            self.error_if_state_merkle_pruned("State merkle", version)?;
+
+            self.state_store
+                .get_state_values_with_multi_proof_by_version(state_keys, version)
+        })
+    }
+
+    fn get_state_value_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+    ) -> Result<Vec<(StateKey, Option<StateValue>, Option<StateValue>)>> {
+        gauged_api("get_state_value_diff", || {
+            let min_version = std::cmp::min(old_version, new_version);
+            self.error_if_state_merkle_pruned("State merkle", min_version)?;
+            self.error_if_state_kv_pruned("StateValue", min_version)?;
+
+            self.state_store
+                .get_state_value_diff(old_version, new_version)
+        })
+    }
+
     fn get_latest_epoch_state(&self) -> Result<EpochState> {
         gauged_api("get_latest_epoch_state", || {
             let latest_ledger_info = self.ledger_store.get_latest_ledger_info()?;
@@ -2010,10 +2140,7 @@ impl DbWriter for AptosDB {
             // Executing and committing from more than one threads not allowed -- consensus and
             // state sync must hand over to each other after all pending execution and committing
             // complete.
-            let _lock = self
-                .ledger_commit_lock
-                .try_lock()
-                .expect("Concurrent committing detected.");
+            let _lock = self.lock_ledger_commit();
 
             self.save_transactions_validation(
                 txns_to_commit,
@@ -2067,10 +2194,7 @@ impl DbWriter for AptosDB {
             // Executing and committing from more than one threads not allowed -- consensus and
             // state sync must hand over to each other after all pending execution and committing
             // complete.
-            let _lock = self
-                .ledger_commit_lock
-                .try_lock()
-                .expect("Concurrent committing detected.");
+            let _lock = self.lock_ledger_commit();
 
             // For reconfig suffix.
             if ledger_info_with_sigs.is_none() && txns_to_commit.is_empty() {
@@ -135,6 +135,7 @@ fn verify_event_by_key_not_in_store(
     event_store: &Arc<EventStore>,
 ) {
     for event in &events[version as usize] {
+        let event = event.v0().unwrap();
         assert!(event_store
             .get_txn_ver_by_seq_num(event.key(), event.sequence_number())
             .is_err())
@@ -147,6 +148,7 @@ fn verify_event_by_key_in_store(
     event_store: &Arc<EventStore>,
 ) {
     for event in events.get(version as usize).unwrap() {
+        let event = event.v0().unwrap();
         assert_eq!(
             event_store
                 .get_txn_ver_by_seq_num(event.key(), event.sequence_number())
@@ -162,6 +164,7 @@ fn verify_event_by_version_not_in_store(
     event_store: &Arc<EventStore>,
 ) {
     for event in events.get(version as usize).unwrap() {
+        let event = event.v0().unwrap();
         assert!(event_store
             .get_latest_sequence_number(version, event.key())
             .unwrap()
@@ -175,6 +178,7 @@ fn verify_event_by_version_in_store(
     event_store: &Arc<EventStore>,
 ) {
     for event in events.get(version as usize).unwrap() {
+        let event = event.v0().unwrap();
         assert!(event_store
             .get_latest_sequence_number(version, event.key())
             .unwrap()
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::pruner::db_pruner::DBPruner;
+use aptos_infallible::MutexGuard;
 use aptos_types::transaction::Version;
 
 /// This module provides `Pruner` which manages a thread pruning old data in the background and is
@@ -35,6 +36,10 @@ pub trait PrunerManager: Sync {
 
     fn is_pruning_pending(&self) -> bool;
 
+    /// Pauses the pruner until the returned guard is dropped (e.g., while a DB
+    /// checkpoint is being created). Returns `None` if the pruner is not enabled.
+    fn pause_pruner(&self) -> Option<MutexGuard<'_, ()>>;
+
     /// (For tests only.) Notifies the worker thread and waits for it to finish its job by polling
     /// an internal counter.
     #[cfg(test)]
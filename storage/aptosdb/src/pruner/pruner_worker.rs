@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::pruner::db_pruner::DBPruner;
+use aptos_infallible::{Mutex, MutexGuard};
 use aptos_logger::{
     error,
     prelude::{sample, SampleRate},
@@ -37,6 +38,8 @@ pub struct PrunerWorkerInner {
     /// Indicates whether the pruning loop should be running. Will only be set to true on pruner
     /// destruction.
     quit_worker: AtomicBool,
+    /// Held by the worker while pruning each batch. Holding it elsewhere pauses the pruner.
+    pruning_lock: Mutex<()>,
 }
 
 impl PrunerWorkerInner {
@@ -46,13 +49,17 @@ impl PrunerWorkerInner {
             pruner,
             batch_size,
             quit_worker: AtomicBool::new(false),
+            pruning_lock: Mutex::new(()),
         })
     }
 
     // Loop that does the real pruning job.
     fn work(&self) {
         while !self.quit_worker.load(Ordering::SeqCst) {
-            let pruner_result = self.pruner.prune(self.batch_size);
+            let pruner_result = {
+                let _pruning_lock = self.pruning_lock.lock();
+                self.pruner.prune(self.batch_size)
+            };
             if pruner_result.is_err() {
                 sample!(
                     SampleRate::Duration(Duration::from_secs(1)),
@@ -99,6 +106,12 @@ impl PrunerWorker {
     pub fn is_pruning_pending(&self) -> bool {
         self.inner.pruner.is_pruning_pending()
     }
+
+    /// Pauses the pruner until the returned guard is dropped. This blocks until
+    /// the batch currently being pruned (if any) is complete.
+    pub fn pause(&self) -> MutexGuard<'_, ()> {
+        self.inner.pruning_lock.lock()
+    }
 }
 
 impl Drop for PrunerWorker {
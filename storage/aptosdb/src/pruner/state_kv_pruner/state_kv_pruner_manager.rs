@@ -11,6 +11,7 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_config::config::LedgerPrunerConfig;
+use aptos_infallible::MutexGuard;
 use aptos_types::transaction::{AtomicVersion, Version};
 use std::sync::{atomic::Ordering, Arc};
 
@@ -71,6 +72,10 @@ impl PrunerManager for StateKvPrunerManager {
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn pause_pruner(&self) -> Option<MutexGuard<'_, ()>> {
+        self.pruner_worker.as_ref().map(|w| w.pause())
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
@@ -16,6 +16,7 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_config::config::StateMerklePrunerConfig;
+use aptos_infallible::MutexGuard;
 use aptos_jellyfish_merkle::StaleNodeIndex;
 use aptos_schemadb::schema::KeyCodec;
 use aptos_types::transaction::{AtomicVersion, Version};
@@ -89,6 +90,10 @@ where
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn pause_pruner(&self) -> Option<MutexGuard<'_, ()>> {
+        self.pruner_worker.as_ref().map(|w| w.pause())
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
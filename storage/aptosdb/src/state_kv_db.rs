@@ -140,20 +140,27 @@ impl StateKvDb {
         cp_root_path: impl AsRef<Path>,
     ) -> Result<()> {
         let state_kv_db = Self::open(db_root_path, RocksdbConfig::default(), false)?;
+        std::fs::remove_dir_all(cp_root_path.as_ref().join(STATE_KV_DB_FOLDER_NAME)).unwrap_or(());
+        state_kv_db.create_checkpoint_from_open_db(cp_root_path)
+    }
+
+    /// Creates a checkpoint of the (already opened) state kv db at `cp_root_path`.
+    /// Note: existing files at the destination are never removed.
+    pub(crate) fn create_checkpoint_from_open_db(
+        &self,
+        cp_root_path: impl AsRef<Path>,
+    ) -> Result<()> {
         let cp_state_kv_db_path = cp_root_path.as_ref().join(STATE_KV_DB_FOLDER_NAME);
 
         info!("Creating state_kv_db checkpoint at: {cp_state_kv_db_path:?}");
 
-        std::fs::remove_dir_all(&cp_state_kv_db_path).unwrap_or(());
-        std::fs::create_dir_all(&cp_state_kv_db_path).unwrap_or(());
+        std::fs::create_dir_all(&cp_state_kv_db_path)?;
 
-        state_kv_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref()))?;
 
         for shard_id in 0..NUM_STATE_SHARDS {
-            state_kv_db
-                .db_shard(shard_id as u8)
+            self.db_shard(shard_id as u8)
                 .create_checkpoint(Self::db_shard_path(cp_root_path.as_ref(), shard_id as u8))?;
         }
 
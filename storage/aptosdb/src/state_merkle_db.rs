@@ -27,7 +27,7 @@ use aptos_schemadb::{SchemaBatch, DB};
 use aptos_scratchpad::get_state_shard_id;
 use aptos_types::{
     nibble::{nibble_path::NibblePath, ROOT_NIBBLE_HEIGHT},
-    proof::{SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{SparseMerkleMultiProof, SparseMerkleProofExt, SparseMerkleRangeProof},
     state_store::state_key::StateKey,
     transaction::Version,
 };
@@ -154,23 +154,32 @@ impl StateMerkleDb {
             /*readonly=*/ false,
             /*max_nodes_per_lru_cache_shard=*/ 0,
         )?;
+        std::fs::remove_dir_all(cp_root_path.as_ref().join(STATE_MERKLE_DB_FOLDER_NAME))
+            .unwrap_or(());
+        state_merkle_db.create_checkpoint_from_open_db(cp_root_path)
+    }
+
+    /// Creates a checkpoint of the (already opened) state merkle db at `cp_root_path`.
+    /// Note: existing files at the destination are never removed.
+    pub(crate) fn create_checkpoint_from_open_db(
+        &self,
+        cp_root_path: impl AsRef<Path>,
+    ) -> Result<()> {
+        let sharding = self.enable_sharding;
         let cp_state_merkle_db_path = cp_root_path.as_ref().join(STATE_MERKLE_DB_FOLDER_NAME);
 
         info!("Creating state_merkle_db checkpoint at: {cp_state_merkle_db_path:?}");
 
-        std::fs::remove_dir_all(&cp_state_merkle_db_path).unwrap_or(());
         if sharding {
-            std::fs::create_dir_all(&cp_state_merkle_db_path).unwrap_or(());
+            std::fs::create_dir_all(&cp_state_merkle_db_path)?;
         }
 
-        state_merkle_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref(), sharding))?;
 
         if sharding {
             for shard_id in 0..NUM_STATE_SHARDS {
-                state_merkle_db
-                    .db_shard(shard_id as u8)
+                self.db_shard(shard_id as u8)
                     .create_checkpoint(Self::db_shard_path(
                         cp_root_path.as_ref(),
                         shard_id as u8,
@@ -231,6 +240,25 @@ impl StateMerkleDb {
         JellyfishMerkleTree::new(self).get_with_proof_ext(state_key.hash(), version)
     }
 
+    pub fn get_with_multi_proof(
+        &self,
+        state_key_hashes: &[HashValue],
+        version: Version,
+    ) -> Result<(
+        Vec<Option<(HashValue, (StateKey, Version))>>,
+        SparseMerkleMultiProof,
+    )> {
+        JellyfishMerkleTree::new(self).get_with_multi_proof(state_key_hashes, version)
+    }
+
+    pub fn get_leaf_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+    ) -> Result<Vec<(Option<LeafNode>, Option<LeafNode>)>> {
+        JellyfishMerkleTree::new(self).get_leaf_diff(old_version, new_version)
+    }
+
     pub fn get_range_proof(
         &self,
         rightmost_key: HashValue,
@@ -44,7 +44,9 @@ use aptos_storage_interface::{
     DbReader, StateSnapshotReceiver,
 };
 use aptos_types::{
-    proof::{definition::LeafCount, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        definition::LeafCount, SparseMerkleMultiProof, SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{
         create_empty_sharded_state_updates,
         state_key::StateKey,
@@ -194,6 +196,68 @@ impl DbReader for StateDb {
         ))
     }
 
+    /// Get the state values of a list of state keys with a multi-proof given the version
+    fn get_state_values_with_multi_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        let mut keys_by_hash: Vec<_> = state_keys.iter().map(|key| (key.hash(), key)).collect();
+        keys_by_hash.sort_by_key(|(hash, _)| *hash);
+        keys_by_hash.dedup_by_key(|(hash, _)| *hash);
+        let hashes: Vec<_> = keys_by_hash.iter().map(|(hash, _)| *hash).collect();
+
+        let (leaf_data, proof) = self
+            .state_merkle_db
+            .get_with_multi_proof(&hashes, version)?;
+        let values = keys_by_hash
+            .into_iter()
+            .zip(leaf_data)
+            .map(|((_, state_key), leaf_data)| {
+                let value = match leaf_data {
+                    Some((_, (key, version))) => Some(self.expect_value_by_version(&key, version)?),
+                    None => None,
+                };
+                Ok((state_key.clone(), value))
+            })
+            .collect::<Result<_>>()?;
+        Ok((values, proof))
+    }
+
+    /// Get the state keys changed between two state snapshots with their old and new values
+    fn get_state_value_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+    ) -> Result<Vec<(StateKey, Option<StateValue>, Option<StateValue>)>> {
+        self.state_merkle_db
+            .get_leaf_diff(old_version, new_version)?
+            .into_iter()
+            .map(|(old_leaf, new_leaf)| {
+                let state_key = old_leaf
+                    .as_ref()
+                    .or(new_leaf.as_ref())
+                    .expect("Diff entry must have at least one leaf.")
+                    .value_index()
+                    .0
+                    .clone();
+                let old_value = old_leaf
+                    .map(|leaf| {
+                        let (key, version) = leaf.value_index();
+                        self.expect_value_by_version(key, *version)
+                    })
+                    .transpose()?;
+                let new_value = new_leaf
+                    .map(|leaf| {
+                        let (key, version) = leaf.value_index();
+                        self.expect_value_by_version(key, *version)
+                    })
+                    .transpose()?;
+                Ok((state_key, old_value, new_value))
+            })
+            .collect()
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         if self.skip_usage {
             return Ok(StateStorageUsage::new_untracked());
@@ -278,6 +342,25 @@ impl DbReader for StateStore {
         self.deref()
             .get_state_value_with_proof_by_version_ext(state_key, version)
     }
+
+    /// Get the state values of a list of state keys with a multi-proof given the version
+    fn get_state_values_with_multi_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        self.deref()
+            .get_state_values_with_multi_proof_by_version(state_keys, version)
+    }
+
+    /// Get the state keys changed between two state snapshots with their old and new values
+    fn get_state_value_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+    ) -> Result<Vec<(StateKey, Option<StateValue>, Option<StateValue>)>> {
+        self.deref().get_state_value_diff(old_version, new_version)
+    }
 }
 
 impl StateDb {
@@ -163,6 +163,108 @@ fn test_state_store_reader_writer() {
     verify_value_and_proof(store, key3, Some(&value3), 1, root);
 }
 
+#[test]
+fn test_state_store_multi_proof() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let keys: Vec<_> = (0..10)
+        .map(|i| StateKey::raw(format!("test_key{}", i).into_bytes()))
+        .collect();
+    let values: Vec<_> = (0..10)
+        .map(|i| StateValue::from(format!("test_val{}", i).into_bytes()))
+        .collect();
+
+    // Only insert the even keys, so the odd ones need non-inclusion proofs.
+    let root = put_value_set(
+        store,
+        keys.iter()
+            .cloned()
+            .zip(values.iter().cloned())
+            .step_by(2)
+            .collect(),
+        0, /* version */
+        None,
+    );
+
+    let mut queried_keys = keys.clone();
+    queried_keys.push(keys[0].clone());
+    let (values_with_keys, proof) = store
+        .get_state_values_with_multi_proof_by_version(&queried_keys, 0)
+        .unwrap();
+    assert_eq!(values_with_keys.len(), keys.len());
+
+    let elements: Vec<_> = values_with_keys
+        .iter()
+        .map(|(key, value)| {
+            let index = keys.iter().position(|k| k == key).unwrap();
+            let expected_value = if index % 2 == 0 {
+                Some(&values[index])
+            } else {
+                None
+            };
+            assert_eq!(value.as_ref(), expected_value);
+            (key.hash(), value.as_ref())
+        })
+        .collect();
+    proof.verify(root, &elements).unwrap();
+}
+
+#[test]
+fn test_state_store_value_diff() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+    let key1 = StateKey::raw(String::from("test_key1").into_bytes());
+    let key2 = StateKey::raw(String::from("test_key2").into_bytes());
+    let key3 = StateKey::raw(String::from("test_key3").into_bytes());
+
+    let value1 = StateValue::from(String::from("test_val1").into_bytes());
+    let value1_update = StateValue::from(String::from("test_val1_update").into_bytes());
+    let value2 = StateValue::from(String::from("test_val2").into_bytes());
+    let value3 = StateValue::from(String::from("test_val3").into_bytes());
+
+    put_value_set(
+        store,
+        vec![
+            (key1.clone(), value1.clone()),
+            (key2.clone(), value2.clone()),
+        ],
+        0, /* version */
+        None,
+    );
+    put_value_set(
+        store,
+        vec![
+            (key1.clone(), value1_update.clone()),
+            (key3.clone(), value3.clone()),
+        ],
+        1, /* version */
+        Some(0),
+    );
+
+    assert!(store.get_state_value_diff(0, 0).unwrap().is_empty());
+    assert!(store.get_state_value_diff(1, 1).unwrap().is_empty());
+
+    let mut expected = vec![
+        (
+            key1.clone(),
+            Some(value1.clone()),
+            Some(value1_update.clone()),
+        ),
+        (key3.clone(), None, Some(value3.clone())),
+    ];
+    expected.sort_by_key(|(key, _, _)| key.hash());
+    assert_eq!(store.get_state_value_diff(0, 1).unwrap(), expected);
+
+    let mut expected_reverse = vec![
+        (key1, Some(value1_update), Some(value1)),
+        (key3, Some(value3), None),
+    ];
+    expected_reverse.sort_by_key(|(key, _, _)| key.hash());
+    assert_eq!(store.get_state_value_diff(1, 0).unwrap(), expected_reverse);
+}
+
 fn traverse_values(
     store: &StateStore,
     prefix: &StateKeyPrefix,
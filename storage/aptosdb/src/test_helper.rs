@@ -523,7 +523,10 @@ fn get_events_by_event_key(
         if num_results == 0 {
             break;
         }
-        assert_eq!(events.first().unwrap().1.sequence_number(), cursor);
+        assert_eq!(
+            events.first().unwrap().1.v0().unwrap().sequence_number(),
+            cursor
+        );
 
         if order == Order::Ascending {
             if cursor + num_results > last_seq_num {
@@ -573,11 +576,15 @@ fn verify_events_by_event_key(
                 .first()
                 .expect("Shouldn't be empty")
                 .1
+                .v0()
+                .unwrap()
                 .sequence_number();
             let last_seq = events
                 .last()
                 .expect("Shouldn't be empty")
                 .1
+                .v0()
+                .unwrap()
                 .sequence_number();
 
             let traversed = get_events_by_event_key(
@@ -616,8 +623,13 @@ fn group_events_by_event_key(
     let mut event_key_to_events: HashMap<EventKey, Vec<(Version, ContractEvent)>> = HashMap::new();
     for (batch_idx, txn) in txns_to_commit.iter().enumerate() {
         for event in txn.events() {
+            let key = match event.event_key() {
+                Some(key) => *key,
+                // Module events are not indexed by key
+                None => continue,
+            };
             event_key_to_events
-                .entry(*event.key())
+                .entry(key)
                 .or_default()
                 .push((first_version + batch_idx as u64, event.clone()));
         }
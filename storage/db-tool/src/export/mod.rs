@@ -0,0 +1,477 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod source;
+mod tables;
+
+use crate::export::{source::ExportSource, tables::PartitionRows};
+use anyhow::{ensure, Result};
+use aptos_backup_cli::{
+    metadata::{cache, cache::MetadataCacheOpt},
+    storage::DBToolStorageOpt,
+    utils::{ConcurrentDownloadsOpt, RocksdbOpt},
+};
+use aptos_config::config::{
+    RocksdbConfigs, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::AptosDB;
+use aptos_logger::info;
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReader};
+use aptos_types::transaction::Version;
+use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::MoveResolverExt};
+use clap::{Parser, Subcommand};
+use parquet::{
+    basic::Compression,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    record::RecordWriter,
+};
+use std::{
+    fs,
+    fs::File,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+// The names of the exported tables (each table is written to its own directory)
+const EVENTS_TABLE: &str = "events";
+const STATE_VALUES_TABLE: &str = "state_values";
+const TRANSACTIONS_TABLE: &str = "transactions";
+const WRITE_SET_CHANGES_TABLE: &str = "write_set_changes";
+
+/// Export transactions, events, write set changes and state values to Parquet files
+#[derive(Subcommand)]
+pub enum Command {
+    #[clap(about = "Export from a local AptosDB.")]
+    FromDb(FromDbOpt),
+    #[clap(about = "Export from the transaction backups in a backup storage.")]
+    FromBackup(FromBackupOpt),
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Command::FromDb(opt) => opt.run().await,
+            Command::FromBackup(opt) => opt.run().await,
+        }
+    }
+}
+
+#[derive(Parser)]
+pub struct ExportOpt {
+    #[clap(
+        long,
+        value_parser,
+        help = "The directory to write the Parquet files to. Each table is written to its own \
+        sub-directory, with one file per partition. Partitions that already exist are skipped, \
+        so an interrupted export can be resumed by rerunning the same command."
+    )]
+    output_dir: PathBuf,
+    #[clap(long, help = "The first version to export. [Defaults to 0]")]
+    start_version: Option<Version>,
+    #[clap(
+        long,
+        help = "The last version to export. [Defaults to the latest version available]"
+    )]
+    end_version: Option<Version>,
+    #[clap(
+        long,
+        default_value_t = 100_000,
+        help = "The number of versions in each partition. Partitions are aligned to multiples of \
+        this value."
+    )]
+    partition_size: u64,
+    #[clap(long, help = "Skip decoding Move values (events and resources).")]
+    skip_decoding: bool,
+}
+
+#[derive(Parser)]
+pub struct FromDbOpt {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(flatten)]
+    export: ExportOpt,
+}
+
+impl FromDbOpt {
+    pub async fn run(self) -> Result<()> {
+        let db = Arc::new(open_db(&self.db_dir, self.rocksdb_opt.into())?);
+        let annotation_db = (!self.export.skip_decoding).then(|| db.clone());
+        Exporter::new(self.export, ExportSource::Db(db), annotation_db)
+            .run()
+            .await
+    }
+}
+
+#[derive(Parser)]
+pub struct FromBackupOpt {
+    #[clap(flatten)]
+    metadata_cache_opt: MetadataCacheOpt,
+    #[clap(flatten)]
+    storage: DBToolStorageOpt,
+    #[clap(flatten)]
+    concurrent_downloads: ConcurrentDownloadsOpt,
+    #[clap(
+        long,
+        value_parser,
+        help = "A local AptosDB used to resolve the Move types when decoding values. If not \
+        specified, values are not decoded."
+    )]
+    annotation_db_dir: Option<PathBuf>,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(flatten)]
+    export: ExportOpt,
+}
+
+impl FromBackupOpt {
+    pub async fn run(self) -> Result<()> {
+        let storage = self.storage.init_storage().await?;
+        let metadata_view = cache::sync_and_load(
+            &self.metadata_cache_opt,
+            storage.clone(),
+            self.concurrent_downloads.get(),
+        )
+        .await?;
+        let transaction_backups = metadata_view.select_transaction_backups(
+            self.export.start_version.unwrap_or(0),
+            self.export.end_version.unwrap_or(Version::MAX),
+        )?;
+
+        let annotation_db = match &self.annotation_db_dir {
+            Some(db_dir) if !self.export.skip_decoding => {
+                Some(Arc::new(open_db(db_dir, self.rocksdb_opt.into())?))
+            },
+            _ => None,
+        };
+        let source = ExportSource::Backup {
+            storage,
+            transaction_backups,
+        };
+        Exporter::new(self.export, source, annotation_db)
+            .run()
+            .await
+    }
+}
+
+/// Exports the transactions of a source, partition by partition
+pub struct Exporter {
+    opt: ExportOpt,
+    source: ExportSource,
+    annotation_db: Option<Arc<AptosDB>>,
+}
+
+impl Exporter {
+    pub fn new(opt: ExportOpt, source: ExportSource, annotation_db: Option<Arc<AptosDB>>) -> Self {
+        Self {
+            opt,
+            source,
+            annotation_db,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        ensure!(
+            self.opt.partition_size > 0,
+            "The partition size must be positive."
+        );
+
+        // Identify the version range to export
+        let latest_version = match self.source.get_latest_version()? {
+            Some(latest_version) => latest_version,
+            None => {
+                info!("Nothing to export. The source is empty.");
+                return Ok(());
+            },
+        };
+        let start_version = self.opt.start_version.unwrap_or(0);
+        let end_version = self.opt.end_version.map_or(latest_version, |end_version| {
+            end_version.min(latest_version)
+        });
+        ensure!(
+            start_version <= end_version,
+            "Invalid version range to export: [{}, {}]",
+            start_version,
+            end_version,
+        );
+
+        // Create the annotator used to decode Move values (using the latest state)
+        let state_view = self
+            .annotation_db
+            .as_ref()
+            .map(|db| (db.clone() as Arc<dyn DbReader>).latest_state_checkpoint_view())
+            .transpose()?;
+        let resolver = state_view
+            .as_ref()
+            .map(|state_view| state_view.as_move_resolver());
+        let annotator = resolver.as_ref().map(AptosValueAnnotator::new);
+
+        // Export each partition
+        let partition_size = self.opt.partition_size;
+        let mut partition_start = start_version;
+        while partition_start <= end_version {
+            let partition_end = (partition_start / partition_size + 1)
+                .saturating_mul(partition_size)
+                .saturating_sub(1)
+                .min(end_version);
+            self.export_partition(partition_start, partition_end, annotator.as_ref())
+                .await?;
+            partition_start = match partition_end.checked_add(1) {
+                Some(next_start) => next_start,
+                None => break,
+            };
+        }
+
+        info!(
+            start_version = start_version,
+            end_version = end_version,
+            "Finished exporting to {:?}.",
+            self.opt.output_dir
+        );
+        Ok(())
+    }
+
+    async fn export_partition<T: MoveResolverExt>(
+        &self,
+        first_version: Version,
+        last_version: Version,
+        annotator: Option<&AptosValueAnnotator<'_, T>>,
+    ) -> Result<()> {
+        // Skip the partition if it was already exported
+        let partition_name = partition_file_name(first_version, last_version);
+        let tables = [
+            TRANSACTIONS_TABLE,
+            EVENTS_TABLE,
+            WRITE_SET_CHANGES_TABLE,
+            STATE_VALUES_TABLE,
+        ];
+        if tables
+            .iter()
+            .all(|table| self.table_dir(table).join(&partition_name).exists())
+        {
+            info!(
+                "Partition [{}, {}] already exported, skipping.",
+                first_version, last_version
+            );
+            return Ok(());
+        }
+
+        // Read the transactions and convert them into table rows
+        let transactions = self
+            .source
+            .get_transactions(first_version, last_version)
+            .await?;
+        let rows = PartitionRows::new(&transactions, annotator);
+
+        // Write each table
+        self.write_table(
+            TRANSACTIONS_TABLE,
+            &partition_name,
+            rows.transactions.as_slice(),
+        )?;
+        self.write_table(EVENTS_TABLE, &partition_name, rows.events.as_slice())?;
+        self.write_table(
+            WRITE_SET_CHANGES_TABLE,
+            &partition_name,
+            rows.write_set_changes.as_slice(),
+        )?;
+        self.write_table(
+            STATE_VALUES_TABLE,
+            &partition_name,
+            rows.state_values.as_slice(),
+        )?;
+
+        info!(
+            "Exported partition [{}, {}] ({} transactions).",
+            first_version,
+            last_version,
+            transactions.len()
+        );
+        Ok(())
+    }
+
+    fn table_dir(&self, table: &str) -> PathBuf {
+        self.opt.output_dir.join(table)
+    }
+
+    /// Writes the rows to the partition file of the given table. The file is
+    /// first written to a temporary path and then renamed, so that partially
+    /// written files are never mistaken for exported partitions.
+    fn write_table<'a, R>(&self, table: &str, partition_name: &str, rows: &'a [R]) -> Result<()>
+    where
+        &'a [R]: RecordWriter<R>,
+    {
+        let table_dir = self.table_dir(table);
+        fs::create_dir_all(&table_dir)?;
+        let partition_path = table_dir.join(partition_name);
+        if partition_path.exists() {
+            return Ok(());
+        }
+
+        // Remove any stale files of the same partition (e.g., a partition that
+        // was previously cut short by the end version).
+        let partition_prefix = partition_name_prefix(partition_name);
+        for entry in fs::read_dir(&table_dir)? {
+            let entry = entry?;
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(partition_prefix)
+            {
+                fs::remove_file(entry.path())?;
+            }
+        }
+
+        let temp_path = table_dir.join(format!("{}.tmp", partition_name));
+        write_parquet_file(&temp_path, rows)?;
+        fs::rename(&temp_path, &partition_path)?;
+        Ok(())
+    }
+}
+
+fn open_db(db_dir: &Path, rocksdb_configs: RocksdbConfigs) -> Result<AptosDB> {
+    AptosDB::open(
+        db_dir,
+        true, /* read_only */
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        rocksdb_configs,
+        false, /* indexer */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+}
+
+/// Returns the file name of the partition. Versions are zero padded so that
+/// the files sort by version.
+fn partition_file_name(first_version: Version, last_version: Version) -> String {
+    format!("{:020}-{:020}.parquet", first_version, last_version)
+}
+
+/// Returns the prefix of the partition file name shared by all
+/// partitions that start at the same version.
+fn partition_name_prefix(partition_name: &str) -> &str {
+    &partition_name[..21]
+}
+
+fn write_parquet_file<'a, R>(path: &Path, rows: &'a [R]) -> Result<()>
+where
+    &'a [R]: RecordWriter<R>,
+{
+    let schema = rows.schema()?;
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+    let mut writer = SerializedFileWriter::new(File::create(path)?, schema, properties)?;
+    if !rows.is_empty() {
+        let mut row_group_writer = writer.next_row_group()?;
+        rows.write_to_row_group(&mut row_group_writer)?;
+        row_group_writer.close()?;
+    }
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_executor_test_helpers::integration_test_impl::test_execution_with_storage_impl;
+    use aptos_temppath::TempPath;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    fn num_rows(path: &Path) -> i64 {
+        let reader = SerializedFileReader::new(File::open(path).unwrap()).unwrap();
+        reader.metadata().file_metadata().num_rows()
+    }
+
+    fn create_export_opt(output_dir: &Path, end_version: Version) -> ExportOpt {
+        ExportOpt {
+            output_dir: output_dir.to_path_buf(),
+            start_version: None,
+            end_version: Some(end_version),
+            partition_size: 10,
+            skip_decoding: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_from_db() {
+        let db = test_execution_with_storage_impl();
+        let latest_version = db.get_latest_version().unwrap();
+        let output_dir = TempPath::new();
+        output_dir.create_as_dir().unwrap();
+
+        // Export a partial range (the last partition is cut short)
+        let end_version = latest_version - 1;
+        Exporter::new(
+            create_export_opt(output_dir.path(), end_version),
+            ExportSource::Db(db.clone()),
+            Some(db.clone()),
+        )
+        .run()
+        .await
+        .unwrap();
+
+        // Export everything (this resumes the export and replaces the partial partition)
+        Exporter::new(
+            create_export_opt(output_dir.path(), latest_version),
+            ExportSource::Db(db.clone()),
+            Some(db.clone()),
+        )
+        .run()
+        .await
+        .unwrap();
+
+        // Verify the transactions table covers all versions exactly once
+        let mut total_transactions = 0;
+        let mut partition_start = 0;
+        while partition_start <= latest_version {
+            let partition_end = (partition_start + 9).min(latest_version);
+            let partition_name = partition_file_name(partition_start, partition_end);
+            for table in [
+                TRANSACTIONS_TABLE,
+                EVENTS_TABLE,
+                WRITE_SET_CHANGES_TABLE,
+                STATE_VALUES_TABLE,
+            ] {
+                let table_dir = output_dir.path().join(table);
+                assert!(table_dir.join(&partition_name).exists());
+                assert_eq!(
+                    fs::read_dir(&table_dir).unwrap().count() as u64,
+                    latest_version / 10 + 1
+                );
+            }
+            total_transactions += num_rows(
+                &output_dir
+                    .path()
+                    .join(TRANSACTIONS_TABLE)
+                    .join(&partition_name),
+            );
+            partition_start += 10;
+        }
+        assert_eq!(total_transactions as u64, latest_version + 1);
+
+        // Verify the genesis partition contains events and write set changes
+        let genesis_partition = partition_file_name(0, 9.min(latest_version));
+        assert!(
+            num_rows(
+                &output_dir
+                    .path()
+                    .join(EVENTS_TABLE)
+                    .join(&genesis_partition)
+            ) > 0
+        );
+        assert!(
+            num_rows(
+                &output_dir
+                    .path()
+                    .join(WRITE_SET_CHANGES_TABLE)
+                    .join(&genesis_partition)
+            ) > 0
+        );
+    }
+}
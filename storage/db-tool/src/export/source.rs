@@ -0,0 +1,157 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_backup_cli::{
+    backup_types::transaction::manifest::TransactionBackup,
+    metadata::TransactionBackupMeta,
+    storage::BackupStorage,
+    utils::{read_record_bytes::ReadRecordBytes, storage_ext::BackupStorageExt},
+};
+use aptos_db::AptosDB;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    contract_event::ContractEvent,
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
+};
+use std::sync::Arc;
+use tokio::io::BufReader;
+
+/// A single committed transaction and all of its outputs
+pub struct TransactionData {
+    pub version: Version,
+    pub transaction: Transaction,
+    pub transaction_info: TransactionInfo,
+    pub events: Vec<ContractEvent>,
+    pub write_set: WriteSet,
+}
+
+/// The place transactions are read from for export
+pub enum ExportSource {
+    /// An AptosDB opened locally
+    Db(Arc<AptosDB>),
+    /// A backup storage, read via the transaction backups in its metadata.
+    /// Note: the backups are read as is, i.e., the transaction accumulator
+    /// proofs are not verified.
+    Backup {
+        storage: Arc<dyn BackupStorage>,
+        transaction_backups: Vec<TransactionBackupMeta>,
+    },
+}
+
+impl ExportSource {
+    /// Returns the latest version that can be exported from the source
+    pub fn get_latest_version(&self) -> Result<Option<Version>> {
+        match self {
+            ExportSource::Db(db) => Ok(db
+                .get_latest_ledger_info_option()?
+                .map(|ledger_info| ledger_info.ledger_info().version())),
+            ExportSource::Backup {
+                transaction_backups,
+                ..
+            } => Ok(transaction_backups
+                .iter()
+                .map(|backup| backup.last_version)
+                .max()),
+        }
+    }
+
+    /// Returns all transactions in the [`first_version`, `last_version`] range
+    /// (right side inclusive), ordered by version.
+    pub async fn get_transactions(
+        &self,
+        first_version: Version,
+        last_version: Version,
+    ) -> Result<Vec<TransactionData>> {
+        let transactions = match self {
+            ExportSource::Db(db) => {
+                Self::get_transactions_from_db(db, first_version, last_version)?
+            },
+            ExportSource::Backup {
+                storage,
+                transaction_backups,
+            } => {
+                Self::get_transactions_from_backup(
+                    storage,
+                    transaction_backups,
+                    first_version,
+                    last_version,
+                )
+                .await?
+            },
+        };
+
+        ensure!(
+            transactions.len() as u64 == last_version - first_version + 1,
+            "Missing transactions in range [{}, {}]. Found {} transactions.",
+            first_version,
+            last_version,
+            transactions.len(),
+        );
+        Ok(transactions)
+    }
+
+    fn get_transactions_from_db(
+        db: &AptosDB,
+        first_version: Version,
+        last_version: Version,
+    ) -> Result<Vec<TransactionData>> {
+        let num_transactions = (last_version - first_version + 1) as usize;
+        db.get_backup_handler()
+            .get_transaction_iter(first_version, num_transactions)?
+            .enumerate()
+            .map(|(idx, result)| {
+                let (transaction, transaction_info, events, write_set) = result?;
+                Ok(TransactionData {
+                    version: first_version + idx as Version,
+                    transaction,
+                    transaction_info,
+                    events,
+                    write_set,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_transactions_from_backup(
+        storage: &Arc<dyn BackupStorage>,
+        transaction_backups: &[TransactionBackupMeta],
+        first_version: Version,
+        last_version: Version,
+    ) -> Result<Vec<TransactionData>> {
+        let mut transactions = vec![];
+        for backup in transaction_backups.iter().filter(|backup| {
+            backup.first_version <= last_version && backup.last_version >= first_version
+        }) {
+            let manifest: TransactionBackup = storage.load_json_file(&backup.manifest).await?;
+            manifest.verify()?;
+
+            for chunk in manifest.chunks.iter().filter(|chunk| {
+                chunk.first_version <= last_version && chunk.last_version >= first_version
+            }) {
+                let mut file = BufReader::new(storage.open_for_read(&chunk.transactions).await?);
+                let mut version = chunk.first_version;
+                while let Some(record_bytes) = file.read_record_bytes().await? {
+                    if version > last_version {
+                        break;
+                    }
+                    if version >= first_version {
+                        let (transaction, transaction_info, events, write_set) =
+                            bcs::from_bytes(&record_bytes)?;
+                        transactions.push(TransactionData {
+                            version,
+                            transaction,
+                            transaction_info,
+                            events,
+                            write_set,
+                        });
+                    }
+                    version += 1;
+                }
+            }
+        }
+
+        Ok(transactions)
+    }
+}
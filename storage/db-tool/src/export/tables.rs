@@ -0,0 +1,275 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::export::source::TransactionData;
+use aptos_crypto::hash::CryptoHash;
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_types::{
+    access_path::Path,
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    event::EventKey,
+    state_store::state_key::{StateKey, StateKeyInner},
+    transaction::{Transaction, TransactionPayload},
+    write_set::WriteOp,
+};
+use aptos_vm::move_vm_ext::MoveResolverExt;
+use parquet_derive::ParquetRecordWriter;
+use std::collections::BTreeMap;
+
+/// A row in the `transactions` table
+#[derive(ParquetRecordWriter)]
+pub struct TransactionRow {
+    pub version: u64,
+    pub transaction_hash: String,
+    pub transaction_type: String,
+    pub sender: Option<String>,
+    pub sequence_number: Option<u64>,
+    pub entry_function: Option<String>,
+    pub gas_used: u64,
+    pub success: bool,
+    pub vm_status: String,
+    pub num_events: u64,
+    pub num_write_set_changes: u64,
+    pub state_change_hash: String,
+    pub event_root_hash: String,
+    pub state_checkpoint_hash: Option<String>,
+}
+
+/// A row in the `events` table
+#[derive(ParquetRecordWriter)]
+pub struct EventRow {
+    pub version: u64,
+    pub event_index: u64,
+    pub account_address: String,
+    pub creation_number: u64,
+    pub sequence_number: u64,
+    pub type_tag: String,
+    pub data: Vec<u8>,
+    pub decoded_data: Option<String>,
+}
+
+/// A row in the `write_set_changes` table
+#[derive(ParquetRecordWriter)]
+pub struct WriteSetChangeRow {
+    pub version: u64,
+    pub change_index: u64,
+    pub state_key_hash: String,
+    pub state_key_type: String,
+    pub address: Option<String>,
+    pub path_type: Option<String>,
+    pub path: Option<String>,
+    pub table_handle: Option<String>,
+    pub table_key: Option<Vec<u8>>,
+    pub write_op: String,
+    pub value: Option<Vec<u8>>,
+    pub decoded_value: Option<String>,
+}
+
+/// A row in the `state_values` table. Each row holds the latest value of a
+/// state key that was written in the exported partition (i.e., the value of
+/// the key as of the last version of the partition).
+#[derive(ParquetRecordWriter)]
+pub struct StateValueRow {
+    pub state_key_hash: String,
+    pub version: u64,
+    pub deleted: bool,
+    pub value: Option<Vec<u8>>,
+    pub decoded_value: Option<String>,
+}
+
+/// All the table rows for a partition of transactions
+#[derive(Default)]
+pub struct PartitionRows {
+    pub transactions: Vec<TransactionRow>,
+    pub events: Vec<EventRow>,
+    pub write_set_changes: Vec<WriteSetChangeRow>,
+    pub state_values: Vec<StateValueRow>,
+}
+
+impl PartitionRows {
+    /// Converts the given transactions into table rows. If an annotator is
+    /// given, Move values are decoded wherever the annotator can resolve them.
+    pub fn new<T: MoveResolverExt>(
+        transactions: &[TransactionData],
+        annotator: Option<&AptosValueAnnotator<T>>,
+    ) -> Self {
+        let mut rows = PartitionRows::default();
+        let mut latest_state_values = BTreeMap::new();
+
+        for data in transactions {
+            rows.transactions.push(create_transaction_row(data));
+
+            for (event_index, event) in data.events.iter().enumerate() {
+                // Module events don't have an event key, which is exported as all zeros
+                let (key, sequence_number) = match event {
+                    ContractEvent::V0(event) => (*event.key(), event.sequence_number()),
+                    ContractEvent::V1(_) => (EventKey::new(0, AccountAddress::ZERO), 0),
+                };
+                rows.events.push(EventRow {
+                    version: data.version,
+                    event_index: event_index as u64,
+                    account_address: key.get_creator_address().to_hex_literal(),
+                    creation_number: key.get_creation_number(),
+                    sequence_number,
+                    type_tag: event.type_tag().to_string(),
+                    data: event.event_data().to_vec(),
+                    decoded_data: annotator.and_then(|annotator| {
+                        annotator
+                            .view_contract_event(event)
+                            .ok()
+                            .map(|value| value.to_string())
+                    }),
+                });
+            }
+
+            for (change_index, (state_key, write_op)) in data.write_set.iter().enumerate() {
+                let state_key_hash = state_key.hash().to_hex_literal();
+                let decoded_value = write_op
+                    .bytes()
+                    .and_then(|bytes| decode_state_value(state_key, bytes, annotator));
+                rows.write_set_changes.push(create_write_set_change_row(
+                    data.version,
+                    change_index as u64,
+                    state_key_hash.clone(),
+                    state_key,
+                    write_op,
+                    decoded_value.clone(),
+                ));
+                latest_state_values.insert(state_key_hash.clone(), StateValueRow {
+                    state_key_hash,
+                    version: data.version,
+                    deleted: write_op.is_deletion(),
+                    value: write_op.bytes().map(|bytes| bytes.to_vec()),
+                    decoded_value,
+                });
+            }
+        }
+
+        rows.state_values = latest_state_values.into_values().collect();
+        rows
+    }
+}
+
+fn create_transaction_row(data: &TransactionData) -> TransactionRow {
+    let (transaction_type, sender, sequence_number, entry_function) = match &data.transaction {
+        Transaction::UserTransaction(signed_txn) => {
+            let entry_function = match signed_txn.payload() {
+                TransactionPayload::EntryFunction(entry_function) => Some(format!(
+                    "{}::{}::{}",
+                    entry_function.module().address().to_hex_literal(),
+                    entry_function.module().name(),
+                    entry_function.function()
+                )),
+                _ => None,
+            };
+            (
+                "user_transaction",
+                Some(signed_txn.sender().to_hex_literal()),
+                Some(signed_txn.sequence_number()),
+                entry_function,
+            )
+        },
+        Transaction::GenesisTransaction(_) => ("genesis_transaction", None, None, None),
+        Transaction::BlockMetadata(block_metadata) => (
+            "block_metadata_transaction",
+            Some(block_metadata.proposer().to_hex_literal()),
+            None,
+            None,
+        ),
+        Transaction::StateCheckpoint(_) => ("state_checkpoint_transaction", None, None, None),
+    };
+
+    let transaction_info = &data.transaction_info;
+    TransactionRow {
+        version: data.version,
+        transaction_hash: transaction_info.transaction_hash().to_hex_literal(),
+        transaction_type: transaction_type.to_string(),
+        sender,
+        sequence_number,
+        entry_function,
+        gas_used: transaction_info.gas_used(),
+        success: transaction_info.status().is_success(),
+        vm_status: format!("{:?}", transaction_info.status()),
+        num_events: data.events.len() as u64,
+        num_write_set_changes: data.write_set.iter().count() as u64,
+        state_change_hash: transaction_info.state_change_hash().to_hex_literal(),
+        event_root_hash: transaction_info.event_root_hash().to_hex_literal(),
+        state_checkpoint_hash: transaction_info
+            .state_checkpoint_hash()
+            .map(|hash| hash.to_hex_literal()),
+    }
+}
+
+fn create_write_set_change_row(
+    version: u64,
+    change_index: u64,
+    state_key_hash: String,
+    state_key: &StateKey,
+    write_op: &WriteOp,
+    decoded_value: Option<String>,
+) -> WriteSetChangeRow {
+    let mut row = WriteSetChangeRow {
+        version,
+        change_index,
+        state_key_hash,
+        state_key_type: String::new(),
+        address: None,
+        path_type: None,
+        path: None,
+        table_handle: None,
+        table_key: None,
+        write_op: match write_op {
+            WriteOp::Creation(_) | WriteOp::CreationWithMetadata { .. } => "creation",
+            WriteOp::Modification(_) | WriteOp::ModificationWithMetadata { .. } => "modification",
+            WriteOp::Deletion | WriteOp::DeletionWithMetadata { .. } => "deletion",
+        }
+        .to_string(),
+        value: write_op.bytes().map(|bytes| bytes.to_vec()),
+        decoded_value,
+    };
+
+    match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) => {
+            row.state_key_type = "access_path".to_string();
+            row.address = Some(access_path.address.to_hex_literal());
+            let (path_type, path) = match access_path.get_path() {
+                Path::Code(module_id) => ("code", module_id.to_string()),
+                Path::Resource(struct_tag) => ("resource", struct_tag.to_string()),
+                Path::ResourceGroup(struct_tag) => ("resource_group", struct_tag.to_string()),
+            };
+            row.path_type = Some(path_type.to_string());
+            row.path = Some(path);
+        },
+        StateKeyInner::TableItem { handle, key } => {
+            row.state_key_type = "table_item".to_string();
+            row.table_handle = Some(handle.0.to_hex_literal());
+            row.table_key = Some(key.clone());
+        },
+        StateKeyInner::Raw(_) => {
+            row.state_key_type = "raw".to_string();
+        },
+    }
+
+    row
+}
+
+/// Decodes the given state value into a human readable string. Only
+/// resources can be decoded (table items carry no type information).
+fn decode_state_value<T: MoveResolverExt>(
+    state_key: &StateKey,
+    bytes: &[u8],
+    annotator: Option<&AptosValueAnnotator<T>>,
+) -> Option<String> {
+    let annotator = annotator?;
+    match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) => match access_path.get_path() {
+            Path::Resource(struct_tag) => annotator
+                .view_resource(&struct_tag, bytes)
+                .ok()
+                .map(|value| value.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -46,6 +46,31 @@ fn test_various_cmd_parsing() {
         ".",
     ]);
 
+    run_cmd(&[
+        "aptos-db-tool",
+        "export",
+        "from-db",
+        "--db-dir",
+        ".",
+        "--output-dir",
+        ".",
+        "--partition-size",
+        "1000",
+    ]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "export",
+        "from-backup",
+        "--local-fs-dir",
+        ".",
+        "--output-dir",
+        ".",
+        "--start-version",
+        "100",
+        "--end-version",
+        "200",
+    ]);
+
     run_cmd(&["aptos-db-tool", "backup", "verify", "--local-fs-dir", "."]);
     run_cmd(&[
         "aptos-db-tool",
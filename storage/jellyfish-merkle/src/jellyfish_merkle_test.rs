@@ -8,7 +8,8 @@ use crate::{
     test_helper::{
         arb_existent_kvs_and_nonexistent_keys, arb_kv_pair_with_distinct_last_nibble,
         arb_tree_with_index, gen_value, test_get_leaf_count, test_get_range_proof,
-        test_get_with_proof, test_get_with_proof_with_distinct_last_nibble, ValueBlob,
+        test_get_with_multi_proof, test_get_with_proof,
+        test_get_with_proof_with_distinct_last_nibble, ValueBlob,
     },
 };
 use aptos_crypto::{hash::SPARSE_MERKLE_PLACEHOLDER_HASH, HashValue};
@@ -16,7 +17,7 @@ use aptos_types::nibble::Nibble;
 use mock_tree_store::MockTreeStore;
 use proptest::{collection::hash_set, prelude::*};
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 fn update_nibble(original_key: &HashValue, n: usize, nibble: u8) -> HashValue {
     assert!(nibble < 16);
@@ -620,6 +621,77 @@ fn test_missing_root() {
     assert_eq!(err.version, 0);
 }
 
+#[test]
+fn test_get_leaf_diff() {
+    let mut rng: StdRng = StdRng::from_seed([0u8; 32]);
+    let db = MockTreeStore::default();
+    let tree = JellyfishMerkleTree::new(&db);
+
+    let keys: Vec<_> = (0..200)
+        .map(|_| HashValue::random_with_rng(&mut rng))
+        .collect();
+    let values: Vec<_> = (0..200).map(|_| gen_value()).collect();
+    let (_root, batch) = tree
+        .put_value_set_test(
+            keys.iter().cloned().zip(values.iter().map(Some)).collect(),
+            0, /* version */
+        )
+        .unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    // Modify 20 keys, delete 20 keys and add 20 keys, half of which share a long prefix with
+    // existing ones so leaves get pushed down.
+    let updated_values: Vec<_> = (0..20).map(|_| gen_value()).collect();
+    let new_keys: Vec<_> = (0..20)
+        .map(|i| {
+            if i % 2 == 0 {
+                update_nibble(&keys[100 + i], 40, 0xF ^ keys[100 + i].nibble(40))
+            } else {
+                HashValue::random_with_rng(&mut rng)
+            }
+        })
+        .collect();
+    let new_values: Vec<_> = (0..20).map(|_| gen_value()).collect();
+    let mut value_set = vec![];
+    let mut expected = BTreeMap::new();
+    for i in 0..20 {
+        value_set.push((keys[i], Some(&updated_values[i])));
+        expected.insert(keys[i], (Some(values[i].0), Some(updated_values[i].0)));
+        value_set.push((keys[50 + i], None));
+        expected.insert(keys[50 + i], (Some(values[50 + i].0), None));
+        value_set.push((new_keys[i], Some(&new_values[i])));
+        expected.insert(new_keys[i], (None, Some(new_values[i].0)));
+    }
+    let (_root, batch) = tree.put_value_set_test(value_set, 1 /* version */).unwrap();
+    db.write_tree_update_batch(batch).unwrap();
+
+    let diff: BTreeMap<_, _> = tree
+        .get_leaf_diff(0, 1)
+        .unwrap()
+        .into_iter()
+        .map(|(old, new)| {
+            let key = old
+                .as_ref()
+                .or(new.as_ref())
+                .map(|leaf| leaf.account_key())
+                .unwrap();
+            (
+                key,
+                (
+                    old.map(|leaf| leaf.value_hash()),
+                    new.map(|leaf| leaf.value_hash()),
+                ),
+            )
+        })
+        .collect();
+    assert_eq!(diff, expected);
+
+    // Diffing in the other direction swaps old and new.
+    let reverse_diff = tree.get_leaf_diff(1, 0).unwrap();
+    assert_eq!(reverse_diff.len(), expected.len());
+    assert!(tree.get_leaf_diff(1, 1).unwrap().is_empty());
+}
+
 fn many_keys_get_proof_and_verify_tree_root(seed: &[u8], num_keys: usize) {
     assert!(seed.len() < 32);
     let mut actual_seed = [0u8; 32];
@@ -792,6 +864,11 @@ proptest! {
         test_get_with_proof((existent_kvs, nonexistent_keys))
     }
 
+    #[test]
+    fn proptest_get_with_multi_proof((existent_kvs, nonexistent_keys) in arb_existent_kvs_and_nonexistent_keys::<ValueBlob>(1000, 100)) {
+        test_get_with_multi_proof((existent_kvs, nonexistent_keys))
+    }
+
     #[test]
     fn proptest_get_with_proof_with_distinct_last_nibble((kv1, kv2) in arb_kv_pair_with_distinct_last_nibble::<ValueBlob>()) {
         test_get_with_proof_with_distinct_last_nibble((kv1, kv2))
@@ -88,12 +88,17 @@ use anyhow::{bail, ensure, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_types::{
     nibble::{nibble_path::NibblePath, Nibble, ROOT_NIBBLE_HEIGHT},
-    proof::{SparseMerkleProof, SparseMerkleProofExt, SparseMerkleRangeProof},
+    proof::{
+        SparseMerkleMultiProof, SparseMerkleMultiProofLeaf, SparseMerkleProof,
+        SparseMerkleProofExt, SparseMerkleRangeProof,
+    },
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::Version,
 };
 use arr_macro::arr;
-use node_type::{Child, Children, InternalNode, LeafNode, Node, NodeKey, NodeType};
+use node_type::{
+    Child, Children, ChildrenWalkStep, InternalNode, LeafNode, Node, NodeKey, NodeType,
+};
 use once_cell::sync::Lazy;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::arbitrary::Arbitrary;
@@ -795,6 +800,137 @@ where
         bail!("Jellyfish Merkle tree has cyclic graph inside.");
     }
 
+    /// Returns the values (if applicable) of a list of keys and a single proof for all of them,
+    /// in which the siblings shared by the keys are only included once. `keys` must be strictly
+    /// increasing.
+    pub fn get_with_multi_proof(
+        &self,
+        keys: &[HashValue],
+        version: Version,
+    ) -> Result<(
+        Vec<Option<(HashValue, (K, Version))>>,
+        SparseMerkleMultiProof,
+    )> {
+        ensure!(
+            keys.windows(2).all(|pair| pair[0] < pair[1]),
+            "Keys must be strictly increasing."
+        );
+
+        let mut leaves = Vec::with_capacity(keys.len());
+        let mut siblings = vec![];
+        if !keys.is_empty() {
+            self.walk_with_multi_proof(
+                NodeKey::new_empty_path(version),
+                keys,
+                (0 /* depth */, 0 /* nibble_depth */),
+                &mut leaves,
+                &mut siblings,
+            )?;
+        }
+        let (values, leaves) = leaves.into_iter().unzip();
+        Ok((values, SparseMerkleMultiProof::new(leaves, siblings)))
+    }
+
+    /// Walks the subtree at `node_key`, which all of `keys` go through, in depth-first,
+    /// left-to-right order, pushing the value and proof leaf of each key and the siblings that
+    /// none of the keys go through.
+    fn walk_with_multi_proof(
+        &self,
+        node_key: NodeKey,
+        keys: &[HashValue],
+        (depth, nibble_depth): (usize, usize),
+        leaves: &mut Vec<(
+            Option<(HashValue, (K, Version))>,
+            SparseMerkleMultiProofLeaf,
+        )>,
+        siblings: &mut Vec<HashValue>,
+    ) -> Result<()> {
+        // We limit the depth of the walk deliberately to avoid potential cyclic graph bugs in the
+        // tree structure.
+        ensure!(
+            nibble_depth <= ROOT_NIBBLE_HEIGHT,
+            "Jellyfish Merkle tree has cyclic graph inside."
+        );
+        let node = self
+            .reader
+            .get_node_with_tag(&node_key, "get_proof")
+            .map_err(|err| {
+                if nibble_depth == 0 {
+                    MissingRootError {
+                        version: node_key.version(),
+                    }
+                    .into()
+                } else {
+                    err
+                }
+            })?;
+
+        match node {
+            Node::Internal(internal_node) => {
+                if internal_node.leaf_count() == 1 {
+                    // Logically this node should be a leaf node, it got pushed down for
+                    // sharding, skip the siblings.
+                    let (only_child_nibble, Child { version, .. }) =
+                        internal_node.children_sorted().next().unwrap();
+                    return self.walk_with_multi_proof(
+                        node_key.gen_child_node_key(*version, *only_child_nibble),
+                        keys,
+                        (depth, nibble_depth + 1),
+                        leaves,
+                        siblings,
+                    );
+                }
+
+                let mut remaining_keys = keys;
+                for step in internal_node.get_children_with_siblings(&node_key, keys, depth) {
+                    match step {
+                        ChildrenWalkStep::Sibling(hash) => siblings.push(hash),
+                        ChildrenWalkStep::Child {
+                            num_keys,
+                            depth,
+                            node_key: child_node_key,
+                        } => {
+                            let (child_keys, rest) = remaining_keys.split_at(num_keys);
+                            self.walk_with_multi_proof(
+                                child_node_key,
+                                child_keys,
+                                (depth, nibble_depth + 1),
+                                leaves,
+                                siblings,
+                            )?;
+                            remaining_keys = rest;
+                        },
+                        ChildrenWalkStep::Empty { num_keys, depth } => {
+                            leaves.extend((0..num_keys).map(|_| {
+                                (None, SparseMerkleMultiProofLeaf::new(depth as u16, None))
+                            }));
+                            remaining_keys = &remaining_keys[num_keys..];
+                        },
+                    }
+                }
+            },
+            Node::Leaf(leaf_node) => {
+                let proof_leaf =
+                    SparseMerkleMultiProofLeaf::new(depth as u16, Some(leaf_node.clone().into()));
+                leaves.extend(keys.iter().map(|key| {
+                    let value = if leaf_node.account_key() == *key {
+                        Some((leaf_node.value_hash(), leaf_node.value_index().clone()))
+                    } else {
+                        None
+                    };
+                    (value, proof_leaf)
+                }));
+            },
+            Node::Null => {
+                leaves.extend(
+                    keys.iter()
+                        .map(|_| (None, SparseMerkleMultiProofLeaf::new(depth as u16, None))),
+                );
+            },
+        }
+        Ok(())
+    }
+
     /// Gets the proof that shows a list of keys up to `rightmost_key_to_prove` exist at `version`.
     pub fn get_range_proof(
         &self,
@@ -822,6 +958,144 @@ where
         Ok(SparseMerkleRangeProof::new(siblings))
     }
 
+    /// Returns the leaves that differ between the trees at `old_version` and `new_version`, sorted
+    /// by key, as `(old_leaf, new_leaf)` pairs. A key only present in the new tree has been added,
+    /// one only present in the old tree has been removed and otherwise its value hash changed.
+    /// The two trees are walked together and subtrees with the same hash on both sides are
+    /// skipped without being read.
+    pub fn get_leaf_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+    ) -> Result<Vec<(Option<LeafNode<K>>, Option<LeafNode<K>>)>> {
+        let old_root_key = NodeKey::new_empty_path(old_version);
+        let old_root =
+            self.reader
+                .get_node_option(&old_root_key, "diff")?
+                .ok_or(MissingRootError {
+                    version: old_version,
+                })?;
+        let new_root_key = NodeKey::new_empty_path(new_version);
+        let new_root =
+            self.reader
+                .get_node_option(&new_root_key, "diff")?
+                .ok_or(MissingRootError {
+                    version: new_version,
+                })?;
+
+        let mut diff = vec![];
+        if old_root.hash() != new_root.hash() {
+            self.diff_subtrees(
+                Some((old_root_key, old_root)),
+                Some((new_root_key, new_root)),
+                &mut diff,
+            )?;
+        }
+        Ok(diff)
+    }
+
+    fn diff_subtrees(
+        &self,
+        old: Option<(NodeKey, Node<K>)>,
+        new: Option<(NodeKey, Node<K>)>,
+        diff: &mut Vec<(Option<LeafNode<K>>, Option<LeafNode<K>>)>,
+    ) -> Result<()> {
+        match (old, new) {
+            (
+                Some((old_node_key, Node::Internal(old_node))),
+                Some((new_node_key, Node::Internal(new_node))),
+            ) => {
+                for i in 0..16 {
+                    let nibble = Nibble::from(i);
+                    let old_child = old_node.child(nibble);
+                    let new_child = new_node.child(nibble);
+                    if let (Some(old_child), Some(new_child)) = (old_child, new_child) {
+                        if old_child.hash == new_child.hash {
+                            continue;
+                        }
+                    }
+                    self.diff_subtrees(
+                        old_child
+                            .map(|child| self.read_child(&old_node_key, nibble, child))
+                            .transpose()?,
+                        new_child
+                            .map(|child| self.read_child(&new_node_key, nibble, child))
+                            .transpose()?,
+                        diff,
+                    )?;
+                }
+            },
+            (old, new) => {
+                // At least one side is not an internal node, so all leaves under both sides are
+                // compared one by one.
+                let mut old_leaves = vec![];
+                if let Some((node_key, node)) = old {
+                    self.collect_leaves(node_key, node, &mut old_leaves)?;
+                }
+                let mut new_leaves = vec![];
+                if let Some((node_key, node)) = new {
+                    self.collect_leaves(node_key, node, &mut new_leaves)?;
+                }
+
+                let mut old_iter = old_leaves.into_iter().peekable();
+                let mut new_iter = new_leaves.into_iter().peekable();
+                loop {
+                    let ordering = match (old_iter.peek(), new_iter.peek()) {
+                        (Some(old_leaf), Some(new_leaf)) => {
+                            old_leaf.account_key().cmp(&new_leaf.account_key())
+                        },
+                        (Some(_), None) => std::cmp::Ordering::Less,
+                        (None, Some(_)) => std::cmp::Ordering::Greater,
+                        (None, None) => break,
+                    };
+                    match ordering {
+                        std::cmp::Ordering::Less => diff.push((old_iter.next(), None)),
+                        std::cmp::Ordering::Greater => diff.push((None, new_iter.next())),
+                        std::cmp::Ordering::Equal => {
+                            let old_leaf = old_iter.next().expect("Peeked.");
+                            let new_leaf = new_iter.next().expect("Peeked.");
+                            if old_leaf.value_hash() != new_leaf.value_hash() {
+                                diff.push((Some(old_leaf), Some(new_leaf)));
+                            }
+                        },
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn read_child(
+        &self,
+        parent_key: &NodeKey,
+        nibble: Nibble,
+        child: &Child,
+    ) -> Result<(NodeKey, Node<K>)> {
+        let child_key = parent_key.gen_child_node_key(child.version, nibble);
+        let child_node = self.reader.get_node_with_tag(&child_key, "diff")?;
+        Ok((child_key, child_node))
+    }
+
+    /// Appends all leaves under `node` to `leaves`, in ascending key order.
+    fn collect_leaves(
+        &self,
+        node_key: NodeKey,
+        node: Node<K>,
+        leaves: &mut Vec<LeafNode<K>>,
+    ) -> Result<()> {
+        match node {
+            Node::Internal(internal_node) => {
+                for (nibble, child) in internal_node.children_sorted() {
+                    let (child_key, child_node) = self.read_child(&node_key, *nibble, child)?;
+                    self.collect_leaves(child_key, child_node, leaves)?;
+                }
+            },
+            Node::Leaf(leaf_node) => leaves.push(leaf_node),
+            Node::Null => {},
+        }
+        Ok(())
+    }
+
     #[cfg(test)]
     pub fn get(&self, key: HashValue, version: Version) -> Result<Option<HashValue>> {
         Ok(self.get_with_proof(key, version)?.0.map(|x| x.0))
@@ -622,6 +622,110 @@ impl InternalNode {
         }
         unreachable!("Impossible to get here without returning even at the lowest level.")
     }
+
+    /// Like [`InternalNode::get_child_with_siblings`], but for several keys going through this
+    /// node at once. `keys` must be strictly increasing and `depth` is the number of bits of the
+    /// path of this node. Returns the steps of a depth-first, left-to-right walk of the subtrees of
+    /// this node containing the keys, so that siblings shared by several keys only appear once.
+    pub(crate) fn get_children_with_siblings(
+        &self,
+        node_key: &NodeKey,
+        keys: &[HashValue],
+        depth: usize,
+    ) -> Vec<ChildrenWalkStep> {
+        assert!(self.leaf_count > 1);
+
+        let mut steps = vec![];
+        self.walk_children(
+            (node_key, self.generate_bitmaps()),
+            0,  /* start index */
+            16, /* width */
+            keys,
+            depth,
+            &mut steps,
+        );
+        steps
+    }
+
+    fn walk_children(
+        &self,
+        (node_key, bitmaps): (&NodeKey, (u16, u16)),
+        start: u8,
+        width: u8,
+        keys: &[HashValue],
+        depth: usize,
+        steps: &mut Vec<ChildrenWalkStep>,
+    ) {
+        let (range_existence_bitmap, range_leaf_bitmap) =
+            Self::range_bitmaps(start, width, bitmaps);
+        if range_existence_bitmap == 0 {
+            // No child in this range.
+            steps.push(ChildrenWalkStep::Empty {
+                num_keys: keys.len(),
+                depth,
+            });
+        } else if width == 1 || (range_existence_bitmap.count_ones() == 1 && range_leaf_bitmap != 0)
+        {
+            // The only leaf child under this subtree, or the lowest level. As in
+            // `get_child_with_siblings`, a leaf child is returned even if it doesn't match.
+            let only_child_index = Nibble::from(range_existence_bitmap.trailing_zeros() as u8);
+            let only_child_version = self
+                .child(only_child_index)
+                .with_context(|| {
+                    format!(
+                        "Corrupted internal node: child_bitmap indicates \
+                         the existence of a non-exist child at index {:x}",
+                        only_child_index
+                    )
+                })
+                .unwrap()
+                .version;
+            steps.push(ChildrenWalkStep::Child {
+                num_keys: keys.len(),
+                depth,
+                node_key: node_key.gen_child_node_key(only_child_version, only_child_index),
+            });
+        } else {
+            let half_width = width / 2;
+            let split = keys.partition_point(|key| !key.bit(depth));
+            for (half_start, half_keys) in [
+                (start, &keys[..split]),
+                (start + half_width, &keys[split..]),
+            ] {
+                if half_keys.is_empty() {
+                    steps.push(ChildrenWalkStep::Sibling(
+                        self.merkle_hash(half_start, half_width, bitmaps),
+                    ));
+                } else {
+                    self.walk_children(
+                        (node_key, bitmaps),
+                        half_start,
+                        half_width,
+                        half_keys,
+                        depth + 1,
+                        steps,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A step of the walk of several keys through an [`InternalNode`], see
+/// [`InternalNode::get_children_with_siblings`].
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum ChildrenWalkStep {
+    /// The hash of a subtree that none of the keys go through.
+    Sibling(HashValue),
+    /// The next `num_keys` keys go through the child at `node_key`, whose root is `depth` bits
+    /// deep in the tree.
+    Child {
+        num_keys: usize,
+        depth: usize,
+        node_key: NodeKey,
+    },
+    /// The next `num_keys` keys would be in an empty subtree `depth` bits deep in the tree.
+    Empty { num_keys: usize, depth: usize },
 }
 
 /// Given a nibble, computes the start position of its `child_half_start` and `sibling_half_start`
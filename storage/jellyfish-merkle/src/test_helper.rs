@@ -10,7 +10,7 @@ use aptos_crypto::{
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_storage_interface::jmt_update_refs;
 use aptos_types::{
-    proof::{SparseMerkleInternalNode, SparseMerkleRangeProof},
+    proof::{SparseMerkleInternalNode, SparseMerkleMultiProof, SparseMerkleRangeProof},
     transaction::Version,
 };
 use proptest::{
@@ -20,7 +20,7 @@ use proptest::{
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     ops::Bound,
 };
 
@@ -191,6 +191,59 @@ pub fn test_get_range_proof<V: TestKey>((btree, n): (BTreeMap<HashValue, (HashVa
     );
 }
 
+pub fn test_get_with_multi_proof<V: TestKey>(
+    (existent_kvs, nonexistent_keys): (HashMap<HashValue, (HashValue, V)>, Vec<HashValue>),
+) {
+    let (db, version) = init_mock_db(&existent_kvs);
+    let tree = JellyfishMerkleTree::new(&db);
+    let root_hash = tree.get_root_hash(version).unwrap();
+
+    let keys: Vec<_> = existent_kvs
+        .keys()
+        .chain(nonexistent_keys.iter())
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let (values, proof) = tree.get_with_multi_proof(&keys, version).unwrap();
+    let elements: Vec<_> = keys
+        .iter()
+        .zip(values.iter())
+        .map(|(key, value)| {
+            let expected = existent_kvs.get(key).map(|(value_hash, _)| *value_hash);
+            assert_eq!(value.as_ref().map(|(value_hash, _)| *value_hash), expected);
+            (*key, expected)
+        })
+        .collect();
+    proof.verify_by_hash(root_hash, &elements).unwrap();
+
+    // The shared walk should produce the same proof as combining the individual proofs, and
+    // never be larger than them.
+    let individual_proofs: Vec<_> = keys
+        .iter()
+        .map(|key| tree.get_with_proof(*key, version).unwrap().1)
+        .collect();
+    assert_eq!(
+        proof,
+        SparseMerkleMultiProof::from_proofs(&keys, &individual_proofs).unwrap()
+    );
+    let num_individual_siblings: usize = individual_proofs
+        .iter()
+        .map(|proof| proof.siblings().len())
+        .sum();
+    assert!(proof.siblings().len() <= num_individual_siblings);
+
+    // Flipping the existence of any element must fail the verification.
+    for i in 0..elements.len() {
+        let mut tampered = elements.clone();
+        tampered[i].1 = match tampered[i].1 {
+            Some(_) => None,
+            None => Some(HashValue::random()),
+        };
+        assert!(proof.verify_by_hash(root_hash, &tampered).is_err());
+    }
+}
+
 fn test_existent_keys_impl<V: TestKey>(
     tree: &JellyfishMerkleTree<'_, MockTreeStore<V>, V>,
     version: Version,
@@ -17,8 +17,9 @@ use aptos_types::{
     move_resource::MoveStorage,
     on_chain_config::{access_path_for_config, ConfigID},
     proof::{
-        AccumulatorConsistencyProof, SparseMerkleProof, SparseMerkleProofExt,
-        SparseMerkleRangeProof, TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
+        AccumulatorConsistencyProof, SparseMerkleMultiProof, SparseMerkleProof,
+        SparseMerkleProofExt, SparseMerkleRangeProof, TransactionAccumulatorRangeProof,
+        TransactionAccumulatorSummary,
     },
     state_proof::StateProof,
     state_store::{
@@ -433,6 +434,30 @@ pub trait DbReader: Send + Sync {
             .map(|(value, proof_ext)| (value, proof_ext.into()))
     }
 
+    /// Gets the state values of a list of state keys along with a single multi-proof, out of the
+    /// ledger state indicated by the state Merkle tree root at `version`. Duplicated keys are
+    /// removed and the result is sorted by the hash of the state keys, which is the order the
+    /// proof is laid out in.
+    fn get_state_values_with_multi_proof_by_version(
+        &self,
+        state_keys: &[StateKey],
+        version: Version,
+    ) -> Result<(Vec<(StateKey, Option<StateValue>)>, SparseMerkleMultiProof)> {
+        unimplemented!()
+    }
+
+    /// Gets the state keys whose values differ between the state snapshots at `old_version` and
+    /// `new_version`, sorted by the hash of the state keys, along with their old and new values.
+    /// A key without an old value has been added, one without a new value has been removed and
+    /// otherwise it has been modified. Both versions must have a state snapshot.
+    fn get_state_value_diff(
+        &self,
+        old_version: Version,
+        new_version: Version,
+    ) -> Result<Vec<(StateKey, Option<StateValue>, Option<StateValue>)>> {
+        unimplemented!()
+    }
+
     /// Gets the latest ExecutedTrees no matter if db has been bootstrapped.
     /// Used by the Db-bootstrapper.
     fn get_latest_executed_trees(&self) -> Result<ExecutedTrees> {
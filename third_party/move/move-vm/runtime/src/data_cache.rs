@@ -9,7 +9,7 @@ use move_core_types::{
     effects::{AccountChanges, ChangeSet, Changes, Event, Op},
     gas_algebra::NumBytes,
     identifier::Identifier,
-    language_storage::{ModuleId, TypeTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     metadata::Metadata,
     resolver::MoveResolver,
     value::MoveTypeLayout,
@@ -230,6 +230,36 @@ impl<'r> TransactionDataCache<'r> {
         ))
     }
 
+    /// Returns the global value of type `tag` at `addr` if it has already been loaded into the
+    /// cache, without loading anything. Used by debuggers, which must not change the state (and
+    /// thus the gas charged) of the execution they inspect.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn peek_resource(
+        &self,
+        loader: &Loader,
+        addr: &AccountAddress,
+        tag: &StructTag,
+    ) -> PartialVMResult<Option<&GlobalValue>> {
+        let account_cache = match self.account_map.get(addr) {
+            Some(account_cache) => account_cache,
+            None => return Ok(None),
+        };
+        for (ty, (_ty_layout, gv)) in &account_cache.data_map {
+            if let TypeTag::Struct(s_tag) = loader.type_to_type_tag(ty)? {
+                if s_tag.as_ref() == tag {
+                    return Ok(Some(gv));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the storage backing this cache.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn remote(&self) -> &'r dyn MoveResolver {
+        self.remote
+    }
+
     pub(crate) fn load_module(&self, module_id: &ModuleId) -> VMResult<Vec<u8>> {
         if let Some(account_cache) = self.account_map.get(module_id.address()) {
             if let Some((blob, _is_republishing)) = account_cache.module_map.get(module_id.name()) {
@@ -2,16 +2,42 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+//! Debugging support for the interpreter.
+//!
+//! Besides the interactive debugger enabled with `MOVE_VM_STEP`, this module allows a
+//! [`DebugHook`] to be installed, which is consulted before every instruction and can pause
+//! execution to inspect the call stack, the operand stack and global storage. Hooks only get
+//! read access to the interpreter, so debugging does not change the outcome or the gas cost of
+//! an execution.
+
 use crate::{
+    data_cache::TransactionDataCache,
     interpreter::Interpreter,
     loader::{Function, Loader},
 };
-use move_binary_format::file_format::Bytecode;
-use move_vm_types::values::{self, Locals};
+use move_binary_format::{
+    errors::{PartialVMError, PartialVMResult},
+    file_format::Bytecode,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    resolver::MoveResolver,
+    vm_status::StatusCode,
+};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    values::{self, Locals, Value},
+};
+use once_cell::sync::Lazy;
 use std::{
     collections::BTreeSet,
     io::{self, Write},
     str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
 };
 
 #[derive(Debug)]
@@ -194,3 +220,189 @@ impl DebugContext {
         }
     }
 }
+
+/// Position of the interpreter before executing an instruction.
+#[derive(Clone, Copy, Debug)]
+pub struct DebugLocation<'a> {
+    /// Module of the executing function, `None` for scripts.
+    pub module_id: Option<&'a ModuleId>,
+    pub function_name: &'a str,
+    pub pc: u16,
+    /// Number of frames on the call stack, including the executing one.
+    pub depth: usize,
+}
+
+/// A frame of the call stack, with its locals rendered as strings.
+#[derive(Clone, Debug)]
+pub struct DebugFrame {
+    /// Module of the function, `None` for scripts.
+    pub module_id: Option<ModuleId>,
+    pub function_name: String,
+    pub ty_args: Vec<TypeTag>,
+    pub pc: u16,
+    /// Values of the locals, indexed like in the bytecode. `None` for locals which are unset.
+    pub locals: Vec<Option<String>>,
+}
+
+/// A resource of global storage, as seen from the paused execution.
+#[derive(Clone, Debug)]
+pub enum DebugResource {
+    /// The resource has been accessed by the session: its current value, or `None` if it does
+    /// not exist at this point of the execution.
+    Cached(Option<String>),
+    /// The resource has not been accessed by the session yet: its BCS blob in storage, if any.
+    Stored(Option<Vec<u8>>),
+}
+
+/// State of the interpreter while paused by a [`DebugHook`].
+pub struct DebugState<'a> {
+    pub(crate) function: &'a Function,
+    pub(crate) ty_args: &'a [Type],
+    pub(crate) locals: &'a Locals,
+    pub(crate) pc: u16,
+    pub(crate) instr: &'a Bytecode,
+    pub(crate) loader: &'a Loader,
+    pub(crate) interp: &'a Interpreter,
+    pub(crate) data_store: &'a TransactionDataCache<'a>,
+}
+
+impl<'a> DebugState<'a> {
+    pub fn location(&self) -> DebugLocation<'_> {
+        DebugLocation {
+            module_id: self.function.module_id(),
+            function_name: self.function.name(),
+            pc: self.pc,
+            depth: self.interp.debug_call_stack().count() + 1,
+        }
+    }
+
+    /// The instruction about to be executed.
+    pub fn instruction(&self) -> &Bytecode {
+        self.instr
+    }
+
+    /// Returns the frames of the call stack, outermost first. The last frame is the executing
+    /// one.
+    pub fn call_stack(&self) -> PartialVMResult<Vec<DebugFrame>> {
+        let mut frames = vec![];
+        for (function, ty_args, locals, pc) in self.interp.debug_call_stack() {
+            frames.push(self.frame(function, ty_args, locals, pc)?);
+        }
+        frames.push(self.frame(self.function, self.ty_args, self.locals, self.pc)?);
+        Ok(frames)
+    }
+
+    /// Returns the values on the operand stack, bottom first.
+    pub fn operand_stack(&self) -> PartialVMResult<Vec<String>> {
+        self.interp
+            .debug_operand_stack()
+            .iter()
+            .map(render_value)
+            .collect()
+    }
+
+    /// Reads the resource of type `tag` at `addr`. Nothing is loaded into the data cache of the
+    /// session, so the read does not affect the rest of the execution.
+    pub fn resource(
+        &self,
+        addr: AccountAddress,
+        tag: &StructTag,
+    ) -> PartialVMResult<DebugResource> {
+        if let Some(gv) = self.data_store.peek_resource(self.loader, &addr, tag)? {
+            let value = if gv.exists()? {
+                Some(render_value(&gv.borrow_global()?)?)
+            } else {
+                None
+            };
+            return Ok(DebugResource::Cached(value));
+        }
+        let blob = self.storage().get_resource(&addr, tag).map_err(|err| {
+            PartialVMError::new(StatusCode::STORAGE_ERROR)
+                .with_message(format!("Unexpected storage error: {:?}", err))
+        })?;
+        Ok(DebugResource::Stored(blob))
+    }
+
+    /// The storage backing the session, without the changes made by the session.
+    pub fn storage(&self) -> &dyn MoveResolver {
+        self.data_store.remote()
+    }
+
+    fn frame(
+        &self,
+        function: &Function,
+        ty_args: &[Type],
+        locals: &Locals,
+        pc: u16,
+    ) -> PartialVMResult<DebugFrame> {
+        let mut rendered_locals = vec![];
+        for idx in 0..function.local_count() {
+            rendered_locals.push(
+                if locals.is_invalid(idx)? {
+                    None
+                } else {
+                    let mut s = String::new();
+                    values::debug::print_local(&mut s, locals, idx)?;
+                    Some(s)
+                },
+            );
+        }
+        Ok(DebugFrame {
+            module_id: function.module_id().cloned(),
+            function_name: function.name().to_string(),
+            ty_args: ty_args
+                .iter()
+                .map(|ty| self.loader.type_to_type_tag(ty))
+                .collect::<PartialVMResult<_>>()?,
+            pc,
+            locals: rendered_locals,
+        })
+    }
+}
+
+fn render_value(value: &Value) -> PartialVMResult<String> {
+    let mut s = String::new();
+    values::debug::print_value(&mut s, value)?;
+    Ok(s)
+}
+
+/// A debugger driving the interpreter. Once installed with [`set_debug_hook`], it is consulted
+/// before every instruction executed by any session of the process.
+pub trait DebugHook: Send {
+    /// Returns whether execution should pause before the instruction at `location`. This is
+    /// called for every instruction and should be cheap.
+    fn should_pause(&mut self, location: &DebugLocation) -> bool;
+
+    /// Called when execution is paused. Execution resumes when this returns.
+    fn on_pause(&mut self, state: &DebugState);
+}
+
+static DEBUG_HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+type SharedDebugHook = Arc<Mutex<Box<dyn DebugHook>>>;
+
+/// The installed hook. The lock of the hook itself, not this one, is held while it runs, so that
+/// pausing execution does not block installing another hook.
+static DEBUG_HOOK: Lazy<Mutex<Option<SharedDebugHook>>> = Lazy::new(|| Mutex::new(None));
+
+/// Installs `hook`, or removes the current hook if `None`. A hook that is removed while it pauses
+/// execution keeps it paused until it returns.
+pub fn set_debug_hook(hook: Option<Box<dyn DebugHook>>) {
+    let mut current = DEBUG_HOOK.lock().unwrap();
+    DEBUG_HOOK_INSTALLED.store(hook.is_some(), Ordering::Release);
+    *current = hook.map(|hook| Arc::new(Mutex::new(hook)));
+}
+
+pub(crate) fn run_debug_hook(state: DebugState) {
+    if !DEBUG_HOOK_INSTALLED.load(Ordering::Acquire) {
+        return;
+    }
+    let hook = match DEBUG_HOOK.lock().unwrap().clone() {
+        Some(hook) => hook,
+        None => return,
+    };
+    let mut hook = hook.lock().unwrap();
+    if hook.should_pause(&state.location()) {
+        hook.on_pause(&state);
+    }
+}
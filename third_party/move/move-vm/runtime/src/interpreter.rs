@@ -779,6 +779,23 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Returns the suspended frames of the call stack, outermost first, as tuples of function,
+    /// type arguments, locals and program counter. The frame currently executing is not included.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn debug_call_stack(
+        &self,
+    ) -> impl Iterator<Item = (&Arc<Function>, &[Type], &Locals, u16)> {
+        self.call_stack
+            .0
+            .iter()
+            .map(|frame| (&frame.function, frame.ty_args(), &frame.locals, frame.pc))
+    }
+
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn debug_operand_stack(&self) -> &[Value] {
+        &self.operand_stack.value
+    }
+
     /// Generate a string which is the status of the interpreter: call stack, current bytecode
     /// stream, locals and operand stack.
     ///
@@ -1760,11 +1777,13 @@ impl Frame {
             for instruction in &code[self.pc as usize..] {
                 trace!(
                     &self.function,
+                    &self.ty_args,
                     &self.locals,
                     self.pc,
                     instruction,
                     resolver,
-                    interpreter
+                    interpreter,
+                    data_store
                 );
 
                 fail_point!("move_vm::interpreter_loop", |_| {
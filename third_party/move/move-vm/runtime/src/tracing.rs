@@ -3,16 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #[cfg(any(debug_assertions, feature = "debugging"))]
-use crate::debug::DebugContext;
+use crate::debug::{self, DebugContext};
 #[cfg(any(debug_assertions, feature = "debugging"))]
 use crate::{
+    data_cache::TransactionDataCache,
     interpreter::Interpreter,
     loader::{Function, Loader},
 };
 #[cfg(any(debug_assertions, feature = "debugging"))]
 use ::{
     move_binary_format::file_format::Bytecode,
-    move_vm_types::values::Locals,
+    move_vm_types::{loaded_data::runtime_types::Type, values::Locals},
     once_cell::sync::Lazy,
     std::{
         env,
@@ -61,11 +62,13 @@ static DEBUG_CONTEXT: Lazy<Mutex<DebugContext>> = Lazy::new(|| Mutex::new(DebugC
 #[cfg(any(debug_assertions, feature = "debugging"))]
 pub(crate) fn trace(
     function_desc: &Function,
+    ty_args: &[Type],
     locals: &Locals,
     pc: u16,
     instr: &Bytecode,
     loader: &Loader,
     interp: &Interpreter,
+    data_store: &TransactionDataCache,
 ) {
     if *TRACING_ENABLED {
         let f = &mut *LOGGING_FILE.lock().unwrap();
@@ -86,20 +89,41 @@ pub(crate) fn trace(
             .unwrap()
             .debug_loop(function_desc, locals, pc, instr, loader, interp);
     }
+    debug::run_debug_hook(debug::DebugState {
+        function: function_desc,
+        ty_args,
+        locals,
+        pc,
+        instr,
+        loader,
+        interp,
+        data_store,
+    });
 }
 
 #[macro_export]
 macro_rules! trace {
-    ($function_desc:expr, $locals:expr, $pc:expr, $instr:tt, $resolver:expr, $interp:expr) => {
+    (
+        $function_desc:expr,
+        $ty_args:expr,
+        $locals:expr,
+        $pc:expr,
+        $instr:tt,
+        $resolver:expr,
+        $interp:expr,
+        $data_store:expr
+    ) => {
         // Only include this code in debug releases
         #[cfg(any(debug_assertions, feature = "debugging"))]
         $crate::tracing::trace(
             &$function_desc,
+            $ty_args,
             $locals,
             $pc,
             &$instr,
             $resolver.loader(),
             $interp,
+            $data_store,
         )
     };
 }
@@ -2910,6 +2910,18 @@ pub mod debug {
     pub fn print_value<B: Write>(buf: &mut B, val: &Value) -> PartialVMResult<()> {
         print_value_impl(buf, &val.0)
     }
+
+    pub fn print_local<B: Write>(buf: &mut B, locals: &Locals, idx: usize) -> PartialVMResult<()> {
+        let v = locals.0.borrow();
+        match v.get(idx) {
+            Some(val) => print_value_impl(buf, val),
+            None => Err(
+                PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION).with_message(
+                    format!("local index out of bounds: got {}, len: {}", idx, v.len()),
+                ),
+            ),
+        }
+    }
 }
 
 /***************************************************************************************
@@ -0,0 +1,697 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    hook::{AdapterHook, Breakpoint, Breakpoints, Snapshot, StepMode, StopReason, VmCommand},
+    protocol::{arguments, read_request, MessageWriter, Request},
+    source_index::SourceIndex,
+};
+use anyhow::{anyhow, bail, Result};
+use move_binary_format::file_format::CodeOffset;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+    parser::parse_struct_tag,
+};
+use move_vm_runtime::debug::{set_debug_hook, DebugFrame};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeSet,
+    io::{BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// The only thread reported to the client: the VM runs one session at a time.
+const THREAD_ID: i64 = 1;
+
+/// Variables reference of the operand stack. Locals of frame `i` use `2 * i + 1`.
+const OPERAND_STACK_REFERENCE: usize = 2;
+
+/// Messages processed by the adapter, in order.
+pub(crate) enum Message {
+    Request(Request),
+    ClientClosed,
+    Stopped(Snapshot),
+    ProgramExited { success: bool },
+}
+
+/// A Debug Adapter Protocol server for the Move VM.
+///
+/// Once the client is done configuring breakpoints, the adapter runs the program to debug, e.g.
+/// the unit tests of a package, and pauses the VM whenever it hits a breakpoint or finishes a
+/// step. While paused, the client can inspect the call stack with its locals, the operand stack,
+/// and read global storage by evaluating `exists<TYPE>(ADDRESS)` or
+/// `borrow_global<TYPE>(ADDRESS)`.
+///
+/// Breakpoints are set on lines of sources indexed by the [`SourceIndex`], or on functions, as
+/// `ADDRESS::MODULE::FUNCTION`, optionally followed by `@OFFSET` to break at a bytecode offset.
+pub struct DebugAdapter {
+    sources: Arc<SourceIndex>,
+}
+
+impl DebugAdapter {
+    pub fn new(sources: SourceIndex) -> Self {
+        Self {
+            sources: Arc::new(sources),
+        }
+    }
+
+    /// Waits for a client to connect on `port` of the loopback interface and serves it.
+    pub fn serve_tcp<T: Send + 'static>(
+        self,
+        port: u16,
+        program: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        eprintln!("Waiting for a debugger to connect on port {}", port);
+        let (stream, _) = listener.accept()?;
+        self.serve(stream.try_clone()?, stream, program)
+    }
+
+    /// Serves a client communicating through `input` and `output`, running `program` once the
+    /// client finished its configuration. Returns the result of `program`.
+    pub fn serve<T: Send + 'static>(
+        self,
+        input: impl Read + Send + 'static,
+        output: impl Write,
+        program: impl FnOnce() -> Result<T> + Send + 'static,
+    ) -> Result<T> {
+        let (messages, receiver) = mpsc::channel();
+        let client_messages = messages.clone();
+        thread::spawn(move || {
+            let mut input = BufReader::new(input);
+            while let Ok(Some(request)) = read_request(&mut input) {
+                if client_messages.send(Message::Request(request)).is_err() {
+                    return;
+                }
+            }
+            let _ = client_messages.send(Message::ClientClosed);
+        });
+
+        let mut session = Session::new(self.sources, MessageWriter::new(output));
+        let mut program = Some(program);
+        let mut program_thread = None;
+        while let Ok(message) = receiver.recv() {
+            match message {
+                Message::Request(request) => match request.command.as_str() {
+                    "configurationDone" => {
+                        let program = match program.take() {
+                            Some(program) => program,
+                            None => {
+                                session.writer.respond_error(
+                                    &request,
+                                    "The program is already running".to_string(),
+                                )?;
+                                continue;
+                            },
+                        };
+                        session.writer.respond(&request, Value::Null)?;
+                        set_debug_hook(Some(Box::new(session.make_hook(messages.clone()))));
+                        let exit_messages = messages.clone();
+                        program_thread = Some(thread::spawn(move || {
+                            let result = program();
+                            let _ = exit_messages.send(Message::ProgramExited {
+                                success: result.is_ok(),
+                            });
+                            result
+                        }));
+                    },
+                    "disconnect" => {
+                        session.detach();
+                        session.writer.respond(&request, Value::Null)?;
+                        break;
+                    },
+                    _ => {
+                        if let Err(err) = session.handle(&request) {
+                            session.writer.respond_error(&request, err.to_string())?;
+                        }
+                    },
+                },
+                Message::ClientClosed => {
+                    session.detach();
+                    break;
+                },
+                Message::Stopped(snapshot) => session.stopped(snapshot)?,
+                Message::ProgramExited { success } => {
+                    let exit_code = if success { 0 } else { 1 };
+                    session
+                        .writer
+                        .send_event("exited", json!({ "exitCode": exit_code }))?;
+                    session.writer.send_event("terminated", Value::Null)?;
+                },
+            }
+        }
+
+        // Dropping the channels lets the program run to completion if it pauses again.
+        drop(session);
+        drop(receiver);
+        let program_thread = program_thread
+            .ok_or_else(|| anyhow!("The client disconnected before the program started"))?;
+        let result = program_thread
+            .join()
+            .map_err(|_| anyhow!("The program panicked"))?;
+        set_debug_hook(None);
+        result
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeArguments {
+    lines_start_at1: Option<bool>,
+    columns_start_at1: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchArguments {
+    #[serde(default)]
+    stop_on_entry: bool,
+}
+
+#[derive(Deserialize)]
+struct Source {
+    path: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct SourceBreakpoint {
+    line: usize,
+}
+
+#[derive(Deserialize)]
+struct SetBreakpointsArguments {
+    source: Source,
+    #[serde(default)]
+    breakpoints: Vec<SourceBreakpoint>,
+}
+
+#[derive(Deserialize)]
+struct FunctionBreakpoint {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct SetFunctionBreakpointsArguments {
+    breakpoints: Vec<FunctionBreakpoint>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FrameArguments {
+    frame_id: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VariablesArguments {
+    variables_reference: usize,
+}
+
+#[derive(Deserialize)]
+struct StepArguments {
+    granularity: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EvaluateArguments {
+    expression: String,
+}
+
+/// State of the connection with the client.
+struct Session<W> {
+    writer: MessageWriter<W>,
+    sources: Arc<SourceIndex>,
+    breakpoints: Arc<Mutex<Breakpoints>>,
+    pause_requested: Arc<AtomicBool>,
+    /// Channel to the hook, once the program runs.
+    commands: Option<Sender<VmCommand>>,
+    /// State of the VM, while paused.
+    snapshot: Option<Snapshot>,
+    next_breakpoint_id: i64,
+    line_base: usize,
+    column_base: usize,
+    stop_on_entry: bool,
+}
+
+impl<W: Write> Session<W> {
+    fn new(sources: Arc<SourceIndex>, writer: MessageWriter<W>) -> Self {
+        Self {
+            writer,
+            sources,
+            breakpoints: Arc::new(Mutex::new(Breakpoints::default())),
+            pause_requested: Arc::new(AtomicBool::new(false)),
+            commands: None,
+            snapshot: None,
+            next_breakpoint_id: 1,
+            line_base: 1,
+            column_base: 1,
+            stop_on_entry: false,
+        }
+    }
+
+    fn make_hook(&mut self, messages: Sender<Message>) -> AdapterHook {
+        let (commands, receiver) = mpsc::channel();
+        self.commands = Some(commands);
+        AdapterHook::new(
+            self.sources.clone(),
+            self.breakpoints.clone(),
+            self.pause_requested.clone(),
+            messages,
+            receiver,
+            self.stop_on_entry,
+        )
+    }
+
+    /// Lets the program run to completion without pausing anymore.
+    fn detach(&mut self) {
+        self.breakpoints.lock().unwrap().clear();
+        self.pause_requested.store(false, Ordering::Relaxed);
+        if self.snapshot.take().is_some() {
+            if let Some(commands) = &self.commands {
+                let _ = commands.send(VmCommand::Resume(StepMode::Continue));
+            }
+        }
+    }
+
+    fn stopped(&mut self, snapshot: Snapshot) -> Result<()> {
+        let mut body = json!({
+            "threadId": THREAD_ID,
+            "allThreadsStopped": true,
+            "description": format!("Paused before {}", snapshot.instruction),
+        });
+        body["reason"] = json!(match &snapshot.reason {
+            StopReason::Entry => "entry",
+            StopReason::Breakpoint(_) => "breakpoint",
+            StopReason::Step => "step",
+            StopReason::Pause => "pause",
+        });
+        if let StopReason::Breakpoint(ids) = &snapshot.reason {
+            body["hitBreakpointIds"] = json!(ids);
+        }
+        self.snapshot = Some(snapshot);
+        self.writer.send_event("stopped", body)
+    }
+
+    fn handle(&mut self, request: &Request) -> Result<()> {
+        let body = match request.command.as_str() {
+            "initialize" => {
+                let args: InitializeArguments = arguments(request)?;
+                self.line_base = usize::from(args.lines_start_at1.unwrap_or(true));
+                self.column_base = usize::from(args.columns_start_at1.unwrap_or(true));
+                self.writer.respond(
+                    request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsFunctionBreakpoints": true,
+                        "supportsSteppingGranularity": true,
+                    }),
+                )?;
+                return self.writer.send_event("initialized", Value::Null);
+            },
+            "launch" | "attach" => {
+                let args: LaunchArguments = arguments(request)?;
+                self.stop_on_entry = args.stop_on_entry;
+                Value::Null
+            },
+            "setBreakpoints" => self.set_breakpoints(arguments(request)?),
+            "setFunctionBreakpoints" => self.set_function_breakpoints(arguments(request)?),
+            "setExceptionBreakpoints" => json!({ "breakpoints": [] }),
+            "threads" => json!({ "threads": [{ "id": THREAD_ID, "name": "Move VM" }] }),
+            "stackTrace" => self.stack_trace()?,
+            "scopes" => self.scopes(arguments(request)?)?,
+            "variables" => self.variables(arguments(request)?)?,
+            "continue" => {
+                self.resume(StepMode::Continue)?;
+                json!({ "allThreadsContinued": true })
+            },
+            "next" | "stepIn" | "stepOut" => {
+                let args: StepArguments = arguments(request)?;
+                let mode = match (request.command.as_str(), args.granularity.as_deref()) {
+                    ("stepOut", _) => StepMode::Out,
+                    (_, Some("instruction")) => StepMode::Instruction,
+                    ("next", _) => StepMode::Over,
+                    _ => StepMode::In,
+                };
+                self.resume(mode)?;
+                Value::Null
+            },
+            "pause" => {
+                self.pause_requested.store(true, Ordering::Relaxed);
+                Value::Null
+            },
+            "evaluate" => self.evaluate(arguments(request)?)?,
+            command => bail!("Unsupported request '{}'", command),
+        };
+        self.writer.respond(request, body)
+    }
+
+    fn set_breakpoints(&mut self, args: SetBreakpointsArguments) -> Value {
+        let mut bound = vec![];
+        let mut responses = vec![];
+        for breakpoint in args.breakpoints {
+            let id = self.next_breakpoint_id;
+            self.next_breakpoint_id += 1;
+            let binding = args.source.path.as_ref().and_then(|path| {
+                self.sources
+                    .line_breakpoint(path, breakpoint.line.saturating_sub(self.line_base))
+            });
+            match binding {
+                Some((line, targets)) => {
+                    bound.extend(
+                        targets
+                            .into_iter()
+                            .map(|(module_id, function_name, offsets)| Breakpoint {
+                                id,
+                                module_id,
+                                function_name,
+                                offsets,
+                            }),
+                    );
+                    responses.push(json!({
+                        "id": id,
+                        "verified": true,
+                        "line": line + self.line_base,
+                    }));
+                },
+                None => responses.push(json!({
+                    "id": id,
+                    "verified": false,
+                    "message": "No code at or after this line",
+                })),
+            }
+        }
+        if let Some(path) = args.source.path {
+            self.breakpoints.lock().unwrap().set_file(path, bound);
+        }
+        json!({ "breakpoints": responses })
+    }
+
+    fn set_function_breakpoints(&mut self, args: SetFunctionBreakpointsArguments) -> Value {
+        let mut bound = vec![];
+        let mut responses = vec![];
+        for breakpoint in args.breakpoints {
+            let id = self.next_breakpoint_id;
+            self.next_breakpoint_id += 1;
+            match parse_function_breakpoint(&self.sources, &breakpoint.name) {
+                Ok((module_id, function_name, offset)) => {
+                    let location = self.sources.location(&module_id, &function_name, offset);
+                    let mut response = json!({ "id": id, "verified": true });
+                    if let Some(location) = location {
+                        response["source"] = json!({ "path": location.path });
+                        response["line"] = json!(location.line + self.line_base);
+                    }
+                    responses.push(response);
+                    bound.push(Breakpoint {
+                        id,
+                        module_id,
+                        function_name,
+                        offsets: BTreeSet::from([offset]),
+                    });
+                },
+                Err(err) => responses.push(json!({
+                    "id": id,
+                    "verified": false,
+                    "message": err.to_string(),
+                })),
+            }
+        }
+        self.breakpoints.lock().unwrap().set_functions(bound);
+        json!({ "breakpoints": responses })
+    }
+
+    fn paused(&self) -> Result<&Snapshot> {
+        self.snapshot
+            .as_ref()
+            .ok_or_else(|| anyhow!("The program is not paused"))
+    }
+
+    /// Returns the frame `frame_id`, counting from the innermost frame.
+    fn frame(&self, frame_id: usize) -> Result<&DebugFrame> {
+        self.paused()?
+            .frames
+            .iter()
+            .rev()
+            .nth(frame_id)
+            .ok_or_else(|| anyhow!("Unknown frame {}", frame_id))
+    }
+
+    fn stack_trace(&self) -> Result<Value> {
+        let snapshot = self.paused()?;
+        let frames = snapshot
+            .frames
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(frame_id, frame)| {
+                let mut name = match &frame.module_id {
+                    Some(module_id) => {
+                        format!(
+                            "{}::{}",
+                            module_id.short_str_lossless(),
+                            frame.function_name
+                        )
+                    },
+                    None => frame.function_name.clone(),
+                };
+                if !frame.ty_args.is_empty() {
+                    let ty_args = frame.ty_args.iter().map(|ty_arg| ty_arg.to_string());
+                    name = format!("{}<{}>", name, ty_args.collect::<Vec<_>>().join(", "));
+                }
+                let location = frame.module_id.as_ref().and_then(|module_id| {
+                    self.sources
+                        .location(module_id, &frame.function_name, frame.pc)
+                });
+                match location {
+                    Some(location) => json!({
+                        "id": frame_id,
+                        "name": name,
+                        "source": { "path": location.path },
+                        "line": location.line + self.line_base,
+                        "column": location.column + self.column_base,
+                        "instructionPointerReference": frame.pc.to_string(),
+                    }),
+                    None => json!({
+                        "id": frame_id,
+                        "name": format!("{} [pc {}]", name, frame.pc),
+                        "line": 0,
+                        "column": 0,
+                        "instructionPointerReference": frame.pc.to_string(),
+                        "presentationHint": "subtle",
+                    }),
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+    }
+
+    fn scopes(&self, args: FrameArguments) -> Result<Value> {
+        self.frame(args.frame_id)?;
+        let mut scopes = vec![json!({
+            "name": "Locals",
+            "presentationHint": "locals",
+            "variablesReference": 2 * args.frame_id + 1,
+            "expensive": false,
+        })];
+        if args.frame_id == 0 {
+            scopes.push(json!({
+                "name": "Operand Stack",
+                "variablesReference": OPERAND_STACK_REFERENCE,
+                "expensive": false,
+            }));
+        }
+        Ok(json!({ "scopes": scopes }))
+    }
+
+    fn variables(&self, args: VariablesArguments) -> Result<Value> {
+        let variables = if args.variables_reference == OPERAND_STACK_REFERENCE {
+            self.paused()?
+                .operand_stack
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(idx, value)| {
+                    json!({ "name": format!("[{}]", idx), "value": value, "variablesReference": 0 })
+                })
+                .collect::<Vec<_>>()
+        } else if args.variables_reference % 2 == 1 {
+            let frame = self.frame(args.variables_reference / 2)?;
+            frame
+                .locals
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, value)| {
+                    let value = value.as_ref()?;
+                    let name = frame
+                        .module_id
+                        .as_ref()
+                        .and_then(|module_id| {
+                            self.sources
+                                .local_name(module_id, &frame.function_name, idx)
+                        })
+                        .map_or_else(|| format!("local#{}", idx), str::to_string);
+                    Some(json!({ "name": name, "value": value, "variablesReference": 0 }))
+                })
+                .collect()
+        } else {
+            bail!("Unknown variables reference {}", args.variables_reference)
+        };
+        Ok(json!({ "variables": variables }))
+    }
+
+    fn resume(&mut self, mode: StepMode) -> Result<()> {
+        self.paused()?;
+        let commands = self
+            .commands
+            .as_ref()
+            .ok_or_else(|| anyhow!("The program is not running"))?;
+        commands
+            .send(VmCommand::Resume(mode))
+            .map_err(|_| anyhow!("The program is not running"))?;
+        self.snapshot = None;
+        Ok(())
+    }
+
+    fn evaluate(&self, args: EvaluateArguments) -> Result<Value> {
+        self.paused()?;
+        let (exists, tag, address) = parse_resource_expression(&self.sources, &args.expression)?;
+        let (reply, result) = mpsc::channel();
+        self.commands
+            .as_ref()
+            .ok_or_else(|| anyhow!("The program is not running"))?
+            .send(VmCommand::ReadResource {
+                address,
+                tag,
+                exists,
+                reply,
+            })
+            .map_err(|_| anyhow!("The program is not running"))?;
+        let value = result
+            .recv()
+            .map_err(|_| anyhow!("The program is not running"))?
+            .map_err(|err| anyhow!(err))?;
+        Ok(json!({ "result": value, "variablesReference": 0 }))
+    }
+}
+
+/// Parses `ADDRESS::MODULE::FUNCTION`, optionally followed by `@OFFSET`. The breakpoint is at
+/// the first instruction if no offset is given.
+fn parse_function_breakpoint(
+    sources: &SourceIndex,
+    name: &str,
+) -> Result<(ModuleId, String, CodeOffset)> {
+    let (path, offset) = match name.trim().split_once('@') {
+        Some((path, offset)) => (path, offset.trim().parse::<CodeOffset>()?),
+        None => (name.trim(), 0),
+    };
+    let parts = path.split("::").collect::<Vec<_>>();
+    let (address, module_name, function_name) = match parts.as_slice() {
+        [address, module_name, function_name] => (address, module_name, function_name),
+        _ => bail!("Expected ADDRESS::MODULE::FUNCTION, got '{}'", path),
+    };
+    let address = sources
+        .resolve_address(address)
+        .ok_or_else(|| anyhow!("Unknown address '{}'", address))?;
+    let module_id = ModuleId::new(address, Identifier::new(*module_name)?);
+    let function_name = Identifier::new(*function_name)?.into_string();
+    if sources.has_module(&module_id) && !sources.has_function(&module_id, &function_name) {
+        bail!("Function '{}' not found", path);
+    }
+    Ok((module_id, function_name, offset))
+}
+
+/// Parses `exists<TYPE>(ADDRESS)` or `borrow_global<TYPE>(ADDRESS)`, returning whether the
+/// expression only checks existence.
+fn parse_resource_expression(
+    sources: &SourceIndex,
+    expression: &str,
+) -> Result<(bool, StructTag, AccountAddress)> {
+    let expression = expression.trim();
+    let (exists, rest) = if let Some(rest) = expression.strip_prefix("exists<") {
+        (true, rest)
+    } else if let Some(rest) = expression.strip_prefix("borrow_global<") {
+        (false, rest)
+    } else {
+        bail!("Expected exists<TYPE>(ADDRESS) or borrow_global<TYPE>(ADDRESS)")
+    };
+    let (tag, address) = rest
+        .rsplit_once(">(")
+        .and_then(|(tag, address)| Some((tag, address.strip_suffix(')')?)))
+        .ok_or_else(|| anyhow!("Expected exists<TYPE>(ADDRESS) or borrow_global<TYPE>(ADDRESS)"))?;
+    let address = address.trim();
+    let address = address.strip_prefix('@').unwrap_or(address);
+    let address = sources
+        .resolve_address(address)
+        .ok_or_else(|| anyhow!("Unknown address '{}'", address))?;
+    Ok((exists, parse_struct_tag(tag.trim())?, address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_function_breakpoint() {
+        let mut sources = SourceIndex::default();
+        sources.add_named_address("std", AccountAddress::ONE);
+
+        let (module_id, function_name, offset) =
+            parse_function_breakpoint(&sources, "0xcafe::counter::increment").unwrap();
+        assert_eq!(
+            module_id,
+            ModuleId::new(
+                AccountAddress::from_hex_literal("0xcafe").unwrap(),
+                Identifier::new("counter").unwrap()
+            )
+        );
+        assert_eq!(function_name, "increment");
+        assert_eq!(offset, 0);
+
+        let (module_id, function_name, offset) =
+            parse_function_breakpoint(&sources, "std::vector::push_back@3").unwrap();
+        assert_eq!(module_id.address(), &AccountAddress::ONE);
+        assert_eq!(function_name, "push_back");
+        assert_eq!(offset, 3);
+
+        assert!(parse_function_breakpoint(&sources, "vector::push_back").is_err());
+        assert!(parse_function_breakpoint(&sources, "unknown::vector::push_back").is_err());
+    }
+
+    #[test]
+    fn test_parse_resource_expression() {
+        let mut sources = SourceIndex::default();
+        sources.add_named_address("admin", AccountAddress::TWO);
+
+        let (exists, tag, address) = parse_resource_expression(
+            &sources,
+            "borrow_global<0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>>(@0xcafe)",
+        )
+        .unwrap();
+        assert!(!exists);
+        assert_eq!(
+            tag.to_string(),
+            parse_struct_tag("0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>")
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(address, AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+        let (exists, _, address) =
+            parse_resource_expression(&sources, "exists<0x1::account::Account>(admin)").unwrap();
+        assert!(exists);
+        assert_eq!(address, AccountAddress::TWO);
+
+        assert!(
+            parse_resource_expression(&sources, "global<0x1::account::Account>(@0x1)").is_err()
+        );
+    }
+}
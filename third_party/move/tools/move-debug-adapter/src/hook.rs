@@ -0,0 +1,292 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The side of the adapter running on the thread of the VM: decides where execution pauses and
+//! serves the requests of the adapter while it is paused.
+
+use crate::{
+    adapter::Message,
+    source_index::{SourceIndex, SourceLocation},
+};
+use move_binary_format::file_format::CodeOffset;
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, StructTag},
+};
+use move_resource_viewer::MoveValueAnnotator;
+use move_vm_runtime::debug::{DebugFrame, DebugHook, DebugLocation, DebugResource, DebugState};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, Sender},
+        Arc, Mutex,
+    },
+};
+
+/// A breakpoint bound to code.
+#[derive(Clone, Debug)]
+pub(crate) struct Breakpoint {
+    pub id: i64,
+    pub module_id: ModuleId,
+    pub function_name: String,
+    pub offsets: BTreeSet<CodeOffset>,
+}
+
+/// The breakpoints set by the client, shared between the adapter and the hook.
+#[derive(Default)]
+pub(crate) struct Breakpoints {
+    by_file: BTreeMap<PathBuf, Vec<Breakpoint>>,
+    functions: Vec<Breakpoint>,
+    /// Identifiers of the breakpoints at each code offset, for fast lookups.
+    index: HashMap<ModuleId, HashMap<String, BTreeMap<CodeOffset, Vec<i64>>>>,
+}
+
+impl Breakpoints {
+    /// Replaces the breakpoints of the source file at `path`.
+    pub fn set_file(&mut self, path: PathBuf, breakpoints: Vec<Breakpoint>) {
+        self.by_file.insert(path, breakpoints);
+        self.rebuild_index();
+    }
+
+    /// Replaces the function breakpoints.
+    pub fn set_functions(&mut self, breakpoints: Vec<Breakpoint>) {
+        self.functions = breakpoints;
+        self.rebuild_index();
+    }
+
+    pub fn clear(&mut self) {
+        self.by_file.clear();
+        self.functions.clear();
+        self.index.clear();
+    }
+
+    fn rebuild_index(&mut self) {
+        self.index.clear();
+        for breakpoint in self.by_file.values().flatten().chain(&self.functions) {
+            let offsets = self
+                .index
+                .entry(breakpoint.module_id.clone())
+                .or_default()
+                .entry(breakpoint.function_name.clone())
+                .or_default();
+            for offset in &breakpoint.offsets {
+                offsets.entry(*offset).or_default().push(breakpoint.id);
+            }
+        }
+    }
+
+    fn hits(&self, location: &DebugLocation) -> Option<&Vec<i64>> {
+        self.index
+            .get(location.module_id?)?
+            .get(location.function_name)?
+            .get(&location.pc)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum StepMode {
+    /// Pause before the first instruction.
+    Entry,
+    Continue,
+    /// Pause before the next instruction.
+    Instruction,
+    /// Pause on the next line, entering calls.
+    In,
+    /// Pause on the next line of the current function or of its callers.
+    Over,
+    /// Pause once the current function returned.
+    Out,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum StopReason {
+    Entry,
+    Breakpoint(Vec<i64>),
+    Step,
+    Pause,
+}
+
+/// State of the VM when it paused.
+pub(crate) struct Snapshot {
+    pub reason: StopReason,
+    /// Frames of the call stack, outermost first.
+    pub frames: Vec<DebugFrame>,
+    pub operand_stack: Vec<String>,
+    pub instruction: String,
+}
+
+/// Requests of the adapter to the paused VM.
+pub(crate) enum VmCommand {
+    Resume(StepMode),
+    /// Reads a resource of global storage, or only checks its existence if `exists` is set.
+    ReadResource {
+        address: AccountAddress,
+        tag: StructTag,
+        exists: bool,
+        reply: Sender<Result<String, String>>,
+    },
+}
+
+/// Where the last step started.
+struct StepOrigin {
+    mode: StepMode,
+    depth: usize,
+    location: Option<SourceLocation>,
+}
+
+pub(crate) struct AdapterHook {
+    sources: Arc<SourceIndex>,
+    breakpoints: Arc<Mutex<Breakpoints>>,
+    pause_requested: Arc<AtomicBool>,
+    messages: Sender<Message>,
+    commands: Receiver<VmCommand>,
+    step: StepOrigin,
+    stop_reason: Option<StopReason>,
+}
+
+impl AdapterHook {
+    pub fn new(
+        sources: Arc<SourceIndex>,
+        breakpoints: Arc<Mutex<Breakpoints>>,
+        pause_requested: Arc<AtomicBool>,
+        messages: Sender<Message>,
+        commands: Receiver<VmCommand>,
+        stop_on_entry: bool,
+    ) -> Self {
+        Self {
+            sources,
+            breakpoints,
+            pause_requested,
+            messages,
+            commands,
+            step: StepOrigin {
+                mode: if stop_on_entry {
+                    StepMode::Entry
+                } else {
+                    StepMode::Continue
+                },
+                depth: 0,
+                location: None,
+            },
+            stop_reason: None,
+        }
+    }
+
+    fn source_location(&self, location: &DebugLocation) -> Option<SourceLocation> {
+        self.sources
+            .location(location.module_id?, location.function_name, location.pc)
+    }
+
+    /// Returns whether a source level step ends at `location`. Code without sources is stepped
+    /// through, unless the step started in code without sources, in which case stepping is per
+    /// instruction.
+    fn is_new_line(&self, location: &DebugLocation) -> bool {
+        let origin = match &self.step.location {
+            Some(origin) => origin,
+            None => return true,
+        };
+        match self.source_location(location) {
+            Some(current) => {
+                location.depth != self.step.depth
+                    || current.path != origin.path
+                    || current.line != origin.line
+            },
+            None => false,
+        }
+    }
+
+    fn read_resource(
+        state: &DebugState,
+        address: AccountAddress,
+        tag: &StructTag,
+        exists: bool,
+    ) -> Result<String, String> {
+        let resource = state
+            .resource(address, tag)
+            .map_err(|err| format!("{:?}", err))?;
+        if exists {
+            let exists = match resource {
+                DebugResource::Cached(value) => value.is_some(),
+                DebugResource::Stored(blob) => blob.is_some(),
+            };
+            return Ok(exists.to_string());
+        }
+        match resource {
+            DebugResource::Cached(Some(value)) => Ok(value),
+            DebugResource::Stored(Some(blob)) => MoveValueAnnotator::new(state.storage())
+                .view_resource(tag, &blob)
+                .map(|resource| resource.to_string())
+                .map_err(|err| err.to_string()),
+            DebugResource::Cached(None) | DebugResource::Stored(None) => Err(format!(
+                "Resource {} does not exist at {}",
+                tag,
+                address.to_hex_literal()
+            )),
+        }
+    }
+}
+
+impl DebugHook for AdapterHook {
+    fn should_pause(&mut self, location: &DebugLocation) -> bool {
+        self.stop_reason = if self.pause_requested.swap(false, Ordering::Relaxed) {
+            Some(StopReason::Pause)
+        } else if let Some(ids) = self.breakpoints.lock().unwrap().hits(location) {
+            Some(StopReason::Breakpoint(ids.clone()))
+        } else {
+            let step_done = match self.step.mode {
+                StepMode::Entry => true,
+                StepMode::Continue => false,
+                StepMode::Instruction => true,
+                StepMode::In => self.is_new_line(location),
+                StepMode::Over => location.depth <= self.step.depth && self.is_new_line(location),
+                StepMode::Out => location.depth < self.step.depth,
+            };
+            if !step_done {
+                None
+            } else if self.step.mode == StepMode::Entry {
+                Some(StopReason::Entry)
+            } else {
+                Some(StopReason::Step)
+            }
+        };
+        self.stop_reason.is_some()
+    }
+
+    fn on_pause(&mut self, state: &DebugState) {
+        let snapshot = Snapshot {
+            reason: self.stop_reason.take().unwrap_or(StopReason::Pause),
+            frames: state.call_stack().unwrap_or_default(),
+            operand_stack: state.operand_stack().unwrap_or_default(),
+            instruction: format!("{:?}", state.instruction()),
+        };
+        let mut mode = StepMode::Continue;
+        if self.messages.send(Message::Stopped(snapshot)).is_ok() {
+            // A closed channel means the adapter is gone, in which case execution continues.
+            while let Ok(command) = self.commands.recv() {
+                match command {
+                    VmCommand::Resume(step_mode) => {
+                        mode = step_mode;
+                        break;
+                    },
+                    VmCommand::ReadResource {
+                        address,
+                        tag,
+                        exists,
+                        reply,
+                    } => {
+                        let _ = reply.send(Self::read_resource(state, address, &tag, exists));
+                    },
+                }
+            }
+        }
+
+        let location = state.location();
+        self.step = StepOrigin {
+            mode,
+            depth: location.depth,
+            location: self.source_location(&location),
+        };
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/) server which
+//! lets editors debug Move code executed by the VM: breakpoints on source lines, functions and
+//! bytecode offsets, stepping, and inspection of locals, the operand stack and global storage.
+
+mod adapter;
+mod hook;
+mod protocol;
+mod source_index;
+
+pub use adapter::DebugAdapter;
+pub use source_index::SourceIndex;
@@ -0,0 +1,159 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Wire format of the Debug Adapter Protocol: JSON messages preceded by a `Content-Length`
+//! header. Only the fields used by the adapter are modeled, everything else is kept as raw JSON.
+
+use anyhow::{anyhow, bail, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub seq: i64,
+    pub command: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OutgoingMessage {
+    Response {
+        seq: i64,
+        request_seq: i64,
+        success: bool,
+        command: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+        #[serde(skip_serializing_if = "Value::is_null")]
+        body: Value,
+    },
+    Event {
+        seq: i64,
+        event: String,
+        #[serde(skip_serializing_if = "Value::is_null")]
+        body: Value,
+    },
+}
+
+/// Reads the next message, or returns `None` once the client closed the connection.
+pub fn read_request(input: &mut impl BufRead) -> Result<Option<Request>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(value.trim().parse::<usize>()?);
+            }
+        }
+    }
+    let content_length =
+        content_length.ok_or_else(|| anyhow!("Message without a Content-Length header"))?;
+    let mut content = vec![0; content_length];
+    input.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Writes messages to the client, numbering them in order.
+pub struct MessageWriter<W> {
+    output: W,
+    seq: i64,
+}
+
+impl<W: Write> MessageWriter<W> {
+    pub fn new(output: W) -> Self {
+        Self { output, seq: 0 }
+    }
+
+    pub fn respond(&mut self, request: &Request, body: Value) -> Result<()> {
+        self.seq += 1;
+        self.write(&OutgoingMessage::Response {
+            seq: self.seq,
+            request_seq: request.seq,
+            success: true,
+            command: request.command.clone(),
+            message: None,
+            body,
+        })
+    }
+
+    pub fn respond_error(&mut self, request: &Request, message: String) -> Result<()> {
+        self.seq += 1;
+        self.write(&OutgoingMessage::Response {
+            seq: self.seq,
+            request_seq: request.seq,
+            success: false,
+            command: request.command.clone(),
+            message: Some(message),
+            body: Value::Null,
+        })
+    }
+
+    pub fn send_event(&mut self, event: &str, body: Value) -> Result<()> {
+        self.seq += 1;
+        self.write(&OutgoingMessage::Event {
+            seq: self.seq,
+            event: event.to_string(),
+            body,
+        })
+    }
+
+    fn write(&mut self, message: &OutgoingMessage) -> Result<()> {
+        let content = serde_json::to_vec(message)?;
+        write!(self.output, "Content-Length: {}\r\n\r\n", content.len())?;
+        self.output.write_all(&content)?;
+        self.output.flush()?;
+        Ok(())
+    }
+}
+
+/// Deserializes the arguments of `request`, which may be omitted if all are optional.
+pub fn arguments<T: DeserializeOwned>(request: &Request) -> Result<T> {
+    let arguments = match &request.arguments {
+        Value::Null => Value::Object(Default::default()),
+        arguments => arguments.clone(),
+    };
+    match serde_json::from_value(arguments) {
+        Ok(arguments) => Ok(arguments),
+        Err(err) => bail!("Invalid arguments for '{}': {}", request.command, err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_round_trip() {
+        let content = r#"{"seq":3,"type":"request","command":"threads"}"#;
+        let input = format!("Content-Length: {}\r\n\r\n{}", content.len(), content);
+        let request = read_request(&mut input.as_bytes()).unwrap().unwrap();
+        assert_eq!(request.seq, 3);
+        assert_eq!(request.command, "threads");
+
+        let mut writer = MessageWriter::new(vec![]);
+        writer.respond(&request, json!({ "threads": [] })).unwrap();
+        let output = String::from_utf8(writer.output).unwrap();
+        let (header, body) = output.split_once("\r\n\r\n").unwrap();
+        assert_eq!(header, format!("Content-Length: {}", body.len()));
+        let body: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["type"], "response");
+        assert_eq!(body["request_seq"], 3);
+        assert_eq!(body["success"], true);
+    }
+
+    #[test]
+    fn test_closed_connection() {
+        assert!(read_request(&mut "".as_bytes()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{CodeOffset, FunctionDefinitionIndex},
+};
+use move_command_line_common::files::FileHash;
+use move_compiler::compiled_unit::CompiledUnit;
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use move_ir_types::location::Loc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    ops::Bound,
+    path::{Path, PathBuf},
+};
+
+/// A position in a source file. Lines and columns start at 0.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SourceLocation {
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+struct SourceFile {
+    path: PathBuf,
+    /// Byte offsets at which the lines of the file start.
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    fn new(path: PathBuf, contents: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(contents.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Self { path, line_starts }
+    }
+
+    fn line_and_column(&self, byte_index: usize) -> (usize, usize) {
+        let line = self
+            .line_starts
+            .partition_point(|start| *start <= byte_index)
+            - 1;
+        (line, byte_index - self.line_starts[line])
+    }
+}
+
+struct FunctionInfo {
+    definition_location: Loc,
+    code_map: BTreeMap<CodeOffset, Loc>,
+    /// Names of the parameters followed by the names of the locals.
+    local_names: Vec<String>,
+}
+
+/// Maps the bytecode executed by the VM back to the Move sources it was compiled from, using the
+/// source maps produced by the compiler.
+#[derive(Default)]
+pub struct SourceIndex {
+    files: BTreeMap<FileHash, SourceFile>,
+    modules: BTreeMap<ModuleId, BTreeMap<String, FunctionInfo>>,
+    named_addresses: BTreeMap<String, AccountAddress>,
+}
+
+impl SourceIndex {
+    /// Indexes a module compiled from `source_path`. Scripts are ignored.
+    pub fn add_compiled_unit(&mut self, unit: &CompiledUnit, source_path: &Path) -> Result<()> {
+        let unit = match unit {
+            CompiledUnit::Module(unit) => unit,
+            CompiledUnit::Script(_) => return Ok(()),
+        };
+        let contents = fs::read_to_string(source_path)
+            .with_context(|| format!("Failed to read {}", source_path.display()))?;
+        let path = source_path
+            .canonicalize()
+            .unwrap_or_else(|_| source_path.to_path_buf());
+        self.files
+            .insert(FileHash::new(&contents), SourceFile::new(path, &contents));
+
+        let module = &unit.module;
+        let mut functions = BTreeMap::new();
+        for (idx, function_def) in module.function_defs().iter().enumerate() {
+            let name = module.identifier_at(module.function_handle_at(function_def.function).name);
+            let function_map = unit
+                .source_map
+                .get_function_source_map(FunctionDefinitionIndex(idx as u16))?;
+            functions.insert(name.to_string(), FunctionInfo {
+                definition_location: function_map.definition_location,
+                code_map: function_map.code_map.clone(),
+                local_names: function_map
+                    .parameters
+                    .iter()
+                    .chain(&function_map.locals)
+                    .map(|(name, _)| name.clone())
+                    .collect(),
+            });
+        }
+        self.modules.insert(module.self_id(), functions);
+        Ok(())
+    }
+
+    pub fn add_named_address(&mut self, name: impl Into<String>, address: AccountAddress) {
+        self.named_addresses.insert(name.into(), address);
+    }
+
+    /// Resolves a named address or an address literal.
+    pub(crate) fn resolve_address(&self, address: &str) -> Option<AccountAddress> {
+        self.named_addresses
+            .get(address)
+            .copied()
+            .or_else(|| AccountAddress::from_hex_literal(address).ok())
+    }
+
+    pub(crate) fn has_module(&self, module_id: &ModuleId) -> bool {
+        self.modules.contains_key(module_id)
+    }
+
+    pub(crate) fn has_function(&self, module_id: &ModuleId, function_name: &str) -> bool {
+        self.function(module_id, function_name).is_some()
+    }
+
+    /// Returns the source location of the instruction at `pc`.
+    pub(crate) fn location(
+        &self,
+        module_id: &ModuleId,
+        function_name: &str,
+        pc: CodeOffset,
+    ) -> Option<SourceLocation> {
+        let function = self.function(module_id, function_name)?;
+        let (_, loc) = function
+            .code_map
+            .range((Bound::Unbounded, Bound::Included(pc)))
+            .next_back()?;
+        self.resolve(*loc)
+    }
+
+    /// Returns the name of the parameter or local at `idx`.
+    pub(crate) fn local_name(
+        &self,
+        module_id: &ModuleId,
+        function_name: &str,
+        idx: usize,
+    ) -> Option<&str> {
+        self.function(module_id, function_name)?
+            .local_names
+            .get(idx)
+            .map(String::as_str)
+    }
+
+    /// Binds a breakpoint set on `line` of the file at `path` to code. If `line` has no code, the
+    /// breakpoint moves to the next line of the same function which has some. Returns the line
+    /// the breakpoint was bound to and the code offsets at which it stops, per function.
+    pub(crate) fn line_breakpoint(
+        &self,
+        path: &Path,
+        line: usize,
+    ) -> Option<(usize, Vec<(ModuleId, String, BTreeSet<CodeOffset>)>)> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let (file_hash, file) = self.files.iter().find(|(_, file)| file.path == path)?;
+
+        let mut bound_line = None;
+        let mut offsets_by_line = BTreeMap::new();
+        for (module_id, functions) in &self.modules {
+            for (function_name, function) in functions {
+                let definition = function.definition_location;
+                if definition.file_hash() != *file_hash {
+                    continue;
+                }
+                let (first_line, _) = file.line_and_column(definition.start() as usize);
+                let (last_line, _) = file.line_and_column(definition.end() as usize);
+                if line < first_line || line > last_line {
+                    continue;
+                }
+                for (offset, loc) in &function.code_map {
+                    if loc.file_hash() != *file_hash {
+                        continue;
+                    }
+                    let (loc_line, _) = file.line_and_column(loc.start() as usize);
+                    if loc_line < line {
+                        continue;
+                    }
+                    bound_line =
+                        Some(bound_line.map_or(loc_line, |bound: usize| bound.min(loc_line)));
+                    offsets_by_line
+                        .entry((loc_line, module_id, function_name))
+                        .or_insert_with(BTreeSet::new)
+                        .insert(*offset);
+                }
+            }
+        }
+
+        let bound_line = bound_line?;
+        let targets = offsets_by_line
+            .into_iter()
+            .filter(|((loc_line, _, _), _)| *loc_line == bound_line)
+            .map(|((_, module_id, function_name), offsets)| {
+                (module_id.clone(), function_name.clone(), offsets)
+            })
+            .collect();
+        Some((bound_line, targets))
+    }
+
+    fn function(&self, module_id: &ModuleId, function_name: &str) -> Option<&FunctionInfo> {
+        self.modules.get(module_id)?.get(function_name)
+    }
+
+    fn resolve(&self, loc: Loc) -> Option<SourceLocation> {
+        let file = self.files.get(&loc.file_hash())?;
+        let (line, column) = file.line_and_column(loc.start() as usize);
+        Some(SourceLocation {
+            path: file.path.clone(),
+            line,
+            column,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{
+    account_address::AccountAddress,
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+};
+use serde::{Deserialize, Serialize};
+
+/// The Move function an account using account abstraction registered to authenticate its
+/// transactions.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuthenticationFunctionResource {
+    module_address: AccountAddress,
+    module_name: String,
+    function_name: String,
+}
+
+impl AuthenticationFunctionResource {
+    pub fn new(module_address: AccountAddress, module_name: String, function_name: String) -> Self {
+        Self {
+            module_address,
+            module_name,
+            function_name,
+        }
+    }
+
+    pub fn module_address(&self) -> AccountAddress {
+        self.module_address
+    }
+
+    pub fn module_name(&self) -> &str {
+        &self.module_name
+    }
+
+    pub fn function_name(&self) -> &str {
+        &self.function_name
+    }
+}
+
+impl MoveStructType for AuthenticationFunctionResource {
+    const MODULE_NAME: &'static IdentStr = ident_str!("account_abstraction");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("AuthenticationFunction");
+}
+
+impl MoveResource for AuthenticationFunctionResource {}
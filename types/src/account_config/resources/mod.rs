@@ -2,12 +2,14 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod authentication_function;
 pub mod chain_id;
 pub mod coin_info;
 pub mod coin_store;
 pub mod core_account;
 pub mod object;
 
+pub use authentication_function::*;
 pub use chain_id::*;
 pub use coin_info::*;
 pub use coin_store::*;
@@ -13,15 +13,16 @@ use move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, ops::Deref};
+use std::convert::TryFrom;
 
 /// This trait is used by block executor to abstractly represent an event.
 /// Block executor uses `get_event_data` to get the event data.
 /// Block executor then checks for the occurences of aggregators and aggregatorsnapshots
 /// in the event data, processes them, and calls `update_event_data` to update the event data.
 pub trait ReadWriteEvent {
-    /// Returns the event data.
-    fn get_event_data(&self) -> (EventKey, u64, &TypeTag, &[u8]);
+    /// Returns the event data. The key and sequence number are only set for events emitted to
+    /// an event handle.
+    fn get_event_data(&self) -> (Option<(EventKey, u64)>, &TypeTag, &[u8]);
     /// Updates the event data.
     fn update_event_data(&mut self, event_data: Vec<u8>);
 }
@@ -29,24 +30,28 @@ pub trait ReadWriteEvent {
 /// Support versioning of the data structure.
 #[derive(Hash, Clone, Eq, PartialEq, Serialize, Deserialize, CryptoHasher, BCSCryptoHash)]
 pub enum ContractEvent {
+    /// An event emitted to an event handle
     V0(ContractEventV0),
+    /// A module event, i.e., an event identified by its type only
+    V1(ContractEventV1),
 }
 
 impl ReadWriteEvent for ContractEvent {
-    fn get_event_data(&self) -> (EventKey, u64, &TypeTag, &[u8]) {
+    fn get_event_data(&self) -> (Option<(EventKey, u64)>, &TypeTag, &[u8]) {
         match self {
             ContractEvent::V0(event) => (
-                *event.key(),
-                event.sequence_number(),
+                Some((*event.key(), event.sequence_number())),
                 event.type_tag(),
                 event.event_data(),
             ),
+            ContractEvent::V1(event) => (None, event.type_tag(), event.event_data()),
         }
     }
 
     fn update_event_data(&mut self, event_data: Vec<u8>) {
         match self {
             ContractEvent::V0(event) => event.event_data = event_data,
+            ContractEvent::V1(event) => event.event_data = event_data,
         }
     }
 }
@@ -65,16 +70,49 @@ impl ContractEvent {
             event_data,
         ))
     }
-}
 
-// Temporary hack to avoid massive changes, it won't work when new variant comes and needs proper
-// dispatch at that time.
-impl Deref for ContractEvent {
-    type Target = ContractEventV0;
+    pub fn new_module_event(type_tag: TypeTag, event_data: Vec<u8>) -> Self {
+        ContractEvent::V1(ContractEventV1::new(type_tag, event_data))
+    }
+
+    pub fn is_v0(&self) -> bool {
+        matches!(self, ContractEvent::V0(_))
+    }
 
-    fn deref(&self) -> &Self::Target {
+    /// Returns the event emitted to an event handle, or an error for module events.
+    pub fn v0(&self) -> Result<&ContractEventV0> {
         match self {
-            ContractEvent::V0(event) => event,
+            ContractEvent::V0(event) => Ok(event),
+            ContractEvent::V1(_) => anyhow::bail!("This is a module event"),
+        }
+    }
+
+    /// Returns the key of the event handle the event was emitted to, if any.
+    pub fn event_key(&self) -> Option<&EventKey> {
+        match self {
+            ContractEvent::V0(event) => Some(event.key()),
+            ContractEvent::V1(_) => None,
+        }
+    }
+
+    pub fn type_tag(&self) -> &TypeTag {
+        match self {
+            ContractEvent::V0(event) => event.type_tag(),
+            ContractEvent::V1(event) => event.type_tag(),
+        }
+    }
+
+    pub fn event_data(&self) -> &[u8] {
+        match self {
+            ContractEvent::V0(event) => event.event_data(),
+            ContractEvent::V1(event) => event.event_data(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        match self {
+            ContractEvent::V0(event) => event.size(),
+            ContractEvent::V1(event) => event.size(),
         }
     }
 }
@@ -129,14 +167,45 @@ impl ContractEventV0 {
     }
 }
 
+/// Entry produced via a call to the `emit` builtin. Module events are keyed by their type.
+#[derive(Hash, Clone, Eq, PartialEq, Serialize, Deserialize, CryptoHasher)]
+pub struct ContractEventV1 {
+    /// The type of the data
+    type_tag: TypeTag,
+    /// The data payload of the event
+    #[serde(with = "serde_bytes")]
+    event_data: Vec<u8>,
+}
+
+impl ContractEventV1 {
+    pub fn new(type_tag: TypeTag, event_data: Vec<u8>) -> Self {
+        Self {
+            type_tag,
+            event_data,
+        }
+    }
+
+    pub fn event_data(&self) -> &[u8] {
+        &self.event_data
+    }
+
+    pub fn type_tag(&self) -> &TypeTag {
+        &self.type_tag
+    }
+
+    pub fn size(&self) -> usize {
+        bcs::to_bytes(&self.type_tag).unwrap().len() + self.event_data.len()
+    }
+}
+
 impl TryFrom<&ContractEvent> for NewBlockEvent {
     type Error = Error;
 
     fn try_from(event: &ContractEvent) -> Result<Self> {
-        if event.type_tag != TypeTag::Struct(Box::new(Self::struct_tag())) {
+        if event.type_tag() != &TypeTag::Struct(Box::new(Self::struct_tag())) {
             anyhow::bail!("Expected NewBlockEvent")
         }
-        Self::try_from_bytes(&event.event_data)
+        Self::try_from_bytes(event.event_data())
     }
 }
 
@@ -144,10 +213,10 @@ impl TryFrom<&ContractEvent> for NewEpochEvent {
     type Error = Error;
 
     fn try_from(event: &ContractEvent) -> Result<Self> {
-        if event.type_tag != TypeTag::Struct(Box::new(Self::struct_tag())) {
+        if event.type_tag() != &TypeTag::Struct(Box::new(Self::struct_tag())) {
             anyhow::bail!("Expected NewEpochEvent")
         }
-        Self::try_from_bytes(&event.event_data)
+        Self::try_from_bytes(event.event_data())
     }
 }
 
@@ -155,10 +224,10 @@ impl TryFrom<&ContractEvent> for WithdrawEvent {
     type Error = Error;
 
     fn try_from(event: &ContractEvent) -> Result<Self> {
-        if event.type_tag != TypeTag::Struct(Box::new(WithdrawEvent::struct_tag())) {
+        if event.type_tag() != &TypeTag::Struct(Box::new(WithdrawEvent::struct_tag())) {
             anyhow::bail!("Expected Sent Payment")
         }
-        Self::try_from_bytes(&event.event_data)
+        Self::try_from_bytes(event.event_data())
     }
 }
 
@@ -166,39 +235,51 @@ impl TryFrom<&ContractEvent> for DepositEvent {
     type Error = Error;
 
     fn try_from(event: &ContractEvent) -> Result<Self> {
-        if event.type_tag != TypeTag::Struct(Box::new(DepositEvent::struct_tag())) {
+        if event.type_tag() != &TypeTag::Struct(Box::new(DepositEvent::struct_tag())) {
             anyhow::bail!("Expected Received Payment")
         }
-        Self::try_from_bytes(&event.event_data)
+        Self::try_from_bytes(event.event_data())
     }
 }
 
 impl std::fmt::Debug for ContractEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "ContractEvent {{ key: {:?}, index: {:?}, type: {:?}, event_data: {:?} }}",
-            self.key,
-            self.sequence_number,
-            self.type_tag,
-            hex::encode(&self.event_data)
-        )
+        match self {
+            ContractEvent::V0(event) => write!(
+                f,
+                "ContractEvent {{ key: {:?}, index: {:?}, type: {:?}, event_data: {:?} }}",
+                event.key,
+                event.sequence_number,
+                event.type_tag,
+                hex::encode(&event.event_data)
+            ),
+            ContractEvent::V1(event) => write!(
+                f,
+                "ModuleEvent {{ type: {:?}, event_data: {:?} }}",
+                event.type_tag,
+                hex::encode(&event.event_data)
+            ),
+        }
     }
 }
 
 impl std::fmt::Display for ContractEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let event = match self {
+            ContractEvent::V0(event) => event,
+            ContractEvent::V1(_) => return write!(f, "{:?}", self),
+        };
         if let Ok(payload) = WithdrawEvent::try_from(self) {
             write!(
                 f,
                 "ContractEvent {{ key: {}, index: {:?}, type: {:?}, event_data: {:?} }}",
-                self.key, self.sequence_number, self.type_tag, payload,
+                event.key, event.sequence_number, event.type_tag, payload,
             )
         } else if let Ok(payload) = DepositEvent::try_from(self) {
             write!(
                 f,
                 "ContractEvent {{ key: {}, index: {:?}, type: {:?}, event_data: {:?} }}",
-                self.key, self.sequence_number, self.type_tag, payload,
+                event.key, event.sequence_number, event.type_tag, payload,
             )
         } else {
             write!(f, "{:?}", self)
@@ -32,6 +32,13 @@ pub enum FeatureFlag {
     GAS_PAYER_ENABLED = 22,
     APTOS_UNIQUE_IDENTIFIERS = 23,
     BULLETPROOFS_NATIVES = 24,
+    MODULE_EVENT = 25,
+    BN254_STRUCTURES = 26,
+    POSEIDON_NATIVES = 27,
+    WEBAUTHN_SIGNATURE = 28,
+    SECP256K1_ECDSA_AUTHENTICATOR = 29,
+    TRANSACTION_CONTEXT_EXTENSION = 30,
+    ACCOUNT_ABSTRACTION = 31,
 }
 
 /// Representation of features on chain as a bitset.
@@ -62,6 +69,16 @@ impl Features {
         byte_index < self.features.len() && (self.features[byte_index] & bit_mask != 0)
     }
 
+    pub fn enable(&mut self, flag: FeatureFlag) {
+        let val = flag as u64;
+        let byte_index = (val / 8) as usize;
+        let bit_mask = 1 << (val % 8);
+        if byte_index >= self.features.len() {
+            self.features.resize(byte_index + 1, 0);
+        }
+        self.features[byte_index] |= bit_mask;
+    }
+
     pub fn are_resource_groups_enabled(&self) -> bool {
         self.is_enabled(FeatureFlag::RESOURCE_GROUPS)
     }
@@ -373,6 +373,307 @@ impl SparseMerkleProof {
     }
 }
 
+/// A proof that can be used to authenticate multiple elements in a Sparse Merkle Tree against a
+/// single trusted root hash. Compared to a list of `SparseMerkleProof`s, siblings that are shared
+/// by the paths of several keys, or that can be computed from the other proven elements, are only
+/// included once (or not at all).
+///
+/// The proof is laid out for the proven keys in ascending order. Verification walks the tree from
+/// the root, splitting the keys by their bit at each level; whenever one side of a split has no
+/// proven key, its hash is taken from `siblings`, in the order of a depth-first, left-to-right
+/// traversal.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SparseMerkleMultiProof {
+    /// One entry for each proven key, in ascending key order. See `SparseMerkleMultiProofLeaf`.
+    leaves: Vec<SparseMerkleMultiProofLeaf>,
+
+    /// Sibling hashes that can't be derived from the proven elements, in the order they are
+    /// consumed by a depth-first, left-to-right traversal of the tree.
+    siblings: Vec<HashValue>,
+}
+
+/// Where the search for a single key in a `SparseMerkleMultiProof` terminates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SparseMerkleMultiProofLeaf {
+    /// The depth of the position where the search terminates, i.e. the number of siblings the
+    /// equivalent `SparseMerkleProof` would have.
+    depth: u16,
+    /// Has the same meaning as `SparseMerkleProof::leaf`.
+    leaf: Option<SparseMerkleLeafNode>,
+}
+
+impl SparseMerkleMultiProofLeaf {
+    pub fn new(depth: u16, leaf: Option<SparseMerkleLeafNode>) -> Self {
+        Self { depth, leaf }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth as usize
+    }
+
+    pub fn leaf(&self) -> Option<SparseMerkleLeafNode> {
+        self.leaf
+    }
+}
+
+impl SparseMerkleMultiProof {
+    /// Constructs a new `SparseMerkleMultiProof` using the per key leaves and a list of siblings.
+    pub fn new(leaves: Vec<SparseMerkleMultiProofLeaf>, siblings: Vec<HashValue>) -> Self {
+        Self { leaves, siblings }
+    }
+
+    /// Combines single key proofs into a multi-proof. `keys` must be strictly increasing and
+    /// `proofs[i]` must be a proof for `keys[i]` against the same root hash.
+    pub fn from_proofs(keys: &[HashValue], proofs: &[SparseMerkleProof]) -> Result<Self> {
+        ensure!(
+            keys.len() == proofs.len(),
+            "Number of keys ({}) does not match the number of proofs ({}).",
+            keys.len(),
+            proofs.len(),
+        );
+        ensure_strictly_increasing(keys.iter())?;
+        for proof in proofs {
+            ensure!(
+                proof.siblings().len() <= HashValue::LENGTH_IN_BITS,
+                "Sparse Merkle Tree proof has more than {} ({}) siblings.",
+                HashValue::LENGTH_IN_BITS,
+                proof.siblings().len(),
+            );
+        }
+
+        let leaves = proofs
+            .iter()
+            .map(|proof| {
+                SparseMerkleMultiProofLeaf::new(proof.siblings().len() as u16, proof.leaf())
+            })
+            .collect::<Vec<_>>();
+        let mut siblings = vec![];
+        if !keys.is_empty() {
+            Self::collect_siblings(keys, proofs, 0, &mut siblings)?;
+        }
+
+        Ok(Self { leaves, siblings })
+    }
+
+    /// Mirrors `compute_subtree_hash`: pushes the siblings in the order they will be consumed by
+    /// the verifier.
+    fn collect_siblings(
+        keys: &[HashValue],
+        proofs: &[SparseMerkleProof],
+        depth: usize,
+        siblings: &mut Vec<HashValue>,
+    ) -> Result<()> {
+        if proofs.iter().any(|proof| proof.siblings().len() == depth) {
+            ensure!(
+                proofs.iter().all(
+                    |proof| proof.siblings().len() == depth && proof.leaf() == proofs[0].leaf()
+                ),
+                "Proofs of keys sharing the same {}-bit prefix are inconsistent.",
+                depth,
+            );
+            return Ok(());
+        }
+
+        // None of the proofs terminate at this depth, so each of them has a sibling here.
+        let sibling_at_depth =
+            |proof: &SparseMerkleProof| proof.siblings()[proof.siblings().len() - depth - 1];
+        let split = keys.partition_point(|key| !key.bit(depth));
+        if split == 0 {
+            siblings.push(sibling_at_depth(&proofs[0]));
+        } else {
+            Self::collect_siblings(&keys[..split], &proofs[..split], depth + 1, siblings)?;
+        }
+        if split == keys.len() {
+            siblings.push(sibling_at_depth(&proofs[0]));
+        } else {
+            Self::collect_siblings(&keys[split..], &proofs[split..], depth + 1, siblings)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the per key leaves in this proof.
+    pub fn leaves(&self) -> &[SparseMerkleMultiProofLeaf] {
+        &self.leaves
+    }
+
+    /// Returns the list of siblings in this proof.
+    pub fn siblings(&self) -> &[HashValue] {
+        &self.siblings
+    }
+
+    pub fn verify<V: CryptoHash>(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<&V>)],
+    ) -> Result<()> {
+        let elements_by_hash = elements
+            .iter()
+            .map(|(key, value)| (*key, value.map(|v| v.hash())))
+            .collect::<Vec<_>>();
+        self.verify_by_hash(expected_root_hash, &elements_by_hash)
+    }
+
+    /// Verifies that each of `elements` exists in the Sparse Merkle Tree with the given value
+    /// hash if present, or doesn't exist in the tree otherwise. The keys of `elements` must be
+    /// strictly increasing.
+    pub fn verify_by_hash(
+        &self,
+        expected_root_hash: HashValue,
+        elements: &[(HashValue, Option<HashValue>)],
+    ) -> Result<()> {
+        ensure!(
+            elements.len() == self.leaves.len(),
+            "Number of elements ({}) does not match the number of leaves in proof ({}).",
+            elements.len(),
+            self.leaves.len(),
+        );
+        ensure_strictly_increasing(elements.iter().map(|(key, _)| key))?;
+
+        for ((element_key, element_hash), proof_leaf) in elements.iter().zip(self.leaves.iter()) {
+            ensure!(
+                proof_leaf.depth() <= HashValue::LENGTH_IN_BITS,
+                "Sparse Merkle Tree multi-proof leaf is deeper than {} ({}).",
+                HashValue::LENGTH_IN_BITS,
+                proof_leaf.depth(),
+            );
+            match (element_hash, proof_leaf.leaf) {
+                (Some(hash), Some(leaf)) => {
+                    ensure!(
+                        *element_key == leaf.key,
+                        "Keys do not match. Key in proof: {:x}. Expected key: {:x}.",
+                        leaf.key,
+                        element_key,
+                    );
+                    ensure!(
+                        *hash == leaf.value_hash,
+                        "Value hashes do not match for key {:x}. Value hash in proof: {:x}. \
+                         Expected value hash: {:x}. ",
+                        element_key,
+                        leaf.value_hash,
+                        hash
+                    );
+                },
+                (Some(hash), None) => {
+                    bail!(
+                        "Expected inclusion proof for key {:x}, value hash: {:x}. Found \
+                         non-inclusion proof.",
+                        element_key,
+                        hash
+                    )
+                },
+                (None, Some(leaf)) => {
+                    ensure!(
+                        *element_key != leaf.key,
+                        "Expected non-inclusion proof, but key exists in proof. Key: {:x}.",
+                        element_key,
+                    );
+                    ensure!(
+                        element_key.common_prefix_bits_len(leaf.key) >= proof_leaf.depth(),
+                        "Key would not have ended up in the subtree where the provided key in \
+                         proof is the only existing key, if it existed. So this is not a valid \
+                         non-inclusion proof. Key: {:x}. Key in proof: {:x}.",
+                        element_key,
+                        leaf.key
+                    );
+                },
+                (None, None) => {},
+            }
+        }
+
+        let actual_root_hash = if elements.is_empty() {
+            ensure!(
+                self.siblings.is_empty(),
+                "Multi-proof without elements should have no siblings."
+            );
+            expected_root_hash
+        } else {
+            let keys = elements.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+            let mut sibling_iter = self.siblings.iter();
+            let root_hash = Self::compute_subtree_hash(&keys, &self.leaves, 0, &mut sibling_iter)?;
+            ensure!(
+                sibling_iter.next().is_none(),
+                "Sparse Merkle Tree multi-proof has unused siblings."
+            );
+            root_hash
+        };
+        ensure!(
+            actual_root_hash == expected_root_hash,
+            "{}: Root hashes do not match. Actual root hash: {:x}. Expected root hash: {:x}.",
+            type_name::<Self>(),
+            actual_root_hash,
+            expected_root_hash,
+        );
+
+        Ok(())
+    }
+
+    /// Computes the hash of the subtree at `depth` that contains all of `keys` (which share the
+    /// same `depth`-bit prefix).
+    fn compute_subtree_hash<'a>(
+        keys: &[HashValue],
+        leaves: &[SparseMerkleMultiProofLeaf],
+        depth: usize,
+        sibling_iter: &mut impl Iterator<Item = &'a HashValue>,
+    ) -> Result<HashValue> {
+        if leaves.iter().any(|leaf| leaf.depth() == depth) {
+            // The searches for all keys in this subtree must terminate at the same position.
+            ensure!(
+                leaves.iter().all(|leaf| *leaf == leaves[0]),
+                "Keys sharing the same {}-bit prefix have different leaves in proof.",
+                depth,
+            );
+            return Ok(leaves[0]
+                .leaf
+                .map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |leaf| leaf.hash()));
+        }
+        ensure!(
+            depth < HashValue::LENGTH_IN_BITS,
+            "Sparse Merkle Tree multi-proof is deeper than {}.",
+            HashValue::LENGTH_IN_BITS,
+        );
+
+        let split = keys.partition_point(|key| !key.bit(depth));
+        let left_hash = if split == 0 {
+            next_sibling(sibling_iter, depth)?
+        } else {
+            Self::compute_subtree_hash(&keys[..split], &leaves[..split], depth + 1, sibling_iter)?
+        };
+        let right_hash = if split == keys.len() {
+            next_sibling(sibling_iter, depth)?
+        } else {
+            Self::compute_subtree_hash(&keys[split..], &leaves[split..], depth + 1, sibling_iter)?
+        };
+
+        Ok(SparseMerkleInternalNode::new(left_hash, right_hash).hash())
+    }
+}
+
+fn next_sibling<'a>(
+    sibling_iter: &mut impl Iterator<Item = &'a HashValue>,
+    depth: usize,
+) -> Result<HashValue> {
+    sibling_iter
+        .next()
+        .copied()
+        .ok_or_else(|| format_err!("Missing sibling at depth {}.", depth))
+}
+
+fn ensure_strictly_increasing<'a>(mut keys: impl Iterator<Item = &'a HashValue>) -> Result<()> {
+    if let Some(mut prev) = keys.next() {
+        for key in keys {
+            ensure!(
+                prev < key,
+                "Keys are not strictly increasing: {:x} is followed by {:x}.",
+                prev,
+                key,
+            );
+            prev = key;
+        }
+    }
+    Ok(())
+}
+
 /// An in-memory accumulator for storing a summary of the core transaction info
 /// accumulator. It is a summary in the sense that it only stores maximally
 /// frozen subtree nodes rather than storing all leaves and internal nodes.
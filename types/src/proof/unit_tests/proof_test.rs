@@ -11,9 +11,9 @@ use crate::{
     ledger_info::LedgerInfo,
     proof::{
         definition::MAX_ACCUMULATOR_PROOF_DEPTH, AccumulatorExtensionProof, AccumulatorRangeProof,
-        SparseMerkleInternalNode, SparseMerkleLeafNode, TestAccumulatorInternalNode,
-        TestAccumulatorProof, TransactionAccumulatorInternalNode, TransactionAccumulatorProof,
-        TransactionInfoListWithProof, TransactionInfoWithProof,
+        SparseMerkleInternalNode, SparseMerkleLeafNode, SparseMerkleMultiProof,
+        TestAccumulatorInternalNode, TestAccumulatorProof, TransactionAccumulatorInternalNode,
+        TransactionAccumulatorProof, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_store::state_value::StateValue,
     transaction::{
@@ -266,6 +266,109 @@ fn test_verify_three_element_sparse_merkle() {
     }
 }
 
+#[test]
+fn test_verify_three_element_sparse_merkle_multi_proof() {
+    //            root
+    //           /    \
+    //          a      default
+    //         / \
+    //     key1   b
+    //           / \
+    //       key2   key3
+    let key1 = b"hello".test_only_hash();
+    let key2 = b"world".test_only_hash();
+    let key3 = b"!".test_only_hash();
+    let non_existing_key1 = b"abc".test_only_hash();
+    let non_existing_key2 = b"def".test_only_hash();
+
+    let blob1 = StateValue::from(b"1".to_vec());
+    let blob2 = StateValue::from(b"2".to_vec());
+    let blob3 = StateValue::from(b"3".to_vec());
+
+    let leaf1 = SparseMerkleLeafNode::new(key1, blob1.hash());
+    let leaf2 = SparseMerkleLeafNode::new(key2, blob2.hash());
+    let leaf3 = SparseMerkleLeafNode::new(key3, blob3.hash());
+    let internal_b_hash = SparseMerkleInternalNode::new(leaf2.hash(), leaf3.hash()).hash();
+    let internal_a_hash = SparseMerkleInternalNode::new(leaf1.hash(), internal_b_hash).hash();
+    let root_hash =
+        SparseMerkleInternalNode::new(internal_a_hash, *SPARSE_MERKLE_PLACEHOLDER_HASH).hash();
+
+    // Keys in ascending order: key1 (0011), non_existing_key1 (0011), key2 (0100),
+    // key3 (0110), non_existing_key2 (1000).
+    let mut keys = vec![key1, non_existing_key1, key2, key3, non_existing_key2];
+    keys.sort();
+    let proofs: Vec<_> = keys
+        .iter()
+        .map(|key| match *key {
+            k if k == key2 => SparseMerkleProof::new(Some(leaf2), vec![
+                leaf3.hash(),
+                leaf1.hash(),
+                *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            ]),
+            k if k == key3 => SparseMerkleProof::new(Some(leaf3), vec![
+                leaf2.hash(),
+                leaf1.hash(),
+                *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            ]),
+            k if k == non_existing_key2 => SparseMerkleProof::new(None, vec![internal_a_hash]),
+            _ => SparseMerkleProof::new(Some(leaf1), vec![
+                internal_b_hash,
+                *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            ]),
+        })
+        .collect();
+    let proof = SparseMerkleMultiProof::from_proofs(&keys, &proofs).unwrap();
+    // Every sibling can be computed from the proven elements.
+    assert!(proof.siblings().is_empty());
+
+    let value_of = |key: &HashValue| match *key {
+        k if k == key1 => Some(&blob1),
+        k if k == key2 => Some(&blob2),
+        k if k == key3 => Some(&blob3),
+        _ => None,
+    };
+    let elements: Vec<_> = keys.iter().map(|key| (*key, value_of(key))).collect();
+    assert!(proof.verify(root_hash, &elements).is_ok());
+
+    // Wrong value for an existing key.
+    let mut wrong_value = elements.clone();
+    let index = keys.iter().position(|key| *key == key2).unwrap();
+    wrong_value[index].1 = Some(&blob3);
+    assert!(proof.verify(root_hash, &wrong_value).is_err());
+
+    // Claiming a non-existing key exists.
+    let mut wrong_existence = elements.clone();
+    let index = keys
+        .iter()
+        .position(|key| *key == non_existing_key1)
+        .unwrap();
+    wrong_existence[index].1 = Some(&blob1);
+    assert!(proof.verify(root_hash, &wrong_existence).is_err());
+
+    // Keys not in ascending order.
+    let mut reversed = elements.clone();
+    reversed.reverse();
+    assert!(proof.verify(root_hash, &reversed).is_err());
+
+    // A subset of the keys yields a proof with siblings, which still verifies.
+    let subset_keys = vec![key1, non_existing_key2];
+    let subset_proofs = vec![
+        SparseMerkleProof::new(Some(leaf1), vec![
+            internal_b_hash,
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
+        ]),
+        SparseMerkleProof::new(None, vec![internal_a_hash]),
+    ];
+    let subset_proof = SparseMerkleMultiProof::from_proofs(&subset_keys, &subset_proofs).unwrap();
+    assert_eq!(subset_proof.siblings(), &[internal_b_hash]);
+    assert!(subset_proof
+        .verify(root_hash, &[
+            (key1, Some(&blob1)),
+            (non_existing_key2, None)
+        ])
+        .is_ok());
+}
+
 #[test]
 fn test_verify_transaction() {
     //            root
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    on_chain_config::CurrentTimeMicroseconds, proof::SparseMerkleRangeProof,
-    state_store::state_key::StateKey, transaction::Version,
+    on_chain_config::CurrentTimeMicroseconds,
+    proof::{SparseMerkleMultiProof, SparseMerkleRangeProof},
+    state_store::state_key::StateKey,
+    transaction::Version,
 };
 use aptos_crypto::{
     hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
@@ -200,6 +202,37 @@ impl StateValueChunkWithProof {
     }
 }
 
+/// The state values of a list of state keys at a specific version, along with
+/// a single proof for all of them.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValuesWithMultiProof {
+    pub state_values: Vec<(StateKey, Option<StateValue>)>, // The state keys and values, sorted by hashed state key
+    pub proof: SparseMerkleMultiProof, // The proof of the values (or their absence) in the hashed states
+}
+
+impl StateValuesWithMultiProof {
+    pub fn new(
+        state_values: Vec<(StateKey, Option<StateValue>)>,
+        proof: SparseMerkleMultiProof,
+    ) -> Self {
+        Self {
+            state_values,
+            proof,
+        }
+    }
+
+    /// Verifies the state values, or their absence, against the root hash of
+    /// the sparse merkle tree at the requested version.
+    pub fn verify(&self, expected_root_hash: HashValue) -> anyhow::Result<()> {
+        let elements: Vec<_> = self
+            .state_values
+            .iter()
+            .map(|(state_key, state_value)| (state_key.hash(), state_value.as_ref()))
+            .collect();
+        self.proof.verify(expected_root_hash, &elements)
+    }
+}
+
 /// Indicates a state value becomes stale since `stale_since_version`.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
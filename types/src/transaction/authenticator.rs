@@ -4,14 +4,18 @@
 
 use crate::{
     account_address::AccountAddress,
-    transaction::{RawTransaction, RawTransactionWithData},
+    transaction::{
+        webauthn::PartialAuthenticatorAssertionResponse, RawTransaction, RawTransactionWithData,
+    },
 };
 use anyhow::{ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
-    traits::Signature,
+    secp256k1_ecdsa::{Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature},
+    secp256r1_ecdsa::Secp256r1EcdsaPublicKey,
+    traits::{signing_message, Signature},
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
 use aptos_crypto_derive::{CryptoHasher, DeserializeKey, SerializeKey};
@@ -65,6 +69,21 @@ pub enum TransactionAuthenticator {
         fee_payer_address: AccountAddress,
         fee_payer_signer: AccountAuthenticator,
     },
+    /// Single secp256r1 ECDSA signature, wrapped in a WebAuthn assertion
+    Secp256r1Ecdsa {
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    },
+    /// Single Ethereum-style secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    },
+    /// Opaque signature checked by the authentication function the sender registered in Move
+    Abstraction {
+        #[serde(with = "serde_bytes")]
+        signature: Vec<u8>,
+    },
 }
 
 impl TransactionAuthenticator {
@@ -104,6 +123,33 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256r1 ECDSA (WebAuthn) authenticator
+    pub fn secp256r1_ecdsa(
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    ) -> Self {
+        Self::Secp256r1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    ) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create an authenticator for a sender using account abstraction
+    pub fn abstraction(signature: Vec<u8>) -> Self {
+        Self::Abstraction { signature }
+    }
+
     /// Create a multi-agent authenticator
     pub fn multi_agent(
         sender: AccountAuthenticator,
@@ -158,6 +204,16 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(raw_txn, public_key),
+            Self::Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(raw_txn, public_key),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(raw_txn, public_key),
+            // Checked by the authentication function of the sender during the prologue.
+            Self::Abstraction { .. } => Ok(()),
             Self::MultiAgent {
                 sender,
                 secondary_signer_addresses,
@@ -191,6 +247,15 @@ impl TransactionAuthenticator {
                 signature,
             } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
             Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => AccountAuthenticator::secp256r1_ecdsa(public_key.clone(), signature.clone()),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => AccountAuthenticator::secp256k1_ecdsa(public_key.clone(), signature.clone()),
+            Self::Abstraction { signature } => AccountAuthenticator::abstraction(signature.clone()),
         }
     }
 
@@ -200,7 +265,10 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::Secp256r1Ecdsa { .. }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::Abstraction { .. } => vec![],
             Self::FeePayer {
                 sender: _,
                 secondary_signer_addresses,
@@ -220,7 +288,10 @@ impl TransactionAuthenticator {
             | Self::MultiEd25519 {
                 public_key: _,
                 signature: _,
-            } => vec![],
+            }
+            | Self::Secp256r1Ecdsa { .. }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::Abstraction { .. } => vec![],
             Self::FeePayer {
                 sender: _,
                 secondary_signer_addresses: _,
@@ -237,7 +308,12 @@ impl TransactionAuthenticator {
 
     pub fn fee_payer_address(&self) -> Option<AccountAddress> {
         match self {
-            Self::Ed25519 { .. } | Self::MultiEd25519 { .. } | Self::MultiAgent { .. } => None,
+            Self::Ed25519 { .. }
+            | Self::MultiEd25519 { .. }
+            | Self::MultiAgent { .. }
+            | Self::Secp256r1Ecdsa { .. }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::Abstraction { .. } => None,
             Self::FeePayer {
                 sender: _,
                 secondary_signer_addresses: _,
@@ -250,7 +326,12 @@ impl TransactionAuthenticator {
 
     pub fn fee_payer_signer(&self) -> Option<AccountAuthenticator> {
         match self {
-            Self::Ed25519 { .. } | Self::MultiEd25519 { .. } | Self::MultiAgent { .. } => None,
+            Self::Ed25519 { .. }
+            | Self::MultiEd25519 { .. }
+            | Self::MultiAgent { .. }
+            | Self::Secp256r1Ecdsa { .. }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::Abstraction { .. } => None,
             Self::FeePayer {
                 sender: _,
                 secondary_signer_addresses: _,
@@ -260,6 +341,40 @@ impl TransactionAuthenticator {
             } => Some(fee_payer_signer.clone()),
         }
     }
+
+    /// Return the authenticators of all the signers of the transaction: the sender, the secondary
+    /// signers and the fee payer, if any.
+    pub fn all_signers(&self) -> Vec<AccountAuthenticator> {
+        let mut signers = vec![self.sender()];
+        signers.extend(self.secondary_signers());
+        signers.extend(self.fee_payer_signer());
+        signers
+    }
+
+    /// Return the sha3-256 digest of the message signed by the signers of the transaction, which
+    /// is passed to the authentication function of the signers using account abstraction.
+    pub fn signing_message_digest(&self, raw_txn: &RawTransaction) -> Result<HashValue> {
+        let message = match self {
+            Self::MultiAgent {
+                secondary_signer_addresses,
+                ..
+            } => signing_message(&RawTransactionWithData::new_multi_agent(
+                raw_txn.clone(),
+                secondary_signer_addresses.clone(),
+            ))?,
+            Self::FeePayer {
+                secondary_signer_addresses,
+                fee_payer_address,
+                ..
+            } => signing_message(&RawTransactionWithData::new_fee_payer(
+                raw_txn.clone(),
+                secondary_signer_addresses.clone(),
+                *fee_payer_address,
+            ))?,
+            _ => signing_message(raw_txn)?,
+        };
+        Ok(HashValue::sha3_256_of(&message))
+    }
 }
 
 impl fmt::Display for TransactionAuthenticator {
@@ -312,6 +427,33 @@ impl fmt::Display for TransactionAuthenticator {
                     self.sender()
                 )
             },
+            Self::Secp256r1Ecdsa {
+                public_key: _,
+                signature: _,
+            } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: Secp256r1Ecdsa, sender: {}]",
+                    self.sender()
+                )
+            },
+            Self::Secp256k1Ecdsa {
+                public_key: _,
+                signature: _,
+            } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: Secp256k1Ecdsa, sender: {}]",
+                    self.sender()
+                )
+            },
+            Self::Abstraction { .. } => {
+                write!(
+                    f,
+                    "TransactionAuthenticator[scheme: Abstraction, sender: {}]",
+                    self.sender()
+                )
+            },
             Self::MultiAgent {
                 sender,
                 secondary_signer_addresses,
@@ -352,6 +494,11 @@ impl fmt::Display for TransactionAuthenticator {
 pub enum Scheme {
     Ed25519 = 0,
     MultiEd25519 = 1,
+    Secp256r1Ecdsa = 2,
+    Secp256k1Ecdsa = 3,
+    /// Not used to derive authentication keys, as abstracted accounts are authenticated by a Move
+    /// function instead.
+    Abstraction = 4,
     // ... add more schemes here
     /// Scheme identifier used to derive addresses (not the authentication key) of objects and
     /// resources accounts. This application serves to domain separate hashes. Without such
@@ -369,6 +516,9 @@ impl fmt::Display for Scheme {
         let display = match self {
             Scheme::Ed25519 => "Ed25519",
             Scheme::MultiEd25519 => "MultiEd25519",
+            Scheme::Secp256r1Ecdsa => "Secp256r1Ecdsa",
+            Scheme::Secp256k1Ecdsa => "Secp256k1Ecdsa",
+            Scheme::Abstraction => "Abstraction",
             Scheme::DeriveAuid => "DeriveAuid",
             Scheme::DeriveObjectAddressFromObject => "DeriveObjectAddressFromObject",
             Scheme::DeriveObjectAddressFromGuid => "DeriveObjectAddressFromGuid",
@@ -391,6 +541,21 @@ pub enum AccountAuthenticator {
         public_key: MultiEd25519PublicKey,
         signature: MultiEd25519Signature,
     },
+    /// Single secp256r1 ECDSA signature, wrapped in a WebAuthn assertion
+    Secp256r1Ecdsa {
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    },
+    /// Single Ethereum-style secp256k1 ECDSA signature
+    Secp256k1Ecdsa {
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    },
+    /// Opaque signature checked by the authentication function the account registered in Move
+    Abstraction {
+        #[serde(with = "serde_bytes")]
+        signature: Vec<u8>,
+    },
     // ... add more schemes here
 }
 
@@ -400,6 +565,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { .. } => Scheme::Ed25519,
             Self::MultiEd25519 { .. } => Scheme::MultiEd25519,
+            Self::Secp256r1Ecdsa { .. } => Scheme::Secp256r1Ecdsa,
+            Self::Secp256k1Ecdsa { .. } => Scheme::Secp256k1Ecdsa,
+            Self::Abstraction { .. } => Scheme::Abstraction,
         }
     }
 
@@ -422,6 +590,33 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Create a single-signature secp256r1 ECDSA (WebAuthn) authenticator
+    pub fn secp256r1_ecdsa(
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    ) -> Self {
+        Self::Secp256r1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create a single-signature secp256k1 ECDSA authenticator
+    pub fn secp256k1_ecdsa(
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    ) -> Self {
+        Self::Secp256k1Ecdsa {
+            public_key,
+            signature,
+        }
+    }
+
+    /// Create an authenticator for an account using account abstraction
+    pub fn abstraction(signature: Vec<u8>) -> Self {
+        Self::Abstraction { signature }
+    }
+
     /// Return Ok if the authenticator's public key matches its signature, Err otherwise
     pub fn verify<T: Serialize + CryptoHash>(&self, message: &T) -> Result<()> {
         match self {
@@ -433,6 +628,16 @@ impl AccountAuthenticator {
                 public_key,
                 signature,
             } => signature.verify(message, public_key),
+            Self::Secp256r1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            Self::Secp256k1Ecdsa {
+                public_key,
+                signature,
+            } => signature.verify(message, public_key),
+            // Checked by the authentication function of the account during the prologue.
+            Self::Abstraction { .. } => Ok(()),
         }
     }
 
@@ -441,6 +646,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { public_key, .. } => public_key.to_bytes().to_vec(),
             Self::MultiEd25519 { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256r1Ecdsa { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Secp256k1Ecdsa { public_key, .. } => public_key.to_bytes().to_vec(),
+            Self::Abstraction { .. } => vec![],
         }
     }
 
@@ -449,6 +657,9 @@ impl AccountAuthenticator {
         match self {
             Self::Ed25519 { signature, .. } => signature.to_bytes().to_vec(),
             Self::MultiEd25519 { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Secp256r1Ecdsa { signature, .. } => signature.to_bytes(),
+            Self::Secp256k1Ecdsa { signature, .. } => signature.to_bytes().to_vec(),
+            Self::Abstraction { signature } => signature.clone(),
         }
     }
 
@@ -465,7 +676,10 @@ impl AccountAuthenticator {
     /// Return the number of signatures included in this account authenticator.
     pub fn number_of_signatures(&self) -> usize {
         match self {
-            Self::Ed25519 { .. } => 1,
+            Self::Ed25519 { .. }
+            | Self::Secp256r1Ecdsa { .. }
+            | Self::Secp256k1Ecdsa { .. }
+            | Self::Abstraction { .. } => 1,
             Self::MultiEd25519 { signature, .. } => signature.signatures().len(),
         }
     }
@@ -519,6 +733,16 @@ impl AuthenticationKey {
         Self::from_preimage(&AuthenticationKeyPreimage::multi_ed25519(public_key))
     }
 
+    /// Create an authentication key from a secp256r1 ECDSA public key
+    pub fn secp256r1_ecdsa(public_key: &Secp256r1EcdsaPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256r1_ecdsa(public_key))
+    }
+
+    /// Create an authentication key from a secp256k1 ECDSA public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1EcdsaPublicKey) -> Self {
+        Self::from_preimage(&AuthenticationKeyPreimage::secp256k1_ecdsa(public_key))
+    }
+
     /// Return an address derived from the last `AccountAddress::LENGTH` bytes of this
     /// authentication key.
     pub fn derived_address(&self) -> AccountAddress {
@@ -574,6 +798,16 @@ impl AuthenticationKeyPreimage {
         Self::new(public_key.to_bytes(), Scheme::MultiEd25519)
     }
 
+    /// Construct a preimage from a secp256r1 ECDSA public key
+    pub fn secp256r1_ecdsa(public_key: &Secp256r1EcdsaPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::Secp256r1Ecdsa)
+    }
+
+    /// Construct a preimage from a secp256k1 ECDSA public key
+    pub fn secp256k1_ecdsa(public_key: &Secp256k1EcdsaPublicKey) -> AuthenticationKeyPreimage {
+        Self::new(public_key.to_bytes().to_vec(), Scheme::Secp256k1Ecdsa)
+    }
+
     /// Construct a preimage from a transaction-derived AUID as (txn_hash || auid_scheme_id)
     pub fn auid(txn_hash: Vec<u8>, auid_counter: u64) -> AuthenticationKeyPreimage {
         let mut hash_arg = Vec::new();
@@ -12,7 +12,10 @@ use crate::{
         accumulator::InMemoryAccumulator, TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_store::ShardedStateUpdates,
-    transaction::authenticator::{AccountAuthenticator, TransactionAuthenticator},
+    transaction::{
+        authenticator::{AccountAuthenticator, TransactionAuthenticator},
+        webauthn::PartialAuthenticatorAssertionResponse,
+    },
     vm_status::{DiscardedVMStatus, KeptVMStatus, StatusCode, StatusType, VMStatus},
     write_set::WriteSet,
 };
@@ -21,6 +24,8 @@ use aptos_crypto::{
     ed25519::*,
     hash::{CryptoHash, EventAccumulatorHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    secp256k1_ecdsa::{Secp256k1EcdsaPublicKey, Secp256k1EcdsaSignature},
+    secp256r1_ecdsa::Secp256r1EcdsaPublicKey,
     traits::{signing_message, SigningKey},
     CryptoMaterialError, HashValue,
 };
@@ -42,6 +47,7 @@ mod module;
 mod multisig;
 mod script;
 mod transaction_argument;
+pub mod webauthn;
 
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
@@ -617,6 +623,41 @@ impl SignedTransaction {
         }
     }
 
+    pub fn new_secp256r1_ecdsa(
+        raw_txn: RawTransaction,
+        public_key: Secp256r1EcdsaPublicKey,
+        signature: PartialAuthenticatorAssertionResponse,
+    ) -> SignedTransaction {
+        let authenticator = TransactionAuthenticator::secp256r1_ecdsa(public_key, signature);
+        SignedTransaction {
+            raw_txn,
+            authenticator,
+            size: OnceCell::new(),
+        }
+    }
+
+    pub fn new_secp256k1_ecdsa(
+        raw_txn: RawTransaction,
+        public_key: Secp256k1EcdsaPublicKey,
+        signature: Secp256k1EcdsaSignature,
+    ) -> SignedTransaction {
+        let authenticator = TransactionAuthenticator::secp256k1_ecdsa(public_key, signature);
+        SignedTransaction {
+            raw_txn,
+            authenticator,
+            size: OnceCell::new(),
+        }
+    }
+
+    pub fn new_abstraction(raw_txn: RawTransaction, signature: Vec<u8>) -> SignedTransaction {
+        let authenticator = TransactionAuthenticator::abstraction(signature);
+        SignedTransaction {
+            raw_txn,
+            authenticator,
+            size: OnceCell::new(),
+        }
+    }
+
     pub fn new_multi_agent(
         raw_txn: RawTransaction,
         sender: AccountAuthenticator,
@@ -0,0 +1,262 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebAuthn assertions for secp256r1 ECDSA (i.e., passkey) signatures.
+//!
+//! A WebAuthn authenticator does not sign the transaction directly. Instead, the signing message
+//! is passed to it as the `challenge`, which ends up base64url-encoded in the `clientDataJSON`
+//! collected by the client, and the authenticator signs
+//! `authenticatorData || SHA2-256(clientDataJSON)`. See
+//! <https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion>.
+
+use anyhow::{ensure, Context, Result};
+use aptos_crypto::{
+    hash::CryptoHash,
+    secp256r1_ecdsa::{Secp256r1EcdsaPublicKey, Secp256r1EcdsaSignature},
+    traits::{signing_message, Signature},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The `type` of the client data collected for an assertion.
+pub const WEBAUTHN_GET_TYPE: &str = "webauthn.get";
+
+/// The minimum length of the authenticator data: the 32-byte RP ID hash, the flags byte and the
+/// 4-byte signature counter.
+pub const MIN_AUTHENTICATOR_DATA_LENGTH: usize = 37;
+
+/// Index of the flags byte in the authenticator data.
+const FLAGS_INDEX: usize = 32;
+
+/// The "user present" (UP) flag.
+const USER_PRESENT_FLAG: u8 = 0x01;
+
+/// The parts of a WebAuthn `AuthenticatorAssertionResponse` needed to verify it against a
+/// transaction: the user handle is not needed, since the public key is known from the
+/// authenticator.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PartialAuthenticatorAssertionResponse {
+    /// The signature over `authenticator_data || SHA2-256(client_data_json)`.
+    signature: Secp256r1EcdsaSignature,
+    /// The raw authenticator data, which the client cannot tamper with.
+    #[serde(with = "serde_bytes")]
+    authenticator_data: Vec<u8>,
+    /// The raw UTF-8 JSON serialization of the `CollectedClientData`.
+    #[serde(with = "serde_bytes")]
+    client_data_json: Vec<u8>,
+}
+
+impl PartialAuthenticatorAssertionResponse {
+    pub fn new(
+        signature: Secp256r1EcdsaSignature,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            signature,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    pub fn signature(&self) -> &Secp256r1EcdsaSignature {
+        &self.signature
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &[u8] {
+        &self.client_data_json
+    }
+
+    /// Return the bytes that are signed by the authenticator, given the authenticator data and
+    /// the client data JSON.
+    pub fn verification_data(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+        let mut data = authenticator_data.to_vec();
+        data.extend_from_slice(&Sha256::digest(client_data_json));
+        data
+    }
+
+    /// Return the BCS serialization of this assertion
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Only unhandleable errors happen here.")
+    }
+
+    /// Return Ok if this is a valid assertion by `public_key` whose challenge is the signing
+    /// message of `message`, Err otherwise
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &Secp256r1EcdsaPublicKey,
+    ) -> Result<()> {
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)
+            .context("Malformed WebAuthn client data JSON")?;
+        ensure!(
+            client_data.ty == WEBAUTHN_GET_TYPE,
+            "Unexpected WebAuthn client data type: {}",
+            client_data.ty
+        );
+        let challenge = base64::decode_config(&client_data.challenge, base64::URL_SAFE_NO_PAD)
+            .context("Malformed WebAuthn challenge")?;
+        ensure!(
+            challenge == signing_message(message)?,
+            "WebAuthn challenge does not match the signing message"
+        );
+
+        ensure!(
+            self.authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LENGTH,
+            "WebAuthn authenticator data is too short"
+        );
+        ensure!(
+            self.authenticator_data[FLAGS_INDEX] & USER_PRESENT_FLAG != 0,
+            "WebAuthn authenticator data does not have the user present flag set"
+        );
+
+        self.signature.verify_arbitrary_msg(
+            &Self::verification_data(&self.authenticator_data, &self.client_data_json),
+            public_key,
+        )
+    }
+}
+
+/// The client data collected by the WebAuthn client, as defined in
+/// <https://www.w3.org/TR/webauthn-2/#dictionary-client-data>. Unknown fields are ignored.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectedClientData {
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The base64url-encoded challenge, without padding.
+    pub challenge: String,
+    pub origin: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cross_origin: Option<bool>,
+}
+
+impl CollectedClientData {
+    /// Create the client data of an assertion for the given challenge, i.e., signing message
+    pub fn new_get(challenge: &[u8], origin: String) -> Self {
+        Self {
+            ty: WEBAUTHN_GET_TYPE.to_string(),
+            challenge: base64::encode_config(challenge, base64::URL_SAFE_NO_PAD),
+            origin,
+            cross_origin: None,
+        }
+    }
+
+    /// Return the JSON serialization of the client data, as it would be collected by a client
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("Only unhandleable errors happen here.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script},
+    };
+    use aptos_crypto::{
+        secp256r1_ecdsa::Secp256r1EcdsaPrivateKey,
+        traits::{SigningKey, Uniform},
+    };
+
+    const ORIGIN: &str = "https://wallet.example";
+
+    fn raw_txn(sequence_number: u64) -> RawTransaction {
+        RawTransaction::new_script(
+            AccountAddress::random(),
+            sequence_number,
+            Script::new(vec![], vec![], vec![]),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        )
+    }
+
+    fn authenticator_data(flags: u8) -> Vec<u8> {
+        let mut data = vec![0xAA; MIN_AUTHENTICATOR_DATA_LENGTH];
+        data[FLAGS_INDEX] = flags;
+        data
+    }
+
+    fn assert_for(
+        private_key: &Secp256r1EcdsaPrivateKey,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> PartialAuthenticatorAssertionResponse {
+        let signature = private_key.sign_arbitrary_message(
+            &PartialAuthenticatorAssertionResponse::verification_data(
+                &authenticator_data,
+                &client_data_json,
+            ),
+        );
+        PartialAuthenticatorAssertionResponse::new(signature, authenticator_data, client_data_json)
+    }
+
+    #[test]
+    fn test_verify_assertion() {
+        let private_key = Secp256r1EcdsaPrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let txn = raw_txn(0);
+        let client_data =
+            CollectedClientData::new_get(&txn.signing_message().unwrap(), ORIGIN.to_string());
+
+        let assertion = assert_for(
+            &private_key,
+            authenticator_data(USER_PRESENT_FLAG),
+            client_data.to_json_bytes(),
+        );
+        assertion.verify(&txn, &public_key).unwrap();
+
+        // The challenge binds the assertion to the transaction.
+        assert!(assertion.verify(&raw_txn(1), &public_key).is_err());
+        let other_key = Secp256r1EcdsaPrivateKey::generate(&mut rand::thread_rng());
+        assert!(assertion.verify(&txn, &other_key.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_reject_malformed_assertions() {
+        let private_key = Secp256r1EcdsaPrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let txn = raw_txn(0);
+        let client_data =
+            CollectedClientData::new_get(&txn.signing_message().unwrap(), ORIGIN.to_string());
+
+        // The user must be present.
+        let assertion = assert_for(
+            &private_key,
+            authenticator_data(0),
+            client_data.to_json_bytes(),
+        );
+        assert!(assertion.verify(&txn, &public_key).is_err());
+
+        // The authenticator data must at least hold the RP ID hash, flags and counter.
+        let mut short_data = authenticator_data(USER_PRESENT_FLAG);
+        short_data.pop();
+        let assertion = assert_for(&private_key, short_data, client_data.to_json_bytes());
+        assert!(assertion.verify(&txn, &public_key).is_err());
+
+        // Only assertions are accepted, not registrations.
+        let mut create = client_data.clone();
+        create.ty = "webauthn.create".to_string();
+        let assertion = assert_for(
+            &private_key,
+            authenticator_data(USER_PRESENT_FLAG),
+            create.to_json_bytes(),
+        );
+        assert!(assertion.verify(&txn, &public_key).is_err());
+
+        let assertion = assert_for(
+            &private_key,
+            authenticator_data(USER_PRESENT_FLAG),
+            b"not json".to_vec(),
+        );
+        assert!(assertion.verify(&txn, &public_key).is_err());
+    }
+}
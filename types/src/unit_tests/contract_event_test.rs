@@ -12,6 +12,11 @@ proptest! {
     fn event_bcs_roundtrip(event in any::<ContractEvent>()) {
         assert_canonical_encode_decode(event);
     }
+
+    #[test]
+    fn module_event_bcs_roundtrip(type_tag in any::<TypeTag>(), event_data in any::<Vec<u8>>()) {
+        assert_canonical_encode_decode(ContractEvent::new_module_event(type_tag, event_data));
+    }
 }
 
 #[test]
@@ -23,3 +28,18 @@ fn test_event_json_serialize() {
     let contract_event2: ContractEvent = serde_json::from_str(contract_json.as_str()).unwrap();
     assert_eq!(contract_event, contract_event2)
 }
+
+#[test]
+fn test_module_event() {
+    let event = ContractEvent::new_module_event(TypeTag::Address, vec![0u8]);
+    assert!(!event.is_v0());
+    assert!(event.v0().is_err());
+    assert_eq!(event.event_key(), None);
+    assert_eq!(event.type_tag(), &TypeTag::Address);
+    assert_eq!(event.event_data(), &[0u8]);
+
+    let event_key = EventKey::random();
+    let event = ContractEvent::new(event_key, 0, TypeTag::Address, vec![0u8]);
+    assert!(event.is_v0());
+    assert_eq!(event.event_key(), Some(&event_key));
+}